@@ -0,0 +1,115 @@
+//! Allocation site tracking.
+//!
+//! When the `allocation-sites` feature is enabled, the interpreter counts the objects created by
+//! each object literal, array literal, `new` expression and function definition of the scripts
+//! it runs. The sites that allocate the most show which parts of a script create the most
+//! garbage pressure.
+//!
+//! The AST does not store source positions, so sites are identified by their kind and a short
+//! excerpt of their code. Objects created internally by builtins are not counted.
+//!
+//! Without the feature, nothing is recorded and `AllocationSites::top` is always empty.
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "allocation-sites")]
+use rustc_hash::FxHashMap;
+use std::fmt::Display;
+
+/// The maximum length of the code excerpt in the description of an allocation site.
+#[cfg_attr(not(feature = "allocation-sites"), allow(dead_code))]
+const EXCERPT_LENGTH: usize = 40;
+
+/// A place in a script that allocates objects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllocationSite {
+    /// The kind of the site and an excerpt of its code, e.g. ``object literal `{ a: 1 }` ``.
+    pub description: String,
+    /// How many objects were allocated at this site.
+    pub count: usize,
+}
+
+/// The allocation counts of every site, see the module documentation.
+#[derive(Debug, Default)]
+#[cfg_attr(not(feature = "allocation-sites"), allow(missing_copy_implementations))]
+pub struct AllocationSites {
+    #[cfg(feature = "allocation-sites")]
+    counts: FxHashMap<String, usize>,
+}
+
+impl AllocationSites {
+    /// Checks if allocation sites are recorded, that is if the `allocation-sites` feature is
+    /// enabled.
+    #[inline]
+    pub const fn is_enabled() -> bool {
+        cfg!(feature = "allocation-sites")
+    }
+
+    /// Records an allocation by the site of the given kind and code.
+    ///
+    /// The description of the site is only built when tracking is enabled.
+    #[inline]
+    pub(crate) fn record<C>(&mut self, kind: &str, code: &C)
+    where
+        C: Display + ?Sized,
+    {
+        #[cfg(feature = "allocation-sites")]
+        {
+            *self.counts.entry(describe(kind, code)).or_insert(0) += 1;
+        }
+        #[cfg(not(feature = "allocation-sites"))]
+        {
+            let _ = (kind, code);
+        }
+    }
+
+    /// Returns the `n` sites that allocated the most objects, the largest first.
+    pub fn top(&self, n: usize) -> Vec<AllocationSite> {
+        #[cfg(feature = "allocation-sites")]
+        {
+            let mut sites: Vec<_> = self
+                .counts
+                .iter()
+                .map(|(description, &count)| AllocationSite {
+                    description: description.clone(),
+                    count,
+                })
+                .collect();
+            sites.sort_by(|a, b| {
+                b.count
+                    .cmp(&a.count)
+                    .then_with(|| a.description.cmp(&b.description))
+            });
+            sites.truncate(n);
+            sites
+        }
+        #[cfg(not(feature = "allocation-sites"))]
+        {
+            let _ = n;
+            Vec::new()
+        }
+    }
+
+    /// Forgets every recorded allocation.
+    #[inline]
+    pub fn clear(&mut self) {
+        #[cfg(feature = "allocation-sites")]
+        self.counts.clear();
+    }
+}
+
+/// Describes a site by its kind and the start of its code, on a single line.
+#[cfg_attr(not(feature = "allocation-sites"), allow(dead_code))]
+fn describe<C>(kind: &str, code: &C) -> String
+where
+    C: Display + ?Sized,
+{
+    let code = code.to_string();
+    let mut excerpt = code.split_whitespace().collect::<Vec<_>>().join(" ");
+    if let Some((index, _)) = excerpt.char_indices().nth(EXCERPT_LENGTH) {
+        excerpt.truncate(index);
+        excerpt.push_str("...");
+    }
+    format!("{} `{}`", kind, excerpt)
+}
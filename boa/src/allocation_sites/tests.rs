@@ -0,0 +1,60 @@
+use super::*;
+use crate::{exec::Interpreter, forward, realm::Realm};
+
+#[test]
+fn describe_truncates_long_code() {
+    assert_eq!(
+        describe("array literal", "[1, 2]"),
+        "array literal `[1, 2]`"
+    );
+    assert_eq!(
+        describe("object literal", "{\n    a: 1\n}"),
+        "object literal `{ a: 1 }`"
+    );
+    assert_eq!(
+        describe("function", &"x".repeat(50)),
+        format!("function `{}...`", "x".repeat(EXCERPT_LENGTH))
+    );
+}
+
+#[cfg(not(feature = "allocation-sites"))]
+#[test]
+fn nothing_is_recorded_when_disabled() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var a = [{}, {}];");
+
+    assert!(!AllocationSites::is_enabled());
+    assert!(engine.allocation_sites().top(10).is_empty());
+}
+
+#[cfg(feature = "allocation-sites")]
+#[test]
+fn top_sites() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        function Point(x) { this.x = x; }
+        var points = [];
+        for (let i = 0; i < 3; i++) {
+            points.push(new Point(i));
+            points.push({ x: i });
+        }
+        for (let i = 0; i < 2; i++) {
+            points.push({ y: i });
+        }
+        "#;
+    forward(&mut engine, init);
+
+    let top = engine.allocation_sites().top(2);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top[0].count, 3);
+    assert_eq!(top[1].count, 3);
+    assert!(top.iter().any(|site| site.description.starts_with("new ")));
+    assert!(top
+        .iter()
+        .any(|site| site.description.starts_with("object literal")));
+
+    engine.allocation_sites_mut().clear();
+    assert!(engine.allocation_sites().top(10).is_empty());
+}
@@ -0,0 +1,80 @@
+//! This module implements the toggle of the web legacy compatibility features of Annex B.
+//!
+//! Annex B of the specification describes features that web browsers keep for the scripts
+//! written before they were standardized, and that a lot of code found in the wild still uses:
+//!
+//!  - `String.prototype.substr`;
+//!  - the global `escape` and `unescape` functions;
+//!  - `RegExp.prototype.compile`;
+//!  - the `Object.prototype.__proto__` accessor;
+//!  - HTML-like comments in scripts, `<!--` anywhere and `-->` at the start of a line, which
+//!    both comment out the rest of the line.
+//!
+//! They are enabled by default, like in every web browser, because the scripts that a host
+//! finds in the wild may rely on them and would otherwise fail to run. They are disabled with
+//! `Interpreter::set_annex_b` or `ContextBuilder::annex_b`, for hosts that want the engine to
+//! only offer the standard language:
+//!
+//! ```
+//! use boa::Context;
+//!
+//! let mut context = Context::builder().annex_b(false).build();
+//! let value = context.eval("typeof globalThis.escape").unwrap();
+//! assert_eq!(value.to_string(), "undefined");
+//! ```
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-additional-ecmascript-features-for-web-browsers
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builtins::{
+        function::{make_builtin_fn, Function},
+        object::{self, Object, PROTOTYPE},
+        property::Property,
+        RegExp, String, Uri, Value,
+    },
+    realm::Realm,
+};
+
+/// Installs the Annex B builtins on the global object of a realm, or removes them if
+/// `enabled` is not set.
+pub(crate) fn install_builtins(realm: &Realm, enabled: bool) {
+    let global = &realm.global_obj;
+    let string_prototype = global.get_field(String::NAME).get_field(PROTOTYPE);
+    let regexp_prototype = global.get_field(RegExp::NAME).get_field(PROTOTYPE);
+    let object_prototype = global.get_field("Object").get_field(PROTOTYPE);
+
+    if !enabled {
+        string_prototype.remove_property("substr");
+        global.remove_property("escape");
+        global.remove_property("unescape");
+        regexp_prototype.remove_property("compile");
+        object_prototype.remove_property("__proto__");
+        return;
+    }
+    if string_prototype.has_field("substr") {
+        return;
+    }
+
+    make_builtin_fn(String::substr, "substr", &string_prototype, 2);
+    make_builtin_fn(Uri::escape, "escape", global, 1);
+    make_builtin_fn(Uri::unescape, "unescape", global, 1);
+    make_builtin_fn(RegExp::compile, "compile", &regexp_prototype, 2);
+
+    let accessor = |function| {
+        Value::from(Object::function(
+            Function::builtin(Vec::new(), function),
+            Value::null(),
+        ))
+    };
+    let mut proto = Property::empty()
+        .get(accessor(object::get_proto))
+        .set(accessor(object::set_proto));
+    proto.set_configurable(true);
+    object_prototype.set_property("__proto__", proto);
+}
@@ -0,0 +1,132 @@
+use crate::{syntax::lexer::Lexer, Context};
+
+/// Evaluates a script that is expected to succeed, and formats its value.
+fn eval(context: &mut Context, src: &str) -> String {
+    context.eval(src).unwrap().to_string()
+}
+
+#[test]
+fn escape_and_unescape() {
+    let mut context = Context::new();
+    assert_eq!(eval(&mut context, "escape('abc123')"), "abc123");
+    assert_eq!(eval(&mut context, "escape('@*_+-./')"), "@*_+-./");
+    assert_eq!(eval(&mut context, "escape('ä ö')"), "%E4%20%F6");
+    assert_eq!(eval(&mut context, "escape('ć')"), "%u0107");
+    assert_eq!(eval(&mut context, "escape('😀')"), "%uD83D%uDE00");
+
+    assert_eq!(eval(&mut context, "unescape('%E4%20%F6')"), "ä ö");
+    assert_eq!(eval(&mut context, "unescape('%u0107')"), "ć");
+    assert_eq!(eval(&mut context, "unescape('%uD83D%uDE00')"), "😀");
+    // Malformed escape sequences are kept.
+    assert_eq!(eval(&mut context, "unescape('%zz%u12%4')"), "%zz%u12%4");
+    assert_eq!(eval(&mut context, "unescape(escape('a ć 😀'))"), "a ć 😀");
+}
+
+#[test]
+fn regexp_compile() {
+    let mut context = Context::new();
+    let init = r#"
+        var re = /a/g;
+        re.lastIndex = 3;
+        var same = re.compile("b+", "i") === re;
+        "#;
+    context.eval(init).unwrap();
+    assert_eq!(eval(&mut context, "same"), "true");
+    assert_eq!(eval(&mut context, "re.lastIndex"), "0");
+    assert_eq!(eval(&mut context, "re.toString()"), "/b+/i");
+    assert_eq!(eval(&mut context, "re.test('aBBa')"), "true");
+    assert_eq!(eval(&mut context, "re.compile(/c/m).toString()"), "/c/m");
+    assert!(context.eval("re.compile(/c/, 'g')").is_err());
+    assert!(context
+        .eval("RegExp.prototype.compile.call({}, 'a')")
+        .is_err());
+}
+
+#[test]
+fn proto_accessor() {
+    let mut context = Context::new();
+    let init = r#"
+        var proto = { greet: function () { return "hi"; } };
+        var object = {};
+        object.__proto__ = proto;
+        "#;
+    context.eval(init).unwrap();
+    assert_eq!(eval(&mut context, "object.greet()"), "hi");
+    assert_eq!(eval(&mut context, "object.__proto__ === proto"), "true");
+    assert_eq!(
+        eval(&mut context, "Object.getPrototypeOf(object) === proto"),
+        "true"
+    );
+    assert_eq!(
+        eval(&mut context, "object.hasOwnProperty('__proto__')"),
+        "false"
+    );
+    assert_eq!(
+        eval(&mut context, "'a'.__proto__ === String.prototype"),
+        "true"
+    );
+
+    // Values that are neither objects nor null are ignored.
+    context.eval("object.__proto__ = 1").unwrap();
+    assert_eq!(eval(&mut context, "object.greet()"), "hi");
+    assert!(context.eval("proto.__proto__ = object").is_err());
+}
+
+#[test]
+fn html_like_comments() {
+    let mut context = Context::new();
+    assert_eq!(eval(&mut context, "var a = 1; <!-- a = 2;\na"), "1");
+    assert_eq!(eval(&mut context, "var b = 1;\n--> b = 2;\nb"), "1");
+    assert_eq!(eval(&mut context, "var c = 1;\n/*\n*/ --> c = 2;\nc"), "1");
+    // `-->` is only a comment at the start of a line.
+    assert_eq!(eval(&mut context, "var d = 3; d-->0; d"), "2");
+
+    let mut lexer = Lexer::new("a <!-- b")
+        .html_comments(true)
+        .keep_comments(true);
+    lexer.lex().unwrap();
+    assert_eq!(lexer.tokens.len(), 1);
+    assert_eq!(lexer.comments[0].text(), "<!-- b");
+    assert!(lexer.comments[0].is_line_comment());
+
+    let mut lexer = Lexer::new("a <!-- b");
+    lexer.lex().unwrap();
+    // `a`, `<`, `!`, `--` and `b`.
+    assert_eq!(lexer.tokens.len(), 5);
+}
+
+#[test]
+fn disabled() {
+    let mut context = Context::builder().annex_b(false).build();
+    assert_eq!(eval(&mut context, "typeof ''.substr"), "undefined");
+    assert_eq!(eval(&mut context, "typeof globalThis.escape"), "undefined");
+    assert_eq!(
+        eval(&mut context, "typeof globalThis.unescape"),
+        "undefined"
+    );
+    assert_eq!(eval(&mut context, "typeof /a/.compile"), "undefined");
+    assert_eq!(eval(&mut context, "'__proto__' in {}"), "false");
+    assert!(context.eval("var a = 1; <!-- a = 2;").is_err());
+
+    // New realms follow the toggle of the interpreter.
+    let realm = context.create_realm();
+    assert_eq!(
+        context
+            .eval_in_realm(realm, "typeof globalThis.escape")
+            .unwrap()
+            .to_string(),
+        "undefined"
+    );
+
+    context.interpreter_mut().set_annex_b(true);
+    assert_eq!(eval(&mut context, "'abc'.substr(1, 1)"), "b");
+    assert_eq!(eval(&mut context, "typeof globalThis.escape"), "function");
+    assert_eq!(eval(&mut context, "var b = 1; <!-- b = 2;\nb"), "1");
+    assert_eq!(
+        context
+            .eval_in_realm(realm, "typeof ({}).__proto__")
+            .unwrap()
+            .to_string(),
+        "object"
+    );
+}
@@ -15,8 +15,10 @@ mod tests;
 use super::function::{make_builtin_fn, make_constructor_fn};
 use crate::{
     builtins::{
-        object::{ObjectData, PROTOTYPE},
+        function::Function,
+        object::{Object, ObjectData, PROTOTYPE},
         property::{Attribute, Property},
+        symbol::WellKnownSymbol,
         value::{same_value_zero, ResultValue, Value},
     },
     exec::Interpreter,
@@ -38,6 +40,15 @@ impl Array {
     /// The amount of arguments this function object takes.
     pub(crate) const LENGTH: usize = 1;
 
+    /// The maximum amount of elements an array can hold.
+    ///
+    /// This is `2^32 - 1` by the specification, and can be lowered with `Interpreter::set_max_array_length`.
+    pub(crate) const MAX_ARRAY_LENGTH: usize = u32::MAX as usize;
+
+    /// The bytes an element takes in the indexed properties of an array, not counting the
+    /// memory its value refers to, see `Interpreter::check_memory`.
+    const ELEMENT_SIZE: usize = size_of::<(u32, Property)>();
+
     /// Creates a new `Array` instance.
     pub(crate) fn new_array(interpreter: &Interpreter) -> ResultValue {
         let array = Value::new_object(Some(
@@ -61,6 +72,36 @@ impl Array {
         Ok(array)
     }
 
+    /// Creates a new array with the same constructor as `original`, consulting its
+    /// `Symbol.species`, so that methods like `map` return instances of subclasses.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-arrayspeciescreate
+    pub(crate) fn array_species_create(
+        original: &Value,
+        length: usize,
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        if !matches!(original.as_object(), Some(object) if object.is_array()) {
+            return Self::new_array(ctx);
+        }
+
+        let mut constructor = ctx.get_field(original, "constructor")?;
+        if constructor.is_object() {
+            constructor = ctx.get_field(&constructor, WellKnownSymbol::Species.key())?;
+        }
+        if constructor.is_null_or_undefined() {
+            return Self::new_array(ctx);
+        }
+
+        if !matches!(constructor.as_object(), Some(object) if object.is_constructable()) {
+            return ctx.throw_type_error("Symbol.species is not a constructor");
+        }
+        ctx.construct(&constructor, &[Value::from(length)])
+    }
+
     /// Utility function for creating array objects.
     ///
     /// `array_obj` can be any array with prototype already set (it will be wiped and
@@ -71,7 +112,7 @@ impl Array {
         // Wipe existing contents of the array object
         let orig_length = i32::from(&array_obj.get_field("length"));
         for n in 0..orig_length {
-            array_obj_ptr.remove_property(&n.to_string());
+            array_obj_ptr.remove_property(n);
         }
 
         // Create length
@@ -82,7 +123,7 @@ impl Array {
         array_obj_ptr.set_property("length".to_string(), length);
 
         for (n, value) in array_contents.iter().enumerate() {
-            array_obj_ptr.set_field(n.to_string(), value);
+            array_obj_ptr.set_field(n, value);
         }
         Ok(array_obj_ptr)
     }
@@ -94,7 +135,7 @@ impl Array {
 
         for (n, value) in add_values.iter().enumerate() {
             let new_index = orig_length.wrapping_add(n as i32);
-            array_ptr.set_field(new_index.to_string(), value);
+            array_ptr.set_field(new_index, value);
         }
 
         array_ptr.set_field(
@@ -125,17 +166,18 @@ impl Array {
         match args.len() {
             1 if args[0].is_integer() => {
                 length = i32::from(&args[0]);
-                // TODO: It should not create an array of undefineds, but an empty array ("holy" array in V8) with length `n`.
-                for n in 0..length {
-                    this.set_field(n.to_string(), Value::undefined());
+                if length < 0 {
+                    return ctx.throw_range_error("invalid array length");
                 }
+                ctx.check_array_length(f64::from(length))?;
             }
             1 if args[0].is_double() => {
                 return ctx.throw_range_error("invalid array length");
             }
             _ => {
+                ctx.check_memory(args.len() * Self::ELEMENT_SIZE)?;
                 for (n, value) in args.iter().enumerate() {
-                    this.set_field(n.to_string(), value.clone());
+                    this.set_field(n, value.clone());
                 }
             }
         }
@@ -173,6 +215,90 @@ impl Array {
         }
     }
 
+    /// `Array.from( items [, mapFn [, thisArg ] ] )`
+    ///
+    /// The `Array.from()` method creates a new, shallow-copied `Array` instance from an
+    /// iterable or array-like object.
+    ///
+    /// Strings are iterated by code point, arrays by index and `Map` objects by their
+    /// `[key, value]` entries. Any other object is treated as an array-like.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-array.from
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/from
+    pub(crate) fn from(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let items = args.get(0).cloned().unwrap_or_default();
+        let map_fn = args.get(1).cloned().unwrap_or_default();
+        let this_arg = args.get(2).cloned().unwrap_or_default();
+
+        if !map_fn.is_undefined() && !map_fn.is_function() {
+            return ctx.throw_type_error("Array.from: mapFn is not a function");
+        }
+        if items.is_null_or_undefined() {
+            return ctx.throw_type_error("Array.from: items is null or undefined");
+        }
+
+        let values = match items {
+            Value::String(ref string) => {
+                string.chars().map(|c| Value::from(c.to_string())).collect()
+            }
+            Value::Object(ref object) if object.borrow().is_map() => object
+                .borrow()
+                .as_map_ref()
+                .expect("checked that the object is a map")
+                .iter()
+                .map(|(key, value)| {
+                    let entry = Self::new_array(ctx)?;
+                    Self::construct_array(&entry, &[key.clone(), value.clone()])
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            _ => {
+                let array_like = ctx.to_object(&items)?;
+                let length_value = ctx.get_field(&array_like, "length")?;
+                let length = ctx.to_length(&length_value)?;
+                ctx.check_array_length(length as f64)?;
+                let mut values = Vec::with_capacity(length);
+                for k in 0..length {
+                    values.push(ctx.get_field(&array_like, k.to_string())?);
+                }
+                values
+            }
+        };
+
+        ctx.check_array_length(values.len() as f64)?;
+        let values = if map_fn.is_undefined() {
+            values
+        } else {
+            values
+                .into_iter()
+                .enumerate()
+                .map(|(k, value)| ctx.call(&map_fn, &this_arg, &[value, Value::from(k)]))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        let array = Self::new_array(ctx)?;
+        Self::construct_array(&array, &values)
+    }
+
+    /// `Array.of( ...items )`
+    ///
+    /// The `Array.of()` method creates a new `Array` instance from a variable number of
+    /// arguments, regardless of number or type of the arguments.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-array.of
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/of
+    pub(crate) fn of(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let array = Self::new_array(ctx)?;
+        Self::construct_array(&array, args)
+    }
+
     /// `Array.prototype.concat(...arguments)`
     ///
     /// When the concat method is called with zero or more arguments, it returns an
@@ -185,7 +311,7 @@ impl Array {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.concat
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/concat
-    pub(crate) fn concat(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    pub(crate) fn concat(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
         if args.is_empty() {
             // If concat is called with no arguments, it returns the original array
             return Ok(this.clone());
@@ -197,17 +323,20 @@ impl Array {
 
         let this_length = i32::from(&this.get_field("length"));
         for n in 0..this_length {
-            new_values.push(this.get_field(n.to_string()));
+            new_values.push(this.get_field(n));
         }
 
         for concat_array in args {
             let concat_length = i32::from(&concat_array.get_field("length"));
+            ctx.check_array_length((new_values.len() as f64) + f64::from(concat_length))?;
+            ctx.check_memory(concat_length.max(0) as usize * Self::ELEMENT_SIZE)?;
             for n in 0..concat_length {
-                new_values.push(concat_array.get_field(n.to_string()));
+                new_values.push(concat_array.get_field(n));
             }
         }
 
-        Self::construct_array(this, &new_values)
+        let new_array = Self::array_species_create(this, 0, ctx)?;
+        Self::construct_array(&new_array, &new_values)
     }
 
     /// `Array.prototype.push( ...items )`
@@ -222,7 +351,9 @@ impl Array {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.push
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/push
-    pub(crate) fn push(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    pub(crate) fn push(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let length = f64::from(i32::from(&this.get_field("length")));
+        ctx.check_array_length(length + args.len() as f64)?;
         let new_array = Self::add_to_array_object(this, args)?;
         Ok(new_array.get_field("length"))
     }
@@ -243,8 +374,8 @@ impl Array {
             return Ok(Value::undefined());
         }
         let pop_index = curr_length.wrapping_sub(1);
-        let pop_value: Value = this.get_field(pop_index.to_string());
-        this.remove_property(&pop_index.to_string());
+        let pop_value: Value = this.get_field(pop_index);
+        this.remove_property(pop_index);
         this.set_field("length", Value::from(pop_index));
         Ok(pop_value)
     }
@@ -274,7 +405,11 @@ impl Array {
         let length = i32::from(&this.get_field("length"));
 
         for i in 0..length {
-            let element = this.get_field(i.to_string());
+            // Holes are skipped.
+            if !this.has_field(i) {
+                continue;
+            }
+            let element = this.get_field(i);
             let arguments = [element, Value::from(i), this.clone()];
 
             interpreter.call(callback_arg, &this_arg, &arguments)?;
@@ -303,14 +438,20 @@ impl Array {
                 .to_string()
         };
 
-        let mut elem_strs = Vec::new();
+        // The length is checked as the string grows, so that it is never built past the maximum.
+        let mut joined = String::new();
         let length = i32::from(&this.get_field("length"));
         for n in 0..length {
-            let elem_str = ctx.to_string(&this.get_field(n.to_string()))?.to_string();
-            elem_strs.push(elem_str);
+            let element = ctx.to_string(&this.get_field(n))?;
+            ctx.check_memory(separator.len() + element.len())?;
+            if n > 0 {
+                joined.push_str(&separator);
+            }
+            joined.push_str(&element);
+            ctx.check_str_length(&joined)?;
         }
 
-        Ok(Value::from(elem_strs.join(&separator)))
+        Ok(Value::from(joined))
     }
 
     /// `Array.prototype.toString( separator )`
@@ -373,21 +514,21 @@ impl Array {
         for lower in 0..middle {
             let upper = len.wrapping_sub(lower).wrapping_sub(1);
 
-            let upper_exists = this.has_field(&upper.to_string());
-            let lower_exists = this.has_field(&lower.to_string());
+            let upper_exists = this.has_field(upper);
+            let lower_exists = this.has_field(lower);
 
-            let upper_value = this.get_field(upper.to_string());
-            let lower_value = this.get_field(lower.to_string());
+            let upper_value = this.get_field(upper);
+            let lower_value = this.get_field(lower);
 
             if upper_exists && lower_exists {
-                this.set_field(upper.to_string(), lower_value);
-                this.set_field(lower.to_string(), upper_value);
+                this.set_field(upper, lower_value);
+                this.set_field(lower, upper_value);
             } else if upper_exists {
-                this.set_field(lower.to_string(), upper_value);
-                this.remove_property(&upper.to_string());
+                this.set_field(lower, upper_value);
+                this.remove_property(upper);
             } else if lower_exists {
-                this.set_field(upper.to_string(), lower_value);
-                this.remove_property(&lower.to_string());
+                this.set_field(upper, lower_value);
+                this.remove_property(lower);
             }
         }
 
@@ -428,7 +569,7 @@ impl Array {
         }
 
         let final_index = len.wrapping_sub(1);
-        this.remove_property(&(final_index).to_string());
+        this.remove_property(final_index);
         this.set_field("length", Value::from(final_index));
 
         Ok(first)
@@ -446,9 +587,10 @@ impl Array {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.unshift
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/unshift
-    pub(crate) fn unshift(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    pub(crate) fn unshift(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
         let len = i32::from(&this.get_field("length"));
         let arg_c: i32 = args.len() as i32;
+        ctx.check_array_length(f64::from(len) + f64::from(arg_c))?;
 
         if arg_c > 0 {
             for k in (1..=len).rev() {
@@ -510,7 +652,11 @@ impl Array {
         let max_len = i32::from(&this.get_field("length"));
         let mut len = max_len;
         while i < len {
-            let element = this.get_field(i.to_string());
+            if !this.has_field(i) {
+                i += 1;
+                continue;
+            }
+            let element = this.get_field(i);
             let arguments = [element, Value::from(i), this.clone()];
             let result = interpreter.call(callback, &this_arg, &arguments)?;
             if !result.to_boolean() {
@@ -545,20 +691,24 @@ impl Array {
 
         let length = i32::from(&this.get_field("length"));
 
-        let new = Self::new_array(interpreter)?;
+        let new = Self::array_species_create(this, length.max(0) as usize, interpreter)?;
 
-        let values: Vec<Value> = (0..length)
-            .map(|idx| {
-                let element = this.get_field(idx.to_string());
-                let args = [element, Value::from(idx), new.clone()];
+        for idx in 0..length {
+            // Holes are skipped, and kept as holes in the new array.
+            if !this.has_field(idx) {
+                continue;
+            }
+            let element = this.get_field(idx);
+            let args = [element, Value::from(idx), new.clone()];
 
-                interpreter
-                    .call(&callback, &this_val, &args)
-                    .unwrap_or_else(|_| Value::undefined())
-            })
-            .collect();
+            let value = interpreter
+                .call(&callback, &this_val, &args)
+                .unwrap_or_else(|_| Value::undefined());
+            new.set_field(idx, value);
+        }
+        new.set_field("length", Value::from(length));
 
-        Self::construct_array(&new, &values)
+        Ok(new)
     }
 
     /// `Array.prototype.indexOf( searchElement[, fromIndex ] )`
@@ -603,9 +753,9 @@ impl Array {
         };
 
         while idx < len {
-            let check_element = this.get_field(idx.to_string()).clone();
+            let check_element = this.get_field(idx).clone();
 
-            if check_element.strict_equals(&search_element) {
+            if this.has_field(idx) && check_element.strict_equals(&search_element) {
                 return Ok(Value::from(idx));
             }
 
@@ -656,9 +806,9 @@ impl Array {
         };
 
         while idx >= 0 {
-            let check_element = this.get_field(idx.to_string()).clone();
+            let check_element = this.get_field(idx).clone();
 
-            if check_element.strict_equals(&search_element) {
+            if this.has_field(idx) && check_element.strict_equals(&search_element) {
                 return Ok(Value::from(idx));
             }
 
@@ -690,7 +840,7 @@ impl Array {
         let this_arg = args.get(1).cloned().unwrap_or_else(Value::undefined);
         let len = i32::from(&this.get_field("length"));
         for i in 0..len {
-            let element = this.get_field(i.to_string());
+            let element = this.get_field(i);
             let arguments = [element.clone(), Value::from(i), this.clone()];
             let result = interpreter.call(callback, &this_arg, &arguments)?;
             if result.to_boolean() {
@@ -730,7 +880,7 @@ impl Array {
         let length = i32::from(&this.get_field("length"));
 
         for i in 0..length {
-            let element = this.get_field(i.to_string());
+            let element = this.get_field(i);
             let arguments = [element, Value::from(i), this.clone()];
 
             let result = interpreter.call(predicate_arg, &this_arg, &arguments)?;
@@ -777,7 +927,7 @@ impl Array {
         };
 
         for i in start..fin {
-            this.set_field(i.to_string(), value.clone());
+            this.set_field(i, value.clone());
         }
 
         Ok(this.clone())
@@ -799,7 +949,7 @@ impl Array {
         let length = i32::from(&this.get_field("length"));
 
         for idx in 0..length {
-            let check_element = this.get_field(idx.to_string()).clone();
+            let check_element = this.get_field(idx).clone();
 
             if same_value_zero(&check_element, &search_element) {
                 return Ok(Value::from(true));
@@ -828,7 +978,6 @@ impl Array {
         args: &[Value],
         interpreter: &mut Interpreter,
     ) -> ResultValue {
-        let new_array = Self::new_array(interpreter)?;
         let len = i32::from(&this.get_field("length"));
 
         let start = match args.get(0) {
@@ -852,9 +1001,10 @@ impl Array {
         };
 
         let span = max(to.wrapping_sub(from), 0);
+        let new_array = Self::array_species_create(this, span as usize, interpreter)?;
         let mut new_array_len: i32 = 0;
         for i in from..from.wrapping_add(span) {
-            new_array.set_field(new_array_len.to_string(), this.get_field(i.to_string()));
+            new_array.set_field(new_array_len, this.get_field(i));
             new_array_len = new_array_len.wrapping_add(1);
         }
         new_array.set_field("length", Value::from(new_array_len));
@@ -888,11 +1038,12 @@ impl Array {
 
         let length = i32::from(&this.get_field("length"));
 
-        let new = Self::new_array(interpreter)?;
+        let new = Self::array_species_create(this, 0, interpreter)?;
 
         let values = (0..length)
+            .filter(|idx| this.has_field(*idx))
             .filter_map(|idx| {
-                let element = this.get_field(idx.to_string());
+                let element = this.get_field(idx);
 
                 let args = [element.clone(), Value::from(idx), new.clone()];
 
@@ -942,7 +1093,11 @@ impl Array {
         let max_len = i32::from(&this.get_field("length"));
         let mut len = max_len;
         while i < len {
-            let element = this.get_field(i.to_string());
+            if !this.has_field(i) {
+                i += 1;
+                continue;
+            }
+            let element = this.get_field(i);
             let arguments = [element, Value::from(i), this.clone()];
             let result = interpreter.call(callback, &this_arg, &arguments)?;
             if result.to_boolean() {
@@ -986,7 +1141,7 @@ impl Array {
         let mut accumulator = if initial_value.is_undefined() {
             let mut k_present = false;
             while k < length {
-                if this.has_field(&k.to_string()) {
+                if this.has_field(k) {
                     k_present = true;
                     break;
                 }
@@ -997,20 +1152,15 @@ impl Array {
                     "Reduce was called on an empty array and with no initial value",
                 );
             }
-            let result = this.get_field(k.to_string());
+            let result = this.get_field(k);
             k += 1;
             result
         } else {
             initial_value
         };
         while k < length {
-            if this.has_field(&k.to_string()) {
-                let arguments = [
-                    accumulator,
-                    this.get_field(k.to_string()),
-                    Value::from(k),
-                    this.clone(),
-                ];
+            if this.has_field(k) {
+                let arguments = [accumulator, this.get_field(k), Value::from(k), this.clone()];
                 accumulator = interpreter.call(&callback, &Value::undefined(), &arguments)?;
                 /* We keep track of possibly shortened length in order to prevent unnecessary iteration.
                 It may also be necessary to do this since shortening the array length does not
@@ -1059,7 +1209,7 @@ impl Array {
         let mut accumulator = if initial_value.is_undefined() {
             let mut k_present = false;
             loop {
-                if this.has_field(&k.to_string()) {
+                if this.has_field(k) {
                     k_present = true;
                     break;
                 }
@@ -1074,20 +1224,15 @@ impl Array {
                     "reduceRight was called on an empty array and with no initial value",
                 );
             }
-            let result = this.get_field(k.to_string());
+            let result = this.get_field(k);
             k -= 1;
             result
         } else {
             initial_value
         };
         loop {
-            if this.has_field(&k.to_string()) {
-                let arguments = [
-                    accumulator,
-                    this.get_field(k.to_string()),
-                    Value::from(k),
-                    this.clone(),
-                ];
+            if this.has_field(k) {
+                let arguments = [accumulator, this.get_field(k), Value::from(k), this.clone()];
                 accumulator = interpreter.call(&callback, &Value::undefined(), &arguments)?;
                 /* We keep track of possibly shortened length in order to prevent unnecessary iteration.
                 It may also be necessary to do this since shortening the array length does not
@@ -1112,6 +1257,21 @@ impl Array {
         Ok(accumulator)
     }
 
+    /// `get Array [ @@species ]`
+    ///
+    /// The `Array[Symbol.species]` accessor returns the `Array` constructor, subclasses can
+    /// override it to change the constructor used by the methods that create new arrays.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-get-array-@@species
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/@@species
+    pub(crate) fn get_species(this: &Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
+        Ok(this.clone())
+    }
+
     /// Initialise the `Array` object on the global object.
     #[inline]
     pub(crate) fn init(global: &Value) -> (&str, Value) {
@@ -1158,6 +1318,16 @@ impl Array {
 
         // Static Methods
         make_builtin_fn(Self::is_array, "isArray", &array, 1);
+        make_builtin_fn(Self::from, "from", &array, 1);
+        make_builtin_fn(Self::of, "of", &array, 0);
+
+        let species = Object::function(
+            Function::builtin(Vec::new(), Self::get_species),
+            Value::null(),
+        );
+        let mut species = Property::empty().get(Value::from(species));
+        species.set_configurable(true);
+        array.set_property(WellKnownSymbol::Species.key(), species);
 
         (Self::NAME, array)
     }
@@ -1043,3 +1043,186 @@ fn call_array_constructor_with_one_argument() {
     let result = forward(&mut engine, "one.length");
     assert_eq!(result, "1");
 }
+
+#[test]
+fn throws_when_exceeding_configured_max_length() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.set_max_array_length(4);
+
+    let init = r#"
+        var arr = [1, 2, 3];
+        "#;
+    forward(&mut engine, init);
+
+    let range_error = "RangeError: invalid array length";
+    let catch = |code: &str| format!("try {{ {} }} catch (e) {{ e.toString() }}", code);
+    assert_eq!(forward(&mut engine, &catch("new Array(5)")), range_error);
+    assert_eq!(forward(&mut engine, &catch("arr.push(4, 5)")), range_error);
+    assert_eq!(
+        forward(&mut engine, &catch("arr.unshift(4, 5)")),
+        range_error
+    );
+    assert_eq!(
+        forward(&mut engine, &catch("arr.concat([4, 5])")),
+        range_error
+    );
+    assert_eq!(forward(&mut engine, &catch("arr.length = 5")), range_error);
+    assert_eq!(forward(&mut engine, &catch("arr[4] = 5")), range_error);
+    assert_eq!(
+        forward(&mut engine, &catch("Array.from('abcde')")),
+        range_error
+    );
+    assert_eq!(
+        forward(&mut engine, &catch("Array.from({ length: 5 })")),
+        range_error
+    );
+    assert_eq!(forward(&mut engine, "arr.length"), "3");
+    assert_eq!(forward(&mut engine, "arr.push(4)"), "4");
+}
+
+#[test]
+fn holes() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var sparse = new Array(3);
+        var elided = [1, , 3];
+        var deleted = [1, 2, 3];
+        delete deleted[1];
+        var visited = 0;
+        elided.forEach(function() { visited++; });
+        var mapped = elided.map(function(x) { return x * 2; });
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "sparse.length"), "3");
+    assert_eq!(forward(&mut engine, "0 in sparse"), "false");
+    assert_eq!(forward(&mut engine, "elided.length"), "3");
+    assert_eq!(forward(&mut engine, "1 in elided"), "false");
+    assert_eq!(forward(&mut engine, "1 in deleted"), "false");
+    assert_eq!(forward(&mut engine, "deleted.length"), "3");
+    assert_eq!(forward(&mut engine, "visited"), "2");
+    assert_eq!(forward(&mut engine, "mapped.length"), "3");
+    assert_eq!(forward(&mut engine, "1 in mapped"), "false");
+    assert_eq!(forward(&mut engine, "mapped[2]"), "6");
+    assert_eq!(forward(&mut engine, "elided.indexOf(undefined)"), "-1");
+}
+
+#[test]
+fn length_assignment() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var arr = [1, 2, 3, 4];
+        arr.length = 2;
+        var grown = [1];
+        grown.length = 3;
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "arr.length"), "2");
+    assert_eq!(forward(&mut engine, "2 in arr"), "false");
+    assert_eq!(forward(&mut engine, "arr[1]"), "2");
+    assert_eq!(forward(&mut engine, "grown.length"), "3");
+    assert_eq!(forward(&mut engine, "2 in grown"), "false");
+}
+
+#[test]
+fn from() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var fromString = Array.from("abc");
+        var fromArray = Array.from([1, 2, 3], function(x) { return x * this.factor; }, { factor: 2 });
+        var fromArrayLike = Array.from({ length: 2, 0: "a", 1: "b" });
+        var map = new Map();
+        map.set("key", "value");
+        var fromMap = Array.from(map);
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "fromString.join()"), "a,b,c");
+    assert_eq!(forward(&mut engine, "fromArray.join()"), "2,4,6");
+    assert_eq!(forward(&mut engine, "fromArrayLike.join()"), "a,b");
+    assert_eq!(forward(&mut engine, "fromMap[0].join()"), "key,value");
+    assert_eq!(
+        forward(&mut engine, "Array.isArray(Array.from({}))"),
+        "true"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { Array.from([], 1) } catch (e) { e.toString() }"
+        ),
+        "TypeError: Array.from: mapFn is not a function"
+    );
+}
+
+#[test]
+fn of() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    assert_eq!(forward(&mut engine, "Array.of(7).length"), "1");
+    assert_eq!(forward(&mut engine, "Array.of(1, 2, 3).join()"), "1,2,3");
+    assert_eq!(forward(&mut engine, "Array.of().length"), "0");
+}
+
+#[test]
+fn species_constructor() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        function MyArray(length) {
+            this.length = length;
+        }
+        let arr = [1, 2, 3];
+        arr.constructor = {};
+        arr.constructor[Symbol.species] = MyArray;
+        let mapped = arr.map(function (x) { return x * 2; });
+        let filtered = arr.filter(function (x) { return x > 1; });
+        let sliced = arr.slice(1);
+    "#;
+
+    forward(&mut engine, init);
+    assert_eq!(
+        forward(&mut engine, "Array[Symbol.species] === Array"),
+        "true"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "Object.getPrototypeOf(mapped) === MyArray.prototype"
+        ),
+        "true"
+    );
+    assert_eq!(forward(&mut engine, "mapped[2]"), "6");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "Object.getPrototypeOf(filtered) === MyArray.prototype"
+        ),
+        "true"
+    );
+    assert_eq!(forward(&mut engine, "filtered.length"), "2");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "Object.getPrototypeOf(sliced) === MyArray.prototype"
+        ),
+        "true"
+    );
+}
+
+#[test]
+fn concat_does_not_modify_this() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(
+        &mut engine,
+        "let arr = [1]; let joined = arr.concat([2, 3]);",
+    );
+    assert_eq!(forward(&mut engine, "arr.length"), "1");
+    assert_eq!(forward(&mut engine, "joined.length"), "3");
+}
@@ -16,6 +16,7 @@ use crate::{
     builtins::{
         function::{make_builtin_fn, make_constructor_fn},
         object::ObjectData,
+        symbol::set_to_string_tag,
         value::{RcBigInt, ResultValue, Value},
     },
     exec::Interpreter,
@@ -207,6 +208,7 @@ impl BigInt {
 
         make_builtin_fn(Self::to_string, "toString", &prototype, 1);
         make_builtin_fn(Self::value_of, "valueOf", &prototype, 0);
+        set_to_string_tag(&prototype, Self::NAME);
 
         let bigint_object = make_constructor_fn(
             Self::NAME,
@@ -19,16 +19,16 @@ mod tests;
 use crate::{
     builtins::{
         function::make_builtin_fn,
-        value::{display_obj, RcString, ResultValue, Value},
+        value::{display_obj, InspectOptions, JsString, ResultValue, Value},
     },
     exec::Interpreter,
     BoaProfiler,
 };
 use rustc_hash::FxHashMap;
-use std::time::SystemTime;
+use std::{cell::RefCell, fmt::Debug, io::Write, mem, rc::Rc};
 
 /// This represents the different types of log messages.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum LogMessage {
     Log(String),
     Info(String),
@@ -36,6 +36,107 @@ pub enum LogMessage {
     Error(String),
 }
 
+/// The destination of the messages printed through the `console` object.
+///
+/// By default messages are written to the standard output, and errors to the standard error.
+/// Embedders can provide their own writer with `Context::set_console_writer`, for instance to
+/// forward the messages to a logger or to display them in a window. `ConsoleBuffer` and
+/// `WriteConsoleWriter` cover the common cases.
+pub trait ConsoleWriter: Debug {
+    /// Writes a single, already formatted and indented, message.
+    fn write(&mut self, msg: LogMessage);
+}
+
+/// The default `ConsoleWriter`, printing to the standard output and the standard error.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdConsoleWriter;
+
+impl ConsoleWriter for StdConsoleWriter {
+    fn write(&mut self, msg: LogMessage) {
+        match msg {
+            LogMessage::Error(msg) => eprintln!("{}", msg),
+            LogMessage::Log(msg) | LogMessage::Info(msg) | LogMessage::Warn(msg) => {
+                println!("{}", msg)
+            }
+        }
+    }
+}
+
+/// A `ConsoleWriter` that writes every message, followed by a new line, to a `Write` sink like a
+/// file or a socket.
+///
+/// Errors of the sink are ignored, since scripts cannot do anything about them.
+#[derive(Debug, Clone, Default)]
+pub struct WriteConsoleWriter<W> {
+    sink: W,
+}
+
+impl<W> WriteConsoleWriter<W> {
+    /// Creates a writer printing to `sink`.
+    #[inline]
+    pub fn new(sink: W) -> Self {
+        Self { sink }
+    }
+}
+
+impl<W: Write + Debug> ConsoleWriter for WriteConsoleWriter<W> {
+    fn write(&mut self, msg: LogMessage) {
+        let (LogMessage::Log(msg)
+        | LogMessage::Info(msg)
+        | LogMessage::Warn(msg)
+        | LogMessage::Error(msg)) = msg;
+        let _ = writeln!(self.sink, "{}", msg);
+    }
+}
+
+/// A `ConsoleWriter` that keeps the messages in memory.
+///
+/// The buffer is shared between its clones, so a clone can be installed on the context while
+/// the host reads the messages from the original:
+///
+/// ```
+/// use boa::{
+///     builtins::console::{ConsoleBuffer, LogMessage},
+///     Context,
+/// };
+///
+/// let buffer = ConsoleBuffer::default();
+/// let mut context = Context::new();
+/// context.set_console_writer(buffer.clone());
+/// context.eval("console.log('hello'); console.error('oops')").unwrap();
+/// assert_eq!(
+///     buffer.take(),
+///     [
+///         LogMessage::Log("hello".to_owned()),
+///         LogMessage::Error("oops".to_owned())
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleBuffer {
+    messages: Rc<RefCell<Vec<LogMessage>>>,
+}
+
+impl ConsoleBuffer {
+    /// Gets the messages printed so far.
+    #[inline]
+    pub fn messages(&self) -> Vec<LogMessage> {
+        self.messages.borrow().clone()
+    }
+
+    /// Gets the messages printed so far, and removes them from the buffer.
+    #[inline]
+    pub fn take(&self) -> Vec<LogMessage> {
+        mem::take(&mut *self.messages.borrow_mut())
+    }
+}
+
+impl ConsoleWriter for ConsoleBuffer {
+    fn write(&mut self, msg: LogMessage) {
+        self.messages.borrow_mut().push(msg);
+    }
+}
+
 /// Helper function that returns the argument at a specified index.
 fn get_arg_at_index<'a, T>(args: &'a [Value], index: usize) -> Option<T>
 where
@@ -45,26 +146,42 @@ where
 }
 
 /// Helper function for logging messages.
-pub(crate) fn logger(msg: LogMessage, console_state: &Console) {
-    let indent = 2 * console_state.groups.len();
-
-    match msg {
-        LogMessage::Error(msg) => {
-            eprintln!("{:>width$}", msg, width = indent);
-        }
-        LogMessage::Log(msg) | LogMessage::Info(msg) | LogMessage::Warn(msg) => {
-            println!("{:>width$}", msg, width = indent);
-        }
-    }
+///
+/// Every line of the message is indented by the current group depth.
+pub(crate) fn logger(msg: LogMessage, console_state: &mut Console) {
+    let indent = " ".repeat(2 * console_state.groups.len());
+    let indented = |msg: String| {
+        msg.lines()
+            .map(|line| format!("{}{}", indent, line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let msg = match msg {
+        LogMessage::Log(msg) => LogMessage::Log(indented(msg)),
+        LogMessage::Info(msg) => LogMessage::Info(indented(msg)),
+        LogMessage::Warn(msg) => LogMessage::Warn(indented(msg)),
+        LogMessage::Error(msg) => LogMessage::Error(indented(msg)),
+    };
+    console_state.writer.write(msg);
 }
 
 /// This represents the `console` formatter.
+///
+/// If the first value is a string, it is used as a format string for the following values,
+/// supporting the `%s`, `%d`, `%i`, `%f`, `%o` and `%O` specifiers. Otherwise the values are
+/// joined by spaces.
 pub fn formatter(data: &[Value], ctx: &mut Interpreter) -> Result<String, Value> {
-    let target = ctx.to_string(&data.get(0).cloned().unwrap_or_default())?;
-    match data.len() {
-        0 => Ok(String::new()),
-        1 => Ok(target.to_string()),
-        _ => {
+    match data {
+        [] => Ok(String::new()),
+        [first, ..] if !first.is_string() => Ok(data
+            .iter()
+            .map(|value| value.inspect(&InspectOptions::default()).text)
+            .collect::<Vec<_>>()
+            .join(" ")),
+        [target] => Ok(ctx.to_string(target)?.to_string()),
+        [target, ..] => {
+            let target = ctx.to_string(target)?;
             let mut formatted = String::new();
             let mut arg_index = 1;
             let mut chars = target.chars();
@@ -84,10 +201,10 @@ pub fn formatter(data: &[Value], ctx: &mut Interpreter) -> Result<String, Value>
                             formatted.push_str(&format!("{number:.prec$}", number = arg, prec = 6));
                             arg_index += 1
                         }
-                        /* object, FIXME: how to render this properly? */
+                        /* object */
                         'o' | 'O' => {
                             let arg = data.get(arg_index).cloned().unwrap_or_default();
-                            formatted.push_str(&format!("{}", arg));
+                            formatted.push_str(&arg.inspect(&InspectOptions::default()).text);
                             arg_index += 1
                         }
                         /* string */
@@ -111,7 +228,8 @@ pub fn formatter(data: &[Value], ctx: &mut Interpreter) -> Result<String, Value>
 
             /* unformatted data */
             for rest in data.iter().skip(arg_index) {
-                formatted.push_str(&format!(" {}", rest))
+                formatted.push(' ');
+                formatted.push_str(&rest.inspect(&InspectOptions::default()).text);
             }
 
             Ok(formatted)
@@ -120,17 +238,34 @@ pub fn formatter(data: &[Value], ctx: &mut Interpreter) -> Result<String, Value>
 }
 
 /// This is the internal console object state.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub(crate) struct Console {
-    count_map: FxHashMap<RcString, u32>,
-    timer_map: FxHashMap<RcString, u128>,
+    count_map: FxHashMap<JsString, u32>,
+    timer_map: FxHashMap<JsString, u64>,
     groups: Vec<String>,
+    writer: Box<dyn ConsoleWriter>,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self {
+            count_map: FxHashMap::default(),
+            timer_map: FxHashMap::default(),
+            groups: Vec::new(),
+            writer: Box::new(StdConsoleWriter),
+        }
+    }
 }
 
 impl Console {
     /// The name of the object.
     pub(crate) const NAME: &'static str = "console";
 
+    /// Replaces the writer that messages are printed to.
+    pub(crate) fn set_writer(&mut self, writer: Box<dyn ConsoleWriter>) {
+        self.writer = writer;
+    }
+
     /// `console.assert(condition, ...data)`
     ///
     /// Prints a JavaScript value to the standard error if first argument evaluates to `false` or there
@@ -157,7 +292,7 @@ impl Console {
                 args[0] = Value::from(concat);
             }
 
-            logger(LogMessage::Error(formatter(&args, ctx)?), ctx.console());
+            logger(LogMessage::Error(formatter(&args, ctx)?), ctx.console_mut());
         }
 
         Ok(Value::undefined())
@@ -189,7 +324,7 @@ impl Console {
     /// [spec]: https://console.spec.whatwg.org/#debug
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/debug
     pub(crate) fn debug(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        logger(LogMessage::Log(formatter(args, ctx)?), ctx.console());
+        logger(LogMessage::Log(formatter(args, ctx)?), ctx.console_mut());
         Ok(Value::undefined())
     }
 
@@ -204,7 +339,7 @@ impl Console {
     /// [spec]: https://console.spec.whatwg.org/#error
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/error
     pub(crate) fn error(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        logger(LogMessage::Error(formatter(args, ctx)?), ctx.console());
+        logger(LogMessage::Error(formatter(args, ctx)?), ctx.console_mut());
         Ok(Value::undefined())
     }
 
@@ -219,7 +354,7 @@ impl Console {
     /// [spec]: https://console.spec.whatwg.org/#info
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/info
     pub(crate) fn info(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        logger(LogMessage::Info(formatter(args, ctx)?), ctx.console());
+        logger(LogMessage::Info(formatter(args, ctx)?), ctx.console_mut());
         Ok(Value::undefined())
     }
 
@@ -234,7 +369,7 @@ impl Console {
     /// [spec]: https://console.spec.whatwg.org/#log
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/log
     pub(crate) fn log(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        logger(LogMessage::Log(formatter(args, ctx)?), ctx.console());
+        logger(LogMessage::Log(formatter(args, ctx)?), ctx.console_mut());
         Ok(Value::undefined())
     }
 
@@ -249,15 +384,14 @@ impl Console {
     /// [spec]: https://console.spec.whatwg.org/#trace
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/trace
     pub(crate) fn trace(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        if !args.is_empty() {
-            logger(LogMessage::Log(formatter(args, ctx)?), ctx.console());
+        let label = if args.is_empty() {
+            "Trace".to_string()
+        } else {
+            format!("Trace: {}", formatter(args, ctx)?)
+        };
+        logger(LogMessage::Log(label), ctx.console_mut());
 
-            /* TODO: get and print stack trace */
-            logger(
-                LogMessage::Log("Not implemented: <stack trace>".to_string()),
-                ctx.console(),
-            )
-        }
+        /* TODO: get and print stack trace */
 
         Ok(Value::undefined())
     }
@@ -273,7 +407,7 @@ impl Console {
     /// [spec]: https://console.spec.whatwg.org/#warn
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/warn
     pub(crate) fn warn(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        logger(LogMessage::Warn(formatter(args, ctx)?), ctx.console());
+        logger(LogMessage::Warn(formatter(args, ctx)?), ctx.console_mut());
         Ok(Value::undefined())
     }
 
@@ -297,7 +431,10 @@ impl Console {
         let c = ctx.console_mut().count_map.entry(label).or_insert(0);
         *c += 1;
 
-        logger(LogMessage::Info(format!("{} {}", msg, c)), ctx.console());
+        logger(
+            LogMessage::Info(format!("{} {}", msg, c)),
+            ctx.console_mut(),
+        );
         Ok(Value::undefined())
     }
 
@@ -321,20 +458,12 @@ impl Console {
 
         logger(
             LogMessage::Warn(format!("countReset {}", label)),
-            ctx.console(),
+            ctx.console_mut(),
         );
 
         Ok(Value::undefined())
     }
 
-    /// Returns current system time in ms.
-    fn system_time_in_ms() -> u128 {
-        let now = SystemTime::now();
-        now.duration_since(SystemTime::UNIX_EPOCH)
-            .expect("negative duration")
-            .as_millis()
-    }
-
     /// `console.time(label)`
     ///
     /// Starts the timer for given label.
@@ -354,10 +483,10 @@ impl Console {
         if ctx.console().timer_map.get(&label).is_some() {
             logger(
                 LogMessage::Warn(format!("Timer '{}' already exist", label)),
-                ctx.console(),
+                ctx.console_mut(),
             );
         } else {
-            let time = Self::system_time_in_ms();
+            let time = ctx.current_time();
             ctx.console_mut().timer_map.insert(label, time);
         }
 
@@ -380,17 +509,17 @@ impl Console {
             None => "default".into(),
         };
 
-        if let Some(t) = ctx.console().timer_map.get(&label) {
-            let time = Self::system_time_in_ms();
+        if let Some(t) = ctx.console().timer_map.get(&label).copied() {
+            let time = ctx.current_time();
             let mut concat = format!("{}: {} ms", label, time - t);
             for msg in args.iter().skip(1) {
                 concat = concat + " " + &msg.to_string();
             }
-            logger(LogMessage::Log(concat), ctx.console());
+            logger(LogMessage::Log(concat), ctx.console_mut());
         } else {
             logger(
                 LogMessage::Warn(format!("Timer '{}' doesn't exist", label)),
-                ctx.console(),
+                ctx.console_mut(),
             );
         }
 
@@ -414,15 +543,15 @@ impl Console {
         };
 
         if let Some(t) = ctx.console_mut().timer_map.remove(label.as_str()) {
-            let time = Self::system_time_in_ms();
+            let time = ctx.current_time();
             logger(
                 LogMessage::Info(format!("{}: {} ms - timer removed", label, time - t)),
-                ctx.console(),
+                ctx.console_mut(),
             );
         } else {
             logger(
                 LogMessage::Warn(format!("Timer '{}' doesn't exist", label)),
-                ctx.console(),
+                ctx.console_mut(),
             );
         }
 
@@ -444,7 +573,7 @@ impl Console {
 
         logger(
             LogMessage::Info(format!("group: {}", &group_label)),
-            ctx.console(),
+            ctx.console_mut(),
         );
         ctx.console_mut().groups.push(group_label);
 
@@ -481,7 +610,7 @@ impl Console {
         let undefined = Value::undefined();
         logger(
             LogMessage::Info(display_obj(args.get(0).unwrap_or(&undefined), true)),
-            ctx.console(),
+            ctx.console_mut(),
         );
 
         Ok(Value::undefined())
@@ -1,8 +1,32 @@
 use crate::{
-    builtins::{console::formatter, value::Value},
+    builtins::{
+        console::{formatter, ConsoleWriter, LogMessage},
+        value::Value,
+    },
     exec::Interpreter,
+    forward,
     realm::Realm,
 };
+use std::{cell::RefCell, rc::Rc};
+
+/// A `ConsoleWriter` that records the messages instead of printing them.
+#[derive(Debug, Clone, Default)]
+struct RecordingWriter(Rc<RefCell<Vec<LogMessage>>>);
+
+impl ConsoleWriter for RecordingWriter {
+    fn write(&mut self, msg: LogMessage) {
+        self.0.borrow_mut().push(msg);
+    }
+}
+
+fn recording_engine() -> (Interpreter, Rc<RefCell<Vec<LogMessage>>>) {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let writer = RecordingWriter::default();
+    let messages = writer.0.clone();
+    engine.set_console_writer(writer);
+    (engine, messages)
+}
 
 #[test]
 fn formatter_no_args_is_empty_string() {
@@ -80,3 +104,159 @@ fn formatter_float_format_works() {
     let res = formatter(&val, &mut engine).unwrap();
     assert_eq!(res, "3.141500");
 }
+
+#[test]
+fn formatter_non_string_first_argument_joins_args() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let val = [Value::integer(1), Value::string("%d"), Value::integer(2)];
+    let res = formatter(&val, &mut engine).unwrap();
+    assert_eq!(res, "1 %d 2");
+}
+
+#[test]
+fn messages_go_through_the_configured_writer() {
+    let (mut engine, messages) = recording_engine();
+    forward(
+        &mut engine,
+        r#"
+        console.log("%s is %d", "answer", 42);
+        console.info("info");
+        console.warn("warn");
+        console.error("error");
+        console.debug("debug");
+        "#,
+    );
+
+    assert_eq!(
+        *messages.borrow(),
+        vec![
+            LogMessage::Log("answer is 42".to_string()),
+            LogMessage::Info("info".to_string()),
+            LogMessage::Warn("warn".to_string()),
+            LogMessage::Error("error".to_string()),
+            LogMessage::Log("debug".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn assert_count_and_trace() {
+    let (mut engine, messages) = recording_engine();
+    forward(
+        &mut engine,
+        r#"
+        console.assert(true, "not printed");
+        console.assert(false, "printed");
+        console.count();
+        console.count("label");
+        console.count();
+        console.trace("here");
+        "#,
+    );
+
+    assert_eq!(
+        *messages.borrow(),
+        vec![
+            LogMessage::Error("Assertion failed: printed".to_string()),
+            LogMessage::Info("count default: 1".to_string()),
+            LogMessage::Info("count label: 1".to_string()),
+            LogMessage::Info("count default: 2".to_string()),
+            LogMessage::Log("Trace: here".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn groups_indent_messages() {
+    let (mut engine, messages) = recording_engine();
+    forward(
+        &mut engine,
+        r#"
+        console.group("outer");
+        console.log("one");
+        console.group("inner");
+        console.log("two");
+        console.groupEnd();
+        console.groupEnd();
+        console.log("three");
+        "#,
+    );
+
+    assert_eq!(
+        *messages.borrow(),
+        vec![
+            LogMessage::Info("group: outer".to_string()),
+            LogMessage::Log("  one".to_string()),
+            LogMessage::Info("  group: inner".to_string()),
+            LogMessage::Log("    two".to_string()),
+            LogMessage::Log("three".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn time_end_removes_timer() {
+    let (mut engine, messages) = recording_engine();
+    forward(
+        &mut engine,
+        r#"
+        console.time("t");
+        console.timeEnd("t");
+        console.timeEnd("t");
+        "#,
+    );
+
+    let messages = messages.borrow();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(
+        messages[1],
+        LogMessage::Warn("Timer 't' doesn't exist".to_string())
+    );
+}
+
+#[test]
+fn timers_use_the_clock_of_the_interpreter() {
+    let (mut engine, messages) = recording_engine();
+    engine.set_clock(Some(1_000));
+    forward(&mut engine, r#"console.time("t");"#);
+    engine.set_clock(Some(1_250));
+    forward(&mut engine, r#"console.timeEnd("t");"#);
+
+    assert_eq!(
+        *messages.borrow(),
+        vec![LogMessage::Info("t: 250 ms - timer removed".to_string())]
+    );
+}
+
+#[test]
+fn write_console_writer_prints_lines() {
+    use crate::builtins::console::WriteConsoleWriter;
+
+    /// A `Write` sink shared with the test.
+    #[derive(Debug, Clone, Default)]
+    struct Sink(Rc<RefCell<Vec<u8>>>);
+
+    impl std::io::Write for Sink {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let sink = Sink::default();
+    engine.set_console_writer(WriteConsoleWriter::new(sink.clone()));
+    forward(
+        &mut engine,
+        "console.log('a'); console.group('g'); console.error('b')",
+    );
+
+    assert_eq!(&*sink.0.borrow(), b"a\ngroup: g\n  b\n");
+}
@@ -25,14 +25,14 @@ pub(crate) mod reference;
 pub(crate) mod syntax;
 pub(crate) mod r#type;
 // pub(crate) mod eval;
-// pub(crate) mod uri;
+pub(crate) mod uri;
 
 pub(crate) use self::r#type::TypeError;
 pub(crate) use self::range::RangeError;
 pub(crate) use self::reference::ReferenceError;
 pub(crate) use self::syntax::SyntaxError;
 // pub(crate) use self::eval::EvalError;
-// pub(crate) use self::uri::UriError;
+pub(crate) use self::uri::UriError;
 
 /// Built-in `Error` object.
 #[derive(Debug, Clone, Copy)]
@@ -54,6 +54,8 @@ impl Error {
         // This value is used by console.log and other routines to match Object type
         // to its Javascript Identifier (global constructor method name)
         this.set_data(ObjectData::Error);
+        // Leaves out the frame of the constructor itself.
+        ctx.capture_stack_trace(this, 1, None);
         Err(this.clone())
     }
 
@@ -74,6 +76,34 @@ impl Error {
         Ok(Value::from(format!("{}: {}", name, message)))
     }
 
+    /// `Error.captureStackTrace( targetObject [, constructorOpt] )`
+    ///
+    /// The `captureStackTrace()` method sets the `stack` property of `targetObject` to the
+    /// current stack trace. If `constructorOpt` is a function, the frames of its innermost call
+    /// and the ones above are left out, which hides the internals of custom error constructors.
+    ///
+    /// This is not part of the specification, but it is implemented by V8 and relied upon by a
+    /// lot of libraries.
+    ///
+    /// More information:
+    ///  - [V8 documentation][v8]
+    ///
+    /// [v8]: https://v8.dev/docs/stack-trace-api
+    pub(crate) fn capture_stack_trace(
+        _: &Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let target = args.first().cloned().unwrap_or_default();
+        if !target.is_object() {
+            return ctx.throw_type_error("Error.captureStackTrace: target is not an object");
+        }
+        let until = args.get(1).filter(|function| function.is_function());
+        // Leaves out the frame of `captureStackTrace` itself.
+        ctx.capture_stack_trace(&target, 1, until);
+        Ok(Value::undefined())
+    }
+
     /// Initialise the global object with the `Error` object.
     #[inline]
     pub(crate) fn init(global: &Value) -> (&str, Value) {
@@ -95,6 +125,13 @@ impl Error {
             true,
         );
 
+        make_builtin_fn(
+            Self::capture_stack_trace,
+            "captureStackTrace",
+            &error_object,
+            2,
+        );
+
         (Self::NAME, error_object)
     }
 }
@@ -42,6 +42,8 @@ impl SyntaxError {
         // This value is used by console.log and other routines to match Object type
         // to its Javascript Identifier (global constructor method name)
         this.set_data(ObjectData::Error);
+        // Leaves out the frame of the constructor itself.
+        ctx.capture_stack_trace(this, 1, None);
         Err(this.clone())
     }
 
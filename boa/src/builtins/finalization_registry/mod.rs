@@ -0,0 +1,173 @@
+//! This module implements the global `FinalizationRegistry` object.
+//!
+//! A `FinalizationRegistry` calls a cleanup callback after the objects registered with it have
+//! been garbage collected. The garbage collector of the engine has no weak references yet, so
+//! registered objects are never reported as collected and the callback is never called. The
+//! specification allows this, since an engine is never required to collect an object.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-finalization-registry-objects
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/FinalizationRegistry
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builtins::{
+        function::{make_builtin_fn, make_constructor_fn},
+        symbol::set_to_string_tag,
+        value::{same_value, ResultValue, Value},
+        Array,
+    },
+    exec::Interpreter,
+    BoaProfiler,
+};
+
+/// The internal slot that holds the cleanup callback of a registry.
+const CLEANUP: &str = "CleanupCallback";
+
+/// The internal slot that holds the array of the registered cells of a registry.
+///
+/// Each cell is an object with the held value and the unregister token of a registration.
+const CELLS: &str = "Cells";
+
+/// JavaScript `FinalizationRegistry` implementation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FinalizationRegistry;
+
+impl FinalizationRegistry {
+    /// The name of the object.
+    pub(crate) const NAME: &'static str = "FinalizationRegistry";
+
+    /// The amount of arguments this function object takes.
+    pub(crate) const LENGTH: usize = 1;
+
+    /// `new FinalizationRegistry( cleanupCallback )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-finalization-registry-cleanup-callback
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/FinalizationRegistry/FinalizationRegistry
+    pub(crate) fn make_finalization_registry(
+        this: &Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let cleanup = args.get(0).cloned().unwrap_or_default();
+        if !cleanup.is_function() {
+            return ctx
+                .throw_type_error("FinalizationRegistry: cleanup callback must be a function");
+        }
+        this.set_internal_slot(CLEANUP, cleanup);
+        this.set_internal_slot(CELLS, Array::new_array(ctx)?);
+        Ok(this.clone())
+    }
+
+    /// Gets the cells of a registry, or throws if `this` is not a registry.
+    fn cells(this: &Value, method: &str, ctx: &mut Interpreter) -> ResultValue {
+        let cells = this.get_internal_slot(CELLS);
+        if !cells.is_object() {
+            return ctx.throw_type_error(format!(
+                "FinalizationRegistry.prototype.{}: 'this' is not a FinalizationRegistry",
+                method
+            ));
+        }
+        Ok(cells)
+    }
+
+    /// `FinalizationRegistry.prototype.register( target, heldValue [ , unregisterToken ] )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-finalization-registry.prototype.register
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/FinalizationRegistry/register
+    pub(crate) fn register(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let cells = Self::cells(this, "register", ctx)?;
+        let target = args.get(0).cloned().unwrap_or_default();
+        let held_value = args.get(1).cloned().unwrap_or_default();
+        let unregister_token = args.get(2).cloned().unwrap_or_default();
+
+        if !target.is_object() {
+            return ctx.throw_type_error(
+                "FinalizationRegistry.prototype.register: target must be an object",
+            );
+        }
+        if same_value(&target, &held_value) {
+            return ctx.throw_type_error(
+                "FinalizationRegistry.prototype.register: target and held value must not be the same",
+            );
+        }
+        if !unregister_token.is_object() && !unregister_token.is_undefined() {
+            return ctx.throw_type_error(
+                "FinalizationRegistry.prototype.register: unregister token must be an object",
+            );
+        }
+
+        let cell = Value::new_object(Some(&ctx.realm().global_obj));
+        cell.set_field("heldValue", held_value);
+        cell.set_field("unregisterToken", unregister_token);
+        Array::add_to_array_object(&cells, &[cell])?;
+        Ok(Value::undefined())
+    }
+
+    /// `FinalizationRegistry.prototype.unregister( unregisterToken )`
+    ///
+    /// Removes every registration made with `unregisterToken`, returns whether there was any.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-finalization-registry.prototype.unregister
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/FinalizationRegistry/unregister
+    pub(crate) fn unregister(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let cells = Self::cells(this, "unregister", ctx)?;
+        let unregister_token = args.get(0).cloned().unwrap_or_default();
+        if !unregister_token.is_object() {
+            return ctx.throw_type_error(
+                "FinalizationRegistry.prototype.unregister: unregister token must be an object",
+            );
+        }
+
+        let length = i32::from(&cells.get_field("length"));
+        let remaining: Vec<Value> = (0..length)
+            .map(|index| cells.get_field(index))
+            .filter(|cell| !same_value(&cell.get_field("unregisterToken"), &unregister_token))
+            .collect();
+        let removed = remaining.len() as i32 != length;
+        if removed {
+            Array::construct_array(&cells, &remaining)?;
+        }
+        Ok(Value::from(removed))
+    }
+
+    /// Initialise the `FinalizationRegistry` object on the global object.
+    #[inline]
+    pub(crate) fn init(global: &Value) -> (&str, Value) {
+        let _timer = BoaProfiler::global().start_event(Self::NAME, "init");
+
+        let prototype = Value::new_object(Some(global));
+        make_builtin_fn(Self::register, "register", &prototype, 2);
+        make_builtin_fn(Self::unregister, "unregister", &prototype, 1);
+        set_to_string_tag(&prototype, Self::NAME);
+
+        let finalization_registry = make_constructor_fn(
+            Self::NAME,
+            Self::LENGTH,
+            Self::make_finalization_registry,
+            global,
+            prototype,
+            true,
+            false,
+        );
+
+        (Self::NAME, finalization_registry)
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{exec::Interpreter, forward, realm::Realm};
+
+#[test]
+fn register_and_unregister() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var registry = new FinalizationRegistry(function(held) {});
+        var token = {};
+        registry.register({}, 'first', token);
+        registry.register({}, 'second', token);
+        registry.register({}, 'third');
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "registry.unregister(token)"), "true");
+    assert_eq!(forward(&mut engine, "registry.unregister(token)"), "false");
+    assert_eq!(forward(&mut engine, "registry.unregister({})"), "false");
+}
+
+#[test]
+fn invalid_arguments() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(
+        &mut engine,
+        "var registry = new FinalizationRegistry(function() {}); var target = {};",
+    );
+
+    let scenario = r#"
+        try {
+            new FinalizationRegistry(1);
+        } catch (e) {
+            e.toString()
+        }
+        "#;
+    assert_eq!(
+        forward(&mut engine, scenario),
+        "TypeError: FinalizationRegistry: cleanup callback must be a function"
+    );
+
+    let scenario = r#"
+        try {
+            registry.register(target, target);
+        } catch (e) {
+            e.toString()
+        }
+        "#;
+    assert_eq!(
+        forward(&mut engine, scenario),
+        "TypeError: FinalizationRegistry.prototype.register: target and held value must not be the same"
+    );
+
+    let scenario = r#"
+        try {
+            registry.register(1, 'held');
+        } catch (e) {
+            e.toString()
+        }
+        "#;
+    assert_eq!(
+        forward(&mut engine, scenario),
+        "TypeError: FinalizationRegistry.prototype.register: target must be an object"
+    );
+
+    let scenario = r#"
+        try {
+            registry.unregister(1);
+        } catch (e) {
+            e.toString()
+        }
+        "#;
+    assert_eq!(
+        forward(&mut engine, scenario),
+        "TypeError: FinalizationRegistry.prototype.unregister: unregister token must be an object"
+    );
+}
@@ -15,22 +15,41 @@ use crate::{
     builtins::{
         object::{Object, ObjectData, PROTOTYPE},
         property::{Attribute, Property},
-        value::{RcString, ResultValue, Value},
+        value::{JsString, ResultValue, Value},
         Array,
     },
     environment::function_environment_record::BindingStatus,
     environment::lexical_environment::{new_function_environment, Environment},
     exec::{Executable, Interpreter},
-    syntax::ast::node::{FormalParameter, StatementList},
+    syntax::ast::{
+        node::{FormalParameter, LazyStatementList},
+        Position,
+    },
     BoaProfiler,
 };
 use bitflags::bitflags;
 use gc::{unsafe_empty_trace, Finalize, Trace};
-use std::fmt::{self, Debug};
+use std::{
+    fmt::{self, Debug},
+    rc::Rc,
+};
 
 /// _fn(this, arguments, ctx) -> ResultValue_ - The signature of a built-in function
 pub type NativeFunctionData = fn(&Value, &[Value], &mut Interpreter) -> ResultValue;
 
+/// _fn(captures, this, arguments, ctx) -> ResultValue_ - The signature of a built-in function
+/// with captured values
+///
+/// Unlike the values captured by a closure, the captured value is traced by the garbage
+/// collector, so it can safely reference the objects that reference the function.
+pub type CapturingFunctionData = fn(&Value, &Value, &[Value], &mut Interpreter) -> ResultValue;
+
+/// _Fn(this, arguments, ctx) -> ResultValue_ - The signature of a native closure
+///
+/// The values captured by a closure are not traced by the garbage collector, they stay alive for
+/// as long as the function does.
+pub type NativeClosure = dyn Fn(&Value, &[Value], &mut Interpreter) -> ResultValue;
+
 /// Sets the ConstructorKind
 #[derive(Debug, Copy, Clone)]
 pub enum ConstructorKind {
@@ -56,13 +75,17 @@ unsafe impl Trace for ThisMode {
 #[derive(Clone, Finalize)]
 pub enum FunctionBody {
     BuiltIn(NativeFunctionData),
-    Ordinary(StatementList),
+    BuiltInWithCaptures(CapturingFunctionData),
+    Closure(Rc<NativeClosure>),
+    Ordinary(LazyStatementList),
 }
 
 impl Debug for FunctionBody {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::BuiltIn(_) => write!(f, "[native]"),
+            Self::BuiltIn(_) | Self::BuiltInWithCaptures(_) | Self::Closure(_) => {
+                write!(f, "[native]")
+            }
             Self::Ordinary(statements) => write!(f, "{:?}", statements),
         }
     }
@@ -72,6 +95,8 @@ impl PartialEq for FunctionBody {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Self::BuiltIn(a), Self::BuiltIn(b)) => std::ptr::eq(a, b),
+            (Self::BuiltInWithCaptures(a), Self::BuiltInWithCaptures(b)) => std::ptr::eq(a, b),
+            (Self::Closure(a), Self::Closure(b)) => Rc::ptr_eq(a, b),
             (Self::Ordinary(a), Self::Ordinary(b)) => a == b,
             (_, _) => false,
         }
@@ -143,6 +168,13 @@ pub struct Function {
     pub environment: Option<Environment>,
     /// Is it constructable or
     flags: FunctionFlags,
+    /// Where the function was defined, for functions created from source code.
+    position: Option<Position>,
+    /// The name of the script that defined the function, for functions created from source
+    /// code.
+    pub(crate) script: Option<JsString>,
+    /// The value captured by a `FunctionBody::BuiltInWithCaptures` function.
+    captures: Value,
 }
 
 impl Function {
@@ -163,6 +195,9 @@ impl Function {
             params: parameter_list.into(),
             this_mode,
             flags: FunctionFlags::from_parameters(callable, constructable),
+            position: None,
+            script: None,
+            captures: Value::undefined(),
         }
     }
 
@@ -172,7 +207,7 @@ impl Function {
     pub fn ordinary<P>(
         parameter_list: P,
         scope: Environment,
-        body: StatementList,
+        body: LazyStatementList,
         this_mode: ThisMode,
     ) -> Self
     where
@@ -206,6 +241,45 @@ impl Function {
         )
     }
 
+    /// This will create a built-in function object that receives the `captures` value when it
+    /// is called.
+    pub fn builtin_with_captures<P>(
+        parameter_list: P,
+        body: CapturingFunctionData,
+        captures: Value,
+    ) -> Self
+    where
+        P: Into<Box<[FormalParameter]>>,
+    {
+        let mut function = Self::new(
+            parameter_list.into(),
+            None,
+            FunctionBody::BuiltInWithCaptures(body),
+            ThisMode::NonLexical,
+            false,
+            true,
+        );
+        function.captures = captures;
+        function
+    }
+
+    /// This will create a built-in function object from a Rust closure
+    pub fn closure<P, F>(parameter_list: P, body: F) -> Self
+    where
+        P: Into<Box<[FormalParameter]>>,
+        F: Fn(&Value, &[Value], &mut Interpreter) -> ResultValue + 'static,
+    {
+        let _timer = BoaProfiler::global().start_event("function::closure", "function");
+        Self::new(
+            parameter_list.into(),
+            None,
+            FunctionBody::Closure(Rc::new(body)),
+            ThisMode::NonLexical,
+            false,
+            true,
+        )
+    }
+
     /// This will handle calls for both ordinary and built-in functions
     ///
     /// <https://tc39.es/ecma262/#sec-prepareforordinarycall>
@@ -221,7 +295,20 @@ impl Function {
         if self.flags.is_callable() {
             match self.body {
                 FunctionBody::BuiltIn(func) => func(this, args_list, interpreter),
+                FunctionBody::BuiltInWithCaptures(func) => {
+                    func(&self.captures, this, args_list, interpreter)
+                }
+                FunctionBody::Closure(ref func) => func(this, args_list, interpreter),
                 FunctionBody::Ordinary(ref body) => {
+                    interpreter.step()?;
+                    interpreter.check_stack()?;
+                    let body = body.statements().map_err(|err| {
+                        interpreter.terminate_with_syntax_error(&err, self.script.clone())
+                    })?;
+                    if let Some(coverage) = interpreter.coverage_mut() {
+                        coverage.record_function(&body);
+                    }
+
                     // Create a new Function environment who's parent is set to the scope of the function declaration (self.environment)
                     // <https://tc39.es/ecma262/#sec-prepareforordinarycall>
                     let local_env = new_function_environment(
@@ -272,7 +359,7 @@ impl Function {
                 }
             }
         } else {
-            panic!("TypeError: class constructors must be invoked with 'new'");
+            interpreter.throw_type_error("class constructors must be invoked with 'new'")
         }
     }
 
@@ -290,7 +377,24 @@ impl Function {
                     func(this, args_list, interpreter)?;
                     Ok(this.clone())
                 }
+                FunctionBody::BuiltInWithCaptures(func) => {
+                    func(&self.captures, this, args_list, interpreter)?;
+                    Ok(this.clone())
+                }
+                FunctionBody::Closure(ref func) => {
+                    func(this, args_list, interpreter)?;
+                    Ok(this.clone())
+                }
                 FunctionBody::Ordinary(ref body) => {
+                    interpreter.step()?;
+                    interpreter.check_stack()?;
+                    let body = body.statements().map_err(|err| {
+                        interpreter.terminate_with_syntax_error(&err, self.script.clone())
+                    })?;
+                    if let Some(coverage) = interpreter.coverage_mut() {
+                        coverage.record_function(&body);
+                    }
+
                     // Create a new Function environment who's parent is set to the scope of the function declaration (self.environment)
                     // <https://tc39.es/ecma262/#sec-prepareforordinarycall>
                     let local_env = new_function_environment(
@@ -393,6 +497,31 @@ impl Function {
     pub fn is_constructable(&self) -> bool {
         self.flags.is_constructable()
     }
+
+    /// Returns the position in the source code where the function was defined.
+    ///
+    /// Built-in functions, and functions built without a parser, have no position.
+    pub fn position(&self) -> Option<Position> {
+        self.position
+    }
+
+    /// Sets the position in the source code where the function was defined.
+    pub(crate) fn set_position(&mut self, position: Option<Position>) {
+        self.position = position;
+    }
+
+    /// Returns the name of the script where the function was defined, like the path of its
+    /// file, see `Interpreter::set_script_name`.
+    ///
+    /// Built-in functions, and functions built without a parser, have no script.
+    pub fn script(&self) -> Option<&str> {
+        self.script.as_deref()
+    }
+
+    /// Sets the name of the script where the function was defined.
+    pub(crate) fn set_script(&mut self, script: Option<JsString>) {
+        self.script = script;
+    }
 }
 
 impl Debug for Function {
@@ -416,7 +545,7 @@ pub fn create_unmapped_arguments_object(arguments_list: &[Value]) -> Value {
         Attribute::WRITABLE | Attribute::NON_ENUMERABLE | Attribute::PERMANENT,
     );
     // Define length as a property
-    obj.define_own_property("length".to_string(), length);
+    obj.define_own_property("length", length);
     let mut index: usize = 0;
     while index < len {
         let val = arguments_list.get(index).expect("Could not get argument");
@@ -425,8 +554,7 @@ pub fn create_unmapped_arguments_object(arguments_list: &[Value]) -> Value {
             Attribute::WRITABLE | Attribute::ENUMERABLE | Attribute::CONFIGURABLE,
         );
 
-        obj.properties_mut()
-            .insert(RcString::from(index.to_string()), prop);
+        obj.insert_property(index, prop);
         index += 1;
     }
 
@@ -531,6 +659,27 @@ where
         .insert_field(name, Value::from(function));
 }
 
+/// Creates a new member function of an `Object` or `prototype` from a Rust closure.
+///
+/// Unlike `make_builtin_fn`, the function can capture its environment, which lets embedders
+/// expose host state to scripts.
+pub fn make_closure_fn<N, F>(function: F, name: N, parent: &Value, length: usize)
+where
+    N: Into<String>,
+    F: Fn(&Value, &[Value], &mut Interpreter) -> ResultValue + 'static,
+{
+    let name = name.into();
+    let _timer = BoaProfiler::global().start_event(&format!("make_closure_fn: {}", &name), "init");
+
+    let mut function = Object::function(Function::closure(Vec::new(), function), Value::null());
+    function.insert_field("length", Value::from(length));
+
+    parent
+        .as_object_mut()
+        .unwrap()
+        .insert_field(name, Value::from(function));
+}
+
 /// Initialise the `Function` object on the global object.
 #[inline]
 pub fn init(global: &Value) -> (&str, Value) {
@@ -1,4 +1,4 @@
-use crate::exec;
+use crate::{exec, exec::Interpreter, forward, realm::Realm};
 
 #[test]
 fn global_this_exists_on_global_object_and_evaluates_to_an_object() {
@@ -8,3 +8,67 @@ fn global_this_exists_on_global_object_and_evaluates_to_an_object() {
 
     assert_eq!(&exec(scenario), "object");
 }
+
+#[test]
+fn global_this_is_the_global_this_binding() {
+    let scenario = r#"
+        this === globalThis;
+        "#;
+
+    assert_eq!(&exec(scenario), "true");
+}
+
+#[test]
+fn var_declarations_are_properties_of_the_global_object() {
+    let scenario = r#"
+        var a = 10;
+        function f() {}
+        [globalThis.a, typeof globalThis.f, globalThis.hasOwnProperty("a")].join();
+        "#;
+
+    assert_eq!(&exec(scenario), "10,function,true");
+}
+
+#[test]
+fn lexical_declarations_are_not_properties_of_the_global_object() {
+    let scenario = r#"
+        let b = 10;
+        const c = 20;
+        [typeof globalThis.b, typeof globalThis.c].join();
+        "#;
+
+    assert_eq!(&exec(scenario), "undefined,undefined");
+}
+
+#[test]
+fn global_object_properties_are_global_bindings() {
+    let scenario = r#"
+        globalThis.d = 10;
+        d = d + 5;
+        var e = 1;
+        globalThis.e = 2;
+        [d, globalThis.d, e].join();
+        "#;
+
+    assert_eq!(&exec(scenario), "15,15,2");
+}
+
+#[test]
+fn strict_assignment_to_read_only_global_throws() {
+    let mut engine = Interpreter::new(Realm::create());
+    forward(
+        &mut engine,
+        "Object.defineProperty(globalThis, 'g', { value: 1, writable: false });",
+    );
+    assert_eq!(forward(&mut engine, "g = 2; g"), "1");
+
+    engine.set_strict(true);
+    assert_eq!(
+        forward(
+            &mut engine,
+            "var name; try { g = 2; } catch (e) { name = e.name; } name"
+        ),
+        "TypeError"
+    );
+    assert_eq!(forward(&mut engine, "g"), "1");
+}
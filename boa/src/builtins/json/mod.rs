@@ -15,7 +15,7 @@
 
 use crate::builtins::{
     function::make_builtin_fn,
-    property::Property,
+    symbol::set_to_string_tag,
     value::{ResultValue, Value},
 };
 use crate::{exec::Interpreter, BoaProfiler};
@@ -74,14 +74,14 @@ impl Json {
 
         let obj = value.as_object().as_deref().cloned();
         if let Some(obj) = obj {
-            for key in obj.properties().keys() {
-                let v = Self::walk(reviver, ctx, &mut value, Value::from(key.as_str()));
+            for key in obj.own_property_keys() {
+                let v = Self::walk(reviver, ctx, &mut value, Value::from(&key));
                 match v {
                     Ok(v) if !v.is_undefined() => {
-                        value.set_field(Value::from(key.as_str()), v);
+                        value.set_field(key, v);
                     }
                     Ok(_) => {
-                        value.remove_property(key.as_str());
+                        value.remove_property(key);
                     }
                     Err(_v) => {}
                 }
@@ -116,7 +116,10 @@ impl Json {
         };
         let replacer = match args.get(1) {
             Some(replacer) if replacer.is_object() => replacer,
-            _ => return Ok(Value::from(object.to_json(ctx)?.to_string())),
+            _ => {
+                let json = object.to_json(ctx)?;
+                return ctx.new_string(json.to_string());
+            }
         };
 
         let replacer_as_object = replacer
@@ -127,46 +130,36 @@ impl Json {
                 .as_object()
                 .map(|obj| {
                     let object_to_return = Value::new_object(None);
-                    for (key, val) in obj
-                        .properties()
-                        .iter()
-                        .filter_map(|(k, v)| v.value.as_ref().map(|value| (k, value)))
-                    {
+                    for key in obj.own_property_keys() {
+                        let val = match obj.property(&key).and_then(|prop| prop.value.clone()) {
+                            Some(val) => val,
+                            None => continue,
+                        };
                         let this_arg = object.clone();
-                        object_to_return.set_property(
-                            key.to_owned(),
-                            Property::default().value(ctx.call(
-                                replacer,
-                                &this_arg,
-                                &[Value::from(key.clone()), val.clone()],
-                            )?),
-                        );
+                        let replaced = ctx.call(replacer, &this_arg, &[Value::from(&key), val])?;
+                        object_to_return.set_field(key, replaced);
                     }
-                    Ok(Value::from(object_to_return.to_json(ctx)?.to_string()))
+                    let json = object_to_return.to_json(ctx)?;
+                    ctx.new_string(json.to_string())
                 })
                 .ok_or_else(Value::undefined)?
         } else if replacer_as_object.is_array() {
-            let mut obj_to_return =
-                serde_json::Map::with_capacity(replacer_as_object.properties().len() - 1);
-            let fields = replacer_as_object.properties().keys().filter_map(|key| {
-                if key == "length" {
-                    None
-                } else {
-                    Some(replacer.get_field(key.to_owned()))
-                }
-            });
+            let length = i32::from(&replacer.get_field("length")).max(0);
+            let mut obj_to_return = serde_json::Map::with_capacity(length as usize);
+            let fields = (0..length).map(|index| replacer.get_field(index));
             for field in fields {
                 if let Some(value) = object
-                    .get_property(&ctx.to_string(&field)?)
+                    .get_property(ctx.to_string(&field)?)
                     .and_then(|prop| prop.value.as_ref().map(|v| v.to_json(ctx)))
                     .transpose()?
                 {
                     obj_to_return.insert(field.to_string(), value);
                 }
             }
-            Ok(Value::from(JSONValue::Object(obj_to_return).to_string()))
+            ctx.new_string(JSONValue::Object(obj_to_return).to_string())
         } else {
-            Ok(Value::from(object.to_json(ctx)?.to_string()))
+            let json = object.to_json(ctx)?;
+            ctx.new_string(json.to_string())
         }
     }
 
@@ -178,6 +171,7 @@ impl Json {
 
         make_builtin_fn(Self::parse, "parse", &json, 2);
         make_builtin_fn(Self::stringify, "stringify", &json, 3);
+        set_to_string_tag(&json, Self::NAME);
 
         (Self::NAME, json)
     }
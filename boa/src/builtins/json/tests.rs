@@ -331,3 +331,13 @@ fn json_fields_should_be_enumerable() {
     assert_eq!(actual_object, expected);
     assert_eq!(actual_array_index, expected);
 }
+
+#[test]
+fn json_stringify_integral_numbers() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let actual = forward(&mut engine, r#"JSON.stringify([1.5, 2 * 2, -0])"#);
+    let expected = forward(&mut engine, r#"'[1.5,4,0]'"#);
+
+    assert_eq!(actual, expected);
+}
@@ -5,6 +5,7 @@ use crate::{
     builtins::{
         object::{ObjectData, PROTOTYPE},
         property::{Attribute, Property},
+        symbol::set_to_string_tag,
         value::{ResultValue, Value},
     },
     exec::Interpreter,
@@ -252,7 +253,7 @@ impl Map {
                         let mut map = OrderedMap::new();
                         let len = i32::from(&args[0].get_field("length"));
                         for i in 0..len {
-                            let val = &args[0].get_field(i.to_string());
+                            let val = &args[0].get_field(i);
                             let (key, value) = Self::get_key_value(val).ok_or_else(|| {
                                 ctx.construct_type_error(
                                     "iterable for Map should have array-like objects",
@@ -296,6 +297,7 @@ impl Map {
         make_builtin_fn(Self::clear, "clear", &prototype, 0);
         make_builtin_fn(Self::has, "has", &prototype, 1);
         make_builtin_fn(Self::for_each, "forEach", &prototype, 1);
+        set_to_string_tag(&prototype, Self::NAME);
 
         let map_object = make_constructor_fn(
             Self::NAME,
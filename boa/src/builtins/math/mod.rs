@@ -14,6 +14,7 @@
 use crate::{
     builtins::{
         function::make_builtin_fn,
+        symbol::set_to_string_tag,
         value::{ResultValue, Value},
     },
     exec::Interpreter,
@@ -21,9 +22,12 @@ use crate::{
 };
 use std::f64;
 
+mod random;
 #[cfg(test)]
 mod tests;
 
+pub(crate) use random::SeededRandom;
+
 /// Javascript `Math` object.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub(crate) struct Math;
@@ -485,14 +489,17 @@ impl Math {
 
     /// Generate a random floating-point number between `0` and `1`.
     ///
+    /// The numbers are reproducible if the interpreter has a seed, see
+    /// `Interpreter::set_random_seed`.
+    ///
     /// More information:
     ///  - [ECMAScript reference][spec]
     ///  - [MDN documentation][mdn]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-math.random
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/random
-    pub(crate) fn random(_: &Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-        Ok(rand::random::<f64>().into())
+    pub(crate) fn random(_: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        Ok(ctx.random().into())
     }
 
     /// Round a number to the nearest integer.
@@ -691,6 +698,7 @@ impl Math {
         make_builtin_fn(Self::tan, "tan", &math, 1);
         make_builtin_fn(Self::tanh, "tanh", &math, 1);
         make_builtin_fn(Self::trunc, "trunc", &math, 1);
+        set_to_string_tag(&math, Self::NAME);
 
         math
     }
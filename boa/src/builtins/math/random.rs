@@ -0,0 +1,40 @@
+//! The seeded pseudo-random number generator of `Math.random`, see
+//! `Interpreter::set_random_seed`.
+//!
+//! The generator is xorshift128+, like in V8 and SpiderMonkey, and its state is initialized
+//! from the seed with SplitMix64. Both are written out here rather than taken from the `rand`
+//! crate, whose generators may change between versions, so that a seed gives the same numbers
+//! with every version of the engine.
+
+/// A xorshift128+ generator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SeededRandom {
+    state: [u64; 2],
+}
+
+impl SeededRandom {
+    /// Creates a generator from a seed.
+    pub(crate) fn new(seed: u64) -> Self {
+        let mut seed = seed;
+        let mut split_mix = || {
+            seed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+        Self {
+            state: [split_mix(), split_mix()],
+        }
+    }
+
+    /// Returns the next number, between `0` included and `1` excluded.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        let [mut s1, s0] = self.state;
+        let result = s0.wrapping_add(s1);
+        s1 ^= s1 << 23;
+        self.state = [s0, s1 ^ s0 ^ (s1 >> 17) ^ (s0 >> 26)];
+        // The 53 high bits fill the mantissa of the number.
+        (result >> 11) as f64 / (1_u64 << 53) as f64
+    }
+}
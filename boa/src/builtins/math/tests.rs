@@ -594,6 +594,30 @@ fn pow() {
     assert_eq!(d.to_number(), 0.020_408_163_265_306_12);
 }
 
+#[test]
+fn random_is_reproducible_with_a_seed() {
+    let numbers = |seed| {
+        let realm = Realm::create();
+        let mut engine = Interpreter::new(realm);
+        engine.set_random_seed(Some(seed));
+        (0..100)
+            .map(|_| {
+                forward_val(&mut engine, "Math.random()")
+                    .unwrap()
+                    .to_number()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let first = numbers(42);
+    assert!(first.iter().all(|&n| (0.0..1.0).contains(&n)));
+    assert_eq!(first, numbers(42));
+    assert_ne!(first, numbers(43));
+    // The numbers must not change between versions of the engine.
+    assert_eq!(first[0], super::SeededRandom::new(42).next_f64());
+    assert_eq!(first[0].to_bits(), 0x3FEC_D8E2_AB3C_4A4B);
+}
+
 #[test]
 fn round() {
     let realm = Realm::create();
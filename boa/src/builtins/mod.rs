@@ -5,6 +5,7 @@ pub mod bigint;
 pub mod boolean;
 pub mod console;
 pub mod error;
+pub mod finalization_registry;
 pub mod function;
 pub mod global_this;
 pub mod infinity;
@@ -14,19 +15,25 @@ pub mod math;
 pub mod nan;
 pub mod number;
 pub mod object;
+pub mod promise;
 pub mod property;
+pub mod queue_microtask;
 pub mod regexp;
 pub mod string;
+pub mod structured_clone;
 pub mod symbol;
 pub mod undefined;
+pub mod uri;
 pub mod value;
+pub mod weak_ref;
 
 pub(crate) use self::{
     array::Array,
     bigint::BigInt,
     boolean::Boolean,
     console::Console,
-    error::{Error, RangeError, ReferenceError, SyntaxError, TypeError},
+    error::{Error, RangeError, ReferenceError, SyntaxError, TypeError, UriError},
+    finalization_registry::FinalizationRegistry,
     global_this::GlobalThis,
     infinity::Infinity,
     json::Json,
@@ -34,11 +41,16 @@ pub(crate) use self::{
     math::Math,
     nan::NaN,
     number::Number,
+    promise::Promise,
+    queue_microtask::QueueMicrotask,
     regexp::RegExp,
     string::String,
+    structured_clone::StructuredClone,
     symbol::Symbol,
     undefined::Undefined,
+    uri::Uri,
     value::{ResultValue, Value},
+    weak_ref::WeakRef,
 };
 
 /// Initializes builtin objects and functions
@@ -55,16 +67,20 @@ pub fn init(global: &Value) {
         Map::init,
         Math::init,
         Number::init,
+        Promise::init,
         RegExp::init,
         String::init,
         Symbol::init,
         Console::init,
+        WeakRef::init,
+        FinalizationRegistry::init,
         // Global error types.
         Error::init,
         RangeError::init,
         ReferenceError::init,
         TypeError::init,
         SyntaxError::init,
+        UriError::init,
         // Global properties.
         NaN::init,
         Infinity::init,
@@ -78,6 +94,10 @@ pub fn init(global: &Value) {
                 let (name, value) = init(global);
                 global_object.borrow_mut().insert_field(name, value);
             }
+            // Global functions.
+            Uri::init(global);
+            StructuredClone::init(global);
+            QueueMicrotask::init(global);
         }
         _ => unreachable!("expect global object"),
     }
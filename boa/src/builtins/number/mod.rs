@@ -191,8 +191,10 @@ impl Number {
     ///
     /// The `toLocaleString()` method returns a string with a language-sensitive representation of this number.
     ///
-    /// Note that while this technically conforms to the Ecma standard, it does no actual
-    /// internationalization logic.
+    /// The number is formatted for the `locales` argument if it is a string, and for the locale
+    /// of the interpreter otherwise, with the digit grouping and decimal separators of the
+    /// language and at most 3 fraction digits. Without any locale it is formatted like
+    /// `toString`. The `options` argument is ignored.
     ///
     /// More information:
     ///  - [ECMAScript reference][spec]
@@ -203,14 +205,72 @@ impl Number {
     #[allow(clippy::wrong_self_convention)]
     pub(crate) fn to_locale_string(
         this: &Value,
-        _args: &[Value],
+        args: &[Value],
         ctx: &mut Interpreter,
     ) -> ResultValue {
         let this_num = Self::this_number_value(this, ctx)?;
-        let this_str_num = format!("{}", this_num);
+        let locale = match args.get(0) {
+            Some(Value::String(ref locale)) => Some(locale.to_string()),
+            _ => ctx.locale().map(str::to_owned),
+        };
+        let this_str_num = match locale {
+            Some(ref locale) => Self::to_locale_native_string(this_num, locale),
+            None => format!("{}", this_num),
+        };
         Ok(Value::from(this_str_num))
     }
 
+    /// Formats a number for a locale, with its digit grouping and decimal separators.
+    fn to_locale_native_string(number: f64, locale: &str) -> String {
+        if !number.is_finite() || number.abs() >= 1e21 {
+            return Self::to_native_string(number);
+        }
+
+        let (group, decimal) = Self::locale_separators(locale);
+        let digits = format!("{:.3}", number.abs());
+        let digits = digits.trim_end_matches('0').trim_end_matches('.');
+        let (integer, fraction) = match digits.find('.') {
+            Some(index) => (&digits[..index], &digits[index + 1..]),
+            None => (digits, ""),
+        };
+
+        let mut result = String::new();
+        if number.is_sign_negative() && digits != "0" {
+            result.push('-');
+        }
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                result.push(group);
+            }
+            result.push(digit);
+        }
+        if !fraction.is_empty() {
+            result.push(decimal);
+            result.push_str(fraction);
+        }
+        result
+    }
+
+    /// Gets the digit grouping and decimal separators of a locale.
+    ///
+    /// Languages that are not known use the separators of English.
+    fn locale_separators(locale: &str) -> (char, char) {
+        let locale = locale.to_ascii_lowercase().replace('_', "-");
+        if locale == "de-ch" || locale.starts_with("de-ch-") {
+            return ('’', '.');
+        }
+        let language = locale.split('-').next().unwrap_or_default();
+        match language {
+            "de" | "es" | "it" | "nl" | "pt" | "id" | "da" | "tr" | "el" | "ro" | "hr" | "sl" => {
+                ('.', ',')
+            }
+            "fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "nb" | "no" | "fi" | "uk" | "hu" | "bg" => {
+                ('\u{a0}', ',')
+            }
+            _ => (',', '.'),
+        }
+    }
+
     /// `Number.prototype.toPrecision( [precision] )`
     ///
     /// The `toPrecision()` method returns a string representing the Number object to the specified precision.
@@ -130,6 +130,40 @@ fn to_locale_string() {
     assert_eq!(neg_locale, String::from("-25"));
 }
 
+#[test]
+fn to_locale_string_with_locale() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.set_locale(Some("en-US"));
+
+    assert_eq!(
+        forward(&mut engine, "(1234567.891).toLocaleString()"),
+        "1,234,567.891"
+    );
+    assert_eq!(
+        forward(&mut engine, "(-1234.56789).toLocaleString()"),
+        "-1,234.568"
+    );
+    assert_eq!(forward(&mut engine, "(999).toLocaleString()"), "999");
+    assert_eq!(forward(&mut engine, "(0.0001).toLocaleString()"), "0");
+    assert_eq!(
+        forward(&mut engine, "(1234.5).toLocaleString('de-DE')"),
+        "1.234,5"
+    );
+    assert_eq!(
+        forward(&mut engine, "(1234.5).toLocaleString('de-CH')"),
+        "1’234.5"
+    );
+    assert_eq!(
+        forward(&mut engine, "(1234.5).toLocaleString('fr')"),
+        "1\u{a0}234,5"
+    );
+    assert_eq!(forward(&mut engine, "(1 / 0).toLocaleString()"), "Infinity");
+
+    engine.set_locale(None::<String>);
+    assert_eq!(forward(&mut engine, "(1234.5).toLocaleString()"), "1234.5");
+}
+
 #[test]
 #[ignore]
 fn to_precision() {
@@ -2,9 +2,14 @@
 //!
 //! The `GcObject` is a garbage collected Object.
 
-use super::Object;
+use super::{InternalState, Object};
 use gc::{Finalize, Gc, GcCell, GcCellRef, GcCellRefMut, Trace};
-use std::fmt::{self, Display};
+use std::{
+    any::Any,
+    fmt::{self, Debug, Display},
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
 
 /// Garbage collected `Object`.
 #[derive(Debug, Trace, Finalize, Clone)]
@@ -36,6 +41,77 @@ impl GcObject {
         self.0.try_borrow_mut().map_err(|_| BorrowMutError)
     }
 
+    /// Checks if the object is callable.
+    #[inline]
+    pub fn is_callable(&self) -> bool {
+        self.borrow().is_callable()
+    }
+
+    /// Checks if the native data of the object, its internal state, has the type `T`.
+    #[inline]
+    pub fn is<T: Any + InternalState>(&self) -> bool {
+        matches!(self.borrow().state(), Some(state) if state.downcast_ref::<T>().is_some())
+    }
+
+    /// Borrows the native data of the object, if it has the type `T`.
+    ///
+    /// This is how host functions get the Rust value of their arguments, like the instances of
+    /// a `Class`:
+    ///
+    /// ```
+    /// use boa::{builtins::object::InternalState, builtins::value::Value, Context};
+    ///
+    /// #[derive(Debug)]
+    /// struct Handle(u32);
+    ///
+    /// #[derive(Debug)]
+    /// struct Other;
+    ///
+    /// impl InternalState for Handle {}
+    /// impl InternalState for Other {}
+    ///
+    /// let mut context = Context::new();
+    /// let handle = Value::new_object(Some(context.global_object()));
+    /// handle.set_internal_state(Handle(7));
+    ///
+    /// let object = handle.as_gc_object().unwrap();
+    /// assert_eq!(object.downcast_ref::<Handle>().unwrap().0, 7);
+    /// assert!(object.downcast_ref::<Other>().is_none());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the object is already mutably borrowed.
+    #[inline]
+    pub fn downcast_ref<T: Any + InternalState>(&self) -> Option<StateRef<'_, T>> {
+        if self.is::<T>() {
+            Some(StateRef {
+                object: self.borrow(),
+                state: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Mutably borrows the native data of the object, if it has the type `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the object is already borrowed, or if its internal state is shared with
+    /// another object.
+    #[inline]
+    pub fn downcast_mut<T: Any + InternalState>(&self) -> Option<StateRefMut<'_, T>> {
+        if self.is::<T>() {
+            Some(StateRefMut {
+                object: self.borrow_mut(),
+                state: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
     /// Checks if the garbage collected memory is the same.
     #[inline]
     pub fn equals(lhs: &Self, rhs: &Self) -> bool {
@@ -50,6 +126,71 @@ impl AsRef<GcCell<Object>> for GcObject {
     }
 }
 
+/// A reference to the native data of an object, returned by
+/// [`GcObject::downcast_ref`](struct.GcObject.html#method.downcast_ref).
+pub struct StateRef<'a, T> {
+    object: GcCellRef<'a, Object>,
+    state: PhantomData<&'a T>,
+}
+
+impl<T: Any + InternalState> Deref for StateRef<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.object
+            .state()
+            .as_ref()
+            .and_then(|state| state.downcast_ref())
+            .expect("the state type was checked")
+    }
+}
+
+impl<T: Any + InternalState> Debug for StateRef<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+/// A mutable reference to the native data of an object, returned by
+/// [`GcObject::downcast_mut`](struct.GcObject.html#method.downcast_mut).
+pub struct StateRefMut<'a, T> {
+    object: GcCellRefMut<'a, Object>,
+    state: PhantomData<&'a mut T>,
+}
+
+impl<T: Any + InternalState> Deref for StateRefMut<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.object
+            .state()
+            .as_ref()
+            .and_then(|state| state.downcast_ref())
+            .expect("the state type was checked")
+    }
+}
+
+impl<T: Any + InternalState> DerefMut for StateRefMut<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        self.object
+            .state_mut()
+            .as_mut()
+            .and_then(|state| state.downcast_mut())
+            .expect("the state type was checked")
+    }
+}
+
+impl<T: Any + InternalState> Debug for StateRefMut<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
 /// An error returned by [`GcObject::try_borrow`](struct.GcObject.html#method.try_borrow).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct BorrowError;
@@ -7,8 +7,8 @@
 
 use crate::builtins::{
     object::{Object, PROTOTYPE},
-    property::{Attribute, Property},
-    value::{same_value, RcString, Value},
+    property::{Attribute, Property, PropertyKey},
+    value::{same_value, Value},
 };
 use crate::BoaProfiler;
 
@@ -19,16 +19,15 @@ impl Object {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-hasproperty-p
-    pub fn has_property(&self, val: &Value) -> bool {
-        debug_assert!(Property::is_property_key(val));
-        let prop = self.get_own_property(val);
-        if prop.value.is_none() {
+    pub fn has_property(&self, key: &PropertyKey) -> bool {
+        let prop = self.get_own_property(key);
+        if prop.is_none() {
             let parent: Value = self.get_prototype_of();
             if !parent.is_null() {
                 // the parent value variant should be an object
                 // In the unlikely event it isn't return false
                 return match parent {
-                    Value::Object(ref obj) => obj.borrow().has_property(val),
+                    Value::Object(ref obj) => obj.borrow().has_property(key),
                     _ => false,
                 };
             }
@@ -61,20 +60,34 @@ impl Object {
         true
     }
 
+    /// Disable extensibility, and make the own properties non-configurable and the own data
+    /// properties read-only, like `Object.freeze`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-setintegritylevel
+    pub fn freeze(&mut self) -> bool {
+        self.prevent_extensions();
+        for key in self.own_property_keys() {
+            let mut property = self.get_own_property(&key);
+            property.set_configurable(false);
+            if property.is_data_descriptor() {
+                property.attribute.set_writable(false);
+            }
+            self.insert_property(key, property);
+        }
+        true
+    }
+
     /// Delete property.
-    pub fn delete(&mut self, prop_key: &Value) -> bool {
-        debug_assert!(Property::is_property_key(prop_key));
-        let desc = self.get_own_property(prop_key);
-        if desc
-            .value
-            .clone()
-            .expect("unable to get value")
-            .is_undefined()
-        {
+    pub fn delete(&mut self, key: &PropertyKey) -> bool {
+        let desc = self.get_own_property(key);
+        if desc.is_none() {
             return true;
         }
         if desc.configurable_or(false) {
-            self.remove_property(&prop_key.to_string());
+            self.remove_property(key);
             return true;
         }
 
@@ -82,16 +95,9 @@ impl Object {
     }
 
     // [[Get]]
-    pub fn get(&self, val: &Value) -> Value {
-        debug_assert!(Property::is_property_key(val));
-        let desc = self.get_own_property(val);
-        if desc.value.clone().is_none()
-            || desc
-                .value
-                .clone()
-                .expect("Failed to get object")
-                .is_undefined()
-        {
+    pub fn get(&self, key: &PropertyKey) -> Value {
+        let desc = self.get_own_property(key);
+        if desc.is_none() {
             // parent will either be null or an Object
             let parent = self.get_prototype_of();
             if parent.is_null() {
@@ -100,7 +106,7 @@ impl Object {
 
             let parent_obj = Object::from(&parent).expect("Failed to get object");
 
-            return parent_obj.get(val);
+            return parent_obj.get(key);
         }
 
         if desc.is_data_descriptor() {
@@ -118,13 +124,11 @@ impl Object {
 
     /// [[Set]]
     /// <https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-set-p-v-receiver>
-    pub fn set(&mut self, field: Value, val: Value) -> bool {
+    pub fn set(&mut self, key: PropertyKey, val: Value) -> bool {
         let _timer = BoaProfiler::global().start_event("Object::set", "object");
-        // [1]
-        debug_assert!(Property::is_property_key(&field));
 
         // Fetch property key
-        let mut own_desc = self.get_own_property(&field);
+        let mut own_desc = self.get_own_property(&key);
         // [2]
         if own_desc.is_none() {
             let parent = self.get_prototype_of();
@@ -144,116 +148,154 @@ impl Object {
 
             // Change value on the current descriptor
             own_desc = own_desc.value(val);
-            return self.define_own_property(field.to_string(), own_desc);
+            return self.define_own_property(key, own_desc);
         }
         // [4]
+        // Calling a setter requires an interpreter, see `Interpreter::set_field`,
+        // so an accessor property can't be set from here.
         debug_assert!(own_desc.is_accessor_descriptor());
-        match own_desc.set {
-            None => false,
-            Some(_) => {
-                unimplemented!();
-            }
-        }
+        false
     }
 
     /// Define an own property.
     ///
+    /// Fields that are absent from `desc` are left unchanged on an existing property,
+    /// and take their default values on a new one.
+    ///
     /// More information:
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-defineownproperty-p-desc
-    pub fn define_own_property(&mut self, property_key: String, desc: Property) -> bool {
+    pub fn define_own_property<K>(&mut self, key: K, desc: Property) -> bool
+    where
+        K: Into<PropertyKey>,
+    {
         let _timer = BoaProfiler::global().start_event("Object::define_own_property", "object");
+        let key = key.into();
 
-        let mut current = self.get_own_property(&Value::from(property_key.to_string()));
+        let mut current = self.get_own_property(&key);
         let extensible = self.is_extensible();
-
         // https://tc39.es/ecma262/#sec-validateandapplypropertydescriptor
-        // There currently isn't a property, lets create a new one
-        if current.value.is_none() || current.value.as_ref().expect("failed").is_undefined() {
+        // 2. There currently isn't a property, lets create a new one
+        if current.is_none() {
             if !extensible {
                 return false;
             }
 
-            self.insert_property(property_key, desc);
+            let mut property = Property::empty();
+            if desc.is_accessor_descriptor() {
+                property.get = Some(desc.get.clone().unwrap_or_else(Value::undefined));
+                property.set = Some(desc.set.clone().unwrap_or_else(Value::undefined));
+            } else {
+                property.value = Some(desc.value.clone().unwrap_or_else(Value::undefined));
+                property.attribute.set_writable(desc.writable_or(false));
+            }
+            property.attribute.set_enumerable(desc.enumerable_or(false));
+            property
+                .attribute
+                .set_configurable(desc.configurable_or(false));
+
+            self.insert_property(key, property);
             return true;
         }
-        // If every field is absent we don't need to set anything
+
+        // 3. If every field is absent we don't need to set anything
         if desc.is_none() {
             return true;
         }
 
-        // 4
+        // 4.
         if !current.configurable_or(false) {
             if desc.configurable_or(false) {
                 return false;
             }
 
-            if desc.enumerable_or(false) != current.enumerable_or(false) {
+            if desc.attribute.has_enumerable() && desc.enumerable() != current.enumerable_or(false)
+            {
                 return false;
             }
         }
 
-        // 5
+        // 5.
         if desc.is_generic_descriptor() {
-            // 6
+            // No further validation is required.
+            // 6.
         } else if current.is_data_descriptor() != desc.is_data_descriptor() {
-            // a
-            if !current.configurable() {
+            // a.
+            if !current.configurable_or(false) {
                 return false;
             }
-            // b
+            // b. Convert to an accessor property, preserving [[Configurable]] and [[Enumerable]].
             if current.is_data_descriptor() {
-                // Convert to accessor
                 current.value = None;
                 current.attribute.remove(Attribute::WRITABLE);
+                current.get = Some(Value::undefined());
+                current.set = Some(Value::undefined());
+            // c. Convert to a data property, preserving [[Configurable]] and [[Enumerable]].
             } else {
-                // c
-                // convert to data
                 current.get = None;
                 current.set = None;
+                current.value = Some(Value::undefined());
+                current.attribute.set_writable(false);
             }
-
-            self.insert_property(property_key.clone(), current);
-        // 7
+        // 7.
         } else if current.is_data_descriptor() && desc.is_data_descriptor() {
-            // a
-            if !current.configurable() && !current.writable() {
+            // a.
+            if !current.configurable_or(false) && !current.writable_or(false) {
                 if desc.writable_or(false) {
                     return false;
                 }
 
-                if desc.value.is_some()
-                    && !same_value(
-                        &desc.value.clone().unwrap(),
-                        &current.value.clone().unwrap(),
-                    )
-                {
-                    return false;
+                if let (Some(desc_value), Some(current_value)) = (&desc.value, &current.value) {
+                    if !same_value(desc_value, current_value) {
+                        return false;
+                    }
                 }
 
                 return true;
             }
-        // 8
-        } else {
-            if !current.configurable() {
-                if desc.set.is_some()
-                    && !same_value(&desc.set.clone().unwrap(), &current.set.clone().unwrap())
-                {
+        // 8.
+        } else if !current.configurable_or(false) {
+            if let (Some(desc_set), Some(current_set)) = (&desc.set, &current.set) {
+                if !same_value(desc_set, current_set) {
                     return false;
                 }
+            }
 
-                if desc.get.is_some()
-                    && !same_value(&desc.get.clone().unwrap(), &current.get.clone().unwrap())
-                {
+            if let (Some(desc_get), Some(current_get)) = (&desc.get, &current.get) {
+                if !same_value(desc_get, current_get) {
                     return false;
                 }
             }
 
             return true;
         }
-        // 9
-        self.insert_property(property_key, desc);
+
+        // 9. Set the fields that are present in the descriptor.
+        if desc.value.is_some() {
+            current.value = desc.value.clone();
+        }
+        if desc.get.is_some() {
+            current.get = desc.get.clone();
+        }
+        if desc.set.is_some() {
+            current.set = desc.set.clone();
+        }
+        if desc.attribute.has_writable() {
+            current.attribute.set_writable(desc.attribute.writable());
+        }
+        if desc.attribute.has_enumerable() {
+            current
+                .attribute
+                .set_enumerable(desc.attribute.enumerable());
+        }
+        if desc.attribute.has_configurable() {
+            current
+                .attribute
+                .set_configurable(desc.attribute.configurable());
+        }
+
+        self.insert_property(key, current);
         true
     }
 
@@ -265,42 +307,48 @@ impl Object {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-getownproperty-p
-    pub fn get_own_property(&self, prop: &Value) -> Property {
+    pub fn get_own_property(&self, key: &PropertyKey) -> Property {
         let _timer = BoaProfiler::global().start_event("Object::get_own_property", "object");
+        tracing::trace!(%key, "get own property");
 
-        debug_assert!(Property::is_property_key(prop));
-        // Prop could either be a String or Symbol
-        match *prop {
-            Value::String(ref st) => self.properties().get(st).map_or_else(Property::empty, |v| {
-                let mut d = Property::empty();
-                if v.is_data_descriptor() {
-                    d.value = v.value.clone();
-                } else {
-                    debug_assert!(v.is_accessor_descriptor());
-                    d.get = v.get.clone();
-                    d.set = v.set.clone();
-                }
-                d.attribute = v.attribute;
-                d
-            }),
-            Value::Symbol(ref symbol) => {
-                self.symbol_properties()
-                    .get(&symbol.hash())
-                    .map_or_else(Property::empty, |v| {
-                        let mut d = Property::empty();
-                        if v.is_data_descriptor() {
-                            d.value = v.value.clone();
-                        } else {
-                            debug_assert!(v.is_accessor_descriptor());
-                            d.get = v.get.clone();
-                            d.set = v.set.clone();
-                        }
-                        d.attribute = v.attribute;
-                        d
-                    })
+        self.property(key).map_or_else(Property::empty, |v| {
+            let mut d = Property::empty();
+            if v.is_data_descriptor() {
+                d.value = v.value.clone();
+            } else {
+                debug_assert!(v.is_accessor_descriptor());
+                d.get = v.get.clone();
+                d.set = v.set.clone();
             }
-            _ => unreachable!("the field can only be of type string or symbol"),
-        }
+            d.attribute = v.attribute;
+            d
+        })
+    }
+
+    /// Returns the keys of the own properties of the object.
+    ///
+    /// Integer indices come first in ascending order, followed by the string keys
+    /// and then the symbol keys.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-ordinaryownpropertykeys
+    pub fn own_property_keys(&self) -> Vec<PropertyKey> {
+        let mut indices: Vec<u32> = self.indexed_properties.keys().copied().collect();
+        indices.sort_unstable();
+
+        indices
+            .into_iter()
+            .map(PropertyKey::Index)
+            .chain(self.properties.keys().cloned().map(PropertyKey::String))
+            .chain(
+                self.symbol_properties
+                    .keys()
+                    .cloned()
+                    .map(PropertyKey::Symbol),
+            )
+            .collect()
     }
 
     /// `Object.setPropertyOf(obj, prototype)`
@@ -367,19 +415,50 @@ impl Object {
         self.internal_slots.insert(name.to_string(), val);
     }
 
+    /// Helper function to get a reference to an own property.
+    #[inline]
+    pub fn property(&self, key: &PropertyKey) -> Option<&Property> {
+        match key {
+            PropertyKey::Index(index) => self.indexed_properties.get(index),
+            PropertyKey::String(ref string) => self.properties.get(string),
+            PropertyKey::Symbol(ref symbol) => self.symbol_properties.get(symbol),
+        }
+    }
+
+    /// Helper function to get a mutable reference to an own property.
+    #[inline]
+    pub fn property_mut(&mut self, key: &PropertyKey) -> Option<&mut Property> {
+        match key {
+            PropertyKey::Index(index) => self.indexed_properties.get_mut(index),
+            PropertyKey::String(ref string) => self.properties.get_mut(string),
+            PropertyKey::Symbol(ref symbol) => self.symbol_properties.get_mut(symbol),
+        }
+    }
+
     /// Helper function for property insertion.
+    ///
+    /// If a property was already in the object with the same key then a `Some` is returned
+    /// with that property, otherwise `None` is returned.
     #[inline]
-    pub(crate) fn insert_property<N>(&mut self, name: N, p: Property)
+    pub(crate) fn insert_property<K>(&mut self, key: K, p: Property) -> Option<Property>
     where
-        N: Into<RcString>,
+        K: Into<PropertyKey>,
     {
-        self.properties.insert(name.into(), p);
+        match key.into() {
+            PropertyKey::Index(index) => self.indexed_properties.insert(index, p),
+            PropertyKey::String(string) => self.properties.insert(string, p),
+            PropertyKey::Symbol(symbol) => self.symbol_properties.insert(symbol, p),
+        }
     }
 
     /// Helper function for property removal.
     #[inline]
-    pub(crate) fn remove_property(&mut self, name: &str) {
-        self.properties.remove(name);
+    pub(crate) fn remove_property(&mut self, key: &PropertyKey) -> Option<Property> {
+        match key {
+            PropertyKey::Index(index) => self.indexed_properties.remove(index),
+            PropertyKey::String(ref string) => self.properties.remove(string),
+            PropertyKey::Symbol(ref symbol) => self.symbol_properties.remove(symbol),
+        }
     }
 
     /// Inserts a field in the object `properties` without checking if it's writable.
@@ -387,12 +466,12 @@ impl Object {
     /// If a field was already in the object with the same name that a `Some` is returned
     /// with that field, otherwise None is retuned.
     #[inline]
-    pub(crate) fn insert_field<N>(&mut self, name: N, value: Value) -> Option<Property>
+    pub(crate) fn insert_field<K>(&mut self, key: K, value: Value) -> Option<Property>
     where
-        N: Into<RcString>,
+        K: Into<PropertyKey>,
     {
-        self.properties.insert(
-            name.into(),
+        self.insert_property(
+            key,
             Property::data_descriptor(
                 value,
                 Attribute::WRITABLE | Attribute::ENUMERABLE | Attribute::CONFIGURABLE,
@@ -405,7 +484,10 @@ impl Object {
     /// if it exist `Some` is returned with a reference to that fields value.
     /// Otherwise `None` is retuned.
     #[inline]
-    pub fn get_field(&self, name: &str) -> Option<&Value> {
-        self.properties.get(name).and_then(|x| x.value.as_ref())
+    pub fn get_field<K>(&self, key: K) -> Option<&Value>
+    where
+        K: Into<PropertyKey>,
+    {
+        self.property(&key.into()).and_then(|x| x.value.as_ref())
     }
 }
@@ -17,9 +17,10 @@ use crate::{
     builtins::{
         function::Function,
         map::ordered_map::OrderedMap,
-        property::Property,
-        value::{RcBigInt, RcString, RcSymbol, ResultValue, Value},
-        BigInt,
+        property::{Property, PropertyKey},
+        symbol::WellKnownSymbol,
+        value::{JsString, RcBigInt, RcSymbol, ResultValue, Value},
+        BigInt, RegExp,
     },
     exec::Interpreter,
     BoaProfiler,
@@ -54,10 +55,12 @@ pub struct Object {
     pub data: ObjectData,
     /// Internal Slots
     internal_slots: FxHashMap<String, Value>,
+    /// Integer indexed properties
+    indexed_properties: FxHashMap<u32, Property>,
     /// Properties
-    properties: FxHashMap<RcString, Property>,
+    properties: FxHashMap<JsString, Property>,
     /// Symbol Properties
-    symbol_properties: FxHashMap<u32, Property>,
+    symbol_properties: FxHashMap<RcSymbol, Property>,
     /// Instance prototype `__proto__`.
     prototype: Value,
     /// Some rust object that stores internal state
@@ -74,7 +77,7 @@ pub enum ObjectData {
     BigInt(RcBigInt),
     Boolean(bool),
     Function(Function),
-    String(RcString),
+    String(JsString),
     Number(f64),
     Symbol(RcSymbol),
     Error,
@@ -109,6 +112,7 @@ impl Default for Object {
         Self {
             data: ObjectData::Ordinary,
             internal_slots: FxHashMap::default(),
+            indexed_properties: FxHashMap::default(),
             properties: FxHashMap::default(),
             symbol_properties: FxHashMap::default(),
             prototype: Value::null(),
@@ -131,6 +135,7 @@ impl Object {
         Self {
             data: ObjectData::Function(function),
             internal_slots: FxHashMap::default(),
+            indexed_properties: FxHashMap::default(),
             properties: FxHashMap::default(),
             symbol_properties: FxHashMap::default(),
             prototype,
@@ -157,6 +162,7 @@ impl Object {
         Self {
             data: ObjectData::Boolean(value),
             internal_slots: FxHashMap::default(),
+            indexed_properties: FxHashMap::default(),
             properties: FxHashMap::default(),
             symbol_properties: FxHashMap::default(),
             prototype: Value::null(),
@@ -170,6 +176,7 @@ impl Object {
         Self {
             data: ObjectData::Number(value),
             internal_slots: FxHashMap::default(),
+            indexed_properties: FxHashMap::default(),
             properties: FxHashMap::default(),
             symbol_properties: FxHashMap::default(),
             prototype: Value::null(),
@@ -181,11 +188,12 @@ impl Object {
     /// Return a new `String` object whose `[[StringData]]` internal slot is set to argument.
     pub fn string<S>(value: S) -> Self
     where
-        S: Into<RcString>,
+        S: Into<JsString>,
     {
         Self {
             data: ObjectData::String(value.into()),
             internal_slots: FxHashMap::default(),
+            indexed_properties: FxHashMap::default(),
             properties: FxHashMap::default(),
             symbol_properties: FxHashMap::default(),
             prototype: Value::null(),
@@ -199,6 +207,7 @@ impl Object {
         Self {
             data: ObjectData::BigInt(value),
             internal_slots: FxHashMap::default(),
+            indexed_properties: FxHashMap::default(),
             properties: FxHashMap::default(),
             symbol_properties: FxHashMap::default(),
             prototype: Value::null(),
@@ -290,7 +299,7 @@ impl Object {
     }
 
     #[inline]
-    pub fn as_string(&self) -> Option<RcString> {
+    pub fn as_string(&self) -> Option<JsString> {
         match self.data {
             ObjectData::String(ref string) => Some(string.clone()),
             _ => None,
@@ -398,22 +407,36 @@ impl Object {
     }
 
     #[inline]
-    pub fn properties(&self) -> &FxHashMap<RcString, Property> {
+    pub fn indexed_properties(&self) -> &FxHashMap<u32, Property> {
+        &self.indexed_properties
+    }
+
+    #[inline]
+    pub fn indexed_properties_mut(&mut self) -> &mut FxHashMap<u32, Property> {
+        &mut self.indexed_properties
+    }
+
+    #[inline]
+    #[allow(clippy::mutable_key_type)]
+    pub fn properties(&self) -> &FxHashMap<JsString, Property> {
         &self.properties
     }
 
     #[inline]
-    pub fn properties_mut(&mut self) -> &mut FxHashMap<RcString, Property> {
+    #[allow(clippy::mutable_key_type)]
+    pub fn properties_mut(&mut self) -> &mut FxHashMap<JsString, Property> {
         &mut self.properties
     }
 
     #[inline]
-    pub fn symbol_properties(&self) -> &FxHashMap<u32, Property> {
+    #[allow(clippy::mutable_key_type)]
+    pub fn symbol_properties(&self) -> &FxHashMap<RcSymbol, Property> {
         &self.symbol_properties
     }
 
     #[inline]
-    pub fn symbol_properties_mut(&mut self) -> &mut FxHashMap<u32, Property> {
+    #[allow(clippy::mutable_key_type)]
+    pub fn symbol_properties_mut(&mut self) -> &mut FxHashMap<RcSymbol, Property> {
         &mut self.symbol_properties
     }
 
@@ -505,18 +528,176 @@ pub fn set_prototype_of(_: &Value, args: &[Value], _: &mut Interpreter) -> Resul
     Ok(obj)
 }
 
-/// Define a property in an object
-pub fn define_property(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    let obj = args.get(0).expect("Cannot get object");
-    let prop = ctx.to_string(args.get(1).expect("Cannot get object"))?;
-    let desc = Property::from(args.get(2).expect("Cannot get object"));
-    obj.set_property(prop, desc);
+/// `get Object.prototype.__proto__`
+///
+/// The `__proto__` accessor returns the prototype of an object. It is an Annex B legacy
+/// feature, see `annex_b`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-get-object.prototype.__proto__
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/proto
+pub(crate) fn get_proto(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let object = ctx.to_object(this)?;
+    Ok(object
+        .as_object()
+        .map_or_else(Value::null, |object| object.prototype().clone()))
+}
+
+/// `set Object.prototype.__proto__`
+///
+/// The `__proto__` accessor sets the prototype of an object, and ignores the values that are
+/// neither objects nor `null`. It is an Annex B legacy feature, see `annex_b`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-set-object.prototype.__proto__
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/proto
+pub(crate) fn set_proto(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    ctx.require_object_coercible(this)?;
+    let proto = args.first().cloned().unwrap_or_else(Value::undefined);
+    if !proto.is_object() && !proto.is_null() || !this.is_object() {
+        return Ok(Value::undefined());
+    }
+    let current = this.as_object().expect("object").prototype().clone();
+    if same_value(&current, &proto) {
+        return Ok(Value::undefined());
+    }
+    if !this.as_object().expect("object").is_extensible() {
+        return ctx.throw_type_error("cannot set the prototype of a non-extensible object");
+    }
+    // The prototype chain must not go through the object itself.
+    let mut p = proto.clone();
+    while p.is_object() {
+        if same_value(&p, this) {
+            return ctx.throw_type_error("cyclic __proto__ value");
+        }
+        let next = p.as_object().expect("object").prototype().clone();
+        p = next;
+    }
+    this.as_object_mut().expect("object").set_prototype(proto);
     Ok(Value::undefined())
 }
 
+/// `Object.defineProperty( obj, prop, descriptor )`
+///
+/// Defines a new property directly on an object, or modifies an existing property on an object, and returns the object.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-object.defineproperty
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/defineProperty
+pub fn define_property(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let obj = args.get(0).cloned().unwrap_or_else(Value::undefined);
+    if !obj.is_object() {
+        return ctx.throw_type_error("Object.defineProperty called on non-object");
+    }
+    let key = ctx.to_property_key(&args.get(1).cloned().unwrap_or_else(Value::undefined))?;
+    let desc =
+        ctx.to_property_descriptor(&args.get(2).cloned().unwrap_or_else(Value::undefined))?;
+
+    define_property_or_throw(&obj, &key, desc, ctx)?;
+    Ok(obj)
+}
+
+/// `Object.defineProperties( obj, props )`
+///
+/// Defines new or modifies existing properties directly on an object, returning the object.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-object.defineproperties
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/defineProperties
+pub fn define_properties(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let obj = args.get(0).cloned().unwrap_or_else(Value::undefined);
+    if !obj.is_object() {
+        return ctx.throw_type_error("Object.defineProperties called on non-object");
+    }
+    let props = ctx.to_object(&args.get(1).cloned().unwrap_or_else(Value::undefined))?;
+
+    // Collect all the descriptors before defining any of them.
+    let keys: Vec<_> = {
+        let props = props.as_object().expect("props is an object");
+        props
+            .own_property_keys()
+            .into_iter()
+            .filter(|key| props.get_own_property(key).enumerable_or(false))
+            .collect()
+    };
+    let mut descriptors = Vec::with_capacity(keys.len());
+    for key in keys {
+        let desc_obj = ctx.get_field(&props, key.clone())?;
+        descriptors.push((key, ctx.to_property_descriptor(&desc_obj)?));
+    }
+
+    for (key, desc) in descriptors {
+        define_property_or_throw(&obj, &key, desc, ctx)?;
+    }
+    Ok(obj)
+}
+
+/// `Object.getOwnPropertyDescriptor( obj, prop )`
+///
+/// Returns an object describing the configuration of a specific property on a given object.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-object.getownpropertydescriptor
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/getOwnPropertyDescriptor
+pub fn get_own_property_descriptor(
+    _: &Value,
+    args: &[Value],
+    ctx: &mut Interpreter,
+) -> ResultValue {
+    let obj = ctx.to_object(&args.get(0).cloned().unwrap_or_else(Value::undefined))?;
+    let key = ctx.to_property_key(&args.get(1).cloned().unwrap_or_else(Value::undefined))?;
+
+    let desc = obj
+        .as_object()
+        .expect("obj is an object")
+        .get_own_property(&key);
+    if desc.is_none() {
+        return Ok(Value::undefined());
+    }
+    Ok(ctx.from_property_descriptor(&desc))
+}
+
+/// Defines a property on an object, throwing a `TypeError` if it can't be defined.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-definepropertyorthrow
+fn define_property_or_throw(
+    obj: &Value,
+    key: &PropertyKey,
+    desc: Property,
+    ctx: &mut Interpreter,
+) -> Result<(), Value> {
+    let success = obj
+        .as_object_mut()
+        .expect("obj is an object")
+        .define_own_property(key.clone(), desc);
+    if !success {
+        return Err(ctx.construct_type_error(format!("cannot redefine property: {}", key)));
+    }
+    Ok(())
+}
+
 /// `Object.prototype.toString()`
 ///
-/// This method returns a string representing the object.
+/// This method returns a string representing the object, of the form `[object Tag]`. The tag
+/// can be customized with the `Symbol.toStringTag` property.
 ///
 /// More information:
 ///  - [ECMAScript reference][spec]
@@ -524,8 +705,37 @@ pub fn define_property(_: &Value, args: &[Value], ctx: &mut Interpreter) -> Resu
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-object.prototype.tostring
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/toString
-pub fn to_string(this: &Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-    Ok(Value::from(this.to_string()))
+pub fn to_string(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    if this.is_undefined() {
+        return Ok("[object Undefined]".into());
+    }
+    if this.is_null() {
+        return Ok("[object Null]".into());
+    }
+    let object = ctx.to_object(this)?;
+
+    let is_regexp = matches!(object.get_internal_state(), Some(state) if state.downcast_ref::<RegExp>().is_some());
+    let builtin_tag = if is_regexp {
+        "RegExp"
+    } else {
+        object
+            .as_object()
+            .map_or("Object", |object| match object.data {
+                ObjectData::Array => "Array",
+                ObjectData::Function(_) => "Function",
+                ObjectData::Error => "Error",
+                ObjectData::Boolean(_) => "Boolean",
+                ObjectData::Number(_) => "Number",
+                ObjectData::String(_) => "String",
+                _ => "Object",
+            })
+    };
+
+    let tag = ctx.get_field(&object, WellKnownSymbol::ToStringTag.key())?;
+    match tag {
+        Value::String(ref tag) => Ok(format!("[object {}]", tag).into()),
+        _ => Ok(format!("[object {}]", builtin_tag).into()),
+    }
 }
 
 /// `Object.prototype.hasOwnPrototype( property )`
@@ -540,16 +750,12 @@ pub fn to_string(this: &Value, _: &[Value], _: &mut Interpreter) -> ResultValue
 /// [spec]: https://tc39.es/ecma262/#sec-object.prototype.hasownproperty
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/hasOwnProperty
 pub fn has_own_property(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    let prop = if args.is_empty() {
-        None
-    } else {
-        Some(ctx.to_string(args.get(0).expect("Cannot get object"))?)
-    };
+    let key = ctx.to_property_key(&args.get(0).cloned().unwrap_or_default())?;
     let own_property = this
         .as_object()
         .as_deref()
         .expect("Cannot get THIS object")
-        .get_own_property(&Value::string(prop.expect("cannot get prop")));
+        .get_own_property(&key);
     if own_property.is_none() {
         Ok(Value::from(false))
     } else {
@@ -598,6 +804,13 @@ pub fn init(global: &Value) -> (&str, Value) {
     make_builtin_fn(set_prototype_of, "setPrototypeOf", &object, 2);
     make_builtin_fn(get_prototype_of, "getPrototypeOf", &object, 1);
     make_builtin_fn(define_property, "defineProperty", &object, 3);
+    make_builtin_fn(define_properties, "defineProperties", &object, 2);
+    make_builtin_fn(
+        get_own_property_descriptor,
+        "getOwnPropertyDescriptor",
+        &object,
+        2,
+    );
     make_builtin_fn(is, "is", &object, 2);
 
     ("Object", object)
@@ -140,3 +140,169 @@ fn object_property_is_enumerable() {
     );
     assert_eq!(forward(&mut engine, r#"x.propertyIsEnumerable()"#), "false",)
 }
+
+#[test]
+fn object_define_property_defaults_to_non_writable() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let x = {};
+        Object.defineProperty(x, 'p', { value: 1 });
+        x.p = 2;
+        delete x.p;
+    "#;
+
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "x.p"), "1");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "let d = Object.getOwnPropertyDescriptor(x, 'p'); [d.value, d.writable, d.enumerable, d.configurable].join()"
+        ),
+        "1,false,false,false"
+    );
+}
+
+#[test]
+fn object_define_property_partial_update() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let x = { p: 1 };
+        Object.defineProperty(x, 'p', { enumerable: false });
+    "#;
+
+    forward(&mut engine, init);
+    assert_eq!(
+        forward(
+            &mut engine,
+            "let d = Object.getOwnPropertyDescriptor(x, 'p'); [d.value, d.writable, d.enumerable, d.configurable].join()"
+        ),
+        "1,true,false,true"
+    );
+}
+
+#[test]
+fn object_define_property_throws_on_non_configurable() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let x = {};
+        Object.defineProperty(x, 'p', { value: 1 });
+    "#;
+
+    forward(&mut engine, init);
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { Object.defineProperty(x, 'p', { value: 2 }) } catch (e) { e.name }"
+        ),
+        "TypeError"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { Object.defineProperty(x, 'p', { get: function() {} }) } catch (e) { e.name }"
+        ),
+        "TypeError"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "Object.defineProperty(x, 'p', { value: 1 }) === x"
+        ),
+        "true"
+    );
+}
+
+#[test]
+fn object_define_property_accessor() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let stored = 0;
+        let x = {};
+        Object.defineProperty(x, 'p', {
+            get: function() { return stored * 2; },
+            set: function(v) { stored = v; },
+            configurable: true,
+        });
+        x.p = 21;
+    "#;
+
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "x.p"), "42");
+    assert_eq!(forward(&mut engine, "x['p']"), "42");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "let d = Object.getOwnPropertyDescriptor(x, 'p'); [typeof d.get, typeof d.set, d.enumerable, d.configurable, 'value' in d].join()"
+        ),
+        "function,function,false,true,false"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { Object.defineProperty(x, 'q', { get: 1 }) } catch (e) { e.name }"
+        ),
+        "TypeError"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { Object.defineProperty(x, 'q', { get: function() {}, value: 1 }) } catch (e) { e.name }"
+        ),
+        "TypeError"
+    );
+}
+
+#[test]
+fn object_define_properties() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let x = {};
+        Object.defineProperties(x, {
+            a: { value: 1, enumerable: true },
+            b: { value: 2, writable: true },
+        });
+    "#;
+
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "x.a + x.b"), "3");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "Object.getOwnPropertyDescriptor(x, 'a').enumerable"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "Object.getOwnPropertyDescriptor(x, 'b').writable"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut engine, "Object.getOwnPropertyDescriptor(x, 'c')"),
+        "undefined"
+    );
+}
+
+#[test]
+fn object_to_string_uses_to_string_tag() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let tagged = {};
+        tagged[Symbol.toStringTag] = "Custom";
+    "#;
+
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "({}).toString()"), "[object Object]");
+    assert_eq!(forward(&mut engine, "tagged.toString()"), "[object Custom]");
+    assert_eq!(forward(&mut engine, "Math.toString()"), "[object Math]");
+    assert_eq!(forward(&mut engine, "JSON.toString()"), "[object JSON]");
+    assert_eq!(forward(&mut engine, "new Map().toString()"), "[object Map]");
+}
@@ -0,0 +1,696 @@
+//! This module implements the global `Promise` object.
+//!
+//! A promise is the eventual result of an asynchronous operation. The reactions registered with
+//! `then` run as microtasks, once the promise is settled and the current script has finished.
+//!
+//! The state of a promise is kept in its internal slots, and the functions that it creates,
+//! like its resolving functions, reference it through their captured value, so that the garbage
+//! collector can trace every promise.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-promise-objects
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Promise
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builtins::{
+        function::{make_builtin_fn, make_constructor_fn, CapturingFunctionData, Function},
+        object::{InternalState, Object, ObjectData, PROTOTYPE},
+        symbol::set_to_string_tag,
+        value::{same_value, ResultValue, Value},
+        Array,
+    },
+    exec::Interpreter,
+    BoaProfiler,
+};
+use std::{cell::RefCell, task::Waker};
+
+/// The internal slot that holds the state of a promise, `"pending"`, `"fulfilled"` or
+/// `"rejected"`.
+const STATE: &str = "PromiseState";
+
+/// The internal slot that holds the value or the reason of a settled promise.
+const RESULT: &str = "PromiseResult";
+
+/// The internal slot that holds the array of the fulfill reactions of a pending promise.
+const FULFILL_REACTIONS: &str = "PromiseFulfillReactions";
+
+/// The internal slot that holds the array of the reject reactions of a pending promise.
+const REJECT_REACTIONS: &str = "PromiseRejectReactions";
+
+/// The internal slots of the records captured by the functions that promises create.
+const PROMISE: &str = "Promise";
+const ALREADY_RESOLVED: &str = "AlreadyResolved";
+const HANDLER: &str = "Handler";
+const KIND: &str = "Type";
+const THENABLE: &str = "Thenable";
+const THEN: &str = "Then";
+const VALUES: &str = "Values";
+const REMAINING: &str = "RemainingElements";
+const INDEX: &str = "Index";
+const RESOLVE: &str = "Resolve";
+
+/// The state of a promise, as seen by the host.
+#[derive(Debug, Clone)]
+pub enum PromiseState {
+    /// The promise is neither fulfilled nor rejected yet.
+    Pending,
+    /// The promise was fulfilled with a value.
+    Fulfilled(Value),
+    /// The promise was rejected with a reason.
+    Rejected(Value),
+}
+
+/// The tasks waiting for a promise to settle, stored in the internal state of the promise by
+/// `PromiseFuture`.
+#[derive(Debug, Default)]
+pub(crate) struct PromiseWakers(RefCell<Vec<Waker>>);
+
+impl InternalState for PromiseWakers {}
+
+impl PromiseWakers {
+    /// Registers a task to wake once the promise settles.
+    pub(crate) fn register(&self, waker: &Waker) {
+        let mut wakers = self.0.borrow_mut();
+        if !wakers.iter().any(|registered| registered.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+}
+
+/// JavaScript `Promise` implementation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Promise;
+
+impl Promise {
+    /// The name of the object.
+    pub(crate) const NAME: &'static str = "Promise";
+
+    /// The amount of arguments this function object takes.
+    pub(crate) const LENGTH: usize = 1;
+
+    /// `new Promise( executor )`
+    ///
+    /// Creates a pending promise, and calls `executor` with the functions that resolve and
+    /// reject it.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-promise-executor
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Promise/Promise
+    pub(crate) fn make_promise(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let executor = args.first().cloned().unwrap_or_default();
+        if !executor.is_callable() {
+            return ctx.throw_type_error("Promise resolver is not a function");
+        }
+        Self::initialize(this, ctx)?;
+
+        let (resolve, reject) = Self::create_resolving_functions(this, ctx);
+        if let Err(error) = ctx.call(&executor, &Value::undefined(), &[resolve, reject.clone()]) {
+            ctx.call(&reject, &Value::undefined(), &[error])?;
+        }
+        Ok(this.clone())
+    }
+
+    /// Creates a new pending promise.
+    pub(crate) fn new_promise(ctx: &mut Interpreter) -> ResultValue {
+        let prototype = ctx
+            .realm()
+            .environment
+            .get_binding_value(Self::NAME)
+            .expect("Promise was not initialized")
+            .get_field(PROTOTYPE);
+        let promise = Value::new_object_from_prototype(prototype, ObjectData::Ordinary);
+        Self::initialize(&promise, ctx)?;
+        Ok(promise)
+    }
+
+    /// Sets the internal slots of a new pending promise.
+    fn initialize(promise: &Value, ctx: &mut Interpreter) -> Result<(), Value> {
+        promise.set_internal_slot(STATE, Value::from("pending"));
+        promise.set_internal_slot(RESULT, Value::undefined());
+        promise.set_internal_slot(FULFILL_REACTIONS, Array::new_array(ctx)?);
+        promise.set_internal_slot(REJECT_REACTIONS, Array::new_array(ctx)?);
+        Ok(())
+    }
+
+    /// Checks if a value is a promise.
+    pub(crate) fn is_promise(value: &Value) -> bool {
+        value.is_object() && value.get_internal_slot(STATE).is_string()
+    }
+
+    /// Gets the state of a promise, or `None` if the value is not a promise.
+    pub(crate) fn state(value: &Value) -> Option<PromiseState> {
+        if !Self::is_promise(value) {
+            return None;
+        }
+        let result = value.get_internal_slot(RESULT);
+        Some(match value.get_internal_slot(STATE).to_string().as_str() {
+            "fulfilled" => PromiseState::Fulfilled(result),
+            "rejected" => PromiseState::Rejected(result),
+            _ => PromiseState::Pending,
+        })
+    }
+
+    /// Creates a function that receives `captures` when it is called.
+    fn make_function(
+        body: CapturingFunctionData,
+        captures: Value,
+        length: usize,
+        ctx: &Interpreter,
+    ) -> Value {
+        let prototype = ctx.realm().function_prototype();
+        let function = Function::builtin_with_captures(Vec::new(), body, captures);
+        let function = Value::from(Object::function(function, prototype));
+        function.set_field("length", Value::from(length));
+        function
+    }
+
+    /// Creates a record, an object that only holds internal slots.
+    fn record(slots: &[(&str, Value)]) -> Value {
+        let record = Value::new_object(None);
+        for (name, value) in slots {
+            record.set_internal_slot(name, value.clone());
+        }
+        record
+    }
+
+    /// Creates the `resolve` and `reject` functions of a promise.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-createresolvingfunctions
+    pub(crate) fn create_resolving_functions(promise: &Value, ctx: &Interpreter) -> (Value, Value) {
+        let record = Self::record(&[
+            (PROMISE, promise.clone()),
+            (ALREADY_RESOLVED, Value::from(false)),
+        ]);
+        let resolve = Self::make_function(Self::resolve_function, record.clone(), 1, ctx);
+        let reject = Self::make_function(Self::reject_function, record, 1, ctx);
+        (resolve, reject)
+    }
+
+    /// The body of the `resolve` functions created by `create_resolving_functions`.
+    fn resolve_function(
+        record: &Value,
+        _: &Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        if record.get_internal_slot(ALREADY_RESOLVED).to_boolean() {
+            return Ok(Value::undefined());
+        }
+        record.set_internal_slot(ALREADY_RESOLVED, Value::from(true));
+
+        let promise = record.get_internal_slot(PROMISE);
+        let resolution = args.first().cloned().unwrap_or_default();
+        Self::resolve_promise(&promise, resolution, ctx);
+        Ok(Value::undefined())
+    }
+
+    /// The body of the `reject` functions created by `create_resolving_functions`.
+    fn reject_function(
+        record: &Value,
+        _: &Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        if record.get_internal_slot(ALREADY_RESOLVED).to_boolean() {
+            return Ok(Value::undefined());
+        }
+        record.set_internal_slot(ALREADY_RESOLVED, Value::from(true));
+
+        let promise = record.get_internal_slot(PROMISE);
+        let reason = args.first().cloned().unwrap_or_default();
+        Self::reject_promise(&promise, reason, ctx);
+        Ok(Value::undefined())
+    }
+
+    /// Resolves a promise with a value, following the value if it is a thenable.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-promise-resolve-functions
+    pub(crate) fn resolve_promise(promise: &Value, resolution: Value, ctx: &mut Interpreter) {
+        if same_value(promise, &resolution) {
+            let error = ctx.construct_type_error("cannot resolve a promise with itself");
+            return Self::reject_promise(promise, error, ctx);
+        }
+        if !resolution.is_object() {
+            return Self::settle(promise, "fulfilled", resolution, ctx);
+        }
+        let then = match ctx.get_field(&resolution, "then") {
+            Ok(then) => then,
+            Err(error) => return Self::reject_promise(promise, error, ctx),
+        };
+        if !then.is_callable() {
+            return Self::settle(promise, "fulfilled", resolution, ctx);
+        }
+
+        let record = Self::record(&[
+            (PROMISE, promise.clone()),
+            (THENABLE, resolution),
+            (THEN, then),
+        ]);
+        let job = Self::make_function(Self::resolve_thenable_job, record, 0, ctx);
+        ctx.enqueue_job(job, Vec::new());
+    }
+
+    /// Rejects a promise with a reason.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-rejectpromise
+    pub(crate) fn reject_promise(promise: &Value, reason: Value, ctx: &mut Interpreter) {
+        Self::settle(promise, "rejected", reason, ctx)
+    }
+
+    /// Fulfills or rejects a promise, and queues the jobs of its reactions.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-triggerpromisereactions
+    fn settle(promise: &Value, state: &str, result: Value, ctx: &mut Interpreter) {
+        let reactions = promise.get_internal_slot(if state == "fulfilled" {
+            FULFILL_REACTIONS
+        } else {
+            REJECT_REACTIONS
+        });
+        promise.set_internal_slot(STATE, Value::from(state));
+        promise.set_internal_slot(RESULT, result.clone());
+        promise.set_internal_slot(FULFILL_REACTIONS, Value::undefined());
+        promise.set_internal_slot(REJECT_REACTIONS, Value::undefined());
+
+        for reaction in ctx.extract_array_properties(&reactions).unwrap_or_default() {
+            Self::enqueue_reaction_job(reaction, result.clone(), ctx);
+        }
+
+        if let Some(state) = promise.get_internal_state() {
+            if let Some(wakers) = state.downcast_ref::<PromiseWakers>() {
+                for waker in wakers.0.borrow_mut().drain(..) {
+                    waker.wake();
+                }
+            }
+        }
+    }
+
+    /// Queues the job that runs a reaction with the result of its promise.
+    fn enqueue_reaction_job(reaction: Value, argument: Value, ctx: &mut Interpreter) {
+        let job = Self::make_function(Self::reaction_job, reaction, 1, ctx);
+        ctx.enqueue_job(job, vec![argument]);
+    }
+
+    /// Calls the `then` method of a thenable that a promise was resolved with.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-newpromiseresolvethenablejob
+    fn resolve_thenable_job(
+        record: &Value,
+        _: &Value,
+        _: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let promise = record.get_internal_slot(PROMISE);
+        let thenable = record.get_internal_slot(THENABLE);
+        let then = record.get_internal_slot(THEN);
+
+        let (resolve, reject) = Self::create_resolving_functions(&promise, ctx);
+        if let Err(error) = ctx.call(&then, &thenable, &[resolve, reject.clone()]) {
+            ctx.call(&reject, &Value::undefined(), &[error])?;
+        }
+        Ok(Value::undefined())
+    }
+
+    /// Calls the handler of a reaction, and settles the promise that `then` returned with its
+    /// result.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-newpromisereactionjob
+    fn reaction_job(
+        reaction: &Value,
+        _: &Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let argument = args.first().cloned().unwrap_or_default();
+        let handler = reaction.get_internal_slot(HANDLER);
+        let result = if handler.is_undefined() {
+            if reaction.get_internal_slot(KIND).to_string() == "fulfill" {
+                Ok(argument)
+            } else {
+                Err(argument)
+            }
+        } else {
+            ctx.call(&handler, &Value::undefined(), &[argument])
+        };
+
+        let promise = reaction.get_internal_slot(PROMISE);
+        if !promise.is_undefined() {
+            match result {
+                Ok(value) => Self::resolve_promise(&promise, value, ctx),
+                Err(reason) => Self::reject_promise(&promise, reason, ctx),
+            }
+        }
+        Ok(Value::undefined())
+    }
+
+    /// Registers the reactions to the settlement of a promise.
+    ///
+    /// `derived` is the promise that is settled with the result of the handlers, or
+    /// `undefined`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-performpromisethen
+    pub(crate) fn perform_then(
+        promise: &Value,
+        on_fulfilled: Value,
+        on_rejected: Value,
+        derived: Value,
+        ctx: &mut Interpreter,
+    ) -> Result<(), Value> {
+        let handler = |handler: Value| {
+            if handler.is_callable() {
+                handler
+            } else {
+                Value::undefined()
+            }
+        };
+        let fulfill_reaction = Self::record(&[
+            (PROMISE, derived.clone()),
+            (HANDLER, handler(on_fulfilled)),
+            (KIND, Value::from("fulfill")),
+        ]);
+        let reject_reaction = Self::record(&[
+            (PROMISE, derived),
+            (HANDLER, handler(on_rejected)),
+            (KIND, Value::from("reject")),
+        ]);
+
+        match Self::state(promise) {
+            Some(PromiseState::Fulfilled(value)) => {
+                Self::enqueue_reaction_job(fulfill_reaction, value, ctx)
+            }
+            Some(PromiseState::Rejected(reason)) => {
+                Self::enqueue_reaction_job(reject_reaction, reason, ctx)
+            }
+            _ => {
+                Array::add_to_array_object(
+                    &promise.get_internal_slot(FULFILL_REACTIONS),
+                    &[fulfill_reaction],
+                )?;
+                Array::add_to_array_object(
+                    &promise.get_internal_slot(REJECT_REACTIONS),
+                    &[reject_reaction],
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Converts a value to a promise, returning it unchanged if it is already a promise.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-promise-resolve
+    pub(crate) fn promise_resolve(value: Value, ctx: &mut Interpreter) -> ResultValue {
+        if Self::is_promise(&value) {
+            return Ok(value);
+        }
+        let promise = Self::new_promise(ctx)?;
+        Self::resolve_promise(&promise, value, ctx);
+        Ok(promise)
+    }
+
+    /// Calls the `then` method of a value.
+    fn invoke_then(value: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let then = ctx.get_field(value, "then")?;
+        ctx.call(&then, value, args)
+    }
+
+    /// `Promise.prototype.then( onFulfilled, onRejected )`
+    ///
+    /// Registers the handlers of the settlement of the promise, and returns a promise settled
+    /// with their result.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-promise.prototype.then
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Promise/then
+    pub(crate) fn then(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        if !Self::is_promise(this) {
+            return ctx.throw_type_error("Promise.prototype.then: 'this' is not a Promise");
+        }
+        let on_fulfilled = args.first().cloned().unwrap_or_default();
+        let on_rejected = args.get(1).cloned().unwrap_or_default();
+
+        let derived = Self::new_promise(ctx)?;
+        Self::perform_then(this, on_fulfilled, on_rejected, derived.clone(), ctx)?;
+        Ok(derived)
+    }
+
+    /// `Promise.prototype.catch( onRejected )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-promise.prototype.catch
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Promise/catch
+    pub(crate) fn catch(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let on_rejected = args.first().cloned().unwrap_or_default();
+        Self::invoke_then(this, &[Value::undefined(), on_rejected], ctx)
+    }
+
+    /// `Promise.prototype.finally( onFinally )`
+    ///
+    /// Calls `onFinally` once the promise is settled, and returns a promise settled like this
+    /// one, unless `onFinally` throws or returns a rejected promise.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-promise.prototype.finally
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Promise/finally
+    pub(crate) fn finally(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let on_finally = args.first().cloned().unwrap_or_default();
+        let (then_finally, catch_finally) = if on_finally.is_callable() {
+            (
+                Self::make_function(Self::then_finally, on_finally.clone(), 1, ctx),
+                Self::make_function(Self::catch_finally, on_finally, 1, ctx),
+            )
+        } else {
+            (on_finally.clone(), on_finally)
+        };
+        Self::invoke_then(this, &[then_finally, catch_finally], ctx)
+    }
+
+    /// The fulfill handler registered by `finally`.
+    fn then_finally(
+        on_finally: &Value,
+        _: &Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let value = args.first().cloned().unwrap_or_default();
+        let result = ctx.call(on_finally, &Value::undefined(), &[])?;
+        let promise = Self::promise_resolve(result, ctx)?;
+        let value_thunk = Self::make_function(|value, _, _, _| Ok(value.clone()), value, 0, ctx);
+        Self::invoke_then(&promise, &[value_thunk], ctx)
+    }
+
+    /// The reject handler registered by `finally`.
+    fn catch_finally(
+        on_finally: &Value,
+        _: &Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let reason = args.first().cloned().unwrap_or_default();
+        let result = ctx.call(on_finally, &Value::undefined(), &[])?;
+        let promise = Self::promise_resolve(result, ctx)?;
+        let thrower = Self::make_function(|reason, _, _, _| Err(reason.clone()), reason, 0, ctx);
+        Self::invoke_then(&promise, &[thrower], ctx)
+    }
+
+    /// `Promise.resolve( value )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-promise.resolve
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Promise/resolve
+    pub(crate) fn resolve(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        Self::promise_resolve(args.first().cloned().unwrap_or_default(), ctx)
+    }
+
+    /// `Promise.reject( reason )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-promise.reject
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Promise/reject
+    pub(crate) fn reject(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let promise = Self::new_promise(ctx)?;
+        Self::reject_promise(&promise, args.first().cloned().unwrap_or_default(), ctx);
+        Ok(promise)
+    }
+
+    /// Gets the elements of the array passed to `Promise.all` or `Promise.race`.
+    ///
+    /// Only arrays are supported, since the engine has no iterators yet.
+    fn elements(args: &[Value], method: &str, ctx: &mut Interpreter) -> Result<Vec<Value>, Value> {
+        let array = args.first().cloned().unwrap_or_default();
+        match ctx.extract_array_properties(&array) {
+            Ok(elements) => Ok(elements),
+            Err(()) => {
+                Err(ctx
+                    .construct_type_error(format!("Promise.{}: argument is not an array", method)))
+            }
+        }
+    }
+
+    /// `Promise.all( promises )`
+    ///
+    /// Returns a promise fulfilled with the array of the values of the promises once they are
+    /// all fulfilled, or rejected as soon as one of them is rejected.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-promise.all
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Promise/all
+    pub(crate) fn all(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let elements = Self::elements(args, "all", ctx)?;
+        let promise = Self::new_promise(ctx)?;
+        let (resolve, reject) = Self::create_resolving_functions(&promise, ctx);
+
+        let values = Array::new_array(ctx)?;
+        Array::add_to_array_object(&values, &vec![Value::undefined(); elements.len()])?;
+        if elements.is_empty() {
+            ctx.call(&resolve, &Value::undefined(), &[values])?;
+            return Ok(promise);
+        }
+
+        let record = Self::record(&[
+            (VALUES, values),
+            (REMAINING, Value::from(elements.len())),
+            (RESOLVE, resolve),
+        ]);
+        for (index, element) in elements.into_iter().enumerate() {
+            let element = Self::promise_resolve(element, ctx)?;
+            let captures = Self::record(&[
+                (PROMISE, record.clone()),
+                (INDEX, Value::from(index)),
+                (ALREADY_RESOLVED, Value::from(false)),
+            ]);
+            let resolve_element = Self::make_function(Self::all_resolve_element, captures, 1, ctx);
+            Self::invoke_then(&element, &[resolve_element, reject.clone()], ctx)?;
+        }
+        Ok(promise)
+    }
+
+    /// The function that stores the value of an element of `Promise.all`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-promise.all-resolve-element-functions
+    fn all_resolve_element(
+        captures: &Value,
+        _: &Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        if captures.get_internal_slot(ALREADY_RESOLVED).to_boolean() {
+            return Ok(Value::undefined());
+        }
+        captures.set_internal_slot(ALREADY_RESOLVED, Value::from(true));
+
+        let record = captures.get_internal_slot(PROMISE);
+        let values = record.get_internal_slot(VALUES);
+        let index = captures.get_internal_slot(INDEX).to_integer();
+        values.set_field(index, args.first().cloned().unwrap_or_default());
+
+        let remaining = record.get_internal_slot(REMAINING).to_integer() - 1;
+        record.set_internal_slot(REMAINING, Value::from(remaining));
+        if remaining == 0 {
+            let resolve = record.get_internal_slot(RESOLVE);
+            ctx.call(&resolve, &Value::undefined(), &[values])?;
+        }
+        Ok(Value::undefined())
+    }
+
+    /// `Promise.race( promises )`
+    ///
+    /// Returns a promise settled like the first of the promises to settle.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-promise.race
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Promise/race
+    pub(crate) fn race(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let elements = Self::elements(args, "race", ctx)?;
+        let promise = Self::new_promise(ctx)?;
+        let (resolve, reject) = Self::create_resolving_functions(&promise, ctx);
+        for element in elements {
+            let element = Self::promise_resolve(element, ctx)?;
+            Self::invoke_then(&element, &[resolve.clone(), reject.clone()], ctx)?;
+        }
+        Ok(promise)
+    }
+
+    /// Initialise the `Promise` object on the global object.
+    #[inline]
+    pub(crate) fn init(global: &Value) -> (&str, Value) {
+        let _timer = BoaProfiler::global().start_event(Self::NAME, "init");
+
+        let prototype = Value::new_object(Some(global));
+        make_builtin_fn(Self::then, "then", &prototype, 2);
+        make_builtin_fn(Self::catch, "catch", &prototype, 1);
+        make_builtin_fn(Self::finally, "finally", &prototype, 1);
+        set_to_string_tag(&prototype, Self::NAME);
+
+        let promise = make_constructor_fn(
+            Self::NAME,
+            Self::LENGTH,
+            Self::make_promise,
+            global,
+            prototype,
+            true,
+            false,
+        );
+        make_builtin_fn(Self::resolve, "resolve", &promise, 1);
+        make_builtin_fn(Self::reject, "reject", &promise, 1);
+        make_builtin_fn(Self::all, "all", &promise, 1);
+        make_builtin_fn(Self::race, "race", &promise, 1);
+
+        (Self::NAME, promise)
+    }
+}
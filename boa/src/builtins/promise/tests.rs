@@ -0,0 +1,120 @@
+use crate::{exec::Interpreter, forward, realm::Realm};
+
+#[test]
+fn reactions_run_after_the_script() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var log = [];
+        var p = new Promise(function(resolve) { log.push('executor'); resolve(1); });
+        p.then(function(v) { log.push('then ' + v); return v + 1; })
+         .then(function(v) { log.push('chained ' + v); });
+        log.push('script');
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(
+        forward(&mut engine, "log.join()"),
+        "executor,script,then 1,chained 2"
+    );
+}
+
+#[test]
+fn rejections_propagate_to_catch() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var log = [];
+        new Promise(function() { throw new TypeError('bad'); })
+            .then(function() { log.push('skipped'); })
+            .catch(function(e) { log.push(e.message); return 'recovered'; })
+            .then(function(v) { log.push(v); });
+        Promise.reject('no').then(null, function(r) { log.push(r); });
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "log.join()"), "no,bad,recovered");
+}
+
+#[test]
+fn resolves_thenables() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var result;
+        var thenable = { then: function(resolve) { resolve(42); } };
+        Promise.resolve(thenable).then(function(v) { result = v; });
+        var inner = Promise.resolve('inner');
+        var same = Promise.resolve(inner) === inner;
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "result"), "42");
+    assert_eq!(forward(&mut engine, "same"), "true");
+}
+
+#[test]
+fn settles_only_once() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var log = [];
+        new Promise(function(resolve, reject) { resolve('a'); reject('b'); resolve('c'); })
+            .then(function(v) { log.push(v); }, function(r) { log.push('rejected ' + r); });
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "log.join()"), "a");
+}
+
+#[test]
+fn finally_keeps_the_result() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var log = [];
+        Promise.resolve(1)
+            .finally(function() { log.push('finally'); return 2; })
+            .then(function(v) { log.push(v); });
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "log.join()"), "finally,1");
+}
+
+#[test]
+fn all_and_race() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var all, race, failed;
+        Promise.all([1, Promise.resolve(2), { then: function(r) { r(3); } }])
+            .then(function(values) { all = values.join(); });
+        Promise.race([new Promise(function() {}), Promise.resolve('fast')])
+            .then(function(v) { race = v; });
+        Promise.all([Promise.reject('boom'), 1]).catch(function(r) { failed = r; });
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "all"), "1,2,3");
+    assert_eq!(forward(&mut engine, "race"), "fast");
+    assert_eq!(forward(&mut engine, "failed"), "boom");
+}
+
+#[test]
+fn executor_must_be_a_function() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let scenario = r#"
+        try {
+            new Promise(1);
+        } catch (e) {
+            e.toString()
+        }
+        "#;
+
+    assert_eq!(
+        forward(&mut engine, scenario),
+        "TypeError: Promise resolver is not a function"
+    );
+}
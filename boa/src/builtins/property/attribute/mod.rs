@@ -77,7 +77,8 @@ impl Attribute {
         if value {
             *self |= Self::WRITABLE;
         } else {
-            *self |= *self & !Self::WRITABLE;
+            self.remove(Self::WRITABLE);
+            *self |= Self::READONLY;
         }
     }
 
@@ -100,7 +101,8 @@ impl Attribute {
         if value {
             *self |= Self::ENUMERABLE;
         } else {
-            *self |= *self & !Self::ENUMERABLE;
+            self.remove(Self::ENUMERABLE);
+            *self |= Self::NON_ENUMERABLE;
         }
     }
 
@@ -123,7 +125,8 @@ impl Attribute {
         if value {
             *self |= Self::CONFIGURABLE;
         } else {
-            *self |= *self & !Self::CONFIGURABLE;
+            self.remove(Self::CONFIGURABLE);
+            *self |= Self::PERMANENT;
         }
     }
 
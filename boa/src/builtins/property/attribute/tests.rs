@@ -165,3 +165,15 @@ fn set_configurable_to_false() {
     assert!(attribute.has_configurable());
     assert!(!attribute.configurable());
 }
+
+#[test]
+fn set_configurable_to_false_on_undefined_flag() {
+    let mut attribute = Attribute::WRITABLE | Attribute::ENUMERABLE;
+
+    attribute.set_configurable(false);
+
+    assert!(attribute.writable());
+    assert!(attribute.enumerable());
+    assert!(attribute.has_configurable());
+    assert!(!attribute.configurable());
+}
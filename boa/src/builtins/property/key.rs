@@ -0,0 +1,208 @@
+//! This module implements the `PropertyKey` type.
+//!
+//! A property key is either a String or a Symbol. Strings that are canonical array indices
+//! are represented as integers, so that indexed access never has to go through a string.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-object-type
+
+use crate::builtins::value::{JsString, RcSymbol, Value};
+use std::fmt::{self, Display};
+
+/// The key of an object property.
+///
+/// Array indices (the integers in the range `0..2^32 - 1`) are stored as `Index`, every other
+/// string as `String`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PropertyKey {
+    /// An integer index, `0 <= index < 2^32 - 1`.
+    Index(u32),
+    /// A string key that is not an array index.
+    String(JsString),
+    /// A symbol key.
+    Symbol(RcSymbol),
+}
+
+impl PropertyKey {
+    /// Returns the array index this key represents, if any.
+    #[inline]
+    pub fn as_index(&self) -> Option<u32> {
+        match *self {
+            Self::Index(index) => Some(index),
+            _ => None,
+        }
+    }
+
+    /// Returns the symbol this key represents, if any.
+    #[inline]
+    pub fn as_symbol(&self) -> Option<&RcSymbol> {
+        match *self {
+            Self::Symbol(ref symbol) => Some(symbol),
+            _ => None,
+        }
+    }
+
+    /// Checks if this key is a symbol.
+    #[inline]
+    pub fn is_symbol(&self) -> bool {
+        matches!(self, Self::Symbol(_))
+    }
+
+    /// Parses `string` as a canonical array index, like `"0"` or `"42"` but not `"01"`.
+    fn parse_index(string: &str) -> Option<u32> {
+        if string.len() > 1 && string.starts_with('0') {
+            return None;
+        }
+        match string.parse::<u32>() {
+            Ok(index) if index != u32::MAX && string.bytes().all(|b| b.is_ascii_digit()) => {
+                Some(index)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Display for PropertyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Index(index) => Display::fmt(&index, f),
+            Self::String(ref string) => Display::fmt(string, f),
+            Self::Symbol(ref symbol) => Display::fmt(symbol, f),
+        }
+    }
+}
+
+impl From<&str> for PropertyKey {
+    #[inline]
+    fn from(string: &str) -> Self {
+        match Self::parse_index(string) {
+            Some(index) => Self::Index(index),
+            None => Self::String(string.into()),
+        }
+    }
+}
+
+impl From<String> for PropertyKey {
+    #[inline]
+    fn from(string: String) -> Self {
+        match Self::parse_index(&string) {
+            Some(index) => Self::Index(index),
+            None => Self::String(string.into()),
+        }
+    }
+}
+
+impl From<&String> for PropertyKey {
+    #[inline]
+    fn from(string: &String) -> Self {
+        Self::from(string.as_str())
+    }
+}
+
+impl From<JsString> for PropertyKey {
+    #[inline]
+    fn from(string: JsString) -> Self {
+        match Self::parse_index(&string) {
+            Some(index) => Self::Index(index),
+            None => Self::String(string),
+        }
+    }
+}
+
+impl From<&JsString> for PropertyKey {
+    #[inline]
+    fn from(string: &JsString) -> Self {
+        Self::from(string.clone())
+    }
+}
+
+impl From<RcSymbol> for PropertyKey {
+    #[inline]
+    fn from(symbol: RcSymbol) -> Self {
+        Self::Symbol(symbol)
+    }
+}
+
+impl From<u32> for PropertyKey {
+    #[inline]
+    fn from(index: u32) -> Self {
+        if index == u32::MAX {
+            Self::String(index.to_string().into())
+        } else {
+            Self::Index(index)
+        }
+    }
+}
+
+impl From<usize> for PropertyKey {
+    #[inline]
+    fn from(index: usize) -> Self {
+        if index < u32::MAX as usize {
+            Self::Index(index as u32)
+        } else {
+            Self::String(index.to_string().into())
+        }
+    }
+}
+
+impl From<i32> for PropertyKey {
+    #[inline]
+    fn from(index: i32) -> Self {
+        if index >= 0 {
+            Self::Index(index as u32)
+        } else {
+            Self::String(index.to_string().into())
+        }
+    }
+}
+
+/// Converts a value to a property key without calling into JavaScript.
+///
+/// Objects are converted using their display form, use `Interpreter::to_property_key`
+/// to convert them with `toString`.
+impl From<&Value> for PropertyKey {
+    fn from(value: &Value) -> Self {
+        match *value {
+            Value::String(ref string) => Self::from(string),
+            Value::Symbol(ref symbol) => Self::Symbol(symbol.clone()),
+            Value::Integer(integer) => Self::from(integer),
+            Value::Rational(rational)
+                if rational.trunc() == rational && (0.0..4_294_967_295.0).contains(&rational) =>
+            {
+                Self::Index(rational as u32)
+            }
+            _ => Self::from(value.to_string()),
+        }
+    }
+}
+
+impl From<Value> for PropertyKey {
+    #[inline]
+    fn from(value: Value) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&PropertyKey> for Value {
+    #[inline]
+    fn from(key: &PropertyKey) -> Self {
+        match *key {
+            PropertyKey::Index(index) => Value::from(index.to_string()),
+            PropertyKey::String(ref string) => Value::from(string.clone()),
+            PropertyKey::Symbol(ref symbol) => Value::Symbol(symbol.clone()),
+        }
+    }
+}
+
+impl From<PropertyKey> for Value {
+    #[inline]
+    fn from(key: PropertyKey) -> Self {
+        match key {
+            PropertyKey::Index(index) => Value::from(index.to_string()),
+            PropertyKey::String(string) => Value::from(string),
+            PropertyKey::Symbol(symbol) => Value::Symbol(symbol),
+        }
+    }
+}
@@ -18,7 +18,9 @@ use crate::builtins::Value;
 use gc::{Finalize, Trace};
 
 pub mod attribute;
+pub mod key;
 pub use attribute::Attribute;
+pub use key::PropertyKey;
 
 #[cfg(test)]
 mod tests;
@@ -232,55 +234,3 @@ impl Default for Property {
         Self::new()
     }
 }
-
-impl From<&Property> for Value {
-    fn from(value: &Property) -> Value {
-        let property = Value::new_object(None);
-        if value.attribute.has_writable() {
-            property.set_field("writable", value.attribute.writable());
-        }
-
-        if value.attribute.has_enumerable() {
-            property.set_field("enumerable", value.attribute.enumerable());
-        }
-
-        if value.attribute.has_configurable() {
-            property.set_field("configurable", value.attribute.configurable());
-        }
-
-        property.set_field("value", value.value.clone().unwrap_or_else(Value::null));
-        property.set_field("get", value.get.clone().unwrap_or_else(Value::null));
-        property.set_field("set", value.set.clone().unwrap_or_else(Value::null));
-        property
-    }
-}
-
-impl<'a> From<&'a Value> for Property {
-    /// Attempt to fetch values "configurable", "enumerable", "writable" from the value,
-    /// if they're not there default to false
-    fn from(value: &Value) -> Self {
-        let mut attribute = Attribute::empty();
-
-        let writable = value.get_field("writable");
-        if !writable.is_undefined() {
-            attribute.set_writable(bool::from(&writable));
-        }
-
-        let enumerable = value.get_field("enumerable");
-        if !enumerable.is_undefined() {
-            attribute.set_enumerable(bool::from(&enumerable));
-        }
-
-        let configurable = value.get_field("configurable");
-        if !configurable.is_undefined() {
-            attribute.set_configurable(bool::from(&configurable));
-        }
-
-        Self {
-            attribute,
-            value: Some(value.get_field("value")),
-            get: Some(value.get_field("get")),
-            set: Some(value.get_field("set")),
-        }
-    }
-}
@@ -8,3 +8,50 @@ fn is_property_key_test() {
     let v = Value::boolean(true);
     assert!(!Property::is_property_key(&v));
 }
+
+#[test]
+fn property_key_from_canonical_index() {
+    assert_eq!(PropertyKey::from("0"), PropertyKey::Index(0));
+    assert_eq!(PropertyKey::from("42"), PropertyKey::Index(42));
+    assert_eq!(
+        PropertyKey::from("4294967294"),
+        PropertyKey::Index(4_294_967_294)
+    );
+    assert_eq!(PropertyKey::from(&Value::integer(7)), PropertyKey::Index(7));
+    assert_eq!(
+        PropertyKey::from(&Value::rational(3.0)),
+        PropertyKey::Index(3)
+    );
+}
+
+#[test]
+fn property_key_from_non_index_string() {
+    for key in &["01", "+1", "-1", "1.5", "4294967295", "", "length"] {
+        assert_eq!(PropertyKey::from(*key), PropertyKey::String((*key).into()));
+    }
+}
+
+#[test]
+fn index_and_string_keys_are_the_same_property() {
+    use crate::{exec::Interpreter, forward, realm::Realm};
+
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var obj = {};
+        obj[1] = "one";
+        obj["2"] = "two";
+        var arr = [];
+        arr["0"] = "zero";
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "obj['1']"), "one");
+    assert_eq!(forward(&mut engine, "obj[2]"), "two");
+    assert_eq!(forward(&mut engine, "obj.hasOwnProperty(1)"), "true");
+    assert_eq!(forward(&mut engine, "arr.length"), "1");
+    assert_eq!(
+        forward(&mut engine, "JSON.stringify(obj)"),
+        r#"{"1":"one","2":"two"}"#
+    );
+}
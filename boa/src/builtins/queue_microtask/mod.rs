@@ -0,0 +1,55 @@
+//! This module implements the global `queueMicrotask` function.
+//!
+//! Microtasks run once the current script has finished, in the order they were queued.
+//!
+//! More information:
+//!  - [HTML reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#microtask-queuing
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/queueMicrotask
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builtins::{
+        function::make_builtin_fn,
+        value::{ResultValue, Value},
+    },
+    exec::Interpreter,
+    BoaProfiler,
+};
+
+/// The `queueMicrotask` host function.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct QueueMicrotask;
+
+impl QueueMicrotask {
+    /// `queueMicrotask( callback )`
+    ///
+    /// Queues `callback` to be called once the current script has finished running.
+    ///
+    /// More information:
+    ///  - [HTML reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#dom-queuemicrotask
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/queueMicrotask
+    pub(crate) fn queue_microtask(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let callback = args.get(0).cloned().unwrap_or_default();
+        if !callback.is_function() {
+            return ctx.throw_type_error("queueMicrotask: callback is not a function");
+        }
+        ctx.enqueue_job(callback, Vec::new());
+        Ok(Value::undefined())
+    }
+
+    /// Initialise the `queueMicrotask` function on the global object.
+    #[inline]
+    pub(crate) fn init(global: &Value) {
+        let _timer = BoaProfiler::global().start_event("queueMicrotask", "init");
+
+        make_builtin_fn(Self::queue_microtask, "queueMicrotask", global, 1);
+    }
+}
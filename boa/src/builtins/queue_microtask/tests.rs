@@ -0,0 +1,69 @@
+use crate::{exec::Interpreter, forward, forward_val, realm::Realm};
+
+#[test]
+fn runs_after_the_script() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var log = [];
+        queueMicrotask(function() { log.push('first'); });
+        queueMicrotask(function() {
+            log.push('second');
+            queueMicrotask(function() { log.push('nested'); });
+        });
+        log.push('script');
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(
+        forward(&mut engine, "log.join()"),
+        "script,first,second,nested"
+    );
+    assert!(!engine.has_pending_jobs());
+}
+
+#[test]
+fn throwing_microtask() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var ran = false;
+        queueMicrotask(function() { throw 'boom'; });
+        queueMicrotask(function() { ran = true; });
+        "#;
+
+    assert_eq!(forward(&mut engine, init), "Error: boom");
+    assert_eq!(forward(&mut engine, "ran"), "true");
+}
+
+#[test]
+fn clear_jobs() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var ran = false;");
+    let callback = forward_val(&mut engine, "(function() { ran = true; })").unwrap();
+    engine.enqueue_job(callback, Vec::new());
+
+    assert!(engine.has_pending_jobs());
+    engine.clear_jobs();
+    assert!(!engine.has_pending_jobs());
+    assert_eq!(forward(&mut engine, "ran"), "false");
+}
+
+#[test]
+fn callback_must_be_a_function() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let scenario = r#"
+        try {
+            queueMicrotask(1);
+        } catch (e) {
+            e.toString()
+        }
+        "#;
+
+    assert_eq!(
+        forward(&mut engine, scenario),
+        "TypeError: queueMicrotask: callback is not a function"
+    );
+}
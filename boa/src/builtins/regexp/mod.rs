@@ -16,7 +16,7 @@ use crate::{
     builtins::{
         object::{InternalState, ObjectData},
         property::Property,
-        value::{RcString, ResultValue, Value},
+        value::{JsString, ResultValue, Value},
     },
     exec::Interpreter,
     BoaProfiler,
@@ -277,6 +277,55 @@ impl RegExp {
         this.with_internal_state_ref(|regex: &RegExp| Ok(Value::from(regex.unicode)))
     }
 
+    /// Checks if a value is a `RegExp` object.
+    fn is_regexp(value: &Value) -> bool {
+        value
+            .as_object()
+            .is_some_and(|object| object.internal_slots().contains_key("RegExpMatcher"))
+    }
+
+    /// `RegExp.prototype.compile( pattern, flags )`
+    ///
+    /// The `compile()` method recompiles a regular expression with a new pattern and flags, and
+    /// resets its `lastIndex`. It is an Annex B legacy feature, see `annex_b`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-regexp.prototype.compile
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/compile
+    pub(crate) fn compile(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        if !Self::is_regexp(this) {
+            return ctx
+                .throw_type_error("RegExp.prototype.compile called on incompatible receiver");
+        }
+        let pattern = args.first().cloned().unwrap_or_else(Value::undefined);
+        let flags = args.get(1).cloned().unwrap_or_else(Value::undefined);
+        let mut args = Vec::with_capacity(2);
+        if Self::is_regexp(&pattern) {
+            if !flags.is_undefined() {
+                return ctx.throw_type_error(
+                    "cannot supply flags when constructing one RegExp from another",
+                );
+            }
+            args.push(pattern);
+        } else {
+            let pattern = if pattern.is_undefined() {
+                Value::from("")
+            } else {
+                Value::from(ctx.to_string(&pattern)?)
+            };
+            args.push(pattern);
+            if !flags.is_undefined() {
+                args.push(Value::from(ctx.to_string(&flags)?));
+            }
+        }
+        Self::make_regexp(this, &args, ctx)?;
+        this.set_field("lastIndex", Value::from(0));
+        Ok(this.clone())
+    }
+
     /// `RegExp.prototype.test( string )`
     ///
     /// The `test()` method executes a search for a match between a regular expression and a specified string.
@@ -372,7 +421,7 @@ impl RegExp {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-regexp.prototype-@@match
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/@@match
-    pub(crate) fn r#match(this: &Value, arg: RcString, ctx: &mut Interpreter) -> ResultValue {
+    pub(crate) fn r#match(this: &Value, arg: JsString, ctx: &mut Interpreter) -> ResultValue {
         let (matcher, flags) = this
             .with_internal_state_ref(|regex: &RegExp| (regex.matcher.clone(), regex.flags.clone()));
         if flags.contains('g') {
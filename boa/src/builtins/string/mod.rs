@@ -17,18 +17,15 @@ use crate::{
     builtins::{
         object::{Object, ObjectData},
         property::Property,
-        value::{RcString, ResultValue, Value},
+        value::{JsString, ResultValue, Value},
         RegExp,
     },
     exec::Interpreter,
     BoaProfiler,
 };
 use regex::Regex;
+use std::cmp::{max, min};
 use std::string::String as StdString;
-use std::{
-    cmp::{max, min},
-    f64::NAN,
-};
 
 /// JavaScript `String` implementation.
 #[derive(Debug, Clone, Copy)]
@@ -45,10 +42,11 @@ impl String {
     /// The range of allowed values can be described like this: `[0, +∞)`.
     ///
     /// The resulting string can also not be larger than the maximum string size,
-    /// which can differ in JavaScript engines. In Boa it is `2^32 - 1`
-    pub(crate) const MAX_STRING_LENGTH: f64 = u32::MAX as f64;
+    /// which can differ in JavaScript engines. In Boa it is `2^32 - 1` by default,
+    /// and can be lowered with `Interpreter::set_max_string_length`.
+    pub(crate) const MAX_STRING_LENGTH: usize = u32::MAX as usize;
 
-    fn this_string_value(this: &Value, ctx: &mut Interpreter) -> Result<RcString, Value> {
+    fn this_string_value(this: &Value, ctx: &mut Interpreter) -> Result<JsString, Value> {
         match this {
             Value::String(ref string) => return Ok(string.clone()),
             Value::Object(ref object) => {
@@ -72,12 +70,10 @@ impl String {
         // to its Javascript Identifier (global constructor method name)
         let string = match args.get(0) {
             Some(ref value) => ctx.to_string(value)?,
-            None => RcString::default(),
+            None => JsString::default(),
         };
 
-        let length = string.chars().count();
-
-        this.set_field("length", Value::from(length as i32));
+        this.set_field("length", Value::from(string.len_utf16()));
 
         this.set_data(ObjectData::String(string.clone()));
 
@@ -117,23 +113,16 @@ impl String {
                 .expect("failed to get argument for String method"),
         );
 
-        // Calling .len() on a string would give the wrong result, as they are bytes not the number of
-        // unicode code points
-        // Note that this is an O(N) operation (because UTF-8 is complex) while getting the number of
-        // bytes is an O(1) operation.
-        let length = primitive_val.chars().count();
+        // The length is the number of UTF-16 code units, not the number of bytes
+        let length = primitive_val.len_utf16();
 
         // We should return an empty string is pos is out of range
         if pos >= length as i32 || pos < 0 {
             return Ok("".into());
         }
 
-        Ok(Value::from(
-            primitive_val
-                .chars()
-                .nth(pos as usize)
-                .expect("failed to get value"),
-        ))
+        let pos = pos as usize;
+        Ok(Value::from(primitive_val.substring(pos, pos + 1)))
     }
 
     /// `String.prototype.charCodeAt( index )`
@@ -155,25 +144,19 @@ impl String {
         // Then we convert it into a Rust String by wrapping it in from_value
         let primitive_val = ctx.to_string(this)?;
 
-        // Calling .len() on a string would give the wrong result, as they are bytes not the number of unicode code points
-        // Note that this is an O(N) operation (because UTF-8 is complex) while getting the number of bytes is an O(1) operation.
-        let length = primitive_val.chars().count();
         let pos = i32::from(
             args.get(0)
                 .expect("failed to get argument for String method"),
         );
 
-        if pos >= length as i32 || pos < 0 {
-            return Ok(Value::from(NAN));
+        // If there is no code unit at that index, the result is NaN
+        if pos < 0 {
+            return Ok(Value::from(f64::NAN));
+        }
+        match primitive_val.code_unit_at(pos as usize) {
+            Some(code_unit) => Ok(Value::from(f64::from(code_unit))),
+            None => Ok(Value::from(f64::NAN)),
         }
-
-        let utf16_val = primitive_val
-            .encode_utf16()
-            .nth(pos as usize)
-            .expect("failed to get utf16 value");
-        // If there is no element at that index, the result is NaN
-        // TODO: We currently don't have NaN
-        Ok(Value::from(f64::from(utf16_val)))
     }
 
     /// `String.prototype.concat( str1[, ...strN] )`
@@ -192,10 +175,13 @@ impl String {
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/concat
     pub(crate) fn concat(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
         let object = ctx.require_object_coercible(this)?;
-        let mut string = ctx.to_string(object)?.to_string();
+        let mut string = ctx.to_string(object)?;
 
         for arg in args {
-            string.push_str(&ctx.to_string(arg)?);
+            let arg = ctx.to_string(arg)?;
+            ctx.check_memory(string.len_utf16() + arg.len_utf16())?;
+            string = string.concat(&arg);
+            ctx.check_string_length(string.len_utf16() as f64)?;
         }
 
         Ok(Value::from(string))
@@ -226,10 +212,11 @@ impl String {
                 return ctx.throw_range_error("repeat count cannot be infinity");
             }
 
-            if n * (string.len() as f64) > Self::MAX_STRING_LENGTH {
+            if n * (string.len_utf16() as f64) > ctx.max_string_length() as f64 {
                 return ctx
                     .throw_range_error("repeat count must not overflow maximum string length");
             }
+            ctx.check_memory(n as usize * string.len_utf16())?;
             Ok(string.repeat(n as usize).into())
         } else {
             Ok("".into())
@@ -258,9 +245,7 @@ impl String {
 
         let end = i32::from(args.get(1).expect("failed to get argument in slice"));
 
-        // Calling .len() on a string would give the wrong result, as they are bytes not the number of unicode code points
-        // Note that this is an O(N) operation (because UTF-8 is complex) while getting the number of bytes is an O(1) operation.
-        let length = primitive_val.chars().count() as i32;
+        let length = primitive_val.len_utf16() as i32;
 
         let from = if start < 0 {
             max(length.wrapping_add(start), 0)
@@ -275,12 +260,9 @@ impl String {
 
         let span = max(to.wrapping_sub(from), 0);
 
-        let new_str: StdString = primitive_val
-            .chars()
-            .skip(from as usize)
-            .take(span as usize)
-            .collect();
-        Ok(Value::from(new_str))
+        Ok(Value::from(
+            primitive_val.substring(from as usize, (from + span) as usize),
+        ))
     }
 
     /// `String.prototype.startWith( searchString[, position] )`
@@ -304,8 +286,8 @@ impl String {
                 .expect("failed to get argument for String method"),
         )?;
 
-        let length = primitive_val.chars().count() as i32;
-        let search_length = search_string.chars().count() as i32;
+        let length = primitive_val.len_utf16() as i32;
+        let search_length = search_string.len_utf16() as i32;
 
         // If less than 2 args specified, position is 'undefined', defaults to 0
         let position = if args.len() < 2 {
@@ -321,7 +303,7 @@ impl String {
             Ok(Value::from(false))
         } else {
             // Only use the part of the string from "start"
-            let this_string: StdString = primitive_val.chars().skip(start as usize).collect();
+            let this_string = primitive_val.substring(start as usize, length as usize);
             Ok(Value::from(this_string.starts_with(search_string.as_str())))
         }
     }
@@ -347,8 +329,8 @@ impl String {
                 .expect("failed to get argument for String method"),
         )?;
 
-        let length = primitive_val.chars().count() as i32;
-        let search_length = search_string.chars().count() as i32;
+        let length = primitive_val.len_utf16() as i32;
+        let search_length = search_string.len_utf16() as i32;
 
         // If less than 2 args specified, end_position is 'undefined', defaults to
         // length of this
@@ -365,7 +347,7 @@ impl String {
             Ok(Value::from(false))
         } else {
             // Only use the part of the string up to "end"
-            let this_string: StdString = primitive_val.chars().take(end as usize).collect();
+            let this_string = primitive_val.substring(0, end as usize);
             Ok(Value::from(this_string.ends_with(search_string.as_str())))
         }
     }
@@ -391,8 +373,6 @@ impl String {
                 .expect("failed to get argument for String method"),
         )?;
 
-        let length = primitive_val.chars().count() as i32;
-
         // If less than 2 args specified, position is 'undefined', defaults to 0
         let position = if args.len() < 2 {
             0
@@ -400,12 +380,11 @@ impl String {
             i32::from(args.get(1).expect("Could not get argument"))
         };
 
-        let start = min(max(position, 0), length);
-
-        // Take the string from "this" and use only the part of it after "start"
-        let this_string: StdString = primitive_val.chars().skip(start as usize).collect();
+        let start = max(position, 0) as usize;
 
-        Ok(Value::from(this_string.contains(search_string.as_str())))
+        Ok(Value::from(
+            primitive_val.index_of(&search_string, start).is_some(),
+        ))
     }
 
     /// Return either the string itself or the string of the regex equivalent
@@ -525,11 +504,75 @@ impl String {
             "undefined".to_string()
         };
 
-        Ok(Value::from(primitive_val.replacen(
-            &mat.as_str(),
-            &replace_value,
-            1,
-        )))
+        ctx.new_string(primitive_val.replacen(&mat.as_str(), &replace_value, 1))
+    }
+
+    /// `String.prototype.replaceAll( substr, newSubstr|function )`
+    ///
+    /// The `replaceAll()` method returns a new string with all matches of a `pattern` replaced by a `replacement`.
+    ///
+    /// It is an experimental feature, see `Experimental::STRING_REPLACE_ALL`, which is only
+    /// installed on `String.prototype` when it is enabled. Only strings are supported as the
+    /// `pattern` for now, a `RegExp` throws a `TypeError`. In a replacement string, `$$` and
+    /// `$&` are substituted, and the other patterns are kept as they are.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.replaceall
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/replaceAll
+    pub(crate) fn replace_all(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let primitive_val = ctx.to_string(this)?;
+        let search_value = args.first().cloned().unwrap_or_else(Value::undefined);
+        let is_regexp = search_value
+            .as_object()
+            .is_some_and(|object| object.internal_slots().get("RegExpMatcher").is_some());
+        if is_regexp {
+            return ctx.throw_type_error("replaceAll with a RegExp is not supported yet");
+        }
+        let search_string = ctx.to_string(&search_value)?;
+        let replace_value = args.get(1).cloned().unwrap_or_else(Value::undefined);
+        let replace_string = if replace_value.is_function() {
+            None
+        } else {
+            Some(ctx.to_string(&replace_value)?)
+        };
+
+        let mut result = StdString::new();
+        let mut end = 0;
+        for (start, matched) in primitive_val.match_indices(search_string.as_str()) {
+            result.push_str(&primitive_val[end..start]);
+            if let Some(ref replacement) = replace_string {
+                let mut chars = replacement.chars().peekable();
+                while let Some(c) = chars.next() {
+                    match (c, chars.peek()) {
+                        ('$', Some('$')) => {
+                            chars.next();
+                            result.push('$');
+                        }
+                        ('$', Some('&')) => {
+                            chars.next();
+                            result.push_str(matched);
+                        }
+                        (c, _) => result.push(c),
+                    }
+                }
+            } else {
+                let position = primitive_val[..start].encode_utf16().count();
+                let args = [
+                    Value::from(matched),
+                    Value::from(position),
+                    Value::from(primitive_val.clone()),
+                ];
+                let replacement = ctx.call(&replace_value, &Value::undefined(), &args)?;
+                result.push_str(&ctx.to_string(&replacement)?);
+            }
+            ctx.check_str_length(&result)?;
+            end = start + matched.len();
+        }
+        result.push_str(&primitive_val[end..]);
+        ctx.new_string(result)
     }
 
     /// `String.prototype.indexOf( searchValue[, fromIndex] )`
@@ -555,8 +598,6 @@ impl String {
                 .expect("failed to get argument for String method"),
         )?;
 
-        let length = primitive_val.chars().count() as i32;
-
         // If less than 2 args specified, position is 'undefined', defaults to 0
         let position = if args.len() < 2 {
             0
@@ -564,21 +605,13 @@ impl String {
             i32::from(args.get(1).expect("Could not get argument"))
         };
 
-        let start = min(max(position, 0), length);
+        let start = max(position, 0) as usize;
 
-        // Here cannot use the &str method "find", because this returns the byte
-        // index: we need to return the char index in the JS String
-        // Instead, iterate over the part we're checking until the slice we're
-        // checking "starts with" the search string
-        for index in start..length {
-            let this_string: StdString = primitive_val.chars().skip(index as usize).collect();
-            if this_string.starts_with(search_string.as_str()) {
-                // Explicitly return early with the index value
-                return Ok(Value::from(index));
-            }
+        // The index is in UTF-16 code units, or -1 if there is no match
+        match primitive_val.index_of(&search_string, start) {
+            Some(index) => Ok(Value::from(index)),
+            None => Ok(Value::from(-1)),
         }
-        // Didn't find a match, so return -1
-        Ok(Value::from(-1))
     }
 
     /// `String.prototype.lastIndexOf( searchValue[, fromIndex] )`
@@ -608,31 +641,22 @@ impl String {
                 .expect("failed to get argument for String method"),
         )?;
 
-        let length = primitive_val.chars().count() as i32;
+        let length = primitive_val.len_utf16() as i32;
 
-        // If less than 2 args specified, position is 'undefined', defaults to 0
+        // If less than 2 args specified, position is 'undefined', and the whole string is searched
         let position = if args.len() < 2 {
-            0
+            length
         } else {
             i32::from(args.get(1).expect("Could not get argument"))
         };
 
-        let start = min(max(position, 0), length);
+        let start = max(position, 0) as usize;
 
-        // Here cannot use the &str method "rfind", because this returns the last
-        // byte index: we need to return the last char index in the JS String
-        // Instead, iterate over the part we're checking keeping track of the higher
-        // index we found that "starts with" the search string
-        let mut highest_index = -1;
-        for index in start..length {
-            let this_string: StdString = primitive_val.chars().skip(index as usize).collect();
-            if this_string.starts_with(search_string.as_str()) {
-                highest_index = index;
-            }
+        // The index is in UTF-16 code units, or -1 if there is no match
+        match primitive_val.last_index_of(&search_string, start) {
+            Some(index) => Ok(Value::from(index)),
+            None => Ok(Value::from(-1)),
         }
-
-        // This will still be -1 if no matches were found, else with be >= 0
-        Ok(Value::from(highest_index))
     }
 
     /// `String.prototype.match( regexp )`
@@ -656,32 +680,35 @@ impl String {
     /// Performs the actual string padding for padStart/End.
     /// <https://tc39.es/ecma262/#sec-stringpad/>
     fn string_pad(
-        primitive: RcString,
+        primitive: JsString,
         max_length: i32,
-        fill_string: Option<RcString>,
+        fill_string: Option<JsString>,
         at_start: bool,
+        ctx: &mut Interpreter,
     ) -> ResultValue {
-        let primitive_length = primitive.len() as i32;
+        let primitive_length = primitive.len_utf16() as i32;
 
         if max_length <= primitive_length {
             return Ok(Value::from(primitive));
         }
 
-        let filter = fill_string.as_deref().unwrap_or(" ");
+        ctx.check_string_length(f64::from(max_length))?;
 
-        let fill_len = max_length.wrapping_sub(primitive_length);
-        let mut fill_str = StdString::new();
-
-        while fill_str.len() < fill_len as usize {
-            fill_str.push_str(filter);
+        let filter = fill_string.unwrap_or_else(|| JsString::from(" "));
+        if filter.is_empty() {
+            return Ok(Value::from(primitive));
         }
+
+        let fill_len = max_length.wrapping_sub(primitive_length) as usize;
+        let fill_str = filter.repeat(fill_len / filter.len_utf16() + 1);
+
         // Cut to size max_length
-        let concat_fill_str: StdString = fill_str.chars().take(fill_len as usize).collect();
+        let concat_fill_str = fill_str.substring(0, fill_len);
 
         if at_start {
-            Ok(Value::from(format!("{}{}", concat_fill_str, &primitive)))
+            Ok(Value::from(concat_fill_str.concat(&primitive)))
         } else {
-            Ok(Value::from(format!("{}{}", primitive, &concat_fill_str)))
+            Ok(Value::from(primitive.concat(&concat_fill_str)))
         }
     }
 
@@ -709,7 +736,7 @@ impl String {
 
         let fill_string = args.get(1).map(|arg| ctx.to_string(arg)).transpose()?;
 
-        Self::string_pad(primitive, max_length, fill_string, false)
+        Self::string_pad(primitive, max_length, fill_string, false, ctx)
     }
 
     /// `String.prototype.padStart( targetLength [, padString] )`
@@ -736,7 +763,7 @@ impl String {
 
         let fill_string = args.get(1).map(|arg| ctx.to_string(arg)).transpose()?;
 
-        Self::string_pad(primitive, max_length, fill_string, true)
+        Self::string_pad(primitive, max_length, fill_string, true, ctx)
     }
 
     /// Helper function to check if a `char` is trimmable.
@@ -836,7 +863,7 @@ impl String {
         let this_str = ctx.to_string(this)?;
         // The Rust String is mapped to uppercase using the builtin .to_lowercase().
         // There might be corner cases where it does not behave exactly like Javascript expects
-        Ok(Value::from(this_str.to_lowercase()))
+        ctx.new_string(this_str.to_lowercase())
     }
 
     /// `String.prototype.toUpperCase()`
@@ -858,7 +885,7 @@ impl String {
         let this_str = ctx.to_string(this)?;
         // The Rust String is mapped to uppercase using the builtin .to_uppercase().
         // There might be corner cases where it does not behave exactly like Javascript expects
-        Ok(Value::from(this_str.to_uppercase()))
+        ctx.new_string(this_str.to_uppercase())
     }
 
     /// `String.prototype.substring( indexStart[, indexEnd] )`
@@ -884,7 +911,7 @@ impl String {
                     .expect("failed to get argument for String method"),
             )
         };
-        let length = primitive_val.chars().count() as i32;
+        let length = primitive_val.len_utf16() as i32;
         // If less than 2 args specified, end is the length of the this object converted to a String
         let end = if args.len() < 2 {
             length
@@ -900,12 +927,7 @@ impl String {
         let to = max(final_start, final_end) as usize;
         // Extract the part of the string contained between the start index and the end index
         // where start is guaranteed to be smaller or equals to end
-        let extracted_string: StdString = primitive_val
-            .chars()
-            .skip(from)
-            .take(to.wrapping_sub(from))
-            .collect();
-        Ok(Value::from(extracted_string))
+        Ok(Value::from(primitive_val.substring(from, to)))
     }
 
     /// `String.prototype.substr( start[, length] )`
@@ -932,7 +954,7 @@ impl String {
                     .expect("failed to get argument for String method"),
             )
         };
-        let length = primitive_val.chars().count() as i32;
+        let length = primitive_val.len_utf16() as i32;
         // If less than 2 args specified, end is +infinity, the maximum number value.
         // Using i32::max_value() should be safe because the final length used is at most
         // the number of code units from start to the end of the string,
@@ -954,13 +976,10 @@ impl String {
         if result_length <= 0 {
             Ok(Value::from(""))
         } else {
-            let extracted_string: StdString = primitive_val
-                .chars()
-                .skip(start as usize)
-                .take(result_length as usize)
-                .collect();
-
-            Ok(Value::from(extracted_string))
+            let start = start as usize;
+            Ok(Value::from(
+                primitive_val.substring(start, start + result_length as usize),
+            ))
         }
     }
 
@@ -1052,7 +1071,6 @@ impl String {
         make_builtin_fn(Self::to_lowercase, "toLowerCase", &prototype, 0);
         make_builtin_fn(Self::to_uppercase, "toUpperCase", &prototype, 0);
         make_builtin_fn(Self::substring, "substring", &prototype, 2);
-        make_builtin_fn(Self::substr, "substr", &prototype, 2);
         make_builtin_fn(Self::value_of, "valueOf", &prototype, 0);
         make_builtin_fn(Self::match_all, "matchAll", &prototype, 1);
         make_builtin_fn(Self::replace, "replace", &prototype, 2);
@@ -1,8 +1,6 @@
 use crate::{exec::Interpreter, forward, forward_val, realm::Realm};
 
-///TODO: re-enable when getProperty() is finished;
 #[test]
-#[ignore]
 fn length() {
     //TEST262: https://github.com/tc39/test262/blob/master/test/built-ins/String/length.js
     let realm = Realm::create();
@@ -11,21 +9,20 @@ fn length() {
     const a = new String(' ');
     const b = new String('\ud834\udf06');
     const c = new String(' \b ');
-    cosnt d = new String('中文长度')
+    const d = new String('中文长度');
     "#;
-    eprintln!("{}", forward(&mut engine, init));
+    forward(&mut engine, init);
     let a = forward(&mut engine, "a.length");
     assert_eq!(a, "1");
+    // The length counts UTF-16 code units, so a surrogate pair has a length of 2
     let b = forward(&mut engine, "b.length");
-    // TODO: fix this
-    // unicode surrogate pair length should be 1
-    // utf16/usc2 length should be 2
-    // utf8 length should be 4
     assert_eq!(b, "2");
     let c = forward(&mut engine, "c.length");
     assert_eq!(c, "3");
     let d = forward(&mut engine, "d.length");
     assert_eq!(d, "4");
+    assert_eq!(forward(&mut engine, r"'\ud834\udf06'.length"), "2");
+    assert_eq!(forward(&mut engine, "'abc'.length"), "3");
 }
 
 #[test]
@@ -188,6 +185,116 @@ fn repeat_throws_when_count_overflows_max_length() {
     );
 }
 
+#[test]
+fn repeat_throws_when_count_overflows_configured_max_length() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.set_max_string_length(16);
+
+    assert_eq!(forward(&mut engine, "'abcd'.repeat(4)"), "abcdabcdabcdabcd");
+    assert_eq!(
+        forward(
+            &mut engine,
+            r#"
+        try {
+            'abcd'.repeat(5)
+        } catch (e) {
+            e.toString()
+        }
+    "#
+        ),
+        "RangeError: repeat count must not overflow maximum string length"
+    );
+}
+
+#[test]
+fn concatenation_throws_when_overflowing_configured_max_length() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.set_max_string_length(8);
+
+    let scenario = r#"
+        var results = [];
+        try { 'abcd' + 'efghi' } catch (e) { results.push(e.toString()) }
+        try { 'abcd'.concat('efgh', 'i') } catch (e) { results.push(e.toString()) }
+        try { 'abcd'.padEnd(9) } catch (e) { results.push(e.toString()) }
+        results.length
+    "#;
+
+    // The results are not joined, because the joined string would exceed the maximum too.
+    assert_eq!(forward(&mut engine, scenario), "3");
+    for index in 0..3 {
+        assert_eq!(
+            forward(&mut engine, &format!("results[{}]", index)),
+            "RangeError: invalid string length"
+        );
+    }
+    assert_eq!(forward(&mut engine, "'abcd' + 'efgh'"), "abcdefgh");
+}
+
+#[test]
+fn max_length_counts_utf16_code_units() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.set_max_string_length(4);
+
+    // Each of these characters is a single code unit, but takes three bytes in UTF-8.
+    assert_eq!(
+        forward(
+            &mut engine,
+            "('\u{20AC}\u{20AC}' + '\u{20AC}\u{20AC}').length"
+        ),
+        "4"
+    );
+    assert_eq!(
+        forward(&mut engine, "['\u{20AC}', '\u{20AC}'].join('--').length"),
+        "4"
+    );
+    assert_eq!(
+        forward(&mut engine, "'\u{20AC}\u{20AC}'.repeat(2).length"),
+        "4"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { '\u{1F600}' + '\u{1F600}!' } catch (e) { e.toString() }"
+        ),
+        "RangeError: invalid string length"
+    );
+}
+
+#[test]
+fn builders_throw_when_overflowing_configured_max_length() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.set_max_string_length(8);
+
+    let range_error = "RangeError: invalid string length";
+    let catch = |code: &str| format!("try {{ {} }} catch (e) {{ e.toString() }}", code);
+    assert_eq!(
+        forward(&mut engine, &catch("['abcd', 'efgh'].join('-')")),
+        range_error
+    );
+    assert_eq!(
+        forward(&mut engine, &catch("['abcd', 'efghi'].toString()")),
+        range_error
+    );
+    assert_eq!(
+        forward(&mut engine, &catch("'abcd'.replace('b', 'bbbbbb')")),
+        range_error
+    );
+    assert_eq!(
+        forward(&mut engine, &catch("'ΐΐΐ'.toUpperCase()")),
+        range_error
+    );
+    assert_eq!(
+        forward(&mut engine, &catch("JSON.stringify({ a: 'bcdef' })")),
+        range_error
+    );
+    assert_eq!(forward(&mut engine, "['abc', 'def'].join('-')"), "abc-def");
+    assert_eq!(forward(&mut engine, "JSON.stringify([1, 2])"), "[1,2]");
+}
+
 #[test]
 fn repeat_generic() {
     let realm = Realm::create();
@@ -409,3 +516,74 @@ fn trim_end() {
     assert_eq!(forward(&mut engine, "'Hello \n'.trimEnd()"), "Hello");
     assert_eq!(forward(&mut engine, "' Hello '.trimEnd()"), " Hello");
 }
+
+#[test]
+fn indices_count_utf16_code_units() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var s = 'a\u{1F600}b';");
+    assert_eq!(forward(&mut engine, "s.charCodeAt(1)"), "55357");
+    assert_eq!(forward(&mut engine, "s.charCodeAt(2)"), "56832");
+    assert_eq!(forward(&mut engine, "s.charCodeAt(4)"), "NaN");
+    assert_eq!(forward(&mut engine, "s.charAt(3)"), "b");
+    assert_eq!(forward(&mut engine, "s.slice(1, 3)"), "\u{1F600}");
+    assert_eq!(forward(&mut engine, "s.substring(3)"), "b");
+    assert_eq!(forward(&mut engine, "s.substr(0, 2)"), "a\u{FFFD}");
+    assert_eq!(forward(&mut engine, "s.indexOf('b')"), "3");
+    assert_eq!(forward(&mut engine, "s.lastIndexOf('a')"), "0");
+    assert_eq!(forward(&mut engine, "'abcabc'.lastIndexOf('c', 4)"), "2");
+    assert_eq!(forward(&mut engine, "s.padEnd(6, 'x')"), "a\u{1F600}bxx");
+}
+
+#[test]
+fn lone_surrogates_are_kept() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert_eq!(forward(&mut engine, "'\\uD83D'.length"), "1");
+    assert_eq!(forward(&mut engine, "'\\uD83D'.charCodeAt(0)"), "55357");
+    assert_eq!(forward(&mut engine, "'\\uD83D' === '\\uFFFD'"), "false");
+    assert_eq!(
+        forward(&mut engine, "'\\uD83D' + '\\uDE00' === '\u{1F600}'"),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut engine, "'\\uD83D\\uDE00' === '\u{1F600}'"),
+        "true"
+    );
+    assert_eq!(forward(&mut engine, "'\\u0041\\u0042'"), "AB");
+    assert_eq!(
+        forward(&mut engine, "'x\\uDE00'.concat('y').charCodeAt(1)"),
+        "56832"
+    );
+    assert_eq!(
+        forward(&mut engine, "'\\uDE00\\uD83D'.indexOf('\\uD83D')"),
+        "1"
+    );
+    assert_eq!(
+        forward(&mut engine, "'\\uD83D'.repeat(2).charCodeAt(1)"),
+        "55357"
+    );
+    assert_eq!(
+        forward(&mut engine, "'a'.padEnd(3, '\\uDE00').charCodeAt(2)"),
+        "56832"
+    );
+}
+
+#[test]
+fn astral_characters_are_surrogate_pairs() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var s = '\u{1F600}';");
+    assert_eq!(forward(&mut engine, "s.length"), "2");
+    assert_eq!(forward(&mut engine, "s.charCodeAt(0)"), "55357");
+    assert_eq!(forward(&mut engine, "s.charAt(1).charCodeAt(0)"), "56832");
+    assert_eq!(
+        forward(&mut engine, "s.slice(0, 1) + s.slice(1, 2) === s"),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut engine, "s.slice(0, 1) === s.slice(1, 2)"),
+        "false"
+    );
+    assert_eq!(forward(&mut engine, "'\u{1F600}' > '\u{FFFF}'"), "false");
+}
@@ -0,0 +1,200 @@
+//! This module implements the global `structuredClone` function.
+//!
+//! `structuredClone` creates a deep copy of a value using the structured clone algorithm of the
+//! HTML standard. Objects that are reachable more than once, including through cycles, are
+//! copied only once, so the copy has the same shape as the original.
+//!
+//! More information:
+//!  - [HTML reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/structured-data.html#dom-structuredclone
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/structuredClone
+
+#![allow(clippy::mutable_key_type)]
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builtins::{
+        function::make_builtin_fn,
+        object::{ObjectData, PROTOTYPE},
+        property::PropertyKey,
+        value::{ResultValue, Value},
+        Array, Map, RegExp,
+    },
+    exec::Interpreter,
+    BoaProfiler,
+};
+use rustc_hash::FxHashMap;
+
+/// The error types whose instances keep their type when they are cloned.
+const ERROR_NAMES: [&str; 6] = [
+    "Error",
+    "RangeError",
+    "ReferenceError",
+    "SyntaxError",
+    "TypeError",
+    "URIError",
+];
+
+/// The `structuredClone` host function.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StructuredClone;
+
+impl StructuredClone {
+    /// `structuredClone( value )`
+    ///
+    /// Returns a deep copy of `value`. Throws a `TypeError` if the value contains something that
+    /// cannot be cloned, like a function or a symbol.
+    ///
+    /// More information:
+    ///  - [HTML reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/structured-data.html#dom-structuredclone
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/structuredClone
+    pub(crate) fn structured_clone(
+        _: &Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let value = args.get(0).cloned().unwrap_or_default();
+        clone_value(&value, &mut FxHashMap::default(), ctx)
+    }
+
+    /// Initialise the `structuredClone` function on the global object.
+    #[inline]
+    pub(crate) fn init(global: &Value) {
+        let _timer = BoaProfiler::global().start_event("structuredClone", "init");
+
+        make_builtin_fn(Self::structured_clone, "structuredClone", global, 1);
+    }
+}
+
+/// Clones `value`, reusing the copies in `memory` for objects that have been cloned already.
+///
+/// More information:
+///  - [HTML reference](https://html.spec.whatwg.org/multipage/structured-data.html#structuredserializeinternal)
+pub(crate) fn clone_value(
+    value: &Value,
+    memory: &mut FxHashMap<Value, Value>,
+    ctx: &mut Interpreter,
+) -> ResultValue {
+    match value {
+        Value::Symbol(_) => return ctx.throw_type_error("Symbol could not be cloned"),
+        Value::Object(_) => {}
+        _ => return Ok(value.clone()),
+    }
+    if let Some(copy) = memory.get(value) {
+        return Ok(copy.clone());
+    }
+
+    if let Some(state) = value.get_internal_state() {
+        if state.downcast_ref::<RegExp>().is_none() {
+            return ctx.throw_type_error("object could not be cloned");
+        }
+        let copy = Value::new_object(Some(&ctx.realm().global_obj));
+        let source = value.get_internal_slot("OriginalSource");
+        let flags = value.get_internal_slot("OriginalFlags");
+        RegExp::make_regexp(&copy, &[source, flags], ctx)?;
+        copy.as_object_mut().expect("regexp object").set_prototype(
+            ctx.realm()
+                .global_obj
+                .get_field("RegExp")
+                .get_field(PROTOTYPE),
+        );
+        memory.insert(value.clone(), copy.clone());
+        return Ok(copy);
+    }
+
+    let data = value.as_object().expect("object").data.clone();
+    let copy = match &data {
+        ObjectData::Boolean(boolean) => ctx.to_object(&Value::from(*boolean))?,
+        ObjectData::Number(number) => ctx.to_object(&Value::from(*number))?,
+        ObjectData::String(string) => ctx.to_object(&Value::from(string.clone()))?,
+        ObjectData::BigInt(bigint) => ctx.to_object(&Value::from(bigint.clone()))?,
+        ObjectData::Symbol(_) => return ctx.throw_type_error("Symbol could not be cloned"),
+        ObjectData::Function(_) => return ctx.throw_type_error("function could not be cloned"),
+        ObjectData::Map(map) => {
+            let copy = Value::new_object(Some(&ctx.realm().global_obj));
+            Map::make_map(&copy, &[], ctx)?;
+            memory.insert(value.clone(), copy.clone());
+            for (key, value) in map.iter() {
+                let key = clone_value(key, memory, ctx)?;
+                let value = clone_value(value, memory, ctx)?;
+                Map::set(&copy, &[key, value], ctx)?;
+            }
+            return Ok(copy);
+        }
+        ObjectData::Error => {
+            let name = ctx.get_field(value, "name")?;
+            let name = ctx.to_string(&name)?;
+            let constructor = if ERROR_NAMES.contains(&name.as_str()) {
+                name.as_str()
+            } else {
+                "Error"
+            };
+            let prototype = ctx
+                .realm()
+                .global_obj
+                .get_field(constructor)
+                .get_field(PROTOTYPE);
+            let copy = Value::new_object_from_prototype(prototype, ObjectData::Error);
+            let message_key = PropertyKey::from("message");
+            let has_message = value
+                .as_object()
+                .expect("error object")
+                .property(&message_key)
+                .is_some();
+            if has_message {
+                let message = ctx.get_field(value, message_key.clone())?;
+                copy.set_field(message_key, ctx.to_string(&message)?);
+            }
+            copy
+        }
+        ObjectData::Array => {
+            let copy = Array::new_array(ctx)?;
+            copy.set_field("length", value.get_field("length"));
+            memory.insert(value.clone(), copy.clone());
+            copy_properties(value, &copy, memory, ctx)?;
+            return Ok(copy);
+        }
+        ObjectData::Ordinary => {
+            let copy = Value::new_object(Some(&ctx.realm().global_obj));
+            memory.insert(value.clone(), copy.clone());
+            copy_properties(value, &copy, memory, ctx)?;
+            return Ok(copy);
+        }
+    };
+
+    memory.insert(value.clone(), copy.clone());
+    Ok(copy)
+}
+
+/// Clones the enumerable own string-keyed properties of `from` into `to`.
+fn copy_properties(
+    from: &Value,
+    to: &Value,
+    memory: &mut FxHashMap<Value, Value>,
+    ctx: &mut Interpreter,
+) -> Result<(), Value> {
+    let keys = from.as_object().expect("object").own_property_keys();
+    for key in keys {
+        let enumerable = match key {
+            PropertyKey::Symbol(_) => false,
+            _ => matches!(
+                from.as_object().expect("object").property(&key),
+                Some(property) if property.enumerable()
+            ),
+        };
+        if !enumerable {
+            continue;
+        }
+        let value = ctx.get_field(from, key.clone())?;
+        let value = clone_value(&value, memory, ctx)?;
+        to.set_field(key, value);
+    }
+    Ok(())
+}
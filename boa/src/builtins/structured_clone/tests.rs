@@ -0,0 +1,120 @@
+use crate::{exec::Interpreter, forward, realm::Realm};
+
+#[test]
+fn primitives() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    assert_eq!(forward(&mut engine, "structuredClone(1.5)"), "1.5");
+    assert_eq!(forward(&mut engine, "structuredClone('abc')"), "abc");
+    assert_eq!(forward(&mut engine, "structuredClone(null)"), "null");
+    assert_eq!(forward(&mut engine, "structuredClone()"), "undefined");
+}
+
+#[test]
+fn objects_and_arrays() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var original = { a: 1, b: { c: [1, , 3] } };
+        var copy = structuredClone(original);
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "copy === original"), "false");
+    assert_eq!(forward(&mut engine, "copy.b === original.b"), "false");
+    assert_eq!(forward(&mut engine, "copy.a"), "1");
+    assert_eq!(forward(&mut engine, "Array.isArray(copy.b.c)"), "true");
+    assert_eq!(forward(&mut engine, "copy.b.c.length"), "3");
+    assert_eq!(forward(&mut engine, "1 in copy.b.c"), "false");
+    assert_eq!(forward(&mut engine, "copy.b.c[2]"), "3");
+}
+
+#[test]
+fn cycles() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var shared = { x: 1 };
+        var original = { first: shared, second: shared };
+        original.self = original;
+        var copy = structuredClone(original);
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "copy.self === copy"), "true");
+    assert_eq!(forward(&mut engine, "copy.first === copy.second"), "true");
+    assert_eq!(forward(&mut engine, "copy.first === shared"), "false");
+}
+
+#[test]
+fn maps_and_regexps() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var key = { k: 1 };
+        var map = new Map([[key, 'value']]);
+        var mapCopy = structuredClone(map);
+        var regexp = structuredClone(/a+b/gi);
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "mapCopy === map"), "false");
+    assert_eq!(forward(&mut engine, "mapCopy.size"), "1");
+    assert_eq!(forward(&mut engine, "mapCopy.has(key)"), "false");
+    assert_eq!(forward(&mut engine, "regexp.toString()"), "/a+b/gi");
+    assert_eq!(forward(&mut engine, "regexp.test('xAAB')"), "true");
+}
+
+#[test]
+fn errors() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let scenario = r#"
+        var error, copy;
+        try {
+            'a'.repeat(-1);
+        } catch (e) {
+            error = e;
+            copy = structuredClone(e);
+        }
+        "#;
+    forward(&mut engine, scenario);
+
+    assert_eq!(forward(&mut engine, "copy === error"), "false");
+    assert_eq!(
+        forward(&mut engine, "copy.toString()"),
+        "RangeError: repeat count cannot be a negative number"
+    );
+}
+
+#[test]
+fn uncloneable_values() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let scenario = r#"
+        try {
+            structuredClone({ f: function() {} });
+        } catch (e) {
+            e.toString()
+        }
+        "#;
+
+    assert_eq!(
+        forward(&mut engine, scenario),
+        "TypeError: function could not be cloned"
+    );
+    let scenario = r#"
+        try {
+            structuredClone(Symbol());
+        } catch (e) {
+            e.toString()
+        }
+        "#;
+
+    assert_eq!(
+        forward(&mut engine, scenario),
+        "TypeError: Symbol could not be cloned"
+    );
+}
@@ -20,14 +20,76 @@ mod tests;
 
 use super::function::{make_builtin_fn, make_constructor_fn};
 use crate::{
-    builtins::value::{RcString, RcSymbol, ResultValue, Value},
+    builtins::{
+        property::{Attribute, Property, PropertyKey},
+        value::{JsString, RcSymbol, ResultValue, Value},
+    },
     exec::Interpreter,
     BoaProfiler,
 };
 use gc::{Finalize, Trace};
 
 #[derive(Debug, Finalize, Trace, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Symbol(Option<RcString>, u32);
+pub struct Symbol(Option<JsString>, u32);
+
+/// The well-known symbols that the engine consults.
+///
+/// Each of them has a reserved hash, so they are the same in every realm and never collide with
+/// the symbols created by scripts.
+///
+/// More information:
+/// - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-well-known-symbols
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WellKnownSymbol {
+    /// `Symbol.species`, the constructor used to create derived objects.
+    Species,
+    /// `Symbol.toPrimitive`, the method that converts an object to a primitive value.
+    ToPrimitive,
+    /// `Symbol.toStringTag`, the tag used by `Object.prototype.toString`.
+    ToStringTag,
+}
+
+impl WellKnownSymbol {
+    /// Every well-known symbol.
+    pub const ALL: [Self; 3] = [Self::Species, Self::ToPrimitive, Self::ToStringTag];
+
+    /// The name of the well-known symbol, as a property of the `Symbol` constructor.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Species => "species",
+            Self::ToPrimitive => "toPrimitive",
+            Self::ToStringTag => "toStringTag",
+        }
+    }
+
+    /// Returns the symbol value.
+    pub fn symbol(self) -> RcSymbol {
+        let description = format!("Symbol.{}", self.name());
+        RcSymbol::from(Symbol(Some(description.into()), self as u32))
+    }
+
+    /// Returns the symbol as a property key.
+    #[inline]
+    pub fn key(self) -> PropertyKey {
+        PropertyKey::Symbol(self.symbol())
+    }
+}
+
+/// Sets the `@@toStringTag` property of a builtin prototype or namespace object.
+///
+/// More information:
+/// - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-symbol.tostringtag
+pub(crate) fn set_to_string_tag(object: &Value, tag: &str) {
+    let property = Property::data_descriptor(
+        tag.into(),
+        Attribute::READONLY | Attribute::NON_ENUMERABLE | Attribute::CONFIGURABLE,
+    );
+    object.set_property(WellKnownSymbol::ToStringTag.key(), property);
+}
 
 impl Symbol {
     /// The name of the object.
@@ -107,6 +169,7 @@ impl Symbol {
         let prototype = Value::new_object(Some(global));
 
         make_builtin_fn(Self::to_string, "toString", &prototype, 0);
+        set_to_string_tag(&prototype, Self::NAME);
 
         let symbol_object = make_constructor_fn(
             Self::NAME,
@@ -118,6 +181,14 @@ impl Symbol {
             true,
         );
 
+        for well_known in &WellKnownSymbol::ALL {
+            let property = Property::data_descriptor(
+                Value::Symbol(well_known.symbol()),
+                Attribute::READONLY | Attribute::NON_ENUMERABLE | Attribute::PERMANENT,
+            );
+            symbol_object.set_property(well_known.name(), property);
+        }
+
         (Self::NAME, symbol_object)
     }
 }
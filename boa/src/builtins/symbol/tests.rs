@@ -23,3 +23,22 @@ fn print_symbol_expect_description() {
     let sym = forward_val(&mut engine, "sym.toString()").unwrap();
     assert_eq!(sym.to_string(), "Symbol(Hello)");
 }
+
+#[test]
+fn well_known_symbols() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert_eq!(
+        forward(&mut engine, "Symbol.toPrimitive.toString()"),
+        "Symbol(Symbol.toPrimitive)"
+    );
+    assert_eq!(forward(&mut engine, "typeof Symbol.toStringTag"), "symbol");
+    assert_eq!(
+        forward(&mut engine, "Symbol.species === Symbol.species"),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut engine, "Symbol('Symbol.species') === Symbol.species"),
+        "false"
+    );
+}
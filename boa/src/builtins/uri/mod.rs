@@ -0,0 +1,306 @@
+//! This module implements the global URI handling functions.
+//!
+//! The functions `encodeURI`, `decodeURI`, `encodeURIComponent` and `decodeURIComponent`
+//! escape and unescape strings so that they can be used as (components of) URIs. The legacy
+//! `escape` and `unescape` functions of Annex B live here too, and are installed by `annex_b`.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!  - [Annex B reference][annex-b]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-uri-handling-functions
+//! [annex-b]: https://tc39.es/ecma262/#sec-additional-properties-of-the-global-object
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builtins::{
+        function::make_builtin_fn,
+        value::{ResultValue, Value},
+    },
+    exec::Interpreter,
+    BoaProfiler,
+};
+
+/// The characters that have a special meaning in a URI.
+///
+/// More information:
+///  - [ECMAScript reference](https://tc39.es/ecma262/#prod-uriReserved)
+const URI_RESERVED: &str = ";/?:@&=+$,";
+
+/// The non alphanumeric characters that never need to be escaped.
+///
+/// More information:
+///  - [ECMAScript reference](https://tc39.es/ecma262/#prod-uriMark)
+const URI_MARK: &str = "-_.!~*'()";
+
+/// The non alphanumeric characters that `escape` leaves as they are.
+///
+/// More information:
+///  - [ECMAScript reference](https://tc39.es/ecma262/#sec-escape-string)
+const ESCAPE_UNESCAPED: &str = "@*_+-./";
+
+/// JavaScript URI handling functions.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Uri;
+
+impl Uri {
+    /// `encodeURI( uri )`
+    ///
+    /// The `encodeURI()` function encodes a URI by replacing each instance of certain characters
+    /// by escape sequences representing the UTF-8 encoding of the character. Characters with a
+    /// special meaning in a URI are not escaped.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-encodeuri-uri
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/encodeURI
+    pub(crate) fn encode_uri(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let uri = ctx.to_string(&args.get(0).cloned().unwrap_or_default())?;
+        Ok(Value::from(encode(&uri, |c| {
+            is_unreserved(c) || URI_RESERVED.contains(c) || c == '#'
+        })))
+    }
+
+    /// `encodeURIComponent( uriComponent )`
+    ///
+    /// The `encodeURIComponent()` function encodes a URI component, escaping every character
+    /// except the unreserved ones.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-encodeuricomponent-uricomponent
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/encodeURIComponent
+    pub(crate) fn encode_uri_component(
+        _: &Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let component = ctx.to_string(&args.get(0).cloned().unwrap_or_default())?;
+        Ok(Value::from(encode(&component, is_unreserved)))
+    }
+
+    /// `decodeURI( encodedURI )`
+    ///
+    /// The `decodeURI()` function decodes a URI previously created by `encodeURI()`.
+    /// Escape sequences of characters with a special meaning in a URI are left as they are.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-decodeuri-encodeduri
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/decodeURI
+    pub(crate) fn decode_uri(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let uri = ctx.to_string(&args.get(0).cloned().unwrap_or_default())?;
+        match decode(&uri, |c| URI_RESERVED.contains(c) || c == '#') {
+            Some(decoded) => Ok(Value::from(decoded)),
+            None => ctx.throw_uri_error("URI malformed"),
+        }
+    }
+
+    /// `decodeURIComponent( encodedURIComponent )`
+    ///
+    /// The `decodeURIComponent()` function decodes a URI component previously created by
+    /// `encodeURIComponent()`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-decodeuricomponent-encodeduricomponent
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/decodeURIComponent
+    pub(crate) fn decode_uri_component(
+        _: &Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let component = ctx.to_string(&args.get(0).cloned().unwrap_or_default())?;
+        match decode(&component, |_| false) {
+            Some(decoded) => Ok(Value::from(decoded)),
+            None => ctx.throw_uri_error("URI malformed"),
+        }
+    }
+
+    /// `escape( string )`
+    ///
+    /// The `escape()` function replaces the code units of a string that are not ASCII letters,
+    /// digits or one of `@*_+-./` by `%XX` escape sequences, or by `%uXXXX` ones above `0xFF`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-escape-string
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/escape
+    pub(crate) fn escape(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let string = ctx.to_string(&args.first().cloned().unwrap_or_default())?;
+        let mut result = String::with_capacity(string.len());
+        for unit in string.encode_utf16() {
+            match char::from_u32(u32::from(unit)) {
+                Some(c) if c.is_ascii_alphanumeric() || ESCAPE_UNESCAPED.contains(c) => {
+                    result.push(c)
+                }
+                _ if unit < 0x100 => result.push_str(&format!("%{:02X}", unit)),
+                _ => result.push_str(&format!("%u{:04X}", unit)),
+            }
+        }
+        Ok(Value::from(result))
+    }
+
+    /// `unescape( string )`
+    ///
+    /// The `unescape()` function replaces the `%XX` and `%uXXXX` escape sequences of a string by
+    /// the code units they represent, and leaves the malformed ones as they are.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-unescape-string
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/unescape
+    pub(crate) fn unescape(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let string = ctx.to_string(&args.first().cloned().unwrap_or_default())?;
+        let units: Vec<u16> = string.encode_utf16().collect();
+        // Parses the hexadecimal digits of an escape sequence.
+        let hex = |digits: &[u16]| {
+            String::from_utf16(digits)
+                .ok()
+                .filter(|digits| digits.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|digits| u16::from_str_radix(&digits, 16).ok())
+        };
+        let mut result = Vec::with_capacity(units.len());
+        let mut k = 0;
+        while k < units.len() {
+            let unit = units[k];
+            k += 1;
+            if unit != u16::from(b'%') {
+                result.push(unit);
+                continue;
+            }
+            if units.get(k) == Some(&u16::from(b'u')) {
+                if let Some(decoded) = units.get(k + 1..k + 5).and_then(hex) {
+                    result.push(decoded);
+                    k += 5;
+                    continue;
+                }
+            }
+            if let Some(decoded) = units.get(k..k + 2).and_then(hex) {
+                result.push(decoded);
+                k += 2;
+                continue;
+            }
+            result.push(unit);
+        }
+        Ok(Value::from(String::from_utf16_lossy(&result)))
+    }
+
+    /// Initialise the URI handling functions on the global object.
+    #[inline]
+    pub(crate) fn init(global: &Value) {
+        let _timer = BoaProfiler::global().start_event("uri", "init");
+
+        make_builtin_fn(Self::encode_uri, "encodeURI", global, 1);
+        make_builtin_fn(Self::encode_uri_component, "encodeURIComponent", global, 1);
+        make_builtin_fn(Self::decode_uri, "decodeURI", global, 1);
+        make_builtin_fn(Self::decode_uri_component, "decodeURIComponent", global, 1);
+    }
+}
+
+/// Checks if the character is in the `uriUnreserved` set.
+fn is_unreserved(c: char) -> bool {
+    c.is_ascii_alphanumeric() || URI_MARK.contains(c)
+}
+
+/// The abstract operation `Encode`, escaping every character not in the unescaped set.
+///
+/// More information:
+///  - [ECMAScript reference](https://tc39.es/ecma262/#sec-encode)
+fn encode<F>(string: &str, is_unescaped: F) -> String
+where
+    F: Fn(char) -> bool,
+{
+    let mut result = String::with_capacity(string.len());
+    let mut buffer = [0; 4];
+    for c in string.chars() {
+        if is_unescaped(c) {
+            result.push(c);
+        } else {
+            for byte in c.encode_utf8(&mut buffer).bytes() {
+                result.push_str(&format!("%{:02X}", byte));
+            }
+        }
+    }
+    result
+}
+
+/// The abstract operation `Decode`, unescaping every escape sequence that does not
+/// represent a character in the reserved set.
+///
+/// Returns `None` if the string contains a malformed escape sequence or invalid UTF-8.
+///
+/// More information:
+///  - [ECMAScript reference](https://tc39.es/ecma262/#sec-decode)
+fn decode<F>(string: &str, is_reserved: F) -> Option<String>
+where
+    F: Fn(char) -> bool,
+{
+    let bytes = string.as_bytes();
+    let mut result = String::with_capacity(string.len());
+    let mut start = 0;
+    let mut k = 0;
+    while k < bytes.len() {
+        if bytes[k] != b'%' {
+            k += 1;
+            continue;
+        }
+        result.push_str(&string[start..k]);
+
+        let first = decode_escape(bytes, k)?;
+        let sequence_length = match first.leading_ones() {
+            0 => 1,
+            n @ 2..=4 => n as usize,
+            _ => return None,
+        };
+
+        let mut octets = vec![first];
+        for i in 1..sequence_length {
+            let octet = decode_escape(bytes, k + 3 * i)?;
+            if octet & 0b1100_0000 != 0b1000_0000 {
+                return None;
+            }
+            octets.push(octet);
+        }
+        let end = k + 3 * sequence_length;
+
+        // Rejects overlong encodings and surrogates.
+        let decoded = std::str::from_utf8(&octets).ok()?;
+        if decoded.chars().all(&is_reserved) {
+            result.push_str(&string[k..end]);
+        } else {
+            result.push_str(decoded);
+        }
+
+        k = end;
+        start = end;
+    }
+    result.push_str(&string[start..]);
+    Some(result)
+}
+
+/// Decodes the `%XX` escape sequence starting at `index`.
+fn decode_escape(bytes: &[u8], index: usize) -> Option<u8> {
+    match bytes.get(index..index + 3) {
+        Some([b'%', high, low]) => {
+            let high = (*high as char).to_digit(16)?;
+            let low = (*low as char).to_digit(16)?;
+            Some((high * 16 + low) as u8)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{exec::Interpreter, forward, realm::Realm};
+
+#[test]
+fn encode_uri() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    assert_eq!(
+        forward(&mut engine, "encodeURI('http://a.b/c d?e=f&g=h#i')"),
+        "http://a.b/c%20d?e=f&g=h#i"
+    );
+    assert_eq!(forward(&mut engine, "encodeURI('\u{00e9}')"), "%C3%A9");
+    assert_eq!(
+        forward(&mut engine, "encodeURI('\u{1f600}')"),
+        "%F0%9F%98%80"
+    );
+}
+
+#[test]
+fn encode_uri_component() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    assert_eq!(
+        forward(&mut engine, "encodeURIComponent('a b/c?d=e&f#g')"),
+        "a%20b%2Fc%3Fd%3De%26f%23g"
+    );
+    assert_eq!(
+        forward(&mut engine, "encodeURIComponent(\"-_.!~*'()\")"),
+        "-_.!~*'()"
+    );
+}
+
+#[test]
+fn decode_uri() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    assert_eq!(
+        forward(&mut engine, "decodeURI('http://a.b/c%20d%3Fe%23f')"),
+        "http://a.b/c d%3Fe%23f"
+    );
+    assert_eq!(forward(&mut engine, "decodeURI('%C3%A9')"), "\u{00e9}");
+}
+
+#[test]
+fn decode_uri_component() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    assert_eq!(
+        forward(
+            &mut engine,
+            "decodeURIComponent('a%20b%2Fc%3Fd%3De%26f%23g')"
+        ),
+        "a b/c?d=e&f#g"
+    );
+    assert_eq!(
+        forward(&mut engine, "decodeURIComponent('%F0%9F%98%80')"),
+        "\u{1f600}"
+    );
+}
+
+#[test]
+fn malformed_uri_throws() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let catch = |code: &str| format!("try {{ {} }} catch (e) {{ e.toString() }}", code);
+    let uri_error = "URIError: URI malformed";
+    assert_eq!(forward(&mut engine, &catch("decodeURI('%')")), uri_error);
+    assert_eq!(forward(&mut engine, &catch("decodeURI('%G0')")), uri_error);
+    assert_eq!(
+        forward(&mut engine, &catch("decodeURIComponent('%C3')")),
+        uri_error
+    );
+    assert_eq!(
+        forward(&mut engine, &catch("decodeURIComponent('%C0%AF')")),
+        uri_error
+    );
+    assert_eq!(
+        forward(&mut engine, &catch("decodeURIComponent('%ED%A0%80')")),
+        uri_error
+    );
+}
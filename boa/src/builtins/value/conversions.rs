@@ -1,5 +1,5 @@
 use super::*;
-use std::convert::TryFrom;
+use std::{collections::HashMap, convert::TryFrom, hash::BuildHasher};
 
 impl From<&Value> for Value {
     fn from(value: &Value) -> Self {
@@ -38,8 +38,8 @@ impl From<char> for Value {
     }
 }
 
-impl From<RcString> for Value {
-    fn from(value: RcString) -> Self {
+impl From<JsString> for Value {
+    fn from(value: JsString) -> Self {
         Value::String(value)
     }
 }
@@ -140,14 +140,7 @@ where
     T: Clone + Into<Value>,
 {
     fn from(value: &[T]) -> Self {
-        let mut array = Object::default();
-        for (i, item) in value.iter().enumerate() {
-            array.properties_mut().insert(
-                RcString::from(i.to_string()),
-                Property::default().value(item.clone().into()),
-            );
-        }
-        Self::from(array)
+        array_from(value.iter().cloned())
     }
 }
 
@@ -156,17 +149,57 @@ where
     T: Into<Value>,
 {
     fn from(value: Vec<T>) -> Self {
-        let mut array = Object::default();
-        for (i, item) in value.into_iter().enumerate() {
-            array.properties_mut().insert(
-                RcString::from(i.to_string()),
-                Property::default().value(item.into()),
-            );
+        array_from(value)
+    }
+}
+
+impl<T, S> From<HashMap<String, T, S>> for Value
+where
+    T: Into<Value>,
+    S: BuildHasher,
+{
+    fn from(value: HashMap<String, T, S>) -> Self {
+        let mut object = Object::default();
+        for (key, item) in value {
+            object.insert_property(key, element(item.into()));
         }
-        Value::from(array)
+        Value::from(object)
     }
 }
 
+/// Creates an array object from the items.
+///
+/// The array has no prototype, because there is no realm to take `Array.prototype` from.
+fn array_from<I>(items: I) -> Value
+where
+    I: IntoIterator,
+    I::Item: Into<Value>,
+{
+    let mut array = Object::default();
+    array.data = ObjectData::Array;
+    let mut length = 0;
+    for (i, item) in items.into_iter().enumerate() {
+        array.insert_property(i, element(item.into()));
+        length = i + 1;
+    }
+    array.insert_property(
+        "length",
+        Property::data_descriptor(
+            length.into(),
+            Attribute::WRITABLE | Attribute::NON_ENUMERABLE | Attribute::PERMANENT,
+        ),
+    );
+    Value::from(array)
+}
+
+/// Creates the property of an array element or object entry.
+fn element(value: Value) -> Property {
+    Property::data_descriptor(
+        value,
+        Attribute::WRITABLE | Attribute::ENUMERABLE | Attribute::CONFIGURABLE,
+    )
+}
+
 impl From<Object> for Value {
     fn from(object: Object) -> Self {
         let _timer = BoaProfiler::global().start_event("From<Object>", "value");
@@ -207,3 +240,175 @@ where
         }
     }
 }
+
+/// The error returned when a value cannot be converted to a Rust type.
+///
+/// Host functions can turn it into a JavaScript `TypeError`:
+///
+/// ```
+/// use boa::{builtins::value::Value, Context};
+/// use std::convert::TryFrom;
+///
+/// let mut context = Context::new();
+/// context.register_global_function("repeat", 2, |_, args, ctx| {
+///     let arg = |i: usize| args.get(i).cloned().unwrap_or_else(Value::undefined);
+///     let (text, count) = match (String::try_from(arg(0)), i32::try_from(arg(1))) {
+///         (Ok(text), Ok(count)) if count >= 0 => (text, count),
+///         (Err(e), _) | (_, Err(e)) => return ctx.throw_type_error(e.to_string()),
+///         _ => return ctx.throw_range_error("negative count"),
+///     };
+///     Ok(Value::from(text.repeat(count as usize)))
+/// });
+///
+/// assert_eq!(context.eval("repeat('ab', 3)").unwrap().to_string(), "ababab");
+/// assert!(context.eval("repeat('ab', 'x')").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromValueError {
+    expected: &'static str,
+    found: Type,
+}
+
+impl TryFromValueError {
+    /// Creates an error for a value of type `found` where `expected` was needed.
+    fn new(expected: &'static str, value: &Value) -> Self {
+        Self {
+            expected,
+            found: value.get_type(),
+        }
+    }
+
+    /// Returns a description of the expected type, like `"32-bit integer"`.
+    #[inline]
+    pub fn expected(&self) -> &'static str {
+        self.expected
+    }
+
+    /// Returns the type of the value that could not be converted.
+    #[inline]
+    pub fn found(&self) -> Type {
+        self.found
+    }
+}
+
+impl Display for TryFromValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let found = match self.found {
+            Type::Null => "null",
+            ref found => found.as_str(),
+        };
+        write!(f, "expected {}, found {}", self.expected, found)
+    }
+}
+
+impl std::error::Error for TryFromValueError {}
+
+/// Converts a number to an `i32`, if it is an integer in range.
+impl TryFrom<Value> for i32 {
+    type Error = TryFromValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Integer(integer) => Ok(integer),
+            Value::Rational(rational)
+                if rational.trunc() == rational
+                    && rational >= f64::from(i32::MIN)
+                    && rational <= f64::from(i32::MAX) =>
+            {
+                Ok(rational as i32)
+            }
+            _ => Err(TryFromValueError::new("a 32-bit integer", &value)),
+        }
+    }
+}
+
+impl TryFrom<Value> for f64 {
+    type Error = TryFromValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Integer(integer) => Ok(f64::from(integer)),
+            Value::Rational(rational) => Ok(rational),
+            _ => Err(TryFromValueError::new("a number", &value)),
+        }
+    }
+}
+
+impl TryFrom<Value> for bool {
+    type Error = TryFromValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Boolean(boolean) => Ok(boolean),
+            _ => Err(TryFromValueError::new("a boolean", &value)),
+        }
+    }
+}
+
+impl TryFrom<Value> for String {
+    type Error = TryFromValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(ref string) => Ok(string.to_string()),
+            _ => Err(TryFromValueError::new("a string", &value)),
+        }
+    }
+}
+
+/// Converts an array, element by element.
+///
+/// Accessor elements are read as `undefined`, because calling their getters needs an
+/// interpreter.
+impl<T> TryFrom<Value> for Vec<T>
+where
+    T: TryFrom<Value, Error = TryFromValueError>,
+{
+    type Error = TryFromValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        if !matches!(value.as_object(), Some(object) if object.is_array()) {
+            return Err(TryFromValueError::new("an array", &value));
+        }
+        let length = usize::from(&value.get_field("length"));
+        (0..length)
+            .map(|i| T::try_from(value.get_field(i)))
+            .collect()
+    }
+}
+
+/// Converts the own enumerable string-keyed properties of an object.
+///
+/// Accessor properties are read as `undefined`, because calling their getters needs an
+/// interpreter.
+impl<T, S> TryFrom<Value> for HashMap<String, T, S>
+where
+    T: TryFrom<Value, Error = TryFromValueError>,
+    S: BuildHasher + Default,
+{
+    type Error = TryFromValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let object = match value.as_object() {
+            Some(object) => object,
+            None => return Err(TryFromValueError::new("an object", &value)),
+        };
+
+        let indexed = object
+            .indexed_properties()
+            .iter()
+            .map(|(index, property)| (index.to_string(), property));
+        let named = object
+            .properties()
+            .iter()
+            .map(|(key, property)| (key.to_string(), property));
+        indexed
+            .chain(named)
+            .filter(|(_, property)| property.enumerable())
+            .map(|(key, property)| {
+                let item = property.value.clone().unwrap_or_else(Value::undefined);
+                Ok((key, T::try_from(item)?))
+            })
+            .collect()
+    }
+}
@@ -42,17 +42,17 @@ macro_rules! print_obj_value {
     };
     (props of $obj:expr, $display_fn:ident, $indent:expr, $encounters:expr, $print_internals:expr) => {
         print_obj_value!(impl properties, $obj, |(key, val)| {
-            let v = &val
-                .value
-                .as_ref()
-                .expect("Could not get the property's value");
-
-            format!(
-                "{:>width$}: {}",
-                key,
-                $display_fn(v, $encounters, $indent.wrapping_add(4), $print_internals),
-                width = $indent,
-            )
+            let v = match (&val.value, &val.get, &val.set) {
+                (Some(v), _, _) => {
+                    $display_fn(v, $encounters, $indent.wrapping_add(4), $print_internals)
+                }
+                (None, Some(_), Some(_)) => String::from("[Getter/Setter]"),
+                (None, Some(_), None) => String::from("[Getter]"),
+                (None, None, Some(_)) => String::from("[Setter]"),
+                (None, None, None) => String::from("undefined"),
+            };
+
+            format!("{:>width$}: {}", key, v, width = $indent)
         })
     };
 
@@ -105,17 +105,10 @@ pub(crate) fn log_string_from(x: &Value, print_internals: bool, print_children:
                             .map(|i| {
                                 // Introduce recursive call to stringify any objects
                                 // which are part of the Array
-                                log_string_from(
-                                    &v.borrow()
-                                        .properties()
-                                        .get(i.to_string().as_str())
-                                        .unwrap()
-                                        .value
-                                        .clone()
-                                        .expect("Could not borrow value"),
-                                    print_internals,
-                                    false,
-                                )
+                                match v.borrow().get_field(i) {
+                                    Some(value) => log_string_from(value, print_internals, false),
+                                    None => String::from("<empty>"),
+                                }
                             })
                             .collect::<Vec<String>>()
                             .join(", ");
@@ -226,6 +219,327 @@ pub(crate) fn display_obj(v: &Value, print_internals: bool) -> String {
     display_obj_internal(v, &mut encounters, 4, print_internals)
 }
 
+/// Options of `Value::inspect`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InspectOptions {
+    /// How many levels of nested objects are shown, deeper objects are summarized like
+    /// `[Object]` or `Array(3)`.
+    pub depth: usize,
+    /// How many elements, entries or properties are shown for each object.
+    pub max_items: usize,
+    /// The index of the first item of the inspected value that is shown, to page through it.
+    pub offset: usize,
+    /// Whether the keys and values are colored with ANSI escape codes, for a terminal.
+    pub colors: bool,
+}
+
+impl Default for InspectOptions {
+    fn default() -> Self {
+        Self {
+            depth: 2,
+            max_items: 100,
+            offset: 0,
+            colors: false,
+        }
+    }
+}
+
+/// The colors of `Value::inspect`, as ANSI escape codes.
+#[derive(Debug, Clone, Copy)]
+enum Style {
+    Key,
+    String,
+    Number,
+    Null,
+    Undefined,
+    Symbol,
+    /// Functions, summaries and other text that is not a value.
+    Special,
+}
+
+impl Style {
+    fn code(self) -> &'static str {
+        match self {
+            Self::Key => "94",
+            Self::String | Self::Symbol => "32",
+            Self::Number => "33",
+            Self::Null => "1",
+            Self::Undefined => "90",
+            Self::Special => "36",
+        }
+    }
+}
+
+/// The result of `Value::inspect`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inspection {
+    /// The text of the value, on a single line.
+    pub text: String,
+    /// How many items of the inspected value come after the shown ones.
+    pub remaining: usize,
+}
+
+impl Display for Inspection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl Value {
+    /// Formats the value for a console, limiting the output for huge and deeply nested values.
+    ///
+    /// Unlike `Display`, this never prints more than `options.max_items` items per object and
+    /// `options.depth` levels of nesting, and it skips the prototypes. The items that are left
+    /// out are summarized, like `... 999900 more items`.
+    ///
+    /// Accessor properties are shown as `[Getter]` or `[Setter]`, because calling them needs an
+    /// interpreter. Nested strings are quoted, and the properties keyed by symbols are shown
+    /// after the other ones.
+    pub fn inspect(&self, options: &InspectOptions) -> Inspection {
+        let mut inspector = Inspector {
+            options,
+            encounters: HashSet::new(),
+            remaining: 0,
+        };
+        let text = inspector.value(self, 0);
+        Inspection {
+            text,
+            remaining: inspector.remaining,
+        }
+    }
+}
+
+/// The state of `Value::inspect`.
+struct Inspector<'a> {
+    options: &'a InspectOptions,
+    /// The addresses of the objects on the current branch, to detect cycles.
+    encounters: HashSet<usize>,
+    /// The number of items of the inspected value that were left out at the end.
+    remaining: usize,
+}
+
+impl Inspector<'_> {
+    /// Formats a value nested `level` levels deep.
+    fn value(&mut self, value: &Value, level: usize) -> String {
+        let object = match value {
+            Value::Object(ref object) => object,
+            Value::String(ref string) if level > 0 => {
+                let quoted = format!("'{}'", string.replace('\\', "\\\\").replace('\'', "\\'"));
+                return self.paint(quoted, Style::String);
+            }
+            Value::String(_) => return value.to_string(),
+            Value::Rational(_) | Value::Integer(_) | Value::BigInt(_) | Value::Boolean(_) => {
+                return self.paint(value.to_string(), Style::Number)
+            }
+            Value::Null => return self.paint(value.to_string(), Style::Null),
+            Value::Undefined => return self.paint(value.to_string(), Style::Undefined),
+            Value::Symbol(_) => return self.paint(value.to_string(), Style::Symbol),
+        };
+
+        let address = object.as_ref() as *const _ as usize;
+        if self.encounters.contains(&address) {
+            return self.paint(String::from("[Cycle]"), Style::Special);
+        }
+
+        let summary = {
+            let object = object.borrow();
+            match object.data {
+                ObjectData::String(_) | ObjectData::Boolean(_) | ObjectData::Number(_) => {
+                    return self.paint(log_string_from(value, false, false), Style::Special)
+                }
+                ObjectData::Error => {
+                    return format!(
+                        "{}: {}",
+                        value.get_field("name"),
+                        value.get_field("message")
+                    )
+                }
+                ObjectData::Function(_) => {
+                    let name = value.get_field("name");
+                    let text = if name.is_string() && !name.to_string().is_empty() {
+                        format!("[Function: {}]", name)
+                    } else {
+                        String::from("[Function]")
+                    };
+                    return self.paint(text, Style::Special);
+                }
+                ObjectData::Array => format!("Array({})", array_length(value)),
+                ObjectData::Map(ref map) => format!("Map({})", map.len()),
+                _ => String::from("[Object]"),
+            }
+        };
+        if level > self.options.depth {
+            return self.paint(summary, Style::Special);
+        }
+
+        self.encounters.insert(address);
+        let offset = if level == 0 { self.options.offset } else { 0 };
+        let result = match object.borrow().data {
+            ObjectData::Array => {
+                let length = array_length(value);
+                let (shown, remaining) = self.page(length, offset);
+                let items = shown
+                    .map(|i| match value.get_property(i) {
+                        Some(property) => self.property(&property, level),
+                        None => String::from("<empty>"),
+                    })
+                    .collect();
+                self.join("[", items, offset, remaining, "items", "]")
+            }
+            ObjectData::Map(ref map) => {
+                let (shown, remaining) = self.page(map.len(), offset);
+                let items = map
+                    .iter()
+                    .skip(shown.start)
+                    .take(shown.len())
+                    .map(|(key, value)| {
+                        format!(
+                            "{} → {}",
+                            self.value(key, level + 1),
+                            self.value(value, level + 1)
+                        )
+                    })
+                    .collect();
+                self.join("Map {", items, offset, remaining, "entries", "}")
+            }
+            _ => {
+                let object = object.borrow();
+                let mut indices: Vec<_> = object
+                    .indexed_properties()
+                    .iter()
+                    .filter(|(_, property)| property.enumerable())
+                    .map(|(index, _)| *index)
+                    .collect();
+                indices.sort_unstable();
+                // Symbols are numbered in the order they are created.
+                let mut symbols: Vec<_> = object
+                    .symbol_properties()
+                    .iter()
+                    .filter(|(_, property)| property.enumerable())
+                    .map(|(symbol, _)| symbol.clone())
+                    .collect();
+                symbols.sort_by_key(|symbol| symbol.hash());
+                let keys: Vec<PropertyKey> = indices
+                    .into_iter()
+                    .map(PropertyKey::from)
+                    .chain(
+                        object
+                            .properties()
+                            .iter()
+                            .filter(|(_, property)| property.enumerable())
+                            .map(|(key, _)| PropertyKey::from(key)),
+                    )
+                    .chain(symbols.into_iter().map(PropertyKey::from))
+                    .collect();
+                drop(object);
+
+                let (shown, remaining) = self.page(keys.len(), offset);
+                let items = keys[shown]
+                    .iter()
+                    .map(|key| {
+                        let property = value.get_property(key.clone()).unwrap_or_default();
+                        format!("{}: {}", self.key(key), self.property(&property, level))
+                    })
+                    .collect();
+                self.join("{", items, offset, remaining, "properties", "}")
+            }
+        };
+        self.encounters.remove(&address);
+
+        if level == 0 {
+            self.remaining = result.1;
+        }
+        result.0
+    }
+
+    /// Formats the value of a property of an object nested `level` levels deep.
+    fn property(&mut self, property: &Property, level: usize) -> String {
+        let accessor = match (&property.value, &property.get, &property.set) {
+            (Some(value), _, _) => return self.value(value, level + 1),
+            (None, Some(_), Some(_)) => "[Getter/Setter]",
+            (None, Some(_), None) => "[Getter]",
+            (None, None, Some(_)) => "[Setter]",
+            (None, None, None) => return self.paint(String::from("undefined"), Style::Undefined),
+        };
+        self.paint(String::from(accessor), Style::Special)
+    }
+
+    /// Formats a property key, quoting the string keys that are not identifiers.
+    fn key(&self, key: &PropertyKey) -> String {
+        let text = match *key {
+            PropertyKey::Index(index) => index.to_string(),
+            PropertyKey::String(ref string) => {
+                let identifier = !string.starts_with(|c: char| c.is_ascii_digit())
+                    && string
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+                if identifier && !string.is_empty() {
+                    string.to_string()
+                } else {
+                    format!("'{}'", string)
+                }
+            }
+            PropertyKey::Symbol(ref symbol) => {
+                format!("[{}]", Value::Symbol(symbol.clone()))
+            }
+        };
+        self.paint(text, Style::Key)
+    }
+
+    /// Colors the text if the options ask for colors.
+    fn paint(&self, text: String, style: Style) -> String {
+        if self.options.colors {
+            format!("\x1b[{}m{}\x1b[0m", style.code(), text)
+        } else {
+            text
+        }
+    }
+
+    /// Returns the range of the `count` items that are shown, starting at `offset`, and the
+    /// number of items after them.
+    fn page(&self, count: usize, offset: usize) -> (std::ops::Range<usize>, usize) {
+        let start = offset.min(count);
+        let end = start.saturating_add(self.options.max_items).min(count);
+        (start..end, count - end)
+    }
+
+    /// Joins the formatted items, with the summaries of the left out ones.
+    ///
+    /// Returns the text and the number of items left out at the end.
+    fn join(
+        &self,
+        open: &str,
+        mut items: Vec<String>,
+        skipped: usize,
+        remaining: usize,
+        noun: &str,
+        close: &str,
+    ) -> (String, usize) {
+        if skipped > 0 {
+            items.insert(0, format!("... {} previous {}", skipped, noun));
+        }
+        if remaining > 0 {
+            items.push(format!("... {} more {}", remaining, noun));
+        }
+        if items.is_empty() {
+            match open {
+                "Map {" => (String::from("Map(0)"), 0),
+                _ => (format!("{}{}", open, close), 0),
+            }
+        } else {
+            (
+                format!("{} {} {}", open, items.join(", "), close),
+                remaining,
+            )
+        }
+    }
+}
+
+/// Reads the `length` of an array, without calling into JavaScript.
+fn array_length(array: &Value) -> usize {
+    usize::from(&array.get_field("length"))
+}
+
 impl Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
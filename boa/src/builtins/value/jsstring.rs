@@ -0,0 +1,337 @@
+//! This module implements the `JsString` type, the strings of JavaScript.
+//!
+//! JavaScript strings are sequences of UTF-16 code units: their `length`, and the indices that
+//! `charAt`, `charCodeAt` or `slice` take, count code units, and a string can hold a lone
+//! surrogate, which has no UTF-8 encoding. A `JsString` stores its code units, so that indexing
+//! it takes constant time and no code unit is lost. Strings that are only made of ASCII
+//! characters store one byte per code unit, which is also their UTF-8 text.
+//!
+//! The rest of the engine can use a `JsString` as a `&str`. The UTF-8 text of a string that
+//! isn't ASCII is built the first time it is needed, and lone surrogates are replaced by U+FFFD
+//! in it, so the operations of JavaScript should use the code units instead.
+//!
+//! Cloning a `JsString` only increments a reference count. Substrings copy their code units, so
+//! they don't keep the string they are taken from alive.
+
+use std::borrow::{Borrow, Cow};
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{Arc, OnceLock};
+
+use gc::{unsafe_empty_trace, Finalize, Trace};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A reference counted JavaScript string.
+///
+/// A `JsString` is a single pointer, which keeps `Value` two words wide. The count is atomic,
+/// so that the string literals of a script parsed on another thread can be sent with it.
+#[derive(Clone)]
+pub struct JsString(Arc<Inner>);
+
+impl Finalize for JsString {}
+
+unsafe impl Trace for JsString {
+    unsafe_empty_trace!();
+}
+
+#[derive(Debug)]
+struct Inner {
+    units: Units,
+    /// The UTF-8 text of a string that isn't ASCII.
+    text: OnceLock<Box<str>>,
+}
+
+/// The code units of a string.
+///
+/// A string is stored as `Ascii` if and only if all of its code units are ASCII characters, so
+/// two strings with the same code units also have the same representation.
+#[derive(Debug)]
+enum Units {
+    Ascii(Box<str>),
+    Utf16(Box<[u16]>),
+}
+
+impl JsString {
+    fn new(units: Units, text: OnceLock<Box<str>>) -> Self {
+        Self(Arc::new(Inner { units, text }))
+    }
+
+    /// Creates a string from its UTF-16 code units, which may contain lone surrogates.
+    pub fn from_utf16(units: &[u16]) -> Self {
+        if units.iter().all(|&unit| unit < 0x80) {
+            let text: String = units.iter().map(|&unit| unit as u8 as char).collect();
+            Self::new(Units::Ascii(text.into_boxed_str()), OnceLock::new())
+        } else {
+            Self::new(Units::Utf16(Box::from(units)), OnceLock::new())
+        }
+    }
+
+    /// Gets the string as a `&str`, in which lone surrogates are replaced by U+FFFD.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        match self.0.units {
+            Units::Ascii(ref text) => text,
+            Units::Utf16(ref units) => self
+                .0
+                .text
+                .get_or_init(|| String::from_utf16_lossy(units).into_boxed_str()),
+        }
+    }
+
+    /// Gets the length of the string in UTF-16 code units, which is its JavaScript `length`.
+    #[inline]
+    pub fn len_utf16(&self) -> usize {
+        match self.0.units {
+            Units::Ascii(ref text) => text.len(),
+            Units::Utf16(ref units) => units.len(),
+        }
+    }
+
+    /// Gets the UTF-16 code unit at the index.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-ecmascript-language-types-string-type
+    #[inline]
+    pub fn code_unit_at(&self, index: usize) -> Option<u16> {
+        match self.0.units {
+            Units::Ascii(ref text) => text.as_bytes().get(index).map(|&byte| u16::from(byte)),
+            Units::Utf16(ref units) => units.get(index).copied(),
+        }
+    }
+
+    /// Iterates over the UTF-16 code units of the string.
+    pub fn code_units(&self) -> impl Iterator<Item = u16> + '_ {
+        let (bytes, units): (&[u8], &[u16]) = match self.0.units {
+            Units::Ascii(ref text) => (text.as_bytes(), &[]),
+            Units::Utf16(ref units) => (&[], units),
+        };
+        bytes
+            .iter()
+            .map(|&byte| u16::from(byte))
+            .chain(units.iter().copied())
+    }
+
+    /// Gets the UTF-16 code units of the string, which are only copied if it is ASCII.
+    fn units(&self) -> Cow<'_, [u16]> {
+        match self.0.units {
+            Units::Ascii(_) => Cow::Owned(self.code_units().collect()),
+            Units::Utf16(ref units) => Cow::Borrowed(units),
+        }
+    }
+
+    /// Gets the code units `start..end` of the string.
+    ///
+    /// The indices are clamped to the length of the string, and the substring is empty if
+    /// `start` is not before `end`.
+    pub fn substring(&self, start: usize, end: usize) -> Self {
+        let len = self.len_utf16();
+        let end = end.min(len);
+        let start = start.min(end);
+        if start == 0 && end == len {
+            return self.clone();
+        }
+
+        match self.0.units {
+            Units::Ascii(ref text) => Self::from(&text[start..end]),
+            Units::Utf16(ref units) => Self::from_utf16(&units[start..end]),
+        }
+    }
+
+    /// Concatenates two strings, keeping the lone surrogates of both.
+    pub fn concat(&self, other: &Self) -> Self {
+        if let (Units::Ascii(ref x), Units::Ascii(ref y)) = (&self.0.units, &other.0.units) {
+            let mut text = String::with_capacity(x.len() + y.len());
+            text.push_str(x);
+            text.push_str(y);
+            return Self::from(text);
+        }
+        let units: Vec<u16> = self.code_units().chain(other.code_units()).collect();
+        Self::from_utf16(&units)
+    }
+
+    /// Repeats the string `count` times, keeping its lone surrogates.
+    pub fn repeat(&self, count: usize) -> Self {
+        match self.0.units {
+            Units::Ascii(ref text) => Self::from(text.repeat(count)),
+            Units::Utf16(ref units) => Self::from_utf16(&units.repeat(count)),
+        }
+    }
+
+    /// Finds the index in code units of the first occurrence of `search` that starts at or
+    /// after the index `from`.
+    pub fn index_of(&self, search: &Self, from: usize) -> Option<usize> {
+        let from = from.min(self.len_utf16());
+        if let (Units::Ascii(ref text), Units::Ascii(ref search)) = (&self.0.units, &search.0.units)
+        {
+            return text[from..].find(&**search).map(|index| index + from);
+        }
+
+        let (units, search) = (self.units(), search.units());
+        if search.is_empty() {
+            return Some(from);
+        }
+        units[from..]
+            .windows(search.len())
+            .position(|window| window == &*search)
+            .map(|index| index + from)
+    }
+
+    /// Finds the index in code units of the last occurrence of `search` that starts at or
+    /// before the index `from`.
+    pub fn last_index_of(&self, search: &Self, from: usize) -> Option<usize> {
+        let len = self.len_utf16();
+        if search.len_utf16() > len {
+            return None;
+        }
+        let from = from.min(len - search.len_utf16());
+        if let (Units::Ascii(ref text), Units::Ascii(ref search)) = (&self.0.units, &search.0.units)
+        {
+            return text[..from + search.len()].rfind(&**search);
+        }
+
+        let (units, search) = (self.units(), search.units());
+        (0..=from)
+            .rev()
+            .find(|&index| units[index..index + search.len()] == *search)
+    }
+}
+
+impl Default for JsString {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Units::Ascii(Box::default()), OnceLock::new())
+    }
+}
+
+impl Debug for JsString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Display for JsString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq for JsString {
+    fn eq(&self, other: &Self) -> bool {
+        if Arc::ptr_eq(&self.0, &other.0) {
+            return true;
+        }
+        match (&self.0.units, &other.0.units) {
+            (Units::Ascii(ref x), Units::Ascii(ref y)) => x == y,
+            (Units::Utf16(ref x), Units::Utf16(ref y)) => x == y,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for JsString {}
+
+impl PartialOrd for JsString {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Strings are ordered by their code units, like the relational operators of JavaScript order
+/// them.
+impl Ord for JsString {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (&self.0.units, &other.0.units) {
+            (Units::Ascii(ref x), Units::Ascii(ref y)) => x.cmp(y),
+            _ => self.code_units().cmp(other.code_units()),
+        }
+    }
+}
+
+/// Strings are hashed by their text, so that they can be looked up with a `&str`.
+impl Hash for JsString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl PartialEq<str> for JsString {
+    fn eq(&self, other: &str) -> bool {
+        match self.0.units {
+            Units::Ascii(ref text) => &**text == other,
+            Units::Utf16(ref units) => units.iter().copied().eq(other.encode_utf16()),
+        }
+    }
+}
+
+impl PartialEq<JsString> for str {
+    fn eq(&self, other: &JsString) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<&str> for JsString {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<JsString> for &str {
+    fn eq(&self, other: &JsString) -> bool {
+        other == *self
+    }
+}
+
+impl Deref for JsString {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for JsString {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<String> for JsString {
+    #[inline]
+    fn from(string: String) -> Self {
+        if string.is_ascii() {
+            Self::new(Units::Ascii(string.into_boxed_str()), OnceLock::new())
+        } else {
+            let units = string.encode_utf16().collect();
+            Self::new(Units::Utf16(units), OnceLock::from(string.into_boxed_str()))
+        }
+    }
+}
+
+impl From<&str> for JsString {
+    #[inline]
+    fn from(string: &str) -> Self {
+        Self::from(string.to_owned())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for JsString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for JsString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
@@ -12,8 +12,8 @@ pub use crate::builtins::value::val_type::Type;
 use crate::builtins::{
     function::Function,
     object::{GcObject, InternalState, InternalStateCell, Object, ObjectData, PROTOTYPE},
-    property::{Attribute, Property},
-    BigInt, Symbol,
+    property::{Attribute, Property, PropertyKey},
+    BigInt, Number, Symbol,
 };
 use crate::exec::Interpreter;
 use crate::BoaProfiler;
@@ -32,18 +32,19 @@ pub mod conversions;
 pub mod display;
 pub mod equality;
 pub mod hash;
+pub mod jsstring;
 pub mod operations;
 pub mod rcbigint;
-pub mod rcstring;
 pub mod rcsymbol;
 
 pub use conversions::*;
 pub(crate) use display::display_obj;
+pub use display::{InspectOptions, Inspection};
 pub use equality::*;
 pub use hash::*;
+pub use jsstring::JsString;
 pub use operations::*;
 pub use rcbigint::RcBigInt;
-pub use rcstring::RcString;
 pub use rcsymbol::RcSymbol;
 
 /// The result of a Javascript expression is represented like this so it can succeed (`Ok`) or fail (`Err`)
@@ -51,6 +52,9 @@ pub use rcsymbol::RcSymbol;
 pub type ResultValue = Result<Value, Value>;
 
 /// A Javascript value
+///
+/// Numbers, booleans, `null` and `undefined` are stored inline, and the other variants hold a
+/// single pointer, so a value is two words wide.
 #[derive(Trace, Finalize, Debug, Clone)]
 pub enum Value {
     /// `null` - A null value, for when a value doesn't exist.
@@ -60,7 +64,7 @@ pub enum Value {
     /// `boolean` - A `true` / `false` value, for if a certain criteria is met.
     Boolean(bool),
     /// `String` - A UTF-8 string, such as `"Hello, world"`.
-    String(RcString),
+    String(JsString),
     /// `Number` - A 64-bit floating point number, such as `3.1415`
     Rational(f64),
     /// `Number` - A 32-bit integer, such as `42`.
@@ -96,7 +100,7 @@ impl Value {
     #[inline]
     pub fn string<S>(value: S) -> Self
     where
-        S: Into<RcString>,
+        S: Into<JsString>,
     {
         Self::String(value.into())
     }
@@ -200,7 +204,7 @@ impl Value {
                 let length = vs.len();
                 for (idx, json) in vs.into_iter().enumerate() {
                     new_obj.set_property(
-                        idx.to_string(),
+                        idx,
                         Property::data_descriptor(
                             Self::from_json(json, interpreter),
                             Attribute::WRITABLE | Attribute::ENUMERABLE | Attribute::CONFIGURABLE,
@@ -238,23 +242,27 @@ impl Value {
             Self::Boolean(b) => Ok(JSONValue::Bool(b)),
             Self::Object(ref obj) => {
                 if obj.borrow().is_array() {
-                    let mut arr: Vec<JSONValue> = Vec::new();
-                    for k in obj.borrow().properties().keys() {
-                        if k != "length" {
-                            let value = self.get_field(k.to_string());
-                            if value.is_undefined() || value.is_function() || value.is_symbol() {
-                                arr.push(JSONValue::Null);
-                            } else {
-                                arr.push(self.get_field(k.to_string()).to_json(interpreter)?);
-                            }
+                    let length = i32::from(&self.get_field("length"));
+                    let mut arr: Vec<JSONValue> = Vec::with_capacity(length.max(0) as usize);
+                    for k in 0..length {
+                        let value = self.get_field(k);
+                        if value.is_undefined() || value.is_function() || value.is_symbol() {
+                            arr.push(JSONValue::Null);
+                        } else {
+                            arr.push(value.to_json(interpreter)?);
                         }
                     }
                     Ok(JSONValue::Array(arr))
                 } else {
                     let mut new_obj = Map::new();
-                    for k in obj.borrow().properties().keys() {
-                        let key = k.clone();
-                        let value = self.get_field(k.to_string());
+                    let keys = obj.borrow().own_property_keys();
+                    let enumerable =
+                        |key: &PropertyKey| obj.borrow().get_own_property(key).enumerable_or(false);
+                    for key in keys
+                        .into_iter()
+                        .filter(|key| !key.is_symbol() && enumerable(key))
+                    {
+                        let value = self.get_field(key.clone());
                         if !value.is_undefined() && !value.is_function() && !value.is_symbol() {
                             new_obj.insert(key.to_string(), value.to_json(interpreter)?);
                         }
@@ -263,6 +271,11 @@ impl Value {
                 }
             }
             Self::String(ref str) => Ok(JSONValue::String(str.to_string())),
+            // Integral numbers are serialized without a fractional part, like `JSON.stringify`
+            // does, so that they can be deserialized as integers.
+            Self::Rational(num) if num.fract() == 0.0 && num.abs() <= Number::MAX_SAFE_INTEGER => {
+                Ok(JSONValue::Number(JSONNumber::from(num as i64)))
+            }
             Self::Rational(num) => Ok(JSONNumber::from_f64(num)
                 .map(JSONValue::Number)
                 .unwrap_or(JSONValue::Null)),
@@ -276,17 +289,17 @@ impl Value {
         }
     }
 
-    /// This will tell us if we can exten an object or not, not properly implemented yet
+    /// This will tell us if we can extend an object or not.
     ///
-    /// For now always returns true.
-    ///
-    /// For scalar types it should be false, for objects check the private field for extensibilaty.
-    /// By default true.
+    /// Scalar types are never extensible.
     ///
     /// <https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/seal would turn extensible to false/>
     /// <https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/freeze would also turn extensible to false/>
     pub fn is_extensible(&self) -> bool {
-        true
+        match self {
+            Self::Object(ref object) => object.borrow().is_extensible(),
+            _ => false,
+        }
     }
 
     /// Returns true if the value is an object
@@ -311,6 +324,18 @@ impl Value {
         }
     }
 
+    /// Returns a handle to the object if the value is an object.
+    ///
+    /// Unlike `as_object`, the object is not borrowed, so the handle can be kept while scripts
+    /// run, and it gives access to the native data of the object with `GcObject::downcast_ref`.
+    #[inline]
+    pub fn as_gc_object(&self) -> Option<&GcObject> {
+        match self {
+            Self::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+
     /// Returns true if the value is a symbol.
     #[inline]
     pub fn is_symbol(&self) -> bool {
@@ -323,6 +348,18 @@ impl Value {
         matches!(self, Self::Object(o) if o.borrow().is_function())
     }
 
+    /// Returns true if the value can be called, like a function or a class constructor.
+    #[inline]
+    pub fn is_callable(&self) -> bool {
+        matches!(self, Self::Object(o) if o.borrow().is_callable())
+    }
+
+    /// Returns true if the value can be called with `new`.
+    #[inline]
+    pub fn is_constructor(&self) -> bool {
+        matches!(self, Self::Object(o) if o.borrow().is_constructable())
+    }
+
     /// Returns true if the value is undefined.
     #[inline]
     pub fn is_undefined(&self) -> bool {
@@ -386,6 +423,36 @@ impl Value {
         matches!(self, Self::BigInt(_))
     }
 
+    /// Returns the number if the value is a number primitive.
+    ///
+    /// Unlike `to_number`, no conversion is done, so `"1"` and `true` are not numbers.
+    #[inline]
+    pub fn as_number(&self) -> Option<f64> {
+        match *self {
+            Self::Integer(integer) => Some(f64::from(integer)),
+            Self::Rational(rational) => Some(rational),
+            _ => None,
+        }
+    }
+
+    /// Returns the string if the value is a string primitive.
+    #[inline]
+    pub fn as_string(&self) -> Option<&JsString> {
+        match self {
+            Self::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean if the value is a boolean primitive.
+    #[inline]
+    pub fn as_boolean(&self) -> Option<bool> {
+        match *self {
+            Self::Boolean(boolean) => Some(boolean),
+            _ => None,
+        }
+    }
+
     /// Returns an optional reference to a `BigInt` if the value is a BigInt primitive.
     #[inline]
     pub fn as_bigint(&self) -> Option<&BigInt> {
@@ -462,29 +529,36 @@ impl Value {
     /// Removes a property from a Value object.
     ///
     /// It will return a boolean based on if the value was removed, if there was no value to remove false is returned.
-    pub fn remove_property(&self, field: &str) -> bool {
+    pub fn remove_property<K>(&self, key: K) -> bool
+    where
+        K: Into<PropertyKey>,
+    {
         self.as_object_mut()
-            .and_then(|mut x| x.properties_mut().remove(field))
+            .and_then(|mut x| x.remove_property(&key.into()))
             .is_some()
     }
 
     /// Resolve the property in the object.
     ///
     /// A copy of the Property is returned.
-    pub fn get_property(&self, field: &str) -> Option<Property> {
+    pub fn get_property<K>(&self, key: K) -> Option<Property>
+    where
+        K: Into<PropertyKey>,
+    {
         let _timer = BoaProfiler::global().start_event("Value::get_property", "value");
+        let key = key.into();
+        tracing::trace!(%key, "get property");
         // Spidermonkey has its own GetLengthProperty: https://searchfox.org/mozilla-central/source/js/src/vm/Interpreter-inl.h#154
         // This is only for primitive strings, String() objects have their lengths calculated in string.rs
         match self {
-            Self::Undefined => None,
-            Self::String(ref s) if field == "length" => {
-                Some(Property::default().value(Value::from(s.chars().count())))
+            Self::String(ref s) if matches!(key, PropertyKey::String(ref k) if k == "length") => {
+                Some(Property::default().value(Value::from(s.len_utf16())))
             }
             Self::Object(ref object) => {
                 let object = object.borrow();
-                match object.properties().get(field) {
-                    Some(value) => Some(value.clone()),
-                    None => object.prototype().get_property(field),
+                match object.property(&key) {
+                    Some(property) => Some(property.clone()),
+                    None => object.prototype().get_property(key),
                 }
             }
             _ => None,
@@ -495,12 +569,15 @@ impl Value {
     /// Set_prop, which will overwrite prop with a new Property
     ///
     /// Mostly used internally for now
-    pub(crate) fn update_property(&self, field: &str, new_property: Property) {
+    pub(crate) fn update_property<K>(&self, key: K, new_property: Property)
+    where
+        K: Into<PropertyKey>,
+    {
         let _timer = BoaProfiler::global().start_event("Value::update_property", "value");
 
         if let Some(ref mut object) = self.as_object_mut() {
             // Use value, or walk up the prototype chain
-            if let Some(property) = object.properties_mut().get_mut(field) {
+            if let Some(property) = object.property_mut(&key.into()) {
                 *property = new_property;
             }
         }
@@ -519,41 +596,17 @@ impl Value {
     }
 
     /// Resolve the property in the object and get its value, or undefined if this is not an object or the field doesn't exist
-    /// get_field recieves a Property from get_prop(). It should then return the [[Get]] result value if that's set, otherwise fall back to [[Value]]
-    /// TODO: this function should use the get Value if its set
-    pub fn get_field<F>(&self, field: F) -> Self
+    ///
+    /// Accessor properties resolve to `undefined` here, because calling the getter needs an interpreter.
+    /// Use `Interpreter::get_field` to call it.
+    pub fn get_field<K>(&self, key: K) -> Self
     where
-        F: Into<Value>,
+        K: Into<PropertyKey>,
     {
         let _timer = BoaProfiler::global().start_event("Value::get_field", "value");
-        match field.into() {
-            // Our field will either be a String or a Symbol
-            Self::String(ref s) => {
-                match self.get_property(s) {
-                    Some(prop) => {
-                        // If the Property has [[Get]] set to a function, we should run that and return the Value
-                        let prop_getter = match prop.get {
-                            Some(_) => None,
-                            None => None,
-                        };
-
-                        // If the getter is populated, use that. If not use [[Value]] instead
-                        if let Some(val) = prop_getter {
-                            val
-                        } else {
-                            let val = prop
-                                .value
-                                .as_ref()
-                                .expect("Could not get property as reference");
-                            val.clone()
-                        }
-                    }
-                    None => Value::undefined(),
-                }
-            }
-            Self::Symbol(_) => unimplemented!(),
-            _ => Value::undefined(),
-        }
+        self.get_property(key)
+            .and_then(|prop| prop.value.clone())
+            .unwrap_or_else(Value::undefined)
     }
 
     /// Check whether an object has an internal state set.
@@ -618,41 +671,50 @@ impl Value {
 
     /// Check to see if the Value has the field, mainly used by environment records.
     #[inline]
-    pub fn has_field(&self, field: &str) -> bool {
+    pub fn has_field<K>(&self, key: K) -> bool
+    where
+        K: Into<PropertyKey>,
+    {
         let _timer = BoaProfiler::global().start_event("Value::has_field", "value");
-        self.get_property(field).is_some()
+        self.get_property(key).is_some()
     }
 
     /// Set the field in the value
-    /// Field could be a Symbol, so we need to accept a Value (not a string)
-    pub fn set_field<F, V>(&self, field: F, val: V) -> Value
+    pub fn set_field<K, V>(&self, key: K, val: V) -> Value
     where
-        F: Into<Value>,
+        K: Into<PropertyKey>,
         V: Into<Value>,
     {
         let _timer = BoaProfiler::global().start_event("Value::set_field", "value");
-        let field = field.into();
+        let key = key.into();
         let val = val.into();
 
         if let Self::Object(ref obj) = *self {
             if obj.borrow().is_array() {
-                if let Ok(num) = field.to_string().parse::<usize>() {
-                    if num > 0 {
+                match key {
+                    PropertyKey::Index(index) => {
                         let len = i32::from(&self.get_field("length"));
-                        if len < (num + 1) as i32 {
-                            self.set_field("length", Value::from(num + 1));
+                        if i64::from(len) <= i64::from(index) {
+                            self.set_field("length", Value::from(index as usize + 1));
                         }
                     }
+                    PropertyKey::String(ref name) if name == "length" => {
+                        // Shrinking the length deletes the elements past the new length,
+                        // growing it leaves holes.
+                        let new_len = val.to_number();
+                        if new_len >= 0.0
+                            && new_len < f64::from(i32::from(&self.get_field("length")))
+                        {
+                            obj.borrow_mut()
+                                .indexed_properties_mut()
+                                .retain(|index, _| f64::from(*index) < new_len);
+                        }
+                    }
+                    _ => {}
                 }
             }
 
-            // Symbols get saved into a different bucket to general properties
-            if field.is_symbol() {
-                obj.borrow_mut().set(field, val.clone());
-            } else {
-                obj.borrow_mut()
-                    .set(Value::from(field.to_string()), val.clone());
-            }
+            obj.borrow_mut().set(key, val.clone());
         }
 
         val
@@ -678,14 +740,12 @@ impl Value {
     }
 
     /// Set the property in the value.
-    pub fn set_property<S>(&self, field: S, property: Property) -> Property
+    pub fn set_property<K>(&self, key: K, property: Property) -> Property
     where
-        S: Into<RcString>,
+        K: Into<PropertyKey>,
     {
         if let Some(mut object) = self.as_object_mut() {
-            object
-                .properties_mut()
-                .insert(field.into(), property.clone());
+            object.insert_property(key, property.clone());
         }
         property
     }
@@ -1,19 +1,27 @@
 use super::*;
 use crate::builtins::number::{f64_to_int32, f64_to_uint32};
 use crate::exec::PreferredType;
+use std::cmp::Ordering;
 
 impl Value {
     #[inline]
     pub fn add(&self, other: &Self, ctx: &mut Interpreter) -> ResultValue {
         Ok(match (self, other) {
             // Fast path:
-            (Self::Integer(x), Self::Integer(y)) => Self::rational(f64::from(*x) + f64::from(*y)),
+            (Self::Integer(x), Self::Integer(y)) => x.checked_add(*y).map_or_else(
+                || Self::rational(f64::from(*x) + f64::from(*y)),
+                Self::integer,
+            ),
             (Self::Rational(x), Self::Rational(y)) => Self::rational(x + y),
             (Self::Integer(x), Self::Rational(y)) => Self::rational(f64::from(*x) + y),
             (Self::Rational(x), Self::Integer(y)) => Self::rational(x + f64::from(*y)),
 
-            (Self::String(ref x), ref y) => Self::string(format!("{}{}", x, ctx.to_string(y)?)),
-            (ref x, Self::String(ref y)) => Self::string(format!("{}{}", ctx.to_string(x)?, y)),
+            (Self::String(ref x), ref y) if !y.is_object() => {
+                concat_strings(x, &ctx.to_string(y)?, ctx)?
+            }
+            (ref x, Self::String(ref y)) if !x.is_object() => {
+                concat_strings(&ctx.to_string(x)?, y, ctx)?
+            }
             (Self::BigInt(ref n1), Self::BigInt(ref n2)) => {
                 Self::bigint(n1.as_inner().clone() + n2.as_inner().clone())
             }
@@ -23,8 +31,8 @@ impl Value {
                 ctx.to_primitive(self, PreferredType::Default)?,
                 ctx.to_primitive(other, PreferredType::Default)?,
             ) {
-                (Self::String(ref x), ref y) => Self::string(format!("{}{}", x, ctx.to_string(y)?)),
-                (ref x, Self::String(ref y)) => Self::string(format!("{}{}", ctx.to_string(x)?, y)),
+                (Self::String(ref x), ref y) => concat_strings(x, &ctx.to_string(y)?, ctx)?,
+                (ref x, Self::String(ref y)) => concat_strings(&ctx.to_string(x)?, y, ctx)?,
                 (x, y) => match (ctx.to_numeric(&x)?, ctx.to_numeric(&y)?) {
                     (Self::Rational(x), Self::Rational(y)) => Self::rational(x + y),
                     (Self::BigInt(ref n1), Self::BigInt(ref n2)) => {
@@ -44,7 +52,10 @@ impl Value {
     pub fn sub(&self, other: &Self, ctx: &mut Interpreter) -> ResultValue {
         Ok(match (self, other) {
             // Fast path:
-            (Self::Integer(x), Self::Integer(y)) => Self::rational(f64::from(*x) - f64::from(*y)),
+            (Self::Integer(x), Self::Integer(y)) => x.checked_sub(*y).map_or_else(
+                || Self::rational(f64::from(*x) - f64::from(*y)),
+                Self::integer,
+            ),
             (Self::Rational(x), Self::Rational(y)) => Self::rational(x - y),
             (Self::Integer(x), Self::Rational(y)) => Self::rational(f64::from(*x) - y),
             (Self::Rational(x), Self::Integer(y)) => Self::rational(x - f64::from(*y)),
@@ -72,7 +83,12 @@ impl Value {
     pub fn mul(&self, other: &Self, ctx: &mut Interpreter) -> ResultValue {
         Ok(match (self, other) {
             // Fast path:
-            (Self::Integer(x), Self::Integer(y)) => Self::rational(f64::from(*x) * f64::from(*y)),
+            // The product of zero and a negative number is `-0`, which is not an integer.
+            (Self::Integer(x), Self::Integer(y)) => match x.checked_mul(*y) {
+                Some(0) if *x < 0 || *y < 0 => Self::rational(-0.0),
+                Some(product) => Self::integer(product),
+                None => Self::rational(f64::from(*x) * f64::from(*y)),
+            },
             (Self::Rational(x), Self::Rational(y)) => Self::rational(x * y),
             (Self::Integer(x), Self::Rational(y)) => Self::rational(f64::from(*x) * y),
             (Self::Rational(x), Self::Integer(y)) => Self::rational(x * f64::from(*y)),
@@ -100,7 +116,11 @@ impl Value {
     pub fn div(&self, other: &Self, ctx: &mut Interpreter) -> ResultValue {
         Ok(match (self, other) {
             // Fast path:
-            (Self::Integer(x), Self::Integer(y)) => Self::rational(f64::from(*x) / f64::from(*y)),
+            // Only exact quotients are integers, and `0 / -1` is `-0`.
+            (Self::Integer(x), Self::Integer(y)) => match x.checked_rem(*y) {
+                Some(0) if !(*x == 0 && *y < 0) => Self::integer(x / y),
+                _ => Self::rational(f64::from(*x) / f64::from(*y)),
+            },
             (Self::Rational(x), Self::Rational(y)) => Self::rational(x / y),
             (Self::Integer(x), Self::Rational(y)) => Self::rational(f64::from(*x) / y),
             (Self::Rational(x), Self::Integer(y)) => Self::rational(x / f64::from(*y)),
@@ -128,7 +148,12 @@ impl Value {
     pub fn rem(&self, other: &Self, ctx: &mut Interpreter) -> ResultValue {
         Ok(match (self, other) {
             // Fast path:
-            (Self::Integer(x), Self::Integer(y)) => Self::integer(x % *y),
+            // The remainder has the sign of the dividend, so `-4 % 2` is `-0`.
+            (Self::Integer(x), Self::Integer(y)) => match x.checked_rem(*y) {
+                Some(0) if *x < 0 => Self::rational(-0.0),
+                Some(remainder) => Self::integer(remainder),
+                None => Self::rational(f64::from(*x) % f64::from(*y)),
+            },
             (Self::Rational(x), Self::Rational(y)) => Self::rational(x % y),
             (Self::Integer(x), Self::Rational(y)) => Self::rational(f64::from(*x) % y),
             (Self::Rational(x), Self::Integer(y)) => Self::rational(x % f64::from(*y)),
@@ -390,7 +415,10 @@ impl Value {
                 Err(_) => NAN,
             }),
             Self::Rational(num) => Self::rational(-num),
-            Self::Integer(num) => Self::rational(-f64::from(num)),
+            Self::Integer(0) => Self::rational(-0.0),
+            Self::Integer(num) => num
+                .checked_neg()
+                .map_or_else(|| Self::rational(-f64::from(num)), Self::integer),
             Self::Boolean(true) => Self::integer(1),
             Self::Boolean(false) | Self::Null => Self::integer(0),
             Self::BigInt(ref num) => Self::bigint(-num.as_inner().clone()),
@@ -401,4 +429,46 @@ impl Value {
     pub fn not(&self, _: &mut Interpreter) -> ResultValue {
         Ok(Self::boolean(!self.to_boolean()))
     }
+
+    /// Adds one to the value converted to a number, like the `++` operators do.
+    #[inline]
+    pub fn increment(&self) -> Self {
+        match *self {
+            Self::Integer(x) => x
+                .checked_add(1)
+                .map_or_else(|| Self::rational(f64::from(x) + 1.0), Self::integer),
+            _ => Self::rational(self.to_number() + 1.0),
+        }
+    }
+
+    /// Subtracts one from the value converted to a number, like the `--` operators do.
+    #[inline]
+    pub fn decrement(&self) -> Self {
+        match *self {
+            Self::Integer(x) => x
+                .checked_sub(1)
+                .map_or_else(|| Self::rational(f64::from(x) - 1.0), Self::integer),
+            _ => Self::rational(self.to_number() - 1.0),
+        }
+    }
+
+    /// Compares the values converted to numbers, like the relational operators do for values
+    /// that are not both strings.
+    ///
+    /// Returns `None` if one of the numbers is `NaN`.
+    #[inline]
+    pub fn number_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Integer(x), Self::Integer(y)) => Some(x.cmp(y)),
+            (_, _) => self.to_number().partial_cmp(&other.to_number()),
+        }
+    }
+}
+
+/// Concatenates two strings, throwing a `RangeError` if the result would exceed the maximum string length.
+#[inline]
+fn concat_strings(x: &JsString, y: &JsString, ctx: &mut Interpreter) -> Result<Value, Value> {
+    ctx.check_string_length((x.len_utf16() + y.len_utf16()) as f64)?;
+    ctx.check_memory(x.len_utf16() + y.len_utf16())?;
+    Ok(Value::string(x.concat(y)))
 }
@@ -3,6 +3,7 @@ use crate::{forward, forward_val, Interpreter, Realm};
 
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
+use std::mem::size_of;
 
 #[test]
 fn is_object() {
@@ -10,6 +11,42 @@ fn is_object() {
     assert_eq!(val.is_object(), true);
 }
 
+#[test]
+fn value_is_two_words() {
+    assert_eq!(size_of::<Value>(), 2 * size_of::<usize>());
+}
+
+#[test]
+fn primitive_accessors() {
+    assert_eq!(Value::from(3).as_number(), Some(3.0));
+    assert_eq!(Value::from(0.5).as_number(), Some(0.5));
+    assert_eq!(Value::from("1").as_number(), None);
+    assert_eq!(
+        Value::from("a").as_string().map(|s| s.to_string()),
+        Some("a".to_owned())
+    );
+    assert_eq!(Value::from(true).as_string(), None);
+    assert_eq!(Value::from(true).as_boolean(), Some(true));
+    assert_eq!(Value::from(1).as_boolean(), None);
+    assert!(Value::undefined().as_gc_object().is_none());
+    assert!(Value::new_object(None).as_gc_object().is_some());
+}
+
+#[test]
+fn callable_and_constructor() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let function = forward_val(&mut engine, "(function () {})").unwrap();
+    assert!(function.is_callable());
+    assert!(function.is_constructor());
+    let arrow = forward_val(&mut engine, "() => 1").unwrap();
+    assert!(arrow.is_callable());
+    assert!(!arrow.is_constructor());
+    let object = forward_val(&mut engine, "({})").unwrap();
+    assert!(!object.is_callable());
+    assert!(!Value::from("f").is_callable());
+}
+
 #[test]
 fn string_to_value() {
     let s = String::from("Hello");
@@ -144,6 +181,63 @@ fn hash_rational() {
     assert_ne!(hash_value(&nan), hash_value(&Value::rational(1.0)));
 }
 
+#[test]
+fn hash_integer() {
+    let integer = Value::integer(1);
+    let rational = Value::rational(1.0);
+    assert_eq!(integer, rational);
+    assert_eq!(hash_value(&integer), hash_value(&rational));
+}
+
+#[test]
+fn integer_arithmetic() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let integers = [
+        "1 + 2",
+        "7 - 10",
+        "6 * 7",
+        "84 / 2",
+        "43 % 2",
+        "-5",
+        "1 + 1 * 2 - 6 / 3",
+    ];
+    for script in integers.iter() {
+        let value = forward_val(&mut engine, script).unwrap();
+        assert!(
+            matches!(value, Value::Integer(_)),
+            "{} is {:?}",
+            script,
+            value
+        );
+    }
+
+    assert_eq!(forward(&mut engine, "2147483647 + 1"), "2147483648");
+    assert_eq!(forward(&mut engine, "-2147483648 - 1"), "-2147483649");
+    assert_eq!(forward(&mut engine, "65536 * 65536"), "4294967296");
+    assert_eq!(forward(&mut engine, "7 / 2"), "3.5");
+    assert_eq!(forward(&mut engine, "1 / 0"), "Infinity");
+    assert_eq!(forward(&mut engine, "5 % 0"), "NaN");
+    assert_eq!(forward(&mut engine, "let a = 0 * -5; 1 / a"), "-Infinity");
+    forward(&mut engine, "let b = 0 / -5");
+    assert_eq!(forward(&mut engine, "1 / b"), "-Infinity");
+    assert_eq!(forward(&mut engine, "let c = -4 % 2; 1 / c"), "-Infinity");
+    assert_eq!(forward(&mut engine, "1 / -0"), "-Infinity");
+    assert_eq!(
+        forward(&mut engine, "let i = 2147483646; i++; i++; i"),
+        "2147483648"
+    );
+    assert_eq!(
+        forward(&mut engine, "let j = 0; while (j < 10) { j++ } j"),
+        "10"
+    );
+    assert_eq!(
+        forward(&mut engine, "3 <= 3 && 2 < 3 && 3 >= 3 && !(NaN < 1)"),
+        "true"
+    );
+}
+
 #[test]
 fn hash_object() {
     let object1 = Value::object(Object::default());
@@ -357,3 +451,183 @@ fn bitand_rational_and_rational() {
     let value = engine.to_int32(&value).unwrap();
     assert_eq!(value, 255);
 }
+
+#[test]
+fn try_from_primitives() {
+    use std::convert::TryFrom;
+
+    assert_eq!(i32::try_from(Value::integer(42)), Ok(42));
+    assert_eq!(i32::try_from(Value::rational(-7.0)), Ok(-7));
+    assert!(i32::try_from(Value::rational(1.5)).is_err());
+    assert!(i32::try_from(Value::rational(4_294_967_296.0)).is_err());
+    assert_eq!(f64::try_from(Value::integer(3)), Ok(3.0));
+    assert_eq!(bool::try_from(Value::boolean(true)), Ok(true));
+    assert_eq!(String::try_from(Value::from("boa")), Ok("boa".to_owned()));
+
+    let error = String::try_from(Value::integer(1)).unwrap_err();
+    assert_eq!(error.found(), Type::Number);
+    assert_eq!(error.to_string(), "expected a string, found number");
+    assert_eq!(
+        bool::try_from(Value::null()).unwrap_err().to_string(),
+        "expected a boolean, found null"
+    );
+}
+
+#[test]
+fn try_from_array() {
+    use std::convert::TryFrom;
+
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let value = forward_val(&mut engine, "[1, 2, 3]").unwrap();
+    assert_eq!(Vec::<i32>::try_from(value), Ok(vec![1, 2, 3]));
+
+    let value = forward_val(&mut engine, "[[true], [false, true]]").unwrap();
+    assert_eq!(
+        Vec::<Vec<bool>>::try_from(value),
+        Ok(vec![vec![true], vec![false, true]])
+    );
+
+    let value = forward_val(&mut engine, "[1, 'two']").unwrap();
+    assert!(Vec::<i32>::try_from(value).is_err());
+    let value = forward_val(&mut engine, "({ length: 0 })").unwrap();
+    assert!(Vec::<i32>::try_from(value).is_err());
+}
+
+#[test]
+fn try_from_object() {
+    use std::{collections::HashMap, convert::TryFrom};
+
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let value = forward_val(&mut engine, "({ a: 'x', 0: 'y' })").unwrap();
+    let map = HashMap::<String, String>::try_from(value).unwrap();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["a"], "x");
+    assert_eq!(map["0"], "y");
+
+    let value = forward_val(&mut engine, "({ a: 1, b: null })").unwrap();
+    assert!(HashMap::<String, f64>::try_from(value).is_err());
+    assert!(HashMap::<String, f64>::try_from(Value::integer(1)).is_err());
+}
+
+#[test]
+fn from_collections() {
+    use std::{collections::HashMap, convert::TryFrom};
+
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let array = Value::from(vec![1, 2, 3]);
+    assert!(matches!(array.as_object(), Some(object) if object.is_array()));
+    assert_eq!(Vec::<i32>::try_from(array.clone()), Ok(vec![1, 2, 3]));
+    engine.realm.global_obj.set_field("array", array);
+    assert_eq!(forward(&mut engine, "array.length"), "3");
+    assert_eq!(forward(&mut engine, "array[2]"), "3");
+
+    let mut map = HashMap::new();
+    map.insert("name".to_owned(), Value::from("boa"));
+    map.insert("tags".to_owned(), Value::from(vec!["a", "b"]));
+    engine.realm.global_obj.set_field("map", Value::from(map));
+    assert_eq!(forward(&mut engine, "map.name"), "boa");
+    assert_eq!(forward(&mut engine, "map.tags[1]"), "b");
+}
+
+#[test]
+fn inspect_truncates_items() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let value = forward_val(&mut engine, "let a = [1, 2, 3, 4, 5]; a").unwrap();
+    let options = InspectOptions {
+        max_items: 2,
+        ..InspectOptions::default()
+    };
+    let inspection = value.inspect(&options);
+    assert_eq!(inspection.text, "[ 1, 2, ... 3 more items ]");
+    assert_eq!(inspection.remaining, 3);
+
+    let inspection = value.inspect(&InspectOptions {
+        offset: 4,
+        ..options
+    });
+    assert_eq!(inspection.text, "[ ... 4 previous items, 5 ]");
+    assert_eq!(inspection.remaining, 0);
+
+    let value = forward_val(&mut engine, "[[1, 2, 3]]").unwrap();
+    assert_eq!(
+        value.inspect(&options).text,
+        "[ [ 1, 2, ... 1 more items ] ]"
+    );
+    assert_eq!(value.inspect(&options).remaining, 0);
+}
+
+#[test]
+fn inspect_limits_depth() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let value = forward_val(&mut engine, "({ a: { b: [1] } })").unwrap();
+    let depth = |depth| {
+        value
+            .inspect(&InspectOptions {
+                depth,
+                ..InspectOptions::default()
+            })
+            .text
+    };
+    assert_eq!(depth(0), "{ a: [Object] }");
+    assert_eq!(depth(1), "{ a: { b: Array(1) } }");
+    assert_eq!(depth(2), "{ a: { b: [ 1 ] } }");
+
+    let value = forward_val(&mut engine, "let o = { m: new Map() }; o.o = o; o.o").unwrap();
+    let text = value.inspect(&InspectOptions::default()).text;
+    assert!(text == "{ m: Map(0), o: [Cycle] }" || text == "{ o: [Cycle], m: Map(0) }");
+}
+
+#[test]
+fn inspect_shows_strings_symbols_and_keys() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    // Each object has a single string key, since their order is not kept.
+    let inspect = |engine: &mut Interpreter, src| {
+        forward_val(engine, src)
+            .unwrap()
+            .inspect(&InspectOptions::default())
+            .text
+    };
+    assert_eq!(
+        inspect(&mut engine, "({ name: 'it\\'s' })"),
+        "{ name: 'it\\'s' }"
+    );
+    assert_eq!(
+        inspect(&mut engine, "({ 'a-b': [null, undefined] })"),
+        "{ 'a-b': [ null, undefined ] }"
+    );
+    assert_eq!(
+        inspect(
+            &mut engine,
+            "let s = Symbol('tag'); let o = { f: function f() {} }; o[s] = 1n; o"
+        ),
+        "{ f: [Function: f], [Symbol(tag)]: 1n }"
+    );
+    assert_eq!(
+        forward_val(&mut engine, "'top'")
+            .unwrap()
+            .inspect(&InspectOptions::default())
+            .text,
+        "top"
+    );
+
+    let colored = forward_val(&mut engine, "({ a: 'x' })")
+        .unwrap()
+        .inspect(&InspectOptions {
+            colors: true,
+            ..InspectOptions::default()
+        })
+        .text;
+    assert_eq!(colored, "{ \u{1b}[94ma\u{1b}[0m: \u{1b}[32m'x'\u{1b}[0m }");
+}
@@ -0,0 +1,102 @@
+//! This module implements the global `WeakRef` object.
+//!
+//! A `WeakRef` holds a reference to an object without keeping it alive. The garbage collector
+//! of the engine has no weak references yet, so the target stays alive as long as the `WeakRef`
+//! does and `deref()` always returns it. The specification allows this, since an engine is
+//! never required to collect an object.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-weak-ref-objects
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/WeakRef
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builtins::{
+        function::{make_builtin_fn, make_constructor_fn},
+        symbol::set_to_string_tag,
+        value::{ResultValue, Value},
+    },
+    exec::Interpreter,
+    BoaProfiler,
+};
+
+/// The internal slot that holds the target of a `WeakRef`.
+const TARGET: &str = "WeakRefTarget";
+
+/// JavaScript `WeakRef` implementation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct WeakRef;
+
+impl WeakRef {
+    /// The name of the object.
+    pub(crate) const NAME: &'static str = "WeakRef";
+
+    /// The amount of arguments this function object takes.
+    pub(crate) const LENGTH: usize = 1;
+
+    /// `new WeakRef( target )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-weak-ref-target
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/WeakRef/WeakRef
+    pub(crate) fn make_weak_ref(
+        this: &Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let target = args.get(0).cloned().unwrap_or_default();
+        if !target.is_object() {
+            return ctx.throw_type_error("WeakRef: target must be an object");
+        }
+        this.set_internal_slot(TARGET, target);
+        Ok(this.clone())
+    }
+
+    /// `WeakRef.prototype.deref()`
+    ///
+    /// Returns the target of the `WeakRef`, or `undefined` if it has been collected.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-weak-ref.prototype.deref
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/WeakRef/deref
+    pub(crate) fn deref(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let target = this.get_internal_slot(TARGET);
+        if !target.is_object() {
+            return ctx.throw_type_error("WeakRef.prototype.deref: 'this' is not a WeakRef");
+        }
+        Ok(target)
+    }
+
+    /// Initialise the `WeakRef` object on the global object.
+    #[inline]
+    pub(crate) fn init(global: &Value) -> (&str, Value) {
+        let _timer = BoaProfiler::global().start_event(Self::NAME, "init");
+
+        let prototype = Value::new_object(Some(global));
+        make_builtin_fn(Self::deref, "deref", &prototype, 0);
+        set_to_string_tag(&prototype, Self::NAME);
+
+        let weak_ref = make_constructor_fn(
+            Self::NAME,
+            Self::LENGTH,
+            Self::make_weak_ref,
+            global,
+            prototype,
+            true,
+            false,
+        );
+
+        (Self::NAME, weak_ref)
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{exec::Interpreter, forward, realm::Realm};
+
+#[test]
+fn deref() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var target = { value: 1 };
+        var ref = new WeakRef(target);
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "ref.deref() === target"), "true");
+    assert_eq!(forward(&mut engine, "ref.deref().value"), "1");
+}
+
+#[test]
+fn target_must_be_an_object() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let scenario = r#"
+        try {
+            new WeakRef(1);
+        } catch (e) {
+            e.toString()
+        }
+        "#;
+
+    assert_eq!(
+        forward(&mut engine, scenario),
+        "TypeError: WeakRef: target must be an object"
+    );
+}
@@ -0,0 +1,182 @@
+//! This module implements the `Class` trait, which exposes Rust types to scripts as classes.
+//!
+//! The Rust value of an instance is stored as the internal state of its object, and the methods
+//! of the class receive it back as `&mut Self`:
+//!
+//! ```
+//! use boa::{
+//!     builtins::{object::InternalState, value::Value},
+//!     class::{Class, ClassBuilder},
+//!     exec::Interpreter,
+//!     Context,
+//! };
+//!
+//! #[derive(Debug)]
+//! struct Counter {
+//!     count: i32,
+//! }
+//!
+//! impl InternalState for Counter {}
+//!
+//! impl Class for Counter {
+//!     const NAME: &'static str = "Counter";
+//!     const LENGTH: usize = 1;
+//!
+//!     fn constructor(_: &Value, args: &[Value], ctx: &mut Interpreter) -> Result<Self, Value> {
+//!         let start = args.get(0).cloned().unwrap_or_default();
+//!         Ok(Self { count: ctx.to_number(&start)? as i32 })
+//!     }
+//!
+//!     fn init(class: &mut ClassBuilder<'_, Self>) {
+//!         class.method("increment", 0, |counter, _, _| {
+//!             counter.count += 1;
+//!             Ok(Value::from(counter.count))
+//!         });
+//!     }
+//! }
+//!
+//! let mut context = Context::new();
+//! context.register_global_class::<Counter>();
+//! let value = context.eval("let c = new Counter(41); c.increment()").unwrap();
+//! assert_eq!(value.to_string(), "42");
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builtins::{
+        function::{make_closure_fn, make_constructor_fn},
+        object::InternalState,
+        value::{ResultValue, Value},
+    },
+    exec::Interpreter,
+};
+use std::{any::Any, marker::PhantomData};
+
+/// A Rust type that scripts can instantiate with `new`.
+///
+/// Classes are registered with `Context::register_global_class`.
+pub trait Class: InternalState + Any + Sized {
+    /// The name of the class, under which its constructor is defined.
+    const NAME: &'static str;
+
+    /// The number of arguments the constructor expects, the `length` of the constructor.
+    const LENGTH: usize = 0;
+
+    /// Creates the Rust value of a new instance from the arguments of `new`.
+    ///
+    /// `this` is the new object, without its Rust value yet.
+    fn constructor(this: &Value, args: &[Value], ctx: &mut Interpreter) -> Result<Self, Value>;
+
+    /// Defines the methods and static methods of the class.
+    fn init(class: &mut ClassBuilder<'_, Self>);
+}
+
+/// Defines the members of a class, see `Class::init`.
+#[derive(Debug)]
+pub struct ClassBuilder<'a, T> {
+    constructor: &'a Value,
+    prototype: &'a Value,
+    class: PhantomData<T>,
+}
+
+impl<'a, T: Class> ClassBuilder<'a, T> {
+    /// Defines a method, shared by the instances through the prototype.
+    ///
+    /// The method receives the Rust value of `this`, and throws a `TypeError` if `this` is not
+    /// an instance of the class.
+    pub fn method<F>(&mut self, name: &str, length: usize, method: F) -> &mut Self
+    where
+        F: Fn(&mut T, &[Value], &mut Interpreter) -> ResultValue + 'static,
+    {
+        make_closure_fn(
+            move |this, args, ctx| {
+                with_instance(this, ctx, |instance: &mut T, ctx| {
+                    method(instance, args, ctx)
+                })?
+            },
+            name,
+            self.prototype,
+            length,
+        );
+        self
+    }
+
+    /// Defines a static method, a property of the constructor.
+    pub fn static_method<F>(&mut self, name: &str, length: usize, method: F) -> &mut Self
+    where
+        F: Fn(&Value, &[Value], &mut Interpreter) -> ResultValue + 'static,
+    {
+        make_closure_fn(method, name, self.constructor, length);
+        self
+    }
+
+    /// Returns the constructor of the class.
+    #[inline]
+    pub fn constructor(&self) -> &Value {
+        self.constructor
+    }
+
+    /// Returns the prototype of the instances of the class.
+    #[inline]
+    pub fn prototype(&self) -> &Value {
+        self.prototype
+    }
+}
+
+/// Runs `f` with the Rust value of an instance of the class `T`.
+///
+/// Throws a `TypeError` if `value` is not an instance of the class. While `f` runs, the value is
+/// taken out of the object, so a nested call on the same instance throws as well.
+pub fn with_instance<T, R, F>(value: &Value, ctx: &mut Interpreter, f: F) -> Result<R, Value>
+where
+    T: Class,
+    F: FnOnce(&mut T, &mut Interpreter) -> R,
+{
+    let state = match value.as_object_mut() {
+        Some(mut object) if matches!(object.state(), Some(state) if state.downcast_ref::<T>().is_some()) => {
+            object.state_mut().take()
+        }
+        _ => None,
+    };
+    let mut state = match state {
+        Some(state) => state,
+        None => {
+            return Err(ctx.construct_type_error(format!("this is not a {} object", T::NAME)));
+        }
+    };
+
+    let result = f(state.downcast_mut().expect("the state was checked"), ctx);
+    if let Some(mut object) = value.as_object_mut() {
+        object.state_mut().replace(state);
+    }
+    Ok(result)
+}
+
+/// Creates the constructor of the class `T`, with its prototype and members.
+pub(crate) fn make_class<T: Class>(global: &Value) -> Value {
+    let prototype = Value::new_object(Some(global));
+    let constructor = make_constructor_fn(
+        T::NAME,
+        T::LENGTH,
+        construct::<T>,
+        global,
+        prototype.clone(),
+        true,
+        false,
+    );
+    T::init(&mut ClassBuilder {
+        constructor: &constructor,
+        prototype: &prototype,
+        class: PhantomData,
+    });
+    constructor
+}
+
+/// The body of the constructor of the class `T`.
+fn construct<T: Class>(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let instance = T::constructor(this, args, ctx)?;
+    this.set_internal_state(instance);
+    Ok(this.clone())
+}
@@ -0,0 +1,110 @@
+use crate::{
+    builtins::{object::InternalState, value::Value},
+    class::{with_instance, Class, ClassBuilder},
+    exec::Interpreter,
+    Context,
+};
+
+#[derive(Debug)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+impl InternalState for Point {}
+
+impl Class for Point {
+    const NAME: &'static str = "Point";
+    const LENGTH: usize = 2;
+
+    fn constructor(_: &Value, args: &[Value], ctx: &mut Interpreter) -> Result<Self, Value> {
+        let x = ctx.to_number(&args.get(0).cloned().unwrap_or_default())?;
+        let y = ctx.to_number(&args.get(1).cloned().unwrap_or_default())?;
+        Ok(Self { x, y })
+    }
+
+    fn init(class: &mut ClassBuilder<'_, Self>) {
+        class
+            .method("length", 0, |point, _, _| {
+                Ok(Value::from(point.x.hypot(point.y)))
+            })
+            .method("scale", 1, |point, args, ctx| {
+                let factor = ctx.to_number(&args.get(0).cloned().unwrap_or_default())?;
+                point.x *= factor;
+                point.y *= factor;
+                Ok(Value::undefined())
+            })
+            .static_method("distance", 2, |_, args, ctx| {
+                let a = args.get(0).cloned().unwrap_or_default();
+                let b = args.get(1).cloned().unwrap_or_default();
+                let (ax, ay) = with_instance(&a, ctx, |a: &mut Point, _| (a.x, a.y))?;
+                let (bx, by) = with_instance(&b, ctx, |b: &mut Point, _| (b.x, b.y))?;
+                Ok(Value::from((ax - bx).hypot(ay - by)))
+            });
+    }
+}
+
+fn context() -> Context {
+    let mut context = Context::new();
+    context.register_global_class::<Point>();
+    context
+}
+
+#[test]
+fn methods_use_the_rust_value() {
+    let mut context = context();
+    let value = context
+        .eval("let p = new Point(3, 4); p.scale(2); p.length()")
+        .unwrap();
+    assert_eq!(value.to_string(), "10");
+
+    let value = context.eval("p").unwrap();
+    let x = with_instance(&value, context.interpreter_mut(), |p: &mut Point, _| p.x);
+    assert_eq!(x.ok(), Some(6.0));
+}
+
+#[test]
+fn instances_can_be_downcast() {
+    let mut context = context();
+    let value = context.eval("new Point(3, 4)").unwrap();
+    let object = value.as_gc_object().unwrap();
+    assert!(object.is::<Point>());
+
+    object.downcast_mut::<Point>().unwrap().x = 6.0;
+    assert_eq!(object.downcast_ref::<Point>().unwrap().x, 6.0);
+
+    let other = context.eval("({})").unwrap();
+    assert!(other
+        .as_gc_object()
+        .unwrap()
+        .downcast_ref::<Point>()
+        .is_none());
+}
+
+#[test]
+fn static_methods() {
+    let mut context = context();
+    let value = context
+        .eval("Point.distance(new Point(1, 1), new Point(4, 5))")
+        .unwrap();
+    assert_eq!(value.to_string(), "5");
+}
+
+#[test]
+fn constructor_properties() {
+    let mut context = context();
+    let value = context
+        .eval("let p = new Point(0, 0); p.constructor === Point")
+        .unwrap();
+    assert_eq!(value.to_string(), "true");
+    assert_eq!(context.eval("Point.name").unwrap().to_string(), "Point");
+    assert_eq!(context.eval("Point.length").unwrap().to_string(), "2");
+}
+
+#[test]
+fn methods_check_this() {
+    let mut context = context();
+    assert!(context.eval("Point.prototype.length.call({})").is_err());
+    assert!(context.eval("Point.distance({}, new Point(0, 0))").is_err());
+    assert!(context.eval("Point(1, 2)").is_err());
+}
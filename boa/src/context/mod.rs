@@ -0,0 +1,841 @@
+//! This module implements the `Context`, the entry point for embedding the engine.
+//!
+//! A context owns an interpreter together with its realm, so that running a script is a single
+//! call:
+//!
+//! ```
+//! use boa::Context;
+//!
+//! let mut context = Context::new();
+//! let value = context.eval("1 + 2").unwrap();
+//! assert_eq!(value.to_string(), "3");
+//! ```
+//!
+//! Contexts are configured with a `ContextBuilder`, obtained from `Context::builder`.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builtins::object::ObjectData,
+    builtins::{
+        console::ConsoleWriter,
+        function::make_closure_fn,
+        property::{Property, PropertyKey},
+        value::{JsString, ResultValue, Value},
+    },
+    class::{make_class, Class},
+    coverage::ANONYMOUS_SCRIPT,
+    environment::lexical_environment::Environment,
+    exec::{EventLoop, Interpreter, InterruptHandle, PromiseFuture},
+    experimental::Experimental,
+    realm::{Realm, RealmId},
+    syntax::ast::Position,
+};
+use std::fmt::{self, Display};
+
+/// A JavaScript execution context, with its own global object.
+#[derive(Debug)]
+pub struct Context {
+    interpreter: Interpreter,
+}
+
+impl Context {
+    /// Creates a new context with the default configuration.
+    #[inline]
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    /// Creates a builder to configure a new context.
+    #[inline]
+    pub fn builder() -> ContextBuilder {
+        ContextBuilder::default()
+    }
+
+    /// Runs a script, and then the microtasks it queued.
+    ///
+    /// Returns the completion value of the script, or the thrown value if the script or one of
+    /// its microtasks throws. Syntax errors are returned as `SyntaxError` objects.
+    pub fn eval(&mut self, src: &str) -> Result<Value, Value> {
+        let statements = match crate::compile(&mut self.interpreter, src) {
+            Ok(statements) => statements,
+            Err(message) => {
+                return Err(self.interpreter.construct_syntax_error(message.to_string()))
+            }
+        };
+        crate::run(&mut self.interpreter, &statements)
+    }
+
+    /// Gets a handle that interrupts the scripts of this context, from any thread.
+    ///
+    /// An interrupted script stops at its next loop iteration or function call, and `eval`
+    /// returns a `RangeError` that the script could not catch:
+    ///
+    /// ```
+    /// use boa::Context;
+    /// use std::{thread, time::Duration};
+    ///
+    /// let mut context = Context::new();
+    /// let handle = context.interrupt_handle();
+    /// thread::spawn(move || {
+    ///     thread::sleep(Duration::from_millis(50));
+    ///     handle.interrupt();
+    /// });
+    ///
+    /// assert!(context.eval("while (true) { try {} catch (e) {} }").is_err());
+    /// assert!(context.is_terminated());
+    /// assert_eq!(context.eval("1 + 1").unwrap().to_string(), "2");
+    /// ```
+    #[inline]
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.interpreter.interrupt_handle()
+    }
+
+    /// Checks if the last script was terminated, by an interruption, by its step budget or by
+    /// its memory limit.
+    #[inline]
+    pub fn is_terminated(&self) -> bool {
+        self.interpreter.is_terminated()
+    }
+
+    /// Sets the writer that the `console` object prints its messages to.
+    ///
+    /// By default messages are printed to the standard output and the standard error.
+    #[inline]
+    pub fn set_console_writer<W>(&mut self, writer: W)
+    where
+        W: ConsoleWriter + 'static,
+    {
+        self.interpreter.set_console_writer(writer);
+    }
+
+    /// Runs the pending microtasks, like the reactions of the promises settled by the host.
+    ///
+    /// Returns the first error thrown by a microtask, the following ones still run.
+    #[inline]
+    pub fn run_jobs(&mut self) -> Result<(), Value> {
+        self.interpreter.run_jobs()
+    }
+
+    /// Creates a future that completes when a promise settles, see
+    /// `Interpreter::promise_to_future`.
+    #[inline]
+    pub fn promise_to_future(&mut self, promise: &Value) -> Result<PromiseFuture, Value> {
+        self.interpreter.promise_to_future(promise)
+    }
+
+    /// Creates a promise that is settled with the output of a future of the host.
+    ///
+    /// Asynchronous host functions return such promises, and the host drives the futures with
+    /// `run_event_loop`:
+    ///
+    /// ```
+    /// use boa::{builtins::value::Value, Context};
+    /// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    /// #     use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+    /// #     fn raw() -> RawWaker {
+    /// #         RawWaker::new(std::ptr::null(), &VTABLE)
+    /// #     }
+    /// #     static VTABLE: RawWakerVTable = RawWakerVTable::new(|_| raw(), |_| {}, |_| {}, |_| {});
+    /// #     let waker = unsafe { Waker::from_raw(raw()) };
+    /// #     let mut future = Box::pin(future);
+    /// #     loop {
+    /// #         if let Poll::Ready(output) = future.as_mut().poll(&mut Context::from_waker(&waker)) {
+    /// #             return output;
+    /// #         }
+    /// #     }
+    /// # }
+    ///
+    /// let mut context = Context::new();
+    /// context.register_global_function("fetchAnswer", 0, |_, _, ctx| {
+    ///     ctx.promise_from_future(async { Ok(Value::from(42)) })
+    /// });
+    ///
+    /// let promise = context.eval("fetchAnswer().then(function (x) { return x * 2; })").unwrap();
+    /// let result = context.promise_to_future(&promise).unwrap();
+    /// block_on(context.run_event_loop()).unwrap();
+    /// assert_eq!(block_on(result).unwrap().to_string(), "84");
+    /// ```
+    #[inline]
+    pub fn promise_from_future<F>(&mut self, future: F) -> ResultValue
+    where
+        F: std::future::Future<Output = ResultValue> + 'static,
+    {
+        self.interpreter.promise_from_future(future)
+    }
+
+    /// Creates a future that runs the pending microtasks and polls the futures of the host
+    /// until there is nothing left to do, see `Interpreter::run_event_loop`.
+    #[inline]
+    pub fn run_event_loop(&mut self) -> EventLoop<'_> {
+        self.interpreter.run_event_loop()
+    }
+
+    /// Creates a new realm, with its own global object and builtins.
+    ///
+    /// Scripts run in a realm with `eval_in_realm`, and values are passed between realms with
+    /// `transfer`, which keeps the realms isolated, for instance to sandbox plugins:
+    ///
+    /// ```
+    /// use boa::Context;
+    ///
+    /// let mut context = Context::new();
+    /// let main = context.interpreter().current_realm();
+    /// let plugin = context.create_realm();
+    ///
+    /// context.eval_in_realm(plugin, "Array.prototype.plugin = true").unwrap();
+    /// let list = context.eval_in_realm(plugin, "[1, 2, 3]").unwrap();
+    ///
+    /// let list = context.transfer(&list, plugin, main).unwrap();
+    /// context.global_object().set_field("list", list);
+    /// assert_eq!(context.eval("list.length").unwrap().to_string(), "3");
+    /// assert_eq!(context.eval("list.plugin").unwrap().to_string(), "undefined");
+    /// ```
+    #[inline]
+    pub fn create_realm(&mut self) -> RealmId {
+        self.interpreter.create_realm()
+    }
+
+    /// Runs a script in the given realm, like `eval` does in the current one.
+    ///
+    /// The returned value belongs to that realm.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `realm` was not created by this context.
+    pub fn eval_in_realm(&mut self, realm: RealmId, src: &str) -> Result<Value, Value> {
+        let statements = match crate::compile(&mut self.interpreter, src) {
+            Ok(statements) => statements,
+            Err(message) => {
+                return Err(self
+                    .interpreter
+                    .with_realm(realm, |ctx| ctx.construct_syntax_error(message.to_string())))
+            }
+        };
+        self.interpreter
+            .with_realm(realm, |ctx| crate::run(ctx, &statements))
+    }
+
+    /// Passes a value from the realm `from` to the realm `to`, see `Interpreter::transfer`.
+    #[inline]
+    pub fn transfer(&mut self, value: &Value, from: RealmId, to: RealmId) -> Result<Value, Value> {
+        self.interpreter.transfer(value, from, to)
+    }
+
+    /// Defines a global function that runs a Rust closure.
+    ///
+    /// The closure receives the `this` value, the arguments and the interpreter, and can capture
+    /// host state:
+    ///
+    /// ```
+    /// use boa::{builtins::value::Value, Context};
+    /// use std::{cell::RefCell, rc::Rc};
+    ///
+    /// let mut context = Context::new();
+    /// let log = Rc::new(RefCell::new(Vec::new()));
+    /// let messages = Rc::clone(&log);
+    /// context.register_global_function("record", 1, move |_, args, ctx| {
+    ///     let message = ctx.to_string(&args.get(0).cloned().unwrap_or_default())?;
+    ///     messages.borrow_mut().push(message.to_string());
+    ///     Ok(Value::undefined())
+    /// });
+    ///
+    /// context.eval("record('hello')").unwrap();
+    /// assert_eq!(*log.borrow(), ["hello"]);
+    /// ```
+    pub fn register_global_function<F>(&mut self, name: &str, length: usize, function: F)
+    where
+        F: Fn(&Value, &[Value], &mut Interpreter) -> ResultValue + 'static,
+    {
+        make_closure_fn(function, name, self.global_object(), length);
+    }
+
+    /// Defines a global class whose instances hold a value of the Rust type `T`.
+    ///
+    /// See the `class` module for an example.
+    pub fn register_global_class<T: Class>(&mut self) {
+        let constructor = make_class::<T>(self.global_object());
+        self.global_object()
+            .as_object_mut()
+            .expect("the global object is an object")
+            .insert_field(T::NAME, constructor);
+    }
+
+    /// Returns where a function was defined in the scripts run by this context, and its name.
+    ///
+    /// This lets hosts attribute the callbacks they receive to script locations:
+    ///
+    /// ```
+    /// use boa::Context;
+    ///
+    /// let mut context = Context::new();
+    /// context.interpreter_mut().set_script_name("timer.js");
+    /// let callback = context.eval("let f = 1;\nfunction onTick() {}\nonTick").unwrap();
+    /// let origin = context.function_origin(&callback).unwrap();
+    /// assert_eq!(origin.to_string(), "onTick (timer.js:2:1)");
+    /// ```
+    ///
+    /// Returns `None` for values that are not functions and for built-in functions. Positions
+    /// are relative to the source passed to the `eval` call that defined the function, and the
+    /// script is the name that the interpreter gave to that source, see
+    /// `Interpreter::set_script_name`.
+    pub fn function_origin(&self, function: &Value) -> Option<FunctionOrigin> {
+        let (position, script) = match function.as_object()?.data {
+            ObjectData::Function(ref function) => (
+                function.position()?,
+                function.script().unwrap_or(ANONYMOUS_SCRIPT).to_owned(),
+            ),
+            _ => return None,
+        };
+        let name = match function.get_field("name") {
+            Value::String(ref name) if !name.is_empty() => Some(name.to_string()),
+            _ => None,
+        };
+        Some(FunctionOrigin {
+            name,
+            script,
+            position,
+        })
+    }
+
+    /// Gets the value of a property of an object, calling its getter if it has one.
+    ///
+    /// Together with `set`, `define_property`, `has`, `delete`, `keys`, `call` and `construct`,
+    /// it lets the host build and inspect objects without evaluating source code:
+    ///
+    /// ```
+    /// use boa::{builtins::value::Value, Context};
+    ///
+    /// let mut context = Context::new();
+    /// let object = context.global_object().get_field("Object");
+    /// let point = context.construct(&object, &[]).unwrap();
+    /// context.set(&point, "x", 3).unwrap();
+    /// context.set(&point, "y", 4).unwrap();
+    /// assert_eq!(context.keys(&point).unwrap().len(), 2);
+    ///
+    /// let max = context.eval("Math.max").unwrap();
+    /// let x = context.get(&point, "x").unwrap();
+    /// let y = context.get(&point, "y").unwrap();
+    /// let result = context.call(&max, &Value::undefined(), &[x, y]).unwrap();
+    /// assert_eq!(result.to_number(), 4.0);
+    /// ```
+    pub fn get<K>(&mut self, object: &Value, key: K) -> Result<Value, Value>
+    where
+        K: Into<PropertyKey>,
+    {
+        let object = self.interpreter.to_object(object)?;
+        self.interpreter.get_field(&object, key)
+    }
+
+    /// Sets the value of a property of an object, calling its setter if it has one.
+    ///
+    /// Returns a `TypeError` if `object` is not an object.
+    pub fn set<K, V>(&mut self, object: &Value, key: K, value: V) -> Result<(), Value>
+    where
+        K: Into<PropertyKey>,
+        V: Into<Value>,
+    {
+        self.require_object(object)?;
+        self.interpreter.set_field(object, key, value.into())?;
+        Ok(())
+    }
+
+    /// Defines or redefines a property of an object, like `Object.defineProperty`.
+    ///
+    /// Returns a `TypeError` if `object` is not an object or if the property cannot be redefined.
+    pub fn define_property<K>(
+        &mut self,
+        object: &Value,
+        key: K,
+        property: Property,
+    ) -> Result<(), Value>
+    where
+        K: Into<PropertyKey>,
+    {
+        self.require_object(object)?;
+        let key = key.into();
+        let defined = object
+            .as_object_mut()
+            .expect("object was checked")
+            .define_own_property(key.clone(), property);
+        if defined {
+            Ok(())
+        } else {
+            Err(self
+                .interpreter
+                .construct_type_error(format!("cannot redefine property: {}", key)))
+        }
+    }
+
+    /// Checks if an object has a property, either its own or an inherited one, like the `in`
+    /// operator.
+    ///
+    /// Values that are not objects have no properties.
+    pub fn has<K>(&self, object: &Value, key: K) -> bool
+    where
+        K: Into<PropertyKey>,
+    {
+        self.interpreter.has_property(object, &key.into())
+    }
+
+    /// Deletes an own property of an object, like the `delete` operator.
+    ///
+    /// Returns `false` if the property is not configurable, and a `TypeError` if `object` is
+    /// not an object.
+    pub fn delete<K>(&mut self, object: &Value, key: K) -> Result<bool, Value>
+    where
+        K: Into<PropertyKey>,
+    {
+        self.require_object(object)?;
+        Ok(object
+            .as_object_mut()
+            .expect("object was checked")
+            .delete(&key.into()))
+    }
+
+    /// Gets the names of the own enumerable properties of an object, like `Object.keys`.
+    ///
+    /// Returns a `TypeError` if `object` is not an object.
+    pub fn keys(&mut self, object: &Value) -> Result<Vec<JsString>, Value> {
+        self.require_object(object)?;
+        let object = object.as_object().expect("object was checked");
+        Ok(object
+            .own_property_keys()
+            .into_iter()
+            .filter(|key| !matches!(key, PropertyKey::Symbol(_)))
+            .filter(|key| object.get_own_property(key).enumerable_or(false))
+            .map(|key| JsString::from(key.to_string()))
+            .collect())
+    }
+
+    /// Calls a function with the given `this` value and arguments.
+    ///
+    /// Returns a `TypeError` if `function` is not a function.
+    pub fn call(&mut self, function: &Value, this: &Value, args: &[Value]) -> ResultValue {
+        self.interpreter.call(function, this, args)
+    }
+
+    /// Creates an object with a constructor and the given arguments, like the `new` operator.
+    ///
+    /// Returns a `TypeError` if `constructor` is not a constructor.
+    pub fn construct(&mut self, constructor: &Value, args: &[Value]) -> ResultValue {
+        self.interpreter.construct(constructor, args)
+    }
+
+    /// Returns a `TypeError` if `value` is not an object.
+    fn require_object(&mut self, value: &Value) -> Result<(), Value> {
+        if value.is_object() {
+            Ok(())
+        } else {
+            Err(self.interpreter.construct_type_error("not an object"))
+        }
+    }
+
+    /// Converts a Rust value into a JavaScript value, through its `serde` representation.
+    ///
+    /// The value is converted like `JSON.parse` would convert its JSON representation, so maps
+    /// and structs become plain objects and sequences become arrays.
+    ///
+    /// ```
+    /// # use boa::Context;
+    /// # use std::collections::BTreeMap;
+    /// let mut context = Context::new();
+    /// let scores: BTreeMap<_, _> = vec![("alice", 3), ("bob", 5)].into_iter().collect();
+    /// let value = context.to_value(&scores).unwrap();
+    /// context.global_object().set_field("scores", value);
+    /// assert_eq!(context.eval("scores.alice + scores.bob").unwrap().to_string(), "8");
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_value<T>(&mut self, value: &T) -> Result<Value, Value>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        match serde_json::to_value(value) {
+            Ok(json) => Ok(Value::from_json(json, &mut self.interpreter)),
+            Err(err) => Err(self.interpreter.construct_type_error(err.to_string())),
+        }
+    }
+
+    /// Converts a JavaScript value into a Rust value, through its `serde` representation.
+    ///
+    /// The value is converted like `JSON.stringify` would convert it, `undefined` is treated as
+    /// `null`. A `TypeError` is returned if the value doesn't match the type.
+    ///
+    /// ```
+    /// # use boa::Context;
+    /// let mut context = Context::new();
+    /// let value = context.eval("[1, 2, 3].map(function (x) { return x * 2; })").unwrap();
+    /// let doubled: Vec<u32> = context.from_value(&value).unwrap();
+    /// assert_eq!(doubled, [2, 4, 6]);
+    /// ```
+    #[cfg(feature = "serde")]
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_value<T>(&mut self, value: &Value) -> Result<T, Value>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let json = match value {
+            Value::Undefined => serde_json::Value::Null,
+            Value::Symbol(_) => {
+                return Err(self
+                    .interpreter
+                    .construct_type_error("Symbol value can't be deserialized"))
+            }
+            value => value.to_json(&mut self.interpreter)?,
+        };
+        serde_json::from_value(json)
+            .map_err(|err| self.interpreter.construct_type_error(err.to_string()))
+    }
+
+    /// Gets the global object of the context.
+    #[inline]
+    pub fn global_object(&self) -> &Value {
+        &self.interpreter.realm.global_obj
+    }
+
+    /// Gets the global environment of the context, which holds the global bindings, like the
+    /// global `let` and `const` declarations.
+    #[inline]
+    pub fn global_environment(&self) -> &Environment {
+        self.interpreter
+            .realm
+            .environment
+            .environments()
+            .last()
+            .expect("the global environment")
+    }
+
+    /// Gets the interpreter of the context.
+    #[inline]
+    pub fn interpreter(&self) -> &Interpreter {
+        &self.interpreter
+    }
+
+    /// Gets the interpreter of the context mutably, to access its debugging and profiling hooks.
+    #[inline]
+    pub fn interpreter_mut(&mut self) -> &mut Interpreter {
+        &mut self.interpreter
+    }
+}
+
+impl Default for Context {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Where a function was defined, returned by `Context::function_origin`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionOrigin {
+    /// The name of the function, if it has one.
+    pub name: Option<String>,
+    /// The name of the script that defined the function, like the path of its file, or
+    /// `<anonymous>` if the script has no name.
+    pub script: String,
+    /// The position of the start of the function in the source code of the script.
+    pub position: Position,
+}
+
+impl Display for FunctionOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.name.as_deref().unwrap_or("<anonymous>");
+        write!(f, "{} ({}:{})", name, self.script, self.position)
+    }
+}
+
+/// A builder for `Context`.
+///
+/// Every option that is not set keeps the default of the interpreter.
+#[derive(Debug, Default, Clone)]
+pub struct ContextBuilder {
+    max_string_length: Option<usize>,
+    max_array_length: Option<usize>,
+    locale: Option<String>,
+    random_seed: Option<u64>,
+    clock: Option<u64>,
+    step_budget: Option<u64>,
+    memory_limit: Option<usize>,
+    max_stack_size: Option<usize>,
+    timeline: bool,
+    profile: bool,
+    coverage: bool,
+    cpu_profile: bool,
+    timings: bool,
+    optimize: bool,
+    lazy_parsing: bool,
+    strict: bool,
+    experimental: Option<Experimental>,
+    annex_b: Option<bool>,
+    removed_builtins: Vec<String>,
+    frozen_globals: Vec<(String, Value)>,
+    frozen_global_object: bool,
+}
+
+impl ContextBuilder {
+    /// Sets the maximum length of a string, in UTF-16 code units, that scripts are allowed to create.
+    #[inline]
+    pub fn max_string_length(mut self, max_string_length: usize) -> Self {
+        self.max_string_length = Some(max_string_length);
+        self
+    }
+
+    /// Sets the maximum amount of elements that scripts are allowed to store in an array.
+    #[inline]
+    pub fn max_array_length(mut self, max_array_length: usize) -> Self {
+        self.max_array_length = Some(max_array_length);
+        self
+    }
+
+    /// Sets the default locale of the locale-sensitive methods, like `"en-US"` or `"de-DE"`.
+    ///
+    /// ```
+    /// use boa::Context;
+    ///
+    /// let mut context = Context::builder().locale("de-DE").build();
+    /// let value = context.eval("(1234.5).toLocaleString()").unwrap();
+    /// assert_eq!(value.to_string(), "1.234,5");
+    /// ```
+    #[inline]
+    pub fn locale<L: Into<String>>(mut self, locale: L) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Sets the seed of `Math.random`, see `Interpreter::set_random_seed`.
+    ///
+    /// ```
+    /// use boa::Context;
+    ///
+    /// let mut first = Context::builder().random_seed(42).build();
+    /// let mut second = Context::builder().random_seed(42).build();
+    /// assert_eq!(
+    ///     first.eval("Math.random()").unwrap().to_number(),
+    ///     second.eval("Math.random()").unwrap().to_number()
+    /// );
+    /// ```
+    #[inline]
+    pub fn random_seed(mut self, seed: u64) -> Self {
+        self.random_seed = Some(seed);
+        self
+    }
+
+    /// Pins the clock of the scripts to a time, in milliseconds since the Unix epoch, see
+    /// `Interpreter::set_clock`.
+    #[inline]
+    pub fn clock(mut self, clock: u64) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Sets the maximum number of steps, loop iterations and function calls, that each script
+    /// may take before it is terminated.
+    #[inline]
+    pub fn step_budget(mut self, step_budget: u64) -> Self {
+        self.step_budget = Some(step_budget);
+        self
+    }
+
+    /// Sets the maximum number of bytes that each script may allocate before it is terminated.
+    ///
+    /// The limit is only enforced if a `memory::CountingAllocator` is the global allocator.
+    #[inline]
+    pub fn memory_limit(mut self, memory_limit: usize) -> Self {
+        self.memory_limit = Some(memory_limit);
+        self
+    }
+
+    /// Sets the maximum number of bytes of native stack that scripts may use, see
+    /// `Interpreter::set_max_stack_size`.
+    #[inline]
+    pub fn max_stack_size(mut self, max_stack_size: usize) -> Self {
+        self.max_stack_size = Some(max_stack_size);
+        self
+    }
+
+    /// Records the execution timeline of the context.
+    #[inline]
+    pub fn timeline(mut self, enabled: bool) -> Self {
+        self.timeline = enabled;
+        self
+    }
+
+    /// Records the execution profile of the context, see `Interpreter::enable_profile`.
+    #[inline]
+    pub fn profile(mut self, enabled: bool) -> Self {
+        self.profile = enabled;
+        self
+    }
+
+    /// Records the code coverage of the context, see `Interpreter::enable_coverage`.
+    #[inline]
+    pub fn coverage(mut self, enabled: bool) -> Self {
+        self.coverage = enabled;
+        self
+    }
+
+    /// Records the CPU profile of the context, see `Interpreter::enable_cpu_profile`.
+    #[inline]
+    pub fn cpu_profile(mut self, enabled: bool) -> Self {
+        self.cpu_profile = enabled;
+        self
+    }
+
+    /// Measures the time spent lexing, parsing and running scripts, see
+    /// `Interpreter::enable_timings`.
+    #[inline]
+    pub fn timings(mut self, enabled: bool) -> Self {
+        self.timings = enabled;
+        self
+    }
+
+    /// Optimizes the syntax tree of scripts before they run, see `Interpreter::set_optimize`.
+    #[inline]
+    pub fn optimize(mut self, enabled: bool) -> Self {
+        self.optimize = enabled;
+        self
+    }
+
+    /// Only parses function bodies when the functions are first called, see
+    /// `Interpreter::set_lazy_parsing`.
+    #[inline]
+    pub fn lazy_parsing(mut self, enabled: bool) -> Self {
+        self.lazy_parsing = enabled;
+        self
+    }
+
+    /// Runs every script as strict mode code, see `Interpreter::set_strict`.
+    #[inline]
+    pub fn strict(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+
+    /// Enables the given experimental features, and disables the others, see
+    /// `Interpreter::set_experimental`.
+    #[inline]
+    pub fn experimental(mut self, experimental: Experimental) -> Self {
+        self.experimental = Some(experimental);
+        self
+    }
+
+    /// Enables or disables the web legacy compatibility features of Annex B, see
+    /// `Interpreter::set_annex_b`. They are enabled by default.
+    #[inline]
+    pub fn annex_b(mut self, enabled: bool) -> Self {
+        self.annex_b = Some(enabled);
+        self
+    }
+
+    /// Leaves a builtin out of the global object, like `"Math"` or `"Promise"`, see
+    /// `Realm::remove_builtin`.
+    ///
+    /// Together with `frozen_global` and `frozen_global_object`, it sandboxes the scripts of
+    /// the context to the globals chosen by the host:
+    ///
+    /// ```
+    /// use boa::{builtins::value::Value, Context};
+    ///
+    /// let mut context = Context::builder()
+    ///     .remove_builtin("Math")
+    ///     .remove_builtin("Promise")
+    ///     .frozen_global("version", "1.0")
+    ///     .frozen_global_object(true)
+    ///     .build();
+    ///
+    /// assert_eq!(context.eval("typeof globalThis.Math").unwrap().to_string(), "undefined");
+    /// assert_eq!(context.eval("version = '2.0'; version").unwrap().to_string(), "1.0");
+    /// assert_eq!(context.eval("Array = null; typeof Array").unwrap().to_string(), "function");
+    /// assert_eq!(context.eval("let x = 1; x + 1").unwrap().to_string(), "2");
+    /// ```
+    ///
+    /// Removing `"Function"` keeps scripts from compiling code at run time with
+    /// `new Function(...)`, while the functions they declare keep working.
+    ///
+    /// Realms created later with `Context::create_realm` have all the builtins.
+    ///
+    /// # Panics
+    ///
+    /// `build` panics if `name` is one of the builtins that the engine needs, listed in
+    /// `Realm::REQUIRED_BUILTINS`.
+    #[inline]
+    pub fn remove_builtin<N: Into<String>>(mut self, name: N) -> Self {
+        self.removed_builtins.push(name.into());
+        self
+    }
+
+    /// Defines a global of the host that scripts can read but not change, see
+    /// `Realm::define_frozen_global`.
+    #[inline]
+    pub fn frozen_global<N, V>(mut self, name: N, value: V) -> Self
+    where
+        N: Into<String>,
+        V: Into<Value>,
+    {
+        self.frozen_globals.push((name.into(), value.into()));
+        self
+    }
+
+    /// Freezes the global object once the builtins and the globals of the host are defined, so
+    /// that scripts can not add, change or delete global properties, see
+    /// `Realm::freeze_global_object`.
+    #[inline]
+    pub fn frozen_global_object(mut self, enabled: bool) -> Self {
+        self.frozen_global_object = enabled;
+        self
+    }
+
+    /// Creates the context.
+    pub fn build(self) -> Context {
+        let mut interpreter = Interpreter::new(Realm::create());
+        if let Some(max_string_length) = self.max_string_length {
+            interpreter.set_max_string_length(max_string_length);
+        }
+        if let Some(max_array_length) = self.max_array_length {
+            interpreter.set_max_array_length(max_array_length);
+        }
+        interpreter.set_locale(self.locale);
+        interpreter.set_random_seed(self.random_seed);
+        interpreter.set_clock(self.clock);
+        interpreter.set_step_budget(self.step_budget);
+        interpreter.set_memory_limit(self.memory_limit);
+        if let Some(max_stack_size) = self.max_stack_size {
+            interpreter.set_max_stack_size(max_stack_size);
+        }
+        if self.timeline {
+            interpreter.enable_timeline();
+        }
+        if self.profile {
+            interpreter.enable_profile();
+        }
+        if self.coverage {
+            interpreter.enable_coverage();
+        }
+        if self.cpu_profile {
+            interpreter.enable_cpu_profile();
+        }
+        if self.timings {
+            interpreter.enable_timings();
+        }
+        interpreter.set_optimize(self.optimize);
+        interpreter.set_lazy_parsing(self.lazy_parsing);
+        interpreter.set_strict(self.strict);
+        if let Some(experimental) = self.experimental {
+            interpreter.set_experimental(experimental);
+        }
+        if let Some(enabled) = self.annex_b {
+            interpreter.set_annex_b(enabled);
+        }
+        for name in &self.removed_builtins {
+            interpreter.realm().remove_builtin(name);
+        }
+        for (name, value) in self.frozen_globals {
+            interpreter.realm().define_frozen_global(&name, value);
+        }
+        if self.frozen_global_object {
+            interpreter.realm().freeze_global_object();
+        }
+        Context { interpreter }
+    }
+}
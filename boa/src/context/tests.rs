@@ -0,0 +1,570 @@
+use crate::{builtins::value::Value, Context};
+use std::{cell::Cell, rc::Rc};
+
+#[test]
+fn eval_returns_completion_value() {
+    let mut context = Context::new();
+    assert_eq!(
+        context.eval("let x = 20; x + 22").unwrap().to_string(),
+        "42"
+    );
+    assert_eq!(context.eval("x").unwrap().to_string(), "20");
+}
+
+#[test]
+fn eval_returns_thrown_value() {
+    let mut context = Context::new();
+    let error = context.eval("throw 'oops'").unwrap_err();
+    assert_eq!(error.to_string(), "oops");
+}
+
+#[test]
+fn eval_returns_syntax_errors() {
+    let mut context = Context::new();
+    let error = context.eval("let = ;").unwrap_err();
+    assert_eq!(error.get_field("name").to_string(), "SyntaxError");
+}
+
+#[test]
+fn global_object_is_shared_with_scripts() {
+    let mut context = Context::new();
+    context.global_object().set_field("answer", Value::from(42));
+    assert_eq!(context.eval("answer").unwrap().to_string(), "42");
+}
+
+#[test]
+fn builder_configures_the_interpreter() {
+    let mut context = Context::builder()
+        .max_string_length(4)
+        .timeline(true)
+        .build();
+    assert_eq!(context.interpreter().max_string_length(), 4);
+    assert!(context.interpreter().timeline().is_some());
+    assert!(context.eval("'abc' + 'de'").is_err());
+}
+
+#[test]
+fn lazy_parsing_defers_function_bodies() {
+    let mut context = Context::builder().lazy_parsing(true).build();
+    let script = "function add(a, b) { return a + b } function broken() { return ) }";
+    assert!(context.eval(script).is_ok());
+    assert_eq!(context.eval("add(40, 2)").unwrap().to_string(), "42");
+    assert_eq!(context.eval("add(1, 2)").unwrap().to_string(), "3");
+    let error = context.eval("broken()").unwrap_err();
+    assert!(error.to_string().starts_with("SyntaxError"), "{}", error);
+    assert!(context.is_terminated());
+
+    // The error is reported as an early error, which scripts cannot catch.
+    let result = context.eval("var caught = false; try { broken() } catch (e) { caught = true }");
+    assert!(result.is_err());
+    assert_eq!(context.eval("caught").unwrap().to_string(), "false");
+}
+
+#[test]
+fn register_global_function() {
+    let mut context = Context::new();
+    context.register_global_function("add", 2, |_, args, ctx| {
+        let x = ctx.to_number(&args.get(0).cloned().unwrap_or_default())?;
+        let y = ctx.to_number(&args.get(1).cloned().unwrap_or_default())?;
+        Ok(Value::from(x + y))
+    });
+    assert_eq!(context.eval("add(40, 2)").unwrap().to_string(), "42");
+    assert_eq!(context.eval("add.length").unwrap().to_string(), "2");
+}
+
+#[test]
+fn register_global_function_with_captured_state() {
+    let mut context = Context::new();
+    let counter = Rc::new(Cell::new(0));
+    let calls = Rc::clone(&counter);
+    context.register_global_function("tick", 0, move |_, _, _| {
+        calls.set(calls.get() + 1);
+        Ok(Value::from(calls.get()))
+    });
+    assert_eq!(context.eval("tick(); tick()").unwrap().to_string(), "2");
+    assert_eq!(counter.get(), 2);
+}
+
+#[test]
+fn register_global_function_errors_are_thrown() {
+    let mut context = Context::new();
+    context.register_global_function("fail", 0, |_, _, ctx| ctx.throw_range_error("out of range"));
+    assert_eq!(
+        context
+            .eval("try { fail() } catch (e) { e.name }")
+            .unwrap()
+            .to_string(),
+        "RangeError"
+    );
+}
+
+#[cfg(feature = "serde")]
+mod serde_interop {
+    use crate::{builtins::value::Value, Context};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+        label: Option<String>,
+    }
+
+    #[test]
+    fn to_value() {
+        let mut context = Context::new();
+        let point = Point {
+            x: 1,
+            y: 2,
+            label: Some("origin".to_owned()),
+        };
+        let value = context.to_value(&point).unwrap();
+        context.global_object().set_field("point", value);
+        assert_eq!(context.eval("point.x + point.y").unwrap().to_string(), "3");
+        assert_eq!(context.eval("point.label").unwrap().to_string(), "origin");
+    }
+
+    #[test]
+    fn from_value() {
+        let mut context = Context::new();
+        let value = context.eval("({ x: 3, y: 4 })").unwrap();
+        let point: Point = context.from_value(&value).unwrap();
+        assert_eq!(
+            point,
+            Point {
+                x: 3,
+                y: 4,
+                label: None
+            }
+        );
+
+        let nothing: Option<Point> = context.from_value(&Value::undefined()).unwrap();
+        assert_eq!(nothing, None);
+    }
+
+    #[test]
+    fn from_value_type_mismatch() {
+        let mut context = Context::new();
+        let value = context.eval("'not a point'").unwrap();
+        let error = context.from_value::<Point>(&value).unwrap_err();
+        assert_eq!(error.get_field("name").to_string(), "TypeError");
+    }
+}
+
+#[test]
+fn function_origin() {
+    let mut context = Context::new();
+    context.interpreter_mut().set_script_name("main.js");
+    context
+        .eval(
+            "function named() {}\n\
+             let expression = function () {};\n\
+             let arrow = (a) => a;\n\
+             let object = { method() {} };",
+        )
+        .unwrap();
+
+    let origin = |context: &mut Context, name: &str| {
+        let value = context.eval(name).unwrap();
+        context
+            .function_origin(&value)
+            .map(|origin| origin.to_string())
+    };
+    assert_eq!(
+        origin(&mut context, "named").as_deref(),
+        Some("named (main.js:1:1)")
+    );
+    assert_eq!(
+        origin(&mut context, "expression").as_deref(),
+        Some("<anonymous> (main.js:2:18)")
+    );
+    assert_eq!(
+        origin(&mut context, "arrow").as_deref(),
+        Some("<anonymous> (main.js:3:13)")
+    );
+    assert_eq!(
+        origin(&mut context, "object.method").as_deref(),
+        Some("<anonymous> (main.js:4:16)")
+    );
+    assert_eq!(origin(&mut context, "Math.max"), None);
+    assert_eq!(origin(&mut context, "1"), None);
+
+    // The functions keep the script that defined them.
+    context.interpreter_mut().set_script_name("other.js");
+    context.eval("function other() {}").unwrap();
+    assert_eq!(
+        origin(&mut context, "named").as_deref(),
+        Some("named (main.js:1:1)")
+    );
+    assert_eq!(
+        origin(&mut context, "other").as_deref(),
+        Some("other (other.js:1:1)")
+    );
+}
+
+#[test]
+fn realms_have_their_own_globals() {
+    let mut context = Context::new();
+    let realm = context.create_realm();
+    context.eval("var shared = 'main'").unwrap();
+    context
+        .eval_in_realm(realm, "var shared = 'other'")
+        .unwrap();
+
+    assert_eq!(context.eval("shared").unwrap().to_string(), "main");
+    assert_eq!(
+        context.eval_in_realm(realm, "shared").unwrap().to_string(),
+        "other"
+    );
+    assert_eq!(
+        context
+            .eval_in_realm(realm, "Object === this.Object")
+            .unwrap()
+            .to_string(),
+        "true"
+    );
+}
+
+#[test]
+fn reset_realm_forgets_globals() {
+    let mut context = Context::new();
+    context
+        .eval("var defined = 1; let declared = 2; Array.prototype.extra = 3")
+        .unwrap();
+    context.interpreter_mut().reset_realm();
+
+    assert!(context.eval("defined").is_err());
+    assert!(context.eval("declared").is_err());
+    assert_eq!(
+        context.eval("typeof [].extra").unwrap().to_string(),
+        "undefined"
+    );
+    assert_eq!(context.eval("[1, 2].length").unwrap().to_string(), "2");
+}
+
+#[test]
+fn transfer_copies_objects_with_the_target_prototypes() {
+    let mut context = Context::new();
+    let main = context.interpreter().current_realm();
+    let realm = context.create_realm();
+    context
+        .eval_in_realm(realm, "Object.prototype.leak = 1")
+        .unwrap();
+    let object = context
+        .eval_in_realm(realm, "({ nested: { value: 42 } })")
+        .unwrap();
+
+    let object = context.transfer(&object, realm, main).unwrap();
+    context.global_object().set_field("object", object);
+    assert_eq!(
+        context.eval("object.nested.value").unwrap().to_string(),
+        "42"
+    );
+    assert_eq!(
+        context
+            .eval("Object.getPrototypeOf(object) === Object.prototype")
+            .unwrap()
+            .to_string(),
+        "true"
+    );
+    assert_eq!(
+        context.eval("typeof object.leak").unwrap().to_string(),
+        "undefined"
+    );
+}
+
+#[test]
+fn transferred_functions_run_in_their_realm() {
+    let mut context = Context::new();
+    let main = context.interpreter().current_realm();
+    let realm = context.create_realm();
+    let function = context
+        .eval_in_realm(
+            realm,
+            "var calls = 0; function wrap(x) { calls++; return { value: x }; }; wrap",
+        )
+        .unwrap();
+    let thrower = context
+        .eval_in_realm(
+            realm,
+            "function fail() { throw new TypeError('nope'); }; fail",
+        )
+        .unwrap();
+
+    let function = context.transfer(&function, realm, main).unwrap();
+    let thrower = context.transfer(&thrower, realm, main).unwrap();
+    context.global_object().set_field("wrap", function);
+    context.global_object().set_field("fail", thrower);
+
+    assert_eq!(context.eval("wrap(7).value").unwrap().to_string(), "7");
+    assert_eq!(
+        context
+            .eval("Object.getPrototypeOf(wrap(7)) === Object.prototype")
+            .unwrap()
+            .to_string(),
+        "true"
+    );
+    assert_eq!(
+        context.eval_in_realm(realm, "calls").unwrap().to_string(),
+        "2"
+    );
+    assert_eq!(
+        context
+            .eval("try { fail() } catch (e) { e.message }")
+            .unwrap()
+            .to_string(),
+        "nope"
+    );
+}
+
+#[test]
+fn object_api_reads_and_writes_properties() {
+    use crate::builtins::property::{Attribute, Property};
+
+    let mut context = Context::new();
+    let object = context
+        .eval(
+            "var object = { a: 1 };
+            Object.defineProperty(object, 'double', {
+                get: function () { return this.a * 2; },
+                set: function (v) { this.a = v / 2; },
+                enumerable: true,
+            });
+            object",
+        )
+        .unwrap();
+
+    assert_eq!(context.get(&object, "double").unwrap().to_string(), "2");
+    context.set(&object, "double", 10).unwrap();
+    assert_eq!(context.get(&object, "a").unwrap().to_string(), "5");
+    assert!(context.has(&object, "toString"));
+    assert!(!context.has(&object, "missing"));
+    assert!(context
+        .get(&Value::from("abc"), "charAt")
+        .unwrap()
+        .is_function());
+
+    let hidden = Property::data_descriptor(Value::from(true), Attribute::NON_ENUMERABLE);
+    context.define_property(&object, "hidden", hidden).unwrap();
+    let mut keys = context.keys(&object).unwrap();
+    keys.sort();
+    assert_eq!(keys, ["a", "double"]);
+    assert!(!context.delete(&object, "hidden").unwrap());
+    assert!(context.delete(&object, "a").unwrap());
+    assert!(!context.has(&object, "a"));
+
+    let error = context.set(&Value::from(1), "a", 1).unwrap_err();
+    assert_eq!(error.to_string(), "TypeError: not an object");
+}
+
+#[test]
+fn object_api_calls_functions() {
+    let mut context = Context::new();
+    let point = context
+        .eval("function Point(x) { this.x = x; } Point.prototype.getX = function () { return this.x; }; Point")
+        .unwrap();
+
+    let instance = context.construct(&point, &[Value::from(7)]).unwrap();
+    let get_x = context.get(&instance, "getX").unwrap();
+    assert_eq!(
+        context.call(&get_x, &instance, &[]).unwrap().to_string(),
+        "7"
+    );
+
+    let error = context
+        .call(&Value::from(1), &Value::undefined(), &[])
+        .unwrap_err();
+    assert_eq!(error.to_string(), "TypeError: not a function");
+}
+
+/// Polls a future once, with a waker that counts how many times it is woken.
+fn poll_once<F: std::future::Future + Unpin>(
+    future: &mut F,
+    wakes: &std::sync::Arc<WakeCounter>,
+) -> std::task::Poll<F::Output> {
+    let waker = std::task::Waker::from(wakes.clone());
+    std::pin::Pin::new(future).poll(&mut std::task::Context::from_waker(&waker))
+}
+
+#[derive(Debug, Default)]
+struct WakeCounter(std::sync::atomic::AtomicUsize);
+
+impl std::task::Wake for WakeCounter {
+    fn wake(self: std::sync::Arc<Self>) {
+        self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn promises_settled_by_host_futures() {
+    use std::task::Poll;
+
+    let mut context = Context::new();
+    let ready = Rc::new(Cell::new(false));
+    let flag = ready.clone();
+    context.register_global_function("later", 0, move |_, _, ctx| {
+        let flag = flag.clone();
+        ctx.promise_from_future(std::future::poll_fn(move |_| {
+            if flag.get() {
+                Poll::Ready(Ok(Value::from("done")))
+            } else {
+                Poll::Pending
+            }
+        }))
+    });
+    context.register_global_function("failing", 0, |_, _, ctx| {
+        ctx.promise_from_future(async { Err(Value::from("failed")) })
+    });
+
+    let promise = context
+        .eval("var log = []; failing().catch(function (e) { log.push(e); }); later()")
+        .unwrap();
+    let wakes = std::sync::Arc::new(WakeCounter::default());
+    let mut result = context.promise_to_future(&promise).unwrap();
+    assert!(poll_once(&mut result, &wakes).is_pending());
+
+    assert!(poll_once(&mut context.run_event_loop(), &wakes).is_pending());
+    assert_eq!(context.eval("log.join()").unwrap().to_string(), "failed");
+
+    ready.set(true);
+    match poll_once(&mut context.run_event_loop(), &wakes) {
+        Poll::Ready(result) => assert!(result.is_ok()),
+        Poll::Pending => panic!("the event loop should have completed"),
+    }
+    assert_eq!(wakes.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+    match poll_once(&mut result, &wakes) {
+        Poll::Ready(Ok(value)) => assert_eq!(value.to_string(), "done"),
+        _ => panic!("the promise should be fulfilled"),
+    }
+}
+
+#[test]
+fn promise_state_and_jobs() {
+    use crate::builtins::promise::PromiseState;
+
+    let mut context = Context::new();
+    let promise = context
+        .eval("var resolve; var p = new Promise(function (r) { resolve = r; }); p")
+        .unwrap();
+    assert!(matches!(
+        context.interpreter().promise_state(&promise),
+        Some(PromiseState::Pending)
+    ));
+
+    let resolve = context.global_object().get_field("resolve");
+    context
+        .call(&resolve, &Value::undefined(), &[Value::from(1)])
+        .unwrap();
+    context
+        .eval("var seen; p.then(function (v) { seen = v; })")
+        .unwrap();
+    assert_eq!(context.eval("seen").unwrap().to_string(), "1");
+    match context.interpreter().promise_state(&promise) {
+        Some(PromiseState::Fulfilled(value)) => assert_eq!(value.to_string(), "1"),
+        _ => panic!("the promise should be fulfilled"),
+    }
+    assert!(context
+        .interpreter()
+        .promise_state(&Value::from(1))
+        .is_none());
+    assert!(context.promise_to_future(&Value::from(1)).is_err());
+    assert!(context.run_jobs().is_ok());
+}
+
+#[test]
+fn sandbox_removes_builtins() {
+    let mut context = Context::builder()
+        .remove_builtin("Math")
+        .remove_builtin("WeakRef")
+        .build();
+    assert_eq!(
+        context.eval("typeof globalThis.Math").unwrap().to_string(),
+        "undefined"
+    );
+    assert_eq!(
+        context
+            .eval("typeof globalThis.WeakRef")
+            .unwrap()
+            .to_string(),
+        "undefined"
+    );
+
+    // The constructor can not be reached from its instances either.
+    context
+        .eval("var promise = new Promise(function () {})")
+        .unwrap();
+    context.interpreter().realm().remove_builtin("Promise");
+    assert_eq!(
+        context
+            .eval("promise.constructor === Object")
+            .unwrap()
+            .to_string(),
+        "true"
+    );
+
+    // Other realms have all the builtins.
+    let realm = context.create_realm();
+    assert_eq!(
+        context
+            .eval_in_realm(realm, "typeof globalThis.Math")
+            .unwrap()
+            .to_string(),
+        "object"
+    );
+}
+
+#[test]
+fn sandbox_removes_function_constructor() {
+    let mut context = Context::builder().remove_builtin("Function").build();
+    let mut eval = |src| context.eval(src).unwrap().to_string();
+    assert_eq!(eval("typeof globalThis.Function"), "undefined");
+    assert_eq!(eval("function f(a) { return a + 1; }\nf(1)"), "2");
+    assert_eq!(eval("typeof f.constructor"), "undefined");
+    assert_eq!(
+        eval("var resolved = 0;\nnew Promise(function (resolve) { resolved = typeof resolve; });\nresolved"),
+        "function"
+    );
+}
+
+#[test]
+#[should_panic(expected = "the `Array` builtin is required by the engine")]
+fn sandbox_keeps_required_builtins() {
+    Context::builder().remove_builtin("Array").build();
+}
+
+#[test]
+fn sandbox_frozen_globals() {
+    let config = Context::new()
+        .eval("({ name: 'app', limits: { depth: 3 } })")
+        .unwrap();
+    let mut context = Context::builder()
+        .frozen_global("config", config)
+        .frozen_global("version", 2)
+        .build();
+    let mut eval = |src| context.eval(src).unwrap().to_string();
+    assert_eq!(eval("version = 3; version"), "2");
+    assert_eq!(eval("delete globalThis.version; version"), "2");
+    assert_eq!(eval("config.name = 'evil'; config.name"), "app");
+    assert_eq!(eval("config.extra = 1; typeof config.extra"), "undefined");
+    assert_eq!(eval("config.limits.depth = 9; config.limits.depth"), "3");
+    // The rest of the global object is still writable.
+    assert_eq!(eval("var other = 1; other"), "1");
+}
+
+#[test]
+fn sandbox_frozen_global_object() {
+    let mut context = Context::builder().frozen_global_object(true).build();
+    let mut eval = |src| context.eval(src).unwrap().to_string();
+    assert_eq!(
+        eval("globalThis.added = 1; typeof globalThis.added"),
+        "undefined"
+    );
+    assert_eq!(eval("Math = null; typeof Math"), "object");
+    assert_eq!(eval("delete globalThis.Math; typeof Math"), "object");
+    assert_eq!(
+        eval("var declared = 1; typeof globalThis.declared"),
+        "undefined"
+    );
+    assert_eq!(eval("let x = 1; const y = 2; x + y"), "3");
+    assert!(context.eval("function f() {} f").is_err());
+}
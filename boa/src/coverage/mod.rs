@@ -0,0 +1,414 @@
+//! This module implements the code coverage.
+//!
+//! The coverage counts how many times each statement, each arm of the `if` statements and each
+//! function of the scripts is run, so that users can see which parts of their scripts their
+//! tests do not reach. It can be exported in the [lcov][lcov] format, which most coverage tools
+//! read, or as JSON.
+//!
+//! Like the profile, the coverage is always compiled in, and only records evaluations once it
+//! has been enabled with `Interpreter::enable_coverage`. Scripts are then run by walking their
+//! syntax tree, and their function bodies are parsed before they run, even if the interpreter
+//! parses them lazily, so that the functions that are never called are reported too.
+//!
+//! Only statements have a position in the source, so the statements that are not in a list,
+//! like the body of an `if` without braces, are only covered by their branch, and the branches
+//! and the functions are on the line of the statement they are in. The scripts are reported
+//! under the name set with `Interpreter::set_script_name`, and the scripts that have the same
+//! name are reported as the same file.
+//!
+//! [lcov]: https://manpages.debian.org/unstable/lcov/geninfo.1.en.html#TRACEFILE_FORMAT
+
+#[cfg(test)]
+mod tests;
+
+use crate::syntax::ast::{
+    node::{ArrowFunctionDecl, FunctionDecl, FunctionExpr, If, LazyStatementList, StatementList},
+    visitor::{walk_if, Visitor},
+    Node, Span,
+};
+use rustc_hash::FxHashMap;
+use serde_json::{json, Map, Value as JSONValue};
+use std::{collections::BTreeMap, fmt::Write, mem};
+
+/// The name of the scripts that were run without a name.
+pub const ANONYMOUS_SCRIPT: &str = "<anonymous>";
+
+/// The coverage of a statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatementCoverage {
+    /// The span of the statement in its script.
+    pub span: Span,
+    /// How many times the statement was run.
+    pub count: u64,
+}
+
+/// The coverage of an arm of a branch, like the `else` arm of an `if` statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BranchCoverage {
+    /// The line of the statement that the branch is in.
+    pub line: u32,
+    /// The number of the branch in its file, which its arms share.
+    pub block: usize,
+    /// The number of the arm in its branch, `0` for the consequent of an `if` statement and `1`
+    /// for its alternative, even if it has no `else`.
+    pub arm: usize,
+    /// How many times the arm was taken.
+    pub count: u64,
+}
+
+/// The coverage of a function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionCoverage {
+    /// The name of the function, or `(anonymous_<n>)` for the `n`th function of its file if it
+    /// has none.
+    pub name: String,
+    /// The line of the statement that the function is in.
+    pub line: u32,
+    /// How many times the function was called.
+    pub count: u64,
+}
+
+/// The coverage of the scripts that have the same name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileCoverage {
+    /// The name of the scripts, see `Interpreter::set_script_name`.
+    pub name: String,
+    /// The statements, in the order they were visited.
+    pub statements: Vec<StatementCoverage>,
+    /// The arms of the branches, the arms of a branch one after another.
+    pub branches: Vec<BranchCoverage>,
+    /// The functions, in the order they were visited.
+    pub functions: Vec<FunctionCoverage>,
+}
+
+impl FileCoverage {
+    /// Returns the lines that statements start on, with the number of times the most run of
+    /// their statements was run.
+    pub fn lines(&self) -> BTreeMap<u32, u64> {
+        let mut lines = BTreeMap::new();
+        for statement in &self.statements {
+            let count = lines
+                .entry(statement.span.start().line_number())
+                .or_insert(0);
+            *count = statement.count.max(*count);
+        }
+        lines
+    }
+
+    /// Checks whether any arm of the given branch was taken, that is whether the branch was
+    /// reached at all.
+    fn is_reached(&self, block: usize) -> bool {
+        self.branches
+            .iter()
+            .any(|branch| branch.block == block && branch.count > 0)
+    }
+}
+
+/// The addresses of the nodes of a script that are not in a function body, which are forgotten
+/// when the script ends, see `Coverage::forget_script`.
+#[derive(Debug, Default)]
+pub(crate) struct ScriptSites {
+    statements: Vec<usize>,
+    branches: Vec<usize>,
+}
+
+/// The code coverage of a run, see the module documentation.
+///
+/// The nodes are identified by their address, which stays the same while they are run. The
+/// function bodies are kept alive, so that their addresses are never reused by another script.
+#[derive(Debug, Clone, Default)]
+pub struct Coverage {
+    files: Vec<FileCoverage>,
+    /// The file and the index of each statement, by the address of its node.
+    statements: FxHashMap<usize, (usize, usize)>,
+    /// The file and the index of the first arm of each `if` statement, by its address.
+    branches: FxHashMap<usize, (usize, usize)>,
+    /// The file and the index of each function, by the address of the statements of its body.
+    functions: FxHashMap<usize, (usize, usize)>,
+    bodies: Vec<LazyStatementList>,
+}
+
+impl Coverage {
+    /// Creates a new, empty coverage.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the coverage of every file, in the order their first script ran.
+    #[inline]
+    pub fn files(&self) -> &[FileCoverage] {
+        &self.files
+    }
+
+    /// Starts covering a script named `name`, before it runs.
+    ///
+    /// Returns the sites to forget when the script ends.
+    pub(crate) fn add_script(&mut self, name: &str, statements: &StatementList) -> ScriptSites {
+        let file = if let Some(file) = self.files.iter().position(|file| file.name == name) {
+            file
+        } else {
+            self.files.push(FileCoverage {
+                name: name.to_owned(),
+                ..FileCoverage::default()
+            });
+            self.files.len() - 1
+        };
+        let mut registration = Registration {
+            coverage: self,
+            file,
+            line: 1,
+            in_function: false,
+            sites: ScriptSites::default(),
+        };
+        registration.visit_statement_list(statements);
+        registration.sites
+    }
+
+    /// Forgets the nodes of a script that ended, which are freed with it, keeping their counts.
+    pub(crate) fn forget_script(&mut self, sites: ScriptSites) {
+        for statement in sites.statements {
+            self.statements.remove(&statement);
+        }
+        for branch in sites.branches {
+            self.branches.remove(&branch);
+        }
+    }
+
+    /// Records that a statement is run.
+    #[inline]
+    pub(crate) fn record_statement(&mut self, statement: &Node) {
+        if let Some(&(file, index)) = self.statements.get(&address(statement)) {
+            self.files[file].statements[index].count += 1;
+        }
+    }
+
+    /// Records that the given arm of an `if` statement is taken.
+    #[inline]
+    pub(crate) fn record_branch(&mut self, if_node: &If, arm: usize) {
+        if let Some(&(file, index)) = self.branches.get(&address(if_node)) {
+            self.files[file].branches[index + arm].count += 1;
+        }
+    }
+
+    /// Records that the function with the given body is called.
+    #[inline]
+    pub(crate) fn record_function(&mut self, body: &StatementList) {
+        if let Some(&(file, index)) = self.functions.get(&address(body)) {
+            self.files[file].functions[index].count += 1;
+        }
+    }
+
+    /// Exports the coverage in the lcov tracefile format, with a record for each file.
+    pub fn to_lcov(&self) -> String {
+        let mut lcov = String::new();
+        for file in &self.files {
+            let _ = writeln!(lcov, "SF:{}", file.name);
+            for function in &file.functions {
+                let _ = writeln!(lcov, "FN:{},{}", function.line, function.name);
+            }
+            for function in &file.functions {
+                let _ = writeln!(lcov, "FNDA:{},{}", function.count, function.name);
+            }
+            let hit = file.functions.iter().filter(|f| f.count > 0).count();
+            let _ = writeln!(lcov, "FNF:{}\nFNH:{}", file.functions.len(), hit);
+
+            for branch in &file.branches {
+                let count = if file.is_reached(branch.block) {
+                    branch.count.to_string()
+                } else {
+                    "-".to_owned()
+                };
+                let _ = writeln!(
+                    lcov,
+                    "BRDA:{},{},{},{}",
+                    branch.line, branch.block, branch.arm, count
+                );
+            }
+            let hit = file.branches.iter().filter(|b| b.count > 0).count();
+            let _ = writeln!(lcov, "BRF:{}\nBRH:{}", file.branches.len(), hit);
+
+            let lines = file.lines();
+            for (line, count) in &lines {
+                let _ = writeln!(lcov, "DA:{},{}", line, count);
+            }
+            let hit = lines.values().filter(|&&count| count > 0).count();
+            let _ = writeln!(lcov, "LF:{}\nLH:{}", lines.len(), hit);
+            lcov.push_str("end_of_record\n");
+        }
+        lcov
+    }
+
+    /// Exports the coverage as a JSON object, with a property for each file.
+    ///
+    /// Each file has the `line`, `column` and `count` of its `statements`, the `line`, `block`,
+    /// `arm` and `count` of its `branches`, and the `name`, `line` and `count` of its
+    /// `functions`.
+    pub fn to_json(&self) -> JSONValue {
+        let mut files = Map::new();
+        for file in &self.files {
+            let statements: Vec<_> = file
+                .statements
+                .iter()
+                .map(|statement| {
+                    json!({
+                        "line": statement.span.start().line_number(),
+                        "column": statement.span.start().column_number(),
+                        "count": statement.count,
+                    })
+                })
+                .collect();
+            let branches: Vec<_> = file
+                .branches
+                .iter()
+                .map(|branch| {
+                    json!({
+                        "line": branch.line,
+                        "block": branch.block,
+                        "arm": branch.arm,
+                        "count": branch.count,
+                    })
+                })
+                .collect();
+            let functions: Vec<_> = file
+                .functions
+                .iter()
+                .map(|function| {
+                    json!({
+                        "name": function.name,
+                        "line": function.line,
+                        "count": function.count,
+                    })
+                })
+                .collect();
+            files.insert(
+                file.name.clone(),
+                json!({
+                    "statements": statements,
+                    "branches": branches,
+                    "functions": functions,
+                }),
+            );
+        }
+        JSONValue::Object(files)
+    }
+}
+
+/// Returns the address of a node, which identifies it while it is alive.
+#[inline]
+fn address<T>(node: &T) -> usize {
+    node as *const T as usize
+}
+
+/// Registers the nodes of a script in the coverage.
+struct Registration<'a> {
+    coverage: &'a mut Coverage,
+    file: usize,
+    /// The line of the statement that is visited.
+    line: u32,
+    /// Whether the visited nodes are in a function body, which outlives the script.
+    in_function: bool,
+    sites: ScriptSites,
+}
+
+impl Registration<'_> {
+    /// Registers a function and the nodes of its body.
+    fn function(&mut self, name: Option<&str>, body: &LazyStatementList) {
+        let statements = match body.statements() {
+            Ok(statements) => statements,
+            Err(_) => return,
+        };
+        let key = address(&*statements);
+        // The bodies of the scripts that are run again are registered once.
+        if self.coverage.functions.contains_key(&key) {
+            return;
+        }
+        let functions = &mut self.coverage.files[self.file].functions;
+        let name = match name {
+            Some(name) if !name.is_empty() => name.to_owned(),
+            _ => format!("(anonymous_{})", functions.len()),
+        };
+        functions.push(FunctionCoverage {
+            name,
+            line: self.line,
+            count: 0,
+        });
+        self.coverage
+            .functions
+            .insert(key, (self.file, functions.len() - 1));
+        self.coverage.bodies.push(body.clone());
+
+        let in_function = mem::replace(&mut self.in_function, true);
+        self.visit_statement_list(&statements);
+        self.in_function = in_function;
+    }
+}
+
+impl Visitor for Registration<'_> {
+    fn visit_statement_list(&mut self, list: &StatementList) {
+        for (statement, span) in list.statements().iter().zip(list.spans()) {
+            let statements = &mut self.coverage.files[self.file].statements;
+            statements.push(StatementCoverage {
+                span: *span,
+                count: 0,
+            });
+            let key = address(statement);
+            self.coverage
+                .statements
+                .insert(key, (self.file, statements.len() - 1));
+            if !self.in_function {
+                self.sites.statements.push(key);
+            }
+            self.line = span.start().line_number();
+            self.visit_node(statement);
+        }
+        // The statements that were not parsed from source code have no position.
+        if list.spans().is_empty() {
+            for statement in list.statements() {
+                self.visit_node(statement);
+            }
+        }
+    }
+
+    fn visit_if(&mut self, if_node: &If) {
+        let branches = &mut self.coverage.files[self.file].branches;
+        let block = branches.last().map_or(0, |branch| branch.block + 1);
+        for arm in 0..2 {
+            branches.push(BranchCoverage {
+                line: self.line,
+                block,
+                arm,
+                count: 0,
+            });
+        }
+        let key = address(if_node);
+        self.coverage
+            .branches
+            .insert(key, (self.file, branches.len() - 2));
+        if !self.in_function {
+            self.sites.branches.push(key);
+        }
+        walk_if(self, if_node);
+    }
+
+    fn visit_function_decl(&mut self, decl: &FunctionDecl) {
+        for parameter in decl.parameters() {
+            self.visit_formal_parameter(parameter);
+        }
+        self.function(Some(decl.name()), decl.body());
+    }
+
+    fn visit_function_expr(&mut self, expr: &FunctionExpr) {
+        for parameter in expr.parameters() {
+            self.visit_formal_parameter(parameter);
+        }
+        self.function(expr.name(), expr.body());
+    }
+
+    fn visit_arrow_function_decl(&mut self, arrow: &ArrowFunctionDecl) {
+        for parameter in arrow.params() {
+            self.visit_formal_parameter(parameter);
+        }
+        self.function(None, arrow.body());
+    }
+}
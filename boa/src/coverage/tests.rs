@@ -0,0 +1,156 @@
+use super::*;
+use crate::{exec::Interpreter, forward, realm::Realm};
+
+/// Runs a script with the coverage enabled, and returns the coverage of its file.
+fn cover(src: &str) -> FileCoverage {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.enable_coverage();
+    engine.set_script_name("test.js");
+    forward(&mut engine, src);
+    let coverage = engine.coverage().expect("coverage was enabled");
+    assert_eq!(coverage.files().len(), 1);
+    coverage.files()[0].clone()
+}
+
+#[test]
+fn coverage_is_disabled_by_default() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "1 + 1");
+
+    assert!(engine.coverage().is_none());
+}
+
+#[test]
+fn coverage_counts_statements() {
+    let file = cover(
+        "var a = 0;\nfor (let i = 0; i < 3; i++) {\n    a++;\n}\nif (a > 5) {\n    a = 0;\n}",
+    );
+    assert_eq!(file.name, "test.js");
+    let lines: Vec<_> = file.lines().into_iter().collect();
+    assert_eq!(lines, [(1, 1), (2, 1), (3, 3), (5, 1), (6, 0)]);
+}
+
+#[test]
+fn coverage_counts_branches() {
+    let file = cover(
+        "function f(x) {\n    if (x) return 1;\n    else return 2;\n}\nf(true); f(true); f(false);\nif (false) f();",
+    );
+    let counts: Vec<_> = file
+        .branches
+        .iter()
+        .map(|branch| (branch.line, branch.block, branch.arm, branch.count))
+        .collect();
+    assert_eq!(
+        counts,
+        [(2, 0, 0, 2), (2, 0, 1, 1), (6, 1, 0, 0), (6, 1, 1, 1)]
+    );
+}
+
+#[test]
+fn coverage_counts_functions() {
+    let file = cover(
+        "function used() {}\nfunction unused() {}\nused(); used();\n[1, 2, 3].forEach(function (x) {});",
+    );
+    let counts: Vec<_> = file
+        .functions
+        .iter()
+        .map(|function| (function.name.as_str(), function.line, function.count))
+        .collect();
+    assert_eq!(
+        counts,
+        [("used", 1, 2), ("unused", 2, 0), ("(anonymous_2)", 4, 3)]
+    );
+}
+
+#[test]
+fn coverage_merges_scripts_with_the_same_name() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.enable_coverage();
+    forward(&mut engine, "function f() { return 1; }");
+    forward(&mut engine, "f(); f();");
+    engine.set_script_name("other.js");
+    forward(&mut engine, "f();");
+
+    let coverage = engine.coverage().expect("coverage was enabled");
+    let names: Vec<_> = coverage.files().iter().map(|file| &file.name).collect();
+    assert_eq!(names, [ANONYMOUS_SCRIPT, "other.js"]);
+    // The functions are counted in the file that defines them.
+    assert_eq!(coverage.files()[0].functions[0].count, 3);
+    assert_eq!(coverage.files()[0].statements.len(), 4);
+}
+
+#[test]
+fn coverage_to_lcov() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.enable_coverage();
+    engine.set_script_name("a.js");
+    forward(
+        &mut engine,
+        "function f() {}\nif (true) f();\nfunction g() {}",
+    );
+
+    let lcov = engine.coverage().expect("coverage was enabled").to_lcov();
+    assert_eq!(
+        lcov,
+        "SF:a.js\nFN:1,f\nFN:3,g\nFNDA:1,f\nFNDA:0,g\nFNF:2\nFNH:1\nBRDA:2,0,0,1\nBRDA:2,0,1,0\n\
+         BRF:2\nBRH:1\nDA:1,1\nDA:2,1\nDA:3,1\nLF:3\nLH:3\nend_of_record\n"
+    );
+}
+
+#[test]
+fn coverage_to_lcov_unreached_branch() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.enable_coverage();
+    forward(&mut engine, "function f(x) {\n    if (x) {}\n}");
+
+    let lcov = engine.coverage().expect("coverage was enabled").to_lcov();
+    assert!(lcov.contains("BRDA:2,0,0,-\nBRDA:2,0,1,-\n"), "{}", lcov);
+    assert!(lcov.contains("DA:2,0\n"), "{}", lcov);
+}
+
+#[test]
+fn coverage_to_json() {
+    let file = {
+        let realm = Realm::create();
+        let mut engine = Interpreter::new(realm);
+        engine.enable_coverage();
+        engine.set_script_name("a.js");
+        forward(&mut engine, "let a = 1;\n  a++;");
+        engine.coverage().expect("coverage was enabled").to_json()
+    };
+    assert_eq!(
+        file,
+        json!({
+            "a.js": {
+                "statements": [
+                    { "line": 1, "column": 1, "count": 1 },
+                    { "line": 2, "column": 3, "count": 1 },
+                ],
+                "branches": [],
+                "functions": [],
+            }
+        })
+    );
+}
+
+#[test]
+fn coverage_with_lazy_parsing() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.enable_coverage();
+    engine.set_lazy_parsing(true);
+    forward(
+        &mut engine,
+        "function f() {\n    return 1;\n}\nfunction g() {\n    return 2;\n}\nf();",
+    );
+
+    let file = &engine.coverage().expect("coverage was enabled").files()[0];
+    let counts: Vec<_> = file.functions.iter().map(|f| f.count).collect();
+    assert_eq!(counts, [1, 0]);
+    assert_eq!(file.lines().get(&5), Some(&0));
+}
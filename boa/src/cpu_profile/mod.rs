@@ -0,0 +1,232 @@
+//! This module implements the CPU profile.
+//!
+//! The profile records when each function call of a run starts and ends, and the scripts the
+//! calls are made from, so that users can see where their scripts spend their time as a flame
+//! graph. It can be exported in the evented format of [speedscope][speedscope], or as folded
+//! stacks, which [flamegraph][flamegraph] and most flame graph tools read.
+//!
+//! Like the execution profile, the CPU profile is always compiled in, and only records calls
+//! once it has been enabled with `Interpreter::enable_cpu_profile`. The calls of built-in
+//! functions are recorded too, so the time of a function includes the time of the built-ins it
+//! calls only in its children.
+//!
+//! [speedscope]: https://www.speedscope.app
+//! [flamegraph]: https://github.com/brendangregg/FlameGraph
+
+#[cfg(test)]
+mod tests;
+
+use crate::syntax::ast::Position;
+use rustc_hash::FxHashMap;
+use serde_json::{json, Value as JSONValue};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Write},
+    time::{Duration, Instant},
+};
+
+/// A function or a script, that calls are made to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CpuFrame {
+    /// The name of the function, or of the script.
+    pub name: String,
+    /// Where the function was defined, for functions created from source code.
+    pub position: Option<Position>,
+}
+
+impl CpuFrame {
+    /// Creates a frame with the given name, defined at `position`.
+    pub fn new<N>(name: N, position: Option<Position>) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            position,
+        }
+    }
+}
+
+impl Display for CpuFrame {
+    /// Formats the frame as its name, followed by its position if it has one, like `f (3:1)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some(position) => write!(
+                f,
+                "{} ({}:{})",
+                self.name,
+                position.line_number(),
+                position.column_number()
+            ),
+            None => f.write_str(&self.name),
+        }
+    }
+}
+
+/// The start or the end of a call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuEvent {
+    /// Whether the call starts, or ends.
+    pub open: bool,
+    /// The index of the frame of the call in `CpuProfile::frames`.
+    pub frame: usize,
+    /// When the event happened, relative to the creation of the profile.
+    pub at: Duration,
+}
+
+/// The CPU profile of a run, see the module documentation.
+#[derive(Debug, Clone)]
+pub struct CpuProfile {
+    origin: Instant,
+    frames: Vec<CpuFrame>,
+    /// The index of each frame in `frames`.
+    indices: FxHashMap<CpuFrame, usize>,
+    events: Vec<CpuEvent>,
+    /// The frames of the calls that have started and not ended yet.
+    stack: Vec<usize>,
+}
+
+impl CpuProfile {
+    /// Creates a new, empty profile, starting now.
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+            frames: Vec::new(),
+            indices: FxHashMap::default(),
+            events: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    /// Records the start of a call to the given frame, which `exit` ends.
+    pub(crate) fn enter(&mut self, frame: CpuFrame) {
+        let frames = &mut self.frames;
+        let index = *self.indices.entry(frame).or_insert_with_key(|frame| {
+            frames.push(frame.clone());
+            frames.len() - 1
+        });
+        self.stack.push(index);
+        self.events.push(CpuEvent {
+            open: true,
+            frame: index,
+            at: self.origin.elapsed(),
+        });
+    }
+
+    /// Records the end of the last call that started.
+    pub(crate) fn exit(&mut self) {
+        if let Some(index) = self.stack.pop() {
+            self.events.push(CpuEvent {
+                open: false,
+                frame: index,
+                at: self.origin.elapsed(),
+            });
+        }
+    }
+
+    /// Returns the frames of the calls, in the order they were first called.
+    #[inline]
+    pub fn frames(&self) -> &[CpuFrame] {
+        &self.frames
+    }
+
+    /// Returns the starts and ends of the calls, in the order they happened.
+    #[inline]
+    pub fn events(&self) -> &[CpuEvent] {
+        &self.events
+    }
+
+    /// Returns the time spent in each stack of calls, from the outermost call to the innermost
+    /// one, without the time of the calls they made.
+    pub fn stacks(&self) -> BTreeMap<Vec<usize>, Duration> {
+        let mut stacks = BTreeMap::new();
+        let mut stack = Vec::new();
+        let mut last = Duration::default();
+        for event in &self.events {
+            if !stack.is_empty() {
+                *stacks.entry(stack.clone()).or_default() += event.at - last;
+            }
+            if event.open {
+                stack.push(event.frame);
+            } else {
+                stack.pop();
+            }
+            last = event.at;
+        }
+        stacks
+    }
+
+    /// Exports the profile as a speedscope JSON file, with a single evented profile named
+    /// `name`.
+    ///
+    /// The calls that have not ended yet are ended at the end of the profile.
+    pub fn to_speedscope(&self, name: &str) -> JSONValue {
+        let frames: Vec<_> = self
+            .frames
+            .iter()
+            .map(|frame| match frame.position {
+                Some(position) => json!({
+                    "name": frame.name,
+                    "line": position.line_number(),
+                    "col": position.column_number(),
+                }),
+                None => json!({ "name": frame.name }),
+            })
+            .collect();
+        let micros = |at: Duration| at.as_secs_f64() * 1_000_000.0;
+        let end = self.events.last().map_or(0.0, |event| micros(event.at));
+        let mut events: Vec<_> = self
+            .events
+            .iter()
+            .map(|event| {
+                json!({
+                    "type": if event.open { "O" } else { "C" },
+                    "frame": event.frame,
+                    "at": micros(event.at),
+                })
+            })
+            .collect();
+        events.extend(self.stack.iter().rev().map(|&frame| {
+            json!({
+                "type": "C",
+                "frame": frame,
+                "at": end,
+            })
+        }));
+
+        json!({
+            "$schema": "https://www.speedscope.app/file-format-schema.json",
+            "shared": { "frames": frames },
+            "profiles": [{
+                "type": "evented",
+                "name": name,
+                "unit": "microseconds",
+                "startValue": 0,
+                "endValue": end,
+                "events": events,
+            }],
+            "name": name,
+            "exporter": "boa",
+        })
+    }
+
+    /// Exports the profile as folded stacks, a line for each stack of calls with the frames
+    /// separated by `;` and the nanoseconds spent in the stack.
+    pub fn to_folded(&self) -> String {
+        let mut folded = String::new();
+        for (stack, time) in self.stacks() {
+            let frames: Vec<_> = stack
+                .iter()
+                .map(|&frame| self.frames[frame].to_string().replace(';', ","))
+                .collect();
+            let _ = writeln!(folded, "{} {}", frames.join(";"), time.as_nanos());
+        }
+        folded
+    }
+}
+
+impl Default for CpuProfile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
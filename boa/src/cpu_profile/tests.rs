@@ -0,0 +1,123 @@
+use super::*;
+use crate::{exec::Interpreter, forward, realm::Realm};
+
+#[test]
+fn cpu_profile_is_disabled_by_default() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "1 + 1");
+
+    assert!(engine.cpu_profile().is_none());
+}
+
+#[test]
+fn cpu_profile_records_calls() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.enable_cpu_profile();
+    engine.set_script_name("a.js");
+    let scenario = r#"
+        function inner() {}
+        function outer() {
+            inner();
+            inner();
+        }
+        outer();
+    "#;
+    forward(&mut engine, scenario);
+
+    let profile = engine.cpu_profile().expect("CPU profile was enabled");
+    let names: Vec<_> = profile.frames().iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, ["a.js", "outer", "inner"]);
+    assert_eq!(
+        profile.frames()[1].position.map(|p| p.line_number()),
+        Some(3)
+    );
+
+    let events: Vec<_> = profile
+        .events()
+        .iter()
+        .map(|event| (event.open, event.frame))
+        .collect();
+    assert_eq!(
+        events,
+        [
+            (true, 0),
+            (true, 1),
+            (true, 2),
+            (false, 2),
+            (true, 2),
+            (false, 2),
+            (false, 1),
+            (false, 0),
+        ]
+    );
+    assert!(profile.events().windows(2).all(|w| w[0].at <= w[1].at));
+}
+
+#[test]
+fn cpu_profile_ends_calls_that_throw() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.enable_cpu_profile();
+    forward(&mut engine, "function f() { throw 1; } f();");
+
+    let profile = engine.cpu_profile().expect("CPU profile was enabled");
+    let opened = profile.events().iter().filter(|e| e.open).count();
+    assert_eq!(opened * 2, profile.events().len());
+}
+
+/// Builds a profile with the given events, at one microsecond from each other.
+fn profile(events: &[(bool, usize)]) -> CpuProfile {
+    let mut profile = CpuProfile::new();
+    profile.frames = vec![
+        CpuFrame::new("main", None),
+        CpuFrame::new("f", Some(Position::new(2, 5))),
+    ];
+    for (i, &(open, frame)) in events.iter().enumerate() {
+        profile.events.push(CpuEvent {
+            open,
+            frame,
+            at: Duration::from_micros(i as u64),
+        });
+    }
+    profile
+}
+
+#[test]
+fn folded_export() {
+    let profile = profile(&[
+        (true, 0),
+        (true, 1),
+        (false, 1),
+        (true, 1),
+        (false, 1),
+        (false, 0),
+    ]);
+    assert_eq!(profile.to_folded(), "main 3000\nmain;f (2:5) 2000\n");
+}
+
+#[test]
+fn speedscope_export() {
+    let mut profile = profile(&[(true, 0), (true, 1)]);
+    profile.stack = vec![0, 1];
+    let json = profile.to_speedscope("test");
+
+    assert_eq!(
+        json["shared"]["frames"],
+        json!([{ "name": "main" }, { "name": "f", "line": 2, "col": 5 }])
+    );
+    let profile = &json["profiles"][0];
+    assert_eq!(profile["type"], "evented");
+    assert_eq!(profile["endValue"], 1.0);
+    // The calls that have not ended are ended at the end of the profile.
+    assert_eq!(
+        profile["events"],
+        json!([
+            { "type": "O", "frame": 0, "at": 0.0 },
+            { "type": "O", "frame": 1, "at": 1.0 },
+            { "type": "C", "frame": 1, "at": 1.0 },
+            { "type": "C", "frame": 0, "at": 1.0 },
+        ])
+    );
+}
@@ -0,0 +1,368 @@
+//! This module implements the debugging hooks of the interpreter.
+//!
+//! Watchpoints report every read or write of a watched object property that is performed by a
+//! script, which helps to track down where a value is being changed. The interpreter calls the
+//! handler set with `Debugger::set_watch_handler` for each such access, with the location of the
+//! statement that made it. The handler can inspect the call stack and the scope chain of the
+//! script, like a pause handler.
+//!
+//! Accesses performed internally by builtins, like `Array.prototype.push` writing its elements,
+//! are not reported.
+//!
+//! A pause handler, set with `Debugger::set_pause_handler`, is called when a script reaches a
+//! `debugger` statement, and then before each statement while stepping. It can inspect the
+//! paused script with `scope_chain` and `evaluate`, and chooses how the script resumes. Scripts
+//! are run by walking their syntax tree while a pause handler is set.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builtins::{object::GcObject, property::PropertyKey, value::Value},
+    environment::lexical_environment::EnvironmentType,
+    exec::{Executable, Interpreter},
+    syntax::ast::{node::Node, Position},
+    JsError, ParseOptions,
+};
+use std::fmt::{self, Debug, Display};
+
+/// The kind of property access that triggered a watchpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchAccess {
+    /// The property was read.
+    Read,
+    /// The property was written.
+    Write,
+}
+
+impl Display for WatchAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Read => "read",
+            Self::Write => "write",
+        })
+    }
+}
+
+/// The identifier of a watchpoint, used to remove it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WatchpointId(u32);
+
+impl Display for WatchpointId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// A property access that triggered a watchpoint.
+#[derive(Debug)]
+pub struct WatchEvent<'a> {
+    /// The watchpoint that was triggered.
+    pub id: WatchpointId,
+    /// Whether the property was read or written.
+    pub access: WatchAccess,
+    /// The object that owns the property.
+    pub object: &'a Value,
+    /// The key of the property.
+    pub key: &'a PropertyKey,
+    /// The value that was read or written.
+    pub value: &'a Value,
+    /// The name of the script of the statement that accessed the property, if it is known, see
+    /// `StackFrame::script`.
+    pub script: Option<&'a str>,
+    /// The start of the statement that accessed the property, if it is known.
+    pub position: Option<Position>,
+}
+
+/// A function that is called every time a watchpoint is triggered.
+pub type WatchHandler = Box<dyn FnMut(&mut Interpreter, &WatchEvent<'_>)>;
+
+/// Why a script paused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseReason {
+    /// The script reached a `debugger` statement.
+    DebuggerStatement,
+    /// The script is about to run a statement after a `Resume::Step`.
+    Step,
+}
+
+impl Display for PauseReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::DebuggerStatement => "debugger statement",
+            Self::Step => "step",
+        })
+    }
+}
+
+/// How a paused script resumes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resume {
+    /// Run until the next `debugger` statement.
+    Continue,
+    /// Pause again before the next statement, which may be in a called function.
+    Step,
+}
+
+/// A pause of a script.
+#[derive(Debug)]
+pub struct PauseEvent<'a> {
+    /// Why the script paused.
+    pub reason: PauseReason,
+    /// The statement that runs next, when stepping.
+    pub statement: Option<&'a Node>,
+}
+
+/// A function that is called every time a script pauses.
+pub type PauseHandler = Box<dyn FnMut(&mut Interpreter, &PauseEvent<'_>) -> Resume>;
+
+/// An environment of the scope chain of a paused script, see `scope_chain`.
+#[derive(Debug, Clone)]
+pub struct Scope {
+    /// The kind of environment, like a function or a block.
+    pub kind: EnvironmentType,
+    /// The declarative bindings of the environment, sorted by name, with `None` for the bindings
+    /// that are not initialized yet.
+    ///
+    /// The bindings of the global object, like `var` declarations at the top level of a script,
+    /// are properties of the global object and are not listed.
+    pub bindings: Vec<(String, Option<Value>)>,
+}
+
+/// A watched object property.
+#[derive(Debug)]
+struct Watchpoint {
+    id: WatchpointId,
+    object: Value,
+    key: PropertyKey,
+}
+
+/// The debugging state of an interpreter.
+#[derive(Default)]
+pub struct Debugger {
+    watchpoints: Vec<Watchpoint>,
+    next_watchpoint_id: u32,
+    watch_handler: Option<WatchHandler>,
+    pause_handler: Option<PauseHandler>,
+    /// Whether the script pauses before its next statement.
+    stepping: bool,
+}
+
+impl Debug for Debugger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Debugger")
+            .field("watchpoints", &self.watchpoints)
+            .field("watch_handler", &self.watch_handler.is_some())
+            .field("pause_handler", &self.pause_handler.is_some())
+            .field("stepping", &self.stepping)
+            .finish()
+    }
+}
+
+impl Debugger {
+    /// Sets the function that is called every time a watchpoint is triggered.
+    ///
+    /// The handler is not called again while it runs, so the properties it accesses are not
+    /// reported.
+    pub fn set_watch_handler<F>(&mut self, handler: F)
+    where
+        F: FnMut(&mut Interpreter, &WatchEvent<'_>) + 'static,
+    {
+        self.watch_handler = Some(Box::new(handler));
+    }
+
+    /// Sets the function that is called every time a script pauses, see `PauseReason`.
+    ///
+    /// The handler is not called again while it runs, so the code it evaluates does not pause.
+    pub fn set_pause_handler<F>(&mut self, handler: F)
+    where
+        F: FnMut(&mut Interpreter, &PauseEvent<'_>) -> Resume + 'static,
+    {
+        self.pause_handler = Some(Box::new(handler));
+    }
+
+    /// Removes the pause handler, `debugger` statements then do nothing.
+    pub fn remove_pause_handler(&mut self) {
+        self.pause_handler = None;
+        self.stepping = false;
+    }
+
+    /// Returns whether a pause handler is set.
+    #[inline]
+    pub fn has_pause_handler(&self) -> bool {
+        self.pause_handler.is_some()
+    }
+
+    /// Watches the property `key` of `object`.
+    ///
+    /// Returns `None` if `object` is not an object.
+    pub fn watch<K>(&mut self, object: &Value, key: K) -> Option<WatchpointId>
+    where
+        K: Into<PropertyKey>,
+    {
+        if !object.is_object() {
+            return None;
+        }
+        let id = WatchpointId(self.next_watchpoint_id);
+        self.next_watchpoint_id += 1;
+        self.watchpoints.push(Watchpoint {
+            id,
+            object: object.clone(),
+            key: key.into(),
+        });
+        Some(id)
+    }
+
+    /// Removes a watchpoint, returns whether it existed.
+    pub fn unwatch(&mut self, id: WatchpointId) -> bool {
+        let len = self.watchpoints.len();
+        self.watchpoints.retain(|watchpoint| watchpoint.id != id);
+        self.watchpoints.len() != len
+    }
+
+    /// Removes every watchpoint.
+    #[inline]
+    pub fn clear_watchpoints(&mut self) {
+        self.watchpoints.clear();
+    }
+}
+
+impl Interpreter {
+    /// Reports an access of the property `key` of `object` to the watch handler, if the property
+    /// is watched.
+    pub(crate) fn notify_watch(
+        &mut self,
+        access: WatchAccess,
+        object: &Value,
+        key: &PropertyKey,
+        value: &Value,
+    ) {
+        let debugger = self.debugger();
+        if debugger.watchpoints.is_empty() || debugger.watch_handler.is_none() {
+            return;
+        }
+        let ids: Vec<_> = debugger
+            .watchpoints
+            .iter()
+            .filter(|watchpoint| watchpoint.key == *key && same_object(&watchpoint.object, object))
+            .map(|watchpoint| watchpoint.id)
+            .collect();
+        let mut handler = match self.debugger_mut().watch_handler.take() {
+            Some(handler) if !ids.is_empty() => handler,
+            handler => {
+                self.debugger_mut().watch_handler = handler;
+                return;
+            }
+        };
+
+        // The statement is the one of the innermost frame that runs code from a script, so that
+        // the accesses made by builtins are reported at the statement that called them.
+        let location = self
+            .call_stack()
+            .iter()
+            .rev()
+            .find_map(|frame| Some((frame.script()?.to_owned(), frame.position()?)));
+        for id in ids {
+            handler(
+                self,
+                &WatchEvent {
+                    id,
+                    access,
+                    object,
+                    key,
+                    value,
+                    script: location.as_ref().map(|(script, _)| script.as_str()),
+                    position: location.as_ref().map(|(_, position)| *position),
+                },
+            );
+        }
+
+        // The handler may have replaced itself.
+        let debugger = self.debugger_mut();
+        if debugger.watch_handler.is_none() {
+            debugger.watch_handler = Some(handler);
+        }
+    }
+
+    /// Pauses the script, by calling the pause handler if there is one.
+    pub(crate) fn pause(&mut self, event: &PauseEvent<'_>) {
+        let mut handler = match self.debugger_mut().pause_handler.take() {
+            Some(handler) => handler,
+            None => return,
+        };
+        self.debugger_mut().stepping = false;
+        let resume = handler(self, event);
+
+        // The handler may have replaced or removed itself.
+        let debugger = self.debugger_mut();
+        if debugger.pause_handler.is_none() {
+            debugger.pause_handler = Some(handler);
+            debugger.stepping = resume == Resume::Step;
+        }
+    }
+
+    /// Pauses the script before a statement, if it is stepping.
+    #[inline]
+    pub(crate) fn before_statement(&mut self, statement: &Node) {
+        if self.debugger().stepping {
+            self.pause(&PauseEvent {
+                reason: PauseReason::Step,
+                statement: Some(statement),
+            });
+        }
+    }
+}
+
+/// Gets the scope chain of a paused script, from the innermost environment to the global one.
+pub fn scope_chain(engine: &Interpreter) -> Vec<Scope> {
+    let mut scopes = Vec::new();
+    let mut environment = Some(
+        engine
+            .realm()
+            .environment
+            .get_current_environment_ref()
+            .clone(),
+    );
+    while let Some(current) = environment {
+        let record = current.borrow();
+        let mut bindings: Vec<_> = record
+            .declarative_bindings()
+            .into_iter()
+            .map(|(name, binding)| (name, binding.value.clone()))
+            .collect();
+        bindings.sort_by(|(a, _), (b, _)| a.cmp(b));
+        scopes.push(Scope {
+            kind: record.get_environment_type(),
+            bindings,
+        });
+        environment = record.get_outer_environment();
+    }
+    scopes
+}
+
+/// Evaluates a source in the innermost scope of a paused script, like a `debugger` prompt does.
+///
+/// Declarations are made in that scope. The paused script resumes from where it was, even if
+/// the source throws.
+pub fn evaluate(engine: &mut Interpreter, src: &str) -> Result<Value, JsError> {
+    let statements = crate::parse(src, ParseOptions::from(&*engine))?;
+
+    let state = engine.take_current_state();
+    let depth = engine.realm().environment.environments().count();
+    let result = statements.run(engine);
+    let environment = &mut engine.realm_mut().environment;
+    while environment.environments().count() > depth {
+        environment.pop();
+    }
+    engine.set_current_state(state);
+
+    result.map_err(|value| JsError::from_thrown(value, engine))
+}
+
+/// Checks if both values are the same object.
+fn same_object(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => GcObject::equals(a, b),
+        _ => false,
+    }
+}
@@ -0,0 +1,216 @@
+use super::{evaluate, scope_chain, PauseReason, Resume, WatchAccess};
+use crate::{
+    environment::lexical_environment::EnvironmentType, exec::Interpreter, forward, forward_val,
+    realm::Realm,
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// Creates an interpreter that records the accesses of its watchpoints as strings.
+fn watched_engine() -> (Interpreter, Rc<RefCell<Vec<String>>>) {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let recorded = Rc::clone(&events);
+    engine.debugger_mut().set_watch_handler(move |_, event| {
+        recorded
+            .borrow_mut()
+            .push(format!("{} {} {}", event.access, event.key, event.value));
+    });
+    (engine, events)
+}
+
+#[test]
+fn reads_and_writes() {
+    let (mut engine, events) = watched_engine();
+    let obj = forward_val(&mut engine, "var obj = { count: 1 }; obj").unwrap();
+    engine.debugger_mut().watch(&obj, "count").unwrap();
+
+    forward(
+        &mut engine,
+        "obj.count = 2; obj['count']; obj.count += 3; obj.other = 4;",
+    );
+
+    assert_eq!(
+        *events.borrow(),
+        vec![
+            "write count 2",
+            "read count 2",
+            "read count 2",
+            "write count 5"
+        ]
+    );
+}
+
+#[test]
+fn other_objects_are_not_reported() {
+    let (mut engine, events) = watched_engine();
+    let obj = forward_val(&mut engine, "var obj = { x: 1 }; var other = { x: 1 }; obj").unwrap();
+    engine.debugger_mut().watch(&obj, "x").unwrap();
+
+    forward(&mut engine, "other.x = 2; other.x;");
+
+    assert!(events.borrow().is_empty());
+}
+
+#[test]
+fn unwatch() {
+    let (mut engine, events) = watched_engine();
+    let obj = forward_val(&mut engine, "var obj = { x: 1 }; obj").unwrap();
+    let id = engine.debugger_mut().watch(&obj, "x").unwrap();
+    let primitive = forward_val(&mut engine, "1").unwrap();
+
+    assert!(engine.debugger_mut().watch(&primitive, "x").is_none());
+    assert!(engine.debugger_mut().unwatch(id));
+    assert!(!engine.debugger_mut().unwatch(id));
+
+    forward(&mut engine, "obj.x = 2;");
+
+    assert!(events.borrow().is_empty());
+}
+
+#[test]
+fn locations() {
+    let mut engine = Interpreter::new(Realm::create());
+    engine.set_script_name("main.js");
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let recorded = Rc::clone(&events);
+    engine
+        .debugger_mut()
+        .set_watch_handler(move |engine, event| {
+            let position = event.position.expect("the position is known");
+            let function = engine
+                .call_stack()
+                .last()
+                .map(|frame| frame.function_name())
+                .unwrap_or_default();
+            recorded.borrow_mut().push(format!(
+                "{} {}:{}:{} in '{}'",
+                event.access,
+                event.script.unwrap_or_default(),
+                position.line_number(),
+                position.column_number(),
+                function
+            ));
+        });
+    let obj = forward_val(&mut engine, "var obj = { x: 1 }; obj").unwrap();
+    engine.debugger_mut().watch(&obj, "x").unwrap();
+
+    forward(
+        &mut engine,
+        "function read() {\n  return obj.x;\n}\nobj.x = 2;\nread();",
+    );
+
+    assert_eq!(
+        *events.borrow(),
+        vec!["write main.js:4:1 in ''", "read main.js:2:3 in 'read'"]
+    );
+}
+
+#[test]
+fn access_display() {
+    assert_eq!(WatchAccess::Read.to_string(), "read");
+    assert_eq!(WatchAccess::Write.to_string(), "write");
+}
+
+#[test]
+fn debugger_statement_without_handler() {
+    let mut engine = Interpreter::new(Realm::create());
+    assert_eq!(forward(&mut engine, "let a = 1; debugger; a + 1"), "2");
+}
+
+#[test]
+fn pause_and_inspect() {
+    let mut engine = Interpreter::new(Realm::create());
+    let pauses = Rc::new(RefCell::new(Vec::new()));
+    let recorded = Rc::clone(&pauses);
+    engine
+        .debugger_mut()
+        .set_pause_handler(move |engine, event| {
+            let reason = event.reason;
+            let scopes = scope_chain(engine);
+            let names: Vec<_> = scopes[0]
+                .bindings
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect();
+            assert_eq!(
+                scopes.last().map(|scope| scope.kind),
+                Some(EnvironmentType::Global)
+            );
+            let x = evaluate(engine, "x * 10").unwrap();
+            recorded
+                .borrow_mut()
+                .push(format!("{} {:?} {}", reason, names, x));
+            Resume::Continue
+        });
+
+    let result = forward(
+        &mut engine,
+        "function f(x) { let y = x + 1; debugger; return y; } f(1) + f(2)",
+    );
+
+    assert_eq!(result, "5");
+    assert_eq!(
+        *pauses.borrow(),
+        vec![
+            "debugger statement [\"arguments\", \"x\", \"y\"] 10",
+            "debugger statement [\"arguments\", \"x\", \"y\"] 20"
+        ]
+    );
+}
+
+#[test]
+fn step() {
+    let mut engine = Interpreter::new(Realm::create());
+    let pauses = Rc::new(RefCell::new(Vec::new()));
+    let recorded = Rc::clone(&pauses);
+    engine
+        .debugger_mut()
+        .set_pause_handler(move |engine, event| {
+            let reason = event.reason;
+            let a = evaluate(engine, "a").unwrap();
+            let statement = event.statement.map(ToString::to_string);
+            recorded
+                .borrow_mut()
+                .push(format!("{} {} {:?}", reason, a, statement));
+            if reason == PauseReason::Step && a.to_string() == "2" {
+                Resume::Continue
+            } else {
+                Resume::Step
+            }
+        });
+
+    forward(
+        &mut engine,
+        "var a = 0; debugger; a = 1; a = 2; a = 3; a = 4;",
+    );
+
+    assert_eq!(
+        *pauses.borrow(),
+        vec![
+            "debugger statement 0 None",
+            "step 0 Some(\"a = 1\")",
+            "step 1 Some(\"a = 2\")",
+            "step 2 Some(\"a = 3\")"
+        ]
+    );
+}
+
+#[test]
+fn evaluate_errors() {
+    let mut engine = Interpreter::new(Realm::create());
+    engine.debugger_mut().set_pause_handler(|engine, _| {
+        assert!(evaluate(engine, "let = ;").is_err());
+        assert!(evaluate(engine, "throw 1").is_err());
+        assert_eq!(evaluate(engine, "inner = 2").unwrap().to_string(), "2");
+        Resume::Continue
+    });
+
+    assert_eq!(
+        forward(&mut engine, "var inner = 1; { debugger; } inner"),
+        "2"
+    );
+    assert!(engine.debugger().has_pause_handler());
+    engine.debugger_mut().remove_pause_handler();
+    assert!(!engine.debugger().has_pause_handler());
+}
@@ -9,7 +9,7 @@ use crate::{
     builtins::value::Value,
     environment::{
         environment_record_trait::EnvironmentRecordTrait,
-        lexical_environment::{Environment, EnvironmentType},
+        lexical_environment::{Environment, EnvironmentError, EnvironmentType},
     },
 };
 use gc::{Finalize, Trace};
@@ -89,7 +89,12 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
     }
 
     #[allow(clippy::else_if_without_else)]
-    fn set_mutable_binding(&mut self, name: &str, value: Value, mut strict: bool) {
+    fn set_mutable_binding(
+        &mut self,
+        name: &str,
+        value: Value,
+        mut strict: bool,
+    ) -> Result<(), EnvironmentError> {
         if self.env_rec.get(name).is_none() {
             if strict {
                 // TODO: change this when error handling comes into play
@@ -98,7 +103,7 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
 
             self.create_mutable_binding(name.to_owned(), true);
             self.initialize_binding(name, value);
-            return;
+            return Ok(());
         }
 
         let record: &mut DeclarativeEnvironmentRecordBinding = self.env_rec.get_mut(name).unwrap();
@@ -116,6 +121,7 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
             // TODO: change this when error handling comes into play
             panic!("TypeError: Cannot mutate an immutable binding {}", name);
         }
+        Ok(())
     }
 
     fn get_binding_value(&self, name: &str, _strict: bool) -> Value {
@@ -179,4 +185,11 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
             None => None,
         }
     }
+
+    fn declarative_bindings(&self) -> Vec<(String, DeclarativeEnvironmentRecordBinding)> {
+        self.env_rec
+            .iter()
+            .map(|(name, binding)| (name.clone(), binding.clone()))
+            .collect()
+    }
 }
@@ -10,7 +10,10 @@
 //!
 use crate::{
     builtins::value::Value,
-    environment::lexical_environment::{Environment, EnvironmentType},
+    environment::{
+        declarative_environment_record::DeclarativeEnvironmentRecordBinding,
+        lexical_environment::{Environment, EnvironmentError, EnvironmentType},
+    },
 };
 use gc::{Finalize, Trace};
 use std::fmt::Debug;
@@ -27,6 +30,14 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// If the Boolean argument deletion is true the binding may be subsequently deleted.
     fn create_mutable_binding(&mut self, name: String, deletion: bool);
 
+    /// Create a new but uninitialized mutable binding for a `var` or function declaration.
+    ///
+    /// For most Environment Records this is the same as `create_mutable_binding`, but the global
+    /// Environment Record stores these bindings as properties of the global object.
+    fn create_var_binding(&mut self, name: String, deletion: bool) {
+        self.create_mutable_binding(name, deletion)
+    }
+
     /// Create a new but uninitialized immutable binding in an Environment Record.
     /// The String value N is the text of the bound name.
     /// If strict is true then attempts to set it after it has been initialized will always throw an exception,
@@ -41,8 +52,14 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// Set the value of an already existing mutable binding in an Environment Record.
     /// The String value `name` is the text of the bound name.
     /// value is the `value` for the binding and may be a value of any ECMAScript language type. S is a Boolean flag.
-    /// If `strict` is true and the binding cannot be set throw a TypeError exception.
-    fn set_mutable_binding(&mut self, name: &str, value: Value, strict: bool);
+    /// If `strict` is true and the binding cannot be set, returns an error that the caller throws
+    /// as a TypeError exception.
+    fn set_mutable_binding(
+        &mut self,
+        name: &str,
+        value: Value,
+        strict: bool,
+    ) -> Result<(), EnvironmentError>;
 
     /// Returns the value of an already existing binding from an Environment Record.
     /// The String value N is the text of the bound name.
@@ -82,4 +99,12 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
 
     /// Fetch global variable
     fn get_global_object(&self) -> Option<Value>;
+
+    /// Get the declarative bindings of the record, like its `let` and `const` declarations.
+    ///
+    /// The bindings of object Environment Records, which are properties of their binding
+    /// object, are not included.
+    fn declarative_bindings(&self) -> Vec<(String, DeclarativeEnvironmentRecordBinding)> {
+        Vec::new()
+    }
 }
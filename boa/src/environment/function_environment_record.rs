@@ -13,7 +13,7 @@ use crate::{
     environment::{
         declarative_environment_record::DeclarativeEnvironmentRecordBinding,
         environment_record_trait::EnvironmentRecordTrait,
-        lexical_environment::{Environment, EnvironmentType},
+        lexical_environment::{Environment, EnvironmentError, EnvironmentType},
     },
 };
 use gc::{unsafe_empty_trace, Finalize, Trace};
@@ -151,7 +151,12 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
     }
 
     #[allow(clippy::else_if_without_else)]
-    fn set_mutable_binding(&mut self, name: &str, value: Value, mut strict: bool) {
+    fn set_mutable_binding(
+        &mut self,
+        name: &str,
+        value: Value,
+        mut strict: bool,
+    ) -> Result<(), EnvironmentError> {
         if self.env_rec.get(name).is_none() {
             if strict {
                 // TODO: change this when error handling comes into play
@@ -160,7 +165,7 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
 
             self.create_mutable_binding(name.to_owned(), true);
             self.initialize_binding(name, value);
-            return;
+            return Ok(());
         }
 
         let record: &mut DeclarativeEnvironmentRecordBinding = self.env_rec.get_mut(name).unwrap();
@@ -179,6 +184,7 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
             // TODO: change this when error handling comes into play
             panic!("TypeError: Cannot mutate an immutable binding {}", name);
         }
+        Ok(())
     }
 
     fn get_binding_value(&self, name: &str, _strict: bool) -> Value {
@@ -248,4 +254,10 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
             None => None,
         }
     }
+    fn declarative_bindings(&self) -> Vec<(String, DeclarativeEnvironmentRecordBinding)> {
+        self.env_rec
+            .iter()
+            .map(|(name, binding)| (name.clone(), binding.clone()))
+            .collect()
+    }
 }
@@ -13,9 +13,11 @@ use crate::{
         Value,
     },
     environment::{
-        declarative_environment_record::DeclarativeEnvironmentRecord,
+        declarative_environment_record::{
+            DeclarativeEnvironmentRecord, DeclarativeEnvironmentRecordBinding,
+        },
         environment_record_trait::EnvironmentRecordTrait,
-        lexical_environment::{Environment, EnvironmentType},
+        lexical_environment::{Environment, EnvironmentError, EnvironmentType},
         object_environment_record::ObjectEnvironmentRecord,
     },
 };
@@ -71,7 +73,7 @@ impl GlobalEnvironmentRecord {
 
     pub fn create_global_function_binding(&mut self, name: &str, value: Value, deletion: bool) {
         let global_object = &mut self.object_record.bindings;
-        let existing_prop = global_object.get_property(&name);
+        let existing_prop = global_object.get_property(name);
         if let Some(prop) = existing_prop {
             if prop.value.is_none() || prop.configurable_or(false) {
                 let mut property =
@@ -80,7 +82,7 @@ impl GlobalEnvironmentRecord {
 
                 global_object.update_property(name, property);
             }
-        } else {
+        } else if global_object.is_extensible() {
             let mut property =
                 Property::data_descriptor(value, Attribute::WRITABLE | Attribute::ENUMERABLE);
             property.set_configurable(deletion);
@@ -122,17 +124,29 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
             .create_immutable_binding(name, strict)
     }
 
+    fn create_var_binding(&mut self, name: String, deletion: bool) {
+        self.create_global_var_binding(name, deletion)
+    }
+
     fn initialize_binding(&mut self, name: &str, value: Value) {
-        if self.declarative_record.has_binding(&name) {
-            // TODO: assert binding is in the object environment record
+        if self.declarative_record.has_binding(name) {
             return self.declarative_record.initialize_binding(name, value);
         }
 
-        panic!("Should not initialized binding without creating first.");
+        debug_assert!(
+            self.object_record.has_binding(name),
+            "Should not initialized binding without creating first."
+        );
+        self.object_record.initialize_binding(name, value)
     }
 
-    fn set_mutable_binding(&mut self, name: &str, value: Value, strict: bool) {
-        if self.declarative_record.has_binding(&name) {
+    fn set_mutable_binding(
+        &mut self,
+        name: &str,
+        value: Value,
+        strict: bool,
+    ) -> Result<(), EnvironmentError> {
+        if self.declarative_record.has_binding(name) {
             return self
                 .declarative_record
                 .set_mutable_binding(name, value, strict);
@@ -141,14 +155,14 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
     }
 
     fn get_binding_value(&self, name: &str, strict: bool) -> Value {
-        if self.declarative_record.has_binding(&name) {
+        if self.declarative_record.has_binding(name) {
             return self.declarative_record.get_binding_value(name, strict);
         }
         self.object_record.get_binding_value(name, strict)
     }
 
     fn delete_binding(&mut self, name: &str) -> bool {
-        if self.declarative_record.has_binding(&name) {
+        if self.declarative_record.has_binding(name) {
             return self.declarative_record.delete_binding(name);
         }
 
@@ -194,4 +208,8 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
     fn get_global_object(&self) -> Option<Value> {
         Some(self.global_this_binding.clone())
     }
+
+    fn declarative_bindings(&self) -> Vec<(String, DeclarativeEnvironmentRecordBinding)> {
+        self.declarative_record.declarative_bindings()
+    }
 }
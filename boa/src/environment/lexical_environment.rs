@@ -136,7 +136,7 @@ impl LexicalEnvironment {
                     })
                     .expect("No function or global environment");
 
-                env.borrow_mut().create_mutable_binding(name, deletion);
+                env.borrow_mut().create_var_binding(name, deletion);
             }
         }
     }
@@ -171,24 +171,35 @@ impl LexicalEnvironment {
         }
     }
 
-    pub fn set_mutable_binding(&mut self, name: &str, value: Value, strict: bool) {
+    /// Sets the value of the innermost binding of `name`.
+    ///
+    /// In strict mode code, fails if the binding can not be set, like a non-writable property
+    /// of the global object, see `EnvironmentRecordTrait::set_mutable_binding`.
+    pub fn set_mutable_binding(
+        &mut self,
+        name: &str,
+        value: Value,
+        strict: bool,
+    ) -> Result<(), EnvironmentError> {
         // Find the first environment which has the given binding
         let env = self
             .environments()
             .find(|env| env.borrow().has_binding(name))
             .expect("Binding does not exists"); // TODO graceful error handling
 
-        env.borrow_mut().set_mutable_binding(name, value, strict);
+        env.borrow_mut().set_mutable_binding(name, value, strict)
     }
 
     pub fn initialize_binding(&mut self, name: &str, value: Value) {
-        // Find the first environment which has the given binding
-        let env = self
+        // Find the first environment which has the given binding.
+        // A `var` declaration has no binding if the global object is not extensible, then the
+        // value is dropped.
+        if let Some(env) = self
             .environments()
             .find(|env| env.borrow().has_binding(name))
-            .expect("Binding does not exists"); // TODO graceful error handling
-
-        env.borrow_mut().initialize_binding(name, value);
+        {
+            env.borrow_mut().initialize_binding(name, value);
+        }
     }
 
     /// get_current_environment_ref is used when you only need to borrow the environment
@@ -13,7 +13,7 @@ use crate::{
     },
     environment::{
         environment_record_trait::EnvironmentRecordTrait,
-        lexical_environment::{Environment, EnvironmentType},
+        lexical_environment::{Environment, EnvironmentError, EnvironmentType},
     },
 };
 use gc::{Finalize, Trace};
@@ -59,15 +59,29 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
         // As all calls to create_mutable_binding are followed by initialized binding
         // The below is just a check.
         debug_assert!(self.has_binding(&name));
-        self.set_mutable_binding(name, value, false)
+        // Outside of strict mode code, setting the binding can not fail.
+        let _ = self.set_mutable_binding(name, value, false);
     }
 
-    fn set_mutable_binding(&mut self, name: &str, value: Value, strict: bool) {
-        debug_assert!(value.is_object() || value.is_function());
-
-        let mut property = Property::data_descriptor(value, Attribute::ENUMERABLE);
-        property.set_configurable(strict);
-        self.bindings.update_property(name, property);
+    fn set_mutable_binding(
+        &mut self,
+        name: &str,
+        value: Value,
+        strict: bool,
+    ) -> Result<(), EnvironmentError> {
+        // The binding object is an ordinary object, so this is just a [[Set]] on it.
+        // This keeps the attributes of existing properties (e.g. on the global object) intact.
+        let set = match self.bindings.as_object_mut() {
+            Some(mut object) => object.set(name.into(), value),
+            None => true,
+        };
+        if !set && strict {
+            return Err(EnvironmentError::new(&format!(
+                "Cannot assign to read only variable '{}'",
+                name
+            )));
+        }
+        Ok(())
     }
 
     fn get_binding_value(&self, name: &str, strict: bool) -> Value {
@@ -0,0 +1,168 @@
+//! This module implements `JsError`, the errors of scripts as seen by the host.
+
+use crate::{
+    builtins::value::Value,
+    exec::{Interpreter, Termination},
+    syntax::ast::Position,
+};
+use std::{
+    error,
+    fmt::{self, Debug, Display},
+};
+
+/// The reason why a script failed, returned by `forward_val`.
+///
+/// Unlike the thrown value, it lets the host tell apart the errors in the source code, the
+/// exceptions that the script did not catch and the limits that stopped it:
+///
+/// ```
+/// use boa::{forward_val, Interpreter, JsError, Realm};
+///
+/// let mut engine = Interpreter::new(Realm::create());
+/// match forward_val(&mut engine, "let x = ;") {
+///     Err(JsError::Syntax { position, .. }) => assert_eq!(position.unwrap().line_number(), 1),
+///     _ => unreachable!(),
+/// }
+/// match forward_val(&mut engine, "throw new TypeError('bad')") {
+///     Err(JsError::Thrown { message, .. }) => assert_eq!(message, "TypeError: bad"),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Clone)]
+pub enum JsError {
+    /// The source code is not valid JavaScript.
+    ///
+    /// With lazy parsing, a syntax error in a function body is only found when the function is
+    /// first called, see `Interpreter::set_lazy_parsing`, and it is reported in the same way.
+    Syntax {
+        /// The description of the error.
+        message: String,
+        /// Where the error was found, if it is known.
+        position: Option<Position>,
+        /// The name of the script of that position, if it is not the script that was run,
+        /// for the errors in the deferred body of a function defined in another script.
+        script: Option<String>,
+    },
+    /// The script threw a value that it did not catch.
+    Thrown {
+        /// The thrown value.
+        value: Value,
+        /// The thrown value converted to a string, like `TypeError: bad` for errors.
+        message: String,
+        /// The start of the innermost statement that threw the value, if it is known.
+        position: Option<Position>,
+        /// The name of the script of that statement, which is not the script that was run if
+        /// the statement is in a function defined in another script.
+        script: Option<String>,
+    },
+    /// The script used more native stack than it may, see `Interpreter::set_max_stack_size`.
+    StackOverflow,
+    /// The script was interrupted with its `InterruptHandle`.
+    Interrupted,
+    /// The script exhausted its step budget, see `Interpreter::set_step_budget`.
+    StepBudgetExceeded,
+    /// The script allocated more than its memory limit, see `Interpreter::set_memory_limit`.
+    MemoryLimitExceeded,
+}
+
+impl JsError {
+    /// Classifies a value thrown by the last script run by `interpreter`.
+    ///
+    /// Values returned by `Context::eval` can be classified too, but its syntax errors are
+    /// `SyntaxError` objects, so they are `Thrown` errors.
+    pub fn from_thrown(value: Value, interpreter: &Interpreter) -> Self {
+        match interpreter.termination() {
+            Some(Termination::Interrupted) => Self::Interrupted,
+            Some(Termination::StepBudget) => Self::StepBudgetExceeded,
+            Some(Termination::MemoryLimit) => Self::MemoryLimitExceeded,
+            Some(Termination::SyntaxError) => Self::Syntax {
+                message: value.get_field("message").to_string(),
+                position: interpreter.error_position(&value),
+                script: interpreter.error_script(&value).map(str::to_owned),
+            },
+            None if interpreter.is_stack_overflow(&value) => Self::StackOverflow,
+            None => Self::Thrown {
+                message: value.to_string(),
+                position: interpreter.error_position(&value),
+                script: interpreter.error_script(&value).map(str::to_owned),
+                value,
+            },
+        }
+    }
+
+    /// Gets where the error was found, if it is known.
+    #[inline]
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Self::Syntax { position, .. } | Self::Thrown { position, .. } => *position,
+            _ => None,
+        }
+    }
+
+    /// Gets the name of the script that the position is in, if the script threw a value from
+    /// a known position, or if the syntax error is in a function body whose parsing was
+    /// deferred.
+    #[inline]
+    pub fn script(&self) -> Option<&str> {
+        match self {
+            Self::Syntax { script, .. } | Self::Thrown { script, .. } => script.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Gets the thrown value, if the script threw one.
+    #[inline]
+    pub fn value(&self) -> Option<&Value> {
+        match self {
+            Self::Thrown { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl Display for JsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax { message, .. } | Self::Thrown { message, .. } => f.write_str(message),
+            Self::StackOverflow => f.write_str("RangeError: Maximum call stack size exceeded"),
+            Self::Interrupted => f.write_str("script interrupted"),
+            Self::StepBudgetExceeded => f.write_str("script exceeded its step budget"),
+            Self::MemoryLimitExceeded => f.write_str("script exceeded its memory limit"),
+        }
+    }
+}
+
+// The thrown value is left out, because the debug representation of objects can be huge.
+impl Debug for JsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax {
+                message,
+                position,
+                script,
+            } => f
+                .debug_struct("Syntax")
+                .field("message", message)
+                .field("position", position)
+                .field("script", script)
+                .finish(),
+            Self::Thrown {
+                message,
+                position,
+                script,
+                ..
+            } => f
+                .debug_struct("Thrown")
+                .field("message", message)
+                .field("position", position)
+                .field("script", script)
+                .finish(),
+            Self::StackOverflow => f.write_str("StackOverflow"),
+            Self::Interrupted => f.write_str("Interrupted"),
+            Self::StepBudgetExceeded => f.write_str("StepBudgetExceeded"),
+            Self::MemoryLimitExceeded => f.write_str("MemoryLimitExceeded"),
+        }
+    }
+}
+
+impl error::Error for JsError {}
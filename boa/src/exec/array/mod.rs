@@ -2,26 +2,42 @@
 
 use super::{Executable, Interpreter};
 use crate::{
-    builtins::{Array, ResultValue},
-    syntax::ast::node::{ArrayDecl, Node},
+    builtins::{Array, ResultValue, Value},
+    syntax::ast::{
+        node::{ArrayDecl, Node},
+        Const,
+    },
     BoaProfiler,
 };
 
 impl Executable for ArrayDecl {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
         let _timer = BoaProfiler::global().start_event("ArrayDecl", "exec");
+        interpreter
+            .allocation_sites_mut()
+            .record("array literal", self);
         let array = Array::new_array(interpreter)?;
-        let mut elements = Vec::new();
+        let mut index: usize = 0;
         for elem in self.as_ref() {
-            if let Node::Spread(ref x) = elem {
-                let val = x.run(interpreter)?;
-                let mut vals = interpreter.extract_array_properties(&val).unwrap();
-                elements.append(&mut vals);
-                continue; // Don't push array after spread
+            match elem {
+                Node::Spread(ref x) => {
+                    let val = x.run(interpreter)?;
+                    let vals = interpreter.extract_array_properties(&val).unwrap();
+                    for val in vals {
+                        array.set_field(index, val);
+                        index += 1;
+                    }
+                }
+                // An elision leaves a hole in the array.
+                Node::Const(Const::Undefined) => index += 1,
+                _ => {
+                    let val = elem.run(interpreter)?;
+                    array.set_field(index, val);
+                    index += 1;
+                }
             }
-            elements.push(elem.run(interpreter)?);
         }
-        Array::add_to_array_object(&array, &elements)?;
+        array.set_field("length", Value::from(index));
 
         Ok(array)
     }
@@ -19,8 +19,15 @@ impl Executable for Block {
         }
 
         let mut obj = Value::null();
-        for statement in self.statements() {
-            obj = statement.run(interpreter)?;
+        for (i, statement) in self.statements().iter().enumerate() {
+            interpreter.set_statement_position(self.spans().get(i));
+            interpreter.before_statement(statement);
+            if let Some(coverage) = interpreter.coverage_mut() {
+                coverage.record_statement(statement);
+            }
+            obj = statement.run(interpreter).inspect_err(|error| {
+                interpreter.record_error_position(error, self.spans().get(i));
+            })?;
 
             match interpreter.get_current_state() {
                 InterpreterState::Return => {
@@ -0,0 +1,220 @@
+//! The call stack of the interpreter, and the stack traces of errors.
+//!
+//! Every script and every function call pushes a frame, and the statements record their
+//! position in the innermost frame as they run. When an error object is created, the frames
+//! are formatted into its `stack` property, like in other engines:
+//!
+//! ```text
+//! TypeError: bad
+//!     at check (main.js:2:5)
+//!     at main.js:5:1
+//! ```
+//!
+//! The frames show the script where their function was defined, and the positions in the
+//! scripts that have a source map, see `Interpreter::set_source_map`, are shown in their
+//! original source.
+
+#[cfg(test)]
+mod tests;
+
+use super::Interpreter;
+use crate::{
+    builtins::{
+        function::FunctionBody,
+        object::ObjectData,
+        property::{Attribute, Property},
+        value::{same_value, JsString, Value},
+    },
+    coverage::ANONYMOUS_SCRIPT,
+    syntax::ast::{Position, Span},
+};
+use std::fmt::Write;
+
+/// The maximum number of frames in a stack trace, like the default `Error.stackTraceLimit`
+/// of V8.
+pub const STACK_TRACE_LIMIT: usize = 10;
+
+/// A frame of the call stack: a script or a function call.
+#[derive(Debug, Clone)]
+pub struct StackFrame {
+    /// The called function, or `None` for a script.
+    function: Option<Value>,
+    /// The start of the statement that runs in the frame, if it is known.
+    position: Option<Position>,
+    /// The name of the script, or of the script that defined the function, if it is known.
+    script: Option<JsString>,
+}
+
+impl StackFrame {
+    /// Gets the called function, or `None` if the frame is a script.
+    #[inline]
+    pub fn function(&self) -> Option<&Value> {
+        self.function.as_ref()
+    }
+
+    /// Gets the name of the called function, which is empty for anonymous functions and
+    /// scripts.
+    pub fn function_name(&self) -> String {
+        match self
+            .function
+            .as_ref()
+            .map(|function| function.get_field("name"))
+        {
+            Some(Value::String(ref name)) => name.to_string(),
+            _ => String::new(),
+        }
+    }
+
+    /// Gets the start of the statement that runs in the frame, or that called the next frame.
+    ///
+    /// It is `None` for the functions implemented in Rust.
+    #[inline]
+    pub fn position(&self) -> Option<Position> {
+        self.position
+    }
+
+    /// Gets the name of the script, or of the script where the called function was defined.
+    ///
+    /// It is `None` for the functions implemented in Rust, and the functions built without a
+    /// parser.
+    #[inline]
+    pub fn script(&self) -> Option<&str> {
+        self.script.as_deref()
+    }
+
+    /// Checks if the called function is implemented in Rust.
+    fn is_native(&self) -> bool {
+        match self.function {
+            Some(Value::Object(ref object)) => match object.borrow().data {
+                ObjectData::Function(ref function) => {
+                    !matches!(function.body, FunctionBody::Ordinary(_))
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Formats the frame as a line of a stack trace, without the indentation, like
+    /// `at f (main.js:2:5)`, with the position in the original source if the script has a
+    /// source map in `interpreter`.
+    fn to_trace_line(&self, interpreter: &Interpreter) -> String {
+        let script = self.script().unwrap_or(ANONYMOUS_SCRIPT);
+        let location = match self.position {
+            _ if self.is_native() => String::from("native"),
+            Some(position) => match interpreter
+                .source_map(script)
+                .and_then(|map| map.lookup(position))
+            {
+                Some(original) => {
+                    format!("{}:{}:{}", original.source, original.line, original.column)
+                }
+                None => format!(
+                    "{}:{}:{}",
+                    script,
+                    position.line_number(),
+                    position.column_number()
+                ),
+            },
+            None => script.to_owned(),
+        };
+        match self.function_name() {
+            name if name.is_empty() => format!("at {}", location),
+            name => format!("at {} ({})", name, location),
+        }
+    }
+}
+
+impl Interpreter {
+    /// Gets the frames of the call stack, from the outermost to the innermost one.
+    #[inline]
+    pub fn call_stack(&self) -> &[StackFrame] {
+        &self.call_stack
+    }
+
+    /// Pushes the frame of a call to `function`, or of a script if it is `None`.
+    pub(crate) fn push_frame(&mut self, function: Option<&Value>) {
+        let script = match function {
+            Some(Value::Object(ref object)) => match object.borrow().data {
+                ObjectData::Function(ref function) => function.script.clone(),
+                _ => None,
+            },
+            Some(_) => None,
+            None => Some(JsString::from(self.script_name())),
+        };
+        self.call_stack.push(StackFrame {
+            function: function.cloned(),
+            position: None,
+            script,
+        });
+    }
+
+    /// Gets the name of the script of the code that is running: the script of the innermost
+    /// frame that has one, or the name of the scripts that are run.
+    pub(crate) fn current_script(&self) -> JsString {
+        self.call_stack
+            .iter()
+            .rev()
+            .find_map(|frame| frame.script.clone())
+            .unwrap_or_else(|| JsString::from(self.script_name()))
+    }
+
+    /// Pops the innermost frame.
+    #[inline]
+    pub(crate) fn pop_frame(&mut self) {
+        self.call_stack.pop();
+    }
+
+    /// Records that the statement at `span` runs in the innermost frame.
+    #[inline]
+    pub(crate) fn set_statement_position(&mut self, span: Option<&Span>) {
+        if let (Some(frame), Some(span)) = (self.call_stack.last_mut(), span) {
+            frame.position = Some(span.start());
+        }
+    }
+
+    /// Formats the stack trace of `error`: its name and message, then the innermost of the
+    /// given frames, up to `STACK_TRACE_LIMIT`.
+    fn format_stack_trace(&self, error: &Value, frames: &[StackFrame]) -> String {
+        let name = match error.get_field("name") {
+            Value::Undefined => String::from("Error"),
+            name => name.to_string(),
+        };
+        let mut trace = match error.get_field("message") {
+            Value::Undefined => name,
+            message if message.to_string().is_empty() => name,
+            message => format!("{}: {}", name, message),
+        };
+        for frame in frames.iter().rev().take(STACK_TRACE_LIMIT) {
+            write!(trace, "\n    {}", frame.to_trace_line(self)).expect("writing to a string");
+        }
+        trace
+    }
+
+    /// Sets the `stack` property of `object` to its stack trace, leaving out the `skip`
+    /// innermost frames of the call stack.
+    ///
+    /// If `until` is a function, the frames of its innermost call and the ones above are left
+    /// out too, and there are no frames if it is not on the call stack.
+    pub(crate) fn capture_stack_trace(&self, object: &Value, skip: usize, until: Option<&Value>) {
+        let end = self.call_stack.len().saturating_sub(skip);
+        let mut frames = &self.call_stack[..end];
+        if let Some(until) = until {
+            let index = frames.iter().rposition(|frame| {
+                frame
+                    .function
+                    .as_ref()
+                    .is_some_and(|function| same_value(function, until))
+            });
+            frames = &frames[..index.unwrap_or(0)];
+        }
+        let trace = self.format_stack_trace(object, frames);
+        object.set_property(
+            "stack",
+            Property::data_descriptor(
+                Value::from(trace),
+                Attribute::WRITABLE | Attribute::NON_ENUMERABLE | Attribute::CONFIGURABLE,
+            ),
+        );
+    }
+}
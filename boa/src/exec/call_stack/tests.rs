@@ -0,0 +1,193 @@
+use crate::{exec::STACK_TRACE_LIMIT, forward, source_map::SourceMap, Context};
+
+/// Builds a context that runs `main.js`.
+fn context() -> Context {
+    let mut context = Context::new();
+    context.interpreter_mut().set_script_name("main.js");
+    context
+}
+
+/// Evaluates a script that is expected to succeed, and formats its value.
+fn eval(context: &mut Context, src: &str) -> String {
+    context.eval(src).unwrap().to_string()
+}
+
+#[test]
+fn error_stack() {
+    let mut context = context();
+    let init = r#"
+function inner() {
+    throw new Error("bad");
+}
+function outer() {
+    var a = 1;
+    return inner();
+}
+var error;
+try {
+    outer();
+} catch (e) {
+    error = e;
+}
+"#;
+    context.eval(init).unwrap();
+    assert_eq!(
+        eval(&mut context, "error.stack"),
+        "Error: bad\n    at inner (main.js:3:5)\n    at outer (main.js:7:5)\n    at main.js:11:5"
+    );
+    assert_eq!(
+        eval(&mut context, "error.propertyIsEnumerable('stack')"),
+        "false"
+    );
+    assert_eq!(
+        eval(&mut context, "JSON.stringify(error)"),
+        r#"{"message":"bad"}"#
+    );
+}
+
+#[test]
+fn runtime_errors() {
+    let mut context = context();
+    let init = r#"
+function check(value) {
+    if (value === undefined) {
+        null.x;
+    }
+}
+var error;
+try {
+    check();
+} catch (e) {
+    error = e;
+}
+"#;
+    context.eval(init).unwrap();
+    let stack = eval(&mut context, "error.stack");
+    assert!(stack.starts_with("TypeError: "), "{}", stack);
+    assert!(
+        stack.ends_with("\n    at check (main.js:4:9)\n    at main.js:9:5"),
+        "{}",
+        stack
+    );
+
+    // The functions implemented in Rust have no position.
+    let stack = eval(
+        &mut context,
+        "var e; try { 'a'.repeat(-1); } catch (x) { e = x; } e.stack",
+    );
+    assert!(
+        stack.ends_with("\n    at native\n    at main.js:1:14"),
+        "{}",
+        stack
+    );
+}
+
+#[test]
+fn scripts_of_functions() {
+    let mut context = context();
+    context.interpreter_mut().set_script_name("lib.js");
+    context
+        .eval("function fail() {\n    throw new Error('bad');\n}")
+        .unwrap();
+    context.interpreter_mut().set_script_name("main.js");
+    let init = r#"
+var error;
+try {
+    fail();
+} catch (e) {
+    error = e;
+}
+"#;
+    context.eval(init).unwrap();
+    assert_eq!(
+        eval(&mut context, "error.stack"),
+        "Error: bad\n    at fail (lib.js:2:5)\n    at main.js:4:5"
+    );
+}
+
+#[test]
+fn source_maps() {
+    let mut context = context();
+    // The fourth line of the script maps to `src/main.ts:11:3`.
+    let map = SourceMap::from_json(
+        r#"{"version": 3, "sources": ["src/main.ts"], "names": [], "mappings": ";;;IAUE"}"#,
+    )
+    .unwrap();
+    context.interpreter_mut().set_source_map("main.js", map);
+    context.interpreter_mut().set_script_name("lib.js");
+    context
+        .eval("function fail() {\n    throw new Error('bad');\n}")
+        .unwrap();
+    context.interpreter_mut().set_script_name("main.js");
+    let init = r#"
+var error;
+try {
+    fail();
+} catch (e) {
+    error = e;
+}
+"#;
+    context.eval(init).unwrap();
+    assert_eq!(
+        eval(&mut context, "error.stack"),
+        "Error: bad\n    at fail (lib.js:2:5)\n    at src/main.ts:11:3"
+    );
+}
+
+#[test]
+fn capture_stack_trace() {
+    let mut context = context();
+    let init = r#"
+function MyError(message) {
+    this.message = message;
+    Error.captureStackTrace(this, MyError);
+}
+function fail() {
+    return new MyError("oops");
+}
+var custom = fail();
+var object = {};
+function capture() {
+    Error.captureStackTrace(object);
+}
+capture();
+"#;
+    context.eval(init).unwrap();
+    assert_eq!(
+        eval(&mut context, "custom.stack"),
+        "Error: oops\n    at fail (main.js:7:5)\n    at main.js:9:1"
+    );
+    assert_eq!(
+        eval(&mut context, "object.stack"),
+        "Error\n    at capture (main.js:12:5)\n    at main.js:14:1"
+    );
+    assert!(context.eval("Error.captureStackTrace(1)").is_err());
+}
+
+#[test]
+fn stack_trace_limit() {
+    let mut context = context();
+    let init = r#"
+function recurse(n) {
+    if (n === 0) {
+        throw new RangeError("deep");
+    }
+    return recurse(n - 1);
+}
+var error;
+try {
+    recurse(20);
+} catch (e) {
+    error = e;
+}
+"#;
+    context.eval(init).unwrap();
+    let stack = eval(&mut context, "error.stack");
+    assert_eq!(stack.lines().count(), STACK_TRACE_LIMIT + 1);
+    assert!(stack.starts_with("RangeError: deep\n    at recurse (main.js:4:9)"));
+    assert!(stack.ends_with("    at recurse (main.js:6:5)"));
+
+    // The frames of a script are popped when it ends, even if it throws.
+    assert!(forward(context.interpreter_mut(), "recurse(3)").contains("RangeError"));
+    assert!(context.interpreter().call_stack().is_empty());
+}
@@ -7,7 +7,11 @@ use std::borrow::Borrow;
 
 impl Executable for If {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
-        Ok(if self.cond().run(interpreter)?.borrow().to_boolean() {
+        let cond = self.cond().run(interpreter)?.borrow().to_boolean();
+        if let Some(coverage) = interpreter.coverage_mut() {
+            coverage.record_branch(self, if cond { 0 } else { 1 });
+        }
+        Ok(if cond {
             self.body().run(interpreter)?
         } else if let Some(ref else_e) = self.else_node() {
             else_e.run(interpreter)?
@@ -4,11 +4,15 @@ use super::{Executable, Interpreter};
 use crate::{
     builtins::{
         function::ThisMode,
+        object::ObjectData,
         value::{ResultValue, Value},
     },
     environment::lexical_environment::VariableScope,
-    syntax::ast::node::{
-        ArrowFunctionDecl, ConstDeclList, FunctionDecl, FunctionExpr, LetDeclList, VarDeclList,
+    syntax::ast::{
+        node::{
+            ArrowFunctionDecl, ConstDeclList, FunctionDecl, FunctionExpr, LetDeclList, VarDeclList,
+        },
+        Position,
     },
     BoaProfiler,
 };
@@ -16,13 +20,15 @@ use crate::{
 impl Executable for FunctionDecl {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
         let _timer = BoaProfiler::global().start_event("FunctionDecl", "exec");
+        interpreter.allocation_sites_mut().record("function", self);
         let val = interpreter.create_function(
             self.parameters().to_vec(),
-            self.body().to_vec(),
+            self.body().clone(),
             ThisMode::NonLexical,
             true,
             true,
         );
+        set_origin(interpreter, &val, self.position());
 
         // Set the name and assign it in the current environment
         val.set_field("name", self.name());
@@ -43,13 +49,15 @@ impl Executable for FunctionDecl {
 
 impl Executable for FunctionExpr {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
+        interpreter.allocation_sites_mut().record("function", self);
         let val = interpreter.create_function(
             self.parameters().to_vec(),
-            self.body().to_vec(),
+            self.body().clone(),
             ThisMode::NonLexical,
             true,
             true,
         );
+        set_origin(interpreter, &val, self.position());
 
         if let Some(name) = self.name() {
             val.set_field("name", Value::from(name));
@@ -70,7 +78,7 @@ impl Executable for VarDeclList {
 
             if environment.has_binding(var.name()) {
                 if var.init().is_some() {
-                    environment.set_mutable_binding(var.name(), val, true);
+                    interpreter.set_mutable_binding(var.name(), val)?;
                 }
             } else {
                 environment.create_mutable_binding(
@@ -127,12 +135,27 @@ impl Executable for LetDeclList {
 
 impl Executable for ArrowFunctionDecl {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
-        Ok(interpreter.create_function(
+        interpreter
+            .allocation_sites_mut()
+            .record("arrow function", self);
+        let val = interpreter.create_function(
             self.params().to_vec(),
-            self.body().to_vec(),
+            self.body().clone(),
             ThisMode::Lexical,
             false,
             true,
-        ))
+        );
+        set_origin(interpreter, &val, self.position());
+        Ok(val)
+    }
+}
+
+/// Records where a function object was defined: its position, and the script that is running.
+fn set_origin(interpreter: &Interpreter, function: &Value, position: Option<Position>) {
+    if let Some(mut object) = function.as_object_mut() {
+        if let ObjectData::Function(ref mut function) = object.data {
+            function.set_position(position);
+            function.set_script(Some(interpreter.current_script()));
+        }
     }
 }
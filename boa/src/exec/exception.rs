@@ -1,13 +1,36 @@
 use super::*;
 use crate::{
+    builtins::value::same_value,
     exec::Executable,
     syntax::ast::{
         node::{Call, Identifier, New},
-        Const,
+        Const, Position, Span,
     },
 };
 
 impl Interpreter {
+    /// Constructs an `Error` with the specified message.
+    pub fn construct_error<M>(&mut self, message: M) -> Value
+    where
+        M: Into<String>,
+    {
+        // Runs a `new Error(message)`.
+        New::from(Call::new(
+            Identifier::from("Error"),
+            vec![Const::from(message.into()).into()],
+        ))
+        .run(self)
+        .expect_err("Error should always throw")
+    }
+
+    /// Throws an `Error` with the specified message.
+    pub fn throw_error<M>(&mut self, message: M) -> ResultValue
+    where
+        M: Into<String>,
+    {
+        Err(self.construct_error(message))
+    }
+
     /// Constructs a `RangeError` with the specified message.
     pub fn construct_range_error<M>(&mut self, message: M) -> Value
     where
@@ -93,4 +116,59 @@ impl Interpreter {
     {
         Err(self.construct_syntax_error(message))
     }
+
+    /// Constructs a `URIError` with the specified message.
+    pub fn construct_uri_error<M>(&mut self, message: M) -> Value
+    where
+        M: Into<String>,
+    {
+        New::from(Call::new(
+            Identifier::from("URIError"),
+            vec![Const::from(message.into()).into()],
+        ))
+        .run(self)
+        .expect_err("URIError should always throw")
+    }
+
+    /// Throws a `URIError` with the specified message.
+    pub fn throw_uri_error<M>(&mut self, message: M) -> ResultValue
+    where
+        M: Into<String>,
+    {
+        Err(self.construct_uri_error(message))
+    }
+
+    /// Records that `error` was thrown by the statement at `span`, unless it was already
+    /// recorded by an inner statement.
+    ///
+    /// Statements that fail record their position on the way up, so the innermost one is kept.
+    pub(crate) fn record_error_position(&mut self, error: &Value, span: Option<&Span>) {
+        let recorded =
+            matches!(&self.error_position, Some((value, ..)) if same_value(value, error));
+        if let (false, Some(span)) = (recorded, span) {
+            self.error_position = Some((error.clone(), span.start(), self.current_script()));
+        }
+    }
+
+    /// Gets the position of the statement that threw `error` in the last script, if it is
+    /// known.
+    ///
+    /// Only the statements parsed from source code know their position.
+    pub fn error_position(&self, error: &Value) -> Option<Position> {
+        match &self.error_position {
+            Some((value, position, _)) if same_value(value, error) => Some(*position),
+            _ => None,
+        }
+    }
+
+    /// Gets the name of the script of the statement that threw `error`, which is the script
+    /// where its function was defined if it was thrown in a function, if it is known.
+    ///
+    /// The position of `error_position` is in this script.
+    pub fn error_script(&self, error: &Value) -> Option<&str> {
+        match &self.error_position {
+            Some((value, _, script)) if same_value(value, error) => Some(script),
+            _ => None,
+        }
+    }
 }
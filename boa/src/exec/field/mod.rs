@@ -11,7 +11,7 @@ impl Executable for GetConstField {
             obj = interpreter.to_object(&obj)?;
         }
 
-        Ok(obj.get_field(self.field()))
+        interpreter.get_field(&obj, self.field())
     }
 }
 
@@ -22,7 +22,8 @@ impl Executable for GetField {
             obj = interpreter.to_object(&obj)?;
         }
         let field = self.field().run(interpreter)?;
+        let field = interpreter.to_property_key(&field)?;
 
-        Ok(obj.get_field(field.to_string()))
+        interpreter.get_field(&obj, field)
     }
 }
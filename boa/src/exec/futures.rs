@@ -0,0 +1,168 @@
+//! The bridge between promises and Rust futures.
+//!
+//! A promise is awaited from Rust with a `PromiseFuture`, and a Rust future is exposed to
+//! scripts as a promise with `Interpreter::promise_from_future`. The futures of the host are
+//! polled, and the promises they settle are reacted to, by the `EventLoop` future, which the host
+//! drives on its executor.
+
+use super::Interpreter;
+use crate::builtins::{
+    promise::{Promise, PromiseState, PromiseWakers},
+    value::{ResultValue, Value},
+};
+use std::{
+    fmt::{self, Debug},
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A future of the host, and the promise it settles.
+pub(crate) struct HostFuture {
+    promise: Value,
+    future: Pin<Box<dyn Future<Output = ResultValue>>>,
+}
+
+impl Debug for HostFuture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HostFuture")
+    }
+}
+
+/// A future that completes when a promise settles, with its value if it is fulfilled or with
+/// its reason if it is rejected.
+///
+/// It is created with `Interpreter::promise_to_future`. The promise only settles while its
+/// interpreter runs scripts or jobs, so the future only completes if the `EventLoop` of the
+/// interpreter, or `run_jobs`, is driven as well.
+#[derive(Debug)]
+pub struct PromiseFuture {
+    promise: Value,
+}
+
+impl Future for PromiseFuture {
+    type Output = ResultValue;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<ResultValue> {
+        match Promise::state(&self.promise) {
+            Some(PromiseState::Fulfilled(value)) => Poll::Ready(Ok(value)),
+            Some(PromiseState::Rejected(reason)) => Poll::Ready(Err(reason)),
+            _ => {
+                if self.promise.get_internal_state().is_none() {
+                    self.promise.set_internal_state(PromiseWakers::default());
+                }
+                if let Some(state) = self.promise.get_internal_state() {
+                    if let Some(wakers) = state.downcast_ref::<PromiseWakers>() {
+                        wakers.register(cx.waker());
+                    }
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// The future returned by `Interpreter::run_event_loop`.
+///
+/// It completes once no job is pending and every future of the host has completed, with the
+/// first error thrown by a job, if any.
+#[derive(Debug)]
+pub struct EventLoop<'a> {
+    interpreter: &'a mut Interpreter,
+    result: Result<(), Value>,
+}
+
+impl Future for EventLoop<'_> {
+    type Output = Result<(), Value>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            if let Err(error) = this.interpreter.run_jobs() {
+                if this.result.is_ok() {
+                    this.result = Err(error);
+                }
+            }
+            if this.interpreter.is_terminated() {
+                this.interpreter.host_futures.clear();
+            }
+
+            let completed = this.interpreter.poll_host_futures(cx);
+            if this.interpreter.host_futures.is_empty() && !this.interpreter.has_pending_jobs() {
+                return Poll::Ready(mem::replace(&mut this.result, Ok(())));
+            }
+            if !completed {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+impl Interpreter {
+    /// Gets the state of a promise, or `None` if the value is not a promise.
+    #[inline]
+    pub fn promise_state(&self, promise: &Value) -> Option<PromiseState> {
+        Promise::state(promise)
+    }
+
+    /// Creates a future that completes when the promise settles.
+    ///
+    /// Returns a `TypeError` if the value is not a promise.
+    pub fn promise_to_future(&mut self, promise: &Value) -> Result<PromiseFuture, Value> {
+        if !Promise::is_promise(promise) {
+            return Err(self.construct_type_error("not a Promise"));
+        }
+        Ok(PromiseFuture {
+            promise: promise.clone(),
+        })
+    }
+
+    /// Creates a promise that is settled with the output of a future of the host.
+    ///
+    /// The future is polled by the `EventLoop` of the interpreter. This is how asynchronous
+    /// host functions, like file or network access, are exposed to scripts.
+    pub fn promise_from_future<F>(&mut self, future: F) -> ResultValue
+    where
+        F: Future<Output = ResultValue> + 'static,
+    {
+        let promise = Promise::new_promise(self)?;
+        self.host_futures.push(HostFuture {
+            promise: promise.clone(),
+            future: Box::pin(future),
+        });
+        Ok(promise)
+    }
+
+    /// Creates a future that runs the pending jobs and polls the futures of the host until
+    /// there is nothing left to do.
+    #[inline]
+    pub fn run_event_loop(&mut self) -> EventLoop<'_> {
+        EventLoop {
+            interpreter: self,
+            result: Ok(()),
+        }
+    }
+
+    /// Polls the futures of the host, and settles the promises of those that completed.
+    ///
+    /// Returns `true` if any of them completed.
+    fn poll_host_futures(&mut self, cx: &mut Context<'_>) -> bool {
+        let mut completed = false;
+        let mut index = 0;
+        while index < self.host_futures.len() {
+            match self.host_futures[index].future.as_mut().poll(cx) {
+                Poll::Ready(result) => {
+                    let HostFuture { promise, .. } = self.host_futures.remove(index);
+                    match result {
+                        Ok(value) => Promise::resolve_promise(&promise, value, self),
+                        Err(reason) => Promise::reject_promise(&promise, reason, self),
+                    }
+                    completed = true;
+                }
+                Poll::Pending => index += 1,
+            }
+        }
+        completed
+    }
+}
@@ -0,0 +1,270 @@
+//! Interruption of running scripts, from another thread, after a number of steps or when they
+//! allocate too much memory, and the limit of the native stack they may use.
+
+use super::Interpreter;
+use crate::{
+    builtins::value::{JsString, Value},
+    memory::RunningCount,
+    syntax::parser::error::ParseError,
+};
+use std::{
+    convert::TryFrom,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// A handle that interrupts the scripts of an interpreter, from any thread.
+///
+/// Obtained with `Interpreter::interrupt_handle`, it can be cloned and sent to other threads.
+#[derive(Debug, Clone, Default)]
+pub struct InterruptHandle {
+    interrupted: Arc<AtomicBool>,
+}
+
+impl InterruptHandle {
+    /// Interrupts the running script, or the next one if no script is running.
+    ///
+    /// The script stops at its next loop iteration or function call.
+    #[inline]
+    pub fn interrupt(&self) {
+        self.interrupted.store(true, Ordering::SeqCst);
+    }
+
+    /// Checks if an interruption is pending.
+    #[inline]
+    pub fn is_interrupted(&self) -> bool {
+        self.interrupted.load(Ordering::SeqCst)
+    }
+
+    /// Consumes the pending interruption, if any.
+    #[inline]
+    fn take(&self) -> bool {
+        self.interrupted.swap(false, Ordering::SeqCst)
+    }
+}
+
+/// The reason why a script was terminated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Termination {
+    /// The script was interrupted with its `InterruptHandle`.
+    Interrupted,
+    /// The script exhausted its step budget.
+    StepBudget,
+    /// The script allocated more than its memory limit.
+    MemoryLimit,
+    /// The script called a function whose body has a syntax error, which was only found when
+    /// the body was parsed, see `Interpreter::set_lazy_parsing`.
+    SyntaxError,
+}
+
+impl Termination {
+    /// Gets the message of the error returned to the host.
+    fn message(self) -> &'static str {
+        match self {
+            Self::Interrupted => "script interrupted",
+            Self::StepBudget => "script exceeded its step budget",
+            Self::MemoryLimit => "script exceeded its memory limit",
+            Self::SyntaxError => "script called a function with a syntax error",
+        }
+    }
+}
+
+/// The default maximum number of bytes of native stack that scripts may use.
+pub(crate) const DEFAULT_MAX_STACK_SIZE: usize = 1024 * 1024;
+
+/// Gets an approximation of the address of the top of the native stack.
+#[inline(never)]
+fn stack_address() -> usize {
+    let marker = 0_u8;
+    &marker as *const u8 as usize
+}
+
+impl Interpreter {
+    /// Gets a handle that interrupts the scripts of this interpreter.
+    #[inline]
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.interrupt.clone()
+    }
+
+    /// Gets the maximum number of steps a script may take, see `set_step_budget`.
+    #[inline]
+    pub fn step_budget(&self) -> Option<u64> {
+        self.step_budget
+    }
+
+    /// Sets the maximum number of steps, loop iterations and function calls, that a script may
+    /// take before it is terminated.
+    ///
+    /// The count starts again for every script.
+    #[inline]
+    pub fn set_step_budget(&mut self, step_budget: Option<u64>) {
+        self.step_budget = step_budget;
+    }
+
+    /// Gets the maximum number of bytes the scripts may allocate, see `set_memory_limit`.
+    #[inline]
+    pub fn memory_limit(&self) -> Option<usize> {
+        self.memory_limit
+    }
+
+    /// Sets the maximum number of bytes that the scripts of the interpreter may allocate, and
+    /// keep allocated, before the running one is terminated.
+    ///
+    /// The count is kept from one script to the next, so the memory that a script keeps
+    /// reachable counts against the scripts that follow it. It is checked at every step, and
+    /// before the builtins that can build large strings or arrays in a single call allocate
+    /// them. The memory allocated at the other steps is only counted if a
+    /// `memory::CountingAllocator` is the global allocator, see the `memory` module.
+    #[inline]
+    pub fn set_memory_limit(&mut self, memory_limit: Option<usize>) {
+        self.memory_limit = memory_limit;
+    }
+
+    /// Gets the maximum number of bytes of native stack that scripts may use.
+    #[inline]
+    pub fn max_stack_size(&self) -> usize {
+        self.max_stack_size
+    }
+
+    /// Sets the maximum number of bytes of native stack that scripts may use, 1 MiB by default.
+    ///
+    /// A function call that would use more throws a `RangeError`, instead of overflowing the
+    /// stack of the thread. The limit must leave room for the frames of the host below the
+    /// script, and for the builtins that the script calls.
+    #[inline]
+    pub fn set_max_stack_size(&mut self, max_stack_size: usize) {
+        self.max_stack_size = max_stack_size;
+    }
+
+    /// Checks if the last script was terminated, by an interruption, by its step budget or by
+    /// its memory limit.
+    ///
+    /// Termination cannot be caught by the script: `catch` and `finally` blocks are skipped, and
+    /// the error is returned to the host.
+    #[inline]
+    pub fn is_terminated(&self) -> bool {
+        self.termination.is_some()
+    }
+
+    /// Gets the reason why the last script was terminated, if it was.
+    #[inline]
+    pub fn termination(&self) -> Option<Termination> {
+        self.termination
+    }
+
+    /// Checks if `error` is the `RangeError` thrown when the last script used more native stack
+    /// than it may.
+    pub fn is_stack_overflow(&self, error: &Value) -> bool {
+        match (error, &self.stack_overflow) {
+            (Value::Object(ref error), Some(Value::Object(ref overflow))) => {
+                std::ptr::eq(error.as_ref(), overflow.as_ref())
+            }
+            _ => false,
+        }
+    }
+
+    /// Prepares the termination state for a new script.
+    pub(crate) fn start_script(&mut self) {
+        self.steps = 0;
+        self.termination = None;
+        self.stack_base = stack_address();
+        self.stack_overflow = None;
+        self.error_position = None;
+    }
+
+    /// Throws a `RangeError` if the running script used more native stack than it may.
+    pub(crate) fn check_stack(&mut self) -> Result<(), Value> {
+        let used = self.stack_base.saturating_sub(stack_address());
+        if used > self.max_stack_size {
+            let error = self.construct_range_error("Maximum call stack size exceeded");
+            self.stack_overflow = Some(error.clone());
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Counts a step of the running script, and terminates it if it was interrupted or if it
+    /// exhausted its step budget.
+    pub(crate) fn step(&mut self) -> Result<(), Value> {
+        if self.is_terminated() {
+            return Err(self.construct_range_error("script terminated"));
+        }
+        if self.interrupt.take() {
+            return Err(self.terminate(Termination::Interrupted));
+        }
+        if let Some(budget) = self.step_budget {
+            self.steps += 1;
+            if self.steps > budget {
+                return Err(self.terminate(Termination::StepBudget));
+            }
+        }
+        self.check_memory(0)
+    }
+
+    /// Terminates the running script if allocating `bytes` more would exceed its memory limit.
+    ///
+    /// The builtins that allocate a size computed from their arguments call it first, so that
+    /// the script is terminated before the allocation instead of at its next step.
+    pub(crate) fn check_memory(&mut self, bytes: usize) -> Result<(), Value> {
+        if let Some(limit) = self.memory_limit {
+            let requested = isize::try_from(bytes).unwrap_or(isize::MAX);
+            let exceeds = |interpreter: &Self| {
+                interpreter.allocated_bytes().saturating_add(requested) > limit as isize
+            };
+            if exceeds(self) {
+                // Garbage counts as allocated until it is collected.
+                tracing::debug!(
+                    allocated = self.allocated_bytes(),
+                    requested,
+                    limit,
+                    "collecting garbage to enforce the memory limit"
+                );
+                gc::force_collect();
+                if exceeds(self) {
+                    return Err(self.terminate(Termination::MemoryLimit));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the bytes allocated by the scripts.
+    #[inline]
+    pub(crate) fn allocated_bytes(&self) -> isize {
+        self.memory.allocated_bytes()
+    }
+
+    /// Charges the allocations of the current thread to the scripts of this interpreter, until
+    /// the returned guard is dropped.
+    pub(crate) fn count_memory(&self) -> RunningCount {
+        self.memory.run()
+    }
+
+    /// Terminates the running script with a syntax error found in the deferred body of a
+    /// function defined in `script`, returning the `SyntaxError` to report.
+    ///
+    /// The error is reported like the syntax errors found before the script runs, so the
+    /// script cannot catch it.
+    pub(crate) fn terminate_with_syntax_error(
+        &mut self,
+        error: &ParseError,
+        script: Option<JsString>,
+    ) -> Value {
+        let value = self.construct_syntax_error(format!("Parsing Error: {}", error));
+        if let Some(position) = error.position() {
+            let script = script.unwrap_or_else(|| self.current_script());
+            self.error_position = Some((value.clone(), position, script));
+        }
+        self.termination = Some(Termination::SyntaxError);
+        value
+    }
+
+    /// Terminates the running script, returning the error to report.
+    fn terminate(&mut self, termination: Termination) -> Value {
+        let error = self.construct_range_error(termination.message());
+        self.termination = Some(termination);
+        error
+    }
+}
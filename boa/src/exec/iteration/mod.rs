@@ -33,6 +33,7 @@ impl Executable for ForLoop {
             .transpose()?
             .unwrap_or(true)
         {
+            interpreter.step()?;
             let result = self.body().run(interpreter)?;
 
             match interpreter.get_current_state() {
@@ -67,6 +68,7 @@ impl Executable for WhileLoop {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
         let mut result = Value::undefined();
         while self.cond().run(interpreter)?.borrow().to_boolean() {
+            interpreter.step()?;
             result = self.expr().run(interpreter)?;
             match interpreter.get_current_state() {
                 InterpreterState::Break(_label) => {
@@ -90,6 +92,7 @@ impl Executable for WhileLoop {
 
 impl Executable for DoWhileLoop {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
+        interpreter.step()?;
         let mut result = self.body().run(interpreter)?;
         match interpreter.get_current_state() {
             InterpreterState::Break(_label) => {
@@ -108,6 +111,7 @@ impl Executable for DoWhileLoop {
         }
 
         while self.cond().run(interpreter)?.borrow().to_boolean() {
+            interpreter.step()?;
             result = self.body().run(interpreter)?;
             match interpreter.get_current_state() {
                 InterpreterState::Break(_label) => {
@@ -4,15 +4,19 @@ mod array;
 mod block;
 mod break_node;
 mod call;
+mod call_stack;
 mod conditional;
 mod declaration;
 mod exception;
 mod field;
+mod futures;
 mod identifier;
+mod interrupt;
 mod iteration;
 mod new;
 mod object;
 mod operator;
+mod realms;
 mod return_smt;
 mod spread;
 mod statement_list;
@@ -23,24 +27,49 @@ mod throw;
 mod try_node;
 
 use crate::{
+    allocation_sites::AllocationSites,
+    annex_b,
     builtins::{
+        console::ConsoleWriter,
         function::{Function as FunctionObject, FunctionBody, ThisMode},
+        math::SeededRandom,
         number::{f64_to_int32, f64_to_uint32},
         object::{Object, ObjectData, PROTOTYPE},
-        property::Property,
-        value::{RcBigInt, RcString, ResultValue, Type, Value},
-        BigInt, Console, Number,
+        property::{Property, PropertyKey},
+        string,
+        symbol::WellKnownSymbol,
+        value::{JsString, RcBigInt, ResultValue, Type, Value},
+        Array, BigInt, Console, Number,
     },
-    realm::Realm,
+    coverage::{Coverage, ScriptSites, ANONYMOUS_SCRIPT},
+    cpu_profile::{CpuFrame, CpuProfile},
+    debugger::{Debugger, PauseEvent, PauseReason, WatchAccess},
+    execution_profile::ExecutionProfile,
+    experimental::{self, Experimental},
+    memory::MemoryCount,
+    phase_timings::{Phase, PhaseTimings},
+    realm::{Realm, RealmId},
+    source_map::SourceMap,
     syntax::ast::{
         constant::Const,
-        node::{FormalParameter, Node, StatementList},
+        node::{FormalParameter, LazyStatementList, Node, StatementList},
+        Position,
     },
+    timeline::Timeline,
     BoaProfiler,
 };
+pub use call_stack::{StackFrame, STACK_TRACE_LIMIT};
+pub use futures::{EventLoop, PromiseFuture};
+pub use interrupt::{InterruptHandle, Termination};
+use rustc_hash::FxHashMap;
+
 use std::borrow::Borrow;
+use std::collections::VecDeque;
 use std::convert::TryFrom;
+use std::fmt::{self, Debug};
+use std::mem::{self, ManuallyDrop};
 use std::ops::Deref;
+use std::time::{Instant, SystemTime};
 
 pub trait Executable {
     /// Runs this executable in the given executor.
@@ -60,30 +89,672 @@ pub enum PreferredType {
     Default,
 }
 
+/// A function that is called right before an interpreter is torn down.
+pub type ShutdownHook = Box<dyn FnOnce(&mut Interpreter)>;
+
+/// The shutdown hooks registered on an interpreter, in registration order.
+#[derive(Default)]
+struct ShutdownHooks(Vec<ShutdownHook>);
+
+impl Debug for ShutdownHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ShutdownHooks({})", self.0.len())
+    }
+}
+
+/// A pending job, a function that is called once the current script has finished running.
+///
+/// More information:
+///  - [ECMAScript reference](https://tc39.es/ecma262/#sec-jobs)
+#[derive(Debug, Clone)]
+pub(crate) struct Job {
+    /// The function to call.
+    function: Value,
+    /// The arguments to call the function with.
+    args: Vec<Value>,
+}
+
 /// A Javascript intepreter
+///
+/// Dropping an interpreter tears it down deterministically: the shutdown hooks run first, then
+/// the pending jobs and the realm are released and a garbage collection is forced, so that the
+/// finalizers of every object that is no longer reachable have run by the time `drop` returns.
 #[derive(Debug)]
 pub struct Interpreter {
     /// the current state of the interpreter.
     state: InterpreterState,
 
     /// realm holds both the global object and the environment
-    pub realm: Realm,
+    ///
+    /// It is only dropped manually, in the `Drop` implementation of the interpreter.
+    pub realm: ManuallyDrop<Realm>,
 
     /// This is for generating an unique internal `Symbol` hash.
     symbol_count: u32,
 
     /// console object state.
     console: Console,
+
+    /// The maximum length of a string, in UTF-16 code units, that scripts are allowed to create.
+    max_string_length: usize,
+
+    /// The maximum amount of elements that scripts are allowed to store in an array.
+    max_array_length: usize,
+
+    /// The default locale of the locale-sensitive methods, like `toLocaleString`.
+    locale: Option<String>,
+
+    /// The generator of `Math.random`, if it has a seed.
+    random: Option<SeededRandom>,
+
+    /// The time that the clock is pinned to, in milliseconds since the Unix epoch.
+    clock: Option<u64>,
+
+    /// The execution timeline, if it is enabled.
+    timeline: Option<Timeline>,
+
+    /// The execution profile, if it is enabled.
+    profile: Option<ExecutionProfile>,
+
+    /// The code coverage, if it is enabled.
+    coverage: Option<Coverage>,
+
+    /// The CPU profile, if it is enabled.
+    cpu_profile: Option<CpuProfile>,
+
+    /// The name of the scripts that are run, see `set_script_name`.
+    script_name: Option<String>,
+
+    /// The source maps of the scripts, by name, see `set_source_map`.
+    source_maps: FxHashMap<String, SourceMap>,
+
+    /// The time spent in each phase of the evaluation of scripts, if it is measured.
+    timings: Option<PhaseTimings>,
+
+    /// Whether the syntax tree of scripts is optimized before they run.
+    optimize: bool,
+
+    /// Whether function bodies are only parsed when they are first called.
+    lazy_parsing: bool,
+
+    /// Whether scripts are run as strict mode code, see `set_strict`.
+    strict: bool,
+
+    /// The experimental features that are enabled, see `set_experimental`.
+    experimental: Experimental,
+
+    /// Whether the web legacy compatibility features are enabled, see `set_annex_b`.
+    annex_b: bool,
+
+    /// The scripts and function calls that are running, see `call_stack`.
+    call_stack: Vec<StackFrame>,
+
+    /// The hooks to run when the interpreter is dropped.
+    shutdown_hooks: ShutdownHooks,
+
+    /// The debugging state, like the watched properties.
+    debugger: Debugger,
+
+    /// The pending microtasks, in the order they were queued.
+    jobs: VecDeque<Job>,
+
+    /// The allocation counts of the sites of the running scripts.
+    allocation_sites: AllocationSites,
+
+    /// The handle that interrupts the running script.
+    interrupt: InterruptHandle,
+
+    /// The maximum number of steps a script may take.
+    step_budget: Option<u64>,
+
+    /// The number of steps the running script took.
+    steps: u64,
+
+    /// Why the running script was terminated, if it was.
+    termination: Option<Termination>,
+
+    /// The maximum number of bytes the scripts may allocate.
+    memory_limit: Option<usize>,
+
+    /// The bytes allocated by the scripts.
+    memory: MemoryCount,
+
+    /// The maximum number of bytes of native stack that scripts may use.
+    max_stack_size: usize,
+
+    /// The address of the native stack when the running script started.
+    stack_base: usize,
+
+    /// The error thrown when the running script used too much native stack.
+    stack_overflow: Option<Value>,
+
+    /// The last error thrown by the running script, with the position of the statement that
+    /// threw it and the name of its script.
+    error_position: Option<(Value, Position, JsString)>,
+
+    /// The realms created with `create_realm`. The slot of the current realm, which is held
+    /// in `realm`, is empty.
+    realms: Vec<Option<Realm>>,
+
+    /// The realm that scripts currently run in.
+    current_realm: RealmId,
+
+    /// The functions called by the wrappers that `transfer` created.
+    wrapped_functions: Vec<Value>,
+
+    /// The futures of the host that settle promises, see `promise_from_future`.
+    host_futures: Vec<futures::HostFuture>,
 }
 
 impl Interpreter {
     /// Creates a new interpreter.
     pub fn new(realm: Realm) -> Self {
+        experimental::install_builtins(&realm, Experimental::default());
         Self {
             state: InterpreterState::Executing,
-            realm,
-            symbol_count: 0,
+            realm: ManuallyDrop::new(realm),
+            symbol_count: WellKnownSymbol::ALL.len() as u32,
             console: Console::default(),
+            max_string_length: string::String::MAX_STRING_LENGTH,
+            max_array_length: Array::MAX_ARRAY_LENGTH,
+            locale: None,
+            random: None,
+            clock: None,
+            timeline: None,
+            profile: None,
+            coverage: None,
+            cpu_profile: None,
+            script_name: None,
+            source_maps: FxHashMap::default(),
+            timings: None,
+            optimize: false,
+            lazy_parsing: false,
+            strict: false,
+            experimental: Experimental::default(),
+            annex_b: true,
+            call_stack: Vec::new(),
+            shutdown_hooks: ShutdownHooks::default(),
+            debugger: Debugger::default(),
+            jobs: VecDeque::new(),
+            allocation_sites: AllocationSites::default(),
+            interrupt: InterruptHandle::default(),
+            step_budget: None,
+            steps: 0,
+            termination: None,
+            memory_limit: None,
+            memory: MemoryCount::default(),
+            max_stack_size: interrupt::DEFAULT_MAX_STACK_SIZE,
+            stack_base: 0,
+            stack_overflow: None,
+            error_position: None,
+            realms: vec![None],
+            current_realm: RealmId::default(),
+            wrapped_functions: Vec::new(),
+            host_futures: Vec::new(),
+        }
+    }
+
+    /// Gets the maximum length of a string, in UTF-16 code units, that scripts are allowed to create.
+    #[inline]
+    pub fn max_string_length(&self) -> usize {
+        self.max_string_length
+    }
+
+    /// Sets the maximum length of a string, in UTF-16 code units, that scripts are allowed to create.
+    ///
+    /// Operations that would create a longer string throw a `RangeError` instead.
+    #[inline]
+    pub fn set_max_string_length(&mut self, max_string_length: usize) {
+        self.max_string_length = max_string_length;
+    }
+
+    /// Gets the maximum amount of elements that scripts are allowed to store in an array.
+    #[inline]
+    pub fn max_array_length(&self) -> usize {
+        self.max_array_length
+    }
+
+    /// Sets the maximum amount of elements that scripts are allowed to store in an array.
+    ///
+    /// Operations that would create a longer array, or make an array longer by assigning its
+    /// `length` or an index past its end, throw a `RangeError` instead.
+    #[inline]
+    pub fn set_max_array_length(&mut self, max_array_length: usize) {
+        self.max_array_length = max_array_length;
+    }
+
+    /// Gets the default locale of the locale-sensitive methods, as a BCP 47 language tag.
+    #[inline]
+    pub fn locale(&self) -> Option<&str> {
+        self.locale.as_deref()
+    }
+
+    /// Sets the default locale of the locale-sensitive methods, like `"en-US"` or `"de-DE"`.
+    ///
+    /// The locale of the host is never used, so that scripts behave the same everywhere.
+    /// Without a locale, numbers are formatted like `toString` does.
+    #[inline]
+    pub fn set_locale<L: Into<String>>(&mut self, locale: Option<L>) {
+        self.locale = locale.map(Into::into);
+    }
+
+    /// Sets the seed of `Math.random`, or removes it with `None`.
+    ///
+    /// With a seed, `Math.random` returns the same numbers every time a script is run, and with
+    /// every version of the engine. Without one, the numbers come from the generator of the
+    /// host.
+    #[inline]
+    pub fn set_random_seed(&mut self, seed: Option<u64>) {
+        self.random = seed.map(SeededRandom::new);
+    }
+
+    /// Returns the next number of `Math.random`, between `0` included and `1` excluded.
+    pub(crate) fn random(&mut self) -> f64 {
+        match self.random {
+            Some(ref mut random) => random.next_f64(),
+            None => rand::random(),
+        }
+    }
+
+    /// Pins the clock of the scripts to a time, in milliseconds since the Unix epoch, or
+    /// unpins it with `None`.
+    ///
+    /// The clock of the scripts is what `console.time` measures durations with, so a pinned
+    /// clock makes their output reproducible. It will also be the time of `Date.now`, which is
+    /// not implemented yet.
+    #[inline]
+    pub fn set_clock(&mut self, clock: Option<u64>) {
+        self.clock = clock;
+    }
+
+    /// Gets the current time of the scripts, in milliseconds since the Unix epoch.
+    ///
+    /// It is the time of the host, unless the clock is pinned with `set_clock`.
+    pub fn current_time(&self) -> u64 {
+        self.clock.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .expect("negative duration")
+                .as_millis() as u64
+        })
+    }
+
+    /// Starts recording the execution timeline.
+    ///
+    /// Does nothing if the timeline is already enabled.
+    #[inline]
+    pub fn enable_timeline(&mut self) {
+        if self.timeline.is_none() {
+            self.timeline = Some(Timeline::new());
+        }
+    }
+
+    /// Gets the execution timeline, if it is enabled.
+    #[inline]
+    pub fn timeline(&self) -> Option<&Timeline> {
+        self.timeline.as_ref()
+    }
+
+    /// Starts recording the execution profile.
+    ///
+    /// Does nothing if the profile is already enabled.
+    #[inline]
+    pub fn enable_profile(&mut self) {
+        if self.profile.is_none() {
+            self.profile = Some(ExecutionProfile::new());
+        }
+    }
+
+    /// Gets the execution profile, if it is enabled.
+    #[inline]
+    pub fn profile(&self) -> Option<&ExecutionProfile> {
+        self.profile.as_ref()
+    }
+
+    /// Starts recording the code coverage, see the `coverage` module.
+    ///
+    /// Does nothing if the coverage is already enabled.
+    #[inline]
+    pub fn enable_coverage(&mut self) {
+        if self.coverage.is_none() {
+            self.coverage = Some(Coverage::new());
+        }
+    }
+
+    /// Gets the code coverage, if it is enabled.
+    #[inline]
+    pub fn coverage(&self) -> Option<&Coverage> {
+        self.coverage.as_ref()
+    }
+
+    /// Gets a mutable reference to the code coverage, if it is enabled.
+    #[inline]
+    pub(crate) fn coverage_mut(&mut self) -> Option<&mut Coverage> {
+        self.coverage.as_mut()
+    }
+
+    /// Starts covering a script before it runs, if the coverage is enabled.
+    ///
+    /// Returns the sites to forget when the script ends, see `Coverage::forget_script`.
+    pub(crate) fn add_coverage_script(
+        &mut self,
+        statements: &StatementList,
+    ) -> Option<ScriptSites> {
+        let name = self.script_name.as_deref().unwrap_or(ANONYMOUS_SCRIPT);
+        self.coverage
+            .as_mut()
+            .map(|coverage| coverage.add_script(name, statements))
+    }
+
+    /// Starts recording the CPU profile, see the `cpu_profile` module.
+    ///
+    /// Does nothing if the CPU profile is already enabled.
+    #[inline]
+    pub fn enable_cpu_profile(&mut self) {
+        if self.cpu_profile.is_none() {
+            self.cpu_profile = Some(CpuProfile::new());
+        }
+    }
+
+    /// Gets the CPU profile, if it is enabled.
+    #[inline]
+    pub fn cpu_profile(&self) -> Option<&CpuProfile> {
+        self.cpu_profile.as_ref()
+    }
+
+    /// Records the start of a script on the call stack, and in the CPU profile if it is
+    /// enabled, which `exit_script_frame` ends.
+    pub(crate) fn enter_script_frame(&mut self) {
+        self.push_frame(None);
+        let name = self.script_name.as_deref().unwrap_or(ANONYMOUS_SCRIPT);
+        if let Some(ref mut profile) = self.cpu_profile {
+            profile.enter(CpuFrame::new(name, None));
+        }
+    }
+
+    /// Records the end of the script that `enter_script_frame` started.
+    #[inline]
+    pub(crate) fn exit_script_frame(&mut self) {
+        self.pop_frame();
+        self.exit_cpu_frame();
+    }
+
+    /// Records the end of the last script or call that started in the CPU profile, if it is
+    /// enabled.
+    #[inline]
+    pub(crate) fn exit_cpu_frame(&mut self) {
+        if let Some(ref mut profile) = self.cpu_profile {
+            profile.exit();
+        }
+    }
+
+    /// Runs `call`, a call to the function `function` defined at `position`, in a `call`
+    /// tracing span and a frame of the call stack, recording it in the CPU profile if it is
+    /// enabled.
+    fn profile_call<F>(
+        &mut self,
+        function: &Value,
+        position: Option<Position>,
+        call: F,
+    ) -> ResultValue
+    where
+        F: FnOnce(&mut Self) -> ResultValue,
+    {
+        let name = || match function.get_field("name") {
+            Value::String(ref name) if !name.is_empty() => name.to_string(),
+            _ => "(anonymous)".to_owned(),
+        };
+        // The name is only computed if the span is enabled.
+        let _span = tracing::debug_span!("call", function = %name()).entered();
+        self.push_frame(Some(function));
+        let result = if let Some(ref mut profile) = self.cpu_profile {
+            profile.enter(CpuFrame::new(name(), position));
+            let result = call(self);
+            self.exit_cpu_frame();
+            result
+        } else {
+            call(self)
+        };
+        self.pop_frame();
+        result
+    }
+
+    /// Gets the name of the scripts that are run, `<anonymous>` if it was not set.
+    #[inline]
+    pub fn script_name(&self) -> &str {
+        self.script_name.as_deref().unwrap_or(ANONYMOUS_SCRIPT)
+    }
+
+    /// Sets the name of the scripts that are run next, like the path of their file.
+    ///
+    /// The code coverage and the stack traces of errors report the scripts under their name.
+    #[inline]
+    pub fn set_script_name<N: Into<String>>(&mut self, name: N) {
+        self.script_name = Some(name.into());
+    }
+
+    /// Sets the source map of the script named `name`, so that the stack traces of errors show
+    /// the positions of its code in the original sources, see the `source_map` module.
+    #[inline]
+    pub fn set_source_map<N: Into<String>>(&mut self, name: N, map: SourceMap) {
+        self.source_maps.insert(name.into(), map);
+    }
+
+    /// Gets the source map of the script named `name`, if it was set.
+    #[inline]
+    pub fn source_map(&self, name: &str) -> Option<&SourceMap> {
+        self.source_maps.get(name)
+    }
+
+    /// Starts measuring the time spent lexing, parsing and running scripts.
+    ///
+    /// Does nothing if the timings are already enabled.
+    #[inline]
+    pub fn enable_timings(&mut self) {
+        if self.timings.is_none() {
+            self.timings = Some(PhaseTimings::new());
+        }
+    }
+
+    /// Takes the time spent in each phase since the timings were last taken, if they are
+    /// enabled.
+    ///
+    /// The timings stay enabled, and start again from zero.
+    #[inline]
+    pub fn take_timings(&mut self) -> Option<PhaseTimings> {
+        self.timings.as_mut().map(mem::take)
+    }
+
+    /// Checks whether the syntax tree of scripts is optimized, see `set_optimize`.
+    #[inline]
+    pub fn optimize(&self) -> bool {
+        self.optimize
+    }
+
+    /// Sets whether the syntax tree of scripts is simplified by `syntax::optimizer::optimize`
+    /// before they run, which folds the operations on literals and removes the branches that
+    /// can never run.
+    #[inline]
+    pub fn set_optimize(&mut self, enabled: bool) {
+        self.optimize = enabled;
+    }
+
+    /// Checks whether function bodies are only parsed when they are first called, see
+    /// `set_lazy_parsing`.
+    #[inline]
+    pub fn lazy_parsing(&self) -> bool {
+        self.lazy_parsing
+    }
+
+    /// Sets whether the parsing of function bodies is deferred until the functions are first
+    /// called, which makes large scripts that only call a few of their functions start faster.
+    ///
+    /// The parser then only checks that the braces of each function body are balanced, so a
+    /// syntax error in a function body is only found when the function is first called. It
+    /// then terminates the script, which cannot catch it, and is reported as a
+    /// `JsError::Syntax` at its position in the body. Deferred bodies are not optimized by
+    /// `set_optimize`.
+    #[inline]
+    pub fn set_lazy_parsing(&mut self, enabled: bool) {
+        self.lazy_parsing = enabled;
+    }
+
+    /// Checks whether scripts are run as strict mode code, see `set_strict`.
+    #[inline]
+    pub fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Sets whether every script is run as strict mode code, as if it began with a
+    /// `"use strict"` directive.
+    ///
+    /// Only two rules of strict mode are enforced: legacy octal literals, like `010`, and
+    /// decimal literals with a leading zero are syntax errors, and assigning to a variable that
+    /// was not declared throws a `ReferenceError` instead of creating a global. The other rules,
+    /// such as the errors on duplicate parameter names and on deleting unqualified names, are
+    /// not.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-strict-mode-of-ecmascript
+    #[inline]
+    pub fn set_strict(&mut self, enabled: bool) {
+        self.strict = enabled;
+    }
+
+    /// Gets the experimental features that are enabled.
+    #[inline]
+    pub fn experimental(&self) -> Experimental {
+        self.experimental
+    }
+
+    /// Enables the given experimental features, and disables the others, see the `experimental`
+    /// module.
+    ///
+    /// The syntax features apply to the scripts that are parsed afterwards, and the builtins
+    /// are installed on, or removed from, every realm of the interpreter.
+    pub fn set_experimental(&mut self, experimental: Experimental) {
+        self.experimental = experimental;
+        experimental::install_builtins(&self.realm, experimental);
+        for realm in self.realms.iter().flatten() {
+            experimental::install_builtins(realm, experimental);
+        }
+    }
+
+    /// Checks if the web legacy compatibility features of Annex B are enabled.
+    #[inline]
+    pub fn annex_b(&self) -> bool {
+        self.annex_b
+    }
+
+    /// Enables or disables the web legacy compatibility features of Annex B, see the `annex_b`
+    /// module. They are enabled by default.
+    ///
+    /// HTML-like comments apply to the scripts that are parsed afterwards, and the builtins
+    /// are installed on, or removed from, every realm of the interpreter.
+    pub fn set_annex_b(&mut self, enabled: bool) {
+        self.annex_b = enabled;
+        annex_b::install_builtins(&self.realm, enabled);
+        for realm in self.realms.iter().flatten() {
+            annex_b::install_builtins(realm, enabled);
+        }
+    }
+
+    /// Gets the start time of a timeline event, or `None` if the timeline is disabled.
+    ///
+    /// The clock is only read when the timeline is enabled, since it is not available on every
+    /// target, like `wasm32-unknown-unknown`.
+    #[inline]
+    pub(crate) fn timeline_start(&self) -> Option<Instant> {
+        self.timeline.as_ref().map(|_| Instant::now())
+    }
+
+    /// Records an event that started at `start` and ends now, if the timeline is enabled.
+    #[inline]
+    pub(crate) fn record_timeline_event(
+        &mut self,
+        name: &str,
+        category: &'static str,
+        start: Option<Instant>,
+    ) {
+        if let (Some(ref mut timeline), Some(start)) = (&mut self.timeline, start) {
+            timeline.record(name, category, start);
+        }
+    }
+
+    /// Gets the start time of a phase, or `None` if the timings are disabled.
+    #[inline]
+    pub(crate) fn phase_start(&self) -> Option<Instant> {
+        self.timings.as_ref().map(|_| Instant::now())
+    }
+
+    /// Records a phase that started at `start` and ends now, if the timings are enabled, and
+    /// returns the start time of the next phase.
+    #[inline]
+    pub(crate) fn record_phase(&mut self, phase: Phase, start: Option<Instant>) -> Option<Instant> {
+        if let (Some(ref mut timings), Some(start)) = (&mut self.timings, start) {
+            let now = Instant::now();
+            timings.add(phase, now - start);
+            return Some(now);
+        }
+        None
+    }
+
+    /// Throws a `RangeError` if a string of `length` UTF-16 code units exceeds the maximum
+    /// string length.
+    pub(crate) fn check_string_length(&mut self, length: f64) -> Result<(), Value> {
+        if length > self.max_string_length as f64 {
+            return Err(self.construct_range_error("invalid string length"));
+        }
+        Ok(())
+    }
+
+    /// Throws a `RangeError` if `string` exceeds the maximum string length.
+    ///
+    /// A string never has more code units than bytes, so they are only counted when it has more
+    /// bytes than the maximum.
+    pub(crate) fn check_str_length(&mut self, string: &str) -> Result<(), Value> {
+        if string.len() <= self.max_string_length {
+            return Ok(());
+        }
+        self.check_string_length(string.encode_utf16().count() as f64)
+    }
+
+    /// Throws a `RangeError` if an array of `length` elements exceeds the maximum array length.
+    pub(crate) fn check_array_length(&mut self, length: f64) -> Result<(), Value> {
+        if length > self.max_array_length as f64 {
+            return Err(self.construct_range_error("invalid array length"));
+        }
+        Ok(())
+    }
+
+    /// Sets the innermost binding of `name`, and throws a `TypeError` if strict mode code sets
+    /// a binding that can not be changed, like a non-writable property of the global object.
+    pub(crate) fn set_mutable_binding(&mut self, name: &str, value: Value) -> Result<(), Value> {
+        let strict = self.strict;
+        self.realm
+            .environment
+            .set_mutable_binding(name, value, strict)
+            .map_err(|error| self.construct_type_error(error.to_string()))
+    }
+
+    /// Converts a string built by a builtin to a value, throwing a `RangeError` if it exceeds
+    /// the maximum string length.
+    pub(crate) fn new_string(&mut self, string: String) -> ResultValue {
+        self.check_str_length(&string)?;
+        Ok(Value::from(string))
+    }
+
+    /// Restores the interpreter to a state where it can run new code after an evaluation was
+    /// aborted midway, for example by a panic that was caught by the embedder.
+    ///
+    /// Every environment above the global one is discarded, the bindings and objects defined in
+    /// the global scope are kept.
+    pub fn reset_execution_state(&mut self) {
+        self.state = InterpreterState::Executing;
+        while self.realm.environment.environments().count() > 1 {
+            self.realm.environment.pop();
         }
     }
 
@@ -101,7 +772,8 @@ impl Interpreter {
 
     /// Generates a new `Symbol` internal hash.
     ///
-    /// This currently is an incremented value.
+    /// This currently is an incremented value, the hashes below `WellKnownSymbol::ALL.len()` are
+    /// reserved for the well-known symbols.
     #[inline]
     pub(crate) fn generate_hash(&mut self) -> u32 {
         let hash = self.symbol_count;
@@ -120,15 +792,9 @@ impl Interpreter {
     ) -> Value
     where
         P: Into<Box<[FormalParameter]>>,
-        B: Into<StatementList>,
+        B: Into<LazyStatementList>,
     {
-        let function_prototype = self
-            .realm
-            .environment
-            .get_global_object()
-            .expect("Could not get the global object")
-            .get_field("Function")
-            .get_field(PROTOTYPE);
+        let function_prototype = self.realm.function_prototype();
 
         // Every new function has a prototype property pre-made
         let global_val = &self
@@ -158,18 +824,19 @@ impl Interpreter {
         val
     }
 
+    /// Calls a function with the given `this` value and arguments.
+    ///
+    /// Returns a `TypeError` if `f` is not a function.
+    ///
     /// <https://tc39.es/ecma262/#sec-call>
-    pub(crate) fn call(
-        &mut self,
-        f: &Value,
-        this: &Value,
-        arguments_list: &[Value],
-    ) -> ResultValue {
+    pub fn call(&mut self, f: &Value, this: &Value, arguments_list: &[Value]) -> ResultValue {
         match *f {
             Value::Object(ref obj) => {
                 let obj = obj.borrow();
                 if let ObjectData::Function(ref func) = obj.data {
-                    return func.call(f.clone(), this, arguments_list, self);
+                    return self.profile_call(f, func.position(), |interpreter| {
+                        func.call(f.clone(), this, arguments_list, interpreter)
+                    });
                 }
                 self.throw_type_error("not a function")
             }
@@ -177,18 +844,45 @@ impl Interpreter {
         }
     }
 
+    /// Creates a new object with the given constructor, like the `new` operator does.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-construct
+    pub(crate) fn construct(
+        &mut self,
+        constructor: &Value,
+        arguments_list: &[Value],
+    ) -> ResultValue {
+        if let Value::Object(ref obj) = *constructor {
+            let obj = obj.borrow();
+            if let ObjectData::Function(ref func) = obj.data {
+                // Create a blank object, then set its __proto__ property to the [Constructor].prototype
+                let this = Value::new_object(None);
+                this.as_object_mut()
+                    .expect("this was not an object")
+                    .set_prototype(constructor.get_field(PROTOTYPE));
+                return self.profile_call(constructor, func.position(), |interpreter| {
+                    func.construct(constructor.clone(), &this, arguments_list, interpreter)
+                });
+            }
+        }
+        self.throw_type_error("not a constructor")
+    }
+
     /// Converts a value into a rust heap allocated string.
     #[allow(clippy::wrong_self_convention)]
-    pub fn to_string(&mut self, value: &Value) -> Result<RcString, Value> {
+    pub fn to_string(&mut self, value: &Value) -> Result<JsString, Value> {
         match value {
-            Value::Null => Ok(RcString::from("null")),
-            Value::Undefined => Ok(RcString::from("undefined".to_owned())),
-            Value::Boolean(boolean) => Ok(RcString::from(boolean.to_string())),
-            Value::Rational(rational) => Ok(RcString::from(Number::to_native_string(*rational))),
-            Value::Integer(integer) => Ok(RcString::from(integer.to_string())),
+            Value::Null => Ok(JsString::from("null")),
+            Value::Undefined => Ok(JsString::from("undefined".to_owned())),
+            Value::Boolean(boolean) => Ok(JsString::from(boolean.to_string())),
+            Value::Rational(rational) => Ok(JsString::from(Number::to_native_string(*rational))),
+            Value::Integer(integer) => Ok(JsString::from(integer.to_string())),
             Value::String(string) => Ok(string.clone()),
             Value::Symbol(_) => Err(self.construct_type_error("can't convert symbol to string")),
-            Value::BigInt(ref bigint) => Ok(RcString::from(bigint.to_string())),
+            Value::BigInt(ref bigint) => Ok(JsString::from(bigint.to_string())),
             Value::Object(_) => {
                 let primitive = self.to_primitive(value, PreferredType::String)?;
                 self.to_string(&primitive)
@@ -372,9 +1066,7 @@ impl Interpreter {
             // Check if object is array
             if let ObjectData::Array = x.deref().borrow().data {
                 let length = i32::from(&value.get_field("length"));
-                let values = (0..length)
-                    .map(|idx| value.get_field(idx.to_string()))
-                    .collect();
+                let values = (0..length).map(|idx| value.get_field(idx)).collect();
                 return Ok(values);
             }
             // Check if object is a Map
@@ -468,16 +1160,37 @@ impl Interpreter {
         // 1. Assert: input is an ECMAScript language value. (always a value not need to check)
         // 2. If Type(input) is Object, then
         if let Value::Object(_) = input {
-            let mut hint = preferred_type;
+            // d. Let exoticToPrim be ? GetMethod(input, @@toPrimitive).
+            let exotic_to_prim = self.get_field(input, WellKnownSymbol::ToPrimitive.key())?;
+            // e. If exoticToPrim is not undefined, then
+            if !exotic_to_prim.is_null_or_undefined() {
+                if !exotic_to_prim.is_function() {
+                    return self.throw_type_error("Symbol.toPrimitive is not a function");
+                }
+                // i-iii. Let hint be "default", "string" or "number".
+                let hint = match preferred_type {
+                    PreferredType::Default => "default",
+                    PreferredType::String => "string",
+                    PreferredType::Number => "number",
+                };
+                // iv. Let result be ? Call(exoticToPrim, input, « hint »).
+                let result = self.call(&exotic_to_prim, input, &[hint.into()])?;
+                // v. If Type(result) is not Object, return result.
+                if result.is_object() {
+                    // vi. Throw a TypeError exception.
+                    return self.throw_type_error("cannot convert object to primitive value");
+                }
+                return Ok(result);
+            }
 
-            // Skip d, e we don't support Symbols yet
-            // TODO: add when symbols are supported
-            // TODO: Add other steps.
-            if hint == PreferredType::Default {
-                hint = PreferredType::Number;
+            // f. If preferredType is not present, let preferredType be number.
+            let hint = if preferred_type == PreferredType::Default {
+                PreferredType::Number
+            } else {
+                preferred_type
             };
 
-            // g. Return ? OrdinaryToPrimitive(input, hint).
+            // g. Return ? OrdinaryToPrimitive(input, preferredType).
             self.ordinary_to_primitive(input, hint)
         } else {
             // 3. Return input.
@@ -489,23 +1202,174 @@ impl Interpreter {
     ///
     /// https://tc39.es/ecma262/#sec-topropertykey
     #[allow(clippy::wrong_self_convention)]
-    pub(crate) fn to_property_key(&mut self, value: &Value) -> ResultValue {
-        let key = self.to_primitive(value, PreferredType::String)?;
-        if key.is_symbol() {
-            Ok(key)
-        } else {
-            self.to_string(&key).map(Value::from)
+    pub(crate) fn to_property_key(&mut self, value: &Value) -> Result<PropertyKey, Value> {
+        let key = match value {
+            Value::String(_) | Value::Symbol(_) | Value::Integer(_) => return Ok(value.into()),
+            _ => self.to_primitive(value, PreferredType::String)?,
+        };
+        match key {
+            Value::Symbol(ref symbol) => Ok(PropertyKey::Symbol(symbol.clone())),
+            key => self.to_string(&key).map(PropertyKey::from),
         }
     }
 
+    /// Gets the value of a property of an object, calling its getter if it is an accessor property.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-get-p-receiver
+    pub fn get_field<K>(&mut self, obj: &Value, key: K) -> ResultValue
+    where
+        K: Into<PropertyKey>,
+    {
+        let key = key.into();
+        let value = match obj.get_property(key.clone()) {
+            Some(ref property) if property.is_accessor_descriptor() => match property.get {
+                Some(ref getter) if getter.is_function() => self.call(getter, obj, &[])?,
+                _ => Value::undefined(),
+            },
+            _ => obj.get_field(key.clone()),
+        };
+        self.notify_watch(WatchAccess::Read, obj, &key, &value);
+        Ok(value)
+    }
+
+    /// Sets the value of a property of an object, calling its setter if it is an accessor property.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-set-p-v-receiver
+    pub(crate) fn set_field<K>(&mut self, obj: &Value, key: K, value: Value) -> ResultValue
+    where
+        K: Into<PropertyKey>,
+    {
+        let key = key.into();
+        self.notify_watch(WatchAccess::Write, obj, &key, &value);
+        // Writing past the end of an array or to its `length` changes the length.
+        let length = match obj.as_object() {
+            Some(object) if object.is_array() => match key {
+                PropertyKey::Index(index) => Some(f64::from(index) + 1.0),
+                PropertyKey::String(ref name) if name == "length" && !value.is_bigint() => {
+                    Some(value.to_number())
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(length) = length {
+            self.check_array_length(length)?;
+        }
+        if let Some(property) = obj.get_property(key.clone()) {
+            if property.is_accessor_descriptor() {
+                if let Some(ref setter) = property.set {
+                    if setter.is_function() {
+                        self.call(setter, obj, std::slice::from_ref(&value))?;
+                    }
+                }
+                return Ok(value);
+            }
+        }
+        Ok(obj.set_field(key, value))
+    }
+
+    /// Converts an object into a property descriptor.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-topropertydescriptor
+    #[allow(clippy::wrong_self_convention)]
+    pub(crate) fn to_property_descriptor(&mut self, value: &Value) -> Result<Property, Value> {
+        // 1. If Type(Obj) is not Object, throw a TypeError exception.
+        if !value.is_object() {
+            return Err(self.construct_type_error("property descriptor must be an object"));
+        }
+
+        let mut desc = Property::empty();
+        if value.has_field("enumerable") {
+            let enumerable = self.get_field(value, "enumerable")?;
+            desc.attribute.set_enumerable(enumerable.to_boolean());
+        }
+        if value.has_field("configurable") {
+            let configurable = self.get_field(value, "configurable")?;
+            desc.attribute.set_configurable(configurable.to_boolean());
+        }
+        if value.has_field("value") {
+            desc.value = Some(self.get_field(value, "value")?);
+        }
+        if value.has_field("writable") {
+            let writable = self.get_field(value, "writable")?;
+            desc.attribute.set_writable(writable.to_boolean());
+        }
+        if value.has_field("get") {
+            let getter = self.get_field(value, "get")?;
+            if !getter.is_function() && !getter.is_undefined() {
+                return Err(self.construct_type_error("property getter must be a function"));
+            }
+            desc.get = Some(getter);
+        }
+        if value.has_field("set") {
+            let setter = self.get_field(value, "set")?;
+            if !setter.is_function() && !setter.is_undefined() {
+                return Err(self.construct_type_error("property setter must be a function"));
+            }
+            desc.set = Some(setter);
+        }
+
+        // 15. If desc.[[Get]] or desc.[[Set]] is present, then
+        //     a. If desc.[[Value]] or desc.[[Writable]] is present, throw a TypeError exception.
+        if desc.is_accessor_descriptor() && desc.is_data_descriptor() {
+            return Err(self.construct_type_error(
+                "property descriptors must not specify a value or be writable when a getter or setter has been specified",
+            ));
+        }
+
+        Ok(desc)
+    }
+
+    /// Converts a property descriptor into an object.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-frompropertydescriptor
+    #[allow(clippy::wrong_self_convention)]
+    pub(crate) fn from_property_descriptor(&self, desc: &Property) -> Value {
+        let global = self
+            .realm
+            .environment
+            .get_global_object()
+            .expect("Could not get the global object");
+        let obj = Value::new_object(Some(&global));
+
+        if let Some(ref value) = desc.value {
+            obj.set_field("value", value.clone());
+        }
+        if desc.attribute.has_writable() {
+            obj.set_field("writable", desc.attribute.writable());
+        }
+        if let Some(ref get) = desc.get {
+            obj.set_field("get", get.clone());
+        }
+        if let Some(ref set) = desc.set {
+            obj.set_field("set", set.clone());
+        }
+        if desc.attribute.has_enumerable() {
+            obj.set_field("enumerable", desc.attribute.enumerable());
+        }
+        if desc.attribute.has_configurable() {
+            obj.set_field("configurable", desc.attribute.configurable());
+        }
+
+        obj
+    }
+
     /// https://tc39.es/ecma262/#sec-hasproperty
-    pub(crate) fn has_property(&self, obj: &Value, key: &Value) -> bool {
+    pub(crate) fn has_property(&self, obj: &Value, key: &PropertyKey) -> bool {
         if let Some(obj) = obj.as_object() {
-            if !Property::is_property_key(key) {
-                false
-            } else {
-                obj.has_property(key)
-            }
+            obj.has_property(key)
         } else {
             false
         }
@@ -565,10 +1429,10 @@ impl Interpreter {
                     .expect("String was not initialized")
                     .get_field(PROTOTYPE);
 
-                Ok(Value::new_object_from_prototype(
-                    proto,
-                    ObjectData::String(string.clone()),
-                ))
+                let object =
+                    Value::new_object_from_prototype(proto, ObjectData::String(string.clone()));
+                object.set_field("length", Value::from(string.len_utf16()));
+                Ok(object)
             }
             Value::Symbol(ref symbol) => {
                 let proto = self
@@ -601,19 +1465,19 @@ impl Interpreter {
     fn set_value(&mut self, node: &Node, value: Value) -> ResultValue {
         match node {
             Node::Identifier(ref name) => {
-                self.realm
-                    .environment
-                    .set_mutable_binding(name.as_ref(), value.clone(), true);
+                self.set_mutable_binding(name.as_ref(), value.clone())?;
                 Ok(value)
             }
-            Node::GetConstField(ref get_const_field_node) => Ok(get_const_field_node
-                .obj()
-                .run(self)?
-                .set_field(get_const_field_node.field(), value)),
-            Node::GetField(ref get_field) => Ok(get_field
-                .obj()
-                .run(self)?
-                .set_field(get_field.field().run(self)?, value)),
+            Node::GetConstField(ref get_const_field_node) => {
+                let obj = get_const_field_node.obj().run(self)?;
+                self.set_field(&obj, get_const_field_node.field(), value)
+            }
+            Node::GetField(ref get_field) => {
+                let obj = get_field.obj().run(self)?;
+                let field = get_field.field().run(self)?;
+                let field = self.to_property_key(&field)?;
+                self.set_field(&obj, field, value)
+            }
             _ => panic!("TypeError: invalid assignment to {}", node),
         }
     }
@@ -628,6 +1492,12 @@ impl Interpreter {
         &self.state
     }
 
+    /// Takes the current state, leaving `InterpreterState::Executing` in its place.
+    #[inline]
+    pub(crate) fn take_current_state(&mut self) -> InterpreterState {
+        mem::replace(&mut self.state, InterpreterState::Executing)
+    }
+
     /// Check if the `Value` can be converted to an `Object`
     ///
     /// The abstract operation `RequireObjectCoercible` takes argument argument.
@@ -657,55 +1527,202 @@ impl Interpreter {
     pub(crate) fn console_mut(&mut self) -> &mut Console {
         &mut self.console
     }
+
+    /// Sets the writer that the `console` object prints its messages to.
+    ///
+    /// By default messages are printed to the standard output and the standard error.
+    #[inline]
+    pub fn set_console_writer<W>(&mut self, writer: W)
+    where
+        W: ConsoleWriter + 'static,
+    {
+        self.console.set_writer(Box::new(writer));
+    }
+
+    /// Gets the allocation counts of the sites of the scripts run so far.
+    ///
+    /// They are only recorded when the `allocation-sites` feature is enabled.
+    #[inline]
+    pub fn allocation_sites(&self) -> &AllocationSites {
+        &self.allocation_sites
+    }
+
+    /// Gets the allocation counts of the sites as a mutable reference, to clear them.
+    #[inline]
+    pub fn allocation_sites_mut(&mut self) -> &mut AllocationSites {
+        &mut self.allocation_sites
+    }
+
+    /// Queues a microtask that calls `function` with `args`.
+    pub(crate) fn enqueue_job(&mut self, function: Value, args: Vec<Value>) {
+        self.jobs.push_back(Job { function, args });
+    }
+
+    /// Checks if there are microtasks waiting to run.
+    #[inline]
+    pub fn has_pending_jobs(&self) -> bool {
+        !self.jobs.is_empty()
+    }
+
+    /// Runs the queued microtasks until the queue is empty, including the microtasks that they
+    /// queue themselves.
+    ///
+    /// A microtask that throws does not stop the others, the first thrown value is returned once
+    /// the queue is empty.
+    pub fn run_jobs(&mut self) -> Result<(), Value> {
+        let _memory = self.count_memory();
+        let mut result = Ok(());
+        while let Some(job) = self.jobs.pop_front() {
+            if self.is_terminated() {
+                self.jobs.clear();
+                break;
+            }
+            if let Err(error) = self.call(&job.function, &Value::undefined(), &job.args) {
+                if result.is_ok() {
+                    result = Err(error);
+                }
+            }
+        }
+        result
+    }
+
+    /// Drops every pending microtask without running it.
+    #[inline]
+    pub fn clear_jobs(&mut self) {
+        self.jobs.clear();
+    }
+
+    /// Gets the debugging state of the interpreter.
+    #[inline]
+    pub fn debugger(&self) -> &Debugger {
+        &self.debugger
+    }
+
+    /// Gets the debugging state of the interpreter as a mutable reference, to set watchpoints.
+    #[inline]
+    pub fn debugger_mut(&mut self) -> &mut Debugger {
+        &mut self.debugger
+    }
+
+    /// Runs a full garbage collection now.
+    ///
+    /// Hosts with latency constraints can call this when they are idle, for example between
+    /// frames, so that the collector is less likely to pause a later script. The `gc` crate does
+    /// not expose its collection threshold or collect incrementally, so this is the only
+    /// scheduling control available.
+    #[inline]
+    pub fn collect_garbage(&mut self) {
+        let start = self.timeline_start();
+        let _span = tracing::debug_span!("collect garbage").entered();
+        gc::force_collect();
+        self.record_timeline_event("collect garbage", "gc", start);
+    }
+
+    /// Registers a hook that is called when the interpreter is dropped.
+    ///
+    /// Hooks run in registration order, before the realm is released, so they can still
+    /// inspect the global object, for example to flush state held by the embedder.
+    #[inline]
+    pub fn on_shutdown<F>(&mut self, hook: F)
+    where
+        F: FnOnce(&mut Interpreter) + 'static,
+    {
+        self.shutdown_hooks.0.push(Box::new(hook));
+    }
+}
+
+impl Drop for Interpreter {
+    fn drop(&mut self) {
+        // Hooks may register other hooks, which run in the same way.
+        while !self.shutdown_hooks.0.is_empty() {
+            for hook in mem::take(&mut self.shutdown_hooks.0) {
+                hook(self);
+            }
+        }
+
+        // Pending jobs may be the last references to some objects.
+        self.clear_jobs();
+        self.host_futures.clear();
+
+        // SAFETY: the realm is never accessed again after this point.
+        unsafe { ManuallyDrop::drop(&mut self.realm) };
+        self.realms.clear();
+        self.wrapped_functions.clear();
+
+        // Objects that are only reachable through cycles are not released when their last
+        // `Gc` pointer is dropped, collect them now so their finalizers run.
+        gc::force_collect();
+    }
 }
 
 impl Executable for Node {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
         let _timer = BoaProfiler::global().start_event("Executable", "exec");
-        match *self {
-            Node::Const(Const::Null) => Ok(Value::null()),
-            Node::Const(Const::Num(num)) => Ok(Value::rational(num)),
-            Node::Const(Const::Int(num)) => Ok(Value::integer(num)),
-            Node::Const(Const::BigInt(ref num)) => Ok(Value::from(num.clone())),
-            // we can't move String from Const into value, because const is a garbage collected value
-            // Which means Drop() get's called on Const, but str will be gone at that point.
-            // Do Const values need to be garbage collected? We no longer need them once we've generated Values
-            Node::Const(Const::String(ref value)) => Ok(Value::string(value.to_string())),
-            Node::Const(Const::Bool(value)) => Ok(Value::boolean(value)),
-            Node::Block(ref block) => block.run(interpreter),
-            Node::Identifier(ref identifier) => identifier.run(interpreter),
-            Node::GetConstField(ref get_const_field_node) => get_const_field_node.run(interpreter),
-            Node::GetField(ref get_field) => get_field.run(interpreter),
-            Node::Call(ref call) => call.run(interpreter),
-            Node::WhileLoop(ref while_loop) => while_loop.run(interpreter),
-            Node::DoWhileLoop(ref do_while) => do_while.run(interpreter),
-            Node::ForLoop(ref for_loop) => for_loop.run(interpreter),
-            Node::If(ref if_smt) => if_smt.run(interpreter),
-            Node::Switch(ref switch) => switch.run(interpreter),
-            Node::Object(ref obj) => obj.run(interpreter),
-            Node::ArrayDecl(ref arr) => arr.run(interpreter),
-            // <https://tc39.es/ecma262/#sec-createdynamicfunction>
-            Node::FunctionDecl(ref decl) => decl.run(interpreter),
-            // <https://tc39.es/ecma262/#sec-createdynamicfunction>
-            Node::FunctionExpr(ref function_expr) => function_expr.run(interpreter),
-            Node::ArrowFunctionDecl(ref decl) => decl.run(interpreter),
-            Node::BinOp(ref op) => op.run(interpreter),
-            Node::UnaryOp(ref op) => op.run(interpreter),
-            Node::New(ref call) => call.run(interpreter),
-            Node::Return(ref ret) => ret.run(interpreter),
-            Node::Throw(ref throw) => throw.run(interpreter),
-            Node::Assign(ref op) => op.run(interpreter),
-            Node::VarDeclList(ref decl) => decl.run(interpreter),
-            Node::LetDeclList(ref decl) => decl.run(interpreter),
-            Node::ConstDeclList(ref decl) => decl.run(interpreter),
-            Node::Spread(ref spread) => spread.run(interpreter),
-            Node::This => {
-                // Will either return `this` binding or undefined
-                Ok(interpreter.realm().environment.get_this_binding())
-            }
-            Node::Try(ref try_node) => try_node.run(interpreter),
-            Node::Break(ref break_node) => break_node.run(interpreter),
-            ref i => unimplemented!("{:?}", i),
+        let start = match interpreter.profile {
+            Some(ref mut profile) => profile.enter(),
+            None => return run_node(self, interpreter),
+        };
+        let result = run_node(self, interpreter);
+        if let Some(ref mut profile) = interpreter.profile {
+            profile.exit(self.kind(), start);
+        }
+        result
+    }
+}
+
+/// Evaluates a node.
+fn run_node(node: &Node, interpreter: &mut Interpreter) -> ResultValue {
+    match *node {
+        Node::Const(Const::Null) => Ok(Value::null()),
+        Node::Const(Const::Undefined) => Ok(Value::undefined()),
+        Node::Const(Const::Num(num)) => Ok(Value::rational(num)),
+        Node::Const(Const::Int(num)) => Ok(Value::integer(num)),
+        Node::Const(Const::BigInt(ref num)) => Ok(Value::from(num.clone())),
+        // we can't move String from Const into value, because const is a garbage collected value
+        // Which means Drop() get's called on Const, but str will be gone at that point.
+        // Do Const values need to be garbage collected? We no longer need them once we've generated Values
+        Node::Const(Const::String(ref value)) => Ok(Value::string(value.clone())),
+        Node::Const(Const::Bool(value)) => Ok(Value::boolean(value)),
+        Node::Block(ref block) => block.run(interpreter),
+        Node::Identifier(ref identifier) => identifier.run(interpreter),
+        Node::GetConstField(ref get_const_field_node) => get_const_field_node.run(interpreter),
+        Node::GetField(ref get_field) => get_field.run(interpreter),
+        Node::Call(ref call) => call.run(interpreter),
+        Node::WhileLoop(ref while_loop) => while_loop.run(interpreter),
+        Node::DoWhileLoop(ref do_while) => do_while.run(interpreter),
+        Node::ForLoop(ref for_loop) => for_loop.run(interpreter),
+        Node::If(ref if_smt) => if_smt.run(interpreter),
+        Node::Switch(ref switch) => switch.run(interpreter),
+        Node::Object(ref obj) => obj.run(interpreter),
+        Node::ArrayDecl(ref arr) => arr.run(interpreter),
+        // <https://tc39.es/ecma262/#sec-createdynamicfunction>
+        Node::FunctionDecl(ref decl) => decl.run(interpreter),
+        // <https://tc39.es/ecma262/#sec-createdynamicfunction>
+        Node::FunctionExpr(ref function_expr) => function_expr.run(interpreter),
+        Node::ArrowFunctionDecl(ref decl) => decl.run(interpreter),
+        Node::BinOp(ref op) => op.run(interpreter),
+        Node::UnaryOp(ref op) => op.run(interpreter),
+        Node::New(ref call) => call.run(interpreter),
+        Node::Return(ref ret) => ret.run(interpreter),
+        Node::Throw(ref throw) => throw.run(interpreter),
+        Node::Assign(ref op) => op.run(interpreter),
+        Node::VarDeclList(ref decl) => decl.run(interpreter),
+        Node::LetDeclList(ref decl) => decl.run(interpreter),
+        Node::ConstDeclList(ref decl) => decl.run(interpreter),
+        Node::Spread(ref spread) => spread.run(interpreter),
+        Node::This => {
+            // Will either return `this` binding or undefined
+            Ok(interpreter.realm().environment.get_this_binding())
+        }
+        Node::Try(ref try_node) => try_node.run(interpreter),
+        Node::Break(ref break_node) => break_node.run(interpreter),
+        Node::Debugger => {
+            interpreter.pause(&PauseEvent {
+                reason: PauseReason::DebuggerStatement,
+                statement: None,
+            });
+            Ok(Value::undefined())
         }
+        ref i => unimplemented!("{:?}", i),
     }
 }
@@ -1,12 +1,5 @@
 use super::{Executable, Interpreter};
-use crate::{
-    builtins::{
-        object::{ObjectData, PROTOTYPE},
-        value::{ResultValue, Value},
-    },
-    syntax::ast::node::New,
-    BoaProfiler,
-};
+use crate::{builtins::value::ResultValue, syntax::ast::node::New, BoaProfiler};
 
 impl Executable for New {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
@@ -21,21 +14,7 @@ impl Executable for New {
         for arg in self.args() {
             v_args.push(arg.run(interpreter)?);
         }
-        let this = Value::new_object(None);
-        // Create a blank object, then set its __proto__ property to the [Constructor].prototype
-        this.as_object_mut()
-            .expect("this was not an object")
-            .set_prototype(func_object.get_field(PROTOTYPE));
-
-        match func_object {
-            Value::Object(ref obj) => {
-                let obj = obj.borrow();
-                if let ObjectData::Function(ref func) = obj.data {
-                    return func.construct(func_object.clone(), &this, &v_args, interpreter);
-                }
-                interpreter.throw_type_error("not a constructor")
-            }
-            _ => Ok(Value::undefined()),
-        }
+        interpreter.allocation_sites_mut().record("new", self);
+        interpreter.construct(&func_object, &v_args)
     }
 }
@@ -17,18 +17,21 @@ impl Executable for Object {
             .get_global_object()
             .expect("Could not get the global object");
         let obj = Value::new_object(Some(global_val));
+        interpreter
+            .allocation_sites_mut()
+            .record("object literal", self);
 
         // TODO: Implement the rest of the property types.
         for property in self.properties().iter() {
             match property {
                 PropertyDefinition::Property(key, value) => {
                     obj.borrow()
-                        .set_field(&key.clone(), value.run(interpreter)?);
+                        .set_field(key.as_ref(), value.run(interpreter)?);
                 }
                 PropertyDefinition::MethodDefinition(kind, name, func) => {
                     if let MethodDefinitionKind::Ordinary = kind {
                         obj.borrow()
-                            .set_field(&name.clone(), func.run(interpreter)?);
+                            .set_field(name.as_ref(), func.run(interpreter)?);
                     } else {
                         // TODO: Implement other types of MethodDefinitionKinds.
                         unimplemented!("other types of property method definitions.");
@@ -4,7 +4,10 @@ mod tests;
 
 use super::{Executable, Interpreter};
 use crate::{
-    builtins::value::{ResultValue, Value},
+    builtins::{
+        property::PropertyKey,
+        value::{ResultValue, Value},
+    },
     environment::lexical_environment::VariableScope,
     syntax::ast::{
         node::{Assign, BinOp, Node, UnaryOp},
@@ -12,6 +15,7 @@ use crate::{
     },
     BoaProfiler,
 };
+use std::cmp::Ordering;
 
 impl Executable for Assign {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
@@ -19,11 +23,14 @@ impl Executable for Assign {
         let val = self.rhs().run(interpreter)?;
         match self.lhs() {
             Node::Identifier(ref name) => {
+                let strict = interpreter.strict();
                 let environment = &mut interpreter.realm_mut().environment;
 
                 if environment.has_binding(name.as_ref()) {
                     // Binding already exists
-                    environment.set_mutable_binding(name.as_ref(), val.clone(), true);
+                    interpreter.set_mutable_binding(name.as_ref(), val.clone())?;
+                } else if strict {
+                    return Err(interpreter.construct_reference_error(name.as_ref()));
                 } else {
                     environment.create_mutable_binding(
                         name.as_ref().to_owned(),
@@ -35,12 +42,13 @@ impl Executable for Assign {
             }
             Node::GetConstField(ref get_const_field) => {
                 let val_obj = get_const_field.obj().run(interpreter)?;
-                val_obj.set_field(get_const_field.field(), val.clone());
+                interpreter.set_field(&val_obj, get_const_field.field(), val.clone())?;
             }
             Node::GetField(ref get_field) => {
                 let val_obj = get_field.obj().run(interpreter)?;
                 let val_field = get_field.field().run(interpreter)?;
-                val_obj.set_field(val_field, val.clone());
+                let val_field = interpreter.to_property_key(&val_field)?;
+                interpreter.set_field(&val_obj, val_field, val.clone())?;
             }
             _ => (),
         }
@@ -83,10 +91,16 @@ impl Executable for BinOp {
                     CompOp::NotEqual => !v_a.equals(&v_b, interpreter)?,
                     CompOp::StrictEqual => v_a.strict_equals(&v_b),
                     CompOp::StrictNotEqual => !v_a.strict_equals(&v_b),
-                    CompOp::GreaterThan => v_a.to_number() > v_b.to_number(),
-                    CompOp::GreaterThanOrEqual => v_a.to_number() >= v_b.to_number(),
-                    CompOp::LessThan => v_a.to_number() < v_b.to_number(),
-                    CompOp::LessThanOrEqual => v_a.to_number() <= v_b.to_number(),
+                    CompOp::GreaterThan => v_a.number_cmp(&v_b) == Some(Ordering::Greater),
+                    CompOp::GreaterThanOrEqual => matches!(
+                        v_a.number_cmp(&v_b),
+                        Some(Ordering::Greater) | Some(Ordering::Equal)
+                    ),
+                    CompOp::LessThan => v_a.number_cmp(&v_b) == Some(Ordering::Less),
+                    CompOp::LessThanOrEqual => matches!(
+                        v_a.number_cmp(&v_b),
+                        Some(Ordering::Less) | Some(Ordering::Equal)
+                    ),
                     CompOp::In => {
                         if !v_b.is_object() {
                             return interpreter.throw_type_error(format!(
@@ -111,6 +125,14 @@ impl Executable for BinOp {
                         to_bool(self.lhs().run(interpreter)?)
                             || to_bool(self.rhs().run(interpreter)?),
                     ),
+                    LogOp::Coalesce => {
+                        let lhs = self.lhs().run(interpreter)?;
+                        if lhs.is_null_or_undefined() {
+                            self.rhs().run(interpreter)?
+                        } else {
+                            lhs
+                        }
+                    }
                 })
             }
             op::BinOp::Assign(op) => match self.lhs() {
@@ -122,19 +144,15 @@ impl Executable for BinOp {
                         .ok_or_else(|| interpreter.construct_reference_error(name.as_ref()))?;
                     let v_b = self.rhs().run(interpreter)?;
                     let value = Self::run_assign(op, v_a, v_b, interpreter)?;
-                    interpreter.realm.environment.set_mutable_binding(
-                        name.as_ref(),
-                        value.clone(),
-                        true,
-                    );
+                    interpreter.set_mutable_binding(name.as_ref(), value.clone())?;
                     Ok(value)
                 }
                 Node::GetConstField(ref get_const_field) => {
                     let v_r_a = get_const_field.obj().run(interpreter)?;
-                    let v_a = v_r_a.get_field(get_const_field.field());
+                    let v_a = interpreter.get_field(&v_r_a, get_const_field.field())?;
                     let v_b = self.rhs().run(interpreter)?;
                     let value = Self::run_assign(op, v_a, v_b, interpreter)?;
-                    v_r_a.set_field(get_const_field.field(), value.clone());
+                    interpreter.set_field(&v_r_a, get_const_field.field(), value.clone())?;
                     Ok(value)
                 }
                 _ => Ok(Value::undefined()),
@@ -168,23 +186,19 @@ impl Executable for UnaryOp {
 
         Ok(match self.op() {
             op::UnaryOp::Minus => x.neg(interpreter)?,
-            op::UnaryOp::Plus => Value::from(x.to_number()),
+            op::UnaryOp::Plus => Value::from(interpreter.to_number(&x)?),
             op::UnaryOp::IncrementPost => {
                 let ret = x.clone();
-                interpreter.set_value(self.target(), Value::from(x.to_number() + 1.0))?;
+                interpreter.set_value(self.target(), x.increment())?;
                 ret
             }
-            op::UnaryOp::IncrementPre => {
-                interpreter.set_value(self.target(), Value::from(x.to_number() + 1.0))?
-            }
+            op::UnaryOp::IncrementPre => interpreter.set_value(self.target(), x.increment())?,
             op::UnaryOp::DecrementPost => {
                 let ret = x.clone();
-                interpreter.set_value(self.target(), Value::from(x.to_number() - 1.0))?;
+                interpreter.set_value(self.target(), x.decrement())?;
                 ret
             }
-            op::UnaryOp::DecrementPre => {
-                interpreter.set_value(self.target(), Value::from(x.to_number() - 1.0))?
-            }
+            op::UnaryOp::DecrementPre => interpreter.set_value(self.target(), x.decrement())?,
             op::UnaryOp::Not => x.not(interpreter)?,
             op::UnaryOp::Tilde => {
                 let num_v_a = x.to_number();
@@ -197,18 +211,17 @@ impl Executable for UnaryOp {
             }
             op::UnaryOp::Void => Value::undefined(),
             op::UnaryOp::Delete => match *self.target() {
-                Node::GetConstField(ref get_const_field) => Value::boolean(
-                    get_const_field
-                        .obj()
-                        .run(interpreter)?
-                        .remove_property(get_const_field.field()),
-                ),
-                Node::GetField(ref get_field) => Value::boolean(
-                    get_field
-                        .obj()
-                        .run(interpreter)?
-                        .remove_property(&get_field.field().run(interpreter)?.to_string()),
-                ),
+                Node::GetConstField(ref get_const_field) => {
+                    let obj = get_const_field.obj().run(interpreter)?;
+                    let field = PropertyKey::from(get_const_field.field());
+                    Value::boolean(delete_own_property(&obj, &field))
+                }
+                Node::GetField(ref get_field) => {
+                    let obj = get_field.obj().run(interpreter)?;
+                    let field = get_field.field().run(interpreter)?;
+                    let field = interpreter.to_property_key(&field)?;
+                    Value::boolean(delete_own_property(&obj, &field))
+                }
                 Node::Identifier(_) => Value::boolean(false),
                 Node::ArrayDecl(_)
                 | Node::Block(_)
@@ -224,3 +237,9 @@ impl Executable for UnaryOp {
         })
     }
 }
+
+/// Deletes an own property of an object, returning `true` if it existed and was configurable.
+fn delete_own_property(obj: &Value, field: &PropertyKey) -> bool {
+    obj.as_object_mut()
+        .is_some_and(|mut obj| !obj.get_own_property(field).is_none() && obj.delete(field))
+}
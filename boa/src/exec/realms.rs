@@ -0,0 +1,138 @@
+//! Additional realms of an interpreter, and the transfer of values between them.
+//!
+//! Every realm has its own global object and builtins. Objects never cross a realm boundary
+//! directly: `Interpreter::transfer` copies them into the target realm, so that they inherit
+//! from its builtins, and wraps functions so that they keep running in the realm that defined
+//! them. This isolates the realms from each other, like the `ShadowRealm` proposal.
+
+use super::Interpreter;
+use crate::{
+    annex_b,
+    builtins::{
+        function::Function,
+        object::Object,
+        structured_clone,
+        value::{ResultValue, Value},
+    },
+    experimental,
+    realm::{Realm, RealmId},
+};
+use rustc_hash::FxHashMap;
+use std::mem;
+
+impl Interpreter {
+    /// Creates a new realm, with its own global object and builtins.
+    ///
+    /// The interpreter keeps running scripts in the current realm, use `with_realm` to run code
+    /// in the new one.
+    pub fn create_realm(&mut self) -> RealmId {
+        let realm = Realm::create();
+        experimental::install_builtins(&realm, self.experimental());
+        annex_b::install_builtins(&realm, self.annex_b());
+        self.realms.push(Some(realm));
+        RealmId(self.realms.len() - 1)
+    }
+
+    /// Replaces the current realm with a new one, with fresh builtins and without the globals
+    /// that scripts defined.
+    ///
+    /// The globals registered by the host, like with `Context::register_global_function`, are
+    /// not kept either.
+    pub fn reset_realm(&mut self) {
+        *self.realm = Realm::create();
+        experimental::install_builtins(&self.realm, self.experimental());
+        annex_b::install_builtins(&self.realm, self.annex_b());
+    }
+
+    /// Gets the realm that scripts currently run in.
+    #[inline]
+    pub fn current_realm(&self) -> RealmId {
+        self.current_realm
+    }
+
+    /// Runs `f` with `realm` as the current realm, then switches back to the previous one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `realm` was not created by this interpreter.
+    pub fn with_realm<R, F>(&mut self, realm: RealmId, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        let previous = self.enter_realm(realm);
+        let result = f(self);
+        self.enter_realm(previous);
+        result
+    }
+
+    /// Makes `realm` the current realm, and returns the previous one.
+    fn enter_realm(&mut self, realm: RealmId) -> RealmId {
+        let previous = self.current_realm;
+        if realm == previous {
+            return previous;
+        }
+
+        let mut next = self
+            .realms
+            .get_mut(realm.0)
+            .and_then(Option::take)
+            .expect("unknown realm");
+        mem::swap(&mut *self.realm, &mut next);
+        self.realms[previous.0] = Some(next);
+        self.current_realm = realm;
+        previous
+    }
+
+    /// Passes `value` from the realm `from` to the realm `to`.
+    ///
+    /// Primitives are passed as they are. Functions are wrapped in a function of `to` that
+    /// calls them in `from`, transferring the arguments and the result, or the thrown value.
+    /// Other objects are copied like `structuredClone` does, with the prototypes of `to`;
+    /// objects that cannot be cloned throw a `TypeError`.
+    ///
+    /// Transferred functions are kept alive until the interpreter is dropped.
+    pub fn transfer(&mut self, value: &Value, from: RealmId, to: RealmId) -> ResultValue {
+        if from == to || !value.is_object() {
+            return Ok(value.clone());
+        }
+
+        if value.is_function() {
+            return Ok(self.with_realm(to, |ctx| wrap_function(value.clone(), from, ctx)));
+        }
+
+        self.with_realm(to, |ctx| {
+            structured_clone::clone_value(value, &mut FxHashMap::default(), ctx)
+        })
+    }
+}
+
+/// Creates a function of the current realm that calls `target` in the realm `realm`.
+///
+/// The target is kept by the interpreter rather than by the closure, because the closure is
+/// not traced by the garbage collector.
+fn wrap_function(target: Value, realm: RealmId, ctx: &mut Interpreter) -> Value {
+    let length = target.get_field("length");
+    let index = ctx.wrapped_functions.len();
+    ctx.wrapped_functions.push(target);
+    let wrapper = Function::closure(Vec::new(), move |_, args, ctx| {
+        let caller = ctx.current_realm();
+        let mut arguments = Vec::with_capacity(args.len());
+        for arg in args {
+            arguments.push(ctx.transfer(arg, caller, realm)?);
+        }
+
+        let target = ctx.wrapped_functions[index].clone();
+        let result = ctx.with_realm(realm, |ctx| {
+            ctx.call(&target, &Value::undefined(), &arguments)
+        });
+        match result {
+            Ok(value) => ctx.transfer(&value, realm, caller),
+            Err(error) => Err(ctx.transfer(&error, realm, caller)?),
+        }
+    });
+
+    let prototype = ctx.realm.function_prototype();
+    let wrapper = Value::from(Object::function(wrapper, prototype));
+    wrapper.set_field("length", length);
+    wrapper
+}
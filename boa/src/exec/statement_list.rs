@@ -13,7 +13,14 @@ impl Executable for StatementList {
         let mut obj = Value::null();
         interpreter.set_current_state(InterpreterState::Executing);
         for (i, item) in self.statements().iter().enumerate() {
-            let val = item.run(interpreter)?;
+            interpreter.set_statement_position(self.spans().get(i));
+            interpreter.before_statement(item);
+            if let Some(coverage) = interpreter.coverage_mut() {
+                coverage.record_statement(item);
+            }
+            let val = item.run(interpreter).inspect_err(|error| {
+                interpreter.record_error_position(error, self.spans().get(i));
+            })?;
             match interpreter.get_current_state() {
                 InterpreterState::Return => {
                     // Early return.
@@ -1163,3 +1163,515 @@ fn not_a_function() {
     "#;
     assert_eq!(forward(&mut engine, scenario), "TypeError: not a function");
 }
+
+#[test]
+fn shutdown_hooks_run_on_drop() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var answer = 42;");
+
+    let first = Rc::clone(&calls);
+    engine.on_shutdown(move |engine| {
+        let answer = forward(engine, "answer");
+        first.borrow_mut().push(format!("first {}", answer));
+    });
+    let second = Rc::clone(&calls);
+    engine.on_shutdown(move |engine| {
+        let nested = Rc::clone(&second);
+        engine.on_shutdown(move |_| nested.borrow_mut().push("nested".to_owned()));
+        second.borrow_mut().push("second".to_owned());
+    });
+
+    assert!(calls.borrow().is_empty());
+    drop(engine);
+    assert_eq!(*calls.borrow(), vec!["first 42", "second", "nested"]);
+}
+
+#[test]
+fn reset_execution_state_keeps_globals() {
+    use crate::environment::lexical_environment::{new_declarative_environment, VariableScope};
+
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var kept = 'yes';");
+
+    // Simulate an evaluation that was aborted inside a nested scope.
+    let current = engine.realm.environment.get_current_environment().clone();
+    engine
+        .realm
+        .environment
+        .push(new_declarative_environment(Some(current)));
+    engine.realm.environment.create_mutable_binding(
+        "inner".to_owned(),
+        false,
+        VariableScope::Block,
+    );
+
+    engine.reset_execution_state();
+
+    assert_eq!(forward(&mut engine, "kept"), "yes");
+    assert!(!engine.realm.environment.has_binding("inner"));
+}
+
+#[test]
+fn to_primitive_uses_symbol_to_primitive() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let hinted = {};
+        hinted[Symbol.toPrimitive] = function (hint) { return hint; };
+        let invalid = {};
+        invalid[Symbol.toPrimitive] = 1;
+        let boxed = {};
+        boxed[Symbol.toPrimitive] = function () { return {}; };
+    "#;
+
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "hinted + ''"), "default");
+    assert_eq!(forward(&mut engine, "String(hinted)"), "string");
+    assert_eq!(forward(&mut engine, "+hinted"), "NaN");
+    assert_eq!(
+        forward(&mut engine, "try { +invalid } catch (e) { e.toString() }"),
+        "TypeError: Symbol.toPrimitive is not a function"
+    );
+    assert_eq!(
+        forward(&mut engine, "try { +boxed } catch (e) { e.toString() }"),
+        "TypeError: cannot convert object to primitive value"
+    );
+}
+
+#[test]
+fn step_budget_terminates_scripts() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.set_step_budget(Some(100));
+
+    let result = crate::forward_val(
+        &mut engine,
+        "var caught = false; try { while (true) {} } catch (e) { caught = true } finally { caught = true }",
+    );
+    assert!(result.is_err());
+    assert!(engine.is_terminated());
+    assert_eq!(forward(&mut engine, "caught"), "false");
+
+    assert_eq!(
+        forward(&mut engine, "let i = 0; while (i < 50) { i++ } i"),
+        "50"
+    );
+    assert!(!engine.is_terminated());
+
+    let result = crate::forward_val(
+        &mut engine,
+        "function f(n) { if (n == 0) { return 0; } return f(n - 1); } f(200)",
+    );
+    assert!(result.is_err());
+    assert!(engine.is_terminated());
+}
+
+#[test]
+fn memory_limit_is_checked_before_large_allocations() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.set_memory_limit(Some(256 * 1024));
+
+    for src in &[
+        "'x'.repeat(1e8)",
+        "let s = 'x'.repeat(1e5); s.concat(s, s)",
+        "'x'.repeat(2e5) + 'y'.repeat(2e5)",
+        "[].concat(Array(2e4).fill(0))",
+    ] {
+        let result = crate::forward_val(&mut engine, src);
+        assert!(result.is_err(), "{} should be terminated", src);
+        assert!(engine.is_terminated());
+    }
+
+    assert_eq!(forward(&mut engine, "'x'.repeat(1e3).length"), "1000");
+    assert!(!engine.is_terminated());
+}
+
+#[test]
+fn interrupt_handle_stops_the_next_script() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let handle = engine.interrupt_handle();
+
+    handle.interrupt();
+    assert!(handle.is_interrupted());
+    assert_eq!(
+        forward(&mut engine, "let n = 0; do { n++ } while (true)"),
+        "Error: RangeError: script interrupted"
+    );
+    assert!(!handle.is_interrupted());
+    assert_eq!(forward(&mut engine, "n"), "0");
+}
+
+#[test]
+fn deferred_syntax_errors_are_reported_in_their_body() {
+    use crate::{forward_val, JsError};
+
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.set_lazy_parsing(true);
+
+    engine.set_script_name("lib.js");
+    forward(
+        &mut engine,
+        "function ok() {\n    return 1;\n}\nfunction broken() {\n    return );\n}",
+    );
+    engine.set_script_name("main.js");
+    assert_eq!(forward(&mut engine, "ok()"), "1");
+    match forward_val(&mut engine, "ok();\nbroken()") {
+        Err(JsError::Syntax {
+            position, script, ..
+        }) => {
+            assert_eq!(
+                position.map(|p| (p.line_number(), p.column_number())),
+                Some((5, 12))
+            );
+            assert_eq!(script.as_deref(), Some("lib.js"));
+        }
+        other => panic!("expected a syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn forward_val_classifies_errors() {
+    use crate::{forward_val, JsError};
+
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    match forward_val(&mut engine, "let a = 1;\nlet b = ;") {
+        Err(JsError::Syntax { position, .. }) => {
+            assert_eq!(position.map(|p| p.line_number()), Some(2))
+        }
+        other => panic!("expected a syntax error, got {:?}", other),
+    }
+
+    match forward_val(&mut engine, "throw new TypeError('bad')") {
+        Err(JsError::Thrown { ref message, .. }) => assert_eq!(message, "TypeError: bad"),
+        other => panic!("expected a thrown error, got {:?}", other),
+    }
+    match forward_val(&mut engine, "throw 42") {
+        Err(error) => assert_eq!(error.value().map(Value::to_string), Some("42".to_owned())),
+        Ok(_) => panic!("expected a thrown value"),
+    }
+
+    // The position of an error thrown in a function is in the script of the function.
+    engine.set_script_name("lib.js");
+    forward(
+        &mut engine,
+        "function fail() {\n    throw new Error('bad');\n}",
+    );
+    engine.set_script_name("main.js");
+    let error = forward_val(&mut engine, "fail()").expect_err("fail should throw");
+    assert_eq!(error.script(), Some("lib.js"));
+    assert_eq!(error.position().map(|p| p.line_number()), Some(2));
+
+    engine.set_max_stack_size(256 * 1024);
+    match forward_val(&mut engine, "function f(n) { return f(n + 1); } f(0)") {
+        Err(JsError::StackOverflow) => {}
+        other => panic!("expected a stack overflow, got {:?}", other),
+    }
+    assert_eq!(
+        forward(
+            &mut engine,
+            "function g(n) { try { return g(n + 1); } catch (e) { return e.name; } } g(0)"
+        ),
+        "RangeError"
+    );
+
+    engine.set_step_budget(Some(100));
+    match forward_val(&mut engine, "while (true) {}") {
+        Err(JsError::StepBudgetExceeded) => {}
+        other => panic!("expected the step budget to be exceeded, got {:?}", other),
+    }
+    engine.set_step_budget(None);
+
+    engine.interrupt_handle().interrupt();
+    match forward_val(&mut engine, "while (true) {}") {
+        Err(JsError::Interrupted) => {}
+        other => panic!("expected an interruption, got {:?}", other),
+    }
+    assert_eq!(
+        forward_val(&mut engine, "1 + 1")
+            .ok()
+            .map(|v| v.to_string()),
+        Some("2".to_owned())
+    );
+}
+
+#[test]
+fn thrown_errors_know_their_position() {
+    use crate::forward_val;
+
+    fn position(engine: &mut Interpreter, src: &str) -> Option<(u32, u32)> {
+        let error = forward_val(engine, src).expect_err("the script should throw");
+        error
+            .position()
+            .map(|position| (position.line_number(), position.column_number()))
+    }
+
+    let mut engine = Interpreter::new(Realm::create());
+
+    assert_eq!(position(&mut engine, "let a = 1;\n  a.b.c;"), Some((2, 3)));
+    assert_eq!(
+        position(&mut engine, "if (true) {\n  let x = 1;\n  x();\n}"),
+        Some((3, 3))
+    );
+    assert_eq!(
+        position(
+            &mut engine,
+            "function f() {\n  return null.x;\n}\nlet y = 2;\nf();"
+        ),
+        Some((2, 3))
+    );
+    assert_eq!(
+        position(
+            &mut engine,
+            "try {\n  null.x;\n} catch (e) {}\n  throw new Error('late');"
+        ),
+        Some((4, 3))
+    );
+}
+
+#[test]
+fn contexts_run_on_separate_threads() {
+    let threads: Vec<_> = (0..4)
+        .map(|n| {
+            std::thread::spawn(move || {
+                let realm = Realm::create();
+                let mut engine = Interpreter::new(realm);
+                let init = format!(
+                    r#"
+                    var result = [];
+                    var s = Symbol('thread');
+                    var o = {{}};
+                    o[s] = {};
+                    Promise.resolve(o[s]).then(function(v) {{ result.push(v); }});
+                    var i = 0;
+                    while (i < 1000) {{ result.length = 0; result.push(s.toString()); i++; }}
+                    "#,
+                    n
+                );
+                forward(&mut engine, &init);
+                forward(&mut engine, "result.join()")
+            })
+        })
+        .collect();
+
+    for (n, thread) in threads.into_iter().enumerate() {
+        assert_eq!(
+            thread.join().expect("the thread panicked"),
+            format!("Symbol(thread),{}", n)
+        );
+    }
+}
+
+#[test]
+fn scripts_parsed_on_other_threads() {
+    use crate::{forward_parsed, parse, ParseOptions};
+
+    let threads: Vec<_> = ["var a = 20;", "a * 2 + 2", "let b = ;"]
+        .iter()
+        .map(|&src| {
+            let options = ParseOptions {
+                lazy_functions: true,
+                ..ParseOptions::default()
+            };
+            std::thread::spawn(move || parse(src, options).map_err(|e| e.to_string()))
+        })
+        .collect();
+    let parsed: Vec<_> = threads
+        .into_iter()
+        .map(|thread| thread.join().expect("the thread panicked"))
+        .collect();
+
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let statements = parsed[0].as_ref().expect("failed to parse");
+    assert!(forward_parsed(&mut engine, statements).is_ok());
+    let statements = parsed[1].as_ref().expect("failed to parse");
+    assert_eq!(
+        forward_parsed(&mut engine, statements)
+            .ok()
+            .map(|v| v.to_string()),
+        Some("42".to_owned())
+    );
+    assert!(parsed[2].is_err());
+}
+
+#[test]
+fn cyclic_structures_are_collected() {
+    use crate::{
+        builtins::object::InternalState,
+        class::{Class, ClassBuilder},
+        Context,
+    };
+    use std::cell::Cell;
+
+    thread_local! {
+        static DROPPED: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[derive(Debug)]
+    struct Tracked;
+
+    impl InternalState for Tracked {}
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            DROPPED.with(|dropped| dropped.set(dropped.get() + 1));
+        }
+    }
+
+    impl Class for Tracked {
+        const NAME: &'static str = "Tracked";
+        const LENGTH: usize = 0;
+
+        fn constructor(_: &Value, _: &[Value], _: &mut Interpreter) -> Result<Self, Value> {
+            Ok(Self)
+        }
+
+        fn init(_: &mut ClassBuilder<'_, Self>) {}
+    }
+
+    let mut context = Context::new();
+    context.register_global_class::<Tracked>();
+    context
+        .eval(
+            r#"
+            function make() {
+                var tracked = new Tracked();
+                var holder = { tracked: tracked };
+                holder.self = holder;
+                holder.get = function() { return holder; };
+                tracked.holder = holder;
+            }
+            var i = 0;
+            while (i < 10) { make(); i++; }
+            "#,
+        )
+        .unwrap();
+
+    context.interpreter_mut().collect_garbage();
+    assert_eq!(DROPPED.with(Cell::get), 10);
+}
+
+#[test]
+fn strict_mode_rules() {
+    let mut engine = Interpreter::new(Realm::create());
+    engine.set_strict(true);
+
+    assert_eq!(
+        forward(&mut engine, "undeclared = 1"),
+        "Error: ReferenceError: undeclared is not defined"
+    );
+    assert_eq!(forward(&mut engine, "var declared; declared = 2"), "2");
+    assert!(forward(&mut engine, "010").starts_with("Syntax Error"));
+
+    engine.set_strict(false);
+    assert_eq!(
+        forward(&mut engine, "undeclared = 3; undeclared + 010"),
+        "11"
+    );
+}
+
+#[test]
+fn tracing_spans() {
+    use std::sync::{Arc, Mutex};
+    use tracing::{
+        span::{Attributes, Id, Record},
+        subscriber, Event, Metadata, Subscriber,
+    };
+
+    /// A subscriber that records the names of the spans that are created.
+    #[derive(Debug, Clone, Default)]
+    struct Spans(Arc<Mutex<Vec<&'static str>>>);
+
+    impl Subscriber for Spans {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut names = self.0.lock().unwrap();
+            names.push(span.metadata().name());
+            Id::from_u64(names.len() as u64)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    let spans = Spans::default();
+    subscriber::with_default(spans.clone(), || {
+        let realm = Realm::create();
+        let mut engine = Interpreter::new(realm);
+        forward(&mut engine, "function f() { return 1; } f();");
+    });
+
+    let names = spans.0.lock().unwrap();
+    for name in &[
+        "lex",
+        "parse",
+        "HoistableDeclaration",
+        "ReturnStatement",
+        "run",
+        "call",
+    ] {
+        assert!(names.contains(name), "no {} span in {:?}", name, names);
+    }
+}
+
+#[test]
+fn compiled_functions() {
+    use crate::{compile_function, JsError};
+
+    let mut engine = Interpreter::new(Realm::create());
+    forward(&mut engine, "var c = 10;");
+
+    let function = compile_function(
+        &mut engine,
+        "sum.js",
+        &["a", "b"],
+        "var local = a + b;\nreturn local + c;",
+    )
+    .expect("failed to compile");
+    let result = engine.call(
+        &function,
+        &Value::undefined(),
+        &[Value::from(1), Value::from(2)],
+    );
+    assert_eq!(result.ok().map(|v| v.to_string()), Some("13".to_owned()));
+    assert_eq!(
+        forward(&mut engine, "local"),
+        "Error: ReferenceError: local is not defined"
+    );
+
+    match compile_function(&mut engine, "a.js", &[], "let a = 1;\nlet b = ;") {
+        Err(JsError::Syntax { position, .. }) => {
+            assert_eq!(position.map(|p| p.line_number()), Some(2))
+        }
+        _ => panic!("expected a syntax error"),
+    }
+    assert!(matches!(
+        compile_function(&mut engine, "a.js", &[], "}); (function () {"),
+        Err(JsError::Syntax { .. })
+    ));
+
+    let error = engine
+        .throw_error("boom")
+        .expect_err("throw_error should throw");
+    assert_eq!(error.to_string(), "Error: boom");
+}
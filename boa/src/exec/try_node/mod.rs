@@ -14,7 +14,11 @@ mod tests;
 impl Executable for Try {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
         let _timer = BoaProfiler::global().start_event("Try", "exec");
-        let res = self.block().run(interpreter).map_or_else(
+        let res = self.block().run(interpreter);
+        if interpreter.is_terminated() {
+            return res;
+        }
+        let res = res.map_or_else(
             |err| {
                 if let Some(catch) = self.catch() {
                     {
@@ -47,6 +51,9 @@ impl Executable for Try {
             Ok,
         );
 
+        if interpreter.is_terminated() {
+            return res;
+        }
         if let Some(finally) = self.finally() {
             finally.run(interpreter)?;
         }
@@ -0,0 +1,121 @@
+//! This module implements the execution profile.
+//!
+//! The profile counts how many times each kind of syntax tree node is evaluated, and how long
+//! the evaluations take, so that users can see where their scripts spend their time. Each node
+//! is charged its self time, the time of its evaluation minus the time of the evaluation of the
+//! nodes it contains, so the times of all the kinds add up to the time of the whole run.
+//!
+//! Like the timeline, the profile is always compiled in, and only records evaluations once it
+//! has been enabled with `Interpreter::enable_profile`.
+
+#[cfg(test)]
+mod tests;
+
+use rustc_hash::FxHashMap;
+use std::{
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
+/// The evaluations of a kind of node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProfileEntry {
+    /// The kind of the nodes, e.g. `"Call"` or `"BinOp"`.
+    pub kind: &'static str,
+    /// How many nodes of this kind were evaluated.
+    pub count: u64,
+    /// How long the evaluations took, without the nodes they contain.
+    pub self_time: Duration,
+}
+
+/// The execution profile of a run, see the module documentation.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionProfile {
+    entries: FxHashMap<&'static str, ProfileEntry>,
+    /// The time spent evaluating the children of each node that is being evaluated.
+    children: Vec<Duration>,
+}
+
+impl ExecutionProfile {
+    /// Creates a new, empty profile.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts the evaluation of a node, which `exit` ends.
+    #[inline]
+    pub(crate) fn enter(&mut self) -> Instant {
+        self.children.push(Duration::default());
+        Instant::now()
+    }
+
+    /// Ends the evaluation of a node of the given kind, that started at `start`.
+    pub(crate) fn exit(&mut self, kind: &'static str, start: Instant) {
+        let time = start.elapsed();
+        let children = self.children.pop().unwrap_or_default();
+        if let Some(parent) = self.children.last_mut() {
+            *parent += time;
+        }
+
+        let entry = self.entries.entry(kind).or_insert(ProfileEntry {
+            kind,
+            count: 0,
+            self_time: Duration::default(),
+        });
+        entry.count += 1;
+        entry.self_time += time.checked_sub(children).unwrap_or_default();
+    }
+
+    /// Returns the entry of every kind of node that was evaluated, the slowest first.
+    pub fn entries(&self) -> Vec<ProfileEntry> {
+        let mut entries: Vec<_> = self.entries.values().copied().collect();
+        entries.sort_by(|a, b| {
+            b.self_time
+                .cmp(&a.self_time)
+                .then_with(|| b.count.cmp(&a.count))
+                .then_with(|| a.kind.cmp(b.kind))
+        });
+        entries
+    }
+
+    /// Returns the time spent evaluating nodes.
+    pub fn total_time(&self) -> Duration {
+        self.entries.values().map(|entry| entry.self_time).sum()
+    }
+
+    /// Forgets every recorded evaluation.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl Display for ExecutionProfile {
+    /// Formats the profile as a table, with a row for each kind of node, the slowest first.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.total_time().as_secs_f64();
+        writeln!(
+            f,
+            "{:<20} {:>10} {:>12} {:>7}",
+            "Node", "Count", "Self (ms)", "%"
+        )?;
+        for entry in self.entries() {
+            let time = entry.self_time.as_secs_f64();
+            let percent = if total > 0.0 {
+                time / total * 100.0
+            } else {
+                0.0
+            };
+            writeln!(
+                f,
+                "{:<20} {:>10} {:>12.3} {:>6.1}%",
+                entry.kind,
+                entry.count,
+                time * 1000.0,
+                percent
+            )?;
+        }
+        Ok(())
+    }
+}
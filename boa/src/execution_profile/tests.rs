@@ -0,0 +1,63 @@
+use super::*;
+use crate::{exec::Interpreter, forward, realm::Realm};
+
+#[test]
+fn profile_is_disabled_by_default() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "1 + 1");
+
+    assert!(engine.profile().is_none());
+}
+
+#[test]
+fn profile_counts_evaluated_nodes() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.enable_profile();
+    let scenario = r#"
+        function add(a, b) {
+            return a + b;
+        }
+        var sum = 0;
+        for (let i = 0; i < 10; i++) {
+            sum = add(sum, i);
+        }
+        sum
+    "#;
+    assert_eq!(forward(&mut engine, scenario), "45");
+
+    let profile = engine.profile().expect("profile was enabled");
+    let count = |kind| {
+        profile
+            .entries()
+            .iter()
+            .find(|entry| entry.kind == kind)
+            .map_or(0, |entry| entry.count)
+    };
+    assert_eq!(count("Call"), 10);
+    assert_eq!(count("Return"), 10);
+    assert_eq!(count("ForLoop"), 1);
+    assert_eq!(count("Try"), 0);
+}
+
+#[test]
+fn entries_are_sorted_by_self_time() {
+    let mut profile = ExecutionProfile::new();
+    let start = profile.enter();
+    let inner = profile.enter();
+    profile.exit("Call", inner);
+    profile.exit("Block", start);
+
+    let entries = profile.entries();
+    assert_eq!(entries.len(), 2);
+    assert!(entries[0].self_time >= entries[1].self_time);
+    assert_eq!(
+        profile.total_time(),
+        entries[0].self_time + entries[1].self_time
+    );
+
+    let table = profile.to_string();
+    assert!(table.starts_with("Node"));
+    assert_eq!(table.lines().count(), 3);
+}
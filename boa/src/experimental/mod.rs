@@ -0,0 +1,113 @@
+//! This module implements the toggles of the experimental language features.
+//!
+//! Experimental features are proposals, or parts of the specification, whose implementation is
+//! incomplete. They are disabled by default, so that the engine can ship them without changing
+//! the behavior of scripts, and are enabled with `Interpreter::set_experimental` or
+//! `ContextBuilder::experimental`:
+//!
+//! ```
+//! use boa::{experimental::Experimental, Context};
+//!
+//! let mut context = Context::builder()
+//!     .experimental(Experimental::NULLISH_COALESCING)
+//!     .build();
+//! let value = context.eval("null ?? 'default'").unwrap();
+//! assert_eq!(value.to_string(), "default");
+//! ```
+//!
+//! Building the engine with the `experimental` cargo feature enables all of them by default.
+//!
+//! Syntax features are gated in the lexer, which only produces their tokens when they are
+//! enabled, and builtins are only installed on the realms of the interpreters that enabled them.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builtins::{function::make_builtin_fn, object::PROTOTYPE, String},
+    realm::Realm,
+};
+use bitflags::bitflags;
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+bitflags! {
+    /// A set of experimental features, see the module documentation.
+    pub struct Experimental: u32 {
+        /// The nullish coalescing operator, `a ?? b`.
+        ///
+        /// It has the precedence of `||`, and mixing it with `&&` or `||` without parentheses
+        /// is not a syntax error yet.
+        const NULLISH_COALESCING = 0b0000_0001;
+
+        /// `String.prototype.replaceAll`, with strings as patterns only.
+        const STRING_REPLACE_ALL = 0b0000_0010;
+    }
+}
+
+impl Experimental {
+    /// The names of the features, as accepted by `from_str`.
+    pub const NAMES: &'static [(&'static str, Self)] = &[
+        ("nullish-coalescing", Self::NULLISH_COALESCING),
+        ("string-replace-all", Self::STRING_REPLACE_ALL),
+    ];
+}
+
+impl Default for Experimental {
+    /// Returns the features that are enabled by default: none of them, or all of them with the
+    /// `experimental` cargo feature.
+    fn default() -> Self {
+        if cfg!(feature = "experimental") {
+            Self::all()
+        } else {
+            Self::empty()
+        }
+    }
+}
+
+impl Display for Experimental {
+    /// Formats the set as the names of its features, separated by commas.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<_> = Self::NAMES
+            .iter()
+            .filter(|(_, feature)| self.contains(*feature))
+            .map(|(name, _)| *name)
+            .collect();
+        f.write_str(&names.join(","))
+    }
+}
+
+impl FromStr for Experimental {
+    type Err = std::string::String;
+
+    /// Parses the names of features separated by commas, or `all`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut features = Self::empty();
+        for name in s.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            features |= match Self::NAMES.iter().find(|(n, _)| *n == name) {
+                Some((_, feature)) => *feature,
+                None if name == "all" => Self::all(),
+                None => return Err(format!("unknown experimental feature `{}`", name)),
+            };
+        }
+        Ok(features)
+    }
+}
+
+/// Installs the builtins of the enabled features on the global object of a realm, and removes
+/// the ones of the disabled features.
+pub(crate) fn install_builtins(realm: &Realm, features: Experimental) {
+    let prototype = realm
+        .global_obj
+        .get_field(String::NAME)
+        .get_field(PROTOTYPE);
+    if features.contains(Experimental::STRING_REPLACE_ALL) {
+        if !prototype.has_field("replaceAll") {
+            make_builtin_fn(String::replace_all, "replaceAll", &prototype, 2);
+        }
+    } else {
+        prototype.remove_property("replaceAll");
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{experimental::Experimental, Context};
+
+/// Builds a context with the given experimental features.
+fn context(experimental: Experimental) -> Context {
+    Context::builder().experimental(experimental).build()
+}
+
+#[test]
+fn nullish_coalescing_is_gated() {
+    let mut disabled = context(Experimental::empty());
+    assert!(disabled.eval("null ?? 1").is_err());
+
+    let mut enabled = context(Experimental::NULLISH_COALESCING);
+    let eval = |context: &mut Context, src| context.eval(src).unwrap().to_string();
+    assert_eq!(eval(&mut enabled, "null ?? 'a'"), "a");
+    assert_eq!(eval(&mut enabled, "undefined ?? 'a'"), "a");
+    assert_eq!(eval(&mut enabled, "0 ?? 'a'"), "0");
+    assert_eq!(eval(&mut enabled, "'' ?? 'a'"), "");
+    assert_eq!(eval(&mut enabled, "false ?? 'a'"), "false");
+    assert_eq!(eval(&mut enabled, "null ?? undefined ?? 2"), "2");
+    // The right operand is only evaluated when the left one is nullish.
+    assert_eq!(
+        eval(&mut enabled, "let n = 0; 1 ?? n++; null ?? n++; n"),
+        "1"
+    );
+}
+
+#[test]
+fn string_replace_all_is_gated() {
+    let mut context = context(Experimental::empty());
+    assert_eq!(
+        context.eval("typeof ''.replaceAll").unwrap().to_string(),
+        "undefined"
+    );
+
+    context
+        .interpreter_mut()
+        .set_experimental(Experimental::STRING_REPLACE_ALL);
+    let eval = |context: &mut Context, src| context.eval(src).unwrap().to_string();
+    assert_eq!(eval(&mut context, "'a-b-c'.replaceAll('-', '+')"), "a+b+c");
+    assert_eq!(
+        eval(&mut context, "'aaa'.replaceAll('a', '$&$$')"),
+        "a$a$a$"
+    );
+    assert_eq!(eval(&mut context, "'xax'.replaceAll('', '_')"), "_x_a_x_");
+    assert_eq!(
+        eval(&mut context, "'1 2'.replaceAll(' ', (m, i) => i)"),
+        "112"
+    );
+    assert!(context.eval("'a'.replaceAll(/a/g, 'b')").is_err());
+    context.interpreter_mut().set_max_string_length(8);
+    assert!(context.eval("'abab'.replaceAll('b', 'bbbb')").is_err());
+    context.interpreter_mut().set_max_string_length(usize::MAX);
+
+    // New realms get the builtins of the enabled features.
+    let realm = context.create_realm();
+    assert_eq!(
+        context
+            .eval_in_realm(realm, "typeof ''.replaceAll")
+            .unwrap()
+            .to_string(),
+        "function"
+    );
+
+    context
+        .interpreter_mut()
+        .set_experimental(Experimental::empty());
+    assert_eq!(eval(&mut context, "typeof ''.replaceAll"), "undefined");
+    assert_eq!(
+        context
+            .eval_in_realm(realm, "typeof ''.replaceAll")
+            .unwrap()
+            .to_string(),
+        "undefined"
+    );
+}
+
+#[test]
+fn parse_and_display() {
+    let features: Experimental = "nullish-coalescing, string-replace-all".parse().unwrap();
+    assert_eq!(features, Experimental::all());
+    assert_eq!("all".parse::<Experimental>().unwrap(), Experimental::all());
+    assert_eq!("".parse::<Experimental>().unwrap(), Experimental::empty());
+    assert!("unknown".parse::<Experimental>().is_err());
+
+    assert_eq!(
+        Experimental::all().to_string(),
+        "nullish-coalescing,string-replace-all"
+    );
+    assert_eq!(
+        Experimental::NULLISH_COALESCING
+            .to_string()
+            .parse::<Experimental>()
+            .unwrap(),
+        Experimental::NULLISH_COALESCING
+    );
+}
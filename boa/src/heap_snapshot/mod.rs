@@ -0,0 +1,576 @@
+//! This module implements heap snapshots.
+//!
+//! A heap snapshot is a graph of every object that is reachable from the global object, with the
+//! references between them. It is used to find out which objects keep memory alive when a script
+//! makes the heap grow.
+//!
+//! Snapshots can be exported in two formats:
+//!  - `HeapSnapshot::to_json`, a documented JSON format that is easy to process with scripts.
+//!  - `HeapSnapshot::to_heapsnapshot`, the [`.heapsnapshot`][format] format of V8, which can be
+//!    loaded in the memory panel of the Chrome developer tools.
+//!
+//! `HeapStats` sums a snapshot up, with the number and the size of the objects of each kind and
+//! of the distinct strings, for the embeddings that watch their heap without keeping snapshots.
+//!
+//! The sizes are estimates of the memory used by the object representation of the engine, they
+//! don't include the memory of strings and the allocator overhead. Bindings declared with `let`
+//! and `const`, and variables captured by closures, are not reachable through object properties
+//! and are not part of the snapshot.
+//!
+//! [format]: https://developer.chrome.com/docs/devtools/memory-problems/heap-snapshots
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builtins::{
+        object::{GcObject, Object, ObjectData},
+        property::{Property, PropertyKey},
+        value::{JsString, Value},
+        RegExp,
+    },
+    exec::Interpreter,
+    memory::thread_allocated_bytes,
+};
+use gc::GcCell;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde_json::{json, Value as JSONValue};
+use std::{
+    collections::VecDeque,
+    fmt::{self, Display},
+    mem::size_of,
+};
+
+/// The kind of a reference between two nodes of the heap graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeapEdgeKind {
+    /// A named property, or a reference from the root node.
+    Property,
+    /// An integer indexed property.
+    Element,
+    /// A reference that scripts can't access by name, like the prototype, an internal slot,
+    /// an accessor function or an entry of a `Map`.
+    Internal,
+}
+
+impl HeapEdgeKind {
+    /// The name of this kind of edge, in both export formats.
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Property => "property",
+            Self::Element => "element",
+            Self::Internal => "internal",
+        }
+    }
+}
+
+/// A node of the heap graph, an object or the synthetic root node.
+#[derive(Debug, Clone)]
+pub struct HeapNode {
+    /// The index of the node in the snapshot, the root node is `0`.
+    pub id: usize,
+    /// The kind of object, like `"Object"`, `"Array"` or `"Function"`.
+    pub name: String,
+    /// The estimated size, in bytes, of the object itself.
+    pub self_size: usize,
+    /// The estimated size, in bytes, of the memory that would be freed if this object was freed,
+    /// this object and every object that is only reachable through it.
+    pub retained_size: usize,
+}
+
+/// A reference from one node of the heap graph to another.
+#[derive(Debug, Clone)]
+pub struct HeapEdge {
+    /// The node that holds the reference.
+    pub from: usize,
+    /// The referenced node.
+    pub to: usize,
+    /// The kind of the reference.
+    pub kind: HeapEdgeKind,
+    /// The name of the reference, e.g. the property key.
+    pub name: String,
+}
+
+/// A snapshot of the objects that are reachable from the global object.
+#[derive(Debug, Clone)]
+pub struct HeapSnapshot {
+    nodes: Vec<HeapNode>,
+    edges: Vec<HeapEdge>,
+    /// The distinct strings of the property keys and values.
+    strings: FxHashSet<JsString>,
+}
+
+impl HeapSnapshot {
+    /// The name of the synthetic root node.
+    pub const ROOT_NAME: &'static str = "(GC roots)";
+
+    /// Takes a snapshot of the heap of `interpreter`.
+    pub fn capture(interpreter: &Interpreter) -> Self {
+        let mut builder = Builder::default();
+        builder.nodes.push(HeapNode {
+            id: 0,
+            name: Self::ROOT_NAME.to_owned(),
+            self_size: 0,
+            retained_size: 0,
+        });
+        builder.reference(
+            0,
+            HeapEdgeKind::Property,
+            "global".to_owned(),
+            &interpreter.realm().global_obj,
+        );
+
+        while let Some((id, object)) = builder.queue.pop_front() {
+            builder.visit(id, &object.borrow());
+        }
+
+        let mut snapshot = Self {
+            nodes: builder.nodes,
+            edges: builder.edges,
+            strings: builder.strings,
+        };
+        snapshot.compute_retained_sizes();
+        snapshot
+    }
+
+    /// Returns the nodes of the snapshot, the root node first.
+    #[inline]
+    pub fn nodes(&self) -> &[HeapNode] {
+        &self.nodes
+    }
+
+    /// Returns the edges of the snapshot, grouped by the node that holds them.
+    #[inline]
+    pub fn edges(&self) -> &[HeapEdge] {
+        &self.edges
+    }
+
+    /// Sums the snapshot up, see `HeapStats`.
+    pub fn stats(&self) -> HeapStats {
+        let mut kinds: FxHashMap<&str, HeapKindStats> = FxHashMap::default();
+        for node in self.nodes.iter().skip(1) {
+            let kind = kinds.entry(&node.name).or_insert_with(|| HeapKindStats {
+                name: node.name.clone(),
+                count: 0,
+                size: 0,
+            });
+            kind.count += 1;
+            kind.size += node.self_size;
+        }
+        let mut kinds: Vec<_> = kinds.into_values().collect();
+        kinds.sort_by(|a, b| {
+            b.size
+                .cmp(&a.size)
+                .then_with(|| b.count.cmp(&a.count))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        HeapStats {
+            object_count: self.nodes.len() - 1,
+            object_size: self.nodes.iter().map(|node| node.self_size).sum(),
+            kinds,
+            string_count: self.strings.len(),
+            string_size: self.strings.iter().map(|string| string.len()).sum(),
+            allocated_bytes: thread_allocated_bytes(),
+        }
+    }
+
+    /// Exports the snapshot as JSON.
+    ///
+    /// The result is an object with two arrays:
+    ///  - `nodes`: objects with the `id`, `name`, `selfSize` and `retainedSize` of each node.
+    ///  - `edges`: objects with the `from` and `to` node ids, the `type` (`"property"`,
+    ///    `"element"` or `"internal"`) and the `name` of each reference.
+    pub fn to_json(&self) -> JSONValue {
+        let nodes: Vec<_> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                json!({
+                    "id": node.id,
+                    "name": node.name,
+                    "selfSize": node.self_size,
+                    "retainedSize": node.retained_size,
+                })
+            })
+            .collect();
+        let edges: Vec<_> = self
+            .edges
+            .iter()
+            .map(|edge| {
+                json!({
+                    "from": edge.from,
+                    "to": edge.to,
+                    "type": edge.kind.as_str(),
+                    "name": edge.name,
+                })
+            })
+            .collect();
+
+        json!({ "nodes": nodes, "edges": edges })
+    }
+
+    /// Exports the snapshot in the `.heapsnapshot` format of V8.
+    pub fn to_heapsnapshot(&self) -> JSONValue {
+        const NODE_TYPES: [&str; 5] = ["hidden", "object", "closure", "regexp", "synthetic"];
+        const EDGE_TYPES: [&str; 3] = ["property", "element", "internal"];
+        const NODE_FIELD_COUNT: usize = 5;
+
+        let mut strings = Vec::new();
+        let mut string_ids = FxHashMap::default();
+        let mut intern = |string: &str| -> usize {
+            *string_ids.entry(string.to_owned()).or_insert_with(|| {
+                strings.push(string.to_owned());
+                strings.len() - 1
+            })
+        };
+
+        let mut edge_counts = vec![0; self.nodes.len()];
+        for edge in &self.edges {
+            edge_counts[edge.from] += 1;
+        }
+
+        let mut nodes = Vec::with_capacity(self.nodes.len() * NODE_FIELD_COUNT);
+        for node in &self.nodes {
+            let node_type = match node.name.as_str() {
+                Self::ROOT_NAME => "synthetic",
+                "Function" => "closure",
+                "RegExp" => "regexp",
+                _ => "object",
+            };
+            nodes.push(NODE_TYPES.iter().position(|t| *t == node_type).unwrap_or(0));
+            nodes.push(intern(&node.name));
+            // Node ids start at 1 in V8 snapshots.
+            nodes.push(node.id + 1);
+            nodes.push(node.self_size);
+            nodes.push(edge_counts[node.id]);
+        }
+
+        let mut edges = Vec::with_capacity(self.edges.len() * 3);
+        for edge in &self.edges {
+            edges.push(
+                EDGE_TYPES
+                    .iter()
+                    .position(|t| *t == edge.kind.as_str())
+                    .unwrap_or(0),
+            );
+            let name_or_index = if edge.kind == HeapEdgeKind::Element {
+                edge.name.parse().unwrap_or_default()
+            } else {
+                intern(&edge.name)
+            };
+            edges.push(name_or_index);
+            edges.push(edge.to * NODE_FIELD_COUNT);
+        }
+
+        json!({
+            "snapshot": {
+                "meta": {
+                    "node_fields": ["type", "name", "id", "self_size", "edge_count"],
+                    "node_types": [NODE_TYPES, "string", "number", "number", "number"],
+                    "edge_fields": ["type", "name_or_index", "to_node"],
+                    "edge_types": [EDGE_TYPES, "string_or_number", "node"],
+                },
+                "node_count": self.nodes.len(),
+                "edge_count": self.edges.len(),
+            },
+            "nodes": nodes,
+            "edges": edges,
+            "strings": strings,
+        })
+    }
+
+    /// Computes the retained size of every node from the dominator tree of the heap graph.
+    ///
+    /// The dominators are computed with the algorithm of [Cooper, Harvey and Kennedy][paper].
+    ///
+    /// [paper]: https://www.cs.rice.edu/~keith/EMBED/dom.pdf
+    fn compute_retained_sizes(&mut self) {
+        let count = self.nodes.len();
+        let mut successors = vec![Vec::new(); count];
+        let mut predecessors = vec![Vec::new(); count];
+        for edge in &self.edges {
+            successors[edge.from].push(edge.to);
+            predecessors[edge.to].push(edge.from);
+        }
+
+        // Reverse postorder of a depth first search from the root.
+        let mut postorder = Vec::with_capacity(count);
+        let mut visited = vec![false; count];
+        let mut stack = vec![(0, 0)];
+        visited[0] = true;
+        while let Some((node, next)) = stack.pop() {
+            if let Some(&successor) = successors[node].get(next) {
+                stack.push((node, next + 1));
+                if !visited[successor] {
+                    visited[successor] = true;
+                    stack.push((successor, 0));
+                }
+            } else {
+                postorder.push(node);
+            }
+        }
+        let mut order = vec![0; count];
+        for (index, &node) in postorder.iter().enumerate() {
+            order[node] = index;
+        }
+
+        let mut dominators: Vec<Option<usize>> = vec![None; count];
+        dominators[0] = Some(0);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &node in postorder.iter().rev().skip(1) {
+                let mut new_dominator = None;
+                for &predecessor in &predecessors[node] {
+                    if dominators[predecessor].is_none() {
+                        continue;
+                    }
+                    new_dominator = Some(match new_dominator {
+                        None => predecessor,
+                        Some(dominator) => intersect(&dominators, &order, predecessor, dominator),
+                    });
+                }
+                if new_dominator != dominators[node] {
+                    dominators[node] = new_dominator;
+                    changed = true;
+                }
+            }
+        }
+
+        for node in &mut self.nodes {
+            node.retained_size = node.self_size;
+        }
+        // Children come before their dominators in postorder.
+        for &node in &postorder {
+            if let Some(dominator) = dominators[node].filter(|&dominator| dominator != node) {
+                self.nodes[dominator].retained_size += self.nodes[node].retained_size;
+            }
+        }
+    }
+}
+
+/// The number and the size of the objects of a kind, see `HeapStats`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeapKindStats {
+    /// The kind of the objects, like `"Object"`, `"Array"` or `"Function"`.
+    pub name: String,
+    /// How many objects of this kind are reachable.
+    pub count: usize,
+    /// The estimated size, in bytes, of the objects of this kind.
+    pub size: usize,
+}
+
+/// The statistics of the heap of an interpreter.
+///
+/// Like the snapshots, the statistics only count the objects that are reachable from the global
+/// object, and their sizes are estimates. The strings are counted once for each distinct
+/// content, whether they are property keys or values.
+///
+/// ```
+/// use boa::{forward, heap_snapshot::HeapStats, Interpreter, Realm};
+///
+/// let mut engine = Interpreter::new(Realm::create());
+/// forward(&mut engine, "var list = [{}, {}, {}];");
+/// let stats = HeapStats::capture(&engine);
+/// let arrays = stats.kinds.iter().find(|kind| kind.name == "Array").unwrap();
+/// assert!(arrays.count >= 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeapStats {
+    /// The number of reachable objects.
+    pub object_count: usize,
+    /// The estimated size, in bytes, of the reachable objects.
+    pub object_size: usize,
+    /// The objects of each kind, the largest first.
+    pub kinds: Vec<HeapKindStats>,
+    /// The number of distinct strings of the property keys and values.
+    pub string_count: usize,
+    /// The size, in bytes, of the distinct strings.
+    pub string_size: usize,
+    /// The net number of bytes allocated by the current thread, see
+    /// `memory::thread_allocated_bytes`. It is always `0` unless a `CountingAllocator` is the
+    /// global allocator.
+    pub allocated_bytes: isize,
+}
+
+impl HeapStats {
+    /// Takes a snapshot of the heap of `interpreter` and sums it up.
+    #[inline]
+    pub fn capture(interpreter: &Interpreter) -> Self {
+        HeapSnapshot::capture(interpreter).stats()
+    }
+
+    /// Returns the estimated size, in bytes, of the reachable objects and strings.
+    #[inline]
+    pub fn total_size(&self) -> usize {
+        self.object_size + self.string_size
+    }
+
+    /// Exports the statistics as JSON, an object with the `objectCount`, `objectSize`,
+    /// `stringCount`, `stringSize`, `totalSize` and `allocatedBytes`, and the `name`, `count` and
+    /// `size` of the `kinds`.
+    pub fn to_json(&self) -> JSONValue {
+        let kinds: Vec<_> = self
+            .kinds
+            .iter()
+            .map(|kind| json!({ "name": kind.name, "count": kind.count, "size": kind.size }))
+            .collect();
+        json!({
+            "objectCount": self.object_count,
+            "objectSize": self.object_size,
+            "kinds": kinds,
+            "stringCount": self.string_count,
+            "stringSize": self.string_size,
+            "totalSize": self.total_size(),
+            "allocatedBytes": self.allocated_bytes,
+        })
+    }
+}
+
+impl Display for HeapStats {
+    /// Formats the statistics as a table, with a row for each kind of object, the largest first,
+    /// followed by the strings and the totals.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<20} {:>10} {:>12}", "Kind", "Count", "Size (B)")?;
+        for kind in &self.kinds {
+            writeln!(f, "{:<20} {:>10} {:>12}", kind.name, kind.count, kind.size)?;
+        }
+        writeln!(
+            f,
+            "{:<20} {:>10} {:>12}",
+            "(strings)", self.string_count, self.string_size
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>10} {:>12}",
+            "Total",
+            self.object_count + self.string_count,
+            self.total_size()
+        )?;
+        if self.allocated_bytes != 0 {
+            writeln!(f, "Allocated by the thread: {} bytes", self.allocated_bytes)?;
+        }
+        Ok(())
+    }
+}
+
+/// Finds the closest common dominator of two nodes.
+fn intersect(dominators: &[Option<usize>], order: &[usize], mut a: usize, mut b: usize) -> usize {
+    while a != b {
+        while order[a] < order[b] {
+            a = dominators[a].expect("processed node");
+        }
+        while order[b] < order[a] {
+            b = dominators[b].expect("processed node");
+        }
+    }
+    a
+}
+
+/// Walks the heap and assigns an id to every reachable object.
+#[derive(Default)]
+struct Builder {
+    nodes: Vec<HeapNode>,
+    edges: Vec<HeapEdge>,
+    ids: FxHashMap<*const GcCell<Object>, usize>,
+    queue: VecDeque<(usize, GcObject)>,
+    strings: FxHashSet<JsString>,
+}
+
+impl Builder {
+    /// Records a reference from the node `from` to `value`, if it is an object.
+    fn reference(&mut self, from: usize, kind: HeapEdgeKind, name: String, value: &Value) {
+        let object = match value {
+            Value::Object(ref object) => object,
+            Value::String(ref string) => {
+                self.strings.insert(string.clone());
+                return;
+            }
+            _ => return,
+        };
+        let pointer: *const GcCell<Object> = object.as_ref();
+        let to = if let Some(&id) = self.ids.get(&pointer) {
+            id
+        } else {
+            let id = self.nodes.len();
+            self.ids.insert(pointer, id);
+            self.nodes.push(HeapNode {
+                id,
+                name: String::new(),
+                self_size: 0,
+                retained_size: 0,
+            });
+            self.queue.push_back((id, object.clone()));
+            id
+        };
+        self.edges.push(HeapEdge {
+            from,
+            to,
+            kind,
+            name,
+        });
+    }
+
+    /// Fills in the node `id` of `object` and records its references.
+    fn visit(&mut self, id: usize, object: &Object) {
+        let is_regexp =
+            matches!(object.state(), Some(state) if state.downcast_ref::<RegExp>().is_some());
+        self.nodes[id].name = match object.data {
+            _ if is_regexp => "RegExp".to_owned(),
+            ObjectData::Ordinary => "Object".to_owned(),
+            ref data => data.to_string(),
+        };
+
+        let keys = object.own_property_keys();
+        let mut self_size = size_of::<Object>() + keys.len() * size_of::<Property>();
+        for key in keys {
+            let property = match object.property(&key) {
+                Some(property) => property,
+                None => continue,
+            };
+            let kind = match key {
+                PropertyKey::Index(_) => HeapEdgeKind::Element,
+                PropertyKey::String(ref string) => {
+                    self.strings.insert(string.clone());
+                    HeapEdgeKind::Property
+                }
+                PropertyKey::Symbol(_) => HeapEdgeKind::Property,
+            };
+            if let Some(ref value) = property.value {
+                self.reference(id, kind, key.to_string(), value);
+            }
+            if let Some(ref get) = property.get {
+                self.reference(id, HeapEdgeKind::Internal, format!("get {}", key), get);
+            }
+            if let Some(ref set) = property.set {
+                self.reference(id, HeapEdgeKind::Internal, format!("set {}", key), set);
+            }
+        }
+
+        self.reference(
+            id,
+            HeapEdgeKind::Internal,
+            "__proto__".to_owned(),
+            &object.get_prototype_of(),
+        );
+        self_size += object.internal_slots().len() * size_of::<(String, Value)>();
+        for (name, value) in object.internal_slots() {
+            self.reference(id, HeapEdgeKind::Internal, format!("[[{}]]", name), value);
+        }
+        if let ObjectData::Map(ref map) = object.data {
+            self_size += map.len() * size_of::<(Value, Value)>();
+            for (index, (key, value)) in map.iter().enumerate() {
+                self.reference(id, HeapEdgeKind::Internal, format!("key {}", index), key);
+                self.reference(
+                    id,
+                    HeapEdgeKind::Internal,
+                    format!("value {}", index),
+                    value,
+                );
+            }
+        }
+
+        self.nodes[id].self_size = self_size;
+    }
+}
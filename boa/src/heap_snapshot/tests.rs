@@ -0,0 +1,169 @@
+use super::*;
+use crate::{forward, realm::Realm};
+
+/// Finds the node that the property `name` of the global object points to.
+fn global_property(snapshot: &HeapSnapshot, name: &str) -> usize {
+    let global = snapshot
+        .edges()
+        .iter()
+        .find(|edge| edge.from == 0 && edge.name == "global")
+        .expect("global edge")
+        .to;
+    snapshot
+        .edges()
+        .iter()
+        .find(|edge| edge.from == global && edge.name == name)
+        .expect("global property")
+        .to
+}
+
+#[test]
+fn captures_reachable_objects() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var data = { list: [{}, {}] };");
+
+    let snapshot = HeapSnapshot::capture(&engine);
+    assert_eq!(snapshot.nodes()[0].name, HeapSnapshot::ROOT_NAME);
+
+    let data = global_property(&snapshot, "data");
+    assert_eq!(snapshot.nodes()[data].name, "Object");
+    let list = snapshot
+        .edges()
+        .iter()
+        .find(|edge| edge.from == data && edge.name == "list")
+        .expect("list edge");
+    assert_eq!(list.kind, HeapEdgeKind::Property);
+    assert_eq!(snapshot.nodes()[list.to].name, "Array");
+
+    let elements: Vec<_> = snapshot
+        .edges()
+        .iter()
+        .filter(|edge| edge.from == list.to && edge.kind == HeapEdgeKind::Element)
+        .collect();
+    assert_eq!(elements.len(), 2);
+}
+
+#[test]
+fn retained_sizes() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var owner = { child: { grandchild: {} } };
+        owner.self = owner;
+        var shared = {};
+        var first = { shared: shared };
+        var second = { shared: shared };
+        "#;
+    forward(&mut engine, init);
+
+    let snapshot = HeapSnapshot::capture(&engine);
+    let node = |name| &snapshot.nodes()[global_property(&snapshot, name)];
+
+    let owner = node("owner");
+    let child = &snapshot.nodes()[snapshot
+        .edges()
+        .iter()
+        .find(|edge| edge.from == owner.id && edge.name == "child")
+        .expect("child edge")
+        .to];
+    assert!(child.retained_size > child.self_size);
+    assert!(owner.retained_size >= owner.self_size + child.retained_size);
+
+    // An object reachable from two places is retained by neither of them.
+    assert_eq!(node("first").retained_size, node("first").self_size);
+    assert!(snapshot.nodes()[0].retained_size >= node("shared").retained_size);
+}
+
+#[test]
+fn heapsnapshot_format() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var data = [1, {}];");
+
+    let snapshot = HeapSnapshot::capture(&engine);
+    let exported = snapshot.to_heapsnapshot();
+    let node_fields = exported["snapshot"]["meta"]["node_fields"]
+        .as_array()
+        .unwrap()
+        .len();
+    let edge_fields = exported["snapshot"]["meta"]["edge_fields"]
+        .as_array()
+        .unwrap()
+        .len();
+
+    assert_eq!(
+        exported["nodes"].as_array().unwrap().len(),
+        snapshot.nodes().len() * node_fields
+    );
+    assert_eq!(
+        exported["edges"].as_array().unwrap().len(),
+        snapshot.edges().len() * edge_fields
+    );
+    assert_eq!(
+        exported["strings"][exported["nodes"][1].as_u64().unwrap() as usize],
+        HeapSnapshot::ROOT_NAME
+    );
+}
+
+#[test]
+fn json_format() {
+    let realm = Realm::create();
+    let engine = Interpreter::new(realm);
+
+    let snapshot = HeapSnapshot::capture(&engine);
+    let exported = snapshot.to_json();
+
+    assert_eq!(exported["nodes"][0]["name"], HeapSnapshot::ROOT_NAME);
+    assert_eq!(exported["edges"][0]["from"], 0);
+    assert_eq!(exported["edges"][0]["type"], "property");
+    assert_eq!(exported["edges"][0]["name"], "global");
+}
+
+#[test]
+fn heap_stats() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let before = HeapStats::capture(&engine);
+    forward(
+        &mut engine,
+        "var data = [{ greeting: 'hello' }, { greeting: 'hello' }, {}];",
+    );
+
+    let stats = HeapStats::capture(&engine);
+    let kind = |stats: &HeapStats, name: &str| {
+        stats
+            .kinds
+            .iter()
+            .find(|kind| kind.name == name)
+            .map_or(0, |kind| kind.count)
+    };
+    assert_eq!(kind(&stats, "Array"), kind(&before, "Array") + 1);
+    assert_eq!(kind(&stats, "Object"), kind(&before, "Object") + 3);
+    assert_eq!(stats.object_count, before.object_count + 4);
+    assert!(stats.object_size > before.object_size);
+    // "data", "greeting" and "hello" are new strings, counted once each.
+    assert_eq!(stats.string_count, before.string_count + 3);
+    assert_eq!(
+        stats.string_size,
+        before.string_size + "data".len() + "greeting".len() + "hello".len()
+    );
+    assert_eq!(stats.total_size(), stats.object_size + stats.string_size);
+    assert!(stats.kinds.windows(2).all(|w| w[0].size >= w[1].size));
+}
+
+#[test]
+fn heap_stats_formats() {
+    let realm = Realm::create();
+    let engine = Interpreter::new(realm);
+
+    let stats = HeapStats::capture(&engine);
+    let exported = stats.to_json();
+    assert_eq!(exported["objectCount"], stats.object_count);
+    assert_eq!(exported["totalSize"], stats.total_size());
+    assert_eq!(exported["kinds"][0]["name"], stats.kinds[0].name.as_str());
+
+    let table = stats.to_string();
+    assert!(table.starts_with("Kind"));
+    assert!(table.contains("(strings)"));
+}
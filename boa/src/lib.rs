@@ -1,4 +1,27 @@
 //! This is an experimental Javascript lexer, parser and compiler written in Rust. Currently, it has support for some of the language.
+//!
+//! The entry point for embedding the engine is [`Context`](context/struct.Context.html):
+//!
+//! ```
+//! use boa::Context;
+//!
+//! let mut context = Context::builder().max_array_length(1 << 20).build();
+//! context.eval("var greeting = 'Hello'").unwrap();
+//! let value = context.eval("greeting + ', world!'").unwrap();
+//! assert_eq!(value.to_string(), "Hello, world!");
+//! ```
+//!
+//! The engine keeps no mutable state outside of its contexts, apart from the profiler of the
+//! `profiler` feature, which the threads share, so separate threads can each run their own
+//! `Context` at the same time. A context and its values can not be sent to another
+//! thread, but its [`InterruptHandle`](exec/struct.InterruptHandle.html) can, to stop its
+//! scripts from the outside.
+//!
+//! The engine reports what it does with [`tracing`](https://docs.rs/tracing) spans and events,
+//! which embedders see by installing a subscriber. Scripts are run in a `run` span and function
+//! calls in `call` spans, at the debug level like lexing, parsing and forced garbage
+//! collections. Each production of the parser has its own span, and each token and property
+//! lookup its own event, at the trace level.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/jasonwilliams/boa/master/assets/logo.svg",
@@ -34,65 +57,323 @@
     missing_doc_code_examples
 )]
 
+pub mod allocation_sites;
+pub mod annex_b;
 pub mod builtins;
+pub mod class;
+pub mod context;
+pub mod coverage;
+pub mod cpu_profile;
+pub mod debugger;
 pub mod environment;
+pub mod error;
 pub mod exec;
+pub mod execution_profile;
+pub mod experimental;
+pub mod heap_snapshot;
+pub mod memory;
+pub mod phase_timings;
 pub mod profiler;
 pub mod realm;
+#[cfg(feature = "serde")]
+pub mod snapshot;
+pub mod source_map;
 pub mod syntax;
+pub mod timeline;
 
-use crate::{builtins::value::ResultValue, syntax::ast::node::StatementList};
+use crate::{
+    builtins::{
+        object::ObjectData,
+        value::{JsString, ResultValue, Value},
+    },
+    experimental::Experimental,
+    phase_timings::Phase,
+    syntax::ast::{
+        node::{Node, StatementList},
+        token::Token,
+    },
+    syntax::parser::error::ParseError,
+};
 pub use crate::{
+    context::{Context, ContextBuilder, FunctionOrigin},
+    error::JsError,
     exec::{Executable, Interpreter},
     profiler::BoaProfiler,
-    realm::Realm,
+    realm::{Realm, RealmId},
     syntax::{lexer::Lexer, parser::Parser},
 };
 
-fn parser_expr(src: &str) -> Result<StatementList, String> {
-    let mut lexer = Lexer::new(src);
-    lexer.lex().map_err(|e| format!("Syntax Error: {}", e))?;
-    let tokens = lexer.tokens;
-    Parser::new(&tokens)
+/// The settings of the interpreter that a script is parsed for by `parse`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParseOptions {
+    /// Only parse the body of each function when the function is first called, see
+    /// `Interpreter::set_lazy_parsing`.
+    pub lazy_functions: bool,
+    /// Parse the script as strict mode code, see `Interpreter::set_strict`.
+    pub strict: bool,
+    /// The experimental syntax that is recognized, see `experimental::Experimental`.
+    pub experimental: Experimental,
+    /// Recognize the HTML-like comments of `annex_b`, see `Interpreter::set_annex_b`.
+    pub annex_b: bool,
+}
+
+impl Default for ParseOptions {
+    /// Returns the options of a new interpreter.
+    fn default() -> Self {
+        Self {
+            lazy_functions: false,
+            strict: false,
+            experimental: Experimental::default(),
+            annex_b: true,
+        }
+    }
+}
+
+impl From<&Interpreter> for ParseOptions {
+    /// Returns the options that the interpreter parses its scripts with.
+    fn from(engine: &Interpreter) -> Self {
+        Self {
+            lazy_functions: engine.lazy_parsing(),
+            strict: engine.strict(),
+            experimental: engine.experimental(),
+            annex_b: engine.annex_b(),
+        }
+    }
+}
+
+/// Parses a script without running it.
+///
+/// Parsing doesn't need an interpreter, so scripts can be parsed on other threads and run later
+/// with `forward_parsed`, with the options of the interpreter that runs them, see
+/// `ParseOptions`.
+///
+/// The error is always a `JsError::Syntax`.
+pub fn parse(src: &str, options: ParseOptions) -> Result<StatementList, JsError> {
+    let tokens = lex(src, options.strict, options.experimental, options.annex_b)?;
+    parse_tokens(&tokens, options.lazy_functions)
+}
+
+/// Checks if a script ends before a construct it started is complete, so that more source
+/// could make it parse, like an unclosed bracket, template literal or block comment, an `if`
+/// without a body, or a trailing binary operator.
+///
+/// Interactive consoles use it to read more lines before they run the script:
+///
+/// ```
+/// assert!(boa::is_incomplete("if (ready)"));
+/// assert!(boa::is_incomplete("1 +"));
+/// assert!(!boa::is_incomplete("1 + 2"));
+/// // More source can't fix other syntax errors.
+/// assert!(!boa::is_incomplete("1 + )"));
+/// ```
+///
+/// The experimental syntax is recognized, see `experimental::Experimental`, and so are the
+/// HTML-like comments of `annex_b`.
+pub fn is_incomplete(src: &str) -> bool {
+    let mut lexer = Lexer::new(src)
+        .experimental(Experimental::all())
+        .html_comments(true);
+    if let Err(error) = lexer.lex() {
+        return error.is_abrupt_end();
+    }
+    matches!(
+        Parser::new(&lexer.tokens).parse_all(),
+        Err(ParseError::AbruptEnd)
+    )
+}
+
+/// Formats a script with consistent indentation and spacing, keeping its comments and the
+/// blank lines between its statements, see `syntax::codegen::format`.
+///
+/// If `strict` is set, the script is parsed as strict mode code. The error is always a
+/// `JsError::Syntax`.
+pub fn format(src: &str, strict: bool) -> Result<String, JsError> {
+    let mut lexer = Lexer::new(src).strict(strict).keep_comments(true);
+    run_lexer(&mut lexer)?;
+    let statements = parse_tokens(&lexer.tokens, false)?;
+    Ok(syntax::codegen::format(
+        &statements,
+        &lexer.tokens,
+        &lexer.comments,
+    ))
+}
+
+/// Lexes a script into tokens, recognizing HTML-like comments if `html_comments` is set.
+fn lex(
+    src: &str,
+    strict: bool,
+    experimental: Experimental,
+    html_comments: bool,
+) -> Result<Vec<Token>, JsError> {
+    let mut lexer = Lexer::new(src)
+        .strict(strict)
+        .experimental(experimental)
+        .html_comments(html_comments);
+    run_lexer(&mut lexer)?;
+    Ok(lexer.tokens)
+}
+
+/// Lexes the whole source of a lexer.
+fn run_lexer(lexer: &mut Lexer<'_>) -> Result<(), JsError> {
+    lexer.lex().map_err(|e| JsError::Syntax {
+        message: format!("Syntax Error: {}", e),
+        position: Some(e.position().unwrap_or_else(|| Lexer::position(lexer))),
+        script: None,
+    })
+}
+
+/// Parses the tokens of a script.
+fn parse_tokens(tokens: &[Token], lazy_functions: bool) -> Result<StatementList, JsError> {
+    Parser::new(tokens)
+        .lazy_functions(lazy_functions)
         .parse_all()
-        .map_err(|e| format!("Parsing Error: {}", e))
+        .map_err(|e| JsError::Syntax {
+            message: format!("Parsing Error: {}", e),
+            position: e.position(),
+            script: None,
+        })
+}
+
+/// Parses the source, and optimizes it if the interpreter is set to, recording it as a
+/// `compile` event on the timeline.
+fn compile(engine: &mut Interpreter, src: &str) -> Result<StatementList, JsError> {
+    let start = engine.timeline_start();
+    let phase_start = engine.phase_start();
+    let tokens = lex(
+        src,
+        engine.strict(),
+        engine.experimental(),
+        engine.annex_b(),
+    );
+    let phase_start = engine.record_phase(Phase::Lex, phase_start);
+    let mut result = tokens.and_then(|tokens| parse_tokens(&tokens, engine.lazy_parsing()));
+    if engine.optimize() {
+        result = result.map(|statements| syntax::optimizer::optimize(&statements));
+    }
+    engine.record_phase(Phase::Parse, phase_start);
+    engine.record_timeline_event("compile", "compile", start);
+    result
+}
+
+/// Runs the parsed code and then the microtasks it queued, recording it as a `script` event on
+/// the timeline.
+///
+/// If the script succeeds but one of the microtasks throws, the thrown value is returned.
+fn run(engine: &mut Interpreter, expr: &StatementList) -> ResultValue {
+    let _span = tracing::debug_span!("run", script = engine.script_name()).entered();
+    let start = engine.timeline_start();
+    let phase_start = engine.phase_start();
+    let _memory = engine.count_memory();
+    engine.start_script();
+    engine.enter_script_frame();
+    let sites = engine.add_coverage_script(expr);
+    let result = expr.run(engine);
+    if engine.is_terminated() {
+        engine.reset_execution_state();
+    }
+    let jobs = engine.run_jobs();
+    if let (Some(sites), Some(coverage)) = (sites, engine.coverage_mut()) {
+        coverage.forget_script(sites);
+    }
+    engine.exit_script_frame();
+    engine.record_phase(Phase::Execute, phase_start);
+    engine.record_timeline_event("script", "script", start);
+    let value = result?;
+    jobs.map(|_| value)
 }
 
 /// Execute the code using an existing Interpreter
 /// The str is consumed and the state of the Interpreter is changed
 pub fn forward(engine: &mut Interpreter, src: &str) -> String {
     // Setup executor
-    let expr = match parser_expr(src) {
+    let expr = match compile(engine, src) {
         Ok(res) => res,
-        Err(e) => return e,
+        Err(e) => return e.to_string(),
     };
-    expr.run(engine)
-        .map_or_else(|e| format!("Error: {}", e), |v| v.to_string())
+    run(engine, &expr).map_or_else(|e| format!("Error: {}", e), |v| v.to_string())
 }
 
 /// Execute the code using an existing Interpreter.
 /// The str is consumed and the state of the Interpreter is changed
 /// Similar to `forward`, except the current value is returned instad of the string
-/// If the interpreter fails parsing an error value is returned instead (error object)
+/// If the script fails, a `JsError` describing the failure is returned instead
 #[allow(clippy::unit_arg, clippy::drop_copy)]
-pub fn forward_val(engine: &mut Interpreter, src: &str) -> ResultValue {
+pub fn forward_val(engine: &mut Interpreter, src: &str) -> Result<Value, JsError> {
     let main_timer = BoaProfiler::global().start_event("Main", "Main");
     // Setup executor
-    let result = match parser_expr(src) {
-        Ok(expr) => expr.run(engine),
-        Err(e) => {
-            eprintln!("{}", e);
-            std::process::exit(1);
-        }
-    };
+    let result = compile(engine, src)
+        .and_then(|expr| run(engine, &expr).map_err(|value| JsError::from_thrown(value, engine)));
 
-    // The main_timer needs to be dropped before the BoaProfiler is.
     drop(main_timer);
-    BoaProfiler::global().drop();
 
     result
 }
 
+/// Runs a script parsed with `parse`, like `forward_val` runs a source.
+///
+/// The script is optimized first if the interpreter is set to, see `Interpreter::set_optimize`.
+pub fn forward_parsed(
+    engine: &mut Interpreter,
+    statements: &StatementList,
+) -> Result<Value, JsError> {
+    let optimized;
+    let statements = if engine.optimize() {
+        optimized = syntax::optimizer::optimize(statements);
+        &optimized
+    } else {
+        statements
+    };
+    run(engine, statements).map_err(|value| JsError::from_thrown(value, engine))
+}
+
+/// Creates a function with the given parameters and body in the global scope, like
+/// `new Function(...params, body)` does, without running it.
+///
+/// Hosts use it to run code with bindings of their own that are not globals, like the
+/// `require` and `module` of CommonJS modules, by calling the function with
+/// `Interpreter::call`. The lines of the body keep their numbers in the positions of its
+/// errors, and the stack traces show them in the script named `name`, like the path of the file
+/// of the body.
+///
+/// The error is always a `JsError::Syntax`.
+pub fn compile_function(
+    engine: &mut Interpreter,
+    name: &str,
+    params: &[&str],
+    body: &str,
+) -> Result<Value, JsError> {
+    let src = format!("(function ({}) {{{}\n}})", params.join(", "), body);
+    let statements = compile(engine, &src)?;
+    let function = match statements.statements() {
+        [Node::FunctionExpr(function)] => function,
+        _ => {
+            return Err(JsError::Syntax {
+                message: "Parsing Error: the body closes the function".to_owned(),
+                position: None,
+                script: None,
+            })
+        }
+    };
+
+    // The scopes of the running code are not visible from the function.
+    let environment = &mut engine.realm_mut().environment;
+    let mut scopes = Vec::new();
+    while environment.environments().count() > 1 {
+        scopes.extend(environment.pop());
+    }
+    let result = function.run(engine);
+    let environment = &mut engine.realm_mut().environment;
+    for scope in scopes.into_iter().rev() {
+        environment.push(scope);
+    }
+    if let Ok(Value::Object(ref object)) = result {
+        if let ObjectData::Function(ref mut function) = object.borrow_mut().data {
+            function.set_script(Some(JsString::from(name)));
+        }
+    }
+    result.map_err(|value| JsError::from_thrown(value, engine))
+}
+
 /// Create a clean Interpreter and execute the code
 pub fn exec(src: &str) -> String {
     // Create new Realm
@@ -0,0 +1,163 @@
+//! This module implements the accounting of the memory allocated by scripts.
+//!
+//! The garbage collector does not know the size of the objects it manages, so the memory of
+//! scripts is measured at the allocator: install a `CountingAllocator` as the global allocator
+//! of the program, enable it, and set a limit with `ContextBuilder::memory_limit` or
+//! `Interpreter::set_memory_limit`.
+//!
+//! ```
+//! use boa::{memory::CountingAllocator, Context};
+//! use std::alloc::System;
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator<System> = CountingAllocator::new(System);
+//!
+//! fn main() {
+//!     ALLOCATOR.enable();
+//!     let mut context = Context::builder().memory_limit(1024 * 1024).build();
+//!     let result = context.eval("let a = []; while (true) { a.push({}) }");
+//!     assert!(result.is_err());
+//!     assert!(context.is_terminated());
+//! }
+//! ```
+//!
+//! Each interpreter has its own count, which is charged with the bytes allocated and freed
+//! while its scripts and their jobs run, and is kept from one script to the next. The memory
+//! freed by the garbage collector is charged to the interpreter that is running when it
+//! collects, so a program that runs several interpreters on a thread should give each of them
+//! its own thread to keep their counts apart.
+
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    cell::Cell,
+    rc::Rc,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+thread_local! {
+    /// The bytes allocated and not yet freed by the current thread.
+    static ALLOCATED: Cell<isize> = const { Cell::new(0) };
+
+    /// The count of the interpreter that is running on the current thread, if any.
+    static RUNNING: Cell<Option<Rc<Cell<isize>>>> = const { Cell::new(None) };
+}
+
+/// A global allocator that counts the bytes allocated by each thread and each interpreter, and
+/// delegates the allocations to another allocator.
+///
+/// Nothing is counted until the allocator is enabled with `CountingAllocator::enable`, so a
+/// program can install it and only pay for the counting when it sets a memory limit.
+#[derive(Debug, Default)]
+pub struct CountingAllocator<A> {
+    inner: A,
+    enabled: AtomicBool,
+}
+
+impl<A> CountingAllocator<A> {
+    /// Creates an allocator that counts the allocations made with `inner` once it is enabled.
+    #[inline]
+    pub const fn new(inner: A) -> Self {
+        Self {
+            inner,
+            enabled: AtomicBool::new(false),
+        }
+    }
+
+    /// Starts counting the allocations.
+    ///
+    /// The allocator should be enabled before the interpreters are created, since the memory
+    /// freed afterwards is counted even if its allocation was not.
+    #[inline]
+    pub fn enable(&self) {
+        self.enabled.store(true, Ordering::Relaxed);
+    }
+
+    /// Adds `bytes` to the counts of the current thread and of the running interpreter.
+    #[inline]
+    fn count(&self, bytes: isize) {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+        // The counts are not available while the thread is being destroyed.
+        let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get().wrapping_add(bytes)));
+        let _ = RUNNING.try_with(|running| {
+            if let Some(count) = running.take() {
+                count.set(count.get().wrapping_add(bytes));
+                running.set(Some(count));
+            }
+        });
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = self.inner.alloc(layout);
+        if !pointer.is_null() {
+            self.count(layout.size() as isize);
+        }
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer = self.inner.alloc_zeroed(layout);
+        if !pointer.is_null() {
+            self.count(layout.size() as isize);
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        self.inner.dealloc(pointer, layout);
+        self.count(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_pointer = self.inner.realloc(pointer, layout, new_size);
+        if !new_pointer.is_null() {
+            self.count(new_size as isize - layout.size() as isize);
+        }
+        new_pointer
+    }
+}
+
+/// Gets the net number of bytes allocated by the current thread, counted by a
+/// `CountingAllocator`.
+///
+/// The number can be negative if the thread freed memory allocated by other threads.
+#[inline]
+pub fn thread_allocated_bytes() -> isize {
+    ALLOCATED.try_with(Cell::get).unwrap_or(0)
+}
+
+/// The bytes allocated by the scripts of an interpreter, counted by a `CountingAllocator`.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct MemoryCount(Rc<Cell<isize>>);
+
+impl MemoryCount {
+    /// Gets the net number of bytes allocated while the count was running.
+    #[inline]
+    pub(crate) fn allocated_bytes(&self) -> isize {
+        self.0.get()
+    }
+
+    /// Charges the allocations of the current thread to this count, until the returned guard
+    /// is dropped.
+    pub(crate) fn run(&self) -> RunningCount {
+        let previous = RUNNING.with(|running| running.replace(Some(self.0.clone())));
+        RunningCount { previous }
+    }
+}
+
+/// Charges the allocations of the current thread to a count while it lives, see
+/// `MemoryCount::run`.
+#[derive(Debug)]
+pub(crate) struct RunningCount {
+    previous: Option<Rc<Cell<isize>>>,
+}
+
+impl Drop for RunningCount {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        let _ = RUNNING.try_with(|running| running.set(previous));
+    }
+}
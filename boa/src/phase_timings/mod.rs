@@ -0,0 +1,77 @@
+//! This module implements the phase timings.
+//!
+//! The timings measure how long the engine spends lexing, parsing and running the scripts it
+//! evaluates, so that users can tell which phase makes a script slow. They add up the phases of
+//! every script evaluated since they were last taken with `Interpreter::take_timings`, and only
+//! record anything once they have been enabled with `Interpreter::enable_timings`.
+//!
+//! The optimization pass is counted as parsing. When function bodies are parsed lazily, they
+//! are parsed while the script runs, so their parsing is counted as execution.
+
+#[cfg(test)]
+mod tests;
+
+use std::{
+    fmt::{self, Display},
+    time::Duration,
+};
+
+/// A phase of the evaluation of a script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Turning the source code into tokens.
+    Lex,
+    /// Turning the tokens into a syntax tree.
+    Parse,
+    /// Running the syntax tree and the microtasks it queued.
+    Execute,
+}
+
+/// The time spent in each phase of the evaluation of scripts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseTimings {
+    /// The time spent turning the source code into tokens.
+    pub lex: Duration,
+    /// The time spent turning the tokens into a syntax tree.
+    pub parse: Duration,
+    /// The time spent running the syntax tree.
+    pub execute: Duration,
+}
+
+impl PhaseTimings {
+    /// Creates new timings, where every phase took no time.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the given time to a phase.
+    #[inline]
+    pub fn add(&mut self, phase: Phase, time: Duration) {
+        match phase {
+            Phase::Lex => self.lex += time,
+            Phase::Parse => self.parse += time,
+            Phase::Execute => self.execute += time,
+        }
+    }
+
+    /// Returns the time spent in all the phases.
+    #[inline]
+    pub fn total(&self) -> Duration {
+        self.lex + self.parse + self.execute
+    }
+}
+
+impl Display for PhaseTimings {
+    /// Formats the timings on a single line, in milliseconds.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "lex {:.3} ms, parse {:.3} ms, execute {:.3} ms, total {:.3} ms",
+            self.lex.as_secs_f64() * 1000.0,
+            self.parse.as_secs_f64() * 1000.0,
+            self.execute.as_secs_f64() * 1000.0,
+            self.total().as_secs_f64() * 1000.0
+        )
+    }
+}
@@ -0,0 +1,47 @@
+use super::*;
+use crate::{exec::Interpreter, forward, realm::Realm};
+
+#[test]
+fn timings_are_disabled_by_default() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "1 + 1");
+
+    assert!(engine.take_timings().is_none());
+}
+
+#[test]
+fn timings_add_up_until_taken() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.enable_timings();
+    let scenario = r#"
+        let sum = 0;
+        for (let i = 0; i < 100; i++) {
+            sum += i;
+        }
+        sum
+    "#;
+    assert_eq!(forward(&mut engine, scenario), "4950");
+
+    let timings = engine.take_timings().expect("timings were enabled");
+    assert!(timings.execute > Duration::default());
+    assert_eq!(
+        timings.total(),
+        timings.lex + timings.parse + timings.execute
+    );
+    assert_eq!(engine.take_timings(), Some(PhaseTimings::new()));
+}
+
+#[test]
+fn display_timings() {
+    let mut timings = PhaseTimings::new();
+    timings.add(Phase::Lex, Duration::from_micros(250));
+    timings.add(Phase::Execute, Duration::from_millis(2));
+    timings.add(Phase::Execute, Duration::from_millis(1));
+
+    assert_eq!(
+        timings.to_string(),
+        "lex 0.250 ms, parse 0.000 ms, execute 3.000 ms, total 3.250 ms"
+    );
+}
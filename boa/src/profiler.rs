@@ -2,13 +2,14 @@
 
 #[cfg(feature = "profiler")]
 use measureme::{EventId, Profiler, TimingGuard};
-#[cfg(feature = "profiler")]
-use once_cell::sync::OnceCell;
 use std::fmt::{self, Debug};
 #[cfg(feature = "profiler")]
 use std::{
     path::Path,
-    thread::{current, ThreadId},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Mutex,
+    },
 };
 
 /// MmapSerializatioSink is faster on macOS and Linux
@@ -24,17 +25,29 @@ pub struct BoaProfiler {
     profiler: Profiler<SerializationSink>,
 }
 
-/// This static instance should never be public, and its only access should be done through the `global()` and `drop()` methods
-/// This is because `get_or_init` manages synchronisation and the case of an empty value
+/// The global profiler, shared by the threads, which is created by `global()` and freed by
+/// `drop()`.
+///
+/// The profile is only written when the profiler is dropped, so it is leaked while it records
+/// events instead of living in a `OnceLock`, which could not give it back.
+#[cfg(feature = "profiler")]
+static INSTANCE: Mutex<Option<&'static BoaProfiler>> = Mutex::new(None);
+
 #[cfg(feature = "profiler")]
-static mut INSTANCE: OnceCell<BoaProfiler> = OnceCell::new();
+thread_local! {
+    /// The number that identifies the current thread in the profile.
+    static THREAD_ID: u32 = {
+        static NEXT_THREAD_ID: AtomicU32 = AtomicU32::new(0);
+        NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed)
+    };
+}
 
 #[cfg(feature = "profiler")]
 impl BoaProfiler {
     pub fn start_event(&self, label: &str, category: &str) -> TimingGuard<'_, SerializationSink> {
         let kind = self.profiler.alloc_string(category);
         let id = EventId::from_label(self.profiler.alloc_string(label));
-        let thread_id = Self::thread_id_to_u32(current().id());
+        let thread_id = THREAD_ID.with(|id| *id);
         self.profiler
             .start_recording_interval_event(kind, id, thread_id)
     }
@@ -45,28 +58,23 @@ impl BoaProfiler {
     }
 
     pub fn global() -> &'static BoaProfiler {
-        unsafe { INSTANCE.get_or_init(Self::default) }
+        let mut instance = INSTANCE.lock().unwrap_or_else(|e| e.into_inner());
+        instance.get_or_insert_with(|| Box::leak(Box::new(Self::default())))
     }
 
-    pub fn drop(&self) {
-        // In order to drop the INSTANCE we need to get ownership of it, which isn't possible on a static unless you make it a mutable static
-        // mutating statics is unsafe, so we need to wrap it as so.
-        // This is actually safe though because init and drop are only called at the beginning and end of the application
-        unsafe {
-            INSTANCE
-                .take()
-                .expect("Could not take back profiler instance");
+    /// Writes the profile and drops the global profiler.
+    ///
+    /// # Safety
+    ///
+    /// The references returned by `global()` and the events they started must not be used
+    /// afterwards, so it should be called once, when the application exits, after the other
+    /// threads stopped recording events.
+    pub unsafe fn drop() {
+        let instance = INSTANCE.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(profiler) = instance {
+            drop(Box::from_raw(profiler as *const Self as *mut Self));
         }
     }
-
-    // Sadly we need to use the unsafe method until this is resolved:
-    // https://github.com/rust-lang/rust/issues/67939
-    // Once `as_64()` is in stable we can do this:
-    // https://github.com/rust-lang/rust/pull/68531/commits/ea42b1c5b85f649728e3a3b334489bac6dce890a
-    // Until then our options are: use rust-nightly or use unsafe {}
-    fn thread_id_to_u32(tid: ThreadId) -> u32 {
-        unsafe { std::mem::transmute::<ThreadId, u64>(tid) as u32 }
-    }
 }
 
 impl Debug for BoaProfiler {
@@ -85,9 +93,12 @@ impl BoaProfiler {
         ()
     }
 
-    pub fn drop(&self) {
-        ()
-    }
+    /// Does nothing without the `profiler` feature.
+    ///
+    /// # Safety
+    ///
+    /// The same as with the `profiler` feature, where it frees the global profiler.
+    pub unsafe fn drop() {}
 
     pub fn global() -> BoaProfiler {
         BoaProfiler
@@ -5,10 +5,13 @@
 //! A realm is represented in this implementation as a Realm struct with the fields specified from the spec.
 
 use crate::{
+    annex_b,
     builtins::{
         self,
         function::{Function, NativeFunctionData},
-        value::Value,
+        object::PROTOTYPE,
+        property::{Attribute, Property},
+        value::{same_value, Value},
     },
     environment::{
         declarative_environment_record::DeclarativeEnvironmentRecord,
@@ -21,6 +24,12 @@ use crate::{
 use gc::{Gc, GcCell};
 use rustc_hash::{FxHashMap, FxHashSet};
 
+/// Identifies a realm of an interpreter, see `Interpreter::create_realm`.
+///
+/// The realm the interpreter was created with is the default one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RealmId(pub(crate) usize);
+
 /// Representation of a Realm.
 ///
 /// In the specification these are called Realm Records.
@@ -29,9 +38,31 @@ pub struct Realm {
     pub global_obj: Value,
     pub global_env: Gc<GcCell<GlobalEnvironmentRecord>>,
     pub environment: LexicalEnvironment,
+    /// The prototype of the functions, kept here so that the engine can create functions after
+    /// the `Function` builtin is removed.
+    function_prototype: Value,
 }
 
 impl Realm {
+    /// The builtins that the engine looks up on the global object while it runs scripts, for
+    /// instance to create array literals or to throw a `TypeError`, so they can not be removed.
+    pub const REQUIRED_BUILTINS: &'static [&'static str] = &[
+        "Object",
+        "Array",
+        "Boolean",
+        "Number",
+        "String",
+        "Symbol",
+        "BigInt",
+        "Map",
+        "RegExp",
+        "RangeError",
+        "ReferenceError",
+        "SyntaxError",
+        "TypeError",
+        "URIError",
+    ];
+
     pub fn create() -> Self {
         let _timer = BoaProfiler::global().start_event("Realm::create", "realm");
         // Create brand new global object
@@ -40,25 +71,41 @@ impl Realm {
         // We need to clone the global here because its referenced from separate places (only pointer is cloned)
         let global_env = new_global_environment(global.clone(), global.clone());
 
-        let new_realm = Self {
+        let mut new_realm = Self {
             global_obj: global.clone(),
             global_env,
-            environment: LexicalEnvironment::new(global),
+            environment: LexicalEnvironment::new(global.clone()),
+            function_prototype: Value::undefined(),
         };
 
         // Add new builtIns to Realm
         // At a later date this can be removed from here and called explicity, but for now we almost always want these default builtins
         new_realm.create_instrinsics();
+        new_realm.function_prototype = global.get_field("Function").get_field(PROTOTYPE);
 
         new_realm
     }
 
+    /// Returns `Function.prototype`, the prototype of the functions of the realm, even if the
+    /// `Function` builtin has been removed.
+    pub fn function_prototype(&self) -> Value {
+        self.function_prototype.clone()
+    }
+
     // Sets up the default global objects within Global
     fn create_instrinsics(&self) {
         let _timer = BoaProfiler::global().start_event("create_instrinsics", "realm");
         let global = &self.global_obj;
         // Create intrinsics, add global objects here
         builtins::init(global);
+        annex_b::install_builtins(self, true);
+
+        // The global object is an ordinary object, so it inherits from `Object.prototype`.
+        let object_prototype = global.get_field("Object").get_field(PROTOTYPE);
+        global
+            .as_object_mut()
+            .expect("global object")
+            .set_prototype(object_prototype);
     }
 
     /// Utility to add a function to the global object
@@ -69,6 +116,79 @@ impl Realm {
 
         self
     }
+
+    /// Removes a builtin from the global object, like `"Math"` or `"Promise"`, so that the
+    /// scripts of the realm can not use it.
+    ///
+    /// A constructor is also removed from the `constructor` property of its prototype, so that
+    /// it can not be reached from its instances either.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is one of the `REQUIRED_BUILTINS`.
+    pub fn remove_builtin(&self, name: &str) {
+        assert!(
+            !Self::REQUIRED_BUILTINS.contains(&name),
+            "the `{}` builtin is required by the engine",
+            name
+        );
+        let builtin = self.global_obj.get_field(name);
+        if builtin.is_function() {
+            let prototype = builtin.get_field(PROTOTYPE);
+            if prototype.is_object() && same_value(&prototype.get_field("constructor"), &builtin) {
+                prototype.remove_property("constructor");
+            }
+        }
+        self.global_obj.remove_property(name);
+    }
+
+    /// Defines a global that the scripts of the realm can read but not change.
+    ///
+    /// The global is read-only and can not be deleted, and if its value is an object, the
+    /// object and the objects held by its properties are frozen, like with `Object.freeze`.
+    pub fn define_frozen_global(&self, name: &str, value: Value) {
+        freeze_deep(&value, &mut Vec::new());
+        self.global_obj.set_property(
+            name,
+            Property::data_descriptor(
+                value,
+                Attribute::READONLY | Attribute::ENUMERABLE | Attribute::PERMANENT,
+            ),
+        );
+    }
+
+    /// Freezes the global object, so that the scripts of the realm can not add, change or
+    /// delete global properties.
+    ///
+    /// Global `let` and `const` declarations are not properties of the global object, so they
+    /// still work.
+    pub fn freeze_global_object(&self) {
+        self.global_obj
+            .as_object_mut()
+            .expect("global object")
+            .freeze();
+    }
+}
+
+/// Freezes an object and, recursively, the objects held by its data properties.
+///
+/// `frozen` holds the objects that are already frozen, so that cycles terminate.
+fn freeze_deep(value: &Value, frozen: &mut Vec<Value>) {
+    if !value.is_object() || frozen.iter().any(|object| same_value(object, value)) {
+        return;
+    }
+    frozen.push(value.clone());
+    let mut object = value.as_object_mut().expect("value is an object");
+    object.freeze();
+    let values: Vec<Value> = object
+        .own_property_keys()
+        .iter()
+        .filter_map(|key| object.get_own_property(key).value.clone())
+        .collect();
+    drop(object);
+    for value in &values {
+        freeze_deep(value, frozen);
+    }
 }
 
 // Similar to new_global_environment in lexical_environment, except we need to return a GlobalEnvirionment
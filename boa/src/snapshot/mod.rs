@@ -0,0 +1,737 @@
+//! This module implements snapshots of realms.
+//!
+//! A `RealmSnapshot` records the state that scripts added to a realm: the globals they defined,
+//! the objects and functions reachable from them, and the changes they made to the builtin
+//! objects. Restoring the snapshot into another interpreter recreates that state, so a
+//! pre-warmed environment can be shared without running its setup scripts again:
+//!
+//! ```
+//! use boa::{snapshot::RealmSnapshot, Context};
+//!
+//! let mut context = Context::new();
+//! context
+//!     .eval("let greeting = 'Hello'; function greet(name) { return greeting + ', ' + name; }")
+//!     .unwrap();
+//! let bytes = RealmSnapshot::capture(context.interpreter()).unwrap().to_bytes();
+//!
+//! let mut restored = Context::new();
+//! RealmSnapshot::from_bytes(&bytes)
+//!     .unwrap()
+//!     .restore(restored.interpreter_mut())
+//!     .unwrap();
+//! assert_eq!(restored.eval("greet('Boa')").unwrap().to_string(), "Hello, Boa");
+//! ```
+//!
+//! Builtin objects are recorded by their path from the global object, like `Array.prototype`,
+//! or `Object.prototype.__proto__.[[Get]]` for the getter of an accessor property, and are
+//! found again in the realm the snapshot is restored into. Native functions registered
+//! by the embedder are not recorded: they must be registered again before restoring. Functions
+//! that close over the variables of another function, symbols and objects with native state
+//! cannot be recorded, and make `capture` fail.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builtins::{
+        function::{Function, FunctionBody, ThisMode},
+        object::{GcObject, Object, ObjectData},
+        property::{Attribute, Property, PropertyKey},
+        value::{same_value, JsString, Value},
+        BigInt,
+    },
+    environment::lexical_environment::Environment,
+    exec::Interpreter,
+    realm::Realm,
+    syntax::ast::{
+        node::{FormalParameter, LazyStatementList},
+        Position,
+    },
+};
+use gc::GcCell;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    error,
+    fmt::{self, Display},
+};
+
+/// The state that scripts added to a realm, see the module documentation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RealmSnapshot {
+    /// The paths of the builtin objects referenced by the snapshot, the global object first.
+    builtins: Vec<Vec<String>>,
+    /// The objects created by scripts.
+    objects: Vec<SnapshotObject>,
+    /// The changes made to the builtin objects.
+    patches: Vec<Patch>,
+    /// The `let`, `const` and `class` declarations of the global scope.
+    bindings: Vec<Binding>,
+}
+
+/// A value in a snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum SnapshotValue {
+    Undefined,
+    Null,
+    Boolean(bool),
+    String(String),
+    Rational(f64),
+    Integer(i32),
+    BigInt(BigInt),
+    /// A builtin object, by its index in `RealmSnapshot::builtins`.
+    Builtin(usize),
+    /// An object created by scripts, by its index in `RealmSnapshot::objects`.
+    Object(usize),
+}
+
+/// An object created by scripts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SnapshotObject {
+    data: SnapshotData,
+    prototype: SnapshotValue,
+    extensible: bool,
+    properties: Vec<SnapshotProperty>,
+    internal_slots: Vec<(String, SnapshotValue)>,
+}
+
+/// The type of an object created by scripts, see `ObjectData`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum SnapshotData {
+    Ordinary,
+    Array,
+    Error,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+    BigInt(BigInt),
+    Map(Vec<(SnapshotValue, SnapshotValue)>),
+    Function(SnapshotFunction),
+}
+
+/// A function defined in the global scope.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SnapshotFunction {
+    params: Box<[FormalParameter]>,
+    body: LazyStatementList,
+    lexical_this: bool,
+    constructable: bool,
+    callable: bool,
+    position: Option<Position>,
+    script: Option<String>,
+}
+
+/// An own property of an object, with a string or index key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SnapshotProperty {
+    key: String,
+    attribute: u8,
+    value: Option<SnapshotValue>,
+    get: Option<SnapshotValue>,
+    set: Option<SnapshotValue>,
+}
+
+/// A change made to a builtin object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum Patch {
+    /// Adds or replaces a property of the builtin at the index.
+    Define(usize, SnapshotProperty),
+    /// Deletes a property of the builtin at the index.
+    Delete(usize, String),
+    /// Replaces the prototype of the builtin at the index.
+    Prototype(usize, SnapshotValue),
+}
+
+/// A declarative binding of the global scope.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Binding {
+    name: String,
+    value: Option<SnapshotValue>,
+    mutable: bool,
+    strict: bool,
+    can_delete: bool,
+}
+
+/// The error returned when a realm cannot be captured or restored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotError {
+    message: String,
+}
+
+impl SnapshotError {
+    fn new<M: Into<String>>(message: M) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl error::Error for SnapshotError {}
+
+/// Gets the pointer that identifies an object.
+fn pointer(object: &GcObject) -> *const GcCell<Object> {
+    object.as_ref()
+}
+
+/// Gets the value of the own data property `key` of `object`.
+fn own_value(object: &Value, key: &str) -> Option<Value> {
+    let object = object.as_object()?;
+    let property = object.property(&PropertyKey::from(key))?;
+    property.value.clone()
+}
+
+/// The segment of a path that follows the key of an accessor property, to go to its getter.
+const GETTER: &str = "[[Get]]";
+
+/// The segment of a path that follows the key of an accessor property, to go to its setter.
+const SETTER: &str = "[[Set]]";
+
+/// Follows a path from the global object, as found by `builtin_paths`.
+fn resolve_path(global: &Value, path: &[String]) -> Option<Value> {
+    let mut value = global.clone();
+    let mut segments = path.iter().peekable();
+    while let Some(key) = segments.next() {
+        value = match segments.peek().map(|segment| segment.as_str()) {
+            Some(GETTER) | Some(SETTER) => {
+                let accessor = segments.next().expect("an accessor segment");
+                let object = value.as_object()?;
+                let property = object.property(&PropertyKey::from(key.as_str()))?;
+                if accessor == GETTER {
+                    property.get.clone()?
+                } else {
+                    property.set.clone()?
+                }
+            }
+            _ => own_value(&value, key)?,
+        };
+    }
+    Some(value)
+}
+
+/// Checks if a value is a function implemented in Rust.
+fn is_native_function(value: &Value) -> bool {
+    match value {
+        Value::Object(ref object) => match object.borrow().data {
+            ObjectData::Function(ref function) => {
+                !matches!(function.body, FunctionBody::Ordinary(_))
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Finds the builtin objects of a realm, and the shortest path to each of them.
+fn builtin_paths(global: &Value) -> Vec<(Vec<String>, GcObject)> {
+    let root = match global {
+        Value::Object(ref object) => object.clone(),
+        _ => return Vec::new(),
+    };
+    let mut seen = FxHashSet::default();
+    seen.insert(pointer(&root));
+    let mut paths = vec![(Vec::new(), root)];
+    let mut next = 0;
+    while next < paths.len() {
+        let (path, object) = paths[next].clone();
+        next += 1;
+
+        let object = object.borrow();
+        let mut keys: Vec<_> = object.properties().keys().cloned().collect();
+        keys.sort();
+        for key in keys {
+            let property = &object.properties()[&key];
+            let values = [
+                (&property.value, None),
+                (&property.get, Some(GETTER)),
+                (&property.set, Some(SETTER)),
+            ];
+            for (value, accessor) in &values {
+                if let Some(Value::Object(ref child)) = value {
+                    if seen.insert(pointer(child)) {
+                        let mut child_path = path.clone();
+                        child_path.push(key.to_string());
+                        child_path.extend(accessor.map(String::from));
+                        paths.push((child_path, child.clone()));
+                    }
+                }
+            }
+        }
+    }
+    paths
+}
+
+/// Records the state of a realm.
+struct Capture {
+    /// The builtins of the captured realm, by pointer.
+    builtins: FxHashMap<*const GcCell<Object>, usize>,
+    /// The builtins of a fresh realm, by pointer.
+    fresh_builtins: FxHashMap<*const GcCell<Object>, usize>,
+    /// The objects created by scripts, by pointer.
+    ids: FxHashMap<*const GcCell<Object>, usize>,
+    objects: Vec<Option<SnapshotObject>>,
+    queue: VecDeque<(usize, GcObject)>,
+    global_environment: Environment,
+}
+
+impl Capture {
+    /// Records a value, queueing the objects seen for the first time.
+    fn value(&mut self, value: &Value) -> Result<SnapshotValue, SnapshotError> {
+        Ok(match *value {
+            Value::Undefined => SnapshotValue::Undefined,
+            Value::Null => SnapshotValue::Null,
+            Value::Boolean(boolean) => SnapshotValue::Boolean(boolean),
+            Value::String(ref string) => SnapshotValue::String(string.to_string()),
+            Value::Rational(rational) => SnapshotValue::Rational(rational),
+            Value::Integer(integer) => SnapshotValue::Integer(integer),
+            Value::BigInt(ref bigint) => SnapshotValue::BigInt(bigint.as_inner().clone()),
+            Value::Symbol(_) => return Err(SnapshotError::new("cannot snapshot symbols")),
+            Value::Object(ref object) => {
+                let pointer = pointer(object);
+                if let Some(&index) = self.builtins.get(&pointer) {
+                    SnapshotValue::Builtin(index)
+                } else if let Some(&id) = self.ids.get(&pointer) {
+                    SnapshotValue::Object(id)
+                } else if is_native_function(value) {
+                    return Err(SnapshotError::new(
+                        "cannot snapshot native functions that are not builtins",
+                    ));
+                } else {
+                    let id = self.objects.len();
+                    self.ids.insert(pointer, id);
+                    self.objects.push(None);
+                    self.queue.push_back((id, object.clone()));
+                    SnapshotValue::Object(id)
+                }
+            }
+        })
+    }
+
+    /// Records an optional value.
+    fn optional(&mut self, value: &Option<Value>) -> Result<Option<SnapshotValue>, SnapshotError> {
+        value.as_ref().map(|value| self.value(value)).transpose()
+    }
+
+    /// Records a property.
+    fn property(
+        &mut self,
+        key: String,
+        property: &Property,
+    ) -> Result<SnapshotProperty, SnapshotError> {
+        Ok(SnapshotProperty {
+            key,
+            attribute: property.attribute.bits(),
+            value: self.optional(&property.value)?,
+            get: self.optional(&property.get)?,
+            set: self.optional(&property.set)?,
+        })
+    }
+
+    /// Checks if a value of the captured realm is the same as a value of the fresh realm.
+    fn unchanged(&self, value: &Option<Value>, fresh: &Option<Value>) -> bool {
+        match (value, fresh) {
+            (None, None) => true,
+            (Some(Value::Object(ref object)), Some(Value::Object(ref fresh))) => {
+                let index = self.builtins.get(&pointer(object));
+                index.is_some() && index == self.fresh_builtins.get(&pointer(fresh))
+            }
+            (Some(Value::Object(_)), _) | (_, Some(Value::Object(_))) => false,
+            (Some(value), Some(fresh)) => same_value(value, fresh),
+            _ => false,
+        }
+    }
+
+    /// Records the differences between a builtin and the same builtin in a fresh realm.
+    fn patch(
+        &mut self,
+        index: usize,
+        object: &GcObject,
+        fresh: &GcObject,
+        patches: &mut Vec<Patch>,
+    ) -> Result<(), SnapshotError> {
+        let object = object.borrow();
+        let fresh = fresh.borrow();
+
+        let prototype = object.prototype().clone();
+        if !self.unchanged(&Some(prototype.clone()), &Some(fresh.prototype().clone())) {
+            patches.push(Patch::Prototype(index, self.value(&prototype)?));
+        }
+
+        for key in object.own_property_keys() {
+            if key.is_symbol() {
+                continue;
+            }
+            let property = match object.property(&key) {
+                Some(property) => property,
+                None => continue,
+            };
+            let unchanged = fresh.property(&key).is_some_and(|fresh_property| {
+                property.attribute == fresh_property.attribute
+                    && self.unchanged(&property.value, &fresh_property.value)
+                    && self.unchanged(&property.get, &fresh_property.get)
+                    && self.unchanged(&property.set, &fresh_property.set)
+            });
+            // Native functions added to the builtins were registered by the embedder.
+            let host = property.value.as_ref().is_some_and(is_native_function)
+                && !self.is_builtin(&property.value);
+            if !unchanged && !host {
+                let property = self.property(key.to_string(), property)?;
+                patches.push(Patch::Define(index, property));
+            }
+        }
+
+        for key in fresh.own_property_keys() {
+            if !key.is_symbol() && object.property(&key).is_none() {
+                patches.push(Patch::Delete(index, key.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks if a value is a builtin object of the captured realm.
+    fn is_builtin(&self, value: &Option<Value>) -> bool {
+        match value {
+            Some(Value::Object(ref object)) => self.builtins.contains_key(&pointer(object)),
+            _ => false,
+        }
+    }
+
+    /// Records an object created by scripts.
+    fn object(&mut self, object: &Object) -> Result<SnapshotObject, SnapshotError> {
+        if object.state().is_some() {
+            return Err(SnapshotError::new(
+                "cannot snapshot objects with native state",
+            ));
+        }
+
+        let data = match object.data {
+            ObjectData::Ordinary => SnapshotData::Ordinary,
+            ObjectData::Array => SnapshotData::Array,
+            ObjectData::Error => SnapshotData::Error,
+            ObjectData::Boolean(boolean) => SnapshotData::Boolean(boolean),
+            ObjectData::Number(number) => SnapshotData::Number(number),
+            ObjectData::String(ref string) => SnapshotData::String(string.to_string()),
+            ObjectData::BigInt(ref bigint) => SnapshotData::BigInt(bigint.as_inner().clone()),
+            ObjectData::Symbol(_) => return Err(SnapshotError::new("cannot snapshot symbols")),
+            ObjectData::Map(ref map) => {
+                let mut entries = Vec::with_capacity(map.len());
+                for (key, value) in map.iter() {
+                    entries.push((self.value(key)?, self.value(value)?));
+                }
+                SnapshotData::Map(entries)
+            }
+            ObjectData::Function(ref function) => SnapshotData::Function(self.function(function)?),
+        };
+
+        let mut properties = Vec::new();
+        for key in object.own_property_keys() {
+            if key.is_symbol() {
+                return Err(SnapshotError::new("cannot snapshot symbol properties"));
+            }
+            if let Some(property) = object.property(&key) {
+                properties.push(self.property(key.to_string(), property)?);
+            }
+        }
+
+        let mut slots: Vec<_> = object.internal_slots().iter().collect();
+        slots.sort_by(|a, b| a.0.cmp(b.0));
+        let mut internal_slots = Vec::with_capacity(slots.len());
+        for (name, value) in slots {
+            internal_slots.push((name.clone(), self.value(value)?));
+        }
+
+        Ok(SnapshotObject {
+            data,
+            prototype: self.value(object.prototype())?,
+            extensible: object.is_extensible(),
+            properties,
+            internal_slots,
+        })
+    }
+
+    /// Records a function defined by scripts.
+    fn function(&mut self, function: &Function) -> Result<SnapshotFunction, SnapshotError> {
+        let body = match function.body {
+            FunctionBody::Ordinary(ref body) => body.clone(),
+            _ => {
+                return Err(SnapshotError::new(
+                    "cannot snapshot native functions that are not builtins",
+                ))
+            }
+        };
+        let global = matches!(
+            function.environment,
+            // `Gc::ptr_eq` also compares the root bits, so the cells are compared instead.
+            Some(ref environment) if std::ptr::eq(&**environment, &*self.global_environment)
+        );
+        if !global {
+            return Err(SnapshotError::new(
+                "cannot snapshot functions that close over local variables",
+            ));
+        }
+        Ok(SnapshotFunction {
+            params: function.params.clone(),
+            body,
+            lexical_this: function.this_mode == ThisMode::Lexical,
+            constructable: function.is_constructable(),
+            callable: function.is_callable(),
+            position: function.position(),
+            script: function.script().map(str::to_owned),
+        })
+    }
+}
+
+impl RealmSnapshot {
+    /// Records the state that scripts added to the realm of `interpreter`.
+    ///
+    /// Fails if the state contains values that cannot be recorded, see the module documentation.
+    pub fn capture(interpreter: &Interpreter) -> Result<Self, SnapshotError> {
+        let realm = interpreter.realm();
+        let fresh_realm = Realm::create();
+        let fresh_paths = builtin_paths(&fresh_realm.global_obj);
+        let global_environment = realm
+            .environment
+            .environments()
+            .last()
+            .expect("the global environment")
+            .clone();
+
+        let mut capture = Capture {
+            builtins: FxHashMap::default(),
+            fresh_builtins: FxHashMap::default(),
+            ids: FxHashMap::default(),
+            objects: Vec::new(),
+            queue: VecDeque::new(),
+            global_environment: global_environment.clone(),
+        };
+
+        // Finds the builtins of the captured realm at the paths of the fresh ones.
+        let mut builtins = Vec::new();
+        let mut pairs = Vec::new();
+        for (path, fresh) in fresh_paths {
+            let object = match resolve_path(&realm.global_obj, &path) {
+                Some(Value::Object(ref object)) => object.clone(),
+                _ => continue,
+            };
+            let fresh_value = Value::Object(fresh.clone());
+            let object_value = Value::Object(object.clone());
+            if is_native_function(&fresh_value) != is_native_function(&object_value)
+                || capture.builtins.contains_key(&pointer(&object))
+            {
+                continue;
+            }
+
+            let index = builtins.len();
+            capture.builtins.insert(pointer(&object), index);
+            capture.fresh_builtins.insert(pointer(&fresh), index);
+            builtins.push(path);
+            pairs.push((object, fresh));
+        }
+
+        let mut patches = Vec::new();
+        for (index, (object, fresh)) in pairs.iter().enumerate() {
+            capture.patch(index, object, fresh, &mut patches)?;
+        }
+
+        let mut bindings = Vec::new();
+        let mut declarative = global_environment.borrow().declarative_bindings();
+        declarative.sort_by(|a, b| a.0.cmp(&b.0));
+        for (name, binding) in declarative {
+            bindings.push(Binding {
+                name,
+                value: capture.optional(&binding.value)?,
+                mutable: binding.mutable,
+                strict: binding.strict,
+                can_delete: binding.can_delete,
+            });
+        }
+
+        while let Some((id, object)) = capture.queue.pop_front() {
+            let object = capture.object(&object.borrow())?;
+            capture.objects[id] = Some(object);
+        }
+
+        Ok(Self {
+            builtins,
+            objects: capture
+                .objects
+                .into_iter()
+                .map(|object| object.expect("every queued object is recorded"))
+                .collect(),
+            patches,
+            bindings,
+        })
+    }
+
+    /// Recreates the recorded state in the realm of `interpreter`.
+    ///
+    /// The realm should be fresh, with the native functions of the embedder registered.
+    pub fn restore(&self, interpreter: &mut Interpreter) -> Result<(), SnapshotError> {
+        let realm = interpreter.realm_mut();
+        let global_environment = realm
+            .environment
+            .environments()
+            .last()
+            .expect("the global environment")
+            .clone();
+
+        let mut builtins = Vec::with_capacity(self.builtins.len());
+        for path in &self.builtins {
+            match resolve_path(&realm.global_obj, path) {
+                Some(object) if object.is_object() => builtins.push(object),
+                _ => {
+                    return Err(SnapshotError::new(format!(
+                        "the builtin {} does not exist",
+                        path.join(".")
+                    )))
+                }
+            }
+        }
+
+        let mut objects = Vec::with_capacity(self.objects.len());
+        for object in &self.objects {
+            let data = match object.data {
+                SnapshotData::Ordinary | SnapshotData::Map(_) => ObjectData::Ordinary,
+                SnapshotData::Array => ObjectData::Array,
+                SnapshotData::Error => ObjectData::Error,
+                SnapshotData::Boolean(boolean) => ObjectData::Boolean(boolean),
+                SnapshotData::Number(number) => ObjectData::Number(number),
+                SnapshotData::String(ref string) => ObjectData::String(string.as_str().into()),
+                SnapshotData::BigInt(ref bigint) => ObjectData::BigInt(bigint.clone().into()),
+                SnapshotData::Function(ref function) => {
+                    let this_mode = if function.lexical_this {
+                        ThisMode::Lexical
+                    } else {
+                        ThisMode::NonLexical
+                    };
+                    let mut new_function = Function::new(
+                        function.params.clone(),
+                        Some(global_environment.clone()),
+                        FunctionBody::Ordinary(function.body.clone()),
+                        this_mode,
+                        function.constructable,
+                        function.callable,
+                    );
+                    new_function.set_position(function.position);
+                    new_function.set_script(function.script.as_deref().map(JsString::from));
+                    ObjectData::Function(new_function)
+                }
+            };
+            let mut new_object = Object::create(Value::null());
+            new_object.data = data;
+            objects.push(Value::from(new_object));
+        }
+
+        let restore = Restore {
+            builtins: &builtins,
+            objects: &objects,
+        };
+        for (object, value) in self.objects.iter().zip(&objects) {
+            let prototype = restore.value(&object.prototype);
+            let mut new_object = value.as_object_mut().expect("restored objects are objects");
+            new_object.set_prototype(prototype);
+            for property in &object.properties {
+                new_object.insert_property(property.key.as_str(), restore.property(property));
+            }
+            for (name, slot) in &object.internal_slots {
+                new_object.set_internal_slot(name, restore.value(slot));
+            }
+            if let SnapshotData::Map(ref entries) = object.data {
+                let mut map = crate::builtins::map::ordered_map::OrderedMap::new();
+                for (key, value) in entries {
+                    map.insert(restore.value(key), restore.value(value));
+                }
+                new_object.data = ObjectData::Map(map);
+            }
+            if !object.extensible {
+                new_object.prevent_extensions();
+            }
+        }
+
+        for patch in &self.patches {
+            match *patch {
+                Patch::Define(index, ref property) => {
+                    let mut builtin = builtins[index].as_object_mut().expect("builtin object");
+                    builtin.insert_property(property.key.as_str(), restore.property(property));
+                }
+                Patch::Delete(index, ref key) => {
+                    let mut builtin = builtins[index].as_object_mut().expect("builtin object");
+                    builtin.remove_property(&PropertyKey::from(key.as_str()));
+                }
+                Patch::Prototype(index, ref prototype) => {
+                    let prototype = restore.value(prototype);
+                    let mut builtin = builtins[index].as_object_mut().expect("builtin object");
+                    builtin.set_prototype(prototype);
+                }
+            }
+        }
+
+        let mut environment = global_environment.borrow_mut();
+        for binding in &self.bindings {
+            if environment.has_binding(&binding.name) {
+                return Err(SnapshotError::new(format!(
+                    "the global {} is already declared",
+                    binding.name
+                )));
+            }
+            if binding.mutable {
+                environment.create_mutable_binding(binding.name.clone(), binding.can_delete);
+            } else {
+                environment.create_immutable_binding(binding.name.clone(), binding.strict);
+            }
+            if let Some(ref value) = binding.value {
+                environment.initialize_binding(&binding.name, restore.value(value));
+            }
+        }
+        Ok(())
+    }
+
+    /// Serializes the snapshot, in a format that `from_bytes` reads.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("snapshots can be serialized")
+    }
+
+    /// Deserializes a snapshot written by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        serde_json::from_slice(bytes)
+            .map_err(|err| SnapshotError::new(format!("invalid snapshot: {}", err)))
+    }
+}
+
+/// Converts the recorded values back to values of the restored realm.
+#[derive(Debug)]
+struct Restore<'a> {
+    builtins: &'a [Value],
+    objects: &'a [Value],
+}
+
+impl Restore<'_> {
+    fn value(&self, value: &SnapshotValue) -> Value {
+        match *value {
+            SnapshotValue::Undefined => Value::undefined(),
+            SnapshotValue::Null => Value::null(),
+            SnapshotValue::Boolean(boolean) => Value::from(boolean),
+            SnapshotValue::String(ref string) => Value::from(string.as_str()),
+            SnapshotValue::Rational(rational) => Value::rational(rational),
+            SnapshotValue::Integer(integer) => Value::integer(integer),
+            SnapshotValue::BigInt(ref bigint) => Value::from(bigint.clone()),
+            SnapshotValue::Builtin(index) => self.builtins[index].clone(),
+            SnapshotValue::Object(id) => self.objects[id].clone(),
+        }
+    }
+
+    fn property(&self, property: &SnapshotProperty) -> Property {
+        let mut new_property = Property::new();
+        new_property.attribute = Attribute::from_bits_truncate(property.attribute);
+        new_property.value = property.value.as_ref().map(|value| self.value(value));
+        new_property.get = property.get.as_ref().map(|value| self.value(value));
+        new_property.set = property.set.as_ref().map(|value| self.value(value));
+        new_property
+    }
+}
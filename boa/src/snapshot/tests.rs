@@ -0,0 +1,99 @@
+use super::RealmSnapshot;
+use crate::Context;
+
+/// Captures the realm of `context` and restores it into a new context.
+fn round_trip(context: &Context) -> Context {
+    let bytes = RealmSnapshot::capture(context.interpreter())
+        .unwrap()
+        .to_bytes();
+    let mut restored = Context::new();
+    RealmSnapshot::from_bytes(&bytes)
+        .unwrap()
+        .restore(restored.interpreter_mut())
+        .unwrap();
+    restored
+}
+
+fn eval(context: &mut Context, src: &str) -> String {
+    context.eval(src).unwrap().to_string()
+}
+
+#[test]
+fn restores_globals_and_objects() {
+    let mut context = Context::new();
+    context
+        .eval(
+            r#"
+            var count = 3;
+            const config = { name: "boa", tags: ["js", "rust"] };
+            config.self = config;
+            let total = 10n;
+        "#,
+        )
+        .unwrap();
+
+    let mut restored = round_trip(&context);
+    assert_eq!(eval(&mut restored, "count"), "3");
+    assert_eq!(eval(&mut restored, "config.tags[1]"), "rust");
+    assert_eq!(eval(&mut restored, "config.tags.length"), "2");
+    assert_eq!(eval(&mut restored, "config.self === config"), "true");
+    assert_eq!(eval(&mut restored, "total + 1n"), "11n");
+}
+
+#[test]
+fn restores_functions() {
+    let mut context = Context::new();
+    context
+        .eval(
+            r#"
+            function Point(x) { this.x = x; }
+            Point.prototype.double = function () { return this.x * 2; };
+            const square = x => x * x;
+        "#,
+        )
+        .unwrap();
+
+    let mut restored = round_trip(&context);
+    assert_eq!(eval(&mut restored, "new Point(4).double()"), "8");
+    assert_eq!(eval(&mut restored, "square(5)"), "25");
+}
+
+#[test]
+fn restores_changes_to_builtins() {
+    let mut context = Context::new();
+    context
+        .eval(
+            r#"
+            Array.prototype.last = function () { return this[this.length - 1]; };
+            delete Math.max;
+        "#,
+        )
+        .unwrap();
+
+    let mut restored = round_trip(&context);
+    assert_eq!(eval(&mut restored, "[1, 2, 3].last()"), "3");
+    assert_eq!(eval(&mut restored, "typeof Math.max"), "undefined");
+    assert_eq!(eval(&mut restored, "Math.min(1, 2)"), "1");
+}
+
+#[test]
+fn skips_host_functions() {
+    let mut context = Context::new();
+    context.register_global_function("host", 0, |_, _, _| Ok(1.into()));
+
+    let mut restored = round_trip(&context);
+    assert_eq!(eval(&mut restored, "typeof globalThis.host"), "undefined");
+}
+
+#[test]
+fn rejects_closures() {
+    let mut context = Context::new();
+    context
+        .eval("function counter() { let n = 0; return () => n++; } var next = counter();")
+        .unwrap();
+    let error = RealmSnapshot::capture(context.interpreter()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "cannot snapshot functions that close over local variables"
+    );
+}
@@ -0,0 +1,354 @@
+//! This module implements source maps, which map positions in generated code back to the
+//! original sources.
+//!
+//! Transpilers and bundlers emit a [source map][format] next to the code they generate, and
+//! point to it with a `//# sourceMappingURL=` comment at the end of the code. The host can load
+//! the map with `source_mapping_url` and `SourceMap::from_json` or `SourceMap::from_data_url`,
+//! and translate the positions of errors, like `JsError::position`, with `SourceMap::lookup`.
+//!
+//! Only the version 3 of the format is supported, without index maps.
+//!
+//! [format]: https://sourcemaps.info/spec.html
+
+#[cfg(test)]
+mod tests;
+
+use crate::syntax::ast::Position;
+use serde_json::Value as JSONValue;
+use std::{error, fmt};
+
+/// The error returned when a source map can not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceMapError {
+    message: String,
+}
+
+impl SourceMapError {
+    fn new<M: Into<String>>(message: M) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SourceMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid source map: {}", self.message)
+    }
+}
+
+impl error::Error for SourceMapError {}
+
+/// A position in an original source, found with `SourceMap::lookup`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OriginalPosition<'a> {
+    /// The path or URL of the original source, with the source root of the map.
+    pub source: &'a str,
+    /// The line in the original source, starting at 1.
+    pub line: u32,
+    /// The column in the original source, starting at 1.
+    pub column: u32,
+    /// The original name of the identifier at this position, if the map has one.
+    pub name: Option<&'a str>,
+    /// The text of the original source, if the map embeds it.
+    pub content: Option<&'a str>,
+}
+
+/// A segment of the mappings, which maps a column of the generated code.
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    /// The column in the generated line, starting at 0.
+    column: u32,
+    /// The source, line, column and name indices of the original position, starting at 0, if
+    /// the generated code has one.
+    original: Option<(usize, u32, u32, Option<usize>)>,
+}
+
+/// A parsed source map.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    sources: Vec<String>,
+    /// The text of each source, if it is embedded in the map.
+    sources_content: Vec<Option<String>>,
+    names: Vec<String>,
+    /// The segments of each generated line, sorted by column.
+    lines: Vec<Vec<Segment>>,
+}
+
+impl SourceMap {
+    /// Reads a source map from its JSON text.
+    pub fn from_json(json: &str) -> Result<Self, SourceMapError> {
+        let map: JSONValue =
+            serde_json::from_str(json).map_err(|e| SourceMapError::new(e.to_string()))?;
+        if map.get("sections").is_some() {
+            return Err(SourceMapError::new("index maps are not supported"));
+        }
+        match map.get("version").and_then(JSONValue::as_u64) {
+            Some(3) => {}
+            _ => return Err(SourceMapError::new("only version 3 is supported")),
+        }
+
+        let root = map
+            .get("sourceRoot")
+            .and_then(JSONValue::as_str)
+            .unwrap_or_default();
+        let sources = strings(&map, "sources")?
+            .into_iter()
+            .map(|source| match root {
+                "" => source,
+                root if root.ends_with('/') => format!("{}{}", root, source),
+                root => format!("{}/{}", root, source),
+            })
+            .collect();
+        let sources_content = match map.get("sourcesContent") {
+            Some(JSONValue::Array(contents)) => contents
+                .iter()
+                .map(|content| content.as_str().map(str::to_owned))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let names = strings(&map, "names")?;
+        let mappings = map
+            .get("mappings")
+            .and_then(JSONValue::as_str)
+            .ok_or_else(|| SourceMapError::new("`mappings` is missing"))?;
+
+        let mut source_map = Self {
+            sources,
+            sources_content,
+            names,
+            lines: Vec::new(),
+        };
+        source_map.lines = source_map.decode_mappings(mappings)?;
+        Ok(source_map)
+    }
+
+    /// Reads a source map from a `data:` URL, like the inline maps of transpilers.
+    ///
+    /// The data can be encoded in base64 or percent-encoded.
+    pub fn from_data_url(url: &str) -> Result<Self, SourceMapError> {
+        let (header, data) = url
+            .strip_prefix("data:")
+            .and_then(|rest| {
+                let comma = rest.find(',')?;
+                Some((&rest[..comma], &rest[comma + 1..]))
+            })
+            .ok_or_else(|| SourceMapError::new("not a data URL"))?;
+        let bytes = if header.ends_with(";base64") {
+            decode_base64(data).ok_or_else(|| SourceMapError::new("invalid base64 data"))?
+        } else {
+            decode_percent(data)
+        };
+        let json = String::from_utf8(bytes)
+            .map_err(|_| SourceMapError::new("the data is not valid UTF-8"))?;
+        Self::from_json(&json)
+    }
+
+    /// Gets the original sources of the map.
+    #[inline]
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+
+    /// Rewrites the names of the original sources, like to resolve them relative to the
+    /// location of the map.
+    pub fn resolve_sources<F>(&mut self, mut resolve: F)
+    where
+        F: FnMut(&str) -> String,
+    {
+        for source in &mut self.sources {
+            *source = resolve(source);
+        }
+    }
+
+    /// Finds the original position of a position in the generated code.
+    ///
+    /// The position maps to the closest segment that starts at or before its column on the same
+    /// line. There is no original position if the line has no such segment, or if the segment
+    /// is generated code that has no original source.
+    pub fn lookup(&self, position: Position) -> Option<OriginalPosition<'_>> {
+        let line = self
+            .lines
+            .get(position.line_number().checked_sub(1)? as usize)?;
+        let column = position.column_number().checked_sub(1)?;
+        let index = line.partition_point(|segment| segment.column <= column);
+        let (source, line, column, name) = line.get(index.checked_sub(1)?)?.original?;
+        Some(OriginalPosition {
+            source: self.sources.get(source)?,
+            line: line + 1,
+            column: column + 1,
+            name: name
+                .and_then(|name| self.names.get(name))
+                .map(String::as_str),
+            content: self.sources_content.get(source).and_then(Option::as_deref),
+        })
+    }
+
+    /// Decodes the segments of the mappings, whose fields are base64 VLQs relative to the
+    /// previous segment.
+    fn decode_mappings(&self, mappings: &str) -> Result<Vec<Vec<Segment>>, SourceMapError> {
+        let (mut source, mut line, mut column, mut name) = (0_i64, 0_i64, 0_i64, 0_i64);
+        let mut lines = Vec::new();
+        for mapped in mappings.split(';') {
+            let mut generated_column = 0_i64;
+            let mut segments = Vec::new();
+            for segment in mapped.split(',').filter(|segment| !segment.is_empty()) {
+                let fields = decode_vlqs(segment)
+                    .ok_or_else(|| SourceMapError::new(format!("invalid segment `{}`", segment)))?;
+                generated_column += fields[0];
+                if generated_column < 0 {
+                    return Err(SourceMapError::new("a generated column is negative"));
+                }
+                let original = match fields.len() {
+                    1 => None,
+                    4 | 5 => {
+                        source += fields[1];
+                        line += fields[2];
+                        column += fields[3];
+                        if source < 0 || source as usize >= self.sources.len() {
+                            return Err(SourceMapError::new("a source index is out of bounds"));
+                        }
+                        if line < 0 || column < 0 {
+                            return Err(SourceMapError::new("an original position is negative"));
+                        }
+                        let name = fields.get(4).map(|delta| {
+                            name += delta;
+                            name as usize
+                        });
+                        Some((source as usize, line as u32, column as u32, name))
+                    }
+                    _ => {
+                        return Err(SourceMapError::new(format!(
+                            "invalid segment `{}`",
+                            segment
+                        )))
+                    }
+                };
+                segments.push(Segment {
+                    column: generated_column as u32,
+                    original,
+                });
+            }
+            segments.sort_by_key(|segment| segment.column);
+            lines.push(segments);
+        }
+        Ok(lines)
+    }
+}
+
+/// Gets an array of strings of a source map, `null` entries being empty strings.
+fn strings(map: &JSONValue, key: &str) -> Result<Vec<String>, SourceMapError> {
+    let array = match map.get(key) {
+        Some(JSONValue::Array(array)) => array,
+        None => return Ok(Vec::new()),
+        Some(_) => return Err(SourceMapError::new(format!("`{}` is not an array", key))),
+    };
+    Ok(array
+        .iter()
+        .map(|value| value.as_str().unwrap_or_default().to_owned())
+        .collect())
+}
+
+/// Gets the value of a base64 digit.
+fn base64_digit(byte: u8) -> Option<u8> {
+    match byte {
+        b'A'..=b'Z' => Some(byte - b'A'),
+        b'a'..=b'z' => Some(byte - b'a' + 26),
+        b'0'..=b'9' => Some(byte - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// Decodes the base64 VLQs of a segment.
+fn decode_vlqs(segment: &str) -> Option<Vec<i64>> {
+    let mut fields = Vec::new();
+    let (mut value, mut shift) = (0_i64, 0);
+    for byte in segment.bytes() {
+        let digit = i64::from(base64_digit(byte)?);
+        if shift > 32 {
+            return None;
+        }
+        value += (digit & 0b1_1111) << shift;
+        if digit & 0b10_0000 == 0 {
+            // The lowest bit is the sign.
+            let magnitude = value >> 1;
+            fields.push(if value & 1 == 1 {
+                -magnitude
+            } else {
+                magnitude
+            });
+            value = 0;
+            shift = 0;
+        } else {
+            shift += 5;
+        }
+    }
+    if shift == 0 {
+        Some(fields)
+    } else {
+        None
+    }
+}
+
+/// Decodes base64 data, with or without padding.
+fn decode_base64(data: &str) -> Option<Vec<u8>> {
+    let data = data.trim_end_matches('=');
+    let mut bytes = Vec::with_capacity(data.len() * 3 / 4);
+    let (mut buffer, mut bits) = (0_u32, 0);
+    for byte in data.bytes() {
+        buffer = buffer << 6 | u32::from(base64_digit(byte)?);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+/// Decodes percent-encoded data, leaving the invalid escapes as they are.
+fn decode_percent(data: &str) -> Vec<u8> {
+    let data = data.as_bytes();
+    let mut bytes = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        let escape = data
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escape {
+            Some(byte) if data[i] == b'%' => {
+                bytes.push(byte);
+                i += 3;
+            }
+            _ => {
+                bytes.push(data[i]);
+                i += 1;
+            }
+        }
+    }
+    bytes
+}
+
+/// Finds the URL of the source map of a script, in its last `//# sourceMappingURL=` comment.
+///
+/// The deprecated `//@ sourceMappingURL=` form is accepted too.
+pub fn source_mapping_url(src: &str) -> Option<&str> {
+    src.lines().rev().find_map(|line| {
+        let comment = line.trim().strip_prefix("//")?;
+        let comment = comment
+            .strip_prefix('#')
+            .or_else(|| comment.strip_prefix('@'))?;
+        let url = comment
+            .trim_start()
+            .strip_prefix("sourceMappingURL=")?
+            .trim();
+        if url.is_empty() {
+            None
+        } else {
+            Some(url)
+        }
+    })
+}
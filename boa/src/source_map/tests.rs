@@ -0,0 +1,105 @@
+use super::*;
+
+fn lookup(map: &SourceMap, line: u32, column: u32) -> Option<OriginalPosition<'_>> {
+    map.lookup(Position::new(line, column))
+}
+
+#[test]
+fn decode_mappings() {
+    let map = SourceMap::from_json(
+        r#"{
+            "version": 3,
+            "sourceRoot": "src",
+            "sources": ["a.ts"],
+            "sourcesContent": ["let foo;"],
+            "names": ["foo"],
+            "mappings": "AAAA;;AAEA,IAAIA,GAKDC"
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(map.sources(), ["src/a.ts"]);
+
+    let position = lookup(&map, 1, 1).unwrap();
+    assert_eq!(
+        (position.source, position.line, position.column),
+        ("src/a.ts", 1, 1)
+    );
+    assert_eq!(position.content, Some("let foo;"));
+    assert_eq!(lookup(&map, 2, 1), None);
+
+    // The segments are relative: the second one of the third line is 4 columns after the
+    // first one, on the same original line.
+    let position = lookup(&map, 3, 6).unwrap();
+    assert_eq!(
+        (position.line, position.column, position.name),
+        (3, 5, Some("foo"))
+    );
+    let position = lookup(&map, 3, 3).unwrap();
+    assert_eq!(
+        (position.line, position.column, position.name),
+        (3, 1, None)
+    );
+
+    // Fields can be negative, and names are relative too, so `C` is out of bounds.
+    let position = lookup(&map, 3, 20).unwrap();
+    assert_eq!(
+        (position.line, position.column, position.name),
+        (8, 4, None)
+    );
+}
+
+#[test]
+fn reject_invalid_maps() {
+    assert!(SourceMap::from_json("not json").is_err());
+    assert!(SourceMap::from_json(r#"{"version": 2, "mappings": ""}"#).is_err());
+    assert!(SourceMap::from_json(r#"{"version": 3, "sections": []}"#).is_err());
+    assert!(SourceMap::from_json(r#"{"version": 3, "sources": [], "mappings": "AAAA"}"#).is_err());
+    assert!(
+        SourceMap::from_json(r#"{"version": 3, "sources": ["a.js"], "mappings": "AA"}"#).is_err()
+    );
+}
+
+#[test]
+fn data_urls() {
+    let map = SourceMap::from_data_url(
+        "data:application/json;charset=utf-8;base64,\
+         eyJ2ZXJzaW9uIjozLCJzb3VyY2VzIjpbImEudHMiXSwibWFwcGluZ3MiOiJBQUFBIn0=",
+    )
+    .unwrap();
+    assert_eq!(map.sources(), ["a.ts"]);
+
+    let map = SourceMap::from_data_url(
+        "data:application/json,%7B%22version%22:3,%22sources%22:[%22b.ts%22],%22mappings%22:%22%22%7D",
+    )
+    .unwrap();
+    assert_eq!(map.sources(), ["b.ts"]);
+
+    assert!(SourceMap::from_data_url("app.js.map").is_err());
+}
+
+#[test]
+fn resolve_sources() {
+    let mut map =
+        SourceMap::from_json(r#"{"version": 3, "sources": ["a.ts"], "mappings": "AAAA"}"#).unwrap();
+    map.resolve_sources(|source| format!("dist/../src/{}", source));
+    assert_eq!(map.sources(), ["dist/../src/a.ts"]);
+    assert_eq!(lookup(&map, 1, 1).unwrap().source, "dist/../src/a.ts");
+}
+
+#[test]
+fn find_source_mapping_url() {
+    assert_eq!(
+        source_mapping_url("f();\n//# sourceMappingURL=app.js.map\n"),
+        Some("app.js.map")
+    );
+    assert_eq!(
+        source_mapping_url("//@ sourceMappingURL=old.map\nf();"),
+        Some("old.map")
+    );
+    assert_eq!(
+        source_mapping_url("//# sourceMappingURL=a.map\n//# sourceMappingURL=b.map"),
+        Some("b.map")
+    );
+    assert_eq!(source_mapping_url("// sourceMappingURL=a.map"), None);
+    assert_eq!(source_mapping_url("f();"), None);
+}
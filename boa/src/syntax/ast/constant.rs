@@ -7,7 +7,7 @@
 //! [spec]: https://tc39.es/ecma262/#sec-primary-expression-literals
 //! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Grammar_and_types#Literals
 
-use crate::builtins::bigint::BigInt;
+use crate::builtins::{bigint::BigInt, value::JsString};
 use gc::{Finalize, Trace};
 use std::fmt::{Display, Formatter, Result};
 
@@ -40,7 +40,7 @@ pub enum Const {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-terms-and-definitions-string-value
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Grammar_and_types#String_literals
-    String(Box<str>),
+    String(JsString),
 
     /// A floating-point number literal.
     ///
@@ -115,25 +115,31 @@ pub enum Const {
 
 impl From<&str> for Const {
     fn from(s: &str) -> Self {
-        Self::String(s.to_owned().into_boxed_str())
+        Self::String(s.into())
     }
 }
 
 impl From<&String> for Const {
     fn from(s: &String) -> Self {
-        Self::String(s.clone().into_boxed_str())
+        Self::String(s.as_str().into())
     }
 }
 
 impl From<Box<str>> for Const {
     fn from(s: Box<str>) -> Self {
-        Self::String(s)
+        Self::String(String::from(s).into())
     }
 }
 
 impl From<String> for Const {
     fn from(s: String) -> Self {
-        Self::String(s.into_boxed_str())
+        Self::String(s.into())
+    }
+}
+
+impl From<JsString> for Const {
+    fn from(s: JsString) -> Self {
+        Self::String(s)
     }
 }
 
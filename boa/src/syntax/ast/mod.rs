@@ -7,6 +7,7 @@ pub mod op;
 pub mod position;
 pub mod punctuator;
 pub mod token;
+pub mod visitor;
 
 pub use self::{
     constant::Const,
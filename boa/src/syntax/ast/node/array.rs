@@ -24,12 +24,19 @@ use serde::{Deserialize, Serialize};
 /// [spec]: https://tc39.es/ecma262/#prod-ArrayLiteral
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct ArrayDecl {
-    #[cfg_attr(feature = "serde", serde(flatten))]
     arr: Box<[Node]>,
 }
 
+impl ArrayDecl {
+    /// Gets a mutable reference to the elements of the array.
+    pub(crate) fn elements_mut(&mut self) -> &mut [Node] {
+        &mut self.arr
+    }
+}
+
 impl AsRef<[Node]> for ArrayDecl {
     fn as_ref(&self) -> &[Node] {
         &self.arr
@@ -1,6 +1,7 @@
 //! Block AST node.
 
 use super::{Node, StatementList};
+use crate::syntax::ast::Span;
 use gc::{Finalize, Trace};
 use std::fmt;
 
@@ -26,16 +27,30 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "serde", serde(transparent))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct Block {
-    #[cfg_attr(feature = "serde", serde(flatten))]
     statements: StatementList,
 }
 
 impl Block {
     /// Gets the list of statements in this block.
-    pub(crate) fn statements(&self) -> &[Node] {
+    pub fn statements(&self) -> &[Node] {
         self.statements.statements()
     }
 
+    /// Gets the span of each statement, see `StatementList::spans`.
+    pub fn spans(&self) -> &[Span] {
+        self.statements.spans()
+    }
+
+    /// Gets the statements of the block as a statement list.
+    pub fn statement_list(&self) -> &StatementList {
+        &self.statements
+    }
+
+    /// Gets a mutable reference to the statements of the block.
+    pub(crate) fn statement_list_mut(&mut self) -> &mut StatementList {
+        &mut self.statements
+    }
+
     /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
         writeln!(f, "{{")?;
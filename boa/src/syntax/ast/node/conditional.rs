@@ -34,14 +34,26 @@ impl If {
         &self.cond
     }
 
+    pub(crate) fn cond_mut(&mut self) -> &mut Node {
+        &mut self.cond
+    }
+
     pub fn body(&self) -> &Node {
         &self.body
     }
 
+    pub(crate) fn body_mut(&mut self) -> &mut Node {
+        &mut self.body
+    }
+
     pub fn else_node(&self) -> Option<&Node> {
         self.else_node.as_ref().map(Box::as_ref)
     }
 
+    pub(crate) fn else_node_mut(&mut self) -> Option<&mut Node> {
+        self.else_node.as_deref_mut()
+    }
+
     /// Creates an `If` AST node.
     pub fn new<C, B, E, OE>(condition: C, body: B, else_node: OE) -> Self
     where
@@ -109,14 +121,26 @@ impl ConditionalOp {
         &self.condition
     }
 
+    pub(crate) fn cond_mut(&mut self) -> &mut Node {
+        &mut self.condition
+    }
+
     pub fn if_true(&self) -> &Node {
         &self.if_true
     }
 
+    pub(crate) fn if_true_mut(&mut self) -> &mut Node {
+        &mut self.if_true
+    }
+
     pub fn if_false(&self) -> &Node {
         &self.if_false
     }
 
+    pub(crate) fn if_false_mut(&mut self) -> &mut Node {
+        &mut self.if_false
+    }
+
     /// Creates a `ConditionalOp` AST node.
     pub fn new<C, T, F>(condition: C, if_true: T, if_false: F) -> Self
     where
@@ -1,6 +1,7 @@
 //! Declaration nodes.
 
-use super::{join_nodes, FormalParameter, Identifier, Node, StatementList};
+use super::{join_nodes, FormalParameter, Identifier, LazyStatementList, Node};
+use crate::syntax::ast::Position;
 use gc::{Finalize, Trace};
 use std::fmt;
 
@@ -26,9 +27,9 @@ use serde::{Deserialize, Serialize};
 /// [spec]: https://tc39.es/ecma262/#prod-VariableStatement
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/var
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct VarDeclList {
-    #[cfg_attr(feature = "serde", serde(flatten))]
     vars: Box<[VarDecl]>,
 }
 
@@ -49,6 +50,13 @@ impl From<VarDecl> for VarDeclList {
     }
 }
 
+impl VarDeclList {
+    /// Gets a mutable reference to the declarations of the list.
+    pub(crate) fn decls_mut(&mut self) -> &mut [VarDecl] {
+        &mut self.vars
+    }
+}
+
 impl AsRef<[VarDecl]> for VarDeclList {
     fn as_ref(&self) -> &[VarDecl] {
         &self.vars
@@ -112,6 +120,11 @@ impl VarDecl {
     pub fn init(&self) -> Option<&Node> {
         self.init.as_ref()
     }
+
+    /// Gets a mutable reference to the initialization node for the variable, if any.
+    pub(crate) fn init_mut(&mut self) -> Option<&mut Node> {
+        self.init.as_mut()
+    }
 }
 
 /// The `function` expression defines a function with the specified parameters.
@@ -131,11 +144,12 @@ impl VarDecl {
 /// [spec]: https://tc39.es/ecma262/#sec-terms-and-definitions-function
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/function
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[derive(Clone, Debug, Trace, Finalize)]
 pub struct FunctionExpr {
     name: Option<Box<str>>,
     parameters: Box<[FormalParameter]>,
-    body: StatementList,
+    body: LazyStatementList,
+    position: Option<Position>,
 }
 
 impl FunctionExpr {
@@ -144,28 +158,51 @@ impl FunctionExpr {
     where
         N: Into<Option<Box<str>>>,
         P: Into<Box<[FormalParameter]>>,
-        B: Into<StatementList>,
+        B: Into<LazyStatementList>,
     {
         Self {
             name: name.into(),
             parameters: parameters.into(),
             body: body.into(),
+            position: None,
         }
     }
 
+    /// Sets the position of the function in the source code.
+    pub(in crate::syntax) fn at(mut self, position: Position) -> Self {
+        self.position = Some(position);
+        self
+    }
+
     /// Gets the name of the function declaration.
     pub fn name(&self) -> Option<&str> {
         self.name.as_ref().map(Box::as_ref)
     }
 
+    /// Gets the position of the `function` keyword or of the method name, if the function was
+    /// parsed from source code.
+    pub fn position(&self) -> Option<Position> {
+        self.position
+    }
+
     /// Gets the list of parameters of the function declaration.
     pub fn parameters(&self) -> &[FormalParameter] {
         &self.parameters
     }
 
+    /// Gets a mutable reference to the list of parameters of the function declaration.
+    pub(crate) fn parameters_mut(&mut self) -> &mut [FormalParameter] {
+        &mut self.parameters
+    }
+
     /// Gets the body of the function declaration.
-    pub fn body(&self) -> &[Node] {
-        self.body.statements()
+    pub fn body(&self) -> &LazyStatementList {
+        &self.body
+    }
+
+    /// Gets a mutable reference to the body of the function declaration.
+    pub(crate) fn body_mut(&mut self) -> &mut LazyStatementList {
+        &mut self.body
     }
 
     /// Implements the display formatting with indentation.
@@ -184,6 +221,13 @@ impl FunctionExpr {
     }
 }
 
+/// The position is left out, so that parsed functions compare equal to built ones.
+impl PartialEq for FunctionExpr {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.parameters == other.parameters && self.body == other.body
+    }
+}
+
 impl fmt::Display for FunctionExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.display(f, 0)
@@ -215,11 +259,12 @@ impl From<FunctionExpr> for Node {
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/function
 /// [func_expr]: ../enum.Node.html#variant.FunctionExpr
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[derive(Clone, Debug, Trace, Finalize)]
 pub struct FunctionDecl {
     name: Box<str>,
     parameters: Box<[FormalParameter]>,
-    body: StatementList,
+    body: LazyStatementList,
+    position: Option<Position>,
 }
 
 impl FunctionDecl {
@@ -228,28 +273,50 @@ impl FunctionDecl {
     where
         N: Into<Box<str>>,
         P: Into<Box<[FormalParameter]>>,
-        B: Into<StatementList>,
+        B: Into<LazyStatementList>,
     {
         Self {
             name: name.into(),
             parameters: parameters.into(),
             body: body.into(),
+            position: None,
         }
     }
 
+    /// Sets the position of the function in the source code.
+    pub(in crate::syntax) fn at(mut self, position: Position) -> Self {
+        self.position = Some(position);
+        self
+    }
+
     /// Gets the name of the function declaration.
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    /// Gets the position of the `function` keyword, if the function was parsed from source code.
+    pub fn position(&self) -> Option<Position> {
+        self.position
+    }
+
     /// Gets the list of parameters of the function declaration.
     pub fn parameters(&self) -> &[FormalParameter] {
         &self.parameters
     }
 
+    /// Gets a mutable reference to the list of parameters of the function declaration.
+    pub(crate) fn parameters_mut(&mut self) -> &mut [FormalParameter] {
+        &mut self.parameters
+    }
+
     /// Gets the body of the function declaration.
-    pub fn body(&self) -> &[Node] {
-        self.body.statements()
+    pub fn body(&self) -> &LazyStatementList {
+        &self.body
+    }
+
+    /// Gets a mutable reference to the body of the function declaration.
+    pub(crate) fn body_mut(&mut self) -> &mut LazyStatementList {
+        &mut self.body
     }
 
     /// Implements the display formatting with indentation.
@@ -270,6 +337,13 @@ impl From<FunctionDecl> for Node {
     }
 }
 
+/// The position is left out, so that parsed functions compare equal to built ones.
+impl PartialEq for FunctionDecl {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.parameters == other.parameters && self.body == other.body
+    }
+}
+
 impl fmt::Display for FunctionDecl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.display(f, 0)
@@ -290,10 +364,11 @@ impl fmt::Display for FunctionDecl {
 /// [spec]: https://tc39.es/ecma262/#prod-ArrowFunction
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Functions/Arrow_functions
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[derive(Clone, Debug, Trace, Finalize)]
 pub struct ArrowFunctionDecl {
     params: Box<[FormalParameter]>,
-    body: StatementList,
+    body: LazyStatementList,
+    position: Option<Position>,
 }
 
 impl ArrowFunctionDecl {
@@ -301,22 +376,44 @@ impl ArrowFunctionDecl {
     pub(in crate::syntax) fn new<P, B>(params: P, body: B) -> Self
     where
         P: Into<Box<[FormalParameter]>>,
-        B: Into<StatementList>,
+        B: Into<LazyStatementList>,
     {
         Self {
             params: params.into(),
             body: body.into(),
+            position: None,
         }
     }
 
+    /// Sets the position of the function in the source code.
+    pub(in crate::syntax) fn at(mut self, position: Position) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Gets the position of the parameters, if the function was parsed from source code.
+    pub fn position(&self) -> Option<Position> {
+        self.position
+    }
+
     /// Gets the list of parameters of the arrow function.
-    pub(crate) fn params(&self) -> &[FormalParameter] {
+    pub fn params(&self) -> &[FormalParameter] {
         &self.params
     }
 
+    /// Gets a mutable reference to the list of parameters of the arrow function.
+    pub(crate) fn params_mut(&mut self) -> &mut [FormalParameter] {
+        &mut self.params
+    }
+
     /// Gets the body of the arrow function.
-    pub(crate) fn body(&self) -> &[Node] {
-        &self.body.statements()
+    pub fn body(&self) -> &LazyStatementList {
+        &self.body
+    }
+
+    /// Gets a mutable reference to the body of the arrow function.
+    pub(crate) fn body_mut(&mut self) -> &mut LazyStatementList {
+        &mut self.body
     }
 
     /// Implements the display formatting with indentation.
@@ -328,6 +425,13 @@ impl ArrowFunctionDecl {
     }
 }
 
+/// The position is left out, so that parsed functions compare equal to built ones.
+impl PartialEq for ArrowFunctionDecl {
+    fn eq(&self, other: &Self) -> bool {
+        self.params == other.params && self.body == other.body
+    }
+}
+
 impl fmt::Display for ArrowFunctionDecl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.display(f, 0)
@@ -359,9 +463,9 @@ impl From<ArrowFunctionDecl> for Node {
 /// [identifier]: https://developer.mozilla.org/en-US/docs/Glossary/identifier
 /// [expression]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Expressions_and_Operators#Expressions
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct ConstDeclList {
-    #[cfg_attr(feature = "serde", serde(flatten))]
     list: Box<[ConstDecl]>,
 }
 
@@ -382,6 +486,13 @@ impl From<ConstDecl> for ConstDeclList {
     }
 }
 
+impl ConstDeclList {
+    /// Gets a mutable reference to the declarations of the list.
+    pub(crate) fn decls_mut(&mut self) -> &mut [ConstDecl] {
+        &mut self.list
+    }
+}
+
 impl AsRef<[ConstDecl]> for ConstDeclList {
     fn as_ref(&self) -> &[ConstDecl] {
         &self.list
@@ -441,6 +552,11 @@ impl ConstDecl {
     pub fn init(&self) -> &Node {
         &self.init
     }
+
+    /// Gets a mutable reference to the initialization node for the variable, if any.
+    pub(crate) fn init_mut(&mut self) -> &mut Node {
+        &mut self.init
+    }
 }
 
 /// The `let` statement declares a block scope local variable, optionally initializing it to a
@@ -461,9 +577,9 @@ impl ConstDecl {
 /// [spec]: https://tc39.es/ecma262/#sec-let-and-const-declarations
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/let
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct LetDeclList {
-    #[cfg_attr(feature = "serde", serde(flatten))]
     list: Box<[LetDecl]>,
 }
 
@@ -484,6 +600,13 @@ impl From<LetDecl> for LetDeclList {
     }
 }
 
+impl LetDeclList {
+    /// Gets a mutable reference to the declarations of the list.
+    pub(crate) fn decls_mut(&mut self) -> &mut [LetDecl] {
+        &mut self.list
+    }
+}
+
 impl AsRef<[LetDecl]> for LetDeclList {
     fn as_ref(&self) -> &[LetDecl] {
         &self.list
@@ -547,4 +670,9 @@ impl LetDecl {
     pub fn init(&self) -> Option<&Node> {
         self.init.as_ref()
     }
+
+    /// Gets a mutable reference to the initialization node for the variable, if any.
+    pub(crate) fn init_mut(&mut self) -> Option<&mut Node> {
+        self.init.as_mut()
+    }
 }
@@ -46,10 +46,20 @@ impl Call {
         &self.expr
     }
 
+    /// Gets a mutable reference to the name of the function call.
+    pub(crate) fn expr_mut(&mut self) -> &mut Node {
+        &mut self.expr
+    }
+
     /// Retrieves the arguments passed to the function.
     pub fn args(&self) -> &[Node] {
         &self.args
     }
+
+    /// Gets a mutable reference to the arguments passed to the function.
+    pub(crate) fn args_mut(&mut self) -> &mut [Node] {
+        &mut self.args
+    }
 }
 
 impl fmt::Display for Call {
@@ -93,10 +103,20 @@ impl New {
         &self.call.expr()
     }
 
+    /// Gets a mutable reference to the name of the function call.
+    pub(crate) fn expr_mut(&mut self) -> &mut Node {
+        self.call.expr_mut()
+    }
+
     /// Retrieves the arguments passed to the function.
     pub fn args(&self) -> &[Node] {
         &self.call.args()
     }
+
+    /// Gets a mutable reference to the arguments passed to the function.
+    pub(crate) fn args_mut(&mut self) -> &mut [Node] {
+        self.call.args_mut()
+    }
 }
 
 impl From<Call> for New {
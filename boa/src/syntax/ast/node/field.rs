@@ -40,6 +40,10 @@ impl GetConstField {
         &self.obj
     }
 
+    pub(crate) fn obj_mut(&mut self) -> &mut Node {
+        &mut self.obj
+    }
+
     pub fn field(&self) -> &str {
         &self.field
     }
@@ -103,10 +107,18 @@ impl GetField {
         &self.obj
     }
 
+    pub(crate) fn obj_mut(&mut self) -> &mut Node {
+        &mut self.obj
+    }
+
     pub fn field(&self) -> &Node {
         &self.field
     }
 
+    pub(crate) fn field_mut(&mut self) -> &mut Node {
+        &mut self.field
+    }
+
     /// Creates a `GetField` AST node.
     pub fn new<V, F>(value: V, field: F) -> Self
     where
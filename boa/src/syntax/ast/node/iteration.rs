@@ -42,21 +42,41 @@ impl ForLoop {
         self.inner.init()
     }
 
+    /// Gets a mutable reference to the initialization node.
+    pub(crate) fn init_mut(&mut self) -> Option<&mut Node> {
+        self.inner.init.as_mut()
+    }
+
     /// Gets the loop condition node.
     pub fn condition(&self) -> Option<&Node> {
         self.inner.condition()
     }
 
+    /// Gets a mutable reference to the loop condition node.
+    pub(crate) fn condition_mut(&mut self) -> Option<&mut Node> {
+        self.inner.condition.as_mut()
+    }
+
     /// Gets the final expression node.
     pub fn final_expr(&self) -> Option<&Node> {
         self.inner.final_expr()
     }
 
+    /// Gets a mutable reference to the final expression node.
+    pub(crate) fn final_expr_mut(&mut self) -> Option<&mut Node> {
+        self.inner.final_expr.as_mut()
+    }
+
     /// Gets the body of the for loop.
     pub fn body(&self) -> &Node {
         self.inner.body()
     }
 
+    /// Gets a mutable reference to the body of the for loop.
+    pub(crate) fn body_mut(&mut self) -> &mut Node {
+        &mut self.inner.body
+    }
+
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
         f.write_str("for (")?;
         if let Some(init) = self.init() {
@@ -161,10 +181,18 @@ impl WhileLoop {
         &self.cond
     }
 
+    pub(crate) fn cond_mut(&mut self) -> &mut Node {
+        &mut self.cond
+    }
+
     pub fn expr(&self) -> &Node {
         &self.expr
     }
 
+    pub(crate) fn expr_mut(&mut self) -> &mut Node {
+        &mut self.expr
+    }
+
     /// Creates a `WhileLoop` AST node.
     pub fn new<C, B>(condition: C, body: B) -> Self
     where
@@ -219,10 +247,18 @@ impl DoWhileLoop {
         &self.body
     }
 
+    pub(crate) fn body_mut(&mut self) -> &mut Node {
+        &mut self.body
+    }
+
     pub fn cond(&self) -> &Node {
         &self.cond
     }
 
+    pub(crate) fn cond_mut(&mut self) -> &mut Node {
+        &mut self.cond
+    }
+
     /// Creates a `DoWhileLoop` AST node.
     pub fn new<B, C>(body: B, condition: C) -> Self
     where
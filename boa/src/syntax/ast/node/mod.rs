@@ -35,12 +35,13 @@ pub use self::{
     operator::{Assign, BinOp, UnaryOp},
     return_smt::Return,
     spread::Spread,
-    statement_list::StatementList,
+    statement_list::{LazyStatementList, StatementList},
     switch::{Case, Switch},
     throw::Throw,
     try_node::{Catch, Finally, Try},
 };
 use super::Const;
+use crate::syntax::codegen::{self, Style};
 use gc::{unsafe_empty_trace, Finalize, Trace};
 use std::{
     cmp::Ordering,
@@ -95,6 +96,17 @@ pub enum Node {
     /// A continue statement. [More information](./iteration/struct.Continue.html).
     Continue(Continue),
 
+    /// The `debugger` statement pauses the execution if a debugger is attached, see
+    /// `Debugger::set_pause_handler`, and does nothing otherwise.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-debugger-statement
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/debugger
+    Debugger,
+
     /// A do ... while statement. [More information](./iteration/struct.DoWhileLoop.html).
     DoWhileLoop(DoWhileLoop),
 
@@ -196,6 +208,49 @@ impl Node {
         Self::This
     }
 
+    /// Gets the name of the kind of the node, which is the name of its variant, e.g. `"Call"`.
+    pub fn kind(&self) -> &'static str {
+        match *self {
+            Self::ArrayDecl(_) => "ArrayDecl",
+            Self::ArrowFunctionDecl(_) => "ArrowFunctionDecl",
+            Self::Assign(_) => "Assign",
+            Self::BinOp(_) => "BinOp",
+            Self::Block(_) => "Block",
+            Self::Break(_) => "Break",
+            Self::Call(_) => "Call",
+            Self::ConditionalOp(_) => "ConditionalOp",
+            Self::Const(_) => "Const",
+            Self::ConstDeclList(_) => "ConstDeclList",
+            Self::Continue(_) => "Continue",
+            Self::Debugger => "Debugger",
+            Self::DoWhileLoop(_) => "DoWhileLoop",
+            Self::FunctionDecl(_) => "FunctionDecl",
+            Self::FunctionExpr(_) => "FunctionExpr",
+            Self::GetConstField(_) => "GetConstField",
+            Self::GetField(_) => "GetField",
+            Self::ForLoop(_) => "ForLoop",
+            Self::If(_) => "If",
+            Self::LetDeclList(_) => "LetDeclList",
+            Self::Identifier(_) => "Identifier",
+            Self::New(_) => "New",
+            Self::Object(_) => "Object",
+            Self::Return(_) => "Return",
+            Self::Switch(_) => "Switch",
+            Self::Spread(_) => "Spread",
+            Self::Throw(_) => "Throw",
+            Self::Try(_) => "Try",
+            Self::This => "This",
+            Self::UnaryOp(_) => "UnaryOp",
+            Self::VarDeclList(_) => "VarDeclList",
+            Self::WhileLoop(_) => "WhileLoop",
+        }
+    }
+
+    /// Generates the JavaScript source code of the node, see `codegen::generate_node`.
+    pub fn to_js_string(&self, style: Style) -> String {
+        codegen::generate_node(self, style)
+    }
+
     /// Implements the display formatting with indentation.
     fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
         let indent = "    ".repeat(indentation);
@@ -209,6 +264,7 @@ impl Node {
             Self::ConditionalOp(ref cond_op) => Display::fmt(cond_op, f),
             Self::ForLoop(ref for_loop) => for_loop.display(f, indentation),
             Self::This => write!(f, "this"),
+            Self::Debugger => write!(f, "debugger"),
             Self::Try(ref try_catch) => try_catch.display(f, indentation),
             Self::Break(ref break_smt) => Display::fmt(break_smt, f),
             Self::Continue(ref cont) => Display::fmt(cont, f),
@@ -302,6 +358,11 @@ impl FormalParameter {
         self.init.as_ref()
     }
 
+    /// Gets a mutable reference to the initialization node of the formal parameter, if any.
+    pub(crate) fn init_mut(&mut self) -> Option<&mut Node> {
+        self.init.as_mut()
+    }
+
     /// Gets wether the parameter is a rest parameter.
     pub fn is_rest_param(&self) -> bool {
         self.is_rest_param
@@ -40,6 +40,10 @@ impl Object {
         &self.properties
     }
 
+    pub(crate) fn properties_mut(&mut self) -> &mut [PropertyDefinition] {
+        &mut self.properties
+    }
+
     /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
         f.write_str("{\n")?;
@@ -42,10 +42,20 @@ impl Assign {
         &self.lhs
     }
 
+    /// Gets a mutable reference to the left hand side of the assignment operation.
+    pub(crate) fn lhs_mut(&mut self) -> &mut Node {
+        &mut self.lhs
+    }
+
     /// Gets the right hand side of the assignment operation.
     pub fn rhs(&self) -> &Node {
         &self.rhs
     }
+
+    /// Gets a mutable reference to the right hand side of the assignment operation.
+    pub(crate) fn rhs_mut(&mut self) -> &mut Node {
+        &mut self.rhs
+    }
 }
 
 impl fmt::Display for Assign {
@@ -99,10 +109,20 @@ impl BinOp {
         &self.lhs
     }
 
+    /// Gets a mutable reference to the left hand side of the binary operation.
+    pub(crate) fn lhs_mut(&mut self) -> &mut Node {
+        &mut self.lhs
+    }
+
     /// Gets the right hand side of the binary operation.
     pub fn rhs(&self) -> &Node {
         &self.rhs
     }
+
+    /// Gets a mutable reference to the right hand side of the binary operation.
+    pub(crate) fn rhs_mut(&mut self) -> &mut Node {
+        &mut self.rhs
+    }
 }
 
 impl fmt::Display for BinOp {
@@ -153,6 +173,11 @@ impl UnaryOp {
     pub fn target(&self) -> &Node {
         self.target.as_ref()
     }
+
+    /// Gets a mutable reference to the target of this unary operator.
+    pub(crate) fn target_mut(&mut self) -> &mut Node {
+        &mut self.target
+    }
 }
 
 impl fmt::Display for UnaryOp {
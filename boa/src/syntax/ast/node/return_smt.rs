@@ -39,6 +39,10 @@ impl Return {
         self.expr.as_ref().map(Box::as_ref)
     }
 
+    pub(crate) fn expr_mut(&mut self) -> Option<&mut Node> {
+        self.expr.as_deref_mut()
+    }
+
     /// Creates a `Return` AST node.
     pub fn new<E, OE, L>(expr: OE, label: L) -> Self
     where
@@ -33,6 +33,10 @@ impl Spread {
         &self.val
     }
 
+    pub(crate) fn val_mut(&mut self) -> &mut Node {
+        &mut self.val
+    }
+
     /// Creates a `Spread` AST node.
     pub fn new<V>(val: V) -> Self
     where
@@ -1,11 +1,19 @@
 //! Statement list node.
 
 use super::Node;
-use gc::{Finalize, Trace};
-use std::fmt;
+use crate::syntax::{
+    ast::{Span, Token},
+    codegen::{self, Style},
+    parser::{error::ParseError, Parser},
+};
+use gc::{unsafe_empty_trace, Finalize, Trace};
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
 
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// List of statements.
 ///
@@ -15,19 +23,50 @@ use serde::{Deserialize, Serialize};
 ///  - [ECMAScript reference][spec]
 ///
 /// [spec]: https://tc39.es/ecma262/#prod-StatementList
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[derive(Clone, Debug, Trace, Finalize)]
 pub struct StatementList {
-    #[cfg_attr(feature = "serde", serde(flatten))]
     statements: Box<[Node]>,
+    spans: Box<[Span]>,
 }
 
 impl StatementList {
+    /// Creates a list of statements parsed from source code, with the span of each statement.
+    pub(in crate::syntax) fn with_spans<T>(statements: T) -> Self
+    where
+        T: IntoIterator<Item = (Node, Span)>,
+    {
+        let (statements, spans): (Vec<_>, Vec<_>) = statements.into_iter().unzip();
+        Self {
+            statements: statements.into(),
+            spans: spans.into(),
+        }
+    }
+
     /// Gets the list of statements.
     pub fn statements(&self) -> &[Node] {
         &self.statements
     }
 
+    /// Gets a mutable reference to the list of statements.
+    pub(crate) fn statements_mut(&mut self) -> &mut [Node] {
+        &mut self.statements
+    }
+
+    /// Gets the span of each statement, from its first token to its last one, if the statements
+    /// were parsed from source code, and an empty slice otherwise.
+    ///
+    /// The function declarations are hoisted to the start of the list, so the spans are not
+    /// always in the order of the source.
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// Generates the JavaScript source code of the statements, see `codegen::generate`.
+    pub fn to_js_string(&self, style: Style) -> String {
+        codegen::generate(self, style)
+    }
+
     /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
         let indent = "    ".repeat(indentation);
@@ -53,12 +92,174 @@ where
     fn from(stm: T) -> Self {
         Self {
             statements: stm.into(),
+            spans: Box::default(),
         }
     }
 }
 
+/// The spans are left out, so that parsed statements compare equal to built ones.
+impl PartialEq for StatementList {
+    fn eq(&self, other: &Self) -> bool {
+        self.statements == other.statements
+    }
+}
+
+/// Statement lists are deserialized from their current form, and from the plain array of
+/// statements that they used to be serialized to.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for StatementList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Plain(Box<[Node]>),
+            WithSpans {
+                statements: Box<[Node]>,
+                #[serde(default)]
+                spans: Box<[Span]>,
+            },
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Plain(statements) => Self::from(statements),
+            Repr::WithSpans { statements, spans } => Self { statements, spans },
+        })
+    }
+}
+
 impl fmt::Display for StatementList {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.display(f, 0)
     }
 }
+
+/// The statements of a function body, which may only be parsed when they are first needed.
+///
+/// When the parser defers function bodies, see `Parser::lazy_functions`, it only checks that the
+/// braces of a body are balanced and keeps its tokens. The body is parsed the first time its
+/// statements are needed, usually when the function is first called, so the functions that
+/// never run are never parsed. The clones of a body share its statements, so a body is parsed
+/// at most once.
+#[derive(Clone)]
+pub struct LazyStatementList(Arc<Mutex<Body>>);
+
+impl Finalize for LazyStatementList {}
+
+/// The syntax tree holds no garbage collected values.
+unsafe impl Trace for LazyStatementList {
+    unsafe_empty_trace!();
+}
+
+enum Body {
+    Parsed(Arc<StatementList>),
+    /// The tokens of the body, which end with the `}` that closes it.
+    Deferred {
+        tokens: Box<[Token]>,
+        allow_yield: bool,
+        allow_await: bool,
+    },
+}
+
+impl LazyStatementList {
+    /// Creates a body whose parsing is deferred.
+    pub(in crate::syntax) fn deferred<T>(tokens: T, allow_yield: bool, allow_await: bool) -> Self
+    where
+        T: Into<Box<[Token]>>,
+    {
+        Self(Arc::new(Mutex::new(Body::Deferred {
+            tokens: tokens.into(),
+            allow_yield,
+            allow_await,
+        })))
+    }
+
+    /// Gets the statements of the body, parsing them if they were not parsed yet.
+    ///
+    /// A syntax error in a deferred body is only found here.
+    pub fn statements(&self) -> Result<Arc<StatementList>, ParseError> {
+        let mut body = self.0.lock().expect("a function body was poisoned");
+        let statements = match *body {
+            Body::Parsed(ref statements) => return Ok(Arc::clone(statements)),
+            Body::Deferred {
+                ref tokens,
+                allow_yield,
+                allow_await,
+            } => Arc::new(
+                Parser::new(tokens)
+                    .lazy_functions(true)
+                    .parse_function_body(allow_yield, allow_await)?,
+            ),
+        };
+        *body = Body::Parsed(Arc::clone(&statements));
+        Ok(statements)
+    }
+
+    /// Checks whether the statements of the body were parsed.
+    pub fn is_parsed(&self) -> bool {
+        matches!(
+            *self.0.lock().expect("a function body was poisoned"),
+            Body::Parsed(_)
+        )
+    }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        match self.statements() {
+            Ok(statements) => statements.display(f, indentation),
+            Err(err) => writeln!(f, "{}/* {} */", "    ".repeat(indentation), err),
+        }
+    }
+}
+
+impl From<StatementList> for LazyStatementList {
+    fn from(statements: StatementList) -> Self {
+        Self(Arc::new(Mutex::new(Body::Parsed(Arc::new(statements)))))
+    }
+}
+
+impl From<Vec<Node>> for LazyStatementList {
+    fn from(statements: Vec<Node>) -> Self {
+        Self::from(StatementList::from(statements))
+    }
+}
+
+impl fmt::Debug for LazyStatementList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self.0.lock().expect("a function body was poisoned") {
+            Body::Parsed(ref statements) => fmt::Debug::fmt(statements, f),
+            Body::Deferred { ref tokens, .. } => f
+                .debug_struct("Deferred")
+                .field("tokens", &tokens.len())
+                .finish(),
+        }
+    }
+}
+
+/// Two bodies are equal if their statements are, so a deferred body is parsed to be compared.
+impl PartialEq for LazyStatementList {
+    fn eq(&self, other: &Self) -> bool {
+        if Arc::ptr_eq(&self.0, &other.0) {
+            return true;
+        }
+        match (self.statements(), other.statements()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for LazyStatementList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.statements()
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for LazyStatementList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        StatementList::deserialize(deserializer).map(Self::from)
+    }
+}
@@ -21,10 +21,18 @@ impl Case {
         &self.condition
     }
 
+    pub(crate) fn condition_mut(&mut self) -> &mut Node {
+        &mut self.condition
+    }
+
     pub fn body(&self) -> &StatementList {
         &self.body
     }
 
+    pub(crate) fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
+
     /// Creates a `Case` AST node.
     pub fn new<C, B>(condition: C, body: B) -> Self
     where
@@ -67,14 +75,26 @@ impl Switch {
         &self.val
     }
 
+    pub(crate) fn val_mut(&mut self) -> &mut Node {
+        &mut self.val
+    }
+
     pub fn cases(&self) -> &[Case] {
         &self.cases
     }
 
+    pub(crate) fn cases_mut(&mut self) -> &mut [Case] {
+        &mut self.cases
+    }
+
     pub fn default(&self) -> Option<&Node> {
         self.default.as_ref().map(Box::as_ref)
     }
 
+    pub(crate) fn default_mut(&mut self) -> Option<&mut Node> {
+        self.default.as_deref_mut()
+    }
+
     /// Creates a `Switch` AST node.
     pub fn new<V, C>(val: V, cases: C, default: Option<V>) -> Self
     where
@@ -30,6 +30,10 @@ impl Throw {
         &self.expr
     }
 
+    pub(crate) fn expr_mut(&mut self) -> &mut Node {
+        &mut self.expr
+    }
+
     /// Creates a `Throw` AST node.
     pub fn new<V>(val: V) -> Self
     where
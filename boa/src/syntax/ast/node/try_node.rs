@@ -53,16 +53,31 @@ impl Try {
         &self.block
     }
 
+    /// Gets a mutable reference to the `try` block.
+    pub(crate) fn block_mut(&mut self) -> &mut Block {
+        &mut self.block
+    }
+
     /// Gets the `catch` block, if any.
     pub fn catch(&self) -> Option<&Catch> {
         self.catch.as_ref()
     }
 
+    /// Gets a mutable reference to the `catch` block, if any.
+    pub(crate) fn catch_mut(&mut self) -> Option<&mut Catch> {
+        self.catch.as_mut()
+    }
+
     /// Gets the `finally` block, if any.
     pub fn finally(&self) -> Option<&Block> {
         self.finally.as_ref().map(Finally::block)
     }
 
+    /// Gets a mutable reference to the `finally` block, if any.
+    pub(crate) fn finally_mut(&mut self) -> Option<&mut Block> {
+        self.finally.as_mut().map(|finally| &mut finally.block)
+    }
+
     /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
         write!(f, "{}try ", "    ".repeat(indentation))?;
@@ -123,6 +138,11 @@ impl Catch {
         &self.block
     }
 
+    /// Gets a mutable reference to the catch execution block.
+    pub(crate) fn block_mut(&mut self) -> &mut Block {
+        &mut self.block
+    }
+
     /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
         f.write_str(" catch")?;
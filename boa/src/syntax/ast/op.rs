@@ -658,6 +658,21 @@ pub enum LogOp {
     /// [spec]: https://tc39.es/ecma262/#prod-LogicalORExpression)
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Logical_Operators#Logical_OR
     Or,
+
+    /// The nullish coalescing operator returns the second operand if the first one is `null`
+    /// or `undefined`; otherwise, it returns the first operand.
+    ///
+    /// Syntax: `x ?? y`
+    ///
+    /// It is an experimental feature, see `Experimental::NULLISH_COALESCING`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#prod-CoalesceExpression
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Nullish_coalescing_operator
+    Coalesce,
 }
 
 impl Display for LogOp {
@@ -668,6 +683,7 @@ impl Display for LogOp {
             match *self {
                 Self::And => "&&",
                 Self::Or => "||",
+                Self::Coalesce => "??",
             }
         )
     }
@@ -1,5 +1,6 @@
 //! This module implements the `Pos` structure, which represents a position in the source code.
 
+use gc::{unsafe_empty_trace, Finalize, Trace};
 use std::{cmp::Ordering, fmt, num::NonZeroU32};
 
 #[cfg(feature = "serde")]
@@ -12,7 +13,7 @@ use serde::{Deserialize, Serialize};
 /// ## Similar Implementations
 /// [V8: Location](https://cs.chromium.org/chromium/src/v8/src/parsing/scanner.h?type=cs&q=isValid+Location&g=0&l=216)
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Position {
     /// Line number.
     line_number: NonZeroU32,
@@ -43,6 +44,13 @@ impl Position {
     }
 }
 
+impl Finalize for Position {}
+
+// SAFETY: A `Position` only contains integers, so it holds no garbage collected values.
+unsafe impl Trace for Position {
+    unsafe_empty_trace!();
+}
+
 impl fmt::Display for Position {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}:{}", self.line_number, self.column_number)
@@ -59,6 +67,13 @@ pub struct Span {
     end: Position,
 }
 
+impl Finalize for Span {}
+
+// SAFETY: A `Span` only contains positions, so it holds no garbage collected values.
+unsafe impl Trace for Span {
+    unsafe_empty_trace!();
+}
+
 impl Span {
     /// Creates a new `Span`.
     #[inline]
@@ -65,6 +65,8 @@ pub enum Punctuator {
     CloseBracket,
     /// `)`
     CloseParen,
+    /// `??`
+    Coalesce,
     /// `:`
     Colon,
     /// `,`
@@ -156,6 +158,7 @@ impl Punctuator {
             Self::Xor => Some(BinOp::Bit(BitOp::Xor)),
             Self::BoolAnd => Some(BinOp::Log(LogOp::And)),
             Self::BoolOr => Some(BinOp::Log(LogOp::Or)),
+            Self::Coalesce => Some(BinOp::Log(LogOp::Coalesce)),
             Self::Eq => Some(BinOp::Comp(CompOp::Equal)),
             Self::NotEq => Some(BinOp::Comp(CompOp::NotEqual)),
             Self::StrictEq => Some(BinOp::Comp(CompOp::StrictEqual)),
@@ -207,6 +210,7 @@ impl Display for Punctuator {
                 Self::CloseBlock => "}",
                 Self::CloseBracket => "]",
                 Self::CloseParen => ")",
+                Self::Coalesce => "??",
                 Self::Colon => ":",
                 Self::Comma => ",",
                 Self::Dec => "--",
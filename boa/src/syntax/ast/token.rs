@@ -5,7 +5,7 @@
 //!
 //! [spec]: https://tc39.es/ecma262/#sec-tokens
 
-use crate::builtins::BigInt;
+use crate::builtins::{value::JsString, BigInt};
 use crate::syntax::{
     ast::{Keyword, Punctuator, Span},
     lexer::LexerError,
@@ -238,7 +238,7 @@ pub enum TokenKind {
     Punctuator(Punctuator),
 
     /// A string literal.
-    StringLiteral(Box<str>),
+    StringLiteral(JsString),
 
     TemplateLiteral(Box<str>),
 
@@ -307,7 +307,7 @@ impl TokenKind {
     /// Creates a `StringLiteral` token type.
     pub fn string_literal<S>(lit: S) -> Self
     where
-        S: Into<Box<str>>,
+        S: Into<JsString>,
     {
         Self::StringLiteral(lit.into())
     }
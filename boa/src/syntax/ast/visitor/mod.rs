@@ -0,0 +1,711 @@
+//! Traversal of the syntax tree, for the tools that analyze or rewrite scripts.
+//!
+//! A `Visitor` has a `visit_*` method for each type of node, whose default implementation calls
+//! the `walk_*` function of the same name, which visits the children of the node. A visitor
+//! overrides the methods of the nodes it is interested in, and calls the `walk_*` function from
+//! them to keep visiting the children. `VisitorMut` is the same for mutable references to the
+//! nodes, so that a visitor can replace them.
+//!
+//! The bodies of the functions are parsed if they were deferred, see `Parser::lazy_functions`,
+//! and the bodies with a syntax error are not visited.
+//!
+//! # Example
+//!
+//! ```
+//! use boa::{
+//!     syntax::ast::{node::Identifier, visitor::Visitor},
+//!     ParseOptions,
+//! };
+//!
+//! /// Collects the names of the variables that a script reads or writes.
+//! #[derive(Default)]
+//! struct Names(Vec<String>);
+//!
+//! impl Visitor for Names {
+//!     fn visit_identifier(&mut self, identifier: &Identifier) {
+//!         self.0.push(identifier.as_ref().to_owned());
+//!     }
+//! }
+//!
+//! let src = "let a = b + c(function () { return d; });";
+//! let statements = boa::parse(src, ParseOptions::default()).unwrap();
+//! let mut names = Names::default();
+//! names.visit_statement_list(&statements);
+//! assert_eq!(names.0, ["b", "c", "d"]);
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use crate::syntax::ast::{
+    node::{
+        ArrayDecl, ArrowFunctionDecl, Assign, BinOp, Block, Break, Call, Case, Catch,
+        ConditionalOp, ConstDecl, ConstDeclList, Continue, DoWhileLoop, ForLoop, FormalParameter,
+        FunctionDecl, FunctionExpr, GetConstField, GetField, Identifier, If, LazyStatementList,
+        LetDecl, LetDeclList, New, Object, PropertyDefinition, Return, Spread, StatementList,
+        Switch, Throw, Try, UnaryOp, VarDecl, VarDeclList, WhileLoop,
+    },
+    Const, Node,
+};
+
+/// Generates the methods of a visitor, which call the walk function of the same name by default.
+macro_rules! visit_methods {
+    ($($(#[$doc:meta])* $visit:ident($node:ty) => $walk:ident;)*) => {
+        $(
+            $(#[$doc])*
+            fn $visit(&mut self, node: $node) {
+                $walk(self, node)
+            }
+        )*
+    };
+}
+
+/// Generates the methods of a visitor for the nodes without children, which do nothing by
+/// default.
+macro_rules! visit_leaves {
+    ($($(#[$doc:meta])* $visit:ident($node:ty);)*) => {
+        $(
+            $(#[$doc])*
+            fn $visit(&mut self, _node: $node) {}
+        )*
+    };
+}
+
+/// A visitor of the syntax tree, see the module documentation.
+pub trait Visitor {
+    visit_methods! {
+        /// Visits a script, a block or a function body.
+        visit_statement_list(&StatementList) => walk_statement_list;
+        /// Visits any node, by calling the method of its type.
+        visit_node(&Node) => walk_node;
+        visit_array_decl(&ArrayDecl) => walk_array_decl;
+        visit_arrow_function_decl(&ArrowFunctionDecl) => walk_arrow_function_decl;
+        visit_assign(&Assign) => walk_assign;
+        visit_bin_op(&BinOp) => walk_bin_op;
+        visit_block(&Block) => walk_block;
+        visit_call(&Call) => walk_call;
+        visit_case(&Case) => walk_case;
+        visit_catch(&Catch) => walk_catch;
+        visit_conditional_op(&ConditionalOp) => walk_conditional_op;
+        visit_const_decl_list(&ConstDeclList) => walk_const_decl_list;
+        visit_const_decl(&ConstDecl) => walk_const_decl;
+        visit_do_while_loop(&DoWhileLoop) => walk_do_while_loop;
+        visit_for_loop(&ForLoop) => walk_for_loop;
+        visit_formal_parameter(&FormalParameter) => walk_formal_parameter;
+        visit_function_decl(&FunctionDecl) => walk_function_decl;
+        visit_function_expr(&FunctionExpr) => walk_function_expr;
+        /// Visits the body of a function, which is parsed if it was deferred.
+        visit_function_body(&LazyStatementList) => walk_function_body;
+        visit_get_const_field(&GetConstField) => walk_get_const_field;
+        visit_get_field(&GetField) => walk_get_field;
+        visit_if(&If) => walk_if;
+        visit_let_decl_list(&LetDeclList) => walk_let_decl_list;
+        visit_let_decl(&LetDecl) => walk_let_decl;
+        visit_new(&New) => walk_new;
+        visit_object(&Object) => walk_object;
+        visit_property_definition(&PropertyDefinition) => walk_property_definition;
+        visit_return(&Return) => walk_return;
+        visit_spread(&Spread) => walk_spread;
+        visit_switch(&Switch) => walk_switch;
+        visit_throw(&Throw) => walk_throw;
+        visit_try(&Try) => walk_try;
+        visit_unary_op(&UnaryOp) => walk_unary_op;
+        visit_var_decl_list(&VarDeclList) => walk_var_decl_list;
+        visit_var_decl(&VarDecl) => walk_var_decl;
+        visit_while_loop(&WhileLoop) => walk_while_loop;
+    }
+
+    visit_leaves! {
+        visit_break(&Break);
+        visit_const(&Const);
+        visit_continue(&Continue);
+        /// Visits an identifier that is read or written, like `a` in `a = b`, but not the names
+        /// of declarations, parameters or properties.
+        visit_identifier(&Identifier);
+    }
+}
+
+/// A visitor of the syntax tree that can change it, see the module documentation.
+pub trait VisitorMut {
+    visit_methods! {
+        /// Visits a script, a block or a function body.
+        visit_statement_list_mut(&mut StatementList) => walk_statement_list_mut;
+        /// Visits any node, by calling the method of its type. A node can be replaced here by a
+        /// node of another type.
+        visit_node_mut(&mut Node) => walk_node_mut;
+        visit_array_decl_mut(&mut ArrayDecl) => walk_array_decl_mut;
+        visit_arrow_function_decl_mut(&mut ArrowFunctionDecl) => walk_arrow_function_decl_mut;
+        visit_assign_mut(&mut Assign) => walk_assign_mut;
+        visit_bin_op_mut(&mut BinOp) => walk_bin_op_mut;
+        visit_block_mut(&mut Block) => walk_block_mut;
+        visit_call_mut(&mut Call) => walk_call_mut;
+        visit_case_mut(&mut Case) => walk_case_mut;
+        visit_catch_mut(&mut Catch) => walk_catch_mut;
+        visit_conditional_op_mut(&mut ConditionalOp) => walk_conditional_op_mut;
+        visit_const_decl_list_mut(&mut ConstDeclList) => walk_const_decl_list_mut;
+        visit_const_decl_mut(&mut ConstDecl) => walk_const_decl_mut;
+        visit_do_while_loop_mut(&mut DoWhileLoop) => walk_do_while_loop_mut;
+        visit_for_loop_mut(&mut ForLoop) => walk_for_loop_mut;
+        visit_formal_parameter_mut(&mut FormalParameter) => walk_formal_parameter_mut;
+        visit_function_decl_mut(&mut FunctionDecl) => walk_function_decl_mut;
+        visit_function_expr_mut(&mut FunctionExpr) => walk_function_expr_mut;
+        /// Visits the body of a function, which is parsed if it was deferred. The body is
+        /// replaced by the visited statements, so the other clones of the function keep the
+        /// original body.
+        visit_function_body_mut(&mut LazyStatementList) => walk_function_body_mut;
+        visit_get_const_field_mut(&mut GetConstField) => walk_get_const_field_mut;
+        visit_get_field_mut(&mut GetField) => walk_get_field_mut;
+        visit_if_mut(&mut If) => walk_if_mut;
+        visit_let_decl_list_mut(&mut LetDeclList) => walk_let_decl_list_mut;
+        visit_let_decl_mut(&mut LetDecl) => walk_let_decl_mut;
+        visit_new_mut(&mut New) => walk_new_mut;
+        visit_object_mut(&mut Object) => walk_object_mut;
+        visit_property_definition_mut(&mut PropertyDefinition) => walk_property_definition_mut;
+        visit_return_mut(&mut Return) => walk_return_mut;
+        visit_spread_mut(&mut Spread) => walk_spread_mut;
+        visit_switch_mut(&mut Switch) => walk_switch_mut;
+        visit_throw_mut(&mut Throw) => walk_throw_mut;
+        visit_try_mut(&mut Try) => walk_try_mut;
+        visit_unary_op_mut(&mut UnaryOp) => walk_unary_op_mut;
+        visit_var_decl_list_mut(&mut VarDeclList) => walk_var_decl_list_mut;
+        visit_var_decl_mut(&mut VarDecl) => walk_var_decl_mut;
+        visit_while_loop_mut(&mut WhileLoop) => walk_while_loop_mut;
+    }
+
+    visit_leaves! {
+        visit_break_mut(&mut Break);
+        visit_const_mut(&mut Const);
+        visit_continue_mut(&mut Continue);
+        /// Visits an identifier that is read or written, see `Visitor::visit_identifier`.
+        visit_identifier_mut(&mut Identifier);
+    }
+}
+
+pub fn walk_statement_list<V: Visitor + ?Sized>(visitor: &mut V, list: &StatementList) {
+    for statement in list.statements() {
+        visitor.visit_node(statement);
+    }
+}
+
+pub fn walk_node<V: Visitor + ?Sized>(visitor: &mut V, node: &Node) {
+    match node {
+        Node::ArrayDecl(n) => visitor.visit_array_decl(n),
+        Node::ArrowFunctionDecl(n) => visitor.visit_arrow_function_decl(n),
+        Node::Assign(n) => visitor.visit_assign(n),
+        Node::BinOp(n) => visitor.visit_bin_op(n),
+        Node::Block(n) => visitor.visit_block(n),
+        Node::Break(n) => visitor.visit_break(n),
+        Node::Call(n) => visitor.visit_call(n),
+        Node::ConditionalOp(n) => visitor.visit_conditional_op(n),
+        Node::Const(n) => visitor.visit_const(n),
+        Node::ConstDeclList(n) => visitor.visit_const_decl_list(n),
+        Node::Continue(n) => visitor.visit_continue(n),
+        Node::DoWhileLoop(n) => visitor.visit_do_while_loop(n),
+        Node::FunctionDecl(n) => visitor.visit_function_decl(n),
+        Node::FunctionExpr(n) => visitor.visit_function_expr(n),
+        Node::GetConstField(n) => visitor.visit_get_const_field(n),
+        Node::GetField(n) => visitor.visit_get_field(n),
+        Node::ForLoop(n) => visitor.visit_for_loop(n),
+        Node::If(n) => visitor.visit_if(n),
+        Node::LetDeclList(n) => visitor.visit_let_decl_list(n),
+        Node::Identifier(n) => visitor.visit_identifier(n),
+        Node::New(n) => visitor.visit_new(n),
+        Node::Object(n) => visitor.visit_object(n),
+        Node::Return(n) => visitor.visit_return(n),
+        Node::Switch(n) => visitor.visit_switch(n),
+        Node::Spread(n) => visitor.visit_spread(n),
+        Node::Throw(n) => visitor.visit_throw(n),
+        Node::Try(n) => visitor.visit_try(n),
+        Node::UnaryOp(n) => visitor.visit_unary_op(n),
+        Node::VarDeclList(n) => visitor.visit_var_decl_list(n),
+        Node::WhileLoop(n) => visitor.visit_while_loop(n),
+        Node::Debugger | Node::This => {}
+    }
+}
+
+pub fn walk_array_decl<V: Visitor + ?Sized>(visitor: &mut V, array: &ArrayDecl) {
+    for element in array.as_ref() {
+        visitor.visit_node(element);
+    }
+}
+
+pub fn walk_arrow_function_decl<V: Visitor + ?Sized>(visitor: &mut V, arrow: &ArrowFunctionDecl) {
+    for parameter in arrow.params() {
+        visitor.visit_formal_parameter(parameter);
+    }
+    visitor.visit_function_body(arrow.body());
+}
+
+pub fn walk_assign<V: Visitor + ?Sized>(visitor: &mut V, assign: &Assign) {
+    visitor.visit_node(assign.lhs());
+    visitor.visit_node(assign.rhs());
+}
+
+pub fn walk_bin_op<V: Visitor + ?Sized>(visitor: &mut V, bin_op: &BinOp) {
+    visitor.visit_node(bin_op.lhs());
+    visitor.visit_node(bin_op.rhs());
+}
+
+pub fn walk_block<V: Visitor + ?Sized>(visitor: &mut V, block: &Block) {
+    visitor.visit_statement_list(block.statement_list());
+}
+
+pub fn walk_call<V: Visitor + ?Sized>(visitor: &mut V, call: &Call) {
+    visitor.visit_node(call.expr());
+    for arg in call.args() {
+        visitor.visit_node(arg);
+    }
+}
+
+pub fn walk_case<V: Visitor + ?Sized>(visitor: &mut V, case: &Case) {
+    visitor.visit_node(case.condition());
+    visitor.visit_statement_list(case.body());
+}
+
+pub fn walk_catch<V: Visitor + ?Sized>(visitor: &mut V, catch: &Catch) {
+    visitor.visit_block(catch.block());
+}
+
+pub fn walk_conditional_op<V: Visitor + ?Sized>(visitor: &mut V, cond_op: &ConditionalOp) {
+    visitor.visit_node(cond_op.cond());
+    visitor.visit_node(cond_op.if_true());
+    visitor.visit_node(cond_op.if_false());
+}
+
+pub fn walk_const_decl_list<V: Visitor + ?Sized>(visitor: &mut V, list: &ConstDeclList) {
+    for decl in list.as_ref() {
+        visitor.visit_const_decl(decl);
+    }
+}
+
+pub fn walk_const_decl<V: Visitor + ?Sized>(visitor: &mut V, decl: &ConstDecl) {
+    visitor.visit_node(decl.init());
+}
+
+pub fn walk_do_while_loop<V: Visitor + ?Sized>(visitor: &mut V, do_while: &DoWhileLoop) {
+    visitor.visit_node(do_while.body());
+    visitor.visit_node(do_while.cond());
+}
+
+pub fn walk_for_loop<V: Visitor + ?Sized>(visitor: &mut V, for_loop: &ForLoop) {
+    if let Some(init) = for_loop.init() {
+        visitor.visit_node(init);
+    }
+    if let Some(condition) = for_loop.condition() {
+        visitor.visit_node(condition);
+    }
+    if let Some(final_expr) = for_loop.final_expr() {
+        visitor.visit_node(final_expr);
+    }
+    visitor.visit_node(for_loop.body());
+}
+
+pub fn walk_formal_parameter<V: Visitor + ?Sized>(visitor: &mut V, parameter: &FormalParameter) {
+    if let Some(init) = parameter.init() {
+        visitor.visit_node(init);
+    }
+}
+
+pub fn walk_function_decl<V: Visitor + ?Sized>(visitor: &mut V, decl: &FunctionDecl) {
+    for parameter in decl.parameters() {
+        visitor.visit_formal_parameter(parameter);
+    }
+    visitor.visit_function_body(decl.body());
+}
+
+pub fn walk_function_expr<V: Visitor + ?Sized>(visitor: &mut V, expr: &FunctionExpr) {
+    for parameter in expr.parameters() {
+        visitor.visit_formal_parameter(parameter);
+    }
+    visitor.visit_function_body(expr.body());
+}
+
+pub fn walk_function_body<V: Visitor + ?Sized>(visitor: &mut V, body: &LazyStatementList) {
+    if let Ok(statements) = body.statements() {
+        visitor.visit_statement_list(&statements);
+    }
+}
+
+pub fn walk_get_const_field<V: Visitor + ?Sized>(visitor: &mut V, get_field: &GetConstField) {
+    visitor.visit_node(get_field.obj());
+}
+
+pub fn walk_get_field<V: Visitor + ?Sized>(visitor: &mut V, get_field: &GetField) {
+    visitor.visit_node(get_field.obj());
+    visitor.visit_node(get_field.field());
+}
+
+pub fn walk_if<V: Visitor + ?Sized>(visitor: &mut V, if_node: &If) {
+    visitor.visit_node(if_node.cond());
+    visitor.visit_node(if_node.body());
+    if let Some(else_node) = if_node.else_node() {
+        visitor.visit_node(else_node);
+    }
+}
+
+pub fn walk_let_decl_list<V: Visitor + ?Sized>(visitor: &mut V, list: &LetDeclList) {
+    for decl in list.as_ref() {
+        visitor.visit_let_decl(decl);
+    }
+}
+
+pub fn walk_let_decl<V: Visitor + ?Sized>(visitor: &mut V, decl: &LetDecl) {
+    if let Some(init) = decl.init() {
+        visitor.visit_node(init);
+    }
+}
+
+pub fn walk_new<V: Visitor + ?Sized>(visitor: &mut V, new: &New) {
+    visitor.visit_node(new.expr());
+    for arg in new.args() {
+        visitor.visit_node(arg);
+    }
+}
+
+pub fn walk_object<V: Visitor + ?Sized>(visitor: &mut V, object: &Object) {
+    for property in object.properties() {
+        visitor.visit_property_definition(property);
+    }
+}
+
+pub fn walk_property_definition<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    property: &PropertyDefinition,
+) {
+    match property {
+        PropertyDefinition::IdentifierReference(_) => {}
+        PropertyDefinition::Property(_, value) => visitor.visit_node(value),
+        PropertyDefinition::MethodDefinition(_, _, function) => {
+            visitor.visit_function_expr(function)
+        }
+        PropertyDefinition::SpreadObject(node) => visitor.visit_node(node),
+    }
+}
+
+pub fn walk_return<V: Visitor + ?Sized>(visitor: &mut V, ret: &Return) {
+    if let Some(expr) = ret.expr() {
+        visitor.visit_node(expr);
+    }
+}
+
+pub fn walk_spread<V: Visitor + ?Sized>(visitor: &mut V, spread: &Spread) {
+    visitor.visit_node(spread.val());
+}
+
+pub fn walk_switch<V: Visitor + ?Sized>(visitor: &mut V, switch: &Switch) {
+    visitor.visit_node(switch.val());
+    for case in switch.cases() {
+        visitor.visit_case(case);
+    }
+    if let Some(default) = switch.default() {
+        visitor.visit_node(default);
+    }
+}
+
+pub fn walk_throw<V: Visitor + ?Sized>(visitor: &mut V, throw: &Throw) {
+    visitor.visit_node(throw.expr());
+}
+
+pub fn walk_try<V: Visitor + ?Sized>(visitor: &mut V, try_node: &Try) {
+    visitor.visit_block(try_node.block());
+    if let Some(catch) = try_node.catch() {
+        visitor.visit_catch(catch);
+    }
+    if let Some(finally) = try_node.finally() {
+        visitor.visit_block(finally);
+    }
+}
+
+pub fn walk_unary_op<V: Visitor + ?Sized>(visitor: &mut V, unary_op: &UnaryOp) {
+    visitor.visit_node(unary_op.target());
+}
+
+pub fn walk_var_decl_list<V: Visitor + ?Sized>(visitor: &mut V, list: &VarDeclList) {
+    for decl in list.as_ref() {
+        visitor.visit_var_decl(decl);
+    }
+}
+
+pub fn walk_var_decl<V: Visitor + ?Sized>(visitor: &mut V, decl: &VarDecl) {
+    if let Some(init) = decl.init() {
+        visitor.visit_node(init);
+    }
+}
+
+pub fn walk_while_loop<V: Visitor + ?Sized>(visitor: &mut V, while_loop: &WhileLoop) {
+    visitor.visit_node(while_loop.cond());
+    visitor.visit_node(while_loop.expr());
+}
+
+pub fn walk_statement_list_mut<V: VisitorMut + ?Sized>(visitor: &mut V, list: &mut StatementList) {
+    for statement in list.statements_mut() {
+        visitor.visit_node_mut(statement);
+    }
+}
+
+pub fn walk_node_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Node) {
+    match node {
+        Node::ArrayDecl(n) => visitor.visit_array_decl_mut(n),
+        Node::ArrowFunctionDecl(n) => visitor.visit_arrow_function_decl_mut(n),
+        Node::Assign(n) => visitor.visit_assign_mut(n),
+        Node::BinOp(n) => visitor.visit_bin_op_mut(n),
+        Node::Block(n) => visitor.visit_block_mut(n),
+        Node::Break(n) => visitor.visit_break_mut(n),
+        Node::Call(n) => visitor.visit_call_mut(n),
+        Node::ConditionalOp(n) => visitor.visit_conditional_op_mut(n),
+        Node::Const(n) => visitor.visit_const_mut(n),
+        Node::ConstDeclList(n) => visitor.visit_const_decl_list_mut(n),
+        Node::Continue(n) => visitor.visit_continue_mut(n),
+        Node::DoWhileLoop(n) => visitor.visit_do_while_loop_mut(n),
+        Node::FunctionDecl(n) => visitor.visit_function_decl_mut(n),
+        Node::FunctionExpr(n) => visitor.visit_function_expr_mut(n),
+        Node::GetConstField(n) => visitor.visit_get_const_field_mut(n),
+        Node::GetField(n) => visitor.visit_get_field_mut(n),
+        Node::ForLoop(n) => visitor.visit_for_loop_mut(n),
+        Node::If(n) => visitor.visit_if_mut(n),
+        Node::LetDeclList(n) => visitor.visit_let_decl_list_mut(n),
+        Node::Identifier(n) => visitor.visit_identifier_mut(n),
+        Node::New(n) => visitor.visit_new_mut(n),
+        Node::Object(n) => visitor.visit_object_mut(n),
+        Node::Return(n) => visitor.visit_return_mut(n),
+        Node::Switch(n) => visitor.visit_switch_mut(n),
+        Node::Spread(n) => visitor.visit_spread_mut(n),
+        Node::Throw(n) => visitor.visit_throw_mut(n),
+        Node::Try(n) => visitor.visit_try_mut(n),
+        Node::UnaryOp(n) => visitor.visit_unary_op_mut(n),
+        Node::VarDeclList(n) => visitor.visit_var_decl_list_mut(n),
+        Node::WhileLoop(n) => visitor.visit_while_loop_mut(n),
+        Node::Debugger | Node::This => {}
+    }
+}
+
+pub fn walk_array_decl_mut<V: VisitorMut + ?Sized>(visitor: &mut V, array: &mut ArrayDecl) {
+    for element in array.elements_mut() {
+        visitor.visit_node_mut(element);
+    }
+}
+
+pub fn walk_arrow_function_decl_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    arrow: &mut ArrowFunctionDecl,
+) {
+    for parameter in arrow.params_mut() {
+        visitor.visit_formal_parameter_mut(parameter);
+    }
+    visitor.visit_function_body_mut(arrow.body_mut());
+}
+
+pub fn walk_assign_mut<V: VisitorMut + ?Sized>(visitor: &mut V, assign: &mut Assign) {
+    visitor.visit_node_mut(assign.lhs_mut());
+    visitor.visit_node_mut(assign.rhs_mut());
+}
+
+pub fn walk_bin_op_mut<V: VisitorMut + ?Sized>(visitor: &mut V, bin_op: &mut BinOp) {
+    visitor.visit_node_mut(bin_op.lhs_mut());
+    visitor.visit_node_mut(bin_op.rhs_mut());
+}
+
+pub fn walk_block_mut<V: VisitorMut + ?Sized>(visitor: &mut V, block: &mut Block) {
+    visitor.visit_statement_list_mut(block.statement_list_mut());
+}
+
+pub fn walk_call_mut<V: VisitorMut + ?Sized>(visitor: &mut V, call: &mut Call) {
+    visitor.visit_node_mut(call.expr_mut());
+    for arg in call.args_mut() {
+        visitor.visit_node_mut(arg);
+    }
+}
+
+pub fn walk_case_mut<V: VisitorMut + ?Sized>(visitor: &mut V, case: &mut Case) {
+    visitor.visit_node_mut(case.condition_mut());
+    visitor.visit_statement_list_mut(case.body_mut());
+}
+
+pub fn walk_catch_mut<V: VisitorMut + ?Sized>(visitor: &mut V, catch: &mut Catch) {
+    visitor.visit_block_mut(catch.block_mut());
+}
+
+pub fn walk_conditional_op_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    cond_op: &mut ConditionalOp,
+) {
+    visitor.visit_node_mut(cond_op.cond_mut());
+    visitor.visit_node_mut(cond_op.if_true_mut());
+    visitor.visit_node_mut(cond_op.if_false_mut());
+}
+
+pub fn walk_const_decl_list_mut<V: VisitorMut + ?Sized>(visitor: &mut V, list: &mut ConstDeclList) {
+    for decl in list.decls_mut() {
+        visitor.visit_const_decl_mut(decl);
+    }
+}
+
+pub fn walk_const_decl_mut<V: VisitorMut + ?Sized>(visitor: &mut V, decl: &mut ConstDecl) {
+    visitor.visit_node_mut(decl.init_mut());
+}
+
+pub fn walk_do_while_loop_mut<V: VisitorMut + ?Sized>(visitor: &mut V, do_while: &mut DoWhileLoop) {
+    visitor.visit_node_mut(do_while.body_mut());
+    visitor.visit_node_mut(do_while.cond_mut());
+}
+
+pub fn walk_for_loop_mut<V: VisitorMut + ?Sized>(visitor: &mut V, for_loop: &mut ForLoop) {
+    if let Some(init) = for_loop.init_mut() {
+        visitor.visit_node_mut(init);
+    }
+    if let Some(condition) = for_loop.condition_mut() {
+        visitor.visit_node_mut(condition);
+    }
+    if let Some(final_expr) = for_loop.final_expr_mut() {
+        visitor.visit_node_mut(final_expr);
+    }
+    visitor.visit_node_mut(for_loop.body_mut());
+}
+
+pub fn walk_formal_parameter_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    parameter: &mut FormalParameter,
+) {
+    if let Some(init) = parameter.init_mut() {
+        visitor.visit_node_mut(init);
+    }
+}
+
+pub fn walk_function_decl_mut<V: VisitorMut + ?Sized>(visitor: &mut V, decl: &mut FunctionDecl) {
+    for parameter in decl.parameters_mut() {
+        visitor.visit_formal_parameter_mut(parameter);
+    }
+    visitor.visit_function_body_mut(decl.body_mut());
+}
+
+pub fn walk_function_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, expr: &mut FunctionExpr) {
+    for parameter in expr.parameters_mut() {
+        visitor.visit_formal_parameter_mut(parameter);
+    }
+    visitor.visit_function_body_mut(expr.body_mut());
+}
+
+pub fn walk_function_body_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    body: &mut LazyStatementList,
+) {
+    if let Ok(statements) = body.statements() {
+        let mut statements = StatementList::clone(&statements);
+        visitor.visit_statement_list_mut(&mut statements);
+        *body = LazyStatementList::from(statements);
+    }
+}
+
+pub fn walk_get_const_field_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    get_field: &mut GetConstField,
+) {
+    visitor.visit_node_mut(get_field.obj_mut());
+}
+
+pub fn walk_get_field_mut<V: VisitorMut + ?Sized>(visitor: &mut V, get_field: &mut GetField) {
+    visitor.visit_node_mut(get_field.obj_mut());
+    visitor.visit_node_mut(get_field.field_mut());
+}
+
+pub fn walk_if_mut<V: VisitorMut + ?Sized>(visitor: &mut V, if_node: &mut If) {
+    visitor.visit_node_mut(if_node.cond_mut());
+    visitor.visit_node_mut(if_node.body_mut());
+    if let Some(else_node) = if_node.else_node_mut() {
+        visitor.visit_node_mut(else_node);
+    }
+}
+
+pub fn walk_let_decl_list_mut<V: VisitorMut + ?Sized>(visitor: &mut V, list: &mut LetDeclList) {
+    for decl in list.decls_mut() {
+        visitor.visit_let_decl_mut(decl);
+    }
+}
+
+pub fn walk_let_decl_mut<V: VisitorMut + ?Sized>(visitor: &mut V, decl: &mut LetDecl) {
+    if let Some(init) = decl.init_mut() {
+        visitor.visit_node_mut(init);
+    }
+}
+
+pub fn walk_new_mut<V: VisitorMut + ?Sized>(visitor: &mut V, new: &mut New) {
+    visitor.visit_node_mut(new.expr_mut());
+    for arg in new.args_mut() {
+        visitor.visit_node_mut(arg);
+    }
+}
+
+pub fn walk_object_mut<V: VisitorMut + ?Sized>(visitor: &mut V, object: &mut Object) {
+    for property in object.properties_mut() {
+        visitor.visit_property_definition_mut(property);
+    }
+}
+
+pub fn walk_property_definition_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    property: &mut PropertyDefinition,
+) {
+    match property {
+        PropertyDefinition::IdentifierReference(_) => {}
+        PropertyDefinition::Property(_, value) => visitor.visit_node_mut(value),
+        PropertyDefinition::MethodDefinition(_, _, function) => {
+            visitor.visit_function_expr_mut(function)
+        }
+        PropertyDefinition::SpreadObject(node) => visitor.visit_node_mut(node),
+    }
+}
+
+pub fn walk_return_mut<V: VisitorMut + ?Sized>(visitor: &mut V, ret: &mut Return) {
+    if let Some(expr) = ret.expr_mut() {
+        visitor.visit_node_mut(expr);
+    }
+}
+
+pub fn walk_spread_mut<V: VisitorMut + ?Sized>(visitor: &mut V, spread: &mut Spread) {
+    visitor.visit_node_mut(spread.val_mut());
+}
+
+pub fn walk_switch_mut<V: VisitorMut + ?Sized>(visitor: &mut V, switch: &mut Switch) {
+    visitor.visit_node_mut(switch.val_mut());
+    for case in switch.cases_mut() {
+        visitor.visit_case_mut(case);
+    }
+    if let Some(default) = switch.default_mut() {
+        visitor.visit_node_mut(default);
+    }
+}
+
+pub fn walk_throw_mut<V: VisitorMut + ?Sized>(visitor: &mut V, throw: &mut Throw) {
+    visitor.visit_node_mut(throw.expr_mut());
+}
+
+pub fn walk_try_mut<V: VisitorMut + ?Sized>(visitor: &mut V, try_node: &mut Try) {
+    visitor.visit_block_mut(try_node.block_mut());
+    if let Some(catch) = try_node.catch_mut() {
+        visitor.visit_catch_mut(catch);
+    }
+    if let Some(finally) = try_node.finally_mut() {
+        visitor.visit_block_mut(finally);
+    }
+}
+
+pub fn walk_unary_op_mut<V: VisitorMut + ?Sized>(visitor: &mut V, unary_op: &mut UnaryOp) {
+    visitor.visit_node_mut(unary_op.target_mut());
+}
+
+pub fn walk_var_decl_list_mut<V: VisitorMut + ?Sized>(visitor: &mut V, list: &mut VarDeclList) {
+    for decl in list.decls_mut() {
+        visitor.visit_var_decl_mut(decl);
+    }
+}
+
+pub fn walk_var_decl_mut<V: VisitorMut + ?Sized>(visitor: &mut V, decl: &mut VarDecl) {
+    if let Some(init) = decl.init_mut() {
+        visitor.visit_node_mut(init);
+    }
+}
+
+pub fn walk_while_loop_mut<V: VisitorMut + ?Sized>(visitor: &mut V, while_loop: &mut WhileLoop) {
+    visitor.visit_node_mut(while_loop.cond_mut());
+    visitor.visit_node_mut(while_loop.expr_mut());
+}
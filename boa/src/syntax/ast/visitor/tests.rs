@@ -0,0 +1,83 @@
+use super::*;
+use crate::syntax::{codegen::Style, lexer::Lexer, parser::Parser};
+
+fn parse(js: &str, lazy_functions: bool) -> StatementList {
+    let mut lexer = Lexer::new(js);
+    lexer.lex().expect("failed to lex");
+    Parser::new(&lexer.tokens)
+        .lazy_functions(lazy_functions)
+        .parse_all()
+        .expect("failed to parse")
+}
+
+/// Collects the identifiers and counts the nodes.
+#[derive(Debug, Default)]
+struct Collector {
+    identifiers: Vec<String>,
+    nodes: usize,
+}
+
+impl Visitor for Collector {
+    fn visit_node(&mut self, node: &Node) {
+        self.nodes += 1;
+        walk_node(self, node);
+    }
+
+    fn visit_identifier(&mut self, identifier: &Identifier) {
+        self.identifiers.push(identifier.as_ref().to_owned());
+    }
+}
+
+#[test]
+fn visit_every_node() {
+    let js = "
+        var a = [b, ...c];
+        function f(d = e) { return g ? h : i; }
+        let o = { p: j, m() { k(); }, ...l };
+        for (let x = m; x < n; x++) { while (q) { do { r.s[t] = new U(v); } while (w); } }
+        switch (y) { case z: aa(); default: bb(); }
+        try { throw cc; } catch (err) { dd; } finally { ee = () => ff; }
+        if (gg) { hh; } else { -ii; }
+    ";
+    for &lazy in &[false, true] {
+        let mut collector = Collector::default();
+        collector.visit_statement_list(&parse(js, lazy));
+        // The function declarations are hoisted to the start of the script.
+        let expected = "e g h i b c j k l m x n x q r t U v w y z aa bb cc dd ee ff gg hh ii";
+        assert_eq!(collector.identifiers.join(" "), expected);
+        assert!(collector.nodes > collector.identifiers.len());
+    }
+}
+
+/// Renames the identifiers, and replaces the additions of two numbers by their sum.
+struct Rewriter;
+
+impl VisitorMut for Rewriter {
+    fn visit_node_mut(&mut self, node: &mut Node) {
+        walk_node_mut(self, node);
+        if let Node::BinOp(bin_op) = node {
+            if let (Node::Const(Const::Int(a)), Node::Const(Const::Int(b))) =
+                (bin_op.lhs(), bin_op.rhs())
+            {
+                *node = Node::Const(Const::Int(a + b));
+            }
+        }
+    }
+
+    fn visit_identifier_mut(&mut self, identifier: &mut Identifier) {
+        *identifier = Identifier::from(format!("_{}", identifier.as_ref()));
+    }
+}
+
+#[test]
+fn rewrite_nodes() {
+    let js = "a = 1 + 2; function f() { return b * (3 + 4); } x = { m() { c(5 + 6); } };";
+    for &lazy in &[false, true] {
+        let mut statements = parse(js, lazy);
+        Rewriter.visit_statement_list_mut(&mut statements);
+        assert_eq!(
+            statements.to_js_string(Style::Compact),
+            "_a=3;function f(){return _b*7;}_x={m(){_c(11);}};"
+        );
+    }
+}
@@ -0,0 +1,1000 @@
+//! Code generation, which renders a syntax tree back to JavaScript source code.
+//!
+//! Unlike the `Display` implementation of the nodes, which is meant for debugging, the generated
+//! code is valid JavaScript that parses back to the same tree: operands are parenthesized where
+//! the precedence of their operators requires it, strings are escaped, and the expression
+//! statements that would be read as a declaration or a block are parenthesized. It is meant for
+//! tools that use the parser to transform code.
+//!
+//! Comments and the original layout are not part of the tree, so they are lost, unless the
+//! comments are kept by the lexer and given to `format`. Array holes are parsed as `undefined`
+//! elements, so they are generated as such.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builtins::Number,
+    syntax::ast::{
+        node::{
+            ArrowFunctionDecl, FormalParameter, LazyStatementList, MethodDefinitionKind, Node,
+            PropertyDefinition, StatementList,
+        },
+        op::{self, BitOp, CompOp, LogOp, NumOp},
+        token::{Token, TokenKind},
+        Const, Position, Punctuator, Span,
+    },
+    syntax::lexer::Comment,
+};
+use std::fmt::Write;
+
+/// The layout of the generated code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Without line breaks, indentation or optional spaces, like minified code.
+    Compact,
+    /// With one statement per line, indented by four spaces.
+    Pretty,
+}
+
+/// Generates the source code of a script.
+pub fn generate(statements: &StatementList, style: Style) -> String {
+    let mut generator = Generator::new(style, 0);
+    generator.statements(statements.statements(), statements.spans());
+    generator.out
+}
+
+/// Formats a script that was parsed from source code, in the pretty style, with its comments
+/// and the blank lines between its statements.
+///
+/// The `tokens` and the `comments` are the ones the lexer found in the source, see
+/// `Lexer::keep_comments`. The comments are placed before the token that follows them, or after
+/// the statement they follow on the same line if no token follows them on their line. The
+/// comments inside an expression are moved to the end of its statement.
+pub fn format(statements: &StatementList, tokens: &[Token], comments: &[Comment]) -> String {
+    let mut generator = Generator::new(Style::Pretty, 0);
+    generator.trivia = Some(Trivia {
+        comments: comments.to_vec(),
+        next: 0,
+        tokens: tokens
+            .iter()
+            .filter(|token| token.kind() != &TokenKind::LineTerminator)
+            .map(|token| token.span().start())
+            .collect(),
+        closing_braces: tokens
+            .iter()
+            .filter(|token| token.kind() == &TokenKind::Punctuator(Punctuator::CloseBlock))
+            .map(|token| token.span().start())
+            .collect(),
+        last_line: 0,
+    });
+    generator.statements(statements.statements(), statements.spans());
+    generator.remaining_comments();
+    let mut out = generator.out;
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+/// Generates the source code of a node, as an expression if it is one, and as a statement
+/// otherwise.
+pub fn generate_node(node: &Node, style: Style) -> String {
+    let mut generator = Generator::new(style, 0);
+    if is_statement(node) {
+        generator.statement(node);
+    } else {
+        generator.expression(node, Precedence::Assignment);
+    }
+    generator.out
+}
+
+/// The precedence of the expressions, from the loosest to the tightest.
+///
+/// An operand whose precedence is looser than what its operator requires is parenthesized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    Assignment,
+    Conditional,
+    LogicalOr,
+    LogicalAnd,
+    BitwiseOr,
+    BitwiseXor,
+    BitwiseAnd,
+    Equality,
+    Relational,
+    Shift,
+    Additive,
+    Multiplicative,
+    Exponentiation,
+    Unary,
+    Update,
+    Call,
+    Member,
+    Primary,
+}
+
+impl Precedence {
+    /// Gets the precedence of a binary operator.
+    fn of_binary(op: op::BinOp) -> Self {
+        match op {
+            op::BinOp::Num(NumOp::Add) | op::BinOp::Num(NumOp::Sub) => Self::Additive,
+            op::BinOp::Num(NumOp::Exp) => Self::Exponentiation,
+            op::BinOp::Num(_) => Self::Multiplicative,
+            op::BinOp::Bit(BitOp::And) => Self::BitwiseAnd,
+            op::BinOp::Bit(BitOp::Or) => Self::BitwiseOr,
+            op::BinOp::Bit(BitOp::Xor) => Self::BitwiseXor,
+            op::BinOp::Bit(_) => Self::Shift,
+            op::BinOp::Comp(CompOp::Equal)
+            | op::BinOp::Comp(CompOp::NotEqual)
+            | op::BinOp::Comp(CompOp::StrictEqual)
+            | op::BinOp::Comp(CompOp::StrictNotEqual) => Self::Equality,
+            op::BinOp::Comp(_) => Self::Relational,
+            op::BinOp::Log(LogOp::And) => Self::LogicalAnd,
+            op::BinOp::Log(LogOp::Or) | op::BinOp::Log(LogOp::Coalesce) => Self::LogicalOr,
+            op::BinOp::Assign(_) => Self::Assignment,
+        }
+    }
+
+    /// Gets the precedence of an expression.
+    fn of(node: &Node) -> Self {
+        match node {
+            Node::Assign(_) | Node::ArrowFunctionDecl(_) | Node::Spread(_) => Self::Assignment,
+            Node::ConditionalOp(_) => Self::Conditional,
+            Node::BinOp(bin_op) => Self::of_binary(bin_op.op()),
+            Node::UnaryOp(unary_op) => match unary_op.op() {
+                op::UnaryOp::IncrementPost | op::UnaryOp::DecrementPost => Self::Update,
+                _ => Self::Unary,
+            },
+            Node::Call(_) => Self::Call,
+            Node::New(_) | Node::GetConstField(_) | Node::GetField(_) => Self::Member,
+            // Negative numbers are generated with a minus sign, and the numbers that have no
+            // literal with a division.
+            Node::Const(Const::Int(value)) if *value < 0 => Self::Unary,
+            Node::Const(Const::Num(value)) if !value.is_finite() => Self::Multiplicative,
+            Node::Const(Const::Num(value)) if value.is_sign_negative() => Self::Unary,
+            _ => Self::Primary,
+        }
+    }
+
+    /// Gets the next tighter precedence.
+    fn next(self) -> Self {
+        match self {
+            Self::Assignment => Self::Conditional,
+            Self::Conditional => Self::LogicalOr,
+            Self::LogicalOr => Self::LogicalAnd,
+            Self::LogicalAnd => Self::BitwiseOr,
+            Self::BitwiseOr => Self::BitwiseXor,
+            Self::BitwiseXor => Self::BitwiseAnd,
+            Self::BitwiseAnd => Self::Equality,
+            Self::Equality => Self::Relational,
+            Self::Relational => Self::Shift,
+            Self::Shift => Self::Additive,
+            Self::Additive => Self::Multiplicative,
+            Self::Multiplicative => Self::Exponentiation,
+            Self::Exponentiation => Self::Unary,
+            Self::Unary => Self::Update,
+            Self::Update => Self::Call,
+            Self::Call => Self::Member,
+            Self::Member | Self::Primary => Self::Primary,
+        }
+    }
+}
+
+/// Checks if a node is a statement rather than an expression.
+fn is_statement(node: &Node) -> bool {
+    matches!(
+        node,
+        Node::Block(_)
+            | Node::Break(_)
+            | Node::ConstDeclList(_)
+            | Node::Continue(_)
+            | Node::Debugger
+            | Node::DoWhileLoop(_)
+            | Node::ForLoop(_)
+            | Node::FunctionDecl(_)
+            | Node::If(_)
+            | Node::LetDeclList(_)
+            | Node::Return(_)
+            | Node::Switch(_)
+            | Node::Throw(_)
+            | Node::Try(_)
+            | Node::VarDeclList(_)
+            | Node::WhileLoop(_)
+    )
+}
+
+/// Checks if two characters would be read as one token if they were not separated, like the
+/// letters of `typeof x` or the signs of `a - -b`.
+fn merges(last: char, first: char) -> bool {
+    let word = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    (word(last) && word(first))
+        || matches!(
+            (last, first),
+            ('+', '+') | ('-', '-') | ('-', '>') | ('<', '!') | ('/', '/') | ('/', '*')
+        )
+}
+
+/// Checks if a property name can be written without quotes.
+fn is_identifier_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Writes a string literal, in double quotes.
+fn string_literal(out: &mut String, string: &str) {
+    out.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            // Line separators end string literals in older engines.
+            '\u{2028}' | '\u{2029}' => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Writes a number literal, which is negative if the number is.
+///
+/// `NaN` and the infinities are written as divisions, because their globals can be shadowed.
+fn number_literal(out: &mut String, number: f64) {
+    if number.is_nan() {
+        out.push_str("0/0");
+    } else if number.is_infinite() {
+        out.push_str(if number > 0.0 { "1/0" } else { "-1/0" });
+    } else if number == 0.0 && number.is_sign_negative() {
+        out.push_str("-0");
+    } else {
+        out.push_str(&Number::to_native_string(number));
+    }
+}
+
+/// What the formatter keeps from the source besides the tree, see `format`.
+#[derive(Debug)]
+struct Trivia {
+    /// The comments of the source, in order.
+    comments: Vec<Comment>,
+    /// The index of the first comment that was not written yet.
+    next: usize,
+    /// The positions of the tokens other than line terminators, in order, to anchor the comments
+    /// to them.
+    tokens: Vec<Position>,
+    /// The positions of the `}` tokens, in order, to find the end of the blocks.
+    closing_braces: Vec<Position>,
+    /// The last line of the source that was written, or `0` before the first one.
+    last_line: u32,
+}
+
+/// The state of the generation of a script.
+#[derive(Debug)]
+struct Generator {
+    style: Style,
+    out: String,
+    /// The number of blocks the current line is nested in.
+    depth: usize,
+    /// The comments and positions of the source, when formatting it.
+    trivia: Option<Trivia>,
+}
+
+impl Generator {
+    fn new(style: Style, depth: usize) -> Self {
+        Self {
+            style,
+            out: String::new(),
+            depth,
+            trivia: None,
+        }
+    }
+
+    /// Writes a token, separated from the previous one if they would merge.
+    fn write(&mut self, token: &str) {
+        if let (Some(last), Some(first)) = (self.out.chars().next_back(), token.chars().next()) {
+            if merges(last, first) {
+                self.out.push(' ');
+            }
+        }
+        self.out.push_str(token);
+    }
+
+    /// Writes a space that is only there for readability.
+    fn space(&mut self) {
+        if self.style == Style::Pretty {
+            self.out.push(' ');
+        }
+    }
+
+    /// Starts a new line at the current depth, unless nothing was written yet.
+    fn line(&mut self) {
+        if self.style == Style::Pretty && !self.out.is_empty() {
+            self.out.push('\n');
+            self.out.push_str(&"    ".repeat(self.depth));
+        }
+    }
+
+    /// Writes a comma-separated list.
+    fn list<T, F>(&mut self, items: &[T], mut item: F)
+    where
+        F: FnMut(&mut Self, &T),
+    {
+        for (i, element) in items.iter().enumerate() {
+            if i > 0 {
+                self.write(",");
+                self.space();
+            }
+            item(self, element);
+        }
+    }
+
+    /// Takes the next comment that starts before `position`, if there is one.
+    fn next_comment_before(&mut self, position: Position) -> Option<Comment> {
+        let trivia = self.trivia.as_mut()?;
+        let comment = trivia.comments.get(trivia.next)?;
+        if comment.span().start() >= position {
+            return None;
+        }
+        trivia.next += 1;
+        Some(comment.clone())
+    }
+
+    /// Gets the position of the first token that starts after `position`, if there is one.
+    fn token_after(&self, position: Position) -> Option<Position> {
+        let tokens = &self.trivia.as_ref()?.tokens;
+        tokens
+            .get(tokens.partition_point(|token| *token <= position))
+            .copied()
+    }
+
+    /// Writes a comment on its own line, after a blank line if there is one before it in the
+    /// source.
+    fn comment_line(&mut self, comment: &Comment) {
+        self.blank_line(comment.span().start().line_number());
+        self.line();
+        self.out.push_str(comment.text());
+        self.written_until(comment.span().end().line_number());
+    }
+
+    /// Writes, on their own lines, the comments that start before `position`.
+    fn comments_before(&mut self, position: Position) {
+        while let Some(comment) = self.next_comment_before(position) {
+            self.comment_line(&comment);
+        }
+    }
+
+    /// Checks whether a token follows `comment` on the line where it ends, which a line comment
+    /// never does.
+    fn leads_token(&self, comment: &Comment) -> bool {
+        let end = comment.span().end();
+        !comment.is_line_comment()
+            && self
+                .token_after(end)
+                .is_some_and(|token| token.line_number() == end.line_number())
+    }
+
+    /// Writes the comments that are inside a statement that ends at `end`, or after it on the
+    /// same line, at the end of its line.
+    ///
+    /// The comments after the statement are left to the token that follows them, if it is
+    /// on their line, or comes before them, like the `}` after the last statement of a block.
+    fn trailing_comments(&mut self, end: Position) {
+        let next_line = Position::new(end.line_number() + 1, 1);
+        let limit = self
+            .token_after(end)
+            .map_or(next_line, |token| token.min(next_line));
+        let mut after_line_comment = false;
+        loop {
+            let leads_token = self
+                .trivia
+                .as_ref()
+                .and_then(|trivia| trivia.comments.get(trivia.next))
+                .is_some_and(|comment| comment.span().start() > end && self.leads_token(comment));
+            if leads_token {
+                break;
+            }
+            let comment = match self.next_comment_before(limit) {
+                Some(comment) => comment,
+                None => break,
+            };
+            // Whatever follows a line comment would be part of it.
+            if after_line_comment {
+                self.line();
+            } else {
+                self.out.push(' ');
+            }
+            self.out.push_str(comment.text());
+            after_line_comment = comment.is_line_comment();
+            self.written_until(comment.span().end().line_number());
+        }
+    }
+
+    /// Writes the comments that were not written yet, at the end of the script.
+    fn remaining_comments(&mut self) {
+        let end = Position::new(u32::MAX, u32::MAX);
+        while let Some(comment) = self.next_comment_before(end) {
+            self.comment_line(&comment);
+        }
+    }
+
+    /// Writes an empty line if `line` is not right after the last line of the source that was
+    /// written, unless the line starts a block or a case.
+    fn blank_line(&mut self, line: u32) {
+        if let Some(trivia) = &self.trivia {
+            let starts_block = self.out.is_empty() || self.out.ends_with(&['{', ':'][..]);
+            if trivia.last_line != 0 && line > trivia.last_line + 1 && !starts_block {
+                self.out.push('\n');
+            }
+        }
+    }
+
+    /// Records the last line of the source that was written.
+    fn written_until(&mut self, line: u32) {
+        if let Some(trivia) = &mut self.trivia {
+            trivia.last_line = trivia.last_line.max(line);
+        }
+    }
+
+    /// Writes a list of statements, in the order of the source if their spans are known.
+    fn statements(&mut self, statements: &[Node], spans: &[Span]) {
+        let mut ordered: Vec<_> = if spans.len() == statements.len() {
+            statements
+                .iter()
+                .zip(spans.iter().copied().map(Some))
+                .collect()
+        } else {
+            statements.iter().zip(std::iter::repeat(None)).collect()
+        };
+        // The function declarations are hoisted to the start of the list by the parser.
+        ordered.sort_by_key(|(_, span)| span.map(Span::start));
+        for (statement, span) in ordered {
+            if let Some(span) = span {
+                self.comments_before(span.start());
+                self.blank_line(span.start().line_number());
+                self.written_until(span.start().line_number());
+            }
+            self.line();
+            self.statement(statement);
+            if let Some(span) = span {
+                self.trailing_comments(span.end());
+                self.written_until(span.end().line_number());
+            }
+        }
+    }
+
+    /// Writes a block of statements, with the braces.
+    fn block(&mut self, statements: &[Node], spans: &[Span]) {
+        self.write("{");
+        if !statements.is_empty() {
+            self.depth += 1;
+            self.statements(statements, spans);
+            // The comments after the last statement of the block are kept inside of it.
+            let closing_brace = self.trivia.as_ref().and_then(|trivia| {
+                let end = spans.iter().map(|span| span.end()).max()?;
+                trivia
+                    .closing_braces
+                    .iter()
+                    .copied()
+                    .find(|brace| *brace > end)
+            });
+            if let Some(closing_brace) = closing_brace {
+                self.comments_before(closing_brace);
+            }
+            self.depth -= 1;
+            self.line();
+        }
+        self.write("}");
+    }
+
+    /// Writes the body of a statement, like a loop body, after its head.
+    fn body(&mut self, body: &Node) {
+        self.space();
+        self.statement(body);
+    }
+
+    fn statement(&mut self, node: &Node) {
+        match node {
+            Node::Block(block) => self.block(block.statements(), block.spans()),
+            Node::VarDeclList(_) | Node::LetDeclList(_) | Node::ConstDeclList(_) => {
+                self.declarations(node);
+                self.write(";");
+            }
+            Node::If(if_node) => {
+                self.write("if");
+                self.space();
+                self.write("(");
+                self.expression(if_node.cond(), Precedence::Assignment);
+                self.write(")");
+                match (if_node.body(), if_node.else_node()) {
+                    // The `else` would belong to the inner `if` without the braces.
+                    (body @ Node::If(_), Some(_)) => {
+                        self.space();
+                        self.block(std::slice::from_ref(body), &[]);
+                    }
+                    (body, _) => self.body(body),
+                }
+                if let Some(else_node) = if_node.else_node() {
+                    self.space();
+                    self.write("else");
+                    self.body(else_node);
+                }
+            }
+            Node::WhileLoop(while_loop) => {
+                self.write("while");
+                self.space();
+                self.write("(");
+                self.expression(while_loop.cond(), Precedence::Assignment);
+                self.write(")");
+                self.body(while_loop.expr());
+            }
+            Node::DoWhileLoop(do_while) => {
+                self.write("do");
+                self.body(do_while.body());
+                self.space();
+                self.write("while");
+                self.space();
+                self.write("(");
+                self.expression(do_while.cond(), Precedence::Assignment);
+                self.write(");");
+            }
+            Node::ForLoop(for_loop) => {
+                self.write("for");
+                self.space();
+                self.write("(");
+                match for_loop.init() {
+                    Some(init) if is_statement(init) => self.declarations(init),
+                    Some(init) => self.expression(init, Precedence::Assignment),
+                    None => {}
+                }
+                self.write(";");
+                if let Some(condition) = for_loop.condition() {
+                    self.space();
+                    self.expression(condition, Precedence::Assignment);
+                }
+                self.write(";");
+                if let Some(final_expr) = for_loop.final_expr() {
+                    self.space();
+                    self.expression(final_expr, Precedence::Assignment);
+                }
+                self.write(")");
+                self.body(for_loop.body());
+            }
+            Node::Switch(switch) => {
+                self.write("switch");
+                self.space();
+                self.write("(");
+                self.expression(switch.val(), Precedence::Assignment);
+                self.write(")");
+                self.space();
+                self.write("{");
+                self.depth += 1;
+                for case in switch.cases() {
+                    self.line();
+                    self.write("case");
+                    self.space();
+                    self.expression(case.condition(), Precedence::Assignment);
+                    self.write(":");
+                    self.depth += 1;
+                    self.statements(case.body().statements(), case.body().spans());
+                    self.depth -= 1;
+                }
+                if let Some(default) = switch.default() {
+                    self.line();
+                    self.write("default:");
+                    self.depth += 1;
+                    match default {
+                        Node::Block(block) => self.statements(block.statements(), block.spans()),
+                        default => {
+                            self.line();
+                            self.statement(default);
+                        }
+                    }
+                    self.depth -= 1;
+                }
+                self.depth -= 1;
+                self.line();
+                self.write("}");
+            }
+            Node::Try(try_node) => {
+                self.write("try");
+                self.space();
+                self.block(try_node.block().statements(), try_node.block().spans());
+                if let Some(catch) = try_node.catch() {
+                    self.space();
+                    self.write("catch");
+                    if let Some(parameter) = catch.parameter() {
+                        self.space();
+                        self.write("(");
+                        self.write(parameter);
+                        self.write(")");
+                    }
+                    self.space();
+                    self.block(catch.block().statements(), catch.block().spans());
+                }
+                if let Some(finally) = try_node.finally() {
+                    self.space();
+                    self.write("finally");
+                    self.space();
+                    self.block(finally.statements(), finally.spans());
+                }
+            }
+            Node::Return(ret) => {
+                self.write("return");
+                if let Some(expr) = ret.expr() {
+                    self.space();
+                    self.expression(expr, Precedence::Assignment);
+                }
+                self.write(";");
+            }
+            Node::Throw(throw) => {
+                self.write("throw");
+                self.space();
+                self.expression(throw.expr(), Precedence::Assignment);
+                self.write(";");
+            }
+            Node::Break(break_node) => {
+                self.write("break");
+                if let Some(label) = break_node.label() {
+                    self.write(" ");
+                    self.write(label);
+                }
+                self.write(";");
+            }
+            Node::Continue(cont) => {
+                self.write("continue");
+                if let Some(label) = cont.label() {
+                    self.write(" ");
+                    self.write(label);
+                }
+                self.write(";");
+            }
+            Node::Debugger => self.write("debugger;"),
+            Node::FunctionDecl(decl) => {
+                self.function(Some(decl.name()), decl.parameters(), decl.body())
+            }
+            expression => {
+                self.guarded_expression(expression, true);
+                self.write(";");
+            }
+        }
+    }
+
+    /// Writes an expression, in parentheses if it starts with a brace, or if `statement` is set
+    /// and it starts like a declaration.
+    ///
+    /// The parentheses keep an expression statement from being read as a block or as a
+    /// declaration, and the body of an arrow function from being read as a block.
+    fn guarded_expression(&mut self, expression: &Node, statement: bool) {
+        // The comments of the functions in the expression are written by the inner generator.
+        let mut generator = Self::new(self.style, self.depth);
+        generator.trivia = self.trivia.take();
+        generator.expression(expression, Precedence::Assignment);
+        self.trivia = generator.trivia;
+        let code = generator.out;
+        let keyword = |keyword: &str| {
+            code.strip_prefix(keyword)
+                .is_some_and(|rest| !rest.starts_with(|c| merges('a', c)))
+        };
+        let guarded = code.starts_with('{')
+            || (statement
+                && (keyword("function") || code.starts_with("let[") || code.starts_with("let [")));
+        if guarded {
+            self.write("(");
+            self.write(&code);
+            self.write(")");
+        } else {
+            self.write(&code);
+        }
+    }
+
+    /// Writes a `var`, `let` or `const` declaration list, without the semicolon.
+    fn declarations(&mut self, node: &Node) {
+        let init = |generator: &mut Self, name: &str, init: Option<&Node>| {
+            generator.write(name);
+            if let Some(init) = init {
+                generator.space();
+                generator.write("=");
+                generator.space();
+                generator.expression(init, Precedence::Assignment);
+            }
+        };
+        match node {
+            Node::VarDeclList(list) => {
+                self.write("var ");
+                self.list(list.as_ref(), |g, decl| init(g, decl.name(), decl.init()));
+            }
+            Node::LetDeclList(list) => {
+                self.write("let ");
+                self.list(list.as_ref(), |g, decl| init(g, decl.name(), decl.init()));
+            }
+            Node::ConstDeclList(list) => {
+                self.write("const ");
+                self.list(list.as_ref(), |g, decl| {
+                    init(g, decl.name(), Some(decl.init()))
+                });
+            }
+            other => self.statement(other),
+        }
+    }
+
+    fn parameters(&mut self, parameters: &[FormalParameter]) {
+        self.write("(");
+        self.list(parameters, |g, parameter| {
+            if parameter.is_rest_param() {
+                g.write("...");
+            }
+            g.write(parameter.name());
+            if let Some(init) = parameter.init() {
+                g.space();
+                g.write("=");
+                g.space();
+                g.expression(init, Precedence::Assignment);
+            }
+        });
+        self.write(")");
+    }
+
+    /// Writes the body of a function, with the error in a comment if its deferred parsing
+    /// fails.
+    fn function_body(&mut self, body: &LazyStatementList) {
+        match body.statements() {
+            Ok(statements) => self.block(statements.statements(), statements.spans()),
+            Err(err) => {
+                self.write("{");
+                self.write(&format!("/* {} */", err));
+                self.write("}");
+            }
+        }
+    }
+
+    fn function(
+        &mut self,
+        name: Option<&str>,
+        parameters: &[FormalParameter],
+        body: &LazyStatementList,
+    ) {
+        self.write("function");
+        if let Some(name) = name {
+            self.write(" ");
+            self.write(name);
+        }
+        self.parameters(parameters);
+        self.space();
+        self.function_body(body);
+    }
+
+    fn arrow_function(&mut self, arrow: &ArrowFunctionDecl) {
+        self.parameters(arrow.params());
+        self.space();
+        self.write("=>");
+        self.space();
+        // A body that only returns an expression is written as a concise body.
+        if let Ok(statements) = arrow.body().statements() {
+            if let [Node::Return(ret)] = statements.statements() {
+                if let (Some(expr), None) = (ret.expr(), ret.label()) {
+                    self.guarded_expression(expr, false);
+                    return;
+                }
+            }
+        }
+        self.function_body(arrow.body());
+    }
+
+    fn arguments(&mut self, args: &[Node]) {
+        self.write("(");
+        self.list(args, |g, arg| g.expression(arg, Precedence::Assignment));
+        self.write(")");
+    }
+
+    fn property_name(&mut self, name: &str) {
+        if is_identifier_name(name) {
+            self.write(name);
+        } else {
+            let mut literal = String::new();
+            string_literal(&mut literal, name);
+            self.write(&literal);
+        }
+    }
+
+    fn object(&mut self, properties: &[PropertyDefinition]) {
+        if properties.is_empty() {
+            self.write("{}");
+            return;
+        }
+        // Objects with methods have one property per line, so that the bodies are indented.
+        let multiline = properties
+            .iter()
+            .any(|property| matches!(property, PropertyDefinition::MethodDefinition(..)));
+        self.write("{");
+        self.depth += 1;
+        for (i, property) in properties.iter().enumerate() {
+            if i > 0 {
+                self.write(",");
+            }
+            if multiline {
+                self.line();
+            } else {
+                self.space();
+            }
+            match property {
+                PropertyDefinition::IdentifierReference(name) => self.write(name),
+                PropertyDefinition::Property(name, value) => {
+                    self.property_name(name);
+                    self.write(":");
+                    self.space();
+                    self.expression(value, Precedence::Assignment);
+                }
+                PropertyDefinition::MethodDefinition(kind, name, function) => {
+                    match kind {
+                        MethodDefinitionKind::Get => self.write("get "),
+                        MethodDefinitionKind::Set => self.write("set "),
+                        MethodDefinitionKind::Ordinary => {}
+                    }
+                    self.property_name(name);
+                    self.parameters(function.parameters());
+                    self.space();
+                    self.function_body(function.body());
+                }
+                PropertyDefinition::SpreadObject(node) => {
+                    self.write("...");
+                    self.expression(node, Precedence::Assignment);
+                }
+            }
+        }
+        self.depth -= 1;
+        if multiline {
+            self.line();
+        } else {
+            self.space();
+        }
+        self.write("}");
+    }
+
+    /// Writes an expression, in parentheses if its precedence is looser than `min`.
+    fn expression(&mut self, node: &Node, min: Precedence) {
+        if Precedence::of(node) < min {
+            self.write("(");
+            self.unparenthesized(node);
+            self.write(")");
+        } else {
+            self.unparenthesized(node);
+        }
+    }
+
+    fn unparenthesized(&mut self, node: &Node) {
+        match node {
+            Node::Const(constant) => {
+                let mut literal = String::new();
+                match constant {
+                    Const::String(string) => string_literal(&mut literal, string),
+                    Const::Num(number) => number_literal(&mut literal, *number),
+                    Const::Int(number) => literal = number.to_string(),
+                    Const::BigInt(number) => literal = format!("{}n", number),
+                    Const::Bool(value) => literal = value.to_string(),
+                    Const::Null => literal.push_str("null"),
+                    Const::Undefined => literal.push_str("undefined"),
+                }
+                self.write(&literal);
+            }
+            Node::Identifier(identifier) => self.write(identifier.as_ref()),
+            Node::This => self.write("this"),
+            Node::ArrayDecl(array) => {
+                self.write("[");
+                self.list(array.as_ref(), |g, element| {
+                    g.expression(element, Precedence::Assignment)
+                });
+                self.write("]");
+            }
+            Node::Object(object) => self.object(object.properties()),
+            Node::FunctionExpr(expr) => self.function(expr.name(), expr.parameters(), expr.body()),
+            Node::ArrowFunctionDecl(arrow) => self.arrow_function(arrow),
+            Node::Spread(spread) => {
+                self.write("...");
+                self.expression(spread.val(), Precedence::Assignment);
+            }
+            Node::Assign(assign) => {
+                self.expression(assign.lhs(), Precedence::Call);
+                self.space();
+                self.write("=");
+                self.space();
+                self.expression(assign.rhs(), Precedence::Assignment);
+            }
+            Node::BinOp(bin_op) => {
+                let precedence = Precedence::of_binary(bin_op.op());
+                let (left, right) = match bin_op.op() {
+                    op::BinOp::Assign(_) => (Precedence::Call, Precedence::Assignment),
+                    // Exponentiation is right-associative, and its base can not be a unary
+                    // expression.
+                    op::BinOp::Num(NumOp::Exp) => (Precedence::Update, precedence),
+                    _ => (precedence, precedence.next()),
+                };
+                self.expression(bin_op.lhs(), left);
+                self.space();
+                self.write(&bin_op.op().to_string());
+                self.space();
+                self.expression(bin_op.rhs(), right);
+            }
+            Node::UnaryOp(unary_op) => match unary_op.op() {
+                op::UnaryOp::IncrementPost | op::UnaryOp::DecrementPost => {
+                    self.expression(unary_op.target(), Precedence::Call);
+                    self.write(&unary_op.op().to_string());
+                }
+                op => {
+                    self.write(&op.to_string());
+                    self.expression(unary_op.target(), Precedence::Unary);
+                }
+            },
+            Node::ConditionalOp(cond_op) => {
+                self.expression(cond_op.cond(), Precedence::LogicalOr);
+                self.space();
+                self.write("?");
+                self.space();
+                self.expression(cond_op.if_true(), Precedence::Assignment);
+                self.space();
+                self.write(":");
+                self.space();
+                self.expression(cond_op.if_false(), Precedence::Assignment);
+            }
+            Node::Call(call) => {
+                self.expression(call.expr(), Precedence::Call);
+                self.arguments(call.args());
+            }
+            Node::New(new) => {
+                self.write("new ");
+                // The arguments would belong to a call in the constructor expression.
+                if contains_call(new.expr()) {
+                    self.write("(");
+                    self.unparenthesized(new.expr());
+                    self.write(")");
+                } else {
+                    self.expression(new.expr(), Precedence::Member);
+                }
+                self.arguments(new.args());
+            }
+            Node::GetConstField(get_const_field) => {
+                self.member_object(get_const_field.obj());
+                self.write(".");
+                self.write(get_const_field.field());
+            }
+            Node::GetField(get_field) => {
+                self.member_object(get_field.obj());
+                self.write("[");
+                self.expression(get_field.field(), Precedence::Assignment);
+                self.write("]");
+            }
+            statement => self.statement(statement),
+        }
+    }
+
+    /// Writes the object of a property access, in parentheses if it is a number, whose dot
+    /// would be read as a decimal point.
+    fn member_object(&mut self, obj: &Node) {
+        if let Node::Const(Const::Int(_)) | Node::Const(Const::Num(_)) = obj {
+            self.write("(");
+            self.unparenthesized(obj);
+            self.write(")");
+        } else {
+            self.expression(obj, Precedence::Call);
+        }
+    }
+}
+
+/// Checks if a member expression has a call in its chain of objects, like `a.b().c`.
+fn contains_call(node: &Node) -> bool {
+    match node {
+        Node::Call(_) => true,
+        Node::GetConstField(get_const_field) => contains_call(get_const_field.obj()),
+        Node::GetField(get_field) => contains_call(get_field.obj()),
+        _ => false,
+    }
+}
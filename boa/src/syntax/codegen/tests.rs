@@ -0,0 +1,210 @@
+//! Tests for the code generation.
+
+use super::{format, generate, Style};
+use crate::syntax::{ast::node::StatementList, lexer::Lexer, optimizer::optimize, parser::Parser};
+
+#[allow(clippy::unwrap_used)]
+fn parse(js: &str) -> StatementList {
+    let mut lexer = Lexer::new(js);
+    lexer
+        .lex()
+        .unwrap_or_else(|e| panic!("failed to lex {:?}: {}", js, e));
+    Parser::new(&lexer.tokens)
+        .parse_all()
+        .unwrap_or_else(|e| panic!("failed to parse {:?}: {}", js, e))
+}
+
+/// Checks that the code generated for the given JavaScript string parses back to the same
+/// statements, in both styles.
+fn check_round_trip(js: &str) {
+    let statements = parse(js);
+    for &style in &[Style::Compact, Style::Pretty] {
+        let generated = generate(&statements, style);
+        assert_eq!(
+            parse(&generated),
+            statements,
+            "{:?} was generated as {:?}",
+            js,
+            generated
+        );
+    }
+}
+
+/// Checks the code generated for the given JavaScript string in both styles.
+fn check_generated(js: &str, compact: &str, pretty: &str) {
+    let statements = parse(js);
+    assert_eq!(generate(&statements, Style::Compact), compact);
+    assert_eq!(generate(&statements, Style::Pretty), pretty);
+}
+
+#[test]
+fn round_trip_statements() {
+    check_round_trip("var a = 1, b; let c; const d = 2;");
+    check_round_trip("if (a) b(); else if (c) { d(); } else e();");
+    check_round_trip("if (a) { if (b) c(); } else d();");
+    check_round_trip("while (i < 10) i++; do { i--; } while (i > 0);");
+    check_round_trip("for (let i = 0; i < n; i++) { if (i) continue; break; }");
+    check_round_trip("for (;;) {}");
+    check_round_trip("switch (x) { case 1: a(); break; case 'two': b(); default: c(); }");
+    check_round_trip("try { a(); } catch (e) { b(e); } finally { c(); }");
+    check_round_trip("try { a(); } finally { c(); }");
+    check_round_trip("function f(a, b = 1, ...rest) { return a + b; } throw f;");
+    check_round_trip("debugger; { let x = 1; }");
+}
+
+#[test]
+fn round_trip_expressions() {
+    check_round_trip("a = b = c; a += 1; a.b **= 2; a[0] -= -1;");
+    check_round_trip("(a + b) * c - d / (e % f) + -g;");
+    check_round_trip("a - -b; a + +b; a - --b; a++ + ++b; - -a;");
+    check_round_trip("2 ** 3 ** 2; (2 ** 3) ** 2; (-2) ** 2; -(2 ** 2);");
+    check_round_trip("a && (b || c); (a && b) || c; !(a && b); typeof a === 'b';");
+    check_round_trip("a ? b : c ? d : e; (a ? b : c) ? d : e; a = b ? c : d;");
+    check_round_trip("a / b / c; a / (b / c); x = /a/g; a /= 2;");
+    check_round_trip("a < b == c > d; 1 << 2 >> 3 >>> 4; a & b | c ^ d; 'a' in b;");
+    check_round_trip("f(a, ...b)(c); new A(b); new (f())(); new (a.b().c)(); new a.b.C();");
+    check_round_trip("a.b.c[d][e](f).g; (1).toString(); (1.5).toFixed(); (-1).x;");
+    check_round_trip("void 0; delete a.b; typeof typeof a; ~a;");
+}
+
+#[test]
+fn round_trip_literals() {
+    check_round_trip(r#"'it\'s'; "a\"b\\c\n\t\r"; " "; "\u0001"; 'é';"#);
+    check_round_trip("0; 1.5; 1e21; 0.000001; 1e-7; 0x10; 10n;");
+    check_round_trip("true; false; null; undefined; this;");
+    check_round_trip("[]; [1, [2, 3], ...a];");
+    check_round_trip("x = {}; x = { a: 1, 'b c': 2, 3: 4, ...e };");
+    check_round_trip("x = { get a() { return 1; }, set a(v) {}, m(a) { return a; } };");
+}
+
+#[test]
+fn round_trip_functions() {
+    check_round_trip("(function () {}); (function f(a) { return a; })();");
+    check_round_trip("x = function () {}; x = () => 1; x = (a, b) => { a(); };");
+    check_round_trip("x = () => ({}); x = () => ({ a: 1 }).a; (() => 1)();");
+    check_round_trip("x = a => b => a + b; x = (a = () => 1) => a;");
+    check_round_trip("({}).toString(); ({ a: 1 }).a = b;");
+}
+
+#[test]
+fn round_trip_optimized() {
+    // The optimizer creates literals that the parser does not, like negative numbers.
+    let statements = optimize(&parse("x = -1 - 1; y = -(1 / 0); z = 1 / 0; w = -0 * 1;"));
+    for &style in &[Style::Compact, Style::Pretty] {
+        let generated = generate(&statements, style);
+        assert_eq!(optimize(&parse(&generated)), statements, "{}", generated);
+    }
+}
+
+#[test]
+fn generate_compact() {
+    check_generated(
+        "if (a) { b(); } else c(); typeof x; a - -b; return_(a) ;",
+        "if(a){b();}else c();typeof x;a- -b;return_(a);",
+        "if (a) {\n    b();\n} else c();\ntypeof x;\na - -b;\nreturn_(a);",
+    );
+}
+
+#[test]
+fn generate_pretty() {
+    check_generated(
+        "function f(a) { for (let i = 0; i < a; i++) { g(i); } return { b: a, c: 1 }; }",
+        "function f(a){for(let i=0;i<a;i++){g(i);}return{b:a,c:1};}",
+        "function f(a) {\n    for (let i = 0; i < a; i++) {\n        g(i);\n    }\n    \
+         return { b: a, c: 1 };\n}",
+    );
+    check_generated(
+        "switch (a) { case 1: b(); break; default: c(); }",
+        "switch(a){case 1:b();break;default:c();}",
+        "switch (a) {\n    case 1:\n        b();\n        break;\n    default:\n        c();\n}",
+    );
+    check_generated(
+        "x = { get a() { return 1; } };",
+        "x={get a(){return 1;}};",
+        "x = {\n    get a() {\n        return 1;\n    }\n};",
+    );
+}
+
+#[test]
+fn node_to_js_string() {
+    let statements = parse("a * (b + c); let d = 1;");
+    assert_eq!(
+        statements.statements()[0].to_js_string(Style::Pretty),
+        "a * (b + c)"
+    );
+    assert_eq!(
+        statements.statements()[1].to_js_string(Style::Compact),
+        "let d=1;"
+    );
+    assert_eq!(statements.to_js_string(Style::Compact), "a*(b+c);let d=1;");
+}
+
+/// Formats the given JavaScript string, with its comments.
+fn format_source(js: &str) -> String {
+    let mut lexer = Lexer::new(js).keep_comments(true);
+    lexer
+        .lex()
+        .unwrap_or_else(|e| panic!("failed to lex {:?}: {}", js, e));
+    let statements = Parser::new(&lexer.tokens)
+        .parse_all()
+        .unwrap_or_else(|e| panic!("failed to parse {:?}: {}", js, e));
+    format(&statements, &lexer.tokens, &lexer.comments)
+}
+
+#[test]
+fn format_comments() {
+    assert_eq!(
+        format_source(
+            "#!/usr/bin/env boa\n// a\nf(); // b\nfunction g() {\n  /* c */ h();\n  // d\n}\n// e"
+        ),
+        "#!/usr/bin/env boa\n// a\nf(); // b\nfunction g() {\n    /* c */\n    h();\n    // d\n}\n// e\n"
+    );
+    // The comments inside an expression are moved to the end of its statement.
+    assert_eq!(
+        format_source("f(a, // a\n  b /* b */);\ng();"),
+        "f(a, b); // a\n/* b */\ng();\n"
+    );
+}
+
+#[test]
+fn format_anchors_comments_to_tokens() {
+    // A comment after the `}` of a function stays after the function.
+    assert_eq!(
+        format_source("function f() { a(); } // f\ng();"),
+        "function f() {\n    a();\n} // f\ng();\n"
+    );
+    assert_eq!(
+        format_source("if (x) { a(); } /* if */\nb();"),
+        "if (x) {\n    a();\n} /* if */\nb();\n"
+    );
+    // A comment before a loop on the line of the previous statement stays before the loop.
+    assert_eq!(
+        format_source("a(); /* loop */ while (x) {\n    b();\n}"),
+        "a();\n/* loop */\nwhile (x) {\n    b();\n}\n"
+    );
+    assert_eq!(
+        format_source("a(); /* x */ /* y */ for (;;) {}"),
+        "a();\n/* x */\n/* y */\nfor (; true;) {}\n"
+    );
+    // A comment before the `}` of a block on the line of its last statement stays inside.
+    assert_eq!(
+        format_source("if (x) { a(); /* end */ }"),
+        "if (x) {\n    a();\n    /* end */\n}\n"
+    );
+}
+
+#[test]
+fn format_blank_lines() {
+    assert_eq!(
+        format_source("a();\n\n\n\nb();\nc();\n\n// d\nfunction f() {\n\n  e();\n}"),
+        "a();\n\nb();\nc();\n\n// d\nfunction f() {\n    e();\n}\n"
+    );
+}
+
+#[test]
+fn format_keeps_order() {
+    // The parser hoists the function declarations, but the formatter keeps them in place.
+    let formatted = format_source("a();\nfunction f() {}\nb();");
+    assert_eq!(formatted, "a();\nfunction f() {}\nb();\n");
+    assert_eq!(format_source(&formatted), formatted);
+}
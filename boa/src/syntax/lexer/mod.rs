@@ -1,35 +1,34 @@
 //! A lexical analyzer for JavaScript source code.
 //!
 //! The Lexer splits its input source code into a sequence of input elements called tokens, represented by the [Token](../ast/token/struct.Token.html) structure.
-//! It also removes whitespace and comments and attaches them to the next token.
+//! It also removes whitespace and comments and attaches them to the next token. The comments can
+//! be kept aside, see `Lexer::keep_comments`, for the tools that print the source back.
 
+mod source;
 #[cfg(test)]
 mod tests;
 
-use crate::builtins::BigInt;
+use crate::builtins::{value::JsString, BigInt};
 use crate::{
+    experimental::Experimental,
     syntax::ast::{
         token::{NumericLiteral, Token, TokenKind},
-        Position, Punctuator, Span,
+        Keyword, Position, Punctuator, Span,
     },
     BoaProfiler,
 };
-use std::{
-    char::{decode_utf16, from_u32},
-    error, fmt,
-    iter::Peekable,
-    str::{Chars, FromStr},
-};
+use source::Source;
+use std::{char::from_u32, error, fmt, io::Read, str::FromStr};
 
 /// `vop` tests the next token to see if we're on an assign operation of just a plain binary operation.
 ///
 /// If the next value is not an assignment operation it will pattern match  the provided values and return the corresponding token.
 macro_rules! vop {
     ($this:ident, $assign_op:expr, $op:expr) => ({
-        let preview = $this.preview_next().ok_or_else(|| LexerError::new("could not preview next value"))?;
+        let preview = $this.preview_next().ok_or_else(|| LexerError::abrupt_end("could not preview next value"))?;
         match preview {
             '=' => {
-                $this.next();
+                $this.next_char();
                 $this.next_column();
                 $assign_op
             }
@@ -37,15 +36,15 @@ macro_rules! vop {
         }
     });
     ($this:ident, $assign_op:expr, $op:expr, {$($case:pat => $block:expr), +}) => ({
-        let preview = $this.preview_next().ok_or_else(|| LexerError::new("could not preview next value"))?;
+        let preview = $this.preview_next().ok_or_else(|| LexerError::abrupt_end("could not preview next value"))?;
         match preview {
             '=' => {
-                $this.next();
+                $this.next_char();
                 $this.next_column();
                 $assign_op
             },
             $($case => {
-                $this.next();
+                $this.next_char();
                 $this.next_column();
                 $block
             })+,
@@ -53,10 +52,10 @@ macro_rules! vop {
         }
     });
     ($this:ident, $op:expr, {$($case:pat => $block:expr),+}) => {
-        let preview = $this.preview_next().ok_or_else(|| LexerError::new("could not preview next value"))?;
+        let preview = $this.preview_next().ok_or_else(|| LexerError::abrupt_end("could not preview next value"))?;
         match preview {
             $($case => {
-                $this.next()?;
+                $this.next_char()?;
                 $this.next_column();
                 $block
             })+,
@@ -84,6 +83,10 @@ macro_rules! op {
 pub struct LexerError {
     /// details will be displayed when a LexerError occurs.
     details: String,
+    /// Where the error was found, if it is known.
+    position: Option<Position>,
+    /// Whether the source ended before the token was complete.
+    abrupt_end: bool,
 }
 
 impl LexerError {
@@ -96,8 +99,41 @@ impl LexerError {
     {
         Self {
             details: msg.into(),
+            position: None,
+            abrupt_end: false,
+        }
+    }
+
+    /// Creates an error for a token that the end of the source cuts short, like an unterminated
+    /// template literal, which more source could complete.
+    fn abrupt_end<M>(msg: M) -> Self
+    where
+        M: Into<String>,
+    {
+        Self {
+            abrupt_end: true,
+            ..Self::new(msg)
         }
     }
+
+    /// Sets where the error was found.
+    fn at(mut self, position: Position) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Gets where the error was found, if it is known.
+    #[inline]
+    pub fn position(&self) -> Option<Position> {
+        self.position
+    }
+
+    /// Checks if the source ended before the token was complete, like in an unterminated
+    /// template literal or block comment, or in a string continued on the next line.
+    #[inline]
+    pub fn is_abrupt_end(&self) -> bool {
+        self.abrupt_end
+    }
 }
 
 impl fmt::Display for LexerError {
@@ -117,6 +153,51 @@ impl error::Error for LexerError {
     }
 }
 
+/// A comment of the source code, with its delimiters, like `// note` or `/* note */`.
+///
+/// The hashbang comment at the start of a script, like `#!/usr/bin/env boa`, is a comment too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    text: String,
+    span: Span,
+}
+
+impl Comment {
+    /// Creates a comment that starts at `start`, and ends at the position of its last character.
+    fn new(text: String, start: Position) -> Self {
+        let lines = text.matches('\n').count() as u32;
+        let last_line = text.rsplit('\n').next().unwrap_or_default();
+        let columns = last_line.chars().count() as u32;
+        let end = if lines == 0 {
+            Position::new(start.line_number(), start.column_number() + columns - 1)
+        } else {
+            Position::new(start.line_number() + lines, columns.max(1))
+        };
+        Self {
+            text,
+            span: Span::new(start, end),
+        }
+    }
+
+    /// Gets the text of the comment, with its delimiters.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Gets the span of the comment, from its first character to its last one.
+    #[inline]
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Checks if the comment ends with its line, like `// note`, rather than with `*/`.
+    #[inline]
+    pub fn is_line_comment(&self) -> bool {
+        !self.text.starts_with("/*")
+    }
+}
+
 /// A lexical analyzer for JavaScript source code.
 #[derive(Debug)]
 pub struct Lexer<'a> {
@@ -124,10 +205,20 @@ pub struct Lexer<'a> {
     ///
     /// This field is public so you can use them once lexing has finished.
     pub tokens: Vec<Token>,
+    /// The comments found so far, in the order of the source, if `keep_comments` is set.
+    pub comments: Vec<Comment>,
+    /// Whether the comments are kept in `comments`.
+    keep_comments: bool,
     /// The current position in the source code.
     position: Position,
-    /// The full Peekable buffer, an array of [Char]s
-    buffer: Peekable<Chars<'a>>,
+    /// The characters of the source code.
+    buffer: Source<'a>,
+    /// Whether the source is strict mode code.
+    strict: bool,
+    /// The experimental syntax that is recognized.
+    experimental: Experimental,
+    /// Whether the HTML-like comments of Annex B are recognized.
+    html_comments: bool,
 }
 
 impl<'a> Lexer<'a> {
@@ -137,13 +228,118 @@ impl<'a> Lexer<'a> {
     pub fn new(buffer: &'a str) -> Lexer<'a> {
         Lexer {
             tokens: Vec::new(),
+            comments: Vec::new(),
+            keep_comments: false,
             position: Position::new(1, 1),
-            buffer: buffer.chars().peekable(),
+            buffer: Source::from_str(buffer),
+            strict: false,
+            experimental: Experimental::default(),
+            html_comments: false,
         }
     }
 
+    /// Returns a Lexer reading UTF-8 source code from `reader`.
+    ///
+    /// The source is read as the lexer needs it, so it is never held in memory as a whole.
+    pub fn from_reader<R>(reader: R) -> Lexer<'a>
+    where
+        R: Read + 'a,
+    {
+        Lexer {
+            tokens: Vec::new(),
+            comments: Vec::new(),
+            keep_comments: false,
+            position: Position::new(1, 1),
+            buffer: Source::from_reader(reader),
+            strict: false,
+            experimental: Experimental::default(),
+            html_comments: false,
+        }
+    }
+
+    /// Lexes the source as strict mode code, where legacy octal literals, like `010`, and
+    /// decimal literals with a leading zero are syntax errors.
+    #[inline]
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Recognizes the experimental syntax of the given features, like the `??` token of
+    /// `Experimental::NULLISH_COALESCING`.
+    #[inline]
+    pub fn experimental(mut self, experimental: Experimental) -> Self {
+        self.experimental = experimental;
+        self
+    }
+
+    /// Recognizes the HTML-like comments of Annex B, which comment out the rest of the line
+    /// after `<!--`, or after `-->` at the start of a line. They are not allowed in modules.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-html-like-comments
+    #[inline]
+    pub fn html_comments(mut self, html_comments: bool) -> Self {
+        self.html_comments = html_comments;
+        self
+    }
+
+    /// Keeps the comments of the source in `comments`, instead of discarding them.
+    #[inline]
+    pub fn keep_comments(mut self, keep_comments: bool) -> Self {
+        self.keep_comments = keep_comments;
+        self
+    }
+
+    /// Gets the current position in the source code, where lexing stopped if it failed.
+    #[inline]
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Gets the number of characters of the source that have been lexed.
+    ///
+    /// Unlike the columns of `position`, which are counted the way error messages show them,
+    /// this is an exact offset, in characters, into the source. When the lexer is used as an
+    /// iterator, it is the offset of the end of the last token that was returned.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.buffer.consumed()
+    }
+
     /// Push a token onto the token queue.
+    /// Checks if a slash can start a regular expression literal, which is not the case after
+    /// the tokens that end an operand, like `a / b`.
+    fn regex_allowed(&self) -> bool {
+        let last = self
+            .tokens
+            .iter()
+            .rev()
+            .find(|token| token.kind() != &TokenKind::LineTerminator);
+        !matches!(
+            last.map(Token::kind),
+            Some(TokenKind::Identifier(_))
+                | Some(TokenKind::NumericLiteral(_))
+                | Some(TokenKind::StringLiteral(_))
+                | Some(TokenKind::TemplateLiteral(_))
+                | Some(TokenKind::BooleanLiteral(_))
+                | Some(TokenKind::NullLiteral)
+                | Some(TokenKind::RegularExpressionLiteral(..))
+                | Some(TokenKind::Keyword(Keyword::This))
+                | Some(TokenKind::Punctuator(Punctuator::CloseParen))
+                | Some(TokenKind::Punctuator(Punctuator::CloseBracket))
+        )
+    }
+
     fn push_token(&mut self, tk: TokenKind, start: Position) {
+        tracing::trace!(
+            token = %tk,
+            line = start.line_number(),
+            column = start.column_number(),
+            "lexed token"
+        );
         let end = if let TokenKind::LineTerminator = tk {
             self.position
         } else {
@@ -155,6 +351,46 @@ impl<'a> Lexer<'a> {
         self.tokens.push(Token::new(tk, Span::new(start, end)))
     }
 
+    /// Keeps a comment, if `keep_comments` is set.
+    fn push_comment(&mut self, text: String, start: Position) {
+        if self.keep_comments {
+            self.comments.push(Comment::new(text, start));
+        }
+    }
+
+    /// Checks if the characters after the current one are `chars`, without consuming them.
+    fn next_are(&mut self, chars: &str) -> bool {
+        chars
+            .chars()
+            .enumerate()
+            .all(|(i, c)| self.preview_multiple_next(i + 1) == Some(c))
+    }
+
+    /// Checks if no token was found on the line of `position` yet, so that `-->` starts an
+    /// HTML-like comment there.
+    fn at_line_start(&self, position: Position) -> bool {
+        self.tokens.last().is_none_or(|token| {
+            token.kind() == &TokenKind::LineTerminator
+                || token.span().end().line_number() < position.line_number()
+        })
+    }
+
+    /// Consumes the rest of the current line, with its line terminator, and returns it without
+    /// the terminator.
+    fn rest_of_line(&mut self) -> String {
+        let mut text = String::new();
+        while self.preview_next().is_some() {
+            match self.next_char() {
+                '\n' => break,
+                c => text.push(c),
+            }
+        }
+        if text.ends_with('\r') {
+            text.pop();
+        }
+        text
+    }
+
     /// Push a punctuation token
     fn push_punc(&mut self, punc: Punctuator, start: Position) {
         self.push_token(TokenKind::Punctuator(punc), start);
@@ -189,14 +425,8 @@ impl<'a> Lexer<'a> {
         self.position = pos;
     }
 
-    /// Changes the current position by advancing the given number of lines.
-    fn move_lines(&mut self, lines: u32) {
-        let pos = Position::new(self.position.line_number() + lines, 1);
-        self.position = pos;
-    }
-
-    /// next fetches the next token and return it, or a LexerError if there are no more.
-    fn next(&mut self) -> char {
+    /// Consumes the next character of the source.
+    fn next_char(&mut self) -> char {
         self.buffer.next().expect(
             "No more more characters to consume from input stream, \
              use preview_next() first to check before calling next()",
@@ -205,19 +435,18 @@ impl<'a> Lexer<'a> {
 
     /// Preview the next character but don't actually increment
     fn preview_next(&mut self) -> Option<char> {
-        self.buffer.peek().copied()
+        self.buffer.peek()
     }
 
     /// Preview a char x indexes further in buf, without incrementing
     fn preview_multiple_next(&mut self, nb_next: usize) -> Option<char> {
         let mut next_peek = None;
 
-        for (i, x) in self.buffer.clone().enumerate() {
-            if i >= nb_next {
-                break;
+        for i in 0..nb_next {
+            match self.buffer.peek_nth(i) {
+                Some(x) => next_peek = Some(x),
+                None => break,
             }
-
-            next_peek = Some(x);
         }
 
         next_peek
@@ -233,7 +462,7 @@ impl<'a> Lexer<'a> {
         while self.buffer.peek().is_some()
             && f(self.preview_next().expect("Could not preview next value"))
         {
-            s.push(self.next());
+            s.push(self.next_char());
         }
 
         Ok(s)
@@ -273,7 +502,7 @@ impl<'a> Lexer<'a> {
     ///  - [ECMAScript Specification][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-literals-numeric-literals
-    fn reed_numerical_literal(&mut self, ch: char) -> Result<(), LexerError> {
+    fn reed_numerical_literal(&mut self, ch: char, start_pos: Position) -> Result<(), LexerError> {
         /// This is a helper structure
         ///
         /// This structure helps with identifying what numerical type it is and what base is it.
@@ -304,16 +533,13 @@ impl<'a> Lexer<'a> {
             }
         }
 
-        // TODO: Setup strict mode.
-        let strict_mode = false;
+        let strict_mode = self.strict;
 
         let mut buf = ch.to_string();
         let mut kind = NumericKind::Integer(10);
-        let start_pos = self.position;
         if ch == '0' {
             match self.preview_next() {
                 None => {
-                    self.next_column();
                     self.push_token(
                         TokenKind::NumericLiteral(NumericLiteral::Integer(0)),
                         start_pos,
@@ -321,17 +547,17 @@ impl<'a> Lexer<'a> {
                     return Ok(());
                 }
                 Some('x') | Some('X') => {
-                    self.next();
+                    self.next_char();
                     self.next_column();
                     kind = NumericKind::Integer(16);
                 }
                 Some('o') | Some('O') => {
-                    self.next();
+                    self.next_char();
                     self.next_column();
                     kind = NumericKind::Integer(8);
                 }
                 Some('b') | Some('B') => {
-                    self.next();
+                    self.next_char();
                     self.next_column();
                     kind = NumericKind::Integer(2);
                 }
@@ -343,7 +569,7 @@ impl<'a> Lexer<'a> {
                         } else if !ch.is_digit(8) {
                             is_implicit_octal = false;
                         }
-                        buf.push(self.next());
+                        buf.push(self.next_char());
                     }
                     if !strict_mode {
                         if is_implicit_octal {
@@ -369,7 +595,7 @@ impl<'a> Lexer<'a> {
             if !ch.is_digit(kind.base()) {
                 break;
             }
-            buf.push(self.next());
+            buf.push(self.next_char());
         }
 
         if self.next_is('n') {
@@ -381,7 +607,7 @@ impl<'a> Lexer<'a> {
                 match ch {
                     '.' => loop {
                         kind = NumericKind::Rational;
-                        buf.push(self.next());
+                        buf.push(self.next_char());
 
                         let c = match self.preview_next() {
                             Some(ch) => ch,
@@ -396,7 +622,7 @@ impl<'a> Lexer<'a> {
                                     .to_digit(10)
                                 {
                                     Some(0..=9) | None => {
-                                        buf.push(self.next());
+                                        buf.push(self.next_char());
                                     }
                                     _ => {
                                         break 'digitloop;
@@ -418,19 +644,19 @@ impl<'a> Lexer<'a> {
                             .to_digit(10)
                         {
                             Some(0..=9) | None => {
-                                buf.push(self.next());
+                                buf.push(self.next_char());
                             }
                             _ => {
                                 break;
                             }
                         }
-                        buf.push(self.next());
+                        buf.push(self.next_char());
                     }
                     '+' | '-' => {
                         break;
                     }
                     _ if ch.is_digit(10) => {
-                        buf.push(self.next());
+                        buf.push(self.next_char());
                     }
                     _ => break,
                 }
@@ -470,7 +696,8 @@ impl<'a> Lexer<'a> {
                 }
             };
 
-        self.move_columns(buf.len() as u32);
+        // The first digit has already been counted.
+        self.move_columns(buf.len() as u32 - 1);
         self.push_token(TokenKind::NumericLiteral(num), start_pos);
 
         Ok(())
@@ -490,381 +717,441 @@ impl<'a> Lexer<'a> {
     /// ```
     pub fn lex(&mut self) -> Result<(), LexerError> {
         let _timer = BoaProfiler::global().start_event("lex", "lexing");
-        loop {
-            // Check if we've reached the end
-            if self.preview_next().is_none() {
-                return Ok(());
+        let _span = tracing::debug_span!("lex").entered();
+        while self.lex_element()? {}
+        Ok(())
+    }
+
+    /// Lexes the next input element, which pushes a token unless it is whitespace or a comment.
+    ///
+    /// Returns `false` if the end of the source was reached.
+    fn lex_element(&mut self) -> Result<bool, LexerError> {
+        // Check if we've reached the end
+        if self.preview_next().is_none() {
+            return match self.buffer.take_error() {
+                Some(err) => Err(LexerError::new(format!(
+                    "could not read the source: {}",
+                    err
+                ))),
+                None => Ok(false),
+            };
+        }
+        let start_pos = self.position;
+        self.next_column();
+        let ch = self.next_char();
+        match ch {
+            // Hashbang comment, like `#!/usr/bin/env boa`, which is only allowed at the very
+            // start of the source.
+            '#' if self.offset() == 1 && self.next_is('!') => {
+                let text = self.rest_of_line();
+                self.push_comment(format!("#!{}", text), start_pos);
+                self.next_line()
             }
-            let start_pos = self.position;
-            self.next_column();
-            let ch = self.next();
-            match ch {
-                 // StringLiteral
-                '"' | '\'' => {
-                    let mut buf = String::new();
-                    loop {
-                        if self.preview_next().is_none() {
-                            return Err(LexerError::new("Unterminated String"));
+             // StringLiteral
+            '"' | '\'' => {
+                // The code units of the string, which may contain lone surrogates.
+                let mut buf: Vec<u16> = Vec::new();
+                // Whether the last character was an escaped line terminator, which continues
+                // the string on the next line.
+                let mut continued = false;
+                loop {
+                    if self.preview_next().is_none() {
+                        return Err(if continued {
+                            LexerError::abrupt_end("Unterminated String")
+                        } else {
+                            LexerError::new("Unterminated String")
+                        });
+                    }
+                    continued = false;
+                    match self.next_char() {
+                        '\'' if ch == '\'' => {
+                            break;
                         }
-                        match self.next() {
-                            '\'' if ch == '\'' => {
-                                break;
-                            }
-                            '"' if ch == '"' => {
-                                break;
+                        '"' if ch == '"' => {
+                            break;
+                        }
+                        '\\' => {
+                            if self.preview_next().is_none() {
+                                return Err(LexerError::abrupt_end("Unterminated String"));
                             }
-                            '\\' => {
-                                if self.preview_next().is_none() {
-                                    return Err(LexerError::new("Unterminated String"));
-                                }
-                                let escape_pos = self.position;
-                                let escape = self.next();
-                                if escape != '\n' {
-                                    let escaped_ch = match escape {
-                                        'n' => '\n',
-                                        'r' => '\r',
-                                        't' => '\t',
-                                        'b' => '\x08',
-                                        'f' => '\x0c',
-                                        '0' => '\0',
-                                        'x' => {
-                                            let mut nums = String::with_capacity(2);
-                                            for _ in 0_u8..2 {
-                                                if self.preview_next().is_none() {
-                                                    return Err(LexerError::new("Unterminated String"));
-                                                }
-                                                nums.push(self.next());
+                            let escape_pos = self.position;
+                            let escape = self.next_char();
+                            continued = escape == '\n';
+                            if escape != '\n' {
+                                let escaped_ch = match escape {
+                                    'n' => '\n',
+                                    'r' => '\r',
+                                    't' => '\t',
+                                    'b' => '\x08',
+                                    'f' => '\x0c',
+                                    '0' => '\0',
+                                    'x' => {
+                                        let mut nums = String::with_capacity(2);
+                                        for _ in 0_u8..2 {
+                                            if self.preview_next().is_none() {
+                                                return Err(LexerError::new("Unterminated String"));
                                             }
-                                            self.move_columns(2);
-                                            let as_num = match u64::from_str_radix(&nums, 16) {
+                                            nums.push(self.next_char());
+                                        }
+                                        self.move_columns(2);
+                                        let as_num = match u64::from_str_radix(&nums, 16) {
+                                            Ok(v) => v,
+                                            Err(_) => 0,
+                                        };
+                                        match from_u32(as_num as u32) {
+                                            Some(v) => v,
+                                            None => panic!(
+                                                "{}: {} is not a valid unicode scalar value",
+                                                self.position, as_num
+                                            ),
+                                        }
+                                    }
+                                    'u' => {
+                                        // There are 2 types of codepoints. Surragate codepoints and unicode codepoints.
+                                        // UTF-16 could be surrogate codepoints, "\uXXXX\uXXXX" which make up a single unicode codepoint.
+                                        // We will need to loop to make sure we catch all UTF-16 codepoints
+                                        // Example Test: https://github.com/tc39/test262/blob/ee3715ee56744ccc8aeb22a921f442e98090b3c1/implementation-contributed/v8/mjsunit/es6/unicode-escapes.js#L39-L44
+
+                                        // Support \u{X..X} (Unicode Codepoint)
+                                        if self.next_is('{') {
+                                            let s = self
+                                                .take_char_while(char::is_alphanumeric)
+                                                .expect("Could not read chars");
+
+                                            // We know this is a single unicode codepoint, convert to u32
+                                            let as_num = match u32::from_str_radix(&s, 16) {
                                                 Ok(v) => v,
                                                 Err(_) => 0,
                                             };
-                                            match from_u32(as_num as u32) {
-                                                Some(v) => v,
-                                                None => panic!(
-                                                    "{}: {} is not a valid unicode scalar value",
-                                                    self.position, as_num
-                                                ),
+                                            let c = from_u32(as_num).ok_or_else(|| LexerError::new("Invalid Unicode escape sequence"))?;
+
+                                            if self.preview_next().is_none() {
+                                                return Err(LexerError::new("Unterminated String"));
                                             }
-                                        }
-                                        'u' => {
-                                            // There are 2 types of codepoints. Surragate codepoints and unicode codepoints.
-                                            // UTF-16 could be surrogate codepoints, "\uXXXX\uXXXX" which make up a single unicode codepoint.
-                                            // We will need to loop to make sure we catch all UTF-16 codepoints
-                                            // Example Test: https://github.com/tc39/test262/blob/ee3715ee56744ccc8aeb22a921f442e98090b3c1/implementation-contributed/v8/mjsunit/es6/unicode-escapes.js#L39-L44
-
-                                            // Support \u{X..X} (Unicode Codepoint)
-                                            if self.next_is('{') {
+                                            self.next_char(); // '}'
+                                            self.move_columns(s.len() as u32);
+                                            c
+                                        } else {
+                                            let mut codepoints: Vec<u16> = vec![];
+                                            loop {
+                                                // Collect each character after \u e.g \uD83D will give "D83D"
                                                 let s = self
                                                     .take_char_while(char::is_alphanumeric)
                                                     .expect("Could not read chars");
 
-                                                // We know this is a single unicode codepoint, convert to u32
-                                                let as_num = match u32::from_str_radix(&s, 16) {
+                                                // Convert to u16
+                                                let as_num = match u16::from_str_radix(&s, 16) {
                                                     Ok(v) => v,
                                                     Err(_) => 0,
                                                 };
-                                                let c = from_u32(as_num).ok_or_else(|| LexerError::new("Invalid Unicode escape sequence"))?;
 
-                                                if self.preview_next().is_none() {
-                                                    return Err(LexerError::new("Unterminated String"));
-                                                }
-                                                self.next(); // '}'
+                                                codepoints.push(as_num);
                                                 self.move_columns(s.len() as u32);
-                                                c
-                                            } else {
-                                                let mut codepoints: Vec<u16> = vec![];
-                                                loop {
-                                                    // Collect each character after \u e.g \uD83D will give "D83D"
-                                                    let s = self
-                                                        .take_char_while(char::is_alphanumeric)
-                                                        .expect("Could not read chars");
-
-                                                    // Convert to u16
-                                                    let as_num = match u16::from_str_radix(&s, 16) {
-                                                        Ok(v) => v,
-                                                        Err(_) => 0,
-                                                    };
-
-                                                    codepoints.push(as_num);
-                                                    self.move_columns(s.len() as u32);
-
-                                                    // Check for another UTF-16 codepoint
-                                                    if self.next_is('\\') && self.next_is('u') {
-                                                        continue;
-                                                    }
-                                                    break;
-                                                }
 
-                                                // codepoints length should either be 1 (unicode codepoint) or 2 (surrogate codepoint).
-                                                // Rust's decode_utf16 will deal with it regardless
-                                                decode_utf16(codepoints.iter().cloned())
-                                                    .next()
-                                                    .expect("Could not get next codepoint")
-                                                    .expect("Could not get next codepoint")
+                                                // Check for another UTF-16 codepoint
+                                                if self.next_is('\\') && self.next_is('u') {
+                                                    continue;
+                                                }
+                                                break;
                                             }
+
+                                            // The code units are kept as they are, so a lone surrogate is not lost.
+                                            buf.extend(codepoints);
+                                            continue;
                                         }
-                                        '\'' | '"' | '\\' => escape,
-                                        ch => {
-                                            let details = format!("invalid escape sequence `{}` at line {}, column {}", escape_pos.line_number(), escape_pos.column_number(), ch);
-                                            return Err(LexerError { details });
-                                        }
-                                    };
-                                    buf.push(escaped_ch);
-                                }
+                                    }
+                                    '\'' | '"' | '\\' => escape,
+                                    ch => {
+                                        let details = format!("invalid escape sequence `{}` at line {}, column {}", ch, escape_pos.line_number(), escape_pos.column_number());
+                                        return Err(LexerError::new(details).at(escape_pos));
+                                    }
+                                };
+                                buf.extend(escaped_ch.encode_utf16(&mut [0; 2]).iter());
                             }
-                            next_ch => buf.push(next_ch),
                         }
+                        next_ch => buf.extend(next_ch.encode_utf16(&mut [0; 2]).iter()),
                     }
-                    let str_length = buf.len() as u32;
-                    // Why +1? Quotation marks are not included,
-                    // So technically it would be +2, (for both " ") but we want to be 1 less
-                    // to compensate for the incrementing at the top
-                    self.move_columns( str_length.wrapping_add(1));
-                    self.push_token(TokenKind::string_literal(buf), start_pos);
                 }
-                // TemplateLiteral
-                '`' => {
-                    let mut buf = String::new();
-                    loop {
-                        if self.preview_next().is_none() {
-                            return Err(LexerError::new("Unterminated template literal"));
-                        }
-                        match self.next() {
-                            '`' => {
-                                break;
-                            }
-                            next_ch => buf.push(next_ch),
-                            // TODO when there is an expression inside the literal
+                let string = JsString::from_utf16(&buf);
+                let str_length = string.len() as u32;
+                // Why +1? Quotation marks are not included,
+                // So technically it would be +2, (for both " ") but we want to be 1 less
+                // to compensate for the incrementing at the top
+                self.move_columns( str_length.wrapping_add(1));
+                self.push_token(TokenKind::string_literal(string), start_pos);
+            }
+            // TemplateLiteral
+            '`' => {
+                let mut buf = String::new();
+                loop {
+                    if self.preview_next().is_none() {
+                        return Err(LexerError::abrupt_end("Unterminated template literal"));
+                    }
+                    match self.next_char() {
+                        '`' => {
+                            break;
                         }
+                        next_ch => buf.push(next_ch),
+                        // TODO when there is an expression inside the literal
                     }
-                    let str_length = buf.len() as u32;
-                    // Why +1? Quotation marks are not included,
-                    // So technically it would be +2, (for both " ") but we want to be 1 less
-                    // to compensate for the incrementing at the top
-                    self.move_columns( str_length.wrapping_add(1));
-                    self.push_token(TokenKind::template_literal(buf), start_pos);
                 }
-                _ if ch.is_digit(10) => self.reed_numerical_literal(ch)?,
-                _ if ch.is_alphabetic() || ch == '$' || ch == '_' => {
-                    let mut buf = ch.to_string();
-                    while let Some(ch) = self.preview_next() {
-                        if ch.is_alphabetic() || ch.is_digit(10) || ch == '_' {
-                            buf.push(self.next());
+                let str_length = buf.len() as u32;
+                // Why +1? Quotation marks are not included,
+                // So technically it would be +2, (for both " ") but we want to be 1 less
+                // to compensate for the incrementing at the top
+                self.move_columns( str_length.wrapping_add(1));
+                self.push_token(TokenKind::template_literal(buf), start_pos);
+            }
+            _ if ch.is_digit(10) => self.reed_numerical_literal(ch, start_pos)?,
+            _ if ch.is_alphabetic() || ch == '$' || ch == '_' => {
+                let mut buf = ch.to_string();
+                while let Some(ch) = self.preview_next() {
+                    if ch.is_alphabetic() || ch.is_digit(10) || ch == '_' {
+                        buf.push(self.next_char());
+                    } else {
+                        break;
+                    }
+                }
+                let tk = match buf.as_str() {
+                    "true" => TokenKind::BooleanLiteral(true),
+                    "false" => TokenKind::BooleanLiteral(false),
+                    "null" => TokenKind::NullLiteral,
+                    slice => {
+                        if let Ok(keyword) = FromStr::from_str(slice) {
+                            TokenKind::Keyword(keyword)
                         } else {
-                            break;
+                            TokenKind::identifier(slice)
                         }
                     }
-                    let tk = match buf.as_str() {
-                        "true" => TokenKind::BooleanLiteral(true),
-                        "false" => TokenKind::BooleanLiteral(false),
-                        "null" => TokenKind::NullLiteral,
-                        slice => {
-                            if let Ok(keyword) = FromStr::from_str(slice) {
-                                TokenKind::Keyword(keyword)
-                            } else {
-                                TokenKind::identifier(slice)
-                            }
-                        }
-                    };
+                };
 
-                    // Move position forward the length of the token
-                    self.move_columns( (buf.len().wrapping_sub(1)) as u32);
+                // Move position forward the length of the token
+                self.move_columns( (buf.len().wrapping_sub(1)) as u32);
 
-                    self.push_token(tk, start_pos);
-                }
-                ';' => self.push_punc(Punctuator::Semicolon, start_pos),
-                ':' => self.push_punc(Punctuator::Colon, start_pos),
-                '.' => {
-                    // . or ...
+                self.push_token(tk, start_pos);
+            }
+            ';' => self.push_punc(Punctuator::Semicolon, start_pos),
+            ':' => self.push_punc(Punctuator::Colon, start_pos),
+            '.' => {
+                // . or ...
+                if self.next_is('.') {
                     if self.next_is('.') {
-                        if self.next_is('.') {
-                            self.push_punc(Punctuator::Spread, start_pos);
-                        } else {
-                            return Err(LexerError::new("Expecting Token ."));
-                        }
+                        self.push_punc(Punctuator::Spread, start_pos);
                     } else {
-                        self.push_punc(Punctuator::Dot, start_pos);
-                    };
-                }
-                '(' => self.push_punc(Punctuator::OpenParen, start_pos),
-                ')' => self.push_punc(Punctuator::CloseParen, start_pos),
-                ',' => self.push_punc(Punctuator::Comma, start_pos),
-                '{' => self.push_punc(Punctuator::OpenBlock, start_pos),
-                '}' => self.push_punc(Punctuator::CloseBlock, start_pos),
-                '[' => self.push_punc(Punctuator::OpenBracket, start_pos),
-                ']' => self.push_punc(Punctuator::CloseBracket, start_pos),
-                '?' => self.push_punc(Punctuator::Question, start_pos),
-                // Comments
-                '/' => {
-                    if let Some(ch) = self.preview_next() {
-                        match ch {
-                            // line comment
-                            '/' => {
-                                while self.preview_next().is_some() {
-                                    if self.next() == '\n' {
-                                        break;
-                                    }
+                        return Err(LexerError::new("Expecting Token ."));
+                    }
+                } else {
+                    self.push_punc(Punctuator::Dot, start_pos);
+                };
+            }
+            '(' => self.push_punc(Punctuator::OpenParen, start_pos),
+            ')' => self.push_punc(Punctuator::CloseParen, start_pos),
+            ',' => self.push_punc(Punctuator::Comma, start_pos),
+            '{' => self.push_punc(Punctuator::OpenBlock, start_pos),
+            '}' => self.push_punc(Punctuator::CloseBlock, start_pos),
+            '[' => self.push_punc(Punctuator::OpenBracket, start_pos),
+            ']' => self.push_punc(Punctuator::CloseBracket, start_pos),
+            '?' if self.experimental.contains(Experimental::NULLISH_COALESCING)
+                && self.next_is('?') =>
+            {
+                self.push_punc(Punctuator::Coalesce, start_pos)
+            }
+            '?' => self.push_punc(Punctuator::Question, start_pos),
+            // Comments
+            '/' => {
+                if let Some(ch) = self.preview_next() {
+                    match ch {
+                        // line comment
+                        '/' => {
+                            let text = self.rest_of_line();
+                            self.push_comment(format!("/{}", text), start_pos);
+                            self.next_line()
+                        }
+                        // block comment
+                        '*' => {
+                            let mut text = String::from("/");
+                            loop {
+                                if self.preview_next().is_none() {
+                                    return Err(LexerError::abrupt_end("unterminated multiline comment"));
                                 }
-                                self.next_line()
+                                let next_ch = self.next_char();
+                                text.push(next_ch);
+                                // The star of `/*` does not also close the comment.
+                                if next_ch == '*' && text.len() > 2 && self.next_is('/') {
+                                    text.push('/');
+                                    break;
+                                }
+                            }
+                            let comment = Comment::new(text, start_pos);
+                            let end = comment.span().end();
+                            self.position =
+                                Position::new(end.line_number(), end.column_number() + 1);
+                            if self.keep_comments {
+                                self.comments.push(comment);
                             }
-                            // block comment
-                            '*' => {
-                                let mut lines = 0;
-                                loop {
-                                    if self.preview_next().is_none() {
-                                        return Err(LexerError::new("unterminated multiline comment"));
+                        }
+                        // division, assigndiv or regex literal
+                        _ => {
+                            // if we fail to parse a regex literal, the consumed characters are
+                            // given back to the buffer later on
+                            self.buffer.checkpoint();
+                            let original_pos = self.position;
+                            // first, try to parse a regex literal
+                            let mut body = String::new();
+                            let mut regex = false;
+                            // After an operand, a slash is a division.
+                            while self.regex_allowed() {
+                                self.next_column();
+                                match self.buffer.next() {
+                                    // end of body
+                                    Some('/') => {
+                                        regex = true;
+                                        break;
                                     }
-                                    match self.next() {
-                                        '*' => {
-                                            if self.next_is('/') {
-                                                break;
-                                            }
+                                    // newline/eof not allowed in regex literal
+                                    n @ Some('\n') | n @ Some('\r') | n @ Some('\u{2028}')
+                                    | n @ Some('\u{2029}') => {
+                                        self.carriage_return();
+                                        if n != Some('\r') {
+                                            self.next_line();
                                         }
-                                        next_ch => {
-                                            if next_ch == '\n' {
-                                                lines += 1;
-                                            }
-                                        },
+                                        break
+                                    },
+                                    None => {
+                                        self.position = Position::new(self.position.line_number(), self.position.column_number()-1);
+                                        break
                                     }
-                                }
-                                self.move_lines(lines);
-                            }
-                            // division, assigndiv or regex literal
-                            _ => {
-                                // if we fail to parse a regex literal, store a copy of the current
-                                // buffer to restore later on
-                                let original_buffer = self.buffer.clone();
-                                let original_pos = self.position;
-                                // first, try to parse a regex literal
-                                let mut body = String::new();
-                                let mut regex = false;
-                                loop {
-                                    self.next_column();
-                                    match self.buffer.next() {
-                                        // end of body
-                                        Some('/') => {
-                                            regex = true;
+                                    // escape sequence
+                                    Some('\\') => {
+                                        body.push('\\');
+                                        if self.preview_next().is_none() {
                                             break;
                                         }
-                                        // newline/eof not allowed in regex literal
-                                        n @ Some('\n') | n @ Some('\r') | n @ Some('\u{2028}')
-                                        | n @ Some('\u{2029}') => {
-                                            self.carriage_return();
-                                            if n != Some('\r') {
-                                                self.next_line();
-                                            }
-                                            break
-                                        },
-                                        None => {
-                                            self.position = Position::new(self.position.line_number(), self.position.column_number()-1);
-                                            break
-                                        }
-                                        // escape sequence
-                                        Some('\\') => {
-                                            body.push('\\');
-                                            if self.preview_next().is_none() {
-                                                break;
-                                            }
-                                            match self.next() {
-                                                // newline not allowed in regex literal
-                                                '\n' | '\r' | '\u{2028}' | '\u{2029}' => break,
-                                                ch => body.push(ch),
-                                            }
+                                        match self.next_char() {
+                                            // newline not allowed in regex literal
+                                            '\n' | '\r' | '\u{2028}' | '\u{2029}' => break,
+                                            ch => body.push(ch),
                                         }
-                                        Some(ch) => body.push(ch),
                                     }
+                                    Some(ch) => body.push(ch),
                                 }
-                                if regex {
-                                    // body was parsed, now look for flags
-                                    let flags = self.take_char_while(char::is_alphabetic)?;
-                                    self.move_columns(body.len() as u32 + 1 + flags.len() as u32);
-                                    self.push_token(TokenKind::regular_expression_literal(
-                                        body, flags.parse()?,
+                            }
+                            if regex {
+                                self.buffer.commit();
+                                // body was parsed, now look for flags
+                                let flags = self.take_char_while(char::is_alphabetic)?;
+                                self.move_columns(body.len() as u32 + 1 + flags.len() as u32);
+                                self.push_token(TokenKind::regular_expression_literal(
+                                    body, flags.parse()?,
+                                ), start_pos);
+                            } else {
+                                // failed to parse regex, restore original buffer position and
+                                // parse either div or assigndiv
+                                self.buffer.rewind();
+                                self.position = original_pos;
+                                if self.next_is('=') {
+                                    self.push_token(TokenKind::Punctuator(
+                                        Punctuator::AssignDiv,
                                     ), start_pos);
                                 } else {
-                                    // failed to parse regex, restore original buffer position and
-                                    // parse either div or assigndiv
-                                    self.buffer = original_buffer;
-                                    self.position = original_pos;
-                                    if self.next_is('=') {
-                                        self.push_token(TokenKind::Punctuator(
-                                            Punctuator::AssignDiv,
-                                        ), start_pos);
-                                    } else {
-                                        self.push_token(TokenKind::Punctuator(Punctuator::Div), start_pos);
-                                    }
+                                    self.push_token(TokenKind::Punctuator(Punctuator::Div), start_pos);
                                 }
                             }
                         }
-                    } else {
-                        return Err(LexerError::new("Expecting Token /,*,= or regex"));
                     }
-                }
-                '*' => op!(self, start_pos, Punctuator::AssignMul, Punctuator::Mul, {
-                    '*' => vop!(self, Punctuator::AssignPow, Punctuator::Exp)
-                }),
-                '+' => op!(self, start_pos, Punctuator::AssignAdd, Punctuator::Add, {
-                    '+' => Punctuator::Inc
-                }),
-                '-' => op!(self, start_pos, Punctuator::AssignSub, Punctuator::Sub, {
-                    '-' => {
-                        Punctuator::Dec
-                    }
-                }),
-                '%' => op!(self, start_pos, Punctuator::AssignMod, Punctuator::Mod),
-                '|' => op!(self, start_pos, Punctuator::AssignOr, Punctuator::Or, {
-                    '|' => Punctuator::BoolOr
-                }),
-                '&' => op!(self, start_pos, Punctuator::AssignAnd, Punctuator::And, {
-                    '&' => Punctuator::BoolAnd
-                }),
-                '^' => op!(self, start_pos, Punctuator::AssignXor, Punctuator::Xor),
-                '=' => op!(self, start_pos, if self.next_is('=') {
-                    Punctuator::StrictEq
                 } else {
-                    Punctuator::Eq
-                }, Punctuator::Assign, {
-                    '>' => {
-                        Punctuator::Arrow
-                    }
-                }),
-                '<' => op!(self, start_pos, Punctuator::LessThanOrEq, Punctuator::LessThan, {
-                    '<' => vop!(self, Punctuator::AssignLeftSh, Punctuator::LeftSh)
-                }),
-                '>' => op!(self, start_pos, Punctuator::GreaterThanOrEq, Punctuator::GreaterThan, {
-                    '>' => vop!(self, Punctuator::AssignRightSh, Punctuator::RightSh, {
-                        '>' => vop!(self, Punctuator::AssignURightSh, Punctuator::URightSh)
-                    })
-                }),
-                '!' => op!(
-                    self,
-                    start_pos,
-                    vop!(self, Punctuator::StrictNotEq, Punctuator::NotEq),
-                    Punctuator::Not
-                ),
-                '~' => self.push_punc(Punctuator::Neg, start_pos),
-                '\n' | '\u{2028}' | '\u{2029}' => {
-                    self.next_line();
-                    self.push_token(TokenKind::LineTerminator, start_pos);
+                    return Err(LexerError::new("Expecting Token /,*,= or regex"));
                 }
-                '\r' => {
-                    self.carriage_return();
+            }
+            '*' => op!(self, start_pos, Punctuator::AssignMul, Punctuator::Mul, {
+                '*' => vop!(self, Punctuator::AssignPow, Punctuator::Exp)
+            }),
+            '+' => op!(self, start_pos, Punctuator::AssignAdd, Punctuator::Add, {
+                '+' => Punctuator::Inc
+            }),
+            '<' | '-' if self.html_comments
+                && (ch == '<' && self.next_are("!--")
+                    || ch == '-' && self.next_are("->") && self.at_line_start(start_pos)) =>
+            {
+                let text = self.rest_of_line();
+                self.push_comment(format!("{}{}", ch, text), start_pos);
+                self.next_line()
+            }
+            '-' => op!(self, start_pos, Punctuator::AssignSub, Punctuator::Sub, {
+                '-' => {
+                    Punctuator::Dec
                 }
-                // The rust char::is_whitespace function and the ecma standard use different sets
-                // of characters as whitespaces:
-                //  * Rust uses \p{White_Space},
-                //  * ecma standard uses \{Space_Separator} + \u{0009}, \u{000B}, \u{000C}, \u{FEFF}
-                //
-                // Explicit whitespace: see https://tc39.es/ecma262/#table-32
-                '\u{0020}' | '\u{0009}' | '\u{000B}' | '\u{000C}' | '\u{00A0}' | '\u{FEFF}' |
-                // Unicode Space_Seperator category (minus \u{0020} and \u{00A0} which are allready stated above)
-                '\u{1680}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => (),
-                _ => {
-                    let details = format!("Unexpected '{}' at line {}, column {}", start_pos.line_number(), start_pos.column_number(), ch);
-                    return Err(LexerError { details });
-                },
+            }),
+            '%' => op!(self, start_pos, Punctuator::AssignMod, Punctuator::Mod),
+            '|' => op!(self, start_pos, Punctuator::AssignOr, Punctuator::Or, {
+                '|' => Punctuator::BoolOr
+            }),
+            '&' => op!(self, start_pos, Punctuator::AssignAnd, Punctuator::And, {
+                '&' => Punctuator::BoolAnd
+            }),
+            '^' => op!(self, start_pos, Punctuator::AssignXor, Punctuator::Xor),
+            '=' => op!(self, start_pos, if self.next_is('=') {
+                Punctuator::StrictEq
+            } else {
+                Punctuator::Eq
+            }, Punctuator::Assign, {
+                '>' => {
+                    Punctuator::Arrow
+                }
+            }),
+            '<' => op!(self, start_pos, Punctuator::LessThanOrEq, Punctuator::LessThan, {
+                '<' => vop!(self, Punctuator::AssignLeftSh, Punctuator::LeftSh)
+            }),
+            '>' => op!(self, start_pos, Punctuator::GreaterThanOrEq, Punctuator::GreaterThan, {
+                '>' => vop!(self, Punctuator::AssignRightSh, Punctuator::RightSh, {
+                    '>' => vop!(self, Punctuator::AssignURightSh, Punctuator::URightSh)
+                })
+            }),
+            '!' => op!(
+                self,
+                start_pos,
+                vop!(self, Punctuator::StrictNotEq, Punctuator::NotEq),
+                Punctuator::Not
+            ),
+            '~' => self.push_punc(Punctuator::Neg, start_pos),
+            '\n' | '\u{2028}' | '\u{2029}' => {
+                self.next_line();
+                self.push_token(TokenKind::LineTerminator, start_pos);
+            }
+            '\r' => {
+                self.carriage_return();
+            }
+            // The rust char::is_whitespace function and the ecma standard use different sets
+            // of characters as whitespaces:
+            //  * Rust uses \p{White_Space},
+            //  * ecma standard uses \{Space_Separator} + \u{0009}, \u{000B}, \u{000C}, \u{FEFF}
+            //
+            // Explicit whitespace: see https://tc39.es/ecma262/#table-32
+            '\u{0020}' | '\u{0009}' | '\u{000B}' | '\u{000C}' | '\u{00A0}' | '\u{FEFF}' |
+            // Unicode Space_Seperator category (minus \u{0020} and \u{00A0} which are allready stated above)
+            '\u{1680}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => (),
+            _ => {
+                let details = format!("Unexpected '{}' at line {}, column {}", ch, start_pos.line_number(), start_pos.column_number());
+                return Err(LexerError::new(details).at(start_pos));
+            },
+        }
+        Ok(true)
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = Result<Token, LexerError>;
+
+    /// Lexes the source until the next token, which is removed from `tokens`.
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if !self.tokens.is_empty() {
+                return Some(Ok(self.tokens.remove(0)));
+            }
+            match self.lex_element() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(err) => return Some(Err(err)),
             }
         }
     }
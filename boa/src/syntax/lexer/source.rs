@@ -0,0 +1,167 @@
+//! The characters that the lexer reads, from a string or from any `Read` source.
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    io::{self, BufReader, Read},
+};
+
+/// The characters of the source code, with a lookahead buffer.
+///
+/// The characters are only read from the underlying source when they are needed, so the source
+/// does not have to be held in memory as a whole.
+pub(super) struct Source<'a> {
+    chars: Box<dyn Iterator<Item = io::Result<char>> + 'a>,
+    /// The characters that were read from `chars` but not consumed yet.
+    lookahead: VecDeque<char>,
+    /// The characters consumed since the last `checkpoint`, if any.
+    recorded: Option<Vec<char>>,
+    /// The error that stopped the reading of the source, if any.
+    error: Option<io::Error>,
+    /// The number of characters consumed so far.
+    consumed: usize,
+}
+
+impl<'a> Source<'a> {
+    /// Creates a source reading from a string.
+    pub(super) fn from_str(source: &'a str) -> Self {
+        Self::new(Box::new(source.chars().map(Ok)))
+    }
+
+    /// Creates a source reading UTF-8 text from a reader.
+    pub(super) fn from_reader<R>(reader: R) -> Self
+    where
+        R: Read + 'a,
+    {
+        Self::new(Box::new(Utf8Chars {
+            bytes: BufReader::new(reader).bytes(),
+        }))
+    }
+
+    fn new(chars: Box<dyn Iterator<Item = io::Result<char>> + 'a>) -> Self {
+        Self {
+            chars,
+            lookahead: VecDeque::new(),
+            recorded: None,
+            error: None,
+            consumed: 0,
+        }
+    }
+
+    /// Reads characters until the lookahead buffer holds `n` of them, or the source ends.
+    fn fill(&mut self, n: usize) {
+        while self.lookahead.len() < n && self.error.is_none() {
+            match self.chars.next() {
+                Some(Ok(ch)) => self.lookahead.push_back(ch),
+                Some(Err(err)) => self.error = Some(err),
+                None => break,
+            }
+        }
+    }
+
+    /// Consumes the next character.
+    pub(super) fn next(&mut self) -> Option<char> {
+        self.fill(1);
+        let ch = self.lookahead.pop_front()?;
+        self.consumed += 1;
+        if let Some(ref mut recorded) = self.recorded {
+            recorded.push(ch);
+        }
+        Some(ch)
+    }
+
+    /// Gets the next character without consuming it.
+    pub(super) fn peek(&mut self) -> Option<char> {
+        self.peek_nth(0)
+    }
+
+    /// Gets the character `n` places after the next one without consuming anything.
+    pub(super) fn peek_nth(&mut self, n: usize) -> Option<char> {
+        self.fill(n + 1);
+        self.lookahead.get(n).copied()
+    }
+
+    /// Starts recording the consumed characters, so that `rewind` can give them back.
+    pub(super) fn checkpoint(&mut self) {
+        self.recorded = Some(Vec::new());
+    }
+
+    /// Gives back the characters consumed since the last `checkpoint`.
+    pub(super) fn rewind(&mut self) {
+        if let Some(recorded) = self.recorded.take() {
+            self.consumed -= recorded.len();
+            for ch in recorded.into_iter().rev() {
+                self.lookahead.push_front(ch);
+            }
+        }
+    }
+
+    /// Stops recording the consumed characters.
+    pub(super) fn commit(&mut self) {
+        self.recorded = None;
+    }
+
+    /// Gets the number of characters consumed so far.
+    pub(super) fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Takes the error that stopped the reading of the source, if any.
+    pub(super) fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+}
+
+impl fmt::Debug for Source<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Source")
+            .field("lookahead", &self.lookahead)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+/// Decodes the UTF-8 characters of a stream of bytes.
+struct Utf8Chars<R> {
+    bytes: io::Bytes<R>,
+}
+
+impl<R: Read> Iterator for Utf8Chars<R> {
+    type Item = io::Result<char>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.bytes.next()? {
+            Ok(byte) => byte,
+            Err(err) => return Some(Err(err)),
+        };
+        let width = match first {
+            0x00..=0x7F => return Some(Ok(char::from(first))),
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => return Some(Err(invalid_utf8())),
+        };
+
+        let mut buf = [first, 0, 0, 0];
+        for byte in &mut buf[1..width] {
+            *byte = match self.bytes.next() {
+                Some(Ok(byte)) => byte,
+                Some(Err(err)) => return Some(Err(err)),
+                None => return Some(Err(invalid_utf8())),
+            };
+        }
+        Some(
+            std::str::from_utf8(&buf[..width])
+                .ok()
+                .and_then(|s| s.chars().next())
+                .ok_or_else(invalid_utf8),
+        )
+    }
+}
+
+fn invalid_utf8() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "stream did not contain valid UTF-8",
+    )
+}
@@ -347,6 +347,18 @@ fn check_positions() {
     assert_eq!(lexer.tokens[6].span(), span((1, 35), (1, 35)));
 }
 
+#[test]
+fn check_numeric_literal_positions() {
+    let s = "1 + 23.5 + 0x1F + 0";
+    let mut lexer = Lexer::new(s);
+    lexer.lex().expect("failed to lex");
+
+    assert_eq!(lexer.tokens[0].span(), span((1, 1), (1, 1)));
+    assert_eq!(lexer.tokens[2].span(), span((1, 5), (1, 8)));
+    assert_eq!(lexer.tokens[4].span(), span((1, 12), (1, 15)));
+    assert_eq!(lexer.tokens[6].span(), span((1, 19), (1, 19)));
+}
+
 #[test]
 #[ignore]
 fn two_divisions_in_expression() {
@@ -475,6 +487,22 @@ fn regex_literal_flags() {
     );
 }
 
+#[test]
+fn division_after_operand() {
+    let mut lexer = Lexer::new("a / b; (c) / d / e; x = /f/g");
+    lexer.lex().expect("failed to lex");
+    let divisions = lexer
+        .tokens
+        .iter()
+        .filter(|token| token.kind == TokenKind::Punctuator(Punctuator::Div))
+        .count();
+    assert_eq!(divisions, 3);
+    assert_eq!(
+        lexer.tokens.last().unwrap().kind,
+        TokenKind::regular_expression_literal("f", "g".parse().unwrap())
+    );
+}
+
 #[test]
 fn addition_no_spaces() {
     let mut lexer = Lexer::new("1+1");
@@ -531,3 +559,95 @@ fn addition_no_spaces_e_number() {
         TokenKind::numeric_literal(100_000_000_000.0)
     );
 }
+
+#[test]
+fn tokens_are_yielded_lazily() {
+    let s = "let a = b / 2\n/x/g";
+    let mut lexer = Lexer::new(s);
+    let first = lexer.next().expect("a token").expect("failed to lex");
+    assert_eq!(first.kind, TokenKind::Keyword(Keyword::Let));
+    assert!(lexer.tokens.is_empty());
+
+    let kinds: Vec<TokenKind> = lexer
+        .map(|token| token.expect("failed to lex").kind)
+        .collect();
+    assert_eq!(kinds[3], TokenKind::Punctuator(Punctuator::Div));
+    assert_eq!(
+        kinds.last(),
+        Some(&TokenKind::regular_expression_literal(
+            "x",
+            "g".parse().expect("valid flags")
+        ))
+    );
+}
+
+#[test]
+fn lex_from_reader() {
+    let source: &[u8] = "var s = '中文';\nx".as_bytes();
+    let mut lexer = Lexer::from_reader(source);
+    lexer.lex().expect("failed to lex");
+    let mut expected = Lexer::new("var s = '中文';\nx");
+    expected.lex().expect("failed to lex");
+    assert_eq!(lexer.tokens, expected.tokens);
+
+    let invalid: &[u8] = b"var s = '\xFF';";
+    let mut lexer = Lexer::from_reader(invalid);
+    assert!(lexer.lex().is_err());
+}
+
+#[test]
+fn offset_is_the_end_of_the_last_token() {
+    let s = "'a\\n中' + 1.5e3 /* c */ /x/g;";
+    let mut lexer = Lexer::new(s);
+    let mut ends = Vec::new();
+    while let Some(token) = lexer.next() {
+        token.expect("failed to lex");
+        ends.push(lexer.offset());
+    }
+    assert_eq!(ends, vec![6, 8, 14, 27, 28]);
+}
+
+#[test]
+fn hashbang_comment() {
+    let mut lexer = Lexer::new("#!/usr/bin/env boa\nlet a;");
+    lexer.lex().expect("failed to lex");
+    assert_eq!(lexer.tokens[0].kind, TokenKind::Keyword(Keyword::Let));
+    assert_eq!(lexer.tokens[0].span().start(), Position::new(2, 1));
+}
+
+#[test]
+fn hashbang_only_at_the_start() {
+    assert!(Lexer::new(" #!/usr/bin/env boa").lex().is_err());
+    assert!(Lexer::new("a\n#!/usr/bin/env boa").lex().is_err());
+}
+
+#[test]
+fn keep_comments() {
+    let s = "#!/usr/bin/env boa\na; // b\r\n/* c\n d */ /*/ e */\n";
+    let mut lexer = Lexer::new(s);
+    lexer.lex().expect("failed to lex");
+    assert!(lexer.comments.is_empty());
+
+    let mut lexer = Lexer::new(s).keep_comments(true);
+    lexer.lex().expect("failed to lex");
+    let comments: Vec<_> = lexer
+        .comments
+        .iter()
+        .map(|comment| (comment.text(), comment.span().start(), comment.span().end()))
+        .collect();
+    assert_eq!(
+        comments,
+        vec![
+            (
+                "#!/usr/bin/env boa",
+                Position::new(1, 1),
+                Position::new(1, 18)
+            ),
+            ("// b", Position::new(2, 4), Position::new(2, 7)),
+            ("/* c\n d */", Position::new(3, 1), Position::new(4, 5)),
+            ("/*/ e */", Position::new(4, 7), Position::new(4, 14)),
+        ]
+    );
+    assert!(lexer.comments[1].is_line_comment());
+    assert!(!lexer.comments[2].is_line_comment());
+}
@@ -1,5 +1,7 @@
 //! Syntactical analysis, such as AST, Parsing and Lexing
 
 pub mod ast;
+pub mod codegen;
 pub mod lexer;
+pub mod optimizer;
 pub mod parser;
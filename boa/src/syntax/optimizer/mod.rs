@@ -0,0 +1,319 @@
+//! An optimization pass over the syntax tree.
+//!
+//! The pass folds the operations whose operands are literals, like `60 * 60 * 24` or
+//! `'Hello, ' + 'world'`, into the literal they evaluate to, and replaces the conditionals whose
+//! condition is a literal, like `if (false) { ... }`, with the branch that would run. It only
+//! rewrites what it can evaluate without running any code, so an optimized script has the same
+//! behaviour as the original one.
+//!
+//! The pass is disabled by default, see `Interpreter::set_optimize`.
+
+#[cfg(test)]
+mod tests;
+
+use crate::syntax::ast::{
+    node::{
+        ArrayDecl, ArrowFunctionDecl, Assign, BinOp, Block, Call, ConditionalOp, ConstDecl,
+        ConstDeclList, DoWhileLoop, ForLoop, FunctionDecl, FunctionExpr, If, LazyStatementList,
+        LetDecl, LetDeclList, Node, Return, StatementList, Throw, UnaryOp, VarDecl, VarDeclList,
+        WhileLoop,
+    },
+    op::{self, CompOp, LogOp, NumOp},
+    Const,
+};
+
+/// Optimizes the statements of a script.
+///
+/// The nodes that the pass does not know how to simplify are kept as they are, and each
+/// statement keeps its span.
+pub fn optimize(statements: &StatementList) -> StatementList {
+    let optimized = optimize_all(statements.statements());
+    if statements.spans().is_empty() {
+        StatementList::from(optimized)
+    } else {
+        StatementList::with_spans(
+            optimized
+                .into_iter()
+                .zip(statements.spans().iter().copied()),
+        )
+    }
+}
+
+fn optimize_all(nodes: &[Node]) -> Vec<Node> {
+    nodes.iter().map(optimize_node).collect()
+}
+
+/// Optimizes a function body, unless its parsing is deferred.
+fn optimize_body(body: &LazyStatementList) -> LazyStatementList {
+    if !body.is_parsed() {
+        return body.clone();
+    }
+    match body.statements() {
+        Ok(statements) => optimize(&statements).into(),
+        Err(_) => body.clone(),
+    }
+}
+
+fn optimize_node(node: &Node) -> Node {
+    match *node {
+        Node::ArrayDecl(ref array) => ArrayDecl::from(optimize_all(array.as_ref())).into(),
+        Node::ArrowFunctionDecl(ref function) => {
+            let optimized =
+                ArrowFunctionDecl::new(function.params(), optimize_body(function.body()));
+            match function.position() {
+                Some(position) => optimized.at(position).into(),
+                None => optimized.into(),
+            }
+        }
+        // The target of an assignment is not evaluated like an expression.
+        Node::Assign(ref assign) => {
+            Assign::new(assign.lhs().clone(), optimize_node(assign.rhs())).into()
+        }
+        Node::BinOp(ref bin_op) => optimize_bin_op(bin_op),
+        Node::Block(ref block) => Block::from(optimize_all(block.statements())).into(),
+        // The callee is kept as it is, since its form decides the `this` of the call.
+        Node::Call(ref call) => Call::new(call.expr().clone(), optimize_all(call.args())).into(),
+        Node::ConditionalOp(ref cond_op) => {
+            let cond = optimize_node(cond_op.cond());
+            match literal_truthiness(&cond) {
+                Some(true) => optimize_node(cond_op.if_true()),
+                Some(false) => optimize_node(cond_op.if_false()),
+                None => ConditionalOp::new(
+                    cond,
+                    optimize_node(cond_op.if_true()),
+                    optimize_node(cond_op.if_false()),
+                )
+                .into(),
+            }
+        }
+        Node::ConstDeclList(ref list) => ConstDeclList::from(
+            list.as_ref()
+                .iter()
+                .map(|decl| ConstDecl::new(decl.name(), optimize_node(decl.init())))
+                .collect::<Vec<_>>(),
+        )
+        .into(),
+        Node::DoWhileLoop(ref do_while) => DoWhileLoop::new(
+            optimize_node(do_while.body()),
+            optimize_node(do_while.cond()),
+        )
+        .into(),
+        Node::ForLoop(ref for_loop) => ForLoop::new(
+            for_loop.init().map(optimize_node),
+            for_loop.condition().map(optimize_node),
+            for_loop.final_expr().map(optimize_node),
+            optimize_node(for_loop.body()),
+        )
+        .into(),
+        Node::FunctionDecl(ref function) => {
+            let optimized = FunctionDecl::new(
+                function.name(),
+                function.parameters(),
+                optimize_body(function.body()),
+            );
+            match function.position() {
+                Some(position) => optimized.at(position).into(),
+                None => optimized.into(),
+            }
+        }
+        Node::FunctionExpr(ref function) => {
+            let optimized = FunctionExpr::new(
+                function.name().map(Box::from),
+                function.parameters(),
+                optimize_body(function.body()),
+            );
+            match function.position() {
+                Some(position) => optimized.at(position).into(),
+                None => optimized.into(),
+            }
+        }
+        Node::If(ref if_smt) => {
+            let cond = optimize_node(if_smt.cond());
+            match literal_truthiness(&cond) {
+                Some(true) => optimize_node(if_smt.body()),
+                Some(false) => match if_smt.else_node() {
+                    Some(else_node) => optimize_node(else_node),
+                    // An `if` statement that runs no branch completes with `undefined`.
+                    None => Const::Undefined.into(),
+                },
+                None => If::new::<_, _, Node, _>(
+                    cond,
+                    optimize_node(if_smt.body()),
+                    if_smt.else_node().map(optimize_node),
+                )
+                .into(),
+            }
+        }
+        Node::LetDeclList(ref list) => LetDeclList::from(
+            list.as_ref()
+                .iter()
+                .map(|decl| LetDecl::new(decl.name(), decl.init().map(optimize_node)))
+                .collect::<Vec<_>>(),
+        )
+        .into(),
+        Node::Return(ref ret) => {
+            Return::new::<Node, _, _>(ret.expr().map(optimize_node), ret.label().map(Box::from))
+                .into()
+        }
+        Node::Throw(ref throw) => Throw::new(optimize_node(throw.expr())).into(),
+        Node::UnaryOp(ref unary_op) => optimize_unary_op(unary_op),
+        Node::VarDeclList(ref list) => VarDeclList::from(
+            list.as_ref()
+                .iter()
+                .map(|decl| VarDecl::new(decl.name(), decl.init().map(optimize_node)))
+                .collect::<Vec<_>>(),
+        )
+        .into(),
+        Node::WhileLoop(ref while_loop) => WhileLoop::new(
+            optimize_node(while_loop.cond()),
+            optimize_node(while_loop.expr()),
+        )
+        .into(),
+        _ => node.clone(),
+    }
+}
+
+fn optimize_bin_op(bin_op: &BinOp) -> Node {
+    let lhs = optimize_node(bin_op.lhs());
+    let rhs = optimize_node(bin_op.rhs());
+    if let (Node::Const(x), Node::Const(y)) = (&lhs, &rhs) {
+        if let Some(value) = fold_bin_op(bin_op.op(), x, y) {
+            return value.into();
+        }
+    }
+    BinOp::new(bin_op.op(), lhs, rhs).into()
+}
+
+fn optimize_unary_op(unary_op: &UnaryOp) -> Node {
+    match unary_op.op() {
+        op::UnaryOp::Minus
+        | op::UnaryOp::Plus
+        | op::UnaryOp::Not
+        | op::UnaryOp::Tilde
+        | op::UnaryOp::TypeOf
+        | op::UnaryOp::Void => {
+            let target = optimize_node(unary_op.target());
+            if let Node::Const(ref x) = target {
+                if let Some(value) = fold_unary_op(unary_op.op(), x) {
+                    return value.into();
+                }
+            }
+            UnaryOp::new(unary_op.op(), target).into()
+        }
+        // The other operators change their target, which must stay a reference.
+        _ => Node::UnaryOp(unary_op.clone()),
+    }
+}
+
+/// Evaluates a binary operation on literals, if it can be done without running any code.
+fn fold_bin_op(op: op::BinOp, x: &Const, y: &Const) -> Option<Const> {
+    match (op, x, y) {
+        (op::BinOp::Num(NumOp::Add), Const::String(x), Const::String(y)) => {
+            Some(Const::String(x.concat(y)))
+        }
+        (op::BinOp::Num(op), _, _) => {
+            let (x, y) = (number(x)?, number(y)?);
+            Some(Const::Num(match op {
+                NumOp::Add => x + y,
+                NumOp::Sub => x - y,
+                NumOp::Mul => x * y,
+                NumOp::Div => x / y,
+                NumOp::Mod => x % y,
+                NumOp::Exp => pow(x, y),
+            }))
+        }
+        (op::BinOp::Comp(op), Const::String(x), Const::String(y)) => match op {
+            CompOp::Equal | CompOp::StrictEqual => Some(Const::Bool(x == y)),
+            CompOp::NotEqual | CompOp::StrictNotEqual => Some(Const::Bool(x != y)),
+            _ => None,
+        },
+        (op::BinOp::Comp(op), _, _) => {
+            let (x, y) = (number(x)?, number(y)?);
+            Some(Const::Bool(match op {
+                CompOp::Equal | CompOp::StrictEqual => x == y,
+                CompOp::NotEqual | CompOp::StrictNotEqual => x != y,
+                CompOp::GreaterThan => x > y,
+                CompOp::GreaterThanOrEqual => x >= y,
+                CompOp::LessThan => x < y,
+                CompOp::LessThanOrEqual => x <= y,
+                CompOp::In => return None,
+            }))
+        }
+        (op::BinOp::Log(op), &Const::Bool(x), &Const::Bool(y)) => Some(Const::Bool(match op {
+            LogOp::And => x && y,
+            LogOp::Or => x || y,
+            LogOp::Coalesce => x,
+        })),
+        _ => None,
+    }
+}
+
+/// Evaluates a unary operation on a literal, if it can be done without running any code.
+fn fold_unary_op(op: op::UnaryOp, x: &Const) -> Option<Const> {
+    match op {
+        op::UnaryOp::Minus => Some(Const::Num(-number(x)?)),
+        op::UnaryOp::Plus => Some(Const::Num(number(x)?)),
+        op::UnaryOp::Not => Some(Const::Bool(!truthiness(x)?)),
+        op::UnaryOp::TypeOf => {
+            let type_of = match x {
+                Const::String(_) => "string",
+                Const::Num(_) | Const::Int(_) => "number",
+                Const::BigInt(_) => "bigint",
+                Const::Bool(_) => "boolean",
+                Const::Null => "object",
+                Const::Undefined => "undefined",
+            };
+            Some(Const::String(type_of.into()))
+        }
+        op::UnaryOp::Void => Some(Const::Undefined),
+        _ => None,
+    }
+}
+
+/// Gets the value of a number literal.
+fn number(x: &Const) -> Option<f64> {
+    match *x {
+        Const::Num(num) => Some(num),
+        Const::Int(num) => Some(f64::from(num)),
+        _ => None,
+    }
+}
+
+/// Converts a literal to a boolean, like `ToBoolean` does.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-toboolean
+fn truthiness(x: &Const) -> Option<bool> {
+    match *x {
+        Const::String(ref string) => Some(!string.is_empty()),
+        Const::Num(num) => Some(num != 0.0 && !num.is_nan()),
+        Const::Int(num) => Some(num != 0),
+        Const::Bool(boolean) => Some(boolean),
+        Const::Null | Const::Undefined => Some(false),
+        Const::BigInt(_) => None,
+    }
+}
+
+/// Gets whether a node is a truthy or a falsy literal, or `None` if it is not a literal.
+fn literal_truthiness(node: &Node) -> Option<bool> {
+    match *node {
+        Node::Const(ref x) => truthiness(x),
+        _ => None,
+    }
+}
+
+/// Computes `x ** y`, which only differs from `f64::powf` in the cases where the result is `NaN`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-numeric-types-number-exponentiate
+fn pow(x: f64, y: f64) -> f64 {
+    if y.is_nan() || (x.abs() == 1.0 && y.is_infinite()) {
+        f64::NAN
+    } else {
+        x.powf(y)
+    }
+}
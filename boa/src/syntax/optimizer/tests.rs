@@ -0,0 +1,92 @@
+//! Tests for the optimization pass.
+
+use super::optimize;
+use crate::{
+    syntax::{
+        ast::{
+            node::{BinOp, Block, Call, Identifier, Node, StatementList},
+            op::NumOp,
+            Const,
+        },
+        lexer::Lexer,
+        parser::Parser,
+    },
+    Context,
+};
+
+/// Checks that the given JavaScript string is optimized into the expected statements.
+#[allow(clippy::unwrap_used)]
+// TODO: #[track_caller]: https://github.com/rust-lang/rust/issues/47809
+fn check_optimizer<L>(js: &str, expected: L)
+where
+    L: Into<Box<[Node]>>,
+{
+    let mut lexer = Lexer::new(js);
+    lexer.lex().expect("failed to lex");
+    let statements = Parser::new(&lexer.tokens)
+        .parse_all()
+        .expect("failed to parse");
+
+    assert_eq!(optimize(&statements), StatementList::from(expected));
+}
+
+#[test]
+fn fold_arithmetic() {
+    check_optimizer("60 * 60 * 24", vec![Const::from(86400.0).into()]);
+    check_optimizer("-(1 + 2) % 2", vec![Const::from(-1.0).into()]);
+    check_optimizer(
+        "1 ** NaN",
+        vec![BinOp::new(NumOp::Exp, Const::from(1), Identifier::from("NaN")).into()],
+    );
+}
+
+#[test]
+fn fold_strings() {
+    check_optimizer(
+        "'Hello, ' + 'world' + '!'",
+        vec![Const::from("Hello, world!").into()],
+    );
+    check_optimizer("typeof null", vec![Const::from("object").into()]);
+    check_optimizer("'a' === 'a'", vec![Const::from(true).into()]);
+}
+
+#[test]
+fn keep_operands_that_are_not_literals() {
+    check_optimizer(
+        "a + (2 + 3)",
+        vec![BinOp::new(NumOp::Add, Identifier::from("a"), Const::from(5.0)).into()],
+    );
+}
+
+#[test]
+fn remove_dead_branches() {
+    check_optimizer(
+        "if (1 > 2) { a(); } else { b(); }",
+        vec![Block::from(vec![Node::from(Call::new(Identifier::from("b"), vec![]))]).into()],
+    );
+    check_optimizer("if ('') { a(); }", vec![Const::Undefined.into()]);
+}
+
+#[test]
+fn optimized_scripts_behave_the_same() {
+    let scripts = [
+        "1 + 2",
+        "'con' + 'cat'",
+        "10 % 4 * 2 ** 3",
+        "!0 && 1 < 2",
+        "if (false) { 1 } else { 2 }",
+        "1; if (false) { 2 }",
+        "function f() { return 6 * 7 } f()",
+        "var x = 2 ** 10; x",
+    ];
+    for script in scripts.iter() {
+        let mut plain = Context::new();
+        let mut optimized = Context::builder().optimize(true).build();
+        assert_eq!(
+            optimized.eval(script).unwrap().to_string(),
+            plain.eval(script).unwrap().to_string(),
+            "{}",
+            script
+        );
+    }
+}
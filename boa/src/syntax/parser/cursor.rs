@@ -3,7 +3,7 @@
 use super::ParseError;
 use crate::syntax::ast::{
     token::{Token, TokenKind},
-    Punctuator,
+    Punctuator, Span,
 };
 
 /// Token cursor.
@@ -15,6 +15,8 @@ pub(super) struct Cursor<'a> {
     tokens: &'a [Token],
     /// The current position within the tokens.
     pos: usize,
+    /// Whether the parsing of function bodies is deferred.
+    lazy_functions: bool,
 }
 
 impl<'a> Cursor<'a> {
@@ -26,6 +28,16 @@ impl<'a> Cursor<'a> {
         }
     }
 
+    /// Checks whether the parsing of function bodies is deferred, see `Parser::lazy_functions`.
+    pub(super) fn lazy_functions(&self) -> bool {
+        self.lazy_functions
+    }
+
+    /// Sets whether the parsing of function bodies is deferred.
+    pub(super) fn set_lazy_functions(&mut self, enabled: bool) {
+        self.lazy_functions = enabled;
+    }
+
     /// Retrieves the current position of the cursor in the token stream.
     pub(super) fn pos(&self) -> usize {
         self.pos
@@ -38,6 +50,41 @@ impl<'a> Cursor<'a> {
         self.pos = pos
     }
 
+    /// Gets the span of the tokens consumed since the given position, see `Cursor::pos()`.
+    ///
+    /// Line terminators are left out, and `None` is returned if no other token was consumed.
+    pub(super) fn span_since(&self, start: usize) -> Option<Span> {
+        let mut consumed = self
+            .tokens
+            .get(start..self.pos)?
+            .iter()
+            .filter(|tk| tk.kind != TokenKind::LineTerminator);
+        let first = consumed.next()?;
+        let last = consumed.next_back().unwrap_or(first);
+        Some(Span::new(first.span().start(), last.span().end()))
+    }
+
+    /// Moves the cursor to the `}` that closes the current block, without parsing the tokens
+    /// before it.
+    ///
+    /// Returns the skipped tokens and the `}`, which is left for the caller to consume.
+    pub(super) fn skip_block(&mut self) -> Result<&'a [Token], ParseError> {
+        let start = self.pos;
+        let mut depth = 0_usize;
+        while let Some(token) = self.tokens.get(self.pos) {
+            match token.kind {
+                TokenKind::Punctuator(Punctuator::OpenBlock) => depth += 1,
+                TokenKind::Punctuator(Punctuator::CloseBlock) if depth == 0 => {
+                    return Ok(&self.tokens[start..=self.pos]);
+                }
+                TokenKind::Punctuator(Punctuator::CloseBlock) => depth -= 1,
+                _ => {}
+            }
+            self.pos += 1;
+        }
+        Err(ParseError::AbruptEnd)
+    }
+
     /// Moves the cursor to the next token and returns the token.
     pub(super) fn next(&mut self) -> Option<&'a Token> {
         loop {
@@ -79,6 +79,17 @@ impl ParseError {
     pub(super) fn general(message: &'static str, position: Position) -> Self {
         Self::General { message, position }
     }
+
+    /// Gets the position in the source code where the error was found, if it is known.
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Self::Expected { found, .. } | Self::Unexpected { found, .. } => {
+                Some(found.span().start())
+            }
+            Self::General { position, .. } => Some(*position),
+            Self::AbruptEnd => None,
+        }
+    }
 }
 
 impl fmt::Display for ParseError {
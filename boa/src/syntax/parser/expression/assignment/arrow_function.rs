@@ -11,7 +11,7 @@ use super::AssignmentExpression;
 use crate::{
     syntax::{
         ast::{
-            node::{ArrowFunctionDecl, FormalParameter, Node, Return, StatementList},
+            node::{ArrowFunctionDecl, FormalParameter, LazyStatementList, Node, Return},
             Punctuator, TokenKind,
         },
         parser::{
@@ -64,7 +64,9 @@ impl TokenParser for ArrowFunction {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("ArrowFunction", "Parsing");
+        let _span = tracing::trace_span!("ArrowFunction").entered();
         let next_token = cursor.peek(0).ok_or(ParseError::AbruptEnd)?;
+        let start = next_token.span().start();
         let params = if let TokenKind::Punctuator(Punctuator::OpenParen) = &next_token.kind {
             // CoverParenthesizedExpressionAndArrowParameterList
             cursor.expect(Punctuator::OpenParen, "arrow function")?;
@@ -84,7 +86,7 @@ impl TokenParser for ArrowFunction {
 
         let body = ConciseBody::new(self.allow_in).parse(cursor)?;
 
-        Ok(ArrowFunctionDecl::new(params, body))
+        Ok(ArrowFunctionDecl::new(params, body).at(start))
     }
 }
 
@@ -107,7 +109,7 @@ impl ConciseBody {
 }
 
 impl TokenParser for ConciseBody {
-    type Output = StatementList;
+    type Output = LazyStatementList;
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         match cursor.peek(0).ok_or(ParseError::AbruptEnd)?.kind {
@@ -117,7 +119,7 @@ impl TokenParser for ConciseBody {
                 cursor.expect(Punctuator::CloseBlock, "arrow function")?;
                 Ok(body)
             }
-            _ => Ok(StatementList::from(vec![Return::new(
+            _ => Ok(LazyStatementList::from(vec![Return::new(
                 ExpressionBody::new(self.allow_in, false).parse(cursor)?,
                 None,
             )
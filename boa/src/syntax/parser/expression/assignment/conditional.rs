@@ -58,6 +58,7 @@ impl TokenParser for ConditionalExpression {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> ParseResult {
         let _timer = BoaProfiler::global().start_event("Conditional", "Parsing");
+        let _span = tracing::trace_span!("Conditional").entered();
         // TODO: coalesce expression
         let lhs = LogicalORExpression::new(self.allow_in, self.allow_yield, self.allow_await)
             .parse(cursor)?;
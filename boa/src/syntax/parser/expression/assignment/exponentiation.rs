@@ -75,6 +75,7 @@ impl TokenParser for ExponentiationExpression {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> ParseResult {
         let _timer = BoaProfiler::global().start_event("ExponentiationExpression", "Parsing");
+        let _span = tracing::trace_span!("ExponentiationExpression").entered();
         if Self::is_unary_expression(cursor) {
             return UnaryExpression::new(self.allow_yield, self.allow_await).parse(cursor);
         }
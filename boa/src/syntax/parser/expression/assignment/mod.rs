@@ -74,6 +74,7 @@ impl TokenParser for AssignmentExpression {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> ParseResult {
         let _timer = BoaProfiler::global().start_event("AssignmentExpression", "Parsing");
+        let _span = tracing::trace_span!("AssignmentExpression").entered();
         // Arrow function
         let next_token = cursor.peek(0).ok_or(ParseError::AbruptEnd)?;
         match next_token.kind {
@@ -51,6 +51,7 @@ impl TokenParser for Arguments {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("Arguments", "Parsing");
+        let _span = tracing::trace_span!("Arguments").entered();
         cursor.expect(Punctuator::OpenParen, "arguments")?;
         let mut args = Vec::new();
         loop {
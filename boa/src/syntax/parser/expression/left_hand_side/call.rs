@@ -59,6 +59,7 @@ impl TokenParser for CallExpression {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> ParseResult {
         let _timer = BoaProfiler::global().start_event("CallExpression", "Parsing");
+        let _span = tracing::trace_span!("CallExpression").entered();
         let mut lhs = match cursor.peek(0) {
             Some(tk) if tk.kind == TokenKind::Punctuator(Punctuator::OpenParen) => {
                 let args = Arguments::new(self.allow_yield, self.allow_await).parse(cursor)?;
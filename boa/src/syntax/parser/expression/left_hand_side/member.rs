@@ -55,6 +55,7 @@ impl TokenParser for MemberExpression {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> ParseResult {
         let _timer = BoaProfiler::global().start_event("MemberExpression", "Parsing");
+        let _span = tracing::trace_span!("MemberExpression").entered();
         let mut lhs = if cursor.peek(0).ok_or(ParseError::AbruptEnd)?.kind
             == TokenKind::Keyword(Keyword::New)
         {
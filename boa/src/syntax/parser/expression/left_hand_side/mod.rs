@@ -53,6 +53,7 @@ impl TokenParser for LeftHandSideExpression {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> ParseResult {
         let _timer = BoaProfiler::global().start_event("LeftHandSIdeExpression", "Parsing");
+        let _span = tracing::trace_span!("LeftHandSideExpression").entered();
         // TODO: Implement NewExpression: new MemberExpression
         let lhs = MemberExpression::new(self.allow_yield, self.allow_await).parse(cursor)?;
         match cursor.peek(0) {
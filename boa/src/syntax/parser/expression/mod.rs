@@ -55,6 +55,7 @@ macro_rules! expression { ($name:ident, $lower:ident, [$( $op:path ),*], [$( $lo
 
         fn parse(self, cursor: &mut Cursor<'_>) -> ParseResult {
             let _timer = BoaProfiler::global().start_event("Expression", "Parsing");
+            let _span = tracing::trace_span!(stringify!($name)).entered();
             let mut lhs = $lower::new($( self.$low_param ),*).parse(cursor)?;
             while let Some(tok) = cursor.peek(0) {
                 match tok.kind {
@@ -122,6 +123,8 @@ expression!(
 
 /// Parses a logical `OR` expression.
 ///
+/// The `??` operator of `Experimental::NULLISH_COALESCING` is parsed with the same precedence.
+///
 /// More information:
 ///  - [MDN documentation][mdn]
 ///  - [ECMAScript specification][spec]
@@ -154,7 +157,7 @@ impl LogicalORExpression {
 expression!(
     LogicalORExpression,
     LogicalANDExpression,
-    [Punctuator::BoolOr],
+    [Punctuator::BoolOr, Punctuator::Coalesce],
     [allow_in, allow_yield, allow_await]
 );
 
@@ -57,6 +57,7 @@ impl TokenParser for ArrayLiteral {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("ArrayLiteral", "Parsing");
+        let _span = tracing::trace_span!("ArrayLiteral").entered();
         let mut elements = Vec::new();
 
         loop {
@@ -35,6 +35,11 @@ impl TokenParser for FunctionExpression {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("FunctionExpression", "Parsing");
+        let _span = tracing::trace_span!("FunctionExpression").entered();
+        let start = cursor
+            .peek_prev()
+            .map(|tok| tok.span().start())
+            .ok_or(ParseError::AbruptEnd)?;
         let name = BindingIdentifier::new(false, false).try_parse(cursor);
 
         cursor.expect(Punctuator::OpenParen, "function expression")?;
@@ -48,6 +53,6 @@ impl TokenParser for FunctionExpression {
 
         cursor.expect(Punctuator::CloseBlock, "function expression")?;
 
-        Ok(FunctionExpr::new(name, params, body))
+        Ok(FunctionExpr::new(name, params, body).at(start))
     }
 }
@@ -87,7 +87,7 @@ impl TokenParser for PrimaryExpression {
             TokenKind::BooleanLiteral(boolean) => Ok(Const::from(*boolean).into()),
             TokenKind::NullLiteral => Ok(Const::Null.into()),
             TokenKind::Identifier(ident) => Ok(Identifier::from(ident.as_ref()).into()), // TODO: IdentifierReference
-            TokenKind::StringLiteral(s) => Ok(Const::from(s.as_ref()).into()),
+            TokenKind::StringLiteral(s) => Ok(Const::from(s.clone()).into()),
             TokenKind::NumericLiteral(NumericLiteral::Integer(num)) => Ok(Const::from(*num).into()),
             TokenKind::NumericLiteral(NumericLiteral::Rational(num)) => {
                 Ok(Const::from(*num).into())
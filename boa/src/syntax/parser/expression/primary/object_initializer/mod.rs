@@ -15,7 +15,7 @@ use crate::{
         ast::{
             node::{self, FunctionExpr, MethodDefinitionKind, Node, Object},
             token::{Token, TokenKind},
-            Punctuator,
+            Position, Punctuator,
         },
         parser::{
             expression::AssignmentExpression,
@@ -59,6 +59,7 @@ impl TokenParser for ObjectLiteral {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("ObjectLiteral", "Parsing");
+        let _span = tracing::trace_span!("ObjectLiteral").entered();
         let mut elements = Vec::new();
 
         loop {
@@ -126,6 +127,10 @@ impl TokenParser for PropertyDefinition {
             return Ok(node::PropertyDefinition::SpreadObject(node));
         }
 
+        let start = cursor
+            .peek(0)
+            .map(|tok| tok.span().start())
+            .ok_or(ParseError::AbruptEnd)?;
         let prop_name = cursor
             .next()
             .map(Token::to_string)
@@ -141,7 +146,7 @@ impl TokenParser for PropertyDefinition {
             .is_some()
             || ["get", "set"].contains(&prop_name.as_str())
         {
-            return MethodDefinition::new(self.allow_yield, self.allow_await, prop_name)
+            return MethodDefinition::new(self.allow_yield, self.allow_await, prop_name, start)
                 .parse(cursor);
         }
 
@@ -164,11 +169,12 @@ struct MethodDefinition {
     allow_yield: AllowYield,
     allow_await: AllowAwait,
     identifier: String,
+    start: Position,
 }
 
 impl MethodDefinition {
-    /// Creates a new `MethodDefinition` parser.
-    fn new<Y, A, I>(allow_yield: Y, allow_await: A, identifier: I) -> Self
+    /// Creates a new `MethodDefinition` parser, for a method starting at `start`.
+    fn new<Y, A, I>(allow_yield: Y, allow_await: A, identifier: I, start: Position) -> Self
     where
         Y: Into<AllowYield>,
         A: Into<AllowAwait>,
@@ -178,6 +184,7 @@ impl MethodDefinition {
             allow_yield: allow_yield.into(),
             allow_await: allow_await.into(),
             identifier: identifier.into(),
+            start,
         }
     }
 }
@@ -196,7 +203,7 @@ impl TokenParser for MethodDefinition {
                     TokenKind::Punctuator(Punctuator::OpenParen),
                     "property method definition",
                 )?;
-                let first_param = cursor.peek(0).expect("current token disappeared").clone();
+                let first_param = cursor.peek(0).ok_or(ParseError::AbruptEnd)?.clone();
                 let params = FormalParameters::new(false, false).parse(cursor)?;
                 cursor.expect(Punctuator::CloseParen, "method definition")?;
                 if idn == "get" {
@@ -241,7 +248,7 @@ impl TokenParser for MethodDefinition {
         Ok(node::PropertyDefinition::method_definition(
             methodkind,
             prop_name,
-            FunctionExpr::new(None, params, body),
+            FunctionExpr::new(None, params, body).at(self.start),
         ))
     }
 }
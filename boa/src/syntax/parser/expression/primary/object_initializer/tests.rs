@@ -6,7 +6,7 @@ use crate::syntax::{
         },
         Const,
     },
-    parser::tests::check_parser,
+    parser::tests::{check_invalid, check_parser},
 };
 
 /// Checks object literal parsing.
@@ -130,3 +130,10 @@ fn check_object_setter() {
         ],
     );
 }
+
+/// Checks that an accessor cut off after its opening parenthesis is a syntax error.
+#[test]
+fn check_object_accessor_abrupt_end() {
+    check_invalid("var o = { get x(");
+    check_invalid("var o = { set x(");
+}
@@ -183,7 +183,12 @@ impl TokenParser for FormalParameter {
 
         let param = BindingIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?;
 
-        let init = Initializer::new(true, self.allow_yield, self.allow_await).try_parse(cursor);
+        let init = match cursor.peek(0) {
+            Some(token) if token.kind == TokenKind::Punctuator(Punctuator::Assign) => {
+                Some(Initializer::new(true, self.allow_yield, self.allow_await).parse(cursor)?)
+            }
+            _ => None,
+        };
 
         Ok(Self::Output::new(param, init, false))
     }
@@ -223,10 +228,12 @@ impl FunctionStatementList {
     }
 }
 
-impl TokenParser for FunctionStatementList {
-    type Output = node::StatementList;
-
-    fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
+impl FunctionStatementList {
+    /// Parses the statements of the body, even if the parsing of function bodies is deferred.
+    pub(in crate::syntax::parser) fn parse_statements(
+        self,
+        cursor: &mut Cursor<'_>,
+    ) -> Result<node::StatementList, ParseError> {
         if let Some(tk) = cursor.peek(0) {
             if tk.kind == Punctuator::CloseBlock.into() {
                 return Ok(Vec::new().into());
@@ -236,3 +243,20 @@ impl TokenParser for FunctionStatementList {
         StatementList::new(self.allow_yield, self.allow_await, true, true).parse(cursor)
     }
 }
+
+impl TokenParser for FunctionStatementList {
+    type Output = node::LazyStatementList;
+
+    fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
+        if cursor.lazy_functions() {
+            let tokens = cursor.skip_block()?;
+            return Ok(node::LazyStatementList::deferred(
+                tokens,
+                self.allow_yield.0,
+                self.allow_await.0,
+            ));
+        }
+        self.parse_statements(cursor)
+            .map(node::LazyStatementList::from)
+    }
+}
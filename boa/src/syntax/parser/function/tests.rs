@@ -3,7 +3,8 @@ use crate::syntax::{
         ArrowFunctionDecl, BinOp, FormalParameter, FunctionDecl, Identifier, Node, Return,
     },
     ast::op::NumOp,
-    parser::tests::check_parser,
+    lexer::Lexer,
+    parser::{tests::check_parser, Parser},
 };
 
 /// Checks basic function declaration parsing.
@@ -176,3 +177,49 @@ fn check_arrow_empty_return_semicolon_insertion() {
         .into()],
     );
 }
+
+/// Checks that deferred function bodies are parsed when their statements are needed.
+#[test]
+fn check_lazy_function_bodies() {
+    let mut lexer = Lexer::new("function foo(a) { function bar() { return { b: 1 }; } return a }");
+    lexer.lex().expect("failed to lex");
+    let script = Parser::new(&lexer.tokens)
+        .lazy_functions(true)
+        .parse_all()
+        .expect("failed to parse");
+
+    let body = match script.statements() {
+        [Node::FunctionDecl(foo)] => foo.body(),
+        statements => panic!("expected a function declaration, got {:?}", statements),
+    };
+    assert!(!body.is_parsed());
+    let statements = body.statements().expect("failed to parse the body");
+    assert!(body.is_parsed());
+    match statements.statements() {
+        [Node::FunctionDecl(bar), Node::Return(_)] => assert!(!bar.body().is_parsed()),
+        statements => panic!("expected a function and a return, got {:?}", statements),
+    }
+}
+
+/// Checks that the syntax errors of deferred function bodies are found when they are parsed.
+#[test]
+fn check_lazy_function_body_errors() {
+    let mut lexer = Lexer::new("function foo() { return ) }");
+    lexer.lex().expect("failed to lex");
+    let script = Parser::new(&lexer.tokens)
+        .lazy_functions(true)
+        .parse_all()
+        .expect("failed to parse");
+
+    match script.statements() {
+        [Node::FunctionDecl(foo)] => assert!(foo.body().statements().is_err()),
+        statements => panic!("expected a function declaration, got {:?}", statements),
+    }
+
+    let mut lexer = Lexer::new("function foo() { if (a) { }");
+    lexer.lex().expect("failed to lex");
+    assert!(Parser::new(&lexer.tokens)
+        .lazy_functions(true)
+        .parse_all()
+        .is_err());
+}
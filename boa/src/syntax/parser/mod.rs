@@ -9,7 +9,7 @@ mod statement;
 mod tests;
 
 use self::error::{ParseError, ParseResult};
-use crate::syntax::ast::{node::StatementList, Token};
+use crate::syntax::ast::{node::StatementList, Punctuator, Token};
 use cursor::Cursor;
 
 /// Trait implemented by parsers.
@@ -102,10 +102,37 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Sets whether the parsing of function bodies is deferred until they are first needed.
+    ///
+    /// The parser then only checks that the braces of a function body are balanced, so the
+    /// syntax errors in the body are only found when it is parsed, see `LazyStatementList`.
+    pub fn lazy_functions(mut self, enabled: bool) -> Self {
+        self.cursor.set_lazy_functions(enabled);
+        self
+    }
+
     /// Parse all expressions in the token array
     pub fn parse_all(&mut self) -> Result<StatementList, ParseError> {
+        let _span = tracing::debug_span!("parse").entered();
         Script.parse(&mut self.cursor)
     }
+
+    /// Parses the tokens of a deferred function body, which end with the `}` that closes it.
+    pub(in crate::syntax) fn parse_function_body(
+        &mut self,
+        allow_yield: bool,
+        allow_await: bool,
+    ) -> Result<StatementList, ParseError> {
+        let _span = tracing::debug_span!("parse function body").entered();
+        let statements = function::FunctionBody::new(allow_yield, allow_await)
+            .parse_statements(&mut self.cursor)?;
+        self.cursor
+            .expect(Punctuator::CloseBlock, "function body")?;
+        match self.cursor.next() {
+            Some(token) => Err(ParseError::unexpected(token.clone(), "function body")),
+            None => Ok(statements),
+        }
+    }
 }
 
 /// Parses a full script.
@@ -53,6 +53,7 @@ impl TokenParser for BreakStatement {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("BreakStatement", "Parsing");
+        let _span = tracing::trace_span!("BreakStatement").entered();
         cursor.expect(Keyword::Break, "break statement")?;
 
         let label = if let (true, tok) = cursor.peek_semicolon(false) {
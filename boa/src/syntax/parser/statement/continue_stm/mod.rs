@@ -53,6 +53,7 @@ impl TokenParser for ContinueStatement {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("ContinueStatement", "Parsing");
+        let _span = tracing::trace_span!("ContinueStatement").entered();
         cursor.expect(Keyword::Continue, "continue statement")?;
 
         let label = if let (true, tok) = cursor.peek_semicolon(false) {
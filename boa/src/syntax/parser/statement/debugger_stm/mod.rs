@@ -0,0 +1,43 @@
+//! Debugger statement parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/debugger
+//! [spec]: https://tc39.es/ecma262/#sec-debugger-statement
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    syntax::{
+        ast::{Keyword, Node},
+        parser::{Cursor, ParseError, TokenParser},
+    },
+    BoaProfiler,
+};
+
+/// Debugger statement parsing
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/debugger
+/// [spec]: https://tc39.es/ecma262/#prod-DebuggerStatement
+#[derive(Debug, Clone, Copy)]
+pub(super) struct DebuggerStatement;
+
+impl TokenParser for DebuggerStatement {
+    type Output = Node;
+
+    fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
+        let _timer = BoaProfiler::global().start_event("DebuggerStatement", "Parsing");
+        let _span = tracing::trace_span!("DebuggerStatement").entered();
+        cursor.expect(Keyword::Debugger, "debugger statement")?;
+        cursor.expect_semicolon(false, "debugger statement")?;
+
+        Ok(Node::Debugger)
+    }
+}
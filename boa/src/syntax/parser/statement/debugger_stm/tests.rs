@@ -0,0 +1,29 @@
+use crate::syntax::{
+    ast::{
+        node::{Block, Node, WhileLoop},
+        Const,
+    },
+    parser::tests::{check_invalid, check_parser},
+};
+
+#[test]
+fn inline() {
+    check_parser(
+        "while (true) debugger;",
+        vec![WhileLoop::new(Const::from(true), Node::Debugger).into()],
+    );
+}
+
+#[test]
+fn semicolon_insertion() {
+    check_parser(
+        "{debugger}
+        debugger",
+        vec![Block::from(vec![Node::Debugger]).into(), Node::Debugger],
+    );
+}
+
+#[test]
+fn not_an_expression() {
+    check_invalid("debugger + 1");
+}
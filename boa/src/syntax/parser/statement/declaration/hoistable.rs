@@ -50,6 +50,7 @@ impl TokenParser for HoistableDeclaration {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> ParseResult {
         let _timer = BoaProfiler::global().start_event("HoistableDeclaration", "Parsing");
+        let _span = tracing::trace_span!("HoistableDeclaration").entered();
         // TODO: check for generators and async functions + generators
         FunctionDeclaration::new(self.allow_yield, self.allow_await, self.is_default)
             .parse(cursor)
@@ -92,6 +93,10 @@ impl TokenParser for FunctionDeclaration {
     type Output = FunctionDecl;
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
+        let start = cursor
+            .peek(0)
+            .map(|tok| tok.span().start())
+            .ok_or(ParseError::AbruptEnd)?;
         cursor.expect(Keyword::Function, "function declaration")?;
 
         // TODO: If self.is_default, then this can be empty.
@@ -108,6 +113,6 @@ impl TokenParser for FunctionDeclaration {
 
         cursor.expect(Punctuator::CloseBlock, "function declaration")?;
 
-        Ok(FunctionDecl::new(name, params, body))
+        Ok(FunctionDecl::new(name, params, body).at(start))
     }
 }
@@ -55,6 +55,7 @@ impl TokenParser for LexicalDeclaration {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> ParseResult {
         let _timer = BoaProfiler::global().start_event("LexicalDeclaration", "Parsing");
+        let _span = tracing::trace_span!("LexicalDeclaration").entered();
         let tok = cursor.next().ok_or(ParseError::AbruptEnd)?;
 
         match tok.kind {
@@ -192,8 +193,13 @@ impl TokenParser for LexicalBinding {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let ident = BindingIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?;
-        let initializer =
-            Initializer::new(self.allow_in, self.allow_yield, self.allow_await).try_parse(cursor);
+        let initializer = match cursor.peek(0) {
+            Some(token) if token.kind == TokenKind::Punctuator(Punctuator::Assign) => Some(
+                Initializer::new(self.allow_in, self.allow_yield, self.allow_await)
+                    .parse(cursor)?,
+            ),
+            _ => None,
+        };
 
         Ok((ident, initializer))
     }
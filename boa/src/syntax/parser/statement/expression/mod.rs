@@ -38,6 +38,7 @@ impl TokenParser for ExpressionStatement {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> ParseResult {
         let _timer = BoaProfiler::global().start_event("ExpressionStatement", "Parsing");
+        let _span = tracing::trace_span!("ExpressionStatement").entered();
         // TODO: lookahead
         let expr = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
 
@@ -58,6 +58,7 @@ impl TokenParser for DoWhileStatement {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("DoWhileStatement", "Parsing");
+        let _span = tracing::trace_span!("DoWhileStatement").entered();
         cursor.expect(Keyword::Do, "do while statement")?;
 
         let body =
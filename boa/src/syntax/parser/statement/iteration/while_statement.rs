@@ -49,6 +49,7 @@ impl TokenParser for WhileStatement {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("WhileStatement", "Parsing");
+        let _span = tracing::trace_span!("WhileStatement").entered();
         cursor.expect(Keyword::While, "while statement")?;
         cursor.expect(Punctuator::OpenParen, "while statement")?;
 
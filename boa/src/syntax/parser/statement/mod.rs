@@ -10,6 +10,7 @@
 mod block;
 mod break_stm;
 mod continue_stm;
+mod debugger_stm;
 mod declaration;
 mod expression;
 mod if_stm;
@@ -24,6 +25,7 @@ use self::{
     block::BlockStatement,
     break_stm::BreakStatement,
     continue_stm::ContinueStatement,
+    debugger_stm::DebuggerStatement,
     declaration::Declaration,
     expression::ExpressionStatement,
     if_stm::IfStatement,
@@ -94,6 +96,7 @@ impl TokenParser for Statement {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("Statement", "Parsing");
+        let _span = tracing::trace_span!("Statement").entered();
         // TODO: add BreakableStatement and divide Whiles, fors and so on to another place.
         let tok = cursor.peek(0).ok_or(ParseError::AbruptEnd)?;
 
@@ -157,6 +160,7 @@ impl TokenParser for Statement {
                     .parse(cursor)
                     .map(Node::from)
             }
+            TokenKind::Keyword(Keyword::Debugger) => DebuggerStatement.parse(cursor),
             TokenKind::Punctuator(Punctuator::OpenBlock) => {
                 BlockStatement::new(self.allow_yield, self.allow_await, self.allow_return)
                     .parse(cursor)
@@ -233,18 +237,24 @@ impl StatementList {
                 _ => {}
             }
 
+            let start = cursor.pos();
             let item =
                 StatementListItem::new(self.allow_yield, self.allow_await, self.allow_return)
                     .parse(cursor)?;
-            items.push(item);
+            // The span includes the semicolon that ends the statement, which is left to the list.
+            cursor.next_if(Punctuator::Semicolon);
+            let span = cursor
+                .span_since(start)
+                .expect("a statement list item consumes at least one token");
+            items.push((item, span));
 
             // move the cursor forward for any consecutive semicolon.
             while cursor.next_if(Punctuator::Semicolon).is_some() {}
         }
 
-        items.sort_by(Node::hoistable_order);
+        items.sort_by(|(a, _), (b, _)| Node::hoistable_order(a, b));
 
-        Ok(items.into())
+        Ok(node::StatementList::with_spans(items))
     }
 }
 
@@ -253,6 +263,7 @@ impl TokenParser for StatementList {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("StatementList", "Parsing");
+        let _span = tracing::trace_span!("StatementList").entered();
         let mut items = Vec::new();
 
         loop {
@@ -274,18 +285,24 @@ impl TokenParser for StatementList {
                 _ => {}
             }
 
+            let start = cursor.pos();
             let item =
                 StatementListItem::new(self.allow_yield, self.allow_await, self.allow_return)
                     .parse(cursor)?;
-            items.push(item);
+            // The span includes the semicolon that ends the statement, which is left to the list.
+            cursor.next_if(Punctuator::Semicolon);
+            let span = cursor
+                .span_since(start)
+                .expect("a statement list item consumes at least one token");
+            items.push((item, span));
 
             // move the cursor forward for any consecutive semicolon.
             while cursor.next_if(Punctuator::Semicolon).is_some() {}
         }
 
-        items.sort_by(Node::hoistable_order);
+        items.sort_by(|(a, _), (b, _)| Node::hoistable_order(a, b));
 
-        Ok(items.into())
+        Ok(node::StatementList::with_spans(items))
     }
 }
 
@@ -327,6 +344,7 @@ impl TokenParser for StatementListItem {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("StatementListItem", "Parsing");
+        let _span = tracing::trace_span!("StatementListItem").entered();
         let tok = cursor.peek(0).ok_or(ParseError::AbruptEnd)?;
 
         match tok.kind {
@@ -383,6 +401,7 @@ impl TokenParser for BindingIdentifier {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("BindingIdentifier", "Parsing");
+        let _span = tracing::trace_span!("BindingIdentifier").entered();
         // TODO: strict mode.
 
         let next_token = cursor.next().ok_or(ParseError::AbruptEnd)?;
@@ -42,6 +42,7 @@ impl TokenParser for ReturnStatement {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("ReturnStatement", "Parsing");
+        let _span = tracing::trace_span!("ReturnStatement").entered();
         cursor.expect(Keyword::Return, "return statement")?;
 
         if let (true, tok) = cursor.peek_semicolon(false) {
@@ -42,6 +42,7 @@ impl TokenParser for ThrowStatement {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("ThrowStatement", "Parsing");
+        let _span = tracing::trace_span!("ThrowStatement").entered();
         cursor.expect(Keyword::Throw, "throw statement")?;
 
         cursor.peek_expect_no_lineterminator(0)?;
@@ -48,6 +48,7 @@ impl TokenParser for Catch {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("Catch", "Parsing");
+        let _span = tracing::trace_span!("Catch").entered();
         cursor.expect(Keyword::Catch, "try statement")?;
         let catch_param = if cursor.next_if(Punctuator::OpenParen).is_some() {
             let catch_param =
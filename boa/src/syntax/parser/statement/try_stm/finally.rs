@@ -45,6 +45,7 @@ impl TokenParser for Finally {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("Finally", "Parsing");
+        let _span = tracing::trace_span!("Finally").entered();
         cursor.expect(Keyword::Finally, "try statement")?;
         Ok(
             Block::new(self.allow_yield, self.allow_await, self.allow_return)
@@ -51,6 +51,7 @@ impl TokenParser for TryStatement {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Try, ParseError> {
         let _timer = BoaProfiler::global().start_event("TryStatement", "Parsing");
+        let _span = tracing::trace_span!("TryStatement").entered();
         // TRY
         cursor.expect(Keyword::Try, "try statement")?;
 
@@ -48,6 +48,7 @@ impl TokenParser for VariableStatement {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("VariableStatement", "Parsing");
+        let _span = tracing::trace_span!("VariableStatement").entered();
         cursor.expect(Keyword::Var, "variable statement")?;
 
         let decl_list =
@@ -165,8 +166,13 @@ impl TokenParser for VariableDeclaration {
 
         let name = BindingIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?;
 
-        let ident =
-            Initializer::new(self.allow_in, self.allow_yield, self.allow_await).try_parse(cursor);
+        let ident = match cursor.peek(0) {
+            Some(token) if token.kind == TokenKind::Punctuator(Punctuator::Assign) => Some(
+                Initializer::new(self.allow_in, self.allow_yield, self.allow_await)
+                    .parse(cursor)?,
+            ),
+            _ => None,
+        };
 
         Ok(VarDecl::new(name, ident))
     }
@@ -8,7 +8,7 @@ use crate::syntax::{
             StatementList, UnaryOp, VarDecl, VarDeclList,
         },
         op::{self, NumOp},
-        Const,
+        Const, Position, Span,
     },
     lexer::Lexer,
 };
@@ -104,3 +104,49 @@ fn hoisting() {
         ],
     );
 }
+
+/// Checks that each statement spans from its first token to its last one, semicolon included.
+#[test]
+fn statement_spans() {
+    let mut lexer = Lexer::new("let a = 1;\n\nif (a) {\n  a++\n}\nfunction f() {}");
+    lexer.lex().expect("failed to lex");
+    let list = Parser::new(&lexer.tokens)
+        .parse_all()
+        .expect("failed to parse");
+
+    let span = |start: (u32, u32), end: (u32, u32)| {
+        Span::new(Position::new(start.0, start.1), Position::new(end.0, end.1))
+    };
+    // The function declaration is hoisted, along with its span.
+    assert_eq!(
+        list.spans(),
+        [
+            span((6, 1), (6, 15)),
+            span((1, 1), (1, 10)),
+            span((3, 1), (5, 1))
+        ]
+    );
+    assert_eq!(list.statements().len(), list.spans().len());
+}
+
+/// Checks that the sources that end too early are incomplete, and that the other syntax
+/// errors are not.
+#[test]
+fn incomplete_sources() {
+    use crate::is_incomplete;
+
+    assert!(is_incomplete("if (x)"));
+    assert!(is_incomplete("while (true)\n"));
+    assert!(is_incomplete("let x =\n"));
+    assert!(is_incomplete("var a = 1, b ="));
+    assert!(is_incomplete("x = 1 +"));
+    assert!(is_incomplete("a ? b :"));
+    assert!(is_incomplete("function f(a = "));
+    assert!(is_incomplete("`template"));
+    assert!(is_incomplete("'a\\\n"));
+
+    assert!(!is_incomplete("let x = 1"));
+    assert!(!is_incomplete("let x = )"));
+    assert!(!is_incomplete("'unterminated"));
+    assert!(!is_incomplete("}"));
+}
@@ -0,0 +1,100 @@
+//! This module implements the execution timeline.
+//!
+//! The timeline records coarse events of a run, like parsing and evaluating a script, with their
+//! timestamps. It can be exported in the [Chrome trace event format][format], which can be
+//! opened in `about:tracing` or [Perfetto](https://ui.perfetto.dev).
+//!
+//! Unlike `BoaProfiler`, the timeline is always compiled in, and only records events once it
+//! has been enabled with `Interpreter::enable_timeline`.
+//!
+//! Only the garbage collections requested with `Interpreter::collect_garbage` are recorded,
+//! because the `gc` crate doesn't report the ones it runs by itself.
+//!
+//! [format]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+
+#[cfg(test)]
+mod tests;
+
+use serde_json::{json, Value as JSONValue};
+use std::time::{Duration, Instant};
+
+/// A single event of the timeline.
+#[derive(Debug, Clone)]
+pub struct TimelineEvent {
+    /// The name of the event, e.g. the name of the script.
+    pub name: String,
+    /// The category of the event, e.g. `"compile"` or `"script"`.
+    pub category: &'static str,
+    /// When the event started, relative to the creation of the timeline.
+    pub start: Duration,
+    /// How long the event lasted.
+    pub duration: Duration,
+}
+
+/// The timeline of the events of a run.
+#[derive(Debug, Clone)]
+pub struct Timeline {
+    origin: Instant,
+    events: Vec<TimelineEvent>,
+}
+
+impl Timeline {
+    /// Creates a new, empty timeline, starting now.
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Records an event that started at `start` and ends now.
+    pub fn record<N>(&mut self, name: N, category: &'static str, start: Instant)
+    where
+        N: Into<String>,
+    {
+        self.events.push(TimelineEvent {
+            name: name.into(),
+            category,
+            start: start.saturating_duration_since(self.origin),
+            duration: start.elapsed(),
+        });
+    }
+
+    /// Returns the recorded events, in the order they ended.
+    #[inline]
+    pub fn events(&self) -> &[TimelineEvent] {
+        &self.events
+    }
+
+    /// Exports the timeline as a Chrome trace event JSON object.
+    ///
+    /// Every event is a complete (`"ph": "X"`) event, with timestamps in microseconds.
+    pub fn to_chrome_trace(&self) -> JSONValue {
+        let events: Vec<_> = self
+            .events
+            .iter()
+            .map(|event| {
+                json!({
+                    "name": event.name,
+                    "cat": event.category,
+                    "ph": "X",
+                    "ts": event.start.as_micros() as u64,
+                    "dur": event.duration.as_micros() as u64,
+                    "pid": 1,
+                    "tid": 1,
+                })
+            })
+            .collect();
+
+        json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        })
+    }
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
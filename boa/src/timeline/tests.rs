@@ -0,0 +1,53 @@
+use super::*;
+use crate::{exec::Interpreter, forward, realm::Realm};
+
+#[test]
+fn timeline_is_disabled_by_default() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "1 + 1");
+
+    assert!(engine.timeline().is_none());
+}
+
+#[test]
+fn timeline_records_compile_and_script_events() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.enable_timeline();
+    forward(&mut engine, "1 + 1");
+    forward(&mut engine, "2 + 2");
+
+    let timeline = engine.timeline().expect("timeline was enabled");
+    let categories: Vec<_> = timeline.events().iter().map(|e| e.category).collect();
+    assert_eq!(categories, ["compile", "script", "compile", "script"]);
+    assert!(timeline.events()[1].start <= timeline.events()[2].start);
+}
+
+#[test]
+fn chrome_trace_export() {
+    let mut timeline = Timeline::new();
+    timeline.record("script", "script", Instant::now());
+
+    let trace = timeline.to_chrome_trace();
+    let events = trace["traceEvents"].as_array().expect("traceEvents array");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["name"], "script");
+    assert_eq!(events[0]["cat"], "script");
+    assert_eq!(events[0]["ph"], "X");
+    assert!(events[0]["ts"].is_u64());
+    assert!(events[0]["dur"].is_u64());
+}
+
+#[test]
+fn timeline_records_requested_garbage_collections() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.enable_timeline();
+    forward(&mut engine, "var kept = { value: 1 };");
+    engine.collect_garbage();
+
+    let timeline = engine.timeline().expect("timeline is enabled");
+    assert_eq!(timeline.events().last().unwrap().category, "gc");
+    assert_eq!(forward(&mut engine, "kept.value"), "1");
+}
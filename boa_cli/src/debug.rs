@@ -0,0 +1,79 @@
+//! The `--debug` mode, which drops into a prompt when a script pauses.
+//!
+//! Scripts pause on `debugger` statements, and before each statement while stepping. The prompt
+//! reads its commands from the standard input and writes to the standard error, so that the
+//! output of the script stays apart.
+
+use boa::{
+    debugger::{evaluate, scope_chain, PauseEvent, PauseReason, Resume},
+    exec::Interpreter,
+    Context,
+};
+use std::io::{self, BufRead, Write};
+
+/// The commands of the prompt.
+const HELP: &str = "\
+commands:
+    c, continue    run until the next debugger statement
+    s, step        run until the next statement
+    scope          print the bindings of the scope chain
+    help           print this help
+    <expression>   evaluate an expression in the current scope
+";
+
+/// Makes the scripts of the context pause on `debugger` statements.
+pub(crate) fn attach(context: &mut Context) {
+    context
+        .interpreter_mut()
+        .debugger_mut()
+        .set_pause_handler(prompt);
+}
+
+/// Reads and runs commands until one resumes the script.
+///
+/// The script also resumes at the end of the input.
+fn prompt(engine: &mut Interpreter, event: &PauseEvent<'_>) -> Resume {
+    let _ = io::stdout().flush();
+    match event.statement {
+        Some(statement) if event.reason == PauseReason::Step => {
+            let statement = statement.to_string();
+            eprintln!("-> {}", statement.lines().next().unwrap_or_default());
+        }
+        _ => eprintln!("paused on a {}, type `help` for the commands", event.reason),
+    }
+
+    let stdin = io::stdin();
+    let mut line = String::new();
+    loop {
+        eprint!("(debug) ");
+        line.clear();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0) | Err(_) => return Resume::Continue,
+            Ok(_) => {}
+        }
+        match line.trim() {
+            "" => {}
+            "c" | "continue" => return Resume::Continue,
+            "s" | "step" => return Resume::Step,
+            "help" => eprint!("{}", HELP),
+            "scope" => print_scopes(engine),
+            expression => match evaluate(engine, expression) {
+                Ok(value) => eprintln!("{}", value),
+                Err(e) => eprintln!("{}", e),
+            },
+        }
+    }
+}
+
+/// Prints the bindings of each environment of the scope chain, the innermost one first.
+fn print_scopes(engine: &Interpreter) {
+    for (depth, scope) in scope_chain(engine).iter().enumerate() {
+        eprintln!("#{} {:?}", depth, scope.kind);
+        for (name, value) in &scope.bindings {
+            match value {
+                Some(value) => eprintln!("    {} = {}", name, value),
+                None => eprintln!("    {} = <uninitialized>", name),
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! The reports of the errors of the files and snippets, with the line of the source where the
+//! error was found.
+//!
+//! Syntax errors point at the token where they were found, and runtime errors at the start of
+//! the innermost statement that threw them, which is in another file if it is in a function
+//! defined there. If the file has a source map, the error points at the original source
+//! instead, see `source_map`.
+
+use crate::source_map;
+use boa::JsError;
+use colored::Colorize;
+use std::fmt::Write;
+
+/// Formats an error of the source named `name`, like `script.js` or `-e #1`.
+///
+/// ```text
+/// error: Parsing Error: expected token ';', got '=' in lexical declaration at line 2, col 9
+///  --> script.js:2:9
+///   |
+/// 2 |   let b = ;
+///   |         ^
+/// ```
+pub(crate) fn render(name: &str, src: &str, error: &JsError) -> String {
+    let mut out = format!("{}: {}\n", "error".red().bold(), error.to_string().bold());
+    let location = if let Some(location) = source_map::locate_error(name, src, error) {
+        location
+    } else {
+        let _ = writeln!(out, " {} {}", "-->".blue().bold(), name);
+        return out;
+    };
+    let (line, column) = (location.line, location.column);
+    let _ = writeln!(
+        out,
+        " {} {}:{}:{}",
+        "-->".blue().bold(),
+        location.name,
+        line,
+        column
+    );
+
+    let number = line.to_string();
+    let gutter = " ".repeat(number.len());
+    let bar = "|".blue().bold();
+    if let Some(text) = &location.text {
+        // The caret is indented like the text before the column, tabs included.
+        let indent: String = text
+            .chars()
+            .take(column as usize - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let _ = writeln!(out, "{} {}", gutter, bar);
+        let _ = writeln!(out, "{} {} {}", number.blue().bold(), bar, text);
+        let _ = writeln!(out, "{} {} {}{}", gutter, bar, indent, "^".red().bold());
+    }
+    if let Some(generated) = location.generated {
+        let _ = writeln!(
+            out,
+            "{} {} generated code at {}:{}:{}",
+            gutter,
+            "=".blue().bold(),
+            error.script().unwrap_or(name),
+            generated.line_number(),
+            generated.column_number()
+        );
+    }
+    out
+}
@@ -0,0 +1,148 @@
+//! The Graphviz DOT format of `--dump-ast`.
+//!
+//! The syntax tree is walked through its serde representation, so every kind of node is drawn
+//! without a dedicated visitor. Each enum variant, like `BinOp` or `Identifier`, becomes a node
+//! labelled with its name, and the fields of the variant become edges labelled with the field
+//! names. The graph can be rendered with `dot -Tpng`. The spans of the statements are left out.
+
+use boa::syntax::ast::node::StatementList;
+use serde_json::{Map, Value};
+use std::fmt::Write;
+
+/// Converts a syntax tree to a DOT graph named `AST`.
+pub(crate) fn to_dot(ast: &StatementList) -> serde_json::Result<String> {
+    let mut graph = Graph {
+        out: String::from("digraph AST {\n    node [shape=box, fontname=\"monospace\"];\n"),
+        nodes: 0,
+    };
+    graph.value("StatementList", &serde_json::to_value(ast.statements())?);
+    graph.out.push_str("}\n");
+    Ok(graph.out)
+}
+
+/// The DOT graph being written.
+struct Graph {
+    out: String,
+    /// The number of nodes written so far, which is the id of the next node.
+    nodes: usize,
+}
+
+impl Graph {
+    /// Writes a node for the value and the nodes of its children, and returns the id of the node.
+    ///
+    /// `name` labels the value if it is not an enum variant. Returns `None` for `null`, which is
+    /// a missing optional field.
+    fn value(&mut self, name: &str, value: &Value) -> Option<usize> {
+        if value.is_null() {
+            return None;
+        }
+        if let Some((variant, mut inner)) = value.as_object().and_then(variant) {
+            // Nested variants, like `Const::Int`, are drawn as a single node.
+            let mut label = String::from(variant);
+            while let Some((variant, nested)) = inner.as_object().and_then(self::variant) {
+                label = format!("{}::{}", label, variant);
+                inner = nested;
+            }
+            if is_leaf(inner) {
+                return Some(self.node(&format!("{}\n{}", label, leaf_label(inner))));
+            }
+            let id = self.node(&label);
+            self.children(id, inner);
+            return Some(id);
+        }
+        if is_leaf(value) {
+            return Some(self.node(&leaf_label(value)));
+        }
+        let id = self.node(name);
+        self.children(id, value);
+        Some(id)
+    }
+
+    /// Writes the children of the node `parent`, which holds `value`.
+    fn children(&mut self, parent: usize, value: &Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, field) in map {
+                    match without_spans(field) {
+                        Value::Array(items) => {
+                            for (index, item) in items.iter().enumerate() {
+                                let label = format!("{}[{}]", key, index);
+                                self.child(parent, &label, item);
+                            }
+                        }
+                        _ => self.child(parent, key, field),
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.child(parent, &index.to_string(), item);
+                }
+            }
+            _ => self.child(parent, "", value),
+        }
+    }
+
+    /// Writes a child of the node `parent`, and the edge to it.
+    fn child(&mut self, parent: usize, label: &str, value: &Value) {
+        if let Some(child) = self.value(label, value) {
+            let _ = writeln!(
+                self.out,
+                "    n{} -> n{} [label=\"{}\"];",
+                parent,
+                child,
+                escape(label)
+            );
+        }
+    }
+
+    /// Writes a node with the label, and returns its id.
+    fn node(&mut self, label: &str) -> usize {
+        let id = self.nodes;
+        self.nodes += 1;
+        let _ = writeln!(self.out, "    n{} [label=\"{}\"];", id, escape(label));
+        id
+    }
+}
+
+/// Gets the name and the contents of an enum variant, which serde writes as an object with a
+/// single key, starting with an uppercase letter.
+fn variant(map: &Map<String, Value>) -> Option<(&str, &Value)> {
+    let mut entries = map.iter();
+    if let (Some((name, inner)), None) = (entries.next(), entries.next()) {
+        if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return Some((name, inner));
+        }
+    }
+    None
+}
+
+/// Gets the statements of a statement list, which serde writes as an object with the statements
+/// and their spans, or the value itself if it is not a statement list.
+fn without_spans(value: &Value) -> &Value {
+    match value.as_object() {
+        Some(map) if map.len() == 2 && map.contains_key("spans") => {
+            map.get("statements").unwrap_or(value)
+        }
+        _ => value,
+    }
+}
+
+fn is_leaf(value: &Value) -> bool {
+    !matches!(value, Value::Object(_) | Value::Array(_))
+}
+
+fn leaf_label(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        _ => value.to_string(),
+    }
+}
+
+/// Escapes a label for a double-quoted DOT string.
+fn escape(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
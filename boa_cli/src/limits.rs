@@ -0,0 +1,80 @@
+//! The `--timeout` and `--max-memory` guards, which terminate the scripts that run for too long
+//! or that allocate too much memory.
+
+use boa::Context;
+use std::{
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread,
+    time::Duration,
+};
+
+/// Parses a duration like `5s`, `500ms`, `1.5m` or `2h`, in seconds if it has no unit.
+pub(crate) fn parse_duration(src: &str) -> Result<Duration, String> {
+    let (number, unit) = split_unit(src);
+    let seconds = match unit {
+        "ms" => 0.001,
+        "" | "s" => 1.0,
+        "m" => 60.0,
+        "h" => 3600.0,
+        _ => return Err(format!("unknown unit `{}`, use ms, s, m or h", unit)),
+    };
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration `{}`", src))?;
+    if !number.is_finite() || number < 0.0 {
+        return Err(format!("invalid duration `{}`", src));
+    }
+    Ok(Duration::from_secs_f64(number * seconds))
+}
+
+/// Parses a number of bytes like `128M`, `1.5G` or `512KiB`, in bytes if it has no unit.
+///
+/// The units are powers of 1024.
+pub(crate) fn parse_size(src: &str) -> Result<usize, String> {
+    let (number, unit) = split_unit(src);
+    let unit = unit.trim_end_matches("iB").trim_end_matches('B');
+    let bytes = match unit.to_ascii_uppercase().as_str() {
+        "" => 1.0,
+        "K" => 1024.0,
+        "M" => 1024.0 * 1024.0,
+        "G" => 1024.0 * 1024.0 * 1024.0,
+        _ => return Err(format!("unknown unit `{}`, use K, M or G", unit)),
+    };
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid size `{}`", src))?;
+    if !number.is_finite() || number < 0.0 {
+        return Err(format!("invalid size `{}`", src));
+    }
+    Ok((number * bytes) as usize)
+}
+
+/// Splits a number from the unit that follows it.
+fn split_unit(src: &str) -> (&str, &str) {
+    let src = src.trim();
+    let end = src
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(src.len());
+    (&src[..end], src[end..].trim_start())
+}
+
+/// Interrupts the scripts of a context once a timeout elapses, unless it is dropped before.
+#[derive(Debug)]
+pub(crate) struct Deadline {
+    /// Dropping the sender wakes the timer thread up before the timeout.
+    _cancel: Sender<()>,
+}
+
+impl Deadline {
+    /// Starts the timer of the scripts of the context.
+    pub(crate) fn start(context: &Context, timeout: Duration) -> Self {
+        let handle = context.interrupt_handle();
+        let (cancel, cancelled) = mpsc::channel();
+        thread::spawn(move || {
+            if let Err(RecvTimeoutError::Timeout) = cancelled.recv_timeout(timeout) {
+                handle.interrupt();
+            }
+        });
+        Self { _cancel: cancel }
+    }
+}
@@ -25,59 +25,122 @@
     clippy::as_conversions
 )]
 
+mod debug;
+mod diagnostic;
+mod dot;
+mod limits;
+mod offsets;
+mod output;
+mod plugin;
+mod require;
+mod source_map;
+mod watch;
+
+use crate::{
+    limits::{parse_duration, parse_size, Deadline},
+    watch::Watcher,
+};
 use boa::{
-    exec::Interpreter,
-    forward_val,
-    realm::Realm,
-    syntax::ast::{node::StatementList, token::Token},
+    builtins::{console::WriteConsoleWriter, value::Value},
+    experimental::Experimental,
+    forward_parsed, forward_val,
+    heap_snapshot::{HeapSnapshot, HeapStats},
+    memory::CountingAllocator,
+    parse,
+    profiler::BoaProfiler,
+    snapshot::RealmSnapshot,
+    syntax::{
+        ast::{node::StatementList, token::Token},
+        codegen::Style,
+    },
+    Context, JsError, ParseOptions,
 };
-use colored::*;
-use lazy_static::lazy_static;
-use regex::{Captures, Regex};
-use rustyline::{
-    config::Config,
-    error::ReadlineError,
-    highlight::Highlighter,
-    validate::{MatchingBracketValidator, ValidationContext, ValidationResult, Validator},
-    EditMode, Editor,
+use boa_repl::Repl;
+use std::{
+    env,
+    fs::{create_dir_all, read, read_to_string, write},
+    future::Future,
+    io::{self, IsTerminal, Read, Write},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    task::{Context as TaskContext, Poll, Wake},
+    thread::{self, Thread},
+    time::Duration,
 };
-use rustyline_derive::{Completer, Helper, Hinter};
-use std::borrow::Cow;
-use std::collections::HashSet;
-use std::{fs::read_to_string, path::PathBuf};
 use structopt::{clap::arg_enum, StructOpt};
+use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter};
 
+// The allocations are counted to enforce `--max-memory`, once it is given.
 #[cfg(all(target_arch = "x86_64", target_os = "linux", target_env = "gnu"))]
 #[cfg_attr(
     all(target_arch = "x86_64", target_os = "linux", target_env = "gnu"),
     global_allocator
 )]
-static ALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
+static ALLOC: CountingAllocator<jemallocator::Jemalloc> =
+    CountingAllocator::new(jemallocator::Jemalloc);
 
-/// CLI configuration for Boa.
+#[cfg(not(all(target_arch = "x86_64", target_os = "linux", target_env = "gnu")))]
+#[global_allocator]
+static ALLOC: CountingAllocator<std::alloc::System> = CountingAllocator::new(std::alloc::System);
+
+/// The history file of the REPL, in the current directory, when no data directory is known.
 static CLI_HISTORY: &str = ".boa_history";
 
-// Added #[allow(clippy::option_option)] because to StructOpt an Option<Option<T>>
+/// The file name that stands for the standard input.
+static STDIN: &str = "-";
+
+/// The exit code when a script throws an uncaught error.
+const RUNTIME_ERROR: i32 = 1;
+
+/// The exit code when a script has a syntax error.
+const SYNTAX_ERROR: i32 = 2;
+
+/// The exit code when a script is terminated by `--timeout` or `--max-memory`.
+const TERMINATED: i32 = 3;
+
+/// The exit code when a file cannot be read or written.
+const IO_ERROR: i32 = 4;
+
 // is an optional argument that optionally takes a value ([--opt=[val]]).
 // https://docs.rs/structopt/0.3.11/structopt/#type-magic
-#[allow(clippy::option_option)]
-#[derive(Debug, StructOpt)]
-#[structopt(author, about, name = "boa")]
+#[derive(Debug, Clone, StructOpt)]
+#[structopt(
+    author,
+    about,
+    name = "boa",
+    after_help = "EXIT STATUS:\n    0    The scripts ran without errors.\n    1    A script threw an uncaught error.\n    2    A script has a syntax error.\n    3    A script ran out of time or memory.\n    4    A file could not be read or written."
+)]
 struct Opt {
-    /// The JavaScript file(s) to be evaluated.
+    /// The JavaScript file(s) to be evaluated, or `-` to read the standard input. The standard
+    /// input is also read when it is not a terminal and no file or code is given.
     #[structopt(name = "FILE", parse(from_os_str))]
     files: Vec<PathBuf>,
 
+    /// The arguments given to the scripts, after `--`. The scripts read them from the global
+    /// `scriptArgs` array, which starts with the path of the first file, or `boa` without one.
+    #[structopt(name = "ARGS", last = true)]
+    script_args: Vec<String>,
+
+    /// Evaluate the given code and print its completion value, before running the files. Can be
+    /// repeated, and the snippets are evaluated in order.
+    #[structopt(long, short = "e", value_name = "CODE", number_of_values = 1)]
+    eval: Vec<String>,
+
     /// Dump the token stream to stdout with the given format.
     #[structopt(
         long,
         short = "t",
         value_name = "FORMAT",
-        possible_values = &DumpFormat::variants(),
+        possible_values = &TokenFormat::variants(),
         case_insensitive = true,
         conflicts_with = "dump-ast"
     )]
-    dump_tokens: Option<Option<DumpFormat>>,
+    dump_tokens: Option<Option<TokenFormat>>,
 
     /// Dump the ast to stdout with the given format.
     #[structopt(
@@ -89,12 +152,222 @@ struct Opt {
     )]
     dump_ast: Option<Option<DumpFormat>>,
 
+    /// Run the files again in a new realm each time one of them changes, until interrupted.
+    #[structopt(long, requires = "FILE")]
+    watch: bool,
+
+    /// The history file of the REPL. Defaults to `$XDG_DATA_HOME/boa/history`, or to
+    /// `~/.local/share/boa/history`.
+    #[structopt(long, value_name = "FILE", env = "BOA_HISTORY", parse(from_os_str))]
+    history: Option<PathBuf>,
+
+    /// The number of lines that the history of the REPL keeps.
+    #[structopt(
+        long,
+        value_name = "LINES",
+        env = "BOA_HISTORY_SIZE",
+        default_value = "1000"
+    )]
+    history_size: usize,
+
     /// Use vi mode in the REPL
     #[structopt(long = "vi")]
     vi_mode: bool,
+
+    /// Fold the operations on literals and remove the branches that can never run before running
+    /// the scripts, or before dumping their ast.
+    #[structopt(long)]
+    optimize: bool,
+
+    /// Only parse the body of each function when the function is first called. A syntax error
+    /// in a body then stops the script when the function is first called, with the exit code of
+    /// syntax errors.
+    #[structopt(long)]
+    lazy_parsing: bool,
+
+    /// Record a timeline of the run and write it to the given file, as Chrome trace event JSON.
+    #[structopt(long, value_name = "FILE", parse(from_os_str))]
+    trace_timeline: Option<PathBuf>,
+
+    /// Count and time the evaluation of each kind of syntax tree node, and print a table of the
+    /// slowest ones after the run. The scripts are then run by walking their syntax tree.
+    #[structopt(long)]
+    profile: bool,
+
+    /// Record how many times each statement, each arm of the `if` statements and each function
+    /// of the files and snippets run, and write the coverage to the given file after the run.
+    /// The scripts are then run by walking their syntax tree.
+    #[structopt(long, value_name = "FILE", parse(from_os_str))]
+    coverage: Option<PathBuf>,
+
+    /// The format of the coverage written by `--coverage`.
+    #[structopt(
+        long,
+        value_name = "FORMAT",
+        possible_values = &CoverageFormat::variants(),
+        case_insensitive = true,
+        default_value = "lcov"
+    )]
+    coverage_format: CoverageFormat,
+
+    /// Record when each function call starts and ends, and write the CPU profile to the given
+    /// file after the run, to see where the scripts spend their time as a flame graph.
+    #[structopt(long, value_name = "FILE", parse(from_os_str))]
+    cpu_prof: Option<PathBuf>,
+
+    /// The format of the CPU profile written by `--cpu-prof`.
+    #[structopt(
+        long,
+        value_name = "FORMAT",
+        possible_values = &CpuProfileFormat::variants(),
+        case_insensitive = true,
+        default_value = "speedscope"
+    )]
+    cpu_prof_format: CpuProfileFormat,
+
+    /// Print the number and the estimated size of the objects of each kind, and of the strings,
+    /// that are still reachable after the run.
+    #[structopt(long)]
+    heap_stats: bool,
+
+    /// Write a snapshot of the objects that are still reachable after the run to the given
+    /// file, in the format of the Chrome developer tools if its name ends with
+    /// ".heapsnapshot", and as JSON otherwise.
+    #[structopt(long, value_name = "FILE", parse(from_os_str))]
+    heap_snapshot: Option<PathBuf>,
+
+    /// Print the time spent lexing, parsing and running each file and snippet, or each line of
+    /// the REPL. The files are then parsed one after another.
+    #[structopt(long)]
+    timing: bool,
+
+    /// Print the tracing spans and events of the engine to the standard error, filtered by a
+    /// directive like "boa=trace" or "boa::syntax::parser=trace,boa=debug", "boa=debug" by
+    /// default. Without this flag, they are printed if the RUST_LOG environment variable is set,
+    /// and filtered by it.
+    #[structopt(long, value_name = "FILTER")]
+    trace: Option<Option<String>>,
+
+    /// Enforce two rules of strict mode in every file, snippet and line of the REPL: legacy
+    /// octal literals, like `010`, and decimals with a leading zero, like `08`, are syntax
+    /// errors, and assigning to an undeclared variable throws a ReferenceError. The other rules
+    /// of strict mode are not enforced.
+    #[structopt(long)]
+    strict: bool,
+
+    /// Terminate the scripts if they have not finished after a duration, like `5s`, `500ms` or
+    /// `2m`. The files and snippets that follow are not run.
+    #[structopt(long, value_name = "DURATION", parse(try_from_str = parse_duration))]
+    timeout: Option<Duration>,
+
+    /// Terminate a script if it allocates more than a number of bytes, like `128M` or `1G`. The
+    /// files and snippets that follow are not run.
+    #[structopt(long, value_name = "SIZE", parse(try_from_str = parse_size))]
+    max_memory: Option<usize>,
+
+    /// Make the runs reproducible: seed `Math.random` with a number, and pin the clock of the
+    /// scripts, which `console.time` measures durations with, to the time given by `--clock`.
+    #[structopt(long, value_name = "N")]
+    seed: Option<u64>,
+
+    /// Pin the clock of the scripts to a time, in milliseconds since the Unix epoch. With
+    /// `--seed`, the clock is pinned to 0 by default.
+    #[structopt(long, value_name = "MS")]
+    clock: Option<u64>,
+
+    /// Enable experimental language features, given as a list of names separated by commas, or
+    /// `all`: nullish-coalescing, string-replace-all.
+    #[structopt(long, value_name = "FEATURES")]
+    experimental: Option<Experimental>,
+
+    /// Disable the web legacy compatibility features of Annex B, like `escape`,
+    /// `String.prototype.substr`, `__proto__` and HTML-like comments.
+    #[structopt(long)]
+    no_annex_b: bool,
+
+    /// Pause on `debugger` statements, with a prompt that steps through the statements,
+    /// continues, and evaluates expressions or prints the scope chain where the script paused.
+    /// The scripts are then run by walking their syntax tree.
+    #[structopt(long)]
+    debug: bool,
+
+    /// Print the completion value of each file and snippet, or its error, with the given format.
+    /// The json format prints a line of JSON for each of them, with the type and the value or
+    /// with the name, the message and the position of the error, and the messages of the
+    /// `console` object go to the standard error.
+    #[structopt(
+        long,
+        value_name = "FORMAT",
+        possible_values = &OutputFormat::variants(),
+        case_insensitive = true,
+        default_value = "text"
+    )]
+    output: OutputFormat,
+
+    /// Only check the syntax of the files and snippets, and report all their syntax errors
+    /// without running them.
+    #[structopt(long, conflicts_with_all = &["dump-tokens", "dump-ast"])]
+    check: bool,
+
+    /// Load a plugin, a dynamic library that registers native globals. Can be repeated.
+    #[structopt(
+        long = "plugin",
+        value_name = "LIBRARY",
+        parse(from_os_str),
+        number_of_values = 1
+    )]
+    plugins: Vec<PathBuf>,
+
+    /// Restore the globals saved in a realm snapshot before running the files.
+    #[structopt(long, value_name = "FILE", parse(from_os_str))]
+    restore: Option<PathBuf>,
+
+    /// Save the globals defined by the files to a realm snapshot, which `--restore` loads.
+    #[structopt(long, value_name = "FILE", parse(from_os_str))]
+    snapshot: Option<PathBuf>,
+
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+/// The commands that work on the source code of the files instead of running them.
+#[derive(Debug, Clone, StructOpt)]
+enum Command {
+    /// Reformat the files and snippets with consistent indentation and spacing, keeping their
+    /// comments, and print them without running them.
+    Fmt(FmtOpt),
+}
+
+/// The arguments of `boa fmt`.
+#[derive(Debug, Clone, StructOpt)]
+struct FmtOpt {
+    /// The JavaScript file(s) to format, or `-` to read the standard input. The standard input
+    /// is also read when it is not a terminal and no file or code is given.
+    #[structopt(name = "FILE", parse(from_os_str))]
+    files: Vec<PathBuf>,
+
+    /// Format the given code. Can be repeated, and the snippets are formatted before the files.
+    #[structopt(long, short = "e", value_name = "CODE", number_of_values = 1)]
+    eval: Vec<String>,
+
+    /// Write the formatted code back to the files instead of printing it. The snippets and the
+    /// standard input are still printed.
+    #[structopt(long)]
+    write: bool,
 }
 
 impl Opt {
+    /// Returns the options that the files and snippets are parsed with, the same as the context
+    /// runs them with.
+    fn parse_options(&self, lazy_functions: bool) -> ParseOptions {
+        ParseOptions {
+            lazy_functions,
+            strict: self.strict,
+            experimental: self.experimental.unwrap_or_default(),
+            annex_b: !self.no_annex_b,
+        }
+    }
+
     /// Returns whether a dump flag has been used.
     fn has_dump_flag(&self) -> bool {
         self.dump_tokens.is_some() || self.dump_ast.is_some()
@@ -102,7 +375,22 @@ impl Opt {
 }
 
 arg_enum! {
-    /// The different types of format available for dumping.
+    /// The formats of the token stream dump.
+    #[derive(Debug, Clone, Copy)]
+    enum TokenFormat {
+        // This is the default format that you get from std::fmt::Debug.
+        Debug,
+
+        // This is a minified json format.
+        Json,
+
+        // This is a pretty printed json format.
+        JsonPretty,
+    }
+}
+
+arg_enum! {
+    /// The different types of format available for dumping the ast.
     ///
     // NOTE: This can easily support other formats just by
     // adding a field to this enum and adding the necessary
@@ -110,7 +398,7 @@ arg_enum! {
     //
     // NOTE: The fields of this enum are not doc comments because
     // arg_enum! macro does not support it.
-    #[derive(Debug)]
+    #[derive(Debug, Clone, Copy)]
     enum DumpFormat {
         // This is the default format that you get from std::fmt::Debug.
         Debug,
@@ -120,6 +408,51 @@ arg_enum! {
 
         // This is a pretty printed json format.
         JsonPretty,
+
+        // This is a Graphviz graph of the ast, which `dot -Tpng` can render.
+        Dot,
+
+        // This is the JavaScript source code of the ast, minified.
+        Js,
+
+        // This is the JavaScript source code of the ast, indented.
+        JsPretty,
+    }
+}
+
+arg_enum! {
+    /// The formats of the results of the files and snippets.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum OutputFormat {
+        // The completion values are printed as they are, and the errors with their source.
+        Text,
+
+        // Each result is a line of JSON, see the `output` module.
+        Json,
+    }
+}
+
+arg_enum! {
+    /// The formats of the code coverage.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum CoverageFormat {
+        // The lcov tracefile format, that most coverage tools read.
+        Lcov,
+
+        // A JSON object with the statements, branches and functions of each file.
+        Json,
+    }
+}
+
+arg_enum! {
+    /// The formats of the CPU profile.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum CpuProfileFormat {
+        // The evented JSON format of speedscope.
+        Speedscope,
+
+        // A line for each stack of calls, that flame graph tools read.
+        Folded,
     }
 }
 
@@ -127,10 +460,13 @@ arg_enum! {
 ///
 /// Returns a error of type String with a message,
 /// if the source has a syntax error.
-fn lex_source(src: &str) -> Result<Vec<Token>, String> {
+fn lex_source(src: &str, options: ParseOptions) -> Result<Vec<Token>, String> {
     use boa::syntax::lexer::Lexer;
 
-    let mut lexer = Lexer::new(src);
+    let mut lexer = Lexer::new(src)
+        .strict(options.strict)
+        .experimental(options.experimental)
+        .html_comments(options.annex_b);
     lexer.lex().map_err(|e| format!("SyntaxError: {}", e))?;
     Ok(lexer.tokens)
 }
@@ -147,35 +483,60 @@ fn parse_tokens(tokens: Vec<Token>) -> Result<StatementList, String> {
         .map_err(|e| format!("ParsingError: {}", e))
 }
 
+/// Serializes a dump to JSON, with the byte offset of each position in the source.
+fn to_json(src: &str, mut json: serde_json::Value, pretty: bool) -> String {
+    offsets::add_offsets(src, &mut json);
+    if pretty {
+        serde_json::to_string_pretty(&json).unwrap()
+    } else {
+        json.to_string()
+    }
+}
+
 /// Dumps the token stream or ast to stdout depending on the given arguments.
 ///
 /// Returns a error of type String with a error message,
 /// if the source has a syntax or parsing error.
 fn dump(src: &str, args: &Opt) -> Result<(), String> {
-    let tokens = lex_source(src)?;
+    // The source is lexed and parsed with the same options as when it is run.
+    let tokens = lex_source(src, args.parse_options(false))?;
 
     if let Some(ref arg) = args.dump_tokens {
         match arg {
             Some(format) => match format {
-                DumpFormat::Debug => println!("{:#?}", tokens),
-                DumpFormat::Json => println!("{}", serde_json::to_string(&tokens).unwrap()),
-                DumpFormat::JsonPretty => {
-                    println!("{}", serde_json::to_string_pretty(&tokens).unwrap())
-                }
+                TokenFormat::Debug => println!("{:#?}", tokens),
+                TokenFormat::Json => println!(
+                    "{}",
+                    to_json(src, serde_json::to_value(&tokens).unwrap(), false)
+                ),
+                TokenFormat::JsonPretty => println!(
+                    "{}",
+                    to_json(src, serde_json::to_value(&tokens).unwrap(), true)
+                ),
             },
             // Default token stream dumping format.
             None => println!("{:#?}", tokens),
         }
     } else if let Some(ref arg) = args.dump_ast {
-        let ast = parse_tokens(tokens)?;
+        let mut ast = parse_tokens(tokens)?;
+        if args.optimize {
+            ast = boa::syntax::optimizer::optimize(&ast);
+        }
 
         match arg {
             Some(format) => match format {
                 DumpFormat::Debug => println!("{:#?}", ast),
-                DumpFormat::Json => println!("{}", serde_json::to_string(&ast).unwrap()),
-                DumpFormat::JsonPretty => {
-                    println!("{}", serde_json::to_string_pretty(&ast).unwrap())
-                }
+                DumpFormat::Json => println!(
+                    "{}",
+                    to_json(src, serde_json::to_value(&ast).unwrap(), false)
+                ),
+                DumpFormat::JsonPretty => println!(
+                    "{}",
+                    to_json(src, serde_json::to_value(&ast).unwrap(), true)
+                ),
+                DumpFormat::Dot => print!("{}", dot::to_dot(&ast).unwrap()),
+                DumpFormat::Js => println!("{}", ast.to_js_string(Style::Compact)),
+                DumpFormat::JsPretty => println!("{}", ast.to_js_string(Style::Pretty)),
             },
             // Default ast dumping format.
             None => println!("{:#?}", ast),
@@ -185,191 +546,560 @@ fn dump(src: &str, args: &Opt) -> Result<(), String> {
     Ok(())
 }
 
-pub fn main() -> Result<(), std::io::Error> {
-    let args = Opt::from_args();
+/// Finds the history file of the REPL, see `Opt::history`, and creates its directory.
+///
+/// Falls back to `.boa_history` in the current directory if the data directory is unknown.
+fn history_file(args: &Opt) -> PathBuf {
+    if let Some(path) = &args.history {
+        return path.clone();
+    }
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")));
+    match data_home {
+        Some(data_home) => {
+            let dir = data_home.join("boa");
+            match create_dir_all(&dir) {
+                Ok(()) => dir.join("history"),
+                Err(_) => PathBuf::from(CLI_HISTORY),
+            }
+        }
+        None => PathBuf::from(CLI_HISTORY),
+    }
+}
 
-    let realm = Realm::create();
+/// Reads the source code of a file, or of the standard input if the file is `-`.
+fn read_source(file: &Path) -> io::Result<String> {
+    if file == Path::new(STDIN) {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        Ok(buffer)
+    } else {
+        read_to_string(file)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", file.display(), e)))
+    }
+}
 
-    let mut engine = Interpreter::new(realm);
+/// Reads and parses the files on a pool of threads, and passes each file, its source and its
+/// statements, or its syntax error, to `run` in the order of the files, until `run` returns
+/// `false`.
+///
+/// The files are run on the current thread as soon as they and the files before them are
+/// parsed, while the next ones are still being parsed.
+fn parse_files<F>(files: &[PathBuf], options: ParseOptions, mut run: F) -> io::Result<()>
+where
+    F: FnMut(&Path, &str, Result<StatementList, JsError>) -> bool,
+{
+    let threads = thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(files.len());
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
 
-    for file in &args.files {
-        let buffer = read_to_string(file)?;
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let (next, sender) = (&next, sender.clone());
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let file = match files.get(index) {
+                    Some(file) => file,
+                    None => break,
+                };
+                let parsed = read_source(file).map(|src| {
+                    // `JsError` cannot be sent, but syntax errors hold no value.
+                    let statements = parse(&src, options).map_err(|e| match e {
+                        JsError::Syntax {
+                            message, position, ..
+                        } => (message, position),
+                        e => (e.to_string(), None),
+                    });
+                    (src, statements)
+                });
+                if sender.send((index, parsed)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
 
-        if args.has_dump_flag() {
-            if let Err(e) = dump(&buffer, &args) {
-                eprintln!("{}", e);
+        let mut parsed: Vec<_> = files.iter().map(|_| None).collect();
+        for index in 0..files.len() {
+            while parsed[index].is_none() {
+                let (received, result) = receiver.recv().expect("a parsing thread panicked");
+                parsed[received] = Some(result);
             }
-        } else {
-            match forward_val(&mut engine, &buffer) {
-                Ok(v) => print!("{}", v.to_string()),
-                Err(v) => eprint!("{}", v.to_string()),
+            if let Some(result) = parsed[index].take() {
+                let (src, statements) = result?;
+                let statements = statements.map_err(|(message, position)| JsError::Syntax {
+                    message,
+                    position,
+                    script: None,
+                });
+                if !run(&files[index], &src, statements) {
+                    break;
+                }
             }
         }
+        Ok(())
+    })
+}
+
+/// Names the scripts that run next after the file or snippet `name`, and registers the source
+/// map of its source `src`, if it has one, so that their stack traces show the original sources.
+fn name_script(context: &mut Context, name: &str, src: &str) {
+    let interpreter = context.interpreter_mut();
+    interpreter.set_script_name(name);
+    if let Some(map) = source_map::load(name, src) {
+        interpreter.set_source_map(name, map);
     }
+}
 
-    if args.files.is_empty() {
-        let config = Config::builder()
-            .keyseq_timeout(1)
-            .edit_mode(if args.vi_mode {
-                EditMode::Vi
+/// Prints the result of a file named `name`, or its error, with the given format,
+/// and settles the promises of the asynchronous host functions it called.
+///
+/// Returns the exit code of the file: `SYNTAX_ERROR` if it has a syntax error, `TERMINATED` if it
+/// ran out of time or memory, `RUNTIME_ERROR` if it or one of the promises threw an uncaught
+/// error, and `0` otherwise.
+fn report(
+    context: &mut Context,
+    name: &str,
+    src: &str,
+    result: Result<Value, JsError>,
+    format: OutputFormat,
+) -> i32 {
+    let mut status = 0;
+    match result {
+        Ok(v) if format == OutputFormat::Json => println!("{}", output::result(name, &v)),
+        Ok(v) => println!("{}", v.to_string()),
+        Err(e) => {
+            if format == OutputFormat::Json {
+                println!("{}", output::error(name, src, &e));
+            } else if let JsError::Interrupted = e {
+                // The only interruptions come from `--timeout`.
+                eprintln!("script timed out");
             } else {
-                EditMode::Emacs
-            })
-            .build();
+                // The results of the previous files come first.
+                let _ = io::stdout().flush();
+                eprint!("{}", diagnostic::render(name, src, &e));
+            }
+            match e {
+                JsError::Syntax { .. } => return SYNTAX_ERROR,
+                JsError::Interrupted | JsError::MemoryLimitExceeded => return TERMINATED,
+                _ => status = RUNTIME_ERROR,
+            }
+        }
+    }
+    if let Err(e) = block_on(context.run_event_loop()) {
+        if format == OutputFormat::Json {
+            let e = JsError::from_thrown(e, context.interpreter());
+            println!("{}", output::error(name, src, &e));
+        } else {
+            eprint!("{}", e);
+        }
+        status = RUNTIME_ERROR;
+    }
+    status
+}
 
-        let mut editor = Editor::with_config(config);
-        let _ = editor.load_history(CLI_HISTORY);
-        editor.set_helper(Some(RLHelper {
-            highlighter: LineHighlighter,
-            validator: MatchingBracketValidator::new(),
-        }));
+/// Checks if the files and snippets that follow a script with the exit code `status` are run.
+fn is_fatal(status: i32) -> bool {
+    status == SYNTAX_ERROR || status == TERMINATED
+}
 
-        let readline = ">> ".cyan().bold().to_string();
+/// Prints the time spent in each phase of a file or snippet, if `--timing` is used.
+fn report_timings(context: &mut Context, name: &str) {
+    if let Some(timings) = context.interpreter_mut().take_timings() {
+        // The result comes first.
+        let _ = io::stdout().flush();
+        eprintln!("{}: {}", name, timings);
+    }
+}
 
-        loop {
-            match editor.readline(&readline) {
-                Ok(line) if line == ".exit" => break,
-                Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
-
-                Ok(line) => {
-                    editor.add_history_entry(&line);
-
-                    if args.has_dump_flag() {
-                        if let Err(e) = dump(&line, &args) {
-                            eprintln!("{}", e);
-                        }
-                    } else {
-                        match forward_val(&mut engine, line.trim_end()) {
-                            Ok(v) => println!("{}", v),
-                            Err(v) => eprintln!("{}: {}", "Uncaught".red(), v.to_string().red()),
-                        }
-                    }
-                }
+/// Wakes the main thread, which parks while the futures of the host are pending.
+#[derive(Debug)]
+struct ThreadWaker(Thread);
 
-                Err(err) => {
-                    eprintln!("Unknown error: {:?}", err);
-                    break;
-                }
-            }
-        }
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
 
-        editor.save_history(CLI_HISTORY).unwrap();
+/// Runs a future to completion on the current thread.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut cx = TaskContext::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        match Pin::as_mut(&mut future).poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
     }
+}
 
-    Ok(())
+/// Prints the tracing spans and events of the engine to the standard error, filtered by the
+/// `--trace` flag, or by the `RUST_LOG` environment variable without it.
+fn init_tracing(trace: Option<&Option<String>>) {
+    let filter = if let Some(filter) = trace {
+        EnvFilter::new(filter.as_deref().unwrap_or("boa=debug"))
+    } else if let Ok(filter) = EnvFilter::try_from_default_env() {
+        filter
+    } else {
+        return;
+    };
+    // Most of the instrumentation of the engine are spans, which are printed when they end,
+    // with the time spent in them.
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(io::stderr)
+        .init();
 }
 
-#[derive(Completer, Helper, Hinter)]
-struct RLHelper {
-    highlighter: LineHighlighter,
-    validator: MatchingBracketValidator,
+/// Colours the diagnostics if the standard error is a terminal.
+///
+/// `colored` checks the standard output instead, which is often redirected while the errors are
+/// still shown on the terminal. `NO_COLOR`, `CLICOLOR_FORCE` and `CLICOLOR=0` are still honoured.
+fn init_colors() {
+    if env::var_os("NO_COLOR").is_some() || env::var_os("CLICOLOR_FORCE").is_some() {
+        return;
+    }
+    let disabled = env::var("CLICOLOR").is_ok_and(|clicolor| clicolor == "0");
+    colored::control::set_override(!disabled && io::stderr().is_terminal());
 }
 
-impl Validator for RLHelper {
-    fn validate(&self, ctx: &mut ValidationContext<'_>) -> Result<ValidationResult, ReadlineError> {
-        self.validator.validate(ctx)
+/// Defines the globals of the CLI, loads the plugins and restores the snapshot, in a new realm.
+///
+/// Exits the process if a plugin or the snapshot cannot be loaded.
+fn setup(context: &mut Context, args: &Opt) -> io::Result<()> {
+    let script = args
+        .files
+        .first()
+        .map_or_else(|| String::from("boa"), |file| file.display().to_string());
+    let script_args: Vec<_> = std::iter::once(script)
+        .chain(args.script_args.iter().cloned())
+        .collect();
+    if let Ok(script_args) = context.to_value(&script_args) {
+        context.global_object().set_field("scriptArgs", script_args);
     }
+    require::install(context.interpreter_mut());
 
-    fn validate_while_typing(&self) -> bool {
-        self.validator.validate_while_typing()
+    // The generator is seeded again before each run with `--watch`, so that every run is the
+    // same.
+    let interpreter = context.interpreter_mut();
+    interpreter.set_random_seed(args.seed);
+    interpreter.set_clock(args.clock.or_else(|| args.seed.map(|_| 0)));
+
+    for path in &args.plugins {
+        if let Err(e) = plugin::load(context.interpreter_mut(), path) {
+            eprintln!("Could not load plugin {}", e);
+            std::process::exit(1);
+        }
     }
+
+    if let Some(path) = &args.restore {
+        let restored = RealmSnapshot::from_bytes(&read(path)?)
+            .and_then(|snapshot| snapshot.restore(context.interpreter_mut()));
+        if let Err(e) = restored {
+            eprintln!("Could not restore {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+    }
+    Ok(())
 }
 
-impl Highlighter for RLHelper {
-    fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
-        hint.into()
+/// Parses the snippets and the files, and reports their syntax errors.
+///
+/// Returns `SYNTAX_ERROR` if one of them has a syntax error, and `0` otherwise.
+fn check(args: &Opt) -> io::Result<i32> {
+    let mut status = 0;
+    let mut report = |name: &str, src: &str, parsed: Result<StatementList, JsError>| {
+        if let Err(e) = parsed {
+            if args.output == OutputFormat::Json {
+                println!("{}", output::error(name, src, &e));
+            } else {
+                eprint!("{}", diagnostic::render(name, src, &e));
+            }
+            status = SYNTAX_ERROR;
+        }
+    };
+    for (index, code) in args.eval.iter().enumerate() {
+        let name = format!("-e #{}", index + 1);
+        report(&name, code, parse(code, args.parse_options(false)));
+    }
+    // Function bodies are parsed eagerly, so that their syntax errors are reported too.
+    parse_files(
+        &args.files,
+        args.parse_options(false),
+        |file, src, parsed| {
+            report(&file.display().to_string(), src, parsed);
+            true
+        },
+    )?;
+    Ok(status)
+}
+
+/// Formats the snippets and the files, see `Command::Fmt`, and reports their syntax errors.
+///
+/// The options given before the command, like `--strict` and `--output`, apply to it too.
+/// Returns `SYNTAX_ERROR` if one of them has a syntax error, and `0` otherwise.
+fn fmt(args: &Opt, fmt_args: &FmtOpt) -> io::Result<i32> {
+    let mut status = 0;
+    let mut report = |name: &str, src: &str, error: &JsError| {
+        if args.output == OutputFormat::Json {
+            println!("{}", output::error(name, src, error));
+        } else {
+            eprint!("{}", diagnostic::render(name, src, error));
+        }
+        status = SYNTAX_ERROR;
+    };
+    for (index, code) in fmt_args.eval.iter().enumerate() {
+        match boa::format(code, args.strict) {
+            Ok(formatted) => print!("{}", formatted),
+            Err(e) => report(&format!("-e #{}", index + 1), code, &e),
+        }
+    }
+    for file in &fmt_args.files {
+        let src = read_source(file)?;
+        match boa::format(&src, args.strict) {
+            // Files that are already formatted are left untouched.
+            Ok(formatted) if fmt_args.write && file != Path::new(STDIN) => {
+                if formatted != src {
+                    write(file, formatted)?;
+                }
+            }
+            Ok(formatted) => print!("{}", formatted),
+            Err(e) => report(&file.display().to_string(), &src, &e),
+        }
     }
+    Ok(status)
+}
 
-    fn highlight<'l>(&self, line: &'l str, pos: usize) -> Cow<'l, str> {
-        self.highlighter.highlight(line, pos)
+/// Dumps or runs the snippets and the files, and returns the exit code of the process.
+///
+/// The snippets and the files after the first one with a syntax error, or that ran out of time
+/// or memory, are not run.
+fn run(context: &mut Context, args: &Opt) -> io::Result<i32> {
+    let _deadline = args
+        .timeout
+        .map(|timeout| Deadline::start(context, timeout));
+    let mut status = 0;
+    if args.check {
+        return check(args);
     }
+    if args.has_dump_flag() {
+        for code in &args.eval {
+            if let Err(e) = dump(code, args) {
+                eprintln!("{}", e);
+                status = SYNTAX_ERROR;
+            }
+        }
+        for file in &args.files {
+            let buffer = read_source(file)?;
+            if let Err(e) = dump(&buffer, args) {
+                eprintln!("{}", e);
+                status = SYNTAX_ERROR;
+            }
+        }
+    } else {
+        for (index, code) in args.eval.iter().enumerate() {
+            let name = format!("-e #{}", index + 1);
+            name_script(context, &name, code);
+            let result = forward_val(context.interpreter_mut(), code);
+            status = report(context, &name, code, result, args.output).max(status);
+            report_timings(context, &name);
+            if is_fatal(status) {
+                return Ok(status);
+            }
+        }
 
-    fn highlight_candidate<'c>(
-        &self,
-        candidate: &'c str,
-        _completion: rustyline::CompletionType,
-    ) -> Cow<'c, str> {
-        self.highlighter.highlight(candidate, 0)
+        if args.files.len() > 1 && !args.timing {
+            parse_files(
+                &args.files,
+                args.parse_options(args.lazy_parsing),
+                |file, src, parsed| {
+                    let name = file.display().to_string();
+                    name_script(context, &name, src);
+                    let result = parsed.and_then(|statements| {
+                        forward_parsed(context.interpreter_mut(), &statements)
+                    });
+                    status = report(context, &name, src, result, args.output).max(status);
+                    !is_fatal(status)
+                },
+            )?;
+        } else {
+            for file in &args.files {
+                let buffer = read_source(file)?;
+                let name = file.display().to_string();
+                name_script(context, &name, &buffer);
+                let result = forward_val(context.interpreter_mut(), &buffer);
+                status = report(context, &name, &buffer, result, args.output).max(status);
+                report_timings(context, &name);
+                if is_fatal(status) {
+                    break;
+                }
+            }
+        }
     }
+    Ok(status)
+}
 
-    fn highlight_char(&self, line: &str, _: usize) -> bool {
-        !line.is_empty()
+pub fn main() {
+    if let Err(e) = start() {
+        // The results of the files that were run come first.
+        let _ = io::stdout().flush();
+        eprintln!("error: {}", e);
+        std::process::exit(IO_ERROR);
     }
 }
 
-lazy_static! {
-    static ref KEYWORDS: HashSet<&'static str> = {
-        let mut keywords = HashSet::new();
-        keywords.insert("break");
-        keywords.insert("case");
-        keywords.insert("catch");
-        keywords.insert("class");
-        keywords.insert("const");
-        keywords.insert("continue");
-        keywords.insert("default");
-        keywords.insert("delete");
-        keywords.insert("do");
-        keywords.insert("else");
-        keywords.insert("export");
-        keywords.insert("extends");
-        keywords.insert("finally");
-        keywords.insert("for");
-        keywords.insert("function");
-        keywords.insert("if");
-        keywords.insert("import");
-        keywords.insert("instanceof");
-        keywords.insert("new");
-        keywords.insert("return");
-        keywords.insert("super");
-        keywords.insert("switch");
-        keywords.insert("this");
-        keywords.insert("throw");
-        keywords.insert("try");
-        keywords.insert("typeof");
-        keywords.insert("var");
-        keywords.insert("void");
-        keywords.insert("while");
-        keywords.insert("with");
-        keywords.insert("yield");
-        keywords.insert("await");
-        keywords.insert("enum");
-        keywords.insert("let");
-        keywords
+/// Runs the command line, and exits with the exit code of the scripts if one of them failed.
+fn start() -> io::Result<()> {
+    let mut args = Opt::from_args();
+    init_tracing(args.trace.as_ref());
+    init_colors();
+
+    if let Some(Command::Fmt(mut fmt_args)) = args.command.take() {
+        if fmt_args.files.is_empty() && fmt_args.eval.is_empty() && !io::stdin().is_terminal() {
+            fmt_args.files.push(PathBuf::from(STDIN));
+        }
+        let status = fmt(&args, &fmt_args)?;
+        if status != 0 {
+            std::process::exit(status);
+        }
+        return Ok(());
+    }
+    if args.files.is_empty() && args.eval.is_empty() && !io::stdin().is_terminal() {
+        args.files.push(PathBuf::from(STDIN));
+    }
+    if args.max_memory.is_some() {
+        ALLOC.enable();
+    }
+
+    let mut context = Context::builder()
+        .timeline(args.trace_timeline.is_some())
+        .profile(args.profile)
+        .coverage(args.coverage.is_some())
+        .cpu_profile(args.cpu_prof.is_some())
+        .timings(args.timing)
+        .optimize(args.optimize)
+        .lazy_parsing(args.lazy_parsing)
+        .strict(args.strict)
+        .experimental(args.experimental.unwrap_or_default())
+        .annex_b(!args.no_annex_b)
+        .build();
+    context.interpreter_mut().set_memory_limit(args.max_memory);
+    if args.output == OutputFormat::Json {
+        // Only the results go to the standard output, so that it can be parsed.
+        context.set_console_writer(WriteConsoleWriter::new(io::stderr()));
+    }
+    if args.debug {
+        debug::attach(&mut context);
+    }
+
+    // The files are watched from the start, so that their changes during the first run are
+    // not missed.
+    let watcher = if args.watch {
+        Some(Watcher::new(&args.files)?)
+    } else {
+        None
     };
-}
+    setup(&mut context, &args)?;
+    let status = run(&mut context, &args)?;
 
-struct LineHighlighter;
-
-impl Highlighter for LineHighlighter {
-    fn highlight<'l>(&self, line: &'l str, _: usize) -> Cow<'l, str> {
-        let mut coloured = line.to_string();
-
-        let reg = Regex::new(
-            r"(?x)
-            (?P<identifier>[$A-z_]+[$A-z_0-9]*) |
-            (?P<op>[+\-/*%~^!&|=<>,.;:])",
-        )
-        .unwrap();
-
-        coloured = reg
-            .replace_all(&coloured, |caps: &Captures<'_>| {
-                if let Some(cap) = caps.name("identifier") {
-                    match cap.as_str() {
-                        "true" | "false" | "null" | "Infinity" => cap.as_str().purple().to_string(),
-                        "undefined" => cap.as_str().truecolor(100, 100, 100).to_string(),
-                        identifier if KEYWORDS.contains(identifier) => {
-                            cap.as_str().yellow().bold().to_string()
-                        }
-                        _ => cap.as_str().to_string(),
-                    }
-                } else if let Some(cap) = caps.name("op") {
-                    cap.as_str().green().to_string()
-                } else {
-                    caps[0].to_string()
+    if let Some(mut watcher) = watcher {
+        loop {
+            io::stdout().flush()?;
+            let changed = watcher.wait()?.display().to_string();
+            eprintln!("\n{} changed, running the files again", changed);
+            context.interpreter_mut().reset_realm();
+            setup(&mut context, &args)?;
+            // A file that cannot be read, e.g. while it is being replaced, is read again on
+            // its next change.
+            if let Err(e) = run(&mut context, &args) {
+                eprintln!("{}", e);
+            }
+        }
+    }
+
+    if args.files.is_empty() && args.eval.is_empty() {
+        let mut repl = Repl::new()
+            .vi_mode(args.vi_mode)
+            .history_file(history_file(&args))
+            .history_size(args.history_size);
+        if args.has_dump_flag() {
+            let dump_args = args.clone();
+            repl = repl.line_handler(move |_, line, _| {
+                if let Err(e) = dump(line, &dump_args) {
+                    eprintln!("{}", e);
                 }
-            })
-            .to_string();
+                Ok(())
+            });
+        }
+
+        if let Err(err) = repl.run(&mut context) {
+            eprintln!("Unknown error: {:?}", err);
+        }
+    }
+
+    if let Some(path) = &args.snapshot {
+        match RealmSnapshot::capture(context.interpreter()) {
+            Ok(snapshot) => write(path, snapshot.to_bytes())?,
+            Err(e) => eprintln!("Could not write {}: {}", path.display(), e),
+        }
+    }
+
+    if let (Some(path), Some(timeline)) = (&args.trace_timeline, context.interpreter().timeline()) {
+        write(path, timeline.to_chrome_trace().to_string())?;
+    }
 
-        coloured.into()
+    if let (Some(path), Some(coverage)) = (&args.coverage, context.interpreter().coverage()) {
+        let report = match args.coverage_format {
+            CoverageFormat::Lcov => coverage.to_lcov(),
+            CoverageFormat::Json => coverage.to_json().to_string(),
+        };
+        write(path, report)?;
     }
+
+    if let (Some(path), Some(profile)) = (&args.cpu_prof, context.interpreter().cpu_profile()) {
+        let report = match args.cpu_prof_format {
+            CpuProfileFormat::Speedscope => {
+                let name = args
+                    .files
+                    .first()
+                    .map_or_else(|| "boa".to_owned(), |file| file.display().to_string());
+                profile.to_speedscope(&name).to_string()
+            }
+            CpuProfileFormat::Folded => profile.to_folded(),
+        };
+        write(path, report)?;
+    }
+
+    if let Some(profile) = context.interpreter().profile() {
+        eprint!("\n{}", profile);
+    }
+
+    if args.heap_stats {
+        eprint!("\n{}", HeapStats::capture(context.interpreter()));
+    }
+
+    if let Some(path) = &args.heap_snapshot {
+        let snapshot = HeapSnapshot::capture(context.interpreter());
+        let contents = if path.extension().is_some_and(|e| e == "heapsnapshot") {
+            snapshot.to_heapsnapshot()
+        } else {
+            snapshot.to_json()
+        };
+        write(path, contents.to_string())?;
+    }
+
+    // The scripts and their threads are done with the profiler.
+    unsafe { BoaProfiler::drop() };
+
+    if status != 0 {
+        std::process::exit(status);
+    }
+    Ok(())
 }
@@ -0,0 +1,67 @@
+//! The byte offsets of the positions in the JSON of `--dump-tokens` and `--dump-ast`.
+//!
+//! The tokens and the statements serialize their spans as lines and columns, so the dumps add the
+//! byte offset of each position in the source, for the tools that slice the source directly.
+
+use serde_json::{Map, Value};
+
+/// Adds an `offset` field to every position, an object with a line and a column, in the JSON.
+///
+/// The end of a span is the position of its last character, so its offset is the offset of the
+/// start of that character.
+pub(crate) fn add_offsets(src: &str, json: &mut Value) {
+    let lines = line_starts(src);
+    visit(src, &lines, json);
+}
+
+fn visit(src: &str, lines: &[usize], json: &mut Value) {
+    match json {
+        Value::Object(map) => {
+            if let Some(offset) = position(map).and_then(|(line, column)| {
+                let start = *lines.get(line.checked_sub(1)?)?;
+                offset(src, start, column)
+            }) {
+                map.insert(String::from("offset"), offset.into());
+                return;
+            }
+            for value in map.values_mut() {
+                visit(src, lines, value);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                visit(src, lines, item);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Gets the line and the column of a position.
+fn position(map: &Map<String, Value>) -> Option<(usize, usize)> {
+    if map.len() != 2 {
+        return None;
+    }
+    let line = map.get("line_number")?.as_u64()?;
+    let column = map.get("column_number")?.as_u64()?;
+    Some((line as usize, column as usize))
+}
+
+/// Gets the byte offset of the start of each line.
+fn line_starts(src: &str) -> Vec<usize> {
+    let mut starts = vec![0];
+    starts.extend(src.match_indices('\n').map(|(index, _)| index + 1));
+    starts
+}
+
+/// Gets the byte offset of the column of the line starting at `start`.
+///
+/// The column may be one past the end of the line, where the line terminator is.
+fn offset(src: &str, start: usize, column: usize) -> Option<usize> {
+    let line = &src[start..];
+    match line.char_indices().nth(column.checked_sub(1)?) {
+        Some((index, _)) => Some(start + index),
+        None if line.chars().count() + 1 == column => Some(src.len()),
+        None => None,
+    }
+}
@@ -0,0 +1,93 @@
+//! The `--output json` mode, which prints the result of each file and snippet as a line of JSON.
+//!
+//! A result is printed as `{"source": "-e #1", "type": "number", "value": "42"}`, where the
+//! value is the completion value converted to a string, and an error as
+//! `{"source": "a.js", "error": {"name": "SyntaxError", "message": "...", "script": "a.js",
+//! "line": 2, "column": 9, "original": null}}`. The line and the column are in `script`, which
+//! is another file if the error was thrown in a function defined there, and the three are
+//! `null` when the position is unknown. If the script has a source map, `original` is the
+//! position in the original source, like `{"source": "src/a.ts", "line": 1, "column": 5}`.
+
+use crate::source_map;
+use boa::{
+    builtins::value::{Type, Value},
+    JsError,
+};
+use serde_json::{json, Value as Json};
+
+/// Formats the completion value of the source named `name`.
+pub(crate) fn result(name: &str, value: &Value) -> Json {
+    json!({
+        "source": name,
+        "type": type_of(value),
+        "value": value.to_string(),
+    })
+}
+
+/// Formats an error of the source `src` named `name`.
+pub(crate) fn error(name: &str, src: &str, error: &JsError) -> Json {
+    let (error_name, message) = match error {
+        JsError::Syntax { message, .. } => (Json::from("SyntaxError"), message.clone()),
+        JsError::Thrown { value, message, .. } => thrown(value, message),
+        JsError::StackOverflow => (
+            Json::from("RangeError"),
+            String::from("Maximum call stack size exceeded"),
+        ),
+        JsError::Interrupted => (Json::from("Interrupted"), error.to_string()),
+        JsError::StepBudgetExceeded => (Json::from("StepBudgetExceeded"), error.to_string()),
+        JsError::MemoryLimitExceeded => (Json::from("MemoryLimitExceeded"), error.to_string()),
+    };
+    let position = error.position();
+    let script = position.map(|_| error.script().unwrap_or(name));
+    let original = source_map::locate_error(name, src, error)
+        .filter(|location| location.generated.is_some())
+        .map(|location| {
+            json!({
+                "source": location.name,
+                "line": location.line,
+                "column": location.column,
+            })
+        });
+    json!({
+        "source": name,
+        "error": {
+            "name": error_name,
+            "message": message,
+            "script": script,
+            "line": position.map(|position| position.line_number()),
+            "column": position.map(|position| position.column_number()),
+            "original": original,
+        },
+    })
+}
+
+/// Gets the name and the message of a thrown value.
+///
+/// Errors have both, other values have no name and are their own message.
+fn thrown(value: &Value, message: &str) -> (Json, String) {
+    if value.is_object() {
+        let name = value.get_field("name");
+        if !name.is_undefined() {
+            return (
+                Json::from(name.to_string()),
+                value.get_field("message").to_string(),
+            );
+        }
+    }
+    (Json::Null, message.to_owned())
+}
+
+/// Gets the type of a value, like `typeof` does except that `null` is its own type.
+fn type_of(value: &Value) -> &'static str {
+    match value.get_type() {
+        Type::Null => "null",
+        Type::Undefined => "undefined",
+        Type::Boolean => "boolean",
+        Type::Number => "number",
+        Type::String => "string",
+        Type::Symbol => "symbol",
+        Type::BigInt => "bigint",
+        Type::Object => "object",
+        Type::Function => "function",
+    }
+}
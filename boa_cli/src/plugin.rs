@@ -0,0 +1,199 @@
+//! Loading of CLI plugins.
+//!
+//! A plugin is a dynamic library that exports a `boa_plugin_init` function with the C ABI. The
+//! function receives a `PluginApi` table, which it uses to register global functions and strings,
+//! and returns `0` on success. See `docs/plugins.md` for the C declarations.
+//!
+//! Values cross the plugin boundary as NUL terminated UTF-8 strings: the arguments of a plugin
+//! function are converted with `ToString`, and the string it returns becomes the return value,
+//! or `undefined` if it returns a null pointer.
+
+use boa::{
+    builtins::{
+        function::make_closure_fn,
+        value::{ResultValue, Value},
+    },
+    exec::Interpreter,
+};
+use std::{
+    ffi::{CStr, CString},
+    os::raw::{c_char, c_int, c_void},
+    path::Path,
+};
+
+/// The version of the plugin API, passed to the plugins in `PluginApi::version`.
+const PLUGIN_API_VERSION: u32 = 1;
+
+/// A function registered by a plugin.
+///
+/// It receives the arguments of the call and the `user_data` pointer it was registered with. The
+/// returned string must stay valid until the function is called again.
+type PluginFunction =
+    extern "C" fn(argc: usize, argv: *const *const c_char, user_data: *mut c_void) -> *const c_char;
+
+/// The `boa_plugin_init` function that plugins export.
+type PluginInit = unsafe extern "C" fn(api: *const PluginApi) -> c_int;
+
+/// The table of functions that is passed to the plugins.
+#[repr(C)]
+#[derive(Debug)]
+struct PluginApi {
+    /// The version of the plugin API.
+    version: u32,
+    /// The registrar, to be passed back to the registration functions.
+    registrar: *mut c_void,
+    /// Registers a global function.
+    register_function: extern "C" fn(
+        registrar: *mut c_void,
+        name: *const c_char,
+        function: PluginFunction,
+        user_data: *mut c_void,
+    ) -> c_int,
+    /// Registers a global string.
+    register_string:
+        extern "C" fn(registrar: *mut c_void, name: *const c_char, value: *const c_char) -> c_int,
+}
+
+/// The globals registered by a plugin while it is initialised.
+#[derive(Debug, Default)]
+struct Registrar {
+    functions: Vec<(String, PluginFunction, *mut c_void)>,
+    strings: Vec<(String, String)>,
+}
+
+/// Loads the plugin at `path` and installs the globals it registers.
+pub(crate) fn load(engine: &mut Interpreter, path: &Path) -> Result<(), String> {
+    let init = open(path)?;
+
+    let mut registrar = Registrar::default();
+    let api = PluginApi {
+        version: PLUGIN_API_VERSION,
+        registrar: &mut registrar as *mut Registrar as *mut c_void,
+        register_function,
+        register_string,
+    };
+    let status = unsafe { init(&api) };
+    if status != 0 {
+        return Err(format!(
+            "{}: initialisation failed with status {}",
+            path.display(),
+            status
+        ));
+    }
+
+    let global = engine.realm.global_obj.clone();
+    for (name, value) in registrar.strings {
+        global.set_field(name, Value::from(value));
+    }
+    for (name, function, user_data) in registrar.functions {
+        make_closure_fn(
+            move |_, args, ctx| call(function, user_data, args, ctx),
+            name,
+            &global,
+            0,
+        );
+    }
+    Ok(())
+}
+
+/// Opens the dynamic library at `path` and looks up its init function.
+///
+/// The library is never closed, because the functions it registered are called until the
+/// process exits.
+#[cfg(unix)]
+fn open(path: &Path) -> Result<PluginInit, String> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let file = CString::new(path.as_os_str().as_bytes()).map_err(|e| e.to_string())?;
+    unsafe {
+        let handle = libc::dlopen(file.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL);
+        if handle.is_null() {
+            return Err(dl_error());
+        }
+        let symbol = libc::dlsym(handle, b"boa_plugin_init\0".as_ptr() as *const c_char);
+        if symbol.is_null() {
+            return Err(dl_error());
+        }
+        Ok(std::mem::transmute::<*mut c_void, PluginInit>(symbol))
+    }
+}
+
+#[cfg(not(unix))]
+fn open(path: &Path) -> Result<PluginInit, String> {
+    Err(format!(
+        "{}: plugins are only supported on Unix platforms",
+        path.display()
+    ))
+}
+
+/// Gets the message of the last `dlopen` or `dlsym` error.
+#[cfg(unix)]
+unsafe fn dl_error() -> String {
+    let message = libc::dlerror();
+    if message.is_null() {
+        "unknown dynamic loading error".to_owned()
+    } else {
+        CStr::from_ptr(message).to_string_lossy().into_owned()
+    }
+}
+
+/// Converts a string received from a plugin.
+unsafe fn from_c_str(string: *const c_char) -> Option<String> {
+    if string.is_null() {
+        None
+    } else {
+        CStr::from_ptr(string).to_str().ok().map(str::to_owned)
+    }
+}
+
+extern "C" fn register_function(
+    registrar: *mut c_void,
+    name: *const c_char,
+    function: PluginFunction,
+    user_data: *mut c_void,
+) -> c_int {
+    let registrar = unsafe { &mut *(registrar as *mut Registrar) };
+    match unsafe { from_c_str(name) } {
+        Some(name) => {
+            registrar.functions.push((name, function, user_data));
+            0
+        }
+        _ => -1,
+    }
+}
+
+extern "C" fn register_string(
+    registrar: *mut c_void,
+    name: *const c_char,
+    value: *const c_char,
+) -> c_int {
+    let registrar = unsafe { &mut *(registrar as *mut Registrar) };
+    match unsafe { (from_c_str(name), from_c_str(value)) } {
+        (Some(name), Some(value)) => {
+            registrar.strings.push((name, value));
+            0
+        }
+        _ => -1,
+    }
+}
+
+/// Calls a plugin function with the arguments converted to strings.
+fn call(
+    function: PluginFunction,
+    user_data: *mut c_void,
+    args: &[Value],
+    ctx: &mut Interpreter,
+) -> ResultValue {
+    let mut strings = Vec::with_capacity(args.len());
+    for arg in args {
+        let string = ctx.to_string(arg)?;
+        match CString::new(string.as_bytes()) {
+            Ok(string) => strings.push(string),
+            Err(_) => return ctx.throw_type_error("plugin arguments cannot contain NUL"),
+        }
+    }
+    let argv: Vec<*const c_char> = strings.iter().map(|string| string.as_ptr()).collect();
+
+    let result = function(argv.len(), argv.as_ptr(), user_data);
+    Ok(unsafe { from_c_str(result) }.map_or_else(Value::undefined, Value::from))
+}
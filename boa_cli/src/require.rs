@@ -0,0 +1,205 @@
+//! The `require` function of CommonJS modules, which the scripts run by the CLI load other
+//! files with.
+//!
+//! `require(path)` runs the file at `path` once, and returns its `module.exports`, which is an
+//! empty object unless the file replaces it or adds properties to its `exports`. The body of the
+//! file is a function of `exports`, `require`, `module`, `__filename` and `__dirname`, so its
+//! declarations are not globals.
+//!
+//! Paths start with `./`, `../` or `/`, and relative paths are resolved from the directory of
+//! the file that requires them, or from the current directory for the snippets, the standard
+//! input and the REPL. A path without an extension is looked up with `.js` then `.json`, and a
+//! directory with its `index.js` or `index.json`. JSON files are parsed and not run. There are
+//! no packages: names like `lodash` are not looked up in `node_modules`.
+//!
+//! The modules are cached by their canonical path, so that requiring a file again returns the
+//! same exports, and a file that requires a file that is still loading gets the exports it has
+//! so far.
+
+use crate::source_map;
+use boa::{
+    builtins::{
+        function::Function,
+        object::Object,
+        value::{ResultValue, Value},
+    },
+    compile_function,
+    exec::Interpreter,
+    JsError,
+};
+use std::{
+    env,
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The parameters of the function that wraps the body of a module.
+const PARAMETERS: [&str; 5] = ["exports", "require", "module", "__filename", "__dirname"];
+
+/// The extensions tried, in order, for a path without one.
+const EXTENSIONS: [&str; 2] = ["js", "json"];
+
+/// The internal slot of the captures of a `require` function with the cache, an object with
+/// the `module` objects of the loaded modules by their canonical path.
+const CACHE: &str = "Cache";
+
+/// The internal slot of the captures of a `require` function with the directory that it
+/// resolves paths from, which the global `require` does not have.
+const DIR: &str = "Dir";
+
+/// Defines the global `require` function, which resolves paths from the directory of the file
+/// that is running.
+///
+/// Each call starts with an empty cache, so that the modules are run again with `--watch`.
+pub(crate) fn install(engine: &mut Interpreter) {
+    let require = make_require(engine, Value::new_object(None), None);
+    engine.realm.global_obj.set_field("require", require);
+}
+
+/// Creates a `require` function that shares `cache` and resolves paths from `dir`.
+///
+/// The cache and the directory are captured in a value that the garbage collector traces.
+fn make_require(engine: &Interpreter, cache: Value, dir: Option<&Path>) -> Value {
+    let captures = Value::new_object(None);
+    captures.set_internal_slot(CACHE, cache);
+    if let Some(dir) = dir {
+        captures.set_internal_slot(DIR, Value::from(dir.display().to_string()));
+    }
+    let function = Function::builtin_with_captures(Vec::new(), require, captures);
+    let function = Value::from(Object::function(
+        function,
+        engine.realm.function_prototype(),
+    ));
+    function.set_field("length", Value::from(1));
+    function
+}
+
+/// Resolves the path given to `require`, and returns the exports of the module.
+fn require(captures: &Value, _: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let specifier = ctx.to_string(&args.first().cloned().unwrap_or_default())?;
+    let dir = captures.get_internal_slot(DIR);
+    let base = if dir.is_undefined() {
+        // The CLI names the scripts of the files after their path.
+        let script = Path::new(ctx.script_name());
+        match script.parent() {
+            Some(dir) if script.is_file() => dir.to_path_buf(),
+            _ => env::current_dir().unwrap_or_default(),
+        }
+    } else {
+        PathBuf::from(dir.to_string())
+    };
+    match resolve(&base, &specifier) {
+        Some(path) => load(&path, &captures.get_internal_slot(CACHE), ctx),
+        None => ctx.throw_error(format!("Cannot find module '{}'", specifier)),
+    }
+}
+
+/// Finds the file of a module, see the module documentation.
+fn resolve(base: &Path, specifier: &str) -> Option<PathBuf> {
+    let is_relative = specifier == "."
+        || specifier == ".."
+        || specifier.starts_with("./")
+        || specifier.starts_with("../");
+    if !is_relative && !Path::new(specifier).is_absolute() {
+        return None;
+    }
+
+    let path = base.join(specifier);
+    let with_extension = |path: &Path, extension| {
+        let mut name = OsString::from(path);
+        name.push(".");
+        name.push(extension);
+        PathBuf::from(name)
+    };
+    let index = path.join("index");
+    std::iter::once(path.clone())
+        .chain(
+            EXTENSIONS
+                .iter()
+                .map(|extension| with_extension(&path, extension)),
+        )
+        .chain(
+            EXTENSIONS
+                .iter()
+                .map(|extension| with_extension(&index, extension)),
+        )
+        .find(|candidate| candidate.is_file())
+        .and_then(|file| file.canonicalize().ok())
+}
+
+/// Runs the module at `path`, unless it is cached, and returns its exports.
+///
+/// A module that throws is removed from the cache, so that requiring it again runs it again.
+fn load(path: &Path, cache: &Value, ctx: &mut Interpreter) -> ResultValue {
+    let filename = path.display().to_string();
+    if cache.has_field(filename.as_str()) {
+        return Ok(cache.get_field(filename.as_str()).get_field("exports"));
+    }
+    let src = match fs::read_to_string(path) {
+        Ok(src) => src,
+        Err(e) => return ctx.throw_error(format!("Cannot read {}: {}", filename, e)),
+    };
+
+    let global = ctx.realm.global_obj.clone();
+    let module = Value::new_object(Some(&global));
+    module.set_field("exports", Value::new_object(Some(&global)));
+    module.set_field("id", Value::from(filename.as_str()));
+    module.set_field("filename", Value::from(filename.as_str()));
+    module.set_field("loaded", Value::from(false));
+    cache.set_field(filename.as_str(), module.clone());
+
+    let result = if path
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        match serde_json::from_str(&src) {
+            Ok(json) => {
+                module.set_field("exports", Value::from_json(json, ctx));
+                Ok(Value::undefined())
+            }
+            Err(e) => ctx.throw_syntax_error(format!("{}: {}", filename, e)),
+        }
+    } else {
+        run(path, &src, cache, &module, ctx)
+    };
+    if let Err(e) = result {
+        cache.remove_property(filename.as_str());
+        return Err(e);
+    }
+    module.set_field("loaded", Value::from(true));
+    Ok(module.get_field("exports"))
+}
+
+/// Runs the body of a JavaScript module, with its own `require` that resolves paths from its
+/// directory.
+fn run(
+    path: &Path,
+    src: &str,
+    cache: &Value,
+    module: &Value,
+    ctx: &mut Interpreter,
+) -> ResultValue {
+    let filename = path.display().to_string();
+    let body = match compile_function(ctx, &filename, &PARAMETERS, src) {
+        Ok(body) => body,
+        Err(JsError::Syntax { message, .. }) => {
+            return ctx.throw_syntax_error(format!("{}: {}", filename, message))
+        }
+        Err(e) => return Err(Value::from(e.to_string())),
+    };
+    if let Some(map) = source_map::load(&filename, src) {
+        ctx.set_source_map(filename.as_str(), map);
+    }
+
+    let dir = path.parent().unwrap_or(path);
+    let exports = module.get_field("exports");
+    let args = [
+        exports.clone(),
+        make_require(ctx, cache.clone(), Some(dir)),
+        module.clone(),
+        Value::from(filename),
+        Value::from(dir.display().to_string()),
+    ];
+    ctx.call(&body, &exports, &args)
+}
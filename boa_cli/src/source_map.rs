@@ -0,0 +1,131 @@
+//! The translation of the positions of errors to the original sources of the files that have a
+//! source map, found with their `//# sourceMappingURL=` comment.
+//!
+//! The map is looked up relative to the file, or decoded from the comment if it is a `data:`
+//! URL, and the sources relative to the map. A map that can not be read is ignored, and the
+//! errors point at the generated code.
+
+use boa::{
+    source_map::{source_mapping_url, SourceMap},
+    syntax::ast::Position,
+    JsError,
+};
+use std::{
+    fs::read_to_string,
+    path::{Component, Path, PathBuf},
+};
+
+/// The position of an error in the source that the user wrote.
+#[derive(Debug, Clone)]
+pub(crate) struct Location {
+    /// The file or snippet, like `script.js`, `src/app.ts` or `-e #1`.
+    pub(crate) name: String,
+    pub(crate) line: u32,
+    pub(crate) column: u32,
+    /// The text of the line, if the source can be read.
+    pub(crate) text: Option<String>,
+    /// The position in the generated code, if the error was mapped to an original source.
+    pub(crate) generated: Option<Position>,
+}
+
+/// Finds the location of an error at `position` in the source `src` of the file named `name`.
+pub(crate) fn locate(name: &str, src: &str, position: Position) -> Location {
+    let line_text = |text: &str, line: u32| {
+        text.lines()
+            .nth((line as usize).saturating_sub(1))
+            .map(str::to_owned)
+    };
+    let generated = Location {
+        name: name.to_owned(),
+        line: position.line_number(),
+        column: position.column_number(),
+        text: line_text(src, position.line_number()),
+        generated: None,
+    };
+
+    let map = match load(name, src) {
+        Some(map) => map,
+        None => return generated,
+    };
+    let original = match map.lookup(position) {
+        Some(original) => original,
+        None => return generated,
+    };
+    let text = match original.content {
+        Some(content) => line_text(content, original.line),
+        None => read_to_string(original.source)
+            .ok()
+            .and_then(|content| line_text(&content, original.line)),
+    };
+    Location {
+        name: original.source.to_owned(),
+        line: original.line,
+        column: original.column,
+        text,
+        generated: Some(position),
+    }
+}
+
+/// Finds the location of an error of the source `src` of the file named `name`, if the error
+/// has a position.
+///
+/// The errors thrown in a function defined in another file, like a module, are located in that
+/// file, whose source is read again and has no text if it can not be.
+pub(crate) fn locate_error(name: &str, src: &str, error: &JsError) -> Option<Location> {
+    let position = error.position()?;
+    match error.script() {
+        Some(script) if script != name => {
+            let src = read_to_string(script).unwrap_or_default();
+            Some(locate(script, &src, position))
+        }
+        _ => Some(locate(name, src, position)),
+    }
+}
+
+/// Loads the source map of a file, with the paths of its sources relative to the current
+/// directory.
+pub(crate) fn load(name: &str, src: &str) -> Option<SourceMap> {
+    let url = source_mapping_url(src)?;
+    // Snippets and the standard input are relative to the current directory.
+    let directory = Path::new(name).parent().unwrap_or_else(|| Path::new(""));
+    let (mut map, base) = if url.starts_with("data:") {
+        (SourceMap::from_data_url(url).ok()?, directory.to_owned())
+    } else {
+        let path = directory.join(url);
+        let map = SourceMap::from_json(&read_to_string(&path).ok()?).ok()?;
+        (
+            map,
+            path.parent().unwrap_or_else(|| Path::new("")).to_owned(),
+        )
+    };
+    // Sources that are URLs, like `webpack://app/src/a.ts`, are shown as they are.
+    map.resolve_sources(|source| {
+        if source.contains("://") {
+            source.to_owned()
+        } else {
+            normalize(&base.join(source)).display().to_string()
+        }
+    });
+    Some(map)
+}
+
+/// Removes the `.` components of a path, and the `..` ones that follow a directory, so that
+/// `dist/../src/a.ts` is shown as `src/a.ts`.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
@@ -0,0 +1,94 @@
+//! The `--watch` mode, which runs the files again when they change.
+//!
+//! The directories of the files are watched with the notifier of the platform, instead of the
+//! files themselves, since editors often save a file by replacing it. Editors also often save a
+//! file with several writes, so the changes that follow each other within a short delay are
+//! reported once.
+
+use notify::{event::EventKind, RecommendedWatcher, RecursiveMode, Watcher as _};
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    sync::mpsc::{channel, Receiver},
+    thread,
+    time::Duration,
+};
+
+/// How long the changes that follow a change are waited for, to report them at once.
+const SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+/// Watches a set of files for changes.
+#[derive(Debug)]
+pub(crate) struct Watcher {
+    /// The files, with their absolute paths.
+    files: Vec<(PathBuf, PathBuf)>,
+    /// The events of the notifier, which stops watching when it is dropped.
+    events: Receiver<notify::Result<notify::Event>>,
+    _notifier: RecommendedWatcher,
+}
+
+impl Watcher {
+    /// Starts watching the files, other than the standard input.
+    pub(crate) fn new(files: &[PathBuf]) -> io::Result<Self> {
+        let cwd = env::current_dir()?;
+        let files: Vec<_> = files
+            .iter()
+            .filter(|file| file.as_path() != Path::new(crate::STDIN))
+            .map(|file| (file.clone(), absolute(&cwd.join(file))))
+            .collect();
+        let (sender, events) = channel();
+        let mut notifier = notify::recommended_watcher(sender).map_err(to_io_error)?;
+        let mut directories: Vec<_> = files.iter().filter_map(|(_, file)| file.parent()).collect();
+        directories.sort();
+        directories.dedup();
+        for directory in directories {
+            notifier
+                .watch(directory, RecursiveMode::NonRecursive)
+                .map_err(to_io_error)?;
+        }
+        Ok(Self {
+            files,
+            events,
+            _notifier: notifier,
+        })
+    }
+
+    /// Blocks until one of the files changes, is created or is removed, and returns its path.
+    pub(crate) fn wait(&mut self) -> io::Result<&Path> {
+        loop {
+            let event = match self.events.recv() {
+                Ok(event) => event.map_err(to_io_error)?,
+                Err(_) => return Err(io::Error::other("the file watcher stopped")),
+            };
+            if let EventKind::Access(_) = event.kind {
+                continue;
+            }
+            let changed = self
+                .files
+                .iter()
+                .position(|(_, file)| event.paths.contains(file));
+            if let Some(index) = changed {
+                thread::sleep(SETTLE_DELAY);
+                while self.events.try_recv().is_ok() {}
+                return Ok(&self.files[index].0);
+            }
+        }
+    }
+}
+
+/// Gets the path of a file in the canonical path of its directory, which the notifier reports
+/// the changes of the file with, even if the file does not exist.
+fn absolute(file: &Path) -> PathBuf {
+    match (file.parent().map(fs::canonicalize), file.file_name()) {
+        (Some(Ok(directory)), Some(name)) => directory.join(name),
+        _ => file.to_owned(),
+    }
+}
+
+/// Converts an error of the notifier to an I/O error.
+fn to_io_error(error: notify::Error) -> io::Error {
+    match error.kind {
+        notify::ErrorKind::Io(error) => error,
+        _ => io::Error::other(error),
+    }
+}
@@ -0,0 +1,388 @@
+//! Runs the `boa` binary on files, to check the flags that change how the files are parsed and
+//! run.
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+    sync::mpsc::channel,
+    thread,
+    time::Duration,
+};
+
+/// A directory of scripts, removed when the test ends.
+struct Scripts(PathBuf);
+
+impl Scripts {
+    /// Writes the scripts, given as file names and sources, to a new directory named after the
+    /// test.
+    fn new(test: &str, files: &[(&str, &str)]) -> Self {
+        let dir = std::env::temp_dir().join(format!("boa_cli_{}_{}", test, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (name, src) in files {
+            fs::write(dir.join(name), src).unwrap();
+        }
+        Self(dir)
+    }
+
+    /// Runs the binary with the given arguments, from the directory of the scripts.
+    fn run(&self, args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_boa"))
+            .args(args)
+            .current_dir(&self.0)
+            .output()
+            .unwrap()
+    }
+
+    /// Runs the binary like `run`, with `input` as its standard input.
+    fn run_with_input(&self, args: &[&str], input: &str) -> Output {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_boa"))
+            .args(args)
+            .current_dir(&self.0)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(input.as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    }
+}
+
+impl Drop for Scripts {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn experimental_features_in_several_files() {
+    let scripts = Scripts::new(
+        "experimental",
+        &[("n1.js", "null ?? 'a'"), ("n2.js", "undefined ?? 'b'")],
+    );
+    let args = ["--experimental", "nullish-coalescing", "n1.js", "n2.js"];
+    let output = scripts.run(&args);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "a\nb\n");
+
+    let output = scripts.run(&["--check", "n1.js", "n2.js"]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = scripts.run(&["--check", "--experimental", "all", "n1.js", "n2.js"]);
+    assert!(output.status.success(), "{:?}", output);
+}
+
+#[test]
+fn no_annex_b_in_several_files() {
+    let scripts = Scripts::new(
+        "annex_b",
+        &[("h1.js", "var a = 1; <!-- a = 2;\na"), ("t2.js", "2")],
+    );
+    let output = scripts.run(&["h1.js", "t2.js"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1\n2\n");
+
+    let output = scripts.run(&["--no-annex-b", "h1.js", "t2.js"]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = scripts.run(&["--check", "--no-annex-b", "h1.js", "t2.js"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn errors_in_functions_of_other_files() {
+    let scripts = Scripts::new(
+        "other_files",
+        &[
+            (
+                "lib.js",
+                "function fail() {\n    throw new Error('bad');\n}",
+            ),
+            ("main.js", "1;\nfail();"),
+        ],
+    );
+    let output = scripts.run(&["lib.js", "main.js"]);
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--> lib.js:2:5"), "{}", stderr);
+    assert!(stderr.contains("throw new Error('bad');"), "{}", stderr);
+}
+
+#[test]
+fn timeout_stops_an_infinite_loop() {
+    let scripts = Scripts::new(
+        "timeout",
+        &[("loop.js", "while (true) {}"), ("after.js", "1")],
+    );
+    let output = scripts.run(&["--timeout", "100ms", "loop.js", "after.js"]);
+    assert_eq!(output.status.code(), Some(3), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("script timed out"));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+}
+
+#[test]
+fn max_memory_stops_runaway_allocations() {
+    let scripts = Scripts::new(
+        "max_memory",
+        &[
+            ("repeat.js", "'x'.repeat(1e9)"),
+            ("push.js", "let a = []; while (true) { a.push({}) }"),
+            ("small.js", "'x'.repeat(1e3).length"),
+        ],
+    );
+    for file in &["repeat.js", "push.js"] {
+        let output = scripts.run(&["--max-memory", "16M", file]);
+        assert_eq!(output.status.code(), Some(3), "{:?}", output);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("memory limit"));
+    }
+
+    let output = scripts.run(&["--max-memory", "16M", "small.js"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1000\n");
+}
+
+#[test]
+fn lazy_parsing_reports_syntax_errors_in_the_body() {
+    let scripts = Scripts::new(
+        "lazy_parsing",
+        &[(
+            "broken.js",
+            "function broken() {\n    return );\n}\nconsole.log('ran');\nbroken();\n",
+        )],
+    );
+    let output = scripts.run(&["--lazy-parsing", "broken.js"]);
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ran\n");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("broken.js:2:12"), "{}", stderr);
+}
+
+#[test]
+fn fmt_command_formats_and_writes_files() {
+    let scripts = Scripts::new(
+        "fmt",
+        &[
+            (
+                "a.js",
+                "function f() { a(); } // f\nb(); /* loop */ while (x) { c() }\n",
+            ),
+            ("bad.js", "let = ;\n"),
+        ],
+    );
+    let formatted =
+        "function f() {\n    a();\n} // f\nb();\n/* loop */\nwhile (x) {\n    c();\n}\n";
+    let output = scripts.run(&["fmt", "a.js"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), formatted);
+
+    let output = scripts.run(&["fmt", "--write", "a.js"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(output.stdout, b"");
+    assert_eq!(
+        fs::read_to_string(scripts.0.join("a.js")).unwrap(),
+        formatted
+    );
+
+    let output = scripts.run(&["fmt", "bad.js"]);
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+}
+
+#[test]
+fn dumps_use_the_parser_options_of_runs() {
+    let scripts = Scripts::new(
+        "dump_options",
+        &[("n.js", "a ?? b"), ("h.js", "a; <!-- b\n")],
+    );
+    let output = scripts.run(&["--dump-ast=js", "n.js"]);
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+    let args = [
+        "--experimental",
+        "nullish-coalescing",
+        "--dump-ast=js",
+        "n.js",
+    ];
+    let output = scripts.run(&args);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "a??b;\n");
+
+    let output = scripts.run(&["--dump-ast=js", "h.js"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "a;\n");
+    let output = scripts.run(&["--no-annex-b", "--dump-ast=js", "h.js"]);
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+}
+
+#[test]
+fn exit_codes_tell_errors_apart() {
+    let scripts = Scripts::new(
+        "exit_codes",
+        &[
+            ("ok.js", "1"),
+            ("syntax.js", "let = ;"),
+            ("throws.js", "throw new Error('bad')"),
+        ],
+    );
+    assert_eq!(scripts.run(&["ok.js"]).status.code(), Some(0));
+    assert_eq!(scripts.run(&["syntax.js"]).status.code(), Some(2));
+    assert_eq!(scripts.run(&["throws.js"]).status.code(), Some(1));
+
+    let output = scripts.run(&["ok.js", "missing.js"]);
+    assert_eq!(output.status.code(), Some(4), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1\n");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("missing.js"));
+}
+
+#[test]
+fn eval_snippets_before_the_files() {
+    let scripts = Scripts::new("eval", &[("f.js", "x * 2")]);
+    let output = scripts.run(&["-e", "1 + 2", "--eval", "var x = 'a'.length", "f.js"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "3\nundefined\n2\n");
+
+    let output = scripts.run(&["-e", "throw new Error('bad')", "-e", "1"]);
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Error: bad"));
+}
+
+#[test]
+fn standard_input_is_a_source() {
+    let scripts = Scripts::new("stdin", &[("f.js", "x + 1")]);
+    let output = scripts.run_with_input(&["-"], "var x = 41; x");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "41\n");
+
+    let output = scripts.run_with_input(&["-", "f.js"], "var x = 1");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "undefined\n2\n");
+
+    // A piped standard input is read without `-`.
+    let output = scripts.run_with_input(&[], "'piped'");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "piped\n");
+
+    let output = scripts.run_with_input(&["-"], "let = ;");
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+}
+
+#[test]
+fn script_args_after_double_dash() {
+    let scripts = Scripts::new("script_args", &[("args.js", "scriptArgs.join(' ')")]);
+    let output = scripts.run(&["args.js", "--", "foo", "--bar", "-e"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "args.js foo --bar -e\n"
+    );
+
+    let output = scripts.run(&["-e", "scriptArgs.join(' ')", "--", "x"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "boa x\n");
+}
+
+#[test]
+fn check_does_not_run_the_files() {
+    let scripts = Scripts::new(
+        "check",
+        &[
+            ("ok.js", "console.log('ran'); throw new Error('ran')"),
+            ("bad1.js", "let = ;"),
+            ("bad2.js", "if ("),
+        ],
+    );
+    let output = scripts.run(&["--check", "ok.js"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(output.stdout, b"");
+
+    // All the syntax errors are reported.
+    let output = scripts.run(&["--check", "bad1.js", "ok.js", "bad2.js"]);
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+    assert_eq!(output.stdout, b"");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("bad1.js") && stderr.contains("bad2.js"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn json_output_of_results_and_errors() {
+    let scripts = Scripts::new(
+        "json_output",
+        &[
+            ("ok.js", "console.log('log'); ({ a: 1 })"),
+            ("throws.js", "\nthrow new TypeError('bad')"),
+            ("syntax.js", "let = ;"),
+        ],
+    );
+    let lines = |output: &Output| -> Vec<serde_json::Value> {
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    };
+
+    let output = scripts.run(&["--output", "json", "-e", "1 + 1", "ok.js"]);
+    assert!(output.status.success(), "{:?}", output);
+    let results = lines(&output);
+    assert_eq!(results.len(), 2, "{:?}", results);
+    assert_eq!(results[0]["type"], "number");
+    assert_eq!(results[0]["value"], "2");
+    assert_eq!(results[1]["source"], "ok.js");
+    assert_eq!(results[1]["type"], "object");
+    // The console writes to the standard error, so that the results can be parsed.
+    assert!(String::from_utf8_lossy(&output.stderr).contains("log"));
+
+    let output = scripts.run(&["--output", "json", "throws.js"]);
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    let error = &lines(&output)[0]["error"];
+    assert_eq!(error["name"], "TypeError");
+    assert_eq!(error["message"], "bad");
+    assert_eq!(error["line"], 2);
+
+    let output = scripts.run(&["--output", "json", "syntax.js"]);
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+    let error = &lines(&output)[0]["error"];
+    assert_eq!(error["name"], "SyntaxError");
+    assert_eq!(error["line"], 1);
+}
+
+#[test]
+fn watch_runs_the_files_again_when_they_change() {
+    let scripts = Scripts::new("watch", &[("w.js", "'first'")]);
+    let mut child = Command::new(env!("CARGO_BIN_EXE_boa"))
+        .args(["--watch", "w.js"])
+        .current_dir(&scripts.0)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+    let (sender, lines) = channel();
+    thread::spawn(move || {
+        for line in stdout.lines() {
+            if sender.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+    let timeout = Duration::from_secs(10);
+
+    assert_eq!(lines.recv_timeout(timeout).as_deref(), Ok("first"));
+    // The file is replaced, like editors save files.
+    fs::write(scripts.0.join("w.js.tmp"), "'second'").unwrap();
+    fs::rename(scripts.0.join("w.js.tmp"), scripts.0.join("w.js")).unwrap();
+    let second = lines.recv_timeout(timeout);
+    fs::write(scripts.0.join("w.js"), "'third'").unwrap();
+    let third = lines.recv_timeout(timeout);
+    let _ = child.kill();
+    let _ = child.wait();
+    assert_eq!(second.as_deref(), Ok("second"));
+    assert_eq!(third.as_deref(), Ok("third"));
+}
@@ -0,0 +1,40 @@
+/* The C API of the Boa JavaScript engine, implemented by the boa_ffi crate. */
+
+#ifndef BOA_H
+#define BOA_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* A JavaScript context. It can only be used from the thread that created it. */
+typedef struct BoaContext BoaContext;
+
+/* The outcome of boa_eval. `value` must be freed with boa_string_free. */
+typedef struct BoaResult {
+    bool is_error;
+    char *value;
+} BoaResult;
+
+/* A function of the host. It returns its result as a string that stays owned by the host, or
+ * NULL for undefined, and sets *is_error to throw the returned string instead. */
+typedef const char *(*BoaCallback)(void *user_data, const char *const *argv, size_t argc,
+                                   bool *is_error);
+
+BoaContext *boa_context_new(void);
+void boa_context_free(BoaContext *context);
+
+BoaResult boa_eval(BoaContext *context, const char *src);
+void boa_string_free(char *string);
+
+bool boa_register_function(BoaContext *context, const char *name, BoaCallback callback,
+                           void *user_data);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* BOA_H */
@@ -0,0 +1,249 @@
+//! A C API to embed the Boa JavaScript engine in applications written in other languages.
+//!
+//! The library is built as a `cdylib` and a `staticlib`, and `include/boa.h` declares its
+//! functions:
+//!
+//! ```c
+//! BoaContext *context = boa_context_new();
+//! BoaResult result = boa_eval(context, "1 + 1");
+//! if (!result.is_error) {
+//!     printf("%s\n", result.value);
+//! }
+//! boa_string_free(result.value);
+//! boa_context_free(context);
+//! ```
+//!
+//! Strings cross the boundary as NUL terminated UTF-8. The strings returned by the library are
+//! owned by the caller, who frees them with `boa_string_free`. A context can only be used from
+//! the thread that created it.
+//!
+//! Panics of the engine do not unwind into the host: `boa_eval` reports them as errors, and
+//! host functions throw them as JavaScript errors.
+
+#![deny(
+    unused_qualifications,
+    clippy::all,
+    unused_import_braces,
+    unused_lifetimes,
+    unreachable_pub,
+    trivial_numeric_casts,
+    // rustdoc,
+    missing_debug_implementations,
+    missing_copy_implementations,
+    deprecated_in_future,
+    non_ascii_idents,
+    rust_2018_compatibility,
+    rust_2018_idioms,
+    future_incompatible,
+    nonstandard_style
+)]
+#![warn(clippy::perf, clippy::single_match_else, clippy::dbg_macro)]
+#![allow(
+    clippy::missing_inline_in_public_items,
+    clippy::cognitive_complexity,
+    clippy::must_use_candidate,
+    clippy::missing_errors_doc,
+    clippy::as_conversions
+)]
+
+#[cfg(test)]
+mod tests;
+
+use boa::{builtins::value::Value, Context, JsError};
+use std::{
+    any::Any,
+    ffi::{CStr, CString},
+    os::raw::{c_char, c_void},
+    panic::{self, AssertUnwindSafe},
+};
+
+/// A JavaScript context, created with `boa_context_new`.
+///
+/// It is opaque to C code, which only handles pointers to it.
+#[derive(Debug)]
+pub struct BoaContext {
+    context: Context,
+}
+
+/// The outcome of `boa_eval`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct BoaResult {
+    /// Whether `value` describes an error instead of the completion value of the script.
+    pub is_error: bool,
+    /// The completion value of the script converted to a string, or the description of the
+    /// error. It is owned by the caller and must be freed with `boa_string_free`.
+    pub value: *mut c_char,
+}
+
+impl BoaResult {
+    fn new(is_error: bool, value: &str) -> Self {
+        Self {
+            is_error,
+            value: to_c_string(value).into_raw(),
+        }
+    }
+}
+
+/// A function of the host, registered with `boa_register_function`.
+///
+/// It receives the `user_data` it was registered with and the arguments of the call converted to
+/// strings. It returns the result of the call as a string, or `NULL` for `undefined`. The
+/// returned string is copied by the engine, so it stays owned by the host, and only needs to be
+/// valid until the next call into the library. If the callback sets `*is_error` to `true`, the
+/// returned string is thrown instead.
+pub type BoaCallback = unsafe extern "C" fn(
+    user_data: *mut c_void,
+    argv: *const *const c_char,
+    argc: usize,
+    is_error: *mut bool,
+) -> *const c_char;
+
+/// Creates a new context, with its own global object.
+///
+/// It must be freed with `boa_context_free`.
+#[no_mangle]
+pub extern "C" fn boa_context_new() -> *mut BoaContext {
+    Box::into_raw(Box::new(BoaContext {
+        context: Context::new(),
+    }))
+}
+
+/// Frees a context created with `boa_context_new`. Passing `NULL` does nothing.
+///
+/// # Safety
+///
+/// `context` must be `NULL` or a context returned by `boa_context_new` that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn boa_context_free(context: *mut BoaContext) {
+    if !context.is_null() {
+        drop(Box::from_raw(context));
+    }
+}
+
+/// Runs a script, and then the microtasks it queued.
+///
+/// Returns the completion value of the script converted to a string, or the description of the
+/// error if the source is not valid, the script throws or the engine panics. After a panic, the
+/// globals that the scripts defined are kept, and the context can run other scripts.
+///
+/// # Safety
+///
+/// `context` must be a live context returned by `boa_context_new`, and `src` a NUL terminated
+/// string.
+#[no_mangle]
+pub unsafe extern "C" fn boa_eval(context: *mut BoaContext, src: *const c_char) -> BoaResult {
+    let context = match context.as_mut() {
+        Some(context) => &mut context.context,
+        None => return BoaResult::new(true, "the context is NULL"),
+    };
+    let src = match from_c_str(src) {
+        Ok(src) => src,
+        Err(message) => return BoaResult::new(true, message),
+    };
+
+    // Unwinding into the host is undefined behavior.
+    let result = panic::catch_unwind(AssertUnwindSafe(|| match context.eval(src) {
+        Ok(value) => BoaResult::new(false, &value.to_string()),
+        Err(value) => {
+            let error = JsError::from_thrown(value, context.interpreter());
+            BoaResult::new(true, &error.to_string())
+        }
+    }));
+    result.unwrap_or_else(|payload| {
+        context.interpreter_mut().reset_execution_state();
+        BoaResult::new(true, &panic_message(payload.as_ref()))
+    })
+}
+
+/// Frees a string returned by the library. Passing `NULL` does nothing.
+///
+/// # Safety
+///
+/// `string` must be `NULL` or a string returned by the library that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn boa_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Defines a global function named `name` that calls `callback` with `user_data`.
+///
+/// Returns `false` if the context or the name is not valid. If the engine panics while it
+/// converts the arguments, the function throws an error with the panic message.
+///
+/// # Safety
+///
+/// `context` must be a live context returned by `boa_context_new`, and `name` a NUL terminated
+/// string. `callback` is called with `user_data` as long as the context lives.
+#[no_mangle]
+pub unsafe extern "C" fn boa_register_function(
+    context: *mut BoaContext,
+    name: *const c_char,
+    callback: BoaCallback,
+    user_data: *mut c_void,
+) -> bool {
+    let context = match context.as_mut() {
+        Some(context) => &mut context.context,
+        None => return false,
+    };
+    let name = match from_c_str(name) {
+        Ok(name) => name,
+        Err(_) => return false,
+    };
+
+    context.register_global_function(name, 0, move |_, args, ctx| {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut strings = Vec::with_capacity(args.len());
+            for arg in args {
+                strings.push(to_c_string(&ctx.to_string(arg)?));
+            }
+            let argv: Vec<*const c_char> = strings.iter().map(|arg| arg.as_ptr()).collect();
+
+            let mut is_error = false;
+            let result = callback(user_data, argv.as_ptr(), argv.len(), &mut is_error);
+            let result = if result.is_null() {
+                Value::undefined()
+            } else {
+                Value::from(CStr::from_ptr(result).to_string_lossy().into_owned())
+            };
+
+            if is_error {
+                Err(result)
+            } else {
+                Ok(result)
+            }
+        }));
+        result.unwrap_or_else(|payload| ctx.throw_error(panic_message(payload.as_ref())))
+    });
+    true
+}
+
+/// Describes a panic of the engine, with its message if it has one.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str));
+    match message {
+        Some(message) => format!("the engine panicked: {}", message),
+        None => String::from("the engine panicked"),
+    }
+}
+
+/// Converts a string of the host, failing with a description of the problem.
+unsafe fn from_c_str<'a>(string: *const c_char) -> Result<&'a str, &'static str> {
+    if string.is_null() {
+        return Err("the string is NULL");
+    }
+    CStr::from_ptr(string)
+        .to_str()
+        .map_err(|_| "the string is not valid UTF-8")
+}
+
+/// Converts a string for the host, which sees it up to its first NUL character.
+fn to_c_string(string: &str) -> CString {
+    let end = string.find('\0').unwrap_or(string.len());
+    CString::new(&string[..end]).expect("the string was cut at its first NUL character")
+}
@@ -0,0 +1,107 @@
+use crate::{
+    boa_context_free, boa_context_new, boa_eval, boa_register_function, boa_string_free, BoaResult,
+};
+use std::{
+    ffi::{CStr, CString},
+    os::raw::{c_char, c_void},
+};
+
+/// Runs a script and returns whether it failed, with its result.
+unsafe fn eval(context: *mut crate::BoaContext, src: &str) -> (bool, String) {
+    let src = CString::new(src).unwrap();
+    let BoaResult { is_error, value } = boa_eval(context, src.as_ptr());
+    let result = CStr::from_ptr(value).to_str().unwrap().to_owned();
+    boa_string_free(value);
+    (is_error, result)
+}
+
+#[test]
+fn evaluates_scripts() {
+    unsafe {
+        let context = boa_context_new();
+        assert_eq!(eval(context, "var x = 40; x + 2"), (false, "42".to_owned()));
+        assert_eq!(eval(context, "'a' + x"), (false, "a40".to_owned()));
+        assert_eq!(
+            eval(context, "throw new TypeError('bad')"),
+            (true, "TypeError: bad".to_owned())
+        );
+        assert!(eval(context, "let = ;").0);
+        boa_context_free(context);
+    }
+}
+
+#[test]
+fn reports_panics() {
+    unsafe {
+        let mut buffer = CString::default();
+        let context = boa_context_new();
+        let name = CString::new("join").unwrap();
+        assert!(boa_register_function(
+            context,
+            name.as_ptr(),
+            join,
+            &mut buffer as *mut CString as *mut c_void
+        ));
+        assert_eq!(eval(context, "var x = 42; x"), (false, "42".to_owned()));
+
+        // Comparing a `BigInt` to a number is not implemented and panics.
+        let (is_error, message) = eval(context, "1n < 2");
+        assert!(is_error);
+        assert!(message.starts_with("the engine panicked"), "{}", message);
+
+        // The panics of the engine in host functions are thrown.
+        assert_eq!(
+            eval(
+                context,
+                "try { join({ toString() { return 1n < 2; } }) } catch (e) { 'caught' }"
+            ),
+            (false, "caught".to_owned())
+        );
+        assert_eq!(eval(context, "x"), (false, "42".to_owned()));
+        boa_context_free(context);
+    }
+}
+
+unsafe extern "C" fn join(
+    user_data: *mut c_void,
+    argv: *const *const c_char,
+    argc: usize,
+    is_error: *mut bool,
+) -> *const c_char {
+    let buffer = &mut *(user_data as *mut CString);
+    if argc == 0 {
+        *is_error = true;
+        *buffer = CString::new("no arguments").unwrap();
+        return buffer.as_ptr();
+    }
+    let args: Vec<_> = (0..argc)
+        .map(|i| CStr::from_ptr(*argv.add(i)).to_str().unwrap())
+        .collect();
+    *buffer = CString::new(args.join("-")).unwrap();
+    buffer.as_ptr()
+}
+
+#[test]
+fn calls_host_functions() {
+    unsafe {
+        let mut buffer = CString::default();
+        let context = boa_context_new();
+        let name = CString::new("join").unwrap();
+        assert!(boa_register_function(
+            context,
+            name.as_ptr(),
+            join,
+            &mut buffer as *mut CString as *mut c_void
+        ));
+
+        assert_eq!(
+            eval(context, "join('a', 1, true)"),
+            (false, "a-1-true".to_owned())
+        );
+        assert_eq!(
+            eval(context, "try { join() } catch (e) { 'caught ' + e }"),
+            (false, "caught no arguments".to_owned())
+        );
+        boa_context_free(context);
+    }
+}
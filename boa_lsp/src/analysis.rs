@@ -0,0 +1,338 @@
+//! The analysis of the documents: their syntax errors, the symbols they declare and the
+//! completions at a position.
+//!
+//! The documents are parsed with the parser of the engine, so only the statements have a span,
+//! and the symbols span the statement that declares them.
+
+use boa::{
+    builtins::{property::PropertyKey, value::Value},
+    parse,
+    syntax::ast::{
+        node::{FunctionDecl, StatementList},
+        Node, Position, Span,
+    },
+    Context, ParseOptions,
+};
+use std::sync::Arc;
+
+/// The keywords and the literals that are completed everywhere.
+const KEYWORDS: &[&str] = &[
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "in",
+    "instanceof",
+    "let",
+    "new",
+    "null",
+    "return",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+];
+
+/// A position in a document, as the protocol counts them: the lines and the UTF-16 code units
+/// in a line start at 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LspPosition {
+    pub(crate) line: u32,
+    pub(crate) character: u32,
+}
+
+/// A range of a document, whose end is excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LspRange {
+    pub(crate) start: LspPosition,
+    pub(crate) end: LspPosition,
+}
+
+/// A syntax error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Diagnostic {
+    pub(crate) range: LspRange,
+    pub(crate) message: String,
+}
+
+/// The kinds of symbols, with their numbers in the protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SymbolKind {
+    Function = 12,
+    Variable = 13,
+    Constant = 14,
+}
+
+/// A function or a variable declared by a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Symbol {
+    pub(crate) name: String,
+    pub(crate) kind: SymbolKind,
+    /// The range of the statement that declares the symbol.
+    pub(crate) range: LspRange,
+    /// The symbols declared in the body of a function.
+    pub(crate) children: Vec<Symbol>,
+}
+
+/// The kinds of completions, with their numbers in the protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CompletionKind {
+    Method = 2,
+    Function = 3,
+    Property = 10,
+    Variable = 6,
+    Keyword = 14,
+    Constant = 21,
+}
+
+/// A word that can be inserted at a position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Completion {
+    pub(crate) label: String,
+    pub(crate) kind: CompletionKind,
+}
+
+/// Converts a position of the engine, whose lines and columns start at 1 and count characters,
+/// to a position of the protocol.
+pub(crate) fn to_lsp(src: &str, position: Position) -> LspPosition {
+    let line = position.line_number() - 1;
+    let text = src.lines().nth(line as usize).unwrap_or_default();
+    let character = text
+        .chars()
+        .take(position.column_number() as usize - 1)
+        .map(char::len_utf16)
+        .sum::<usize>();
+    LspPosition {
+        line,
+        character: character as u32,
+    }
+}
+
+/// Converts the span of a statement, whose end is its last character, to a range.
+fn span_to_lsp(src: &str, span: Span) -> LspRange {
+    let mut end = to_lsp(src, span.end());
+    let last = src
+        .lines()
+        .nth(end.line as usize)
+        .and_then(|line| line.chars().nth(span.end().column_number() as usize - 1));
+    end.character += last.map_or(1, char::len_utf16) as u32;
+    LspRange {
+        start: to_lsp(src, span.start()),
+        end,
+    }
+}
+
+/// Parses a document and returns its syntax error, if it has one.
+///
+/// The parser stops at the first error, so there is at most one.
+pub(crate) fn diagnostics(src: &str) -> Vec<Diagnostic> {
+    match parse(src, ParseOptions::default()) {
+        Ok(_) => Vec::new(),
+        Err(e) => {
+            let start = e.position().map_or(
+                LspPosition {
+                    line: 0,
+                    character: 0,
+                },
+                |position| to_lsp(src, position),
+            );
+            let end = LspPosition {
+                line: start.line,
+                character: start.character + 1,
+            };
+            vec![Diagnostic {
+                range: LspRange { start, end },
+                message: e.to_string(),
+            }]
+        }
+    }
+}
+
+/// Returns the functions and the variables declared by the statements of a document, in the
+/// order of the source, or nothing if the document has a syntax error.
+pub(crate) fn symbols(src: &str) -> Vec<Symbol> {
+    parse(src, ParseOptions::default())
+        .map(|statements| list_symbols(src, &statements))
+        .unwrap_or_default()
+}
+
+/// Returns the symbols declared by the statements of a list.
+fn list_symbols(src: &str, list: &StatementList) -> Vec<Symbol> {
+    let mut symbols = Vec::new();
+    for (statement, &span) in list.statements().iter().zip(list.spans()) {
+        let range = span_to_lsp(src, span);
+        // The variables initialized with a function are shown as functions.
+        let symbol = |name: &str, kind, init: Option<&Node>| {
+            let body = init.and_then(function_body);
+            Symbol {
+                name: name.to_owned(),
+                kind: if body.is_some() {
+                    SymbolKind::Function
+                } else {
+                    kind
+                },
+                range,
+                children: body
+                    .map(|body| list_symbols(src, &body))
+                    .unwrap_or_default(),
+            }
+        };
+        match statement {
+            Node::FunctionDecl(decl) => symbols.push(Symbol {
+                name: decl.name().to_owned(),
+                kind: SymbolKind::Function,
+                range,
+                children: decl_symbols(src, decl),
+            }),
+            Node::VarDeclList(list) => symbols.extend(
+                list.as_ref()
+                    .iter()
+                    .map(|decl| symbol(decl.name(), SymbolKind::Variable, decl.init())),
+            ),
+            Node::LetDeclList(list) => symbols.extend(
+                list.as_ref()
+                    .iter()
+                    .map(|decl| symbol(decl.name(), SymbolKind::Variable, decl.init())),
+            ),
+            Node::ConstDeclList(list) => symbols.extend(
+                list.as_ref()
+                    .iter()
+                    .map(|decl| symbol(decl.name(), SymbolKind::Constant, Some(decl.init()))),
+            ),
+            _ => {}
+        }
+    }
+    // The function declarations are hoisted to the start of the list.
+    symbols.sort_by_key(|symbol| (symbol.range.start.line, symbol.range.start.character));
+    symbols
+}
+
+/// Returns the symbols declared in the body of a function declaration.
+fn decl_symbols(src: &str, decl: &FunctionDecl) -> Vec<Symbol> {
+    decl.body()
+        .statements()
+        .map(|body| list_symbols(src, &body))
+        .unwrap_or_default()
+}
+
+/// Returns the body of a function expression.
+fn function_body(node: &Node) -> Option<Arc<StatementList>> {
+    let body = match node {
+        Node::FunctionExpr(expr) => expr.body(),
+        Node::ArrowFunctionDecl(arrow) => arrow.body(),
+        _ => return None,
+    };
+    body.statements().ok()
+}
+
+/// Returns the completions at a position of a document.
+///
+/// After `name.`, where `name` is a global object like `Math`, the completions are the
+/// properties of the object. Elsewhere, they are the keywords, the globals of `context` and the
+/// symbols of the document.
+pub(crate) fn completions(src: &str, position: LspPosition, context: &Context) -> Vec<Completion> {
+    let line = src.lines().nth(position.line as usize).unwrap_or_default();
+    let mut units = 0;
+    let before: String = line
+        .chars()
+        .take_while(|c| {
+            units += c.len_utf16();
+            units <= position.character as usize
+        })
+        .collect();
+    let before = before.trim_end_matches(is_identifier_char);
+
+    if let Some(object) = before.strip_suffix('.') {
+        let name = &object[object
+            .rfind(|c| !is_identifier_char(c))
+            .map_or(0, |index| index + 1)..];
+        let global = context.global_object();
+        return match global.as_object().and_then(|g| own_property(&g, name)) {
+            Some(object) => properties(&object, CompletionKind::Property),
+            None => Vec::new(),
+        };
+    }
+
+    let mut completions: Vec<_> = KEYWORDS
+        .iter()
+        .map(|&keyword| Completion {
+            label: keyword.to_owned(),
+            kind: CompletionKind::Keyword,
+        })
+        .collect();
+    completions.extend(properties(
+        context.global_object(),
+        CompletionKind::Variable,
+    ));
+    let mut pending = symbols(src);
+    while let Some(symbol) = pending.pop() {
+        completions.push(Completion {
+            label: symbol.name,
+            kind: match symbol.kind {
+                SymbolKind::Function => CompletionKind::Function,
+                SymbolKind::Variable => CompletionKind::Variable,
+                SymbolKind::Constant => CompletionKind::Constant,
+            },
+        });
+        pending.extend(symbol.children);
+    }
+    completions.sort_by(|a, b| a.label.cmp(&b.label));
+    completions.dedup_by(|a, b| a.label == b.label);
+    completions
+}
+
+/// Checks whether a character can be part of an identifier.
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Gets an own property of an object by its name.
+fn own_property(object: &boa::builtins::object::Object, name: &str) -> Option<Value> {
+    object
+        .property(&PropertyKey::from(name))
+        .and_then(|property| property.value.clone())
+}
+
+/// Returns the names of the own string properties of a value, as completions of the given
+/// kind, or of the `Method` kind for the functions.
+fn properties(value: &Value, kind: CompletionKind) -> Vec<Completion> {
+    let object = match value.as_object() {
+        Some(object) => object,
+        None => return Vec::new(),
+    };
+    object
+        .own_property_keys()
+        .into_iter()
+        .filter_map(|key| match key {
+            PropertyKey::String(ref name) => {
+                let is_function = own_property(&object, name).is_some_and(|v| v.is_function());
+                Some(Completion {
+                    label: name.to_string(),
+                    kind: match kind {
+                        CompletionKind::Property if is_function => CompletionKind::Method,
+                        CompletionKind::Variable if is_function => CompletionKind::Function,
+                        kind => kind,
+                    },
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
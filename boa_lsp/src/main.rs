@@ -0,0 +1,239 @@
+//! A [language server](https://microsoft.github.io/language-server-protocol) for JavaScript,
+//! built on the parser of Boa.
+//!
+//! The server talks to the editor on its standard input and output, and offers:
+//!
+//!  - the syntax errors of the open documents, as diagnostics;
+//!  - the functions and variables they declare, as document symbols;
+//!  - the completion of keywords, of the globals of the engine and of the symbols of the
+//!    document, and of the properties of a global object after `Math.` for example.
+//!
+//! Editors start it like any other language server:
+//!
+//! ```text
+//! cargo build --release --bin boa_lsp
+//! ```
+
+#![deny(
+    unused_qualifications,
+    clippy::all,
+    unused_import_braces,
+    unused_lifetimes,
+    unreachable_pub,
+    trivial_numeric_casts,
+    // rustdoc,
+    missing_debug_implementations,
+    missing_copy_implementations,
+    deprecated_in_future,
+    non_ascii_idents,
+    rust_2018_compatibility,
+    rust_2018_idioms,
+    future_incompatible,
+    nonstandard_style
+)]
+#![warn(clippy::perf, clippy::single_match_else, clippy::dbg_macro)]
+#![allow(
+    clippy::missing_inline_in_public_items,
+    clippy::cognitive_complexity,
+    clippy::must_use_candidate,
+    clippy::missing_errors_doc,
+    clippy::as_conversions
+)]
+
+mod analysis;
+mod protocol;
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    analysis::{completions, diagnostics, symbols, LspPosition, LspRange, Symbol},
+    protocol::{read_message, write_message},
+};
+use boa::Context;
+use serde_json::{json, Value as JSONValue};
+use std::{
+    collections::HashMap,
+    io::{self, BufReader},
+    process,
+};
+
+/// The error code of the requests whose method is not supported.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// The state of the server: the open documents, and a context for the completion of globals.
+#[derive(Debug)]
+struct Server {
+    /// The text of each open document, by URI.
+    documents: HashMap<String, String>,
+    context: Context,
+    /// Whether the editor asked the server to shut down.
+    shutdown: bool,
+}
+
+impl Server {
+    /// Creates a server with no open documents.
+    fn new() -> Self {
+        Self {
+            documents: HashMap::new(),
+            context: Context::new(),
+            shutdown: false,
+        }
+    }
+
+    /// Handles a request or a notification, and returns the messages to send back.
+    fn handle(&mut self, message: &JSONValue) -> Vec<JSONValue> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let uri = || {
+            params["textDocument"]["uri"]
+                .as_str()
+                .unwrap_or_default()
+                .to_owned()
+        };
+        let result = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "documentSymbolProvider": true,
+                    "completionProvider": { "triggerCharacters": ["."] },
+                },
+                "serverInfo": {
+                    "name": "boa_lsp",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            }),
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                return self.update(uri(), text.to_owned());
+            }
+            "textDocument/didChange" => {
+                // The documents are synchronized in full, so the last change is the whole text.
+                let changes = params["contentChanges"].as_array();
+                let text = changes
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                    .unwrap_or_default();
+                return self.update(uri(), text.to_owned());
+            }
+            "textDocument/didClose" => {
+                let uri = uri();
+                self.documents.remove(&uri);
+                return vec![publish_diagnostics(&uri, "")];
+            }
+            "textDocument/documentSymbol" => {
+                let text = self.documents.get(&uri()).map_or("", String::as_str);
+                JSONValue::Array(symbols(text).iter().map(symbol_to_json).collect())
+            }
+            "textDocument/completion" => {
+                let text = self.documents.get(&uri()).map_or("", String::as_str);
+                let position = LspPosition {
+                    line: params["position"]["line"].as_u64().unwrap_or_default() as u32,
+                    character: params["position"]["character"].as_u64().unwrap_or_default() as u32,
+                };
+                completions(text, position, &self.context)
+                    .into_iter()
+                    .map(|completion| {
+                        json!({
+                            "label": completion.label,
+                            "kind": completion.kind as u8,
+                        })
+                    })
+                    .collect()
+            }
+            "shutdown" => {
+                self.shutdown = true;
+                JSONValue::Null
+            }
+            "exit" => process::exit(if self.shutdown { 0 } else { 1 }),
+            _ => {
+                // The notifications that are not supported, like `initialized`, are ignored.
+                return match message.get("id") {
+                    Some(id) => vec![json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": {
+                            "code": METHOD_NOT_FOUND,
+                            "message": format!("unsupported method: {}", method),
+                        },
+                    })],
+                    None => Vec::new(),
+                };
+            }
+        };
+        vec![json!({ "jsonrpc": "2.0", "id": message["id"], "result": result })]
+    }
+
+    /// Stores the new text of a document, and returns its diagnostics.
+    fn update(&mut self, uri: String, text: String) -> Vec<JSONValue> {
+        let message = publish_diagnostics(&uri, &text);
+        self.documents.insert(uri, text);
+        vec![message]
+    }
+}
+
+/// Builds the notification of the diagnostics of a document.
+fn publish_diagnostics(uri: &str, text: &str) -> JSONValue {
+    let diagnostics: Vec<_> = diagnostics(text)
+        .into_iter()
+        .map(|diagnostic| {
+            json!({
+                "range": range_to_json(diagnostic.range),
+                "severity": 1,
+                "source": "boa",
+                "message": diagnostic.message,
+            })
+        })
+        .collect();
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    })
+}
+
+/// Converts a range to its JSON representation.
+fn range_to_json(range: LspRange) -> JSONValue {
+    json!({
+        "start": { "line": range.start.line, "character": range.start.character },
+        "end": { "line": range.end.line, "character": range.end.character },
+    })
+}
+
+/// Converts a symbol to a `DocumentSymbol`.
+fn symbol_to_json(symbol: &Symbol) -> JSONValue {
+    let children: Vec<_> = symbol.children.iter().map(symbol_to_json).collect();
+    json!({
+        "name": symbol.name,
+        "kind": symbol.kind as u8,
+        "range": range_to_json(symbol.range),
+        "selectionRange": range_to_json(symbol.range),
+        "children": children,
+    })
+}
+
+fn main() {
+    let stdin = io::stdin();
+    let mut reader = BufReader::new(stdin.lock());
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+    let mut server = Server::new();
+
+    loop {
+        let message = match read_message(&mut reader) {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(e) => {
+                eprintln!("boa_lsp: {}", e);
+                process::exit(1);
+            }
+        };
+        for response in server.handle(&message) {
+            if let Err(e) = write_message(&mut writer, &response) {
+                eprintln!("boa_lsp: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+    // The input ended without an `exit` notification.
+    process::exit(if server.shutdown { 0 } else { 1 });
+}
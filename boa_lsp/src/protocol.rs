@@ -0,0 +1,47 @@
+//! The base protocol of the language server protocol: JSON-RPC messages, each preceded by a
+//! `Content-Length` header.
+
+use serde_json::Value as JSONValue;
+use std::io::{self, BufRead, Write};
+
+/// Reads the next message, or `None` at the end of the input.
+pub(crate) fn read_message<R: BufRead>(reader: &mut R) -> io::Result<Option<JSONValue>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        // The other header, `Content-Type`, always has the default value in practice.
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let length = length.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length header")
+    })?;
+
+    let mut content = vec![0; length];
+    reader.read_exact(&mut content)?;
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Writes a message, with its header.
+pub(crate) fn write_message<W: Write>(writer: &mut W, message: &JSONValue) -> io::Result<()> {
+    let content = message.to_string();
+    write!(
+        writer,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )?;
+    writer.flush()
+}
@@ -0,0 +1,184 @@
+use crate::{
+    analysis::{
+        completions, diagnostics, symbols, CompletionKind, LspPosition, LspRange, SymbolKind,
+    },
+    protocol::{read_message, write_message},
+    Server,
+};
+use boa::Context;
+use serde_json::json;
+
+/// Builds a range on a single line.
+fn range(line: u32, start: u32, end: u32) -> LspRange {
+    LspRange {
+        start: LspPosition {
+            line,
+            character: start,
+        },
+        end: LspPosition {
+            line,
+            character: end,
+        },
+    }
+}
+
+#[test]
+fn reports_syntax_errors() {
+    assert!(diagnostics("let a = 1;\nfunction f() { return a; }").is_empty());
+
+    let errors = diagnostics("let a = 1;\nlet = ;");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].range.start.line, 1);
+    assert!(!errors[0].message.is_empty());
+}
+
+#[test]
+fn counts_utf16_code_units() {
+    let errors = diagnostics("/* 😀 */ )");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].range, range(0, 9, 10));
+}
+
+#[test]
+fn lists_symbols() {
+    let src =
+        "var a = 1, b;\nconst c = () => {\n    let d = 2;\n};\nfunction f(x) {\n    var y = x;\n}";
+    let found = symbols(src);
+    let names: Vec<_> = found
+        .iter()
+        .map(|symbol| (symbol.name.as_str(), symbol.kind))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            ("a", SymbolKind::Variable),
+            ("b", SymbolKind::Variable),
+            ("c", SymbolKind::Function),
+            ("f", SymbolKind::Function),
+        ]
+    );
+    assert_eq!(found[0].range, range(0, 0, 13));
+    assert_eq!(found[2].children[0].name, "d");
+    assert_eq!(found[3].children[0].name, "y");
+    assert_eq!(found[3].range.start.line, 4);
+
+    // A document with a syntax error has no symbols.
+    assert!(symbols("var a = ;").is_empty());
+}
+
+#[test]
+fn completes_globals_keywords_and_symbols() {
+    let context = Context::new();
+    let src = "let total = 0;\nfunction add(x) {}\nt";
+    let found = completions(
+        src,
+        LspPosition {
+            line: 2,
+            character: 1,
+        },
+        &context,
+    );
+    let kind = |label: &str| {
+        found
+            .iter()
+            .find(|completion| completion.label == label)
+            .map(|completion| completion.kind)
+    };
+    assert_eq!(kind("total"), Some(CompletionKind::Variable));
+    assert_eq!(kind("add"), Some(CompletionKind::Function));
+    assert_eq!(kind("typeof"), Some(CompletionKind::Keyword));
+    assert_eq!(kind("Math"), Some(CompletionKind::Variable));
+    assert_eq!(kind("parseInt"), Some(CompletionKind::Function));
+    assert!(found.windows(2).all(|w| w[0].label < w[1].label));
+}
+
+#[test]
+fn completes_properties_of_globals() {
+    let context = Context::new();
+    let found = completions(
+        "let x = Math.fl",
+        LspPosition {
+            line: 0,
+            character: 15,
+        },
+        &context,
+    );
+    assert!(found
+        .iter()
+        .any(|c| c.label == "floor" && c.kind == CompletionKind::Method));
+    assert!(found
+        .iter()
+        .any(|c| c.label == "PI" && c.kind == CompletionKind::Property));
+    assert!(!found.iter().any(|c| c.label == "typeof"));
+
+    let unknown = LspPosition {
+        line: 0,
+        character: 8,
+    };
+    assert!(completions("unknown.", unknown, &context).is_empty());
+}
+
+#[test]
+fn frames_messages() {
+    let mut buffer = Vec::new();
+    write_message(&mut buffer, &json!({ "id": 1, "text": "é" })).unwrap();
+    assert!(buffer.starts_with(b"Content-Length: 20\r\n\r\n"));
+
+    let mut reader = &buffer[..];
+    assert_eq!(
+        read_message(&mut reader).unwrap(),
+        Some(json!({ "id": 1, "text": "é" }))
+    );
+    assert_eq!(read_message(&mut reader).unwrap(), None);
+
+    let mut missing = &b"Content-Type: x\r\n\r\n{}"[..];
+    assert!(read_message(&mut missing).is_err());
+}
+
+#[test]
+fn server_handles_requests() {
+    let mut server = Server::new();
+    let initialize = server.handle(&json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize" }));
+    assert_eq!(
+        initialize[0]["result"]["capabilities"]["documentSymbolProvider"],
+        true
+    );
+
+    let opened = server.handle(&json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": { "textDocument": { "uri": "file:///a.js", "text": "var a = ;" } },
+    }));
+    assert_eq!(opened[0]["method"], "textDocument/publishDiagnostics");
+    assert_eq!(opened[0]["params"]["diagnostics"][0]["severity"], 1);
+
+    let changed = server.handle(&json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didChange",
+        "params": {
+            "textDocument": { "uri": "file:///a.js" },
+            "contentChanges": [{ "text": "function f() {}" }],
+        },
+    }));
+    assert_eq!(changed[0]["params"]["diagnostics"], json!([]));
+
+    let symbols = server.handle(&json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "textDocument/documentSymbol",
+        "params": { "textDocument": { "uri": "file:///a.js" } },
+    }));
+    assert_eq!(symbols[0]["id"], 2);
+    assert_eq!(symbols[0]["result"][0]["name"], "f");
+    assert_eq!(symbols[0]["result"][0]["kind"], 12);
+
+    let unknown = server.handle(&json!({ "jsonrpc": "2.0", "id": 3, "method": "foo" }));
+    assert_eq!(unknown[0]["error"]["code"], -32601);
+    assert!(server
+        .handle(&json!({ "jsonrpc": "2.0", "method": "initialized" }))
+        .is_empty());
+
+    let shutdown = server.handle(&json!({ "jsonrpc": "2.0", "id": 4, "method": "shutdown" }));
+    assert_eq!(shutdown[0]["result"], json!(null));
+    assert!(server.shutdown);
+}
@@ -0,0 +1,176 @@
+//! The dot-commands of the REPL, like `.snapshot` or `.watch`.
+
+use crate::Painter;
+use boa::{
+    allocation_sites::AllocationSites,
+    builtins::value::{InspectOptions, ResultValue, Value},
+    exec::Interpreter,
+    heap_snapshot::HeapSnapshot,
+    Context,
+};
+use std::{fs::write, io, io::Write};
+
+/// The dot-commands and their descriptions, which `.help` lists.
+const COMMANDS: &[(&str, &str)] = &[
+    (
+        ".allocations",
+        "Print the sites that allocated the most objects",
+    ),
+    (".clear", "Replace the global state with a fresh realm"),
+    (
+        ".editor",
+        "Enter the editor mode to write several lines at once",
+    ),
+    (".exit", "End the session"),
+    (".help", "Print this list of commands"),
+    (".load <file>", "Evaluate a file in the session"),
+    (".more", "Show the next items of the last result"),
+    (
+        ".save <file>",
+        "Write the code evaluated in the session to a file",
+    ),
+    (".snapshot <file>", "Write a heap snapshot to a file"),
+    (".unwatch", "Remove every watchpoint"),
+    (
+        ".watch <object>.<property>",
+        "Report the reads and writes of a property",
+    ),
+];
+
+/// Runs the `.help` command, which lists the dot-commands.
+pub(crate) fn help(out: &mut dyn Write) -> io::Result<()> {
+    for (command, description) in COMMANDS {
+        writeln!(out, "{:<28}{}", command, description)?;
+    }
+    Ok(())
+}
+
+/// Runs the `.save <file>` command, which writes the sources evaluated in the session to the
+/// file, one after the other.
+pub(crate) fn save(session: &[String], path: &str, out: &mut dyn Write) -> io::Result<()> {
+    if path.is_empty() {
+        return writeln!(out, "Usage: .save <file>");
+    }
+
+    let mut contents = session.join("\n");
+    contents.push('\n');
+    match write(path, contents) {
+        Ok(()) => writeln!(out, "Session saved to {}", path),
+        Err(err) => writeln!(out, "Could not write {}: {}", path, err),
+    }
+}
+
+/// Runs the `.allocations` command, which prints the sites that allocated the most objects.
+pub(crate) fn allocations(context: &Context, out: &mut dyn Write) -> io::Result<()> {
+    if !AllocationSites::is_enabled() {
+        return writeln!(
+            out,
+            "Allocation sites are not recorded, build with `--features allocation-sites`"
+        );
+    }
+
+    for site in context.interpreter().allocation_sites().top(10) {
+        writeln!(out, "{:>8}  {}", site.count, site.description)?;
+    }
+    Ok(())
+}
+
+/// Runs the `.snapshot <file>` command.
+///
+/// Writes a heap snapshot to the file, in the V8 format if the file name ends with
+/// `.heapsnapshot`, and in the JSON format of `HeapSnapshot::to_json` otherwise.
+pub(crate) fn snapshot(context: &Context, path: &str, out: &mut dyn Write) -> io::Result<()> {
+    if path.is_empty() {
+        return writeln!(out, "Usage: .snapshot <file>");
+    }
+
+    let snapshot = HeapSnapshot::capture(context.interpreter());
+    let contents = if path.ends_with(".heapsnapshot") {
+        snapshot.to_heapsnapshot()
+    } else {
+        snapshot.to_json()
+    };
+    match write(path, contents.to_string()) {
+        Ok(()) => writeln!(
+            out,
+            "Wrote {} objects to {}",
+            snapshot.nodes().len() - 1,
+            path
+        ),
+        Err(err) => writeln!(out, "Could not write {}: {}", path, err),
+    }
+}
+
+/// Runs the `.watch <object>.<property>` command.
+///
+/// Evaluates the object expression and reports every following read and write of the property.
+pub(crate) fn watch(
+    repl: &mut crate::Repl,
+    context: &mut Context,
+    target: &str,
+    painter: Painter,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let (object, property) = match target.rfind('.') {
+        Some(index) if index > 0 && index + 1 < target.len() => {
+            (&target[..index], &target[index + 1..])
+        }
+        _ => return writeln!(out, "Usage: .watch <object>.<property>"),
+    };
+
+    match context.eval(object) {
+        Ok(value) => match context
+            .interpreter_mut()
+            .debugger_mut()
+            .watch(&value, property)
+        {
+            Some(id) => {
+                repl.watch_labels.borrow_mut().insert(id, target.to_owned());
+                Ok(())
+            }
+            None => writeln!(out, "{} is not an object", object),
+        },
+        Err(v) => painter.uncaught(out, &v),
+    }
+}
+
+/// Runs the `.unwatch` command, which removes every watchpoint.
+pub(crate) fn unwatch(repl: &mut crate::Repl, context: &mut Context) {
+    context.interpreter_mut().debugger_mut().clear_watchpoints();
+    repl.watch_labels.borrow_mut().clear();
+}
+
+/// The global `inspect(value, { depth, maxItems })` function.
+///
+/// Formats the value like the results of the REPL, with the given limits, which default to the
+/// ones of the REPL. `Infinity` removes a limit.
+pub(crate) fn inspect(
+    args: &[Value],
+    defaults: InspectOptions,
+    ctx: &mut Interpreter,
+) -> ResultValue {
+    let value = args.first().cloned().unwrap_or_else(Value::undefined);
+    let mut options = defaults;
+    if let Some(object) = args.get(1).filter(|options| options.is_object()) {
+        let limit = |ctx: &mut Interpreter, name: &str| -> Result<Option<usize>, Value> {
+            let limit = ctx.get_field(object, name)?;
+            if limit.is_undefined() {
+                return Ok(None);
+            }
+            let limit = ctx.to_number(&limit)?;
+            if limit.is_nan() || limit < 0.0 {
+                return Err(
+                    ctx.construct_range_error(format!("{} must be a positive number", name))
+                );
+            }
+            Ok(Some(limit as usize))
+        };
+        if let Some(depth) = limit(ctx, "depth")? {
+            options.depth = depth;
+        }
+        if let Some(max_items) = limit(ctx, "maxItems")? {
+            options.max_items = max_items;
+        }
+    }
+    Ok(Value::from(value.inspect(&options).text))
+}
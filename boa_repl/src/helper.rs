@@ -0,0 +1,357 @@
+//! The line editor helper of the terminal REPL, which highlights the input, completes the names
+//! of globals and properties, and validates that the source is complete before it is evaluated.
+
+use boa::{
+    builtins::value::Value,
+    environment::lexical_environment::Environment,
+    syntax::{ast::token::TokenKind, lexer::Lexer},
+};
+use colored::*;
+use lazy_static::lazy_static;
+use rustyline::{
+    completion::Completer,
+    error::ReadlineError,
+    highlight::Highlighter,
+    validate::{ValidationContext, ValidationResult, Validator},
+    Context,
+};
+use rustyline_derive::{Helper, Hinter};
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
+
+#[derive(Helper, Hinter)]
+pub(crate) struct RLHelper {
+    highlighter: LineHighlighter,
+    completer: GlobalCompleter,
+}
+
+impl RLHelper {
+    pub(crate) fn new(completer: GlobalCompleter) -> Self {
+        Self {
+            highlighter: LineHighlighter,
+            completer,
+        }
+    }
+
+    /// Completes the bindings of the given global environment from now on.
+    pub(crate) fn set_environment(&mut self, environment: Environment) {
+        self.completer = GlobalCompleter::new(environment);
+    }
+}
+
+impl Completer for RLHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> Result<(usize, Vec<String>), ReadlineError> {
+        Ok(self.completer.complete(&line[..pos]))
+    }
+}
+
+/// Keeps reading lines while the parser reports that the source ends too early, see
+/// `boa::is_incomplete`.
+///
+/// A pasted snippet is inserted as a whole on terminals that support bracketed paste, so it is
+/// only evaluated once Enter is pressed after it, if it is complete by then. Otherwise, each of
+/// its lines is validated like a typed line, and joined with the next ones until it is complete.
+impl Validator for RLHelper {
+    fn validate(&self, ctx: &mut ValidationContext<'_>) -> Result<ValidationResult, ReadlineError> {
+        if boa::is_incomplete(ctx.input()) {
+            Ok(ValidationResult::Incomplete)
+        } else {
+            Ok(ValidationResult::Valid(None))
+        }
+    }
+}
+
+impl Highlighter for RLHelper {
+    fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
+        hint.into()
+    }
+
+    fn highlight<'l>(&self, line: &'l str, pos: usize) -> Cow<'l, str> {
+        self.highlighter.highlight(line, pos)
+    }
+
+    fn highlight_candidate<'c>(
+        &self,
+        candidate: &'c str,
+        _completion: rustyline::CompletionType,
+    ) -> Cow<'c, str> {
+        self.highlighter.highlight(candidate, 0)
+    }
+
+    fn highlight_char(&self, line: &str, _: usize) -> bool {
+        !line.is_empty()
+    }
+}
+
+lazy_static! {
+    static ref KEYWORDS: HashSet<&'static str> = {
+        let mut keywords = HashSet::new();
+        keywords.insert("break");
+        keywords.insert("case");
+        keywords.insert("catch");
+        keywords.insert("class");
+        keywords.insert("const");
+        keywords.insert("continue");
+        keywords.insert("default");
+        keywords.insert("delete");
+        keywords.insert("do");
+        keywords.insert("else");
+        keywords.insert("export");
+        keywords.insert("extends");
+        keywords.insert("finally");
+        keywords.insert("for");
+        keywords.insert("function");
+        keywords.insert("if");
+        keywords.insert("import");
+        keywords.insert("instanceof");
+        keywords.insert("new");
+        keywords.insert("return");
+        keywords.insert("super");
+        keywords.insert("switch");
+        keywords.insert("this");
+        keywords.insert("throw");
+        keywords.insert("try");
+        keywords.insert("typeof");
+        keywords.insert("var");
+        keywords.insert("void");
+        keywords.insert("while");
+        keywords.insert("with");
+        keywords.insert("yield");
+        keywords.insert("await");
+        keywords.insert("enum");
+        keywords.insert("let");
+        keywords
+    };
+}
+
+/// Completes the names of the globals and the keywords, and the properties of an object after
+/// a `.`, like `Math.fl` or `obj.nested.`.
+///
+/// The completer holds the global environment of the context, so it sees the bindings and the
+/// objects as they are when the completion is requested. It only reads data properties, so no
+/// script code runs while typing, and objects behind calls or getters are not completed.
+pub(crate) struct GlobalCompleter {
+    environment: Environment,
+}
+
+impl GlobalCompleter {
+    pub(crate) fn new(environment: Environment) -> Self {
+        Self { environment }
+    }
+
+    /// Returns where the completed word starts in the line, and the candidates to replace it,
+    /// sorted.
+    pub(crate) fn complete(&self, line: &str) -> (usize, Vec<String>) {
+        let start = line
+            .char_indices()
+            .rev()
+            .take_while(|&(_, c)| is_identifier_char(c) || c == '.')
+            .last()
+            .map_or(line.len(), |(index, _)| index);
+        let path = &line[start..];
+        // Dot-commands, and properties of values that are not names, aren't completed.
+        if path.starts_with('.') {
+            return (line.len(), Vec::new());
+        }
+
+        let mut segments: Vec<_> = path.split('.').collect();
+        let word = segments.pop().unwrap_or_default();
+        let candidates = match segments.split_first() {
+            None => self.global_names(),
+            Some((first, rest)) => {
+                let mut value = match self.global(first) {
+                    Some(value) => value,
+                    None => return (line.len(), Vec::new()),
+                };
+                for segment in rest {
+                    value = match data_property(&value, segment) {
+                        Some(value) => value,
+                        None => return (line.len(), Vec::new()),
+                    };
+                }
+                property_names(&value)
+            }
+        };
+
+        let candidates = candidates
+            .into_iter()
+            .filter(|name| name.starts_with(word) && is_identifier(name))
+            .collect();
+        (line.len() - word.len(), candidates)
+    }
+
+    /// Gets the names of the global bindings and the keywords.
+    fn global_names(&self) -> BTreeSet<String> {
+        let environment = self.environment.borrow();
+        let mut names: BTreeSet<_> = KEYWORDS.iter().map(|&keyword| keyword.to_owned()).collect();
+        names.extend(
+            environment
+                .declarative_bindings()
+                .into_iter()
+                .map(|(name, _)| name),
+        );
+        if let Some(global) = environment.get_global_object() {
+            if let Some(global) = global.as_object() {
+                names.extend(global.properties().keys().map(ToString::to_string));
+            }
+        }
+        names
+    }
+
+    /// Gets the value of a global binding.
+    fn global(&self, name: &str) -> Option<Value> {
+        let environment = self.environment.borrow();
+        let declarative = environment
+            .declarative_bindings()
+            .into_iter()
+            .find(|(binding, _)| binding == name);
+        match declarative {
+            Some((_, binding)) => binding.value.clone(),
+            None => data_property(&environment.get_global_object()?, name),
+        }
+    }
+}
+
+/// Gets the value of a data property of an object or of its prototypes.
+fn data_property(value: &Value, key: &str) -> Option<Value> {
+    let mut value = value.clone();
+    loop {
+        let prototype = {
+            let object = value.as_object()?;
+            if let Some(property) = object.properties().get(key) {
+                return if property.is_data_descriptor() {
+                    property.value.clone()
+                } else {
+                    None
+                };
+            }
+            object.prototype().clone()
+        };
+        value = prototype;
+    }
+}
+
+/// Gets the names of the properties of an object and of its prototypes.
+fn property_names(value: &Value) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let mut value = value.clone();
+    loop {
+        let prototype = match value.as_object() {
+            Some(object) => {
+                names.extend(object.properties().keys().map(ToString::to_string));
+                object.prototype().clone()
+            }
+            None => return names,
+        };
+        value = prototype;
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Checks if a property name can be written after a `.`.
+fn is_identifier(name: &str) -> bool {
+    !name.starts_with(|c: char| c.is_ascii_digit()) && name.chars().all(is_identifier_char)
+}
+
+/// Highlights the input by the kind of its tokens.
+///
+/// The lexer gives the end of each token, and the whitespace and comments before a token are
+/// skipped here to find where it starts. Once the lexer fails, e.g. on an unterminated string,
+/// the rest of the input is red.
+pub(crate) struct LineHighlighter;
+
+impl Highlighter for LineHighlighter {
+    fn highlight<'l>(&self, line: &'l str, _: usize) -> Cow<'l, str> {
+        let chars: Vec<char> = line.chars().collect();
+        let mut coloured = String::with_capacity(line.len());
+        let mut lexer = Lexer::new(line);
+        let mut end = 0;
+        loop {
+            let token = lexer.next();
+            let start = skip_trivia(&chars, end);
+            coloured.push_str(&paint_trivia(&chars[end..start]));
+            match token {
+                Some(Ok(token)) => {
+                    end = lexer.offset().max(start);
+                    let text: String = chars[start..end].iter().collect();
+                    coloured.push_str(&paint_token(token.kind(), text));
+                }
+                Some(Err(_)) => {
+                    let text: String = chars[start..].iter().collect();
+                    coloured.push_str(&text.red().to_string());
+                    break;
+                }
+                None => {
+                    coloured.extend(&chars[start..]);
+                    break;
+                }
+            }
+        }
+        coloured.into()
+    }
+}
+
+/// Skips the whitespace and the comments that start at `index`, and returns the index of the
+/// next token.
+fn skip_trivia(chars: &[char], mut index: usize) -> usize {
+    loop {
+        match (chars.get(index), chars.get(index + 1)) {
+            (Some(c), _) if c.is_whitespace() => index += 1,
+            (Some('/'), Some('/')) => {
+                while index < chars.len() && chars[index] != '\n' {
+                    index += 1;
+                }
+            }
+            (Some('/'), Some('*')) => {
+                index = chars[index + 2..]
+                    .windows(2)
+                    .position(|pair| pair == ['*', '/'])
+                    .map_or(chars.len(), |position| index + 2 + position + 2);
+            }
+            _ => return index,
+        }
+    }
+}
+
+/// Colors the comments between two tokens.
+fn paint_trivia(trivia: &[char]) -> String {
+    let text: String = trivia.iter().collect();
+    let comments = text.trim();
+    if comments.is_empty() {
+        return text;
+    }
+    let start = text.len() - text.trim_start().len();
+    format!(
+        "{}{}{}",
+        &text[..start],
+        comments.dimmed(),
+        &text[start + comments.len()..]
+    )
+}
+
+/// Colors the source text of a token.
+fn paint_token(kind: &TokenKind, text: String) -> String {
+    match kind {
+        TokenKind::Keyword(_) => text.yellow().bold().to_string(),
+        TokenKind::BooleanLiteral(_) | TokenKind::NullLiteral => text.purple().to_string(),
+        TokenKind::Identifier(name) => match &**name {
+            "Infinity" | "NaN" => text.purple().to_string(),
+            "undefined" => text.truecolor(100, 100, 100).to_string(),
+            _ => text,
+        },
+        TokenKind::NumericLiteral(_) => text.cyan().to_string(),
+        TokenKind::StringLiteral(_) | TokenKind::TemplateLiteral(_) => text.green().to_string(),
+        TokenKind::RegularExpressionLiteral(..) => text.blue().to_string(),
+        TokenKind::Punctuator(_) | TokenKind::LineTerminator | TokenKind::EOF => text,
+    }
+}
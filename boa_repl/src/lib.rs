@@ -0,0 +1,624 @@
+//! An interactive console for the Boa JavaScript engine.
+//!
+//! `Repl` reads lines of JavaScript, evaluates them in a `Context` and prints the results. It
+//! runs either on the terminal, with highlighting, history and multi-line editing, or over any
+//! pair of input and output streams, which lets applications offer a console over their own
+//! transport:
+//!
+//! ```
+//! use boa::Context;
+//! use boa_repl::Repl;
+//!
+//! let mut context = Context::new();
+//! let mut output = Vec::new();
+//! Repl::new()
+//!     .run_with(&mut context, "let x = 40;\nx + 2\n".as_bytes(), &mut output)
+//!     .unwrap();
+//! assert_eq!(String::from_utf8(output).unwrap(), "undefined\n42\n");
+//! ```
+//!
+//! Besides JavaScript, the REPL understands these dot-commands:
+//!
+//! - `.help` lists the dot-commands.
+//! - `.exit` ends the session.
+//! - `.load <file>` evaluates a file in the session.
+//! - `.save <file>` writes the code evaluated in the session to a file.
+//! - `.clear` replaces the global state with a fresh realm.
+//! - `.editor` starts the editor mode, where the following lines are evaluated together once
+//!   Ctrl-D is pressed, or discarded with Ctrl-C. Over streams, the rest of the input is
+//!   evaluated together.
+//! - `.more` shows the next items of the last result, when it was too long to print at once.
+//! - `.allocations` prints the sites that allocated the most objects.
+//! - `.snapshot <file>` writes a heap snapshot.
+//! - `.watch <object>.<property>` reports the reads and writes of a property, `.unwatch`
+//!   removes every watchpoint.
+//!
+//! On the terminal, the Tab key completes the names of the globals and the keywords, and after
+//! a `.` the properties of the object before it.
+//!
+//! Results are printed with `Value::inspect`, so huge arrays and deeply nested objects are
+//! truncated instead of flooding the output, and on the terminal they are colored. The REPL also
+//! defines a global `inspect(value, { depth, maxItems })` function, which formats a value with
+//! other limits.
+//!
+//! When the timings of the context are enabled, see `Interpreter::enable_timings`, the time
+//! spent lexing, parsing and running each line is printed after its result.
+//!
+//! Like in Node's REPL, the global `_` holds the result of the last line that was evaluated, and
+//! `_error` the last value that was thrown and not caught. Once the code assigns one of them
+//! itself, the REPL stops updating it.
+//!
+//! Output of the `console` object goes to the console writer of the context, see
+//! `Interpreter::set_console_writer`.
+
+#![deny(
+    unused_qualifications,
+    clippy::all,
+    unused_import_braces,
+    unused_lifetimes,
+    unreachable_pub,
+    trivial_numeric_casts,
+    // rustdoc,
+    missing_debug_implementations,
+    missing_copy_implementations,
+    deprecated_in_future,
+    non_ascii_idents,
+    rust_2018_compatibility,
+    rust_2018_idioms,
+    future_incompatible,
+    nonstandard_style
+)]
+#![warn(clippy::perf, clippy::single_match_else, clippy::dbg_macro)]
+#![allow(
+    clippy::missing_inline_in_public_items,
+    clippy::cognitive_complexity,
+    clippy::must_use_candidate,
+    clippy::missing_errors_doc,
+    clippy::as_conversions
+)]
+
+mod commands;
+mod helper;
+#[cfg(test)]
+mod tests;
+
+use crate::helper::{GlobalCompleter, RLHelper};
+use boa::{
+    builtins::value::{equality::same_value, InspectOptions, Value},
+    debugger::{WatchEvent, WatchpointId},
+    Context,
+};
+use colored::*;
+use rustyline::{config::Config, error::ReadlineError, EditMode, Editor};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt, fs,
+    io::{self, BufRead, Write},
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    rc::Rc,
+};
+
+/// The number of lines that the terminal history keeps by default.
+pub const DEFAULT_HISTORY_SIZE: usize = 1000;
+
+/// A function that handles the lines that are not dot-commands, instead of evaluating them.
+pub type LineHandler = Box<dyn FnMut(&mut Context, &str, &mut dyn Write) -> io::Result<()>>;
+
+/// An interactive JavaScript console.
+pub struct Repl {
+    prompt: String,
+    vi_mode: bool,
+    history_file: Option<PathBuf>,
+    history_size: usize,
+    line_handler: Option<LineHandler>,
+    inspect_options: InspectOptions,
+    /// The last result that was truncated, and the offset of its next page.
+    more: Option<(Value, usize)>,
+    watch_labels: Rc<RefCell<HashMap<WatchpointId, String>>>,
+    watch_events: Rc<RefCell<Vec<String>>>,
+    /// The sources evaluated in the session, which `.save` writes.
+    session: Vec<String>,
+    /// The values of `_` and `_error`, or `None` once the code assigned them.
+    last_result: Option<Value>,
+    last_error: Option<Value>,
+}
+
+impl fmt::Debug for Repl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Repl")
+            .field("prompt", &self.prompt)
+            .field("vi_mode", &self.vi_mode)
+            .field("history_file", &self.history_file)
+            .field("history_size", &self.history_size)
+            .field("inspect_options", &self.inspect_options)
+            .finish()
+    }
+}
+
+impl Default for Repl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Repl {
+    /// Creates a new REPL with the default prompt and without history.
+    pub fn new() -> Self {
+        Self {
+            prompt: ">> ".to_owned(),
+            vi_mode: false,
+            history_file: None,
+            history_size: DEFAULT_HISTORY_SIZE,
+            line_handler: None,
+            inspect_options: InspectOptions::default(),
+            more: None,
+            watch_labels: Rc::default(),
+            watch_events: Rc::default(),
+            session: Vec::new(),
+            last_result: None,
+            last_error: None,
+        }
+    }
+
+    /// Sets the prompt shown on the terminal.
+    pub fn prompt<P: Into<String>>(mut self, prompt: P) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Uses the vi key bindings on the terminal, instead of the emacs ones.
+    pub fn vi_mode(mut self, vi_mode: bool) -> Self {
+        self.vi_mode = vi_mode;
+        self
+    }
+
+    /// Loads the terminal history from the file, and saves it there when the session ends.
+    pub fn history_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.history_file = Some(path.into());
+        self
+    }
+
+    /// Sets how many lines the terminal history keeps, the oldest ones are forgotten first.
+    /// A line that repeats the previous one is not added to the history.
+    ///
+    /// Defaults to `DEFAULT_HISTORY_SIZE`.
+    pub fn history_size(mut self, size: usize) -> Self {
+        self.history_size = size;
+        self
+    }
+
+    /// Handles the lines that are not dot-commands with the given function, instead of
+    /// evaluating them.
+    pub fn line_handler<F>(mut self, handler: F) -> Self
+    where
+        F: FnMut(&mut Context, &str, &mut dyn Write) -> io::Result<()> + 'static,
+    {
+        self.line_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Sets the limits used to print the results.
+    ///
+    /// `.more` pages through a truncated result `max_items` items at a time.
+    pub fn inspect_options(mut self, options: InspectOptions) -> Self {
+        self.inspect_options = options;
+        self
+    }
+
+    /// Runs the REPL on the terminal until `.exit` or the end of the input.
+    ///
+    /// Results are printed to the standard output, and errors to the standard error.
+    pub fn run(&mut self, context: &mut Context) -> Result<(), ReadlineError> {
+        let config = Config::builder()
+            .keyseq_timeout(1)
+            .max_history_size(self.history_size)
+            .history_ignore_dups(true)
+            .edit_mode(if self.vi_mode {
+                EditMode::Vi
+            } else {
+                EditMode::Emacs
+            })
+            .build();
+
+        let mut editor = Editor::with_config(config);
+        if let Some(ref path) = self.history_file {
+            let _ = editor.load_history(path);
+        }
+        let completer = GlobalCompleter::new(context.global_environment().clone());
+        editor.set_helper(Some(RLHelper::new(completer)));
+
+        let prompt = self.prompt.cyan().bold().to_string();
+        let painter = Painter { colors: true };
+        self.install(context, painter);
+
+        let stdout = io::stdout();
+        let stderr = io::stderr();
+        let result = loop {
+            match editor.readline(&prompt) {
+                Ok(line) => {
+                    editor.add_history_entry(&line);
+                    let line = if line.trim() == ".editor" {
+                        match read_editor(&mut editor, painter) {
+                            Ok(Some(source)) => {
+                                editor.add_history_entry(source.trim_end());
+                                source
+                            }
+                            Ok(None) => continue,
+                            Err(e) => break Err(e),
+                        }
+                    } else {
+                        line
+                    };
+                    let mut out = stdout.lock();
+                    let mut err = stderr.lock();
+                    let keep_going = self.handle_line(context, &line, painter, &mut out, &mut err);
+                    // `.clear` replaces the global environment.
+                    if let Some(helper) = editor.helper_mut() {
+                        helper.set_environment(context.global_environment().clone());
+                    }
+                    match keep_going {
+                        Ok(true) => {}
+                        Ok(false) => break Ok(()),
+                        Err(e) => break Err(e.into()),
+                    }
+                }
+                Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break Ok(()),
+                Err(e) => break Err(e),
+            }
+        };
+
+        if let Some(ref path) = self.history_file {
+            editor.save_history(path)?;
+        }
+        result
+    }
+
+    /// Runs the REPL over the given streams until `.exit` or the end of the input.
+    ///
+    /// Results and errors are written to `output`, without colors. An incomplete line, with
+    /// unclosed brackets for example, is evaluated together with the following ones, until they
+    /// complete it.
+    pub fn run_with<R, W>(&mut self, context: &mut Context, input: R, output: W) -> io::Result<()>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        let painter = Painter { colors: false };
+        self.install(context, painter);
+
+        let output = RefCell::new(output);
+        let mut source = String::new();
+        let mut lines = input.lines();
+        while let Some(line) = lines.next() {
+            source.push_str(&line?);
+            source.push('\n');
+            if source.trim() == ".editor" {
+                // The rest of the input is a single source, like the input of the editor mode
+                // on the terminal until Ctrl-D.
+                painter.hint(&mut SharedWriter(&output), EDITOR_HINT)?;
+                source.clear();
+                for line in &mut lines {
+                    source.push_str(&line?);
+                    source.push('\n');
+                }
+                break;
+            }
+            if boa::is_incomplete(&source) {
+                continue;
+            }
+
+            let (mut out, mut err) = (SharedWriter(&output), SharedWriter(&output));
+            let keep_going = self.handle_line(context, &source, painter, &mut out, &mut err)?;
+            source.clear();
+            if !keep_going {
+                return Ok(());
+            }
+        }
+
+        if !source.trim().is_empty() {
+            let (mut out, mut err) = (SharedWriter(&output), SharedWriter(&output));
+            self.handle_line(context, &source, painter, &mut out, &mut err)?;
+        }
+        Ok(())
+    }
+
+    /// Defines the globals of the REPL and routes the watch events of the context to it.
+    fn install(&mut self, context: &mut Context, painter: Painter) {
+        let global = context.global_object().clone();
+        for name in &["_", "_error"] {
+            let _ = context.set(&global, *name, Value::undefined());
+        }
+        self.last_result = Some(Value::undefined());
+        self.last_error = Some(Value::undefined());
+
+        // The global function returns a string, which must not contain escape codes.
+        let defaults = InspectOptions {
+            colors: false,
+            ..self.inspect_options
+        };
+        context.register_global_function("inspect", 2, move |_, args, ctx| {
+            commands::inspect(args, defaults, ctx)
+        });
+
+        let labels = Rc::clone(&self.watch_labels);
+        let events = Rc::clone(&self.watch_events);
+        context.interpreter_mut().debugger_mut().set_watch_handler(
+            move |_, event: &WatchEvent<'_>| {
+                let labels = labels.borrow();
+                let label = labels.get(&event.id).map_or("?", String::as_str);
+                events.borrow_mut().push(painter.watch_event(label, event));
+            },
+        );
+    }
+
+    /// Runs a dot-command or evaluates the line.
+    ///
+    /// Returns `false` if the session should end.
+    fn handle_line(
+        &mut self,
+        context: &mut Context,
+        line: &str,
+        painter: Painter,
+        out: &mut dyn Write,
+        err: &mut dyn Write,
+    ) -> io::Result<bool> {
+        let line = line.trim_end();
+        let (command, argument) = match line.find(char::is_whitespace) {
+            Some(index) => (&line[..index], line[index..].trim()),
+            None => (line, ""),
+        };
+        match command {
+            ".exit" => return Ok(false),
+            ".help" => commands::help(out)?,
+            ".allocations" => commands::allocations(context, out)?,
+            ".more" => self.more(painter, out, err)?,
+            ".snapshot" => commands::snapshot(context, argument, out)?,
+            ".watch" => commands::watch(self, context, argument, painter, err)?,
+            ".unwatch" => commands::unwatch(self, context),
+            ".load" => self.load(context, argument, painter, out, err)?,
+            ".save" => commands::save(&self.session, argument, out)?,
+            ".clear" => self.clear(context, painter, out)?,
+            _ => self.evaluate(context, line, painter, out, err)?,
+        }
+
+        for event in self.watch_events.borrow_mut().drain(..) {
+            writeln!(err, "{}", event)?;
+        }
+        Ok(true)
+    }
+
+    /// Evaluates a source, or passes it to the line handler, and records it in the session.
+    fn evaluate(
+        &mut self,
+        context: &mut Context,
+        source: &str,
+        painter: Painter,
+        out: &mut dyn Write,
+        err: &mut dyn Write,
+    ) -> io::Result<()> {
+        self.session.push(source.to_owned());
+        if let Some(ref mut handler) = self.line_handler {
+            return handler(context, source, out);
+        }
+
+        // Only the phases of this source are reported, not the ones of the REPL's own scripts.
+        context.interpreter_mut().take_timings();
+        // An engine bug should not end the session and throw away everything that was defined
+        // so far, the panic message has already been printed.
+        let result = panic::catch_unwind(AssertUnwindSafe(|| context.eval(source)));
+        match result {
+            Ok(Ok(v)) => {
+                bind(context, "_", &mut self.last_result, &v, painter, err)?;
+                self.more = Some((v, 0));
+                self.more(painter, out, err)?;
+            }
+            Ok(Err(v)) => {
+                painter.uncaught(err, &v)?;
+                bind(context, "_error", &mut self.last_error, &v, painter, err)?;
+            }
+            Err(_) => {
+                context.interpreter_mut().reset_execution_state();
+                return painter.panicked(err);
+            }
+        }
+        match context.interpreter_mut().take_timings() {
+            Some(timings) => painter.hint(err, &format!("// {}", timings)),
+            None => Ok(()),
+        }
+    }
+
+    /// Runs the `.load <file>` command, which evaluates a file in the session.
+    fn load(
+        &mut self,
+        context: &mut Context,
+        path: &str,
+        painter: Painter,
+        out: &mut dyn Write,
+        err: &mut dyn Write,
+    ) -> io::Result<()> {
+        if path.is_empty() {
+            return writeln!(err, "Usage: .load <file>");
+        }
+        match fs::read_to_string(path) {
+            Ok(source) => self.evaluate(context, source.trim_end(), painter, out, err),
+            Err(e) => writeln!(err, "Could not read {}: {}", path, e),
+        }
+    }
+
+    /// Runs the `.clear` command, which replaces the realm of the context with a fresh one.
+    ///
+    /// The globals of the REPL are defined again, and the watchpoints are removed since they
+    /// watch objects of the previous realm.
+    fn clear(
+        &mut self,
+        context: &mut Context,
+        painter: Painter,
+        out: &mut dyn Write,
+    ) -> io::Result<()> {
+        commands::unwatch(self, context);
+        context.interpreter_mut().reset_realm();
+        self.install(context, painter);
+        self.more = None;
+        writeln!(out, "Clearing context...")
+    }
+
+    /// Prints the next page of the last truncated result.
+    fn more(
+        &mut self,
+        painter: Painter,
+        out: &mut dyn Write,
+        err: &mut dyn Write,
+    ) -> io::Result<()> {
+        let (value, offset) = match self.more.take() {
+            Some(more) => more,
+            None => return writeln!(err, "Nothing more to show"),
+        };
+
+        let options = InspectOptions {
+            offset,
+            colors: painter.colors,
+            ..self.inspect_options
+        };
+        let inspection = value.inspect(&options);
+        writeln!(out, "{}", inspection)?;
+        if inspection.remaining > 0 {
+            let shown = options.max_items.min(inspection.remaining);
+            painter.hint(err, &format!("Type .more to show the next {} items", shown))?;
+            self.more = Some((value, offset + options.max_items));
+        }
+        Ok(())
+    }
+}
+
+/// Sets the global `name` to the value, unless the code assigned the global since it was last set
+/// to `binding`, in which case the global is left to the code from then on.
+fn bind(
+    context: &mut Context,
+    name: &str,
+    binding: &mut Option<Value>,
+    value: &Value,
+    painter: Painter,
+    err: &mut dyn Write,
+) -> io::Result<()> {
+    let previous = match binding {
+        Some(previous) => previous,
+        None => return Ok(()),
+    };
+    let global = context.global_object().clone();
+    let current = context.get(&global, name).unwrap_or_default();
+    if !same_value(&current, previous) {
+        *binding = None;
+        return painter.hint(
+            err,
+            &format!("// Expression assignment to {} now disabled", name),
+        );
+    }
+    if context.set(&global, name, value.clone()).is_ok() {
+        *binding = Some(value.clone());
+    }
+    Ok(())
+}
+
+/// The hint printed when the editor mode starts.
+const EDITOR_HINT: &str = "// Entering editor mode (Ctrl+D to finish, Ctrl+C to cancel)";
+
+/// Reads the lines of the `.editor` command until Ctrl-D, and returns them as a single source.
+///
+/// Returns `None` if the input is cancelled with Ctrl-C.
+fn read_editor(
+    editor: &mut Editor<RLHelper>,
+    painter: Painter,
+) -> Result<Option<String>, ReadlineError> {
+    painter.hint(&mut io::stderr(), EDITOR_HINT)?;
+    let mut source = String::new();
+    loop {
+        match editor.readline("") {
+            Ok(line) => {
+                source.push_str(&line);
+                source.push('\n');
+            }
+            Err(ReadlineError::Eof) => return Ok(Some(source)),
+            Err(ReadlineError::Interrupted) => return Ok(None),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Formats the messages of the REPL, with or without colors.
+#[derive(Debug, Clone, Copy)]
+struct Painter {
+    colors: bool,
+}
+
+impl Painter {
+    /// Prints a value that was thrown and not caught.
+    fn uncaught(self, out: &mut dyn Write, value: &Value) -> io::Result<()> {
+        if self.colors {
+            writeln!(out, "{}: {}", "Uncaught".red(), value.to_string().red())
+        } else {
+            writeln!(out, "Uncaught: {}", value)
+        }
+    }
+
+    /// Prints that the engine panicked.
+    fn panicked(self, out: &mut dyn Write) -> io::Result<()> {
+        let message = "Internal error: the engine panicked, global state was kept";
+        if self.colors {
+            writeln!(out, "{}", message.red())
+        } else {
+            writeln!(out, "{}", message)
+        }
+    }
+
+    /// Prints a hint about the commands of the REPL.
+    fn hint(self, out: &mut dyn Write, message: &str) -> io::Result<()> {
+        if self.colors {
+            writeln!(out, "{}", message.dimmed())
+        } else {
+            writeln!(out, "{}", message)
+        }
+    }
+
+    /// Formats a watch event, with the location of the statement that accessed the property,
+    /// like `watch: write o.a = 2 at <anonymous>:1:1`.
+    fn watch_event(self, label: &str, event: &WatchEvent<'_>) -> String {
+        let location = match (event.script, event.position) {
+            (Some(script), Some(position)) => format!(
+                " at {}:{}:{}",
+                script,
+                position.line_number(),
+                position.column_number()
+            ),
+            _ => String::new(),
+        };
+        let (access, value) = (event.access, event.value);
+        if self.colors {
+            let location = location.dimmed();
+            format!(
+                "{} {} {} = {}{}",
+                "watch:".yellow(),
+                access,
+                label,
+                value,
+                location
+            )
+        } else {
+            format!("watch: {} {} = {}{}", access, label, value, location)
+        }
+    }
+}
+
+/// A writer that shares a stream with other writers.
+struct SharedWriter<'a, W>(&'a RefCell<W>);
+
+impl<W: Write> Write for SharedWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.borrow_mut().flush()
+    }
+}
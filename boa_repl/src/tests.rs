@@ -0,0 +1,274 @@
+use crate::Repl;
+use boa::{builtins::value::InspectOptions, is_incomplete, Context};
+
+/// Runs the REPL over the given input and returns its output.
+fn run(input: &str) -> String {
+    let mut context = Context::new();
+    let mut output = Vec::new();
+    Repl::new()
+        .run_with(&mut context, input.as_bytes(), &mut output)
+        .unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn evaluates_lines() {
+    assert_eq!(run("let x = 1;\nx + 1\n"), "undefined\n2\n");
+}
+
+#[test]
+fn reports_uncaught_errors() {
+    assert_eq!(
+        run("throw 'oops'\n'still running'\n"),
+        "Uncaught: oops\nstill running\n"
+    );
+}
+
+#[test]
+fn joins_lines_with_unclosed_brackets() {
+    assert_eq!(
+        run("function f() {\n  return 3;\n}\nf()\n"),
+        "undefined\n3\n"
+    );
+}
+
+#[test]
+fn exit_ends_the_session() {
+    assert_eq!(run("1\n.exit\n2\n"), "1\n");
+}
+
+#[test]
+fn watch_reports_accesses() {
+    let output = run("let o = { a: 1 };\n.watch o.a\no.a = 2;\n.unwatch\no.a = 3;\n");
+    assert_eq!(
+        output,
+        "undefined\n2\nwatch: write o.a = 2 at <anonymous>:1:1\n3\n"
+    );
+
+    // The accesses in functions are reported in the function.
+    assert_eq!(
+        run("let o = { a: 1 };\nfunction f() { return o.a; }\n.watch o.a\nf();\n"),
+        "undefined\nundefined\n1\nwatch: read o.a = 1 at <anonymous>:1:16\n"
+    );
+}
+
+#[test]
+fn prints_the_timings_of_each_line() {
+    let mut context = Context::builder().timings(true).build();
+    let mut output = Vec::new();
+    Repl::new()
+        .run_with(&mut context, "1 + 1\n".as_bytes(), &mut output)
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let mut lines = output.lines();
+    assert_eq!(lines.next(), Some("2"));
+    assert!(lines.next().unwrap().starts_with("// lex "));
+    assert_eq!(lines.next(), None);
+}
+
+#[test]
+fn line_handler_replaces_evaluation() {
+    let mut context = Context::new();
+    let mut output = Vec::new();
+    Repl::new()
+        .line_handler(|_, line, out| writeln!(out, "got {}", line))
+        .run_with(&mut context, "1 + 1\n".as_bytes(), &mut output)
+        .unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), "got 1 + 1\n");
+}
+
+#[test]
+fn incomplete_sources() {
+    assert!(is_incomplete("function f() {"));
+    assert!(is_incomplete("[1, (2"));
+    assert!(!is_incomplete("f({ a: [1] })"));
+    assert!(!is_incomplete("'{' + \"(\" // ["));
+    assert!(!is_incomplete("/* { */ 1"));
+    assert!(is_incomplete("`a\n${b}"));
+    assert!(is_incomplete("1 /* {"));
+    assert!(is_incomplete("'a\\"));
+    assert!(!is_incomplete("'a\n+ 1"));
+    assert!(is_incomplete("if (ready)"));
+    assert!(is_incomplete("if (ready) {} else"));
+    assert!(is_incomplete("let x =\n"));
+    assert!(is_incomplete("1 +"));
+    assert!(is_incomplete("a ? b :"));
+    assert!(!is_incomplete("1 + )"));
+    assert!(!is_incomplete(".help"));
+}
+
+#[test]
+fn joins_lines_until_the_source_parses() {
+    assert_eq!(run("if (true)\n  'yes'\n"), "yes\n");
+    assert_eq!(run("let total = 1 +\n  2;\ntotal\n"), "undefined\n3\n");
+    assert_eq!(run("[1,\n  2].length\n"), "2\n");
+}
+
+#[test]
+fn evaluates_pasted_snippets_as_a_whole() {
+    assert_eq!(run("1 + /*\n * )\n */ 2\n'a\\\nb'.length\n"), "3\n2\n");
+}
+
+#[test]
+fn more_pages_through_long_results() {
+    let mut context = Context::new();
+    let mut output = Vec::new();
+    Repl::new()
+        .inspect_options(InspectOptions {
+            max_items: 2,
+            ..InspectOptions::default()
+        })
+        .run_with(
+            &mut context,
+            "[1, 2, 3, 4, 5]\n.more\n.more\n.more\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "[ 1, 2, ... 3 more items ]\n\
+         Type .more to show the next 2 items\n\
+         [ ... 2 previous items, 3, 4, ... 1 more items ]\n\
+         Type .more to show the next 1 items\n\
+         [ ... 4 previous items, 5 ]\n\
+         Nothing more to show\n"
+    );
+}
+
+#[test]
+fn inspect_formats_with_limits() {
+    assert_eq!(
+        run("inspect({ a: { b: [1, 2, 3] } }, { depth: 1, maxItems: 2 })\n"),
+        "{ a: { b: Array(3) } }\n"
+    );
+    assert_eq!(
+        run("inspect([1, 2, 3], { maxItems: 1 })\n"),
+        "[ 1, ... 2 more items ]\n"
+    );
+}
+
+#[test]
+fn completes_globals_and_properties() {
+    use crate::helper::GlobalCompleter;
+
+    let mut context = Context::new();
+    context
+        .eval("let point = { x: 1, y: 2, nested: { deep: true } }; var counter = 0;")
+        .unwrap();
+    let completer = GlobalCompleter::new(context.global_environment().clone());
+
+    assert_eq!(completer.complete("poi"), (0, vec!["point".to_owned()]));
+    assert_eq!(
+        completer.complete("1 + cou"),
+        (4, vec!["counter".to_owned()])
+    );
+    assert_eq!(completer.complete("ret"), (0, vec!["return".to_owned()]));
+    assert_eq!(completer.complete("Math.fl"), (5, vec!["floor".to_owned()]));
+    assert_eq!(
+        completer.complete("point.nested.d"),
+        (13, vec!["deep".to_owned()])
+    );
+    assert!(completer
+        .complete("point.")
+        .1
+        .contains(&"hasOwnProperty".to_owned()));
+    assert!(completer.complete("missing.").1.is_empty());
+    assert!(completer.complete(".ex").1.is_empty());
+
+    // The completer sees the bindings defined after it was created.
+    context.eval("const later = 1;").unwrap();
+    assert_eq!(completer.complete("lat"), (0, vec!["later".to_owned()]));
+}
+
+#[test]
+fn editor_evaluates_the_rest_of_the_input_together() {
+    assert_eq!(
+        run("1\n.editor\nfunction f(a)\n{ return a * 2; }\nf(21)\n"),
+        "1\n// Entering editor mode (Ctrl+D to finish, Ctrl+C to cancel)\n42\n"
+    );
+}
+
+#[test]
+fn help_lists_the_commands() {
+    let output = run(".help\n");
+    assert!(output.lines().any(|line| line.starts_with(".load <file>")));
+    assert!(output.lines().any(|line| line.starts_with(".clear")));
+}
+
+#[test]
+fn save_and_load_the_session() {
+    let path = std::env::temp_dir().join(format!("boa_repl_session_{}.js", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    let output = run(&format!(
+        "let a = 20;\nfunction twice(x) {{\n  return x * 2;\n}}\n.save {}\n",
+        path
+    ));
+    assert_eq!(
+        output,
+        format!("undefined\nundefined\nSession saved to {}\n", path)
+    );
+    assert_eq!(
+        std::fs::read_to_string(path).unwrap(),
+        "let a = 20;\nfunction twice(x) {\n  return x * 2;\n}\n"
+    );
+
+    assert_eq!(
+        run(&format!(".load {}\ntwice(a) + 2\n", path)),
+        "undefined\n42\n"
+    );
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn clear_resets_the_globals() {
+    assert_eq!(
+        run("var x = 1;\nArray.prototype.y = 2;\n.clear\nthis.x\n[].y\ninspect(1)\n"),
+        "undefined\n2\nClearing context...\nundefined\nundefined\n1\n"
+    );
+}
+
+#[test]
+fn highlights_tokens_by_kind() {
+    use crate::helper::LineHighlighter;
+    use colored::*;
+    use rustyline::highlight::Highlighter;
+
+    control::set_override(true);
+    assert_eq!(
+        LineHighlighter.highlight("let s = 'a/b' + 1.5 // c", 0),
+        format!(
+            "{} s = {} + {} {}",
+            "let".yellow().bold(),
+            "'a/b'".green(),
+            "1.5".cyan(),
+            "// c".dimmed()
+        )
+    );
+    assert_eq!(
+        LineHighlighter.highlight("f(/* x */ true, 'oops", 0),
+        format!(
+            "f({} {}, {}",
+            "/* x */".dimmed(),
+            "true".purple(),
+            "'oops".red()
+        )
+    );
+}
+
+#[test]
+fn underscore_holds_the_last_result() {
+    assert_eq!(run("40 + 2\n_ - 2\n_\n"), "42\n40\n40\n");
+    assert_eq!(
+        run("throw 'oops'\n_error + '!'\n"),
+        "Uncaught: oops\noops!\n"
+    );
+}
+
+#[test]
+fn assigning_underscore_disables_it() {
+    assert_eq!(
+        run("_ = 5\n6\n_\n"),
+        "// Expression assignment to _ now disabled\n5\n6\n5\n"
+    );
+}
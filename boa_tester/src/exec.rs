@@ -0,0 +1,242 @@
+//! Running a test in a fresh context, in the modes that its flags ask for.
+
+use crate::read::{Negative, Test};
+use boa::{
+    builtins::{function::make_closure_fn, value::Value},
+    forward_val, parse, Context, JsError, ParseOptions,
+};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    rc::Rc,
+};
+
+/// The message that the `async` tests print, through `$DONE`, when they succeed.
+const ASYNC_COMPLETE: &str = "Test262:AsyncTestComplete";
+
+/// The files of the `harness` directory, which define the assertions of the tests.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Harness {
+    files: HashMap<String, String>,
+}
+
+impl Harness {
+    /// Reads the harness of a test262 checkout.
+    pub(crate) fn read(root: &Path) -> io::Result<Self> {
+        let mut files = HashMap::new();
+        for entry in fs::read_dir(root.join("harness"))? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == "js") {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                files.insert(name.into_owned(), fs::read_to_string(&path)?);
+            }
+        }
+        Ok(Self { files })
+    }
+
+    /// Gets the source of a harness file.
+    fn get(&self, name: &str) -> Result<&str, String> {
+        self.files
+            .get(name)
+            .map(String::as_str)
+            .ok_or_else(|| format!("the harness file {} is missing", name))
+    }
+}
+
+/// The result of a test.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Outcome {
+    Passed,
+    /// The test failed, for the given reason.
+    Failed(String),
+}
+
+/// Runs a test, in non-strict mode and then in strict mode unless its flags ask for only one of
+/// them.
+///
+/// Each run has its own context, whose scripts are terminated after `step_budget` steps. A panic
+/// of the engine fails the test.
+pub(crate) fn run_test(test: &Test, harness: &Harness, step_budget: u64) -> Outcome {
+    let metadata = &test.metadata;
+    let modes: &[bool] = if metadata.has_flag("onlyStrict") {
+        &[true]
+    } else if metadata.has_flag("noStrict")
+        || metadata.has_flag("raw")
+        || metadata.has_flag("module")
+    {
+        &[false]
+    } else {
+        &[false, true]
+    };
+    for &strict in modes {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            run_mode(test, harness, strict, step_budget)
+        }))
+        .unwrap_or_else(|payload| {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| (*message).to_owned())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(format!("the engine panicked: {}", message))
+        });
+        if let Err(reason) = result {
+            let reason = if modes.len() > 1 {
+                let mode = if strict { "strict" } else { "non-strict" };
+                format!("{} mode: {}", mode, reason)
+            } else {
+                reason
+            };
+            return Outcome::Failed(reason);
+        }
+    }
+    Outcome::Passed
+}
+
+/// Runs a test in one mode, and returns why it failed if it did.
+fn run_mode(test: &Test, harness: &Harness, strict: bool, step_budget: u64) -> Result<(), String> {
+    let metadata = &test.metadata;
+    let source = source(test, harness, strict)?;
+
+    if let Some(negative) = &metadata.negative {
+        match negative.phase.as_str() {
+            "parse" | "early" => {
+                let module = metadata.has_flag("module");
+                let options = ParseOptions {
+                    strict: strict || module,
+                    // Modules never have HTML-like comments.
+                    annex_b: !module,
+                    ..ParseOptions::default()
+                };
+                return match parse(&source, options) {
+                    Ok(_) => Err(format!(
+                        "expected a {} when parsing, but the test parsed",
+                        negative.error_type
+                    )),
+                    Err(_) if negative.error_type == "SyntaxError" => Ok(()),
+                    Err(e) => Err(format!("expected a {}, got {}", negative.error_type, e)),
+                };
+            }
+            "resolution" => return Err(String::from("module resolution is not supported")),
+            _ => {}
+        }
+    }
+    if metadata.has_flag("module") {
+        return Err(String::from("modules are not supported"));
+    }
+
+    let mut context = Context::builder()
+        .strict(strict)
+        .step_budget(step_budget)
+        .build();
+    let printed = define_host(&mut context);
+    let result = forward_val(context.interpreter_mut(), &source).and_then(|value| {
+        context
+            .run_jobs()
+            .map(|_| value)
+            .map_err(|thrown| JsError::from_thrown(thrown, context.interpreter()))
+    });
+
+    match (&metadata.negative, result) {
+        (Some(negative), Ok(_)) => Err(format!("expected a {} to be thrown", negative.error_type)),
+        (Some(negative), Err(e)) => check_error(negative, &e),
+        (None, Err(e)) => Err(describe(&e)),
+        (None, Ok(_)) if metadata.has_flag("async") => {
+            let printed = printed.borrow();
+            match printed.iter().find(|line| line.starts_with("Test262:")) {
+                Some(line) if line == ASYNC_COMPLETE => Ok(()),
+                Some(line) => Err(line.clone()),
+                None => Err(String::from("the test did not call $DONE")),
+            }
+        }
+        (None, Ok(_)) => Ok(()),
+    }
+}
+
+/// Builds the source of a test, with the harness files it needs and the `"use strict"`
+/// directive in strict mode.
+fn source(test: &Test, harness: &Harness, strict: bool) -> Result<String, String> {
+    if test.metadata.has_flag("raw") {
+        return Ok(test.source.clone());
+    }
+    let mut source = String::new();
+    if strict {
+        source.push_str("\"use strict\";\n");
+    }
+    let mut includes = vec!["assert.js", "sta.js"];
+    if test.metadata.has_flag("async") {
+        includes.push("doneprintHandle.js");
+    }
+    includes.extend(test.metadata.includes.iter().map(String::as_str));
+    for include in includes {
+        source.push_str(harness.get(include)?);
+        source.push('\n');
+    }
+    source.push_str(&test.source);
+    Ok(source)
+}
+
+/// Checks that the error of a negative test is the expected one.
+fn check_error(negative: &Negative, error: &JsError) -> Result<(), String> {
+    let name = match error {
+        JsError::Syntax { .. } => String::from("SyntaxError"),
+        JsError::Thrown { value, .. } => error_name(value),
+        JsError::StackOverflow => String::from("RangeError"),
+        e => return Err(e.to_string()),
+    };
+    if name == negative.error_type {
+        Ok(())
+    } else {
+        Err(format!(
+            "expected a {} to be thrown, got {}",
+            negative.error_type,
+            describe(error)
+        ))
+    }
+}
+
+/// Describes an error, as `name: message` if an object was thrown, since the objects thrown by
+/// the harness, like `Test262Error`, have no useful string conversion.
+fn describe(error: &JsError) -> String {
+    match error {
+        JsError::Thrown { value, .. } if value.is_object() => {
+            format!("{}: {}", error_name(value), value.get_field("message"))
+        }
+        e => e.to_string(),
+    }
+}
+
+/// Gets the name of the constructor of a thrown value, or its `name` property.
+fn error_name(value: &Value) -> String {
+    let name = value.get_field("constructor").get_field("name");
+    if name.is_undefined() {
+        value.get_field("name").to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Defines the globals that test262 expects from the host: `print`, whose messages are
+/// returned, and the `$262` object.
+///
+/// Only the `global` and `gc` members of `$262` are defined, the tests that use the other ones
+/// fail.
+fn define_host(context: &mut Context) -> Rc<RefCell<Vec<String>>> {
+    let printed = Rc::new(RefCell::new(Vec::new()));
+    let sink = Rc::clone(&printed);
+    context.register_global_function("print", 1, move |_, args, _| {
+        let message = args.first().map(Value::to_string).unwrap_or_default();
+        sink.borrow_mut().push(message);
+        Ok(Value::undefined())
+    });
+
+    let global = context.global_object().clone();
+    let host = Value::new_object(Some(&global));
+    host.set_field("global", global.clone());
+    make_closure_fn(|_, _, _| Ok(Value::undefined()), "gc", &host, 0);
+    global.set_field("$262", host);
+    printed
+}
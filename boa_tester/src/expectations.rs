@@ -0,0 +1,95 @@
+//! The list of the tests that are skipped or known to fail.
+
+use crate::read::Metadata;
+
+/// The built-in list, used unless another one is given with `--expectations`.
+pub(crate) const DEFAULT: &str = include_str!("../expectations.txt");
+
+/// What is expected of a test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Expectation {
+    /// The test is not run.
+    Skip,
+    /// The test is run and is known to fail.
+    Fail,
+}
+
+/// What a rule of the list matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Pattern {
+    /// A test, or the tests of a directory, relative to the test262 checkout.
+    Path(String),
+    /// The tests that use a feature.
+    Feature(String),
+    /// The tests with a flag.
+    Flag(String),
+}
+
+/// The rules of a list of expectations.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Expectations {
+    rules: Vec<(Expectation, Pattern)>,
+}
+
+impl Expectations {
+    /// Parses a list, where each line is `skip <pattern>` or `fail <pattern>`, and `#` starts a
+    /// comment.
+    pub(crate) fn parse(src: &str) -> Result<Self, String> {
+        let mut rules = Vec::new();
+        for (index, line) in src.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            let expectation = match words.next() {
+                Some("skip") => Expectation::Skip,
+                Some("fail") => Expectation::Fail,
+                _ => {
+                    return Err(format!(
+                        "line {}: expected `skip` or `fail`, got `{}`",
+                        index + 1,
+                        line
+                    ))
+                }
+            };
+            let pattern = match (words.next(), words.next()) {
+                (Some(pattern), None) => pattern,
+                _ => return Err(format!("line {}: expected a single pattern", index + 1)),
+            };
+            let pattern = if let Some(feature) = pattern.strip_prefix("feature:") {
+                Pattern::Feature(feature.to_owned())
+            } else if let Some(flag) = pattern.strip_prefix("flag:") {
+                Pattern::Flag(flag.to_owned())
+            } else {
+                Pattern::Path(pattern.trim_end_matches('/').to_owned())
+            };
+            rules.push((expectation, pattern));
+        }
+        Ok(Self { rules })
+    }
+
+    /// Gets what is expected of the test named `name`, if it is listed.
+    ///
+    /// Skipping takes precedence over failing, whatever the order of the rules.
+    pub(crate) fn get(&self, name: &str, metadata: &Metadata) -> Option<Expectation> {
+        let mut expectation = None;
+        for (rule, pattern) in &self.rules {
+            let matches = match pattern {
+                Pattern::Path(path) => {
+                    name == path
+                        || (name.starts_with(path.as_str()) && name[path.len()..].starts_with('/'))
+                }
+                Pattern::Feature(feature) => metadata.features.contains(feature),
+                Pattern::Flag(flag) => metadata.has_flag(flag),
+            };
+            if matches {
+                if *rule == Expectation::Skip {
+                    return Some(Expectation::Skip);
+                }
+                expectation = Some(*rule);
+            }
+        }
+        expectation
+    }
+}
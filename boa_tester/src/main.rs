@@ -0,0 +1,361 @@
+//! A runner of the [test262](https://github.com/tc39/test262) conformance suite.
+//!
+//! The runner reads the tests of a test262 checkout, runs each of them with the harness files
+//! it includes, in non-strict and in strict mode, and prints how many tests passed in each
+//! directory:
+//!
+//! ```text
+//! git clone --depth 1 https://github.com/tc39/test262
+//! cargo run --release --bin boa_tester -- test/built-ins/Array
+//! ```
+//!
+//! The tests listed in `expectations.txt` are skipped or known to fail. The runner exits with
+//! status 1 if a test fails without being listed, or if a listed test passes, so that the list
+//! is updated when the conformance improves.
+
+#![deny(
+    unused_qualifications,
+    clippy::all,
+    unused_import_braces,
+    unused_lifetimes,
+    unreachable_pub,
+    trivial_numeric_casts,
+    // rustdoc,
+    missing_debug_implementations,
+    missing_copy_implementations,
+    deprecated_in_future,
+    non_ascii_idents,
+    rust_2018_compatibility,
+    rust_2018_idioms,
+    future_incompatible,
+    nonstandard_style
+)]
+#![warn(clippy::perf, clippy::single_match_else, clippy::dbg_macro)]
+#![allow(
+    clippy::missing_inline_in_public_items,
+    clippy::cognitive_complexity,
+    clippy::must_use_candidate,
+    clippy::missing_errors_doc,
+    clippy::as_conversions
+)]
+
+mod exec;
+mod expectations;
+mod read;
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    exec::{run_test, Harness, Outcome},
+    expectations::{Expectation, Expectations},
+    read::{find_tests, read_test},
+};
+use colored::Colorize;
+use rayon::prelude::*;
+use serde_json::{json, Map};
+use std::{
+    collections::BTreeMap,
+    fs, io, panic,
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+use structopt::StructOpt;
+
+/// The repository cloned by `--clone`.
+const TEST262_REPOSITORY: &str = "https://github.com/tc39/test262";
+
+#[derive(Debug, Clone, StructOpt)]
+#[structopt(author, about, name = "boa_tester")]
+struct Opt {
+    /// The tests to run, files or directories relative to the test262 checkout.
+    #[structopt(name = "SUITE", parse(from_os_str), default_value = "test")]
+    suites: Vec<PathBuf>,
+
+    /// The test262 checkout.
+    #[structopt(
+        long,
+        value_name = "DIR",
+        parse(from_os_str),
+        default_value = "test262"
+    )]
+    test262: PathBuf,
+
+    /// Clone test262 into the checkout directory if it does not exist.
+    #[structopt(long)]
+    clone: bool,
+
+    /// The list of the tests that are skipped or known to fail, instead of the built-in one.
+    /// Each line is `skip <pattern>` or `fail <pattern>`, where the pattern is a path relative
+    /// to the checkout, `feature:<name>` or `flag:<name>`.
+    #[structopt(long, value_name = "FILE", parse(from_os_str))]
+    expectations: Option<PathBuf>,
+
+    /// The number of path components, after `test/`, of the directories whose statistics are
+    /// printed.
+    #[structopt(long, value_name = "N", default_value = "2")]
+    depth: usize,
+
+    /// The number of steps after which a test is terminated, see `ContextBuilder::step_budget`.
+    #[structopt(long, value_name = "STEPS", default_value = "10000000")]
+    step_budget: u64,
+
+    /// Print the reason of each unexpected failure.
+    #[structopt(long, short = "v")]
+    verbose: bool,
+
+    /// Write the statistics of each directory to a file, as JSON.
+    #[structopt(long, value_name = "FILE", parse(from_os_str))]
+    json: Option<PathBuf>,
+}
+
+/// The result of a test, compared to its expectation.
+#[derive(Debug, Clone, PartialEq)]
+enum Verdict {
+    Passed,
+    Skipped,
+    /// The test failed, as expected.
+    KnownFailure,
+    /// The test failed without being listed, for the given reason.
+    Failed(String),
+    /// The test is listed as failing, but passed.
+    UnexpectedPass,
+}
+
+/// The statistics of the tests of a directory.
+#[derive(Debug, Clone, Copy, Default)]
+struct Stats {
+    total: usize,
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+}
+
+impl Stats {
+    fn add(&mut self, verdict: &Verdict) {
+        self.total += 1;
+        match verdict {
+            Verdict::Passed | Verdict::UnexpectedPass => self.passed += 1,
+            Verdict::Skipped => self.skipped += 1,
+            Verdict::KnownFailure | Verdict::Failed(_) => self.failed += 1,
+        }
+    }
+
+    /// The percentage of the tests that were run and passed.
+    fn conformance(self) -> f64 {
+        let run = self.total - self.skipped;
+        if run == 0 {
+            100.0
+        } else {
+            self.passed as f64 * 100.0 / run as f64
+        }
+    }
+}
+
+/// Clones test262 into `dir`.
+fn clone(dir: &Path) -> io::Result<()> {
+    eprintln!("Cloning {} into {}", TEST262_REPOSITORY, dir.display());
+    let status = Command::new("git")
+        .args(["clone", "--depth", "1", TEST262_REPOSITORY])
+        .arg(dir)
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other("git clone failed"))
+    }
+}
+
+/// Gets the directory of a test whose statistics are grouped, see `Opt::depth`.
+fn directory(name: &str, depth: usize) -> String {
+    let mut components: Vec<_> = name.split('/').collect();
+    // The file name is not a directory.
+    components.pop();
+    let skip = usize::from(components.first() == Some(&"test"));
+    components
+        .into_iter()
+        .skip(skip)
+        .take(depth)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Runs a test and compares its outcome to its expectation.
+fn judge(
+    path: &Path,
+    args: &Opt,
+    harness: &Harness,
+    expectations: &Expectations,
+) -> (String, Verdict) {
+    let test = match read_test(&args.test262, path) {
+        Ok(test) => test,
+        Err(e) => {
+            let name = path.display().to_string();
+            return (
+                name,
+                Verdict::Failed(format!("could not read the test: {}", e)),
+            );
+        }
+    };
+    let expectation = expectations.get(&test.name, &test.metadata);
+    let verdict = match (expectation, run_test(&test, harness, args.step_budget)) {
+        (Some(Expectation::Skip), _) => Verdict::Skipped,
+        (Some(Expectation::Fail), Outcome::Passed) => Verdict::UnexpectedPass,
+        (Some(Expectation::Fail), Outcome::Failed(_)) => Verdict::KnownFailure,
+        (None, Outcome::Passed) => Verdict::Passed,
+        (None, Outcome::Failed(reason)) => Verdict::Failed(reason),
+    };
+    (test.name, verdict)
+}
+
+/// Prints the statistics of each directory and of all the tests.
+fn print_stats(stats: &BTreeMap<String, Stats>, total: Stats) {
+    let width = stats.keys().map(String::len).max().unwrap_or(0).max(5);
+    println!(
+        "{:width$}  {:>7}  {:>7}  {:>7}  {:>7}  {:>7}",
+        "",
+        "total",
+        "passed",
+        "failed",
+        "skipped",
+        "%",
+        width = width
+    );
+    let rows = stats.iter().map(|(dir, stats)| (dir.as_str(), *stats));
+    for (dir, stats) in rows.chain(std::iter::once(("total", total))) {
+        let conformance = format!("{:.2}", stats.conformance());
+        let conformance = if stats.failed == 0 {
+            conformance.green()
+        } else {
+            conformance.yellow()
+        };
+        println!(
+            "{:width$}  {:>7}  {:>7}  {:>7}  {:>7}  {:>7}",
+            dir,
+            stats.total,
+            stats.passed,
+            stats.failed,
+            stats.skipped,
+            conformance,
+            width = width
+        );
+    }
+}
+
+/// Writes the statistics of each directory and of all the tests, as JSON.
+fn write_json(path: &Path, stats: &BTreeMap<String, Stats>, total: Stats) -> io::Result<()> {
+    let to_json = |stats: Stats| {
+        json!({
+            "total": stats.total,
+            "passed": stats.passed,
+            "failed": stats.failed,
+            "skipped": stats.skipped,
+        })
+    };
+    let directories: Map<_, _> = stats
+        .iter()
+        .map(|(dir, stats)| (dir.clone(), to_json(*stats)))
+        .collect();
+    let json = json!({ "directories": directories, "total": to_json(total) });
+    fs::write(path, format!("{:#}\n", json))
+}
+
+/// Runs the tests, prints the statistics and the unexpected results, and returns the exit code.
+fn run(args: &Opt) -> io::Result<i32> {
+    if !args.test262.exists() {
+        if args.clone {
+            clone(&args.test262)?;
+        } else {
+            eprintln!(
+                "{} does not exist, clone test262 there or use --clone",
+                args.test262.display()
+            );
+            return Ok(2);
+        }
+    }
+    let expectations = match &args.expectations {
+        Some(path) => Expectations::parse(&fs::read_to_string(path)?),
+        None => Expectations::parse(expectations::DEFAULT),
+    };
+    let expectations = match expectations {
+        Ok(expectations) => expectations,
+        Err(e) => {
+            eprintln!("Invalid expectations: {}", e);
+            return Ok(2);
+        }
+    };
+    let harness = Harness::read(&args.test262)?;
+    let tests = find_tests(&args.test262, &args.suites)?;
+    eprintln!("Running {} tests", tests.len());
+
+    // The panics of the engine are reported as failures.
+    panic::set_hook(Box::new(|_| {}));
+    let pool = rayon::ThreadPoolBuilder::new()
+        .stack_size(16 * 1024 * 1024)
+        .build()
+        .map_err(io::Error::other)?;
+    let verdicts: Vec<_> = pool.install(|| {
+        tests
+            .par_iter()
+            .map(|path| judge(path, args, &harness, &expectations))
+            .collect()
+    });
+    drop(panic::take_hook());
+
+    let mut stats = BTreeMap::new();
+    let mut total = Stats::default();
+    let (mut failures, mut unexpected_passes) = (Vec::new(), Vec::new());
+    for (name, verdict) in &verdicts {
+        stats
+            .entry(directory(name, args.depth))
+            .or_insert_with(Stats::default)
+            .add(verdict);
+        total.add(verdict);
+        match verdict {
+            Verdict::Failed(reason) => failures.push((name, reason)),
+            Verdict::UnexpectedPass => unexpected_passes.push(name),
+            _ => {}
+        }
+    }
+
+    print_stats(&stats, total);
+    if let Some(path) = &args.json {
+        write_json(path, &stats, total)?;
+    }
+    if args.verbose {
+        for (name, reason) in &failures {
+            println!("{} {}: {}", "FAIL".red(), name, reason);
+        }
+    }
+    for name in &unexpected_passes {
+        println!(
+            "{} {} passed, remove it from the expectations",
+            "PASS".green(),
+            name
+        );
+    }
+    if !failures.is_empty() {
+        println!(
+            "{} tests failed unexpectedly{}",
+            failures.len(),
+            if args.verbose {
+                ""
+            } else {
+                ", use -v to list them"
+            }
+        );
+    }
+    Ok(i32::from(
+        !failures.is_empty() || !unexpected_passes.is_empty(),
+    ))
+}
+
+pub fn main() {
+    let args = Opt::from_args();
+    match run(&args) {
+        Ok(status) => process::exit(status),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(2);
+        }
+    }
+}
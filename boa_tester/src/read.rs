@@ -0,0 +1,182 @@
+//! Finding the tests of a test262 checkout and reading their metadata.
+//!
+//! The metadata is the YAML block at the top of each test, between `/*---` and `---*/`. Only
+//! the keys that the runner needs are read, with a parser for the subset of YAML that test262
+//! uses: scalars, inline `[a, b]` lists, `- item` lists and one level of nested keys.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use walkdir::WalkDir;
+
+/// A test, with its source and its metadata.
+#[derive(Debug, Clone)]
+pub(crate) struct Test {
+    /// The path of the test, relative to the test262 checkout, with `/` separators.
+    pub(crate) name: String,
+    pub(crate) source: String,
+    pub(crate) metadata: Metadata,
+}
+
+/// The metadata of a test.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Metadata {
+    /// The files of the `harness` directory that the test uses, besides `assert.js` and
+    /// `sta.js`.
+    pub(crate) includes: Vec<String>,
+    /// The flags, like `onlyStrict`, `raw` or `async`.
+    pub(crate) flags: Vec<String>,
+    /// The language features that the test uses.
+    pub(crate) features: Vec<String>,
+    /// The error that the test expects, if it is a negative test.
+    pub(crate) negative: Option<Negative>,
+}
+
+impl Metadata {
+    /// Checks if the test has a flag.
+    pub(crate) fn has_flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f == flag)
+    }
+}
+
+/// The error expected by a negative test.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Negative {
+    /// When the error is thrown: `parse`, `resolution` or `runtime`.
+    pub(crate) phase: String,
+    /// The name of the constructor of the error, like `SyntaxError`.
+    pub(crate) error_type: String,
+}
+
+/// Finds the tests in the given files and directories, sorted by name.
+///
+/// The fixtures, whose names end in `_FIXTURE.js`, are modules imported by tests and not tests
+/// themselves.
+pub(crate) fn find_tests(root: &Path, suites: &[PathBuf]) -> io::Result<Vec<PathBuf>> {
+    let mut tests = Vec::new();
+    for suite in suites {
+        for entry in WalkDir::new(root.join(suite)) {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy();
+            if entry.file_type().is_file()
+                && name.ends_with(".js")
+                && !name.ends_with("_FIXTURE.js")
+            {
+                tests.push(entry.into_path());
+            }
+        }
+    }
+    tests.sort();
+    tests.dedup();
+    Ok(tests)
+}
+
+/// Reads a test.
+pub(crate) fn read_test(root: &Path, path: &Path) -> io::Result<Test> {
+    let source = fs::read_to_string(path)?;
+    let metadata =
+        parse_metadata(&source).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let name = path
+        .strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    Ok(Test {
+        name,
+        source,
+        metadata,
+    })
+}
+
+/// Parses the metadata block of a test.
+pub(crate) fn parse_metadata(source: &str) -> Result<Metadata, String> {
+    let start = source
+        .find("/*---")
+        .ok_or_else(|| String::from("the test has no metadata"))?;
+    let end = source[start..]
+        .find("---*/")
+        .ok_or_else(|| String::from("the metadata of the test is not closed"))?;
+    let block = &source[start + "/*---".len()..start + end];
+
+    let mut metadata = Metadata::default();
+    let mut key = "";
+    for line in block.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) {
+            // Other top-level lines, like comments, are ignored.
+            if let Some((name, value)) = split_key(line) {
+                key = name;
+                if let Some(items) = inline_list(value) {
+                    if let Some(list) = list_mut(&mut metadata, key) {
+                        list.extend(items);
+                    }
+                }
+            }
+            continue;
+        }
+
+        // The lines of a block scalar, like a multi-line description, can look like anything.
+        let line = line.trim();
+        if let Some(item) = line.strip_prefix("- ") {
+            if let Some(list) = list_mut(&mut metadata, key) {
+                list.push(unquote(item).to_owned());
+            }
+        } else if key == "negative" {
+            if let Some((name, value)) = split_key(line) {
+                let negative = metadata.negative.get_or_insert_with(Negative::default);
+                match name {
+                    "phase" => negative.phase = unquote(value).to_owned(),
+                    "type" => negative.error_type = unquote(value).to_owned(),
+                    _ => {}
+                }
+            }
+        }
+    }
+    Ok(metadata)
+}
+
+/// Gets the list of the metadata that holds the values of a key, if it is read.
+fn list_mut<'a>(metadata: &'a mut Metadata, key: &str) -> Option<&'a mut Vec<String>> {
+    match key {
+        "includes" => Some(&mut metadata.includes),
+        "flags" => Some(&mut metadata.flags),
+        "features" => Some(&mut metadata.features),
+        _ => None,
+    }
+}
+
+/// Splits a `key: value` line.
+fn split_key(line: &str) -> Option<(&str, &str)> {
+    let colon = line.find(':')?;
+    Some((line[..colon].trim(), line[colon + 1..].trim()))
+}
+
+/// Parses an inline list, like `[a, b]`.
+fn inline_list(value: &str) -> Option<Vec<String>> {
+    let items = value.strip_prefix('[')?.strip_suffix(']')?;
+    Some(
+        items
+            .split(',')
+            .map(|item| unquote(item.trim()).to_owned())
+            .filter(|item| !item.is_empty())
+            .collect(),
+    )
+}
+
+/// Removes the quotes around a scalar.
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .or_else(|| {
+            value
+                .strip_prefix('\'')
+                .and_then(|value| value.strip_suffix('\''))
+        })
+        .unwrap_or(value)
+}
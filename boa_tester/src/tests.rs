@@ -0,0 +1,61 @@
+use crate::{
+    directory,
+    expectations::{Expectation, Expectations},
+    read::{parse_metadata, Metadata, Negative},
+};
+
+#[test]
+fn reads_the_metadata() {
+    let source = "// Copyright\n/*---\nesid: sec-array\ndescription: >\n  a long\n  - description\nincludes: [compareArray.js, \"propertyHelper.js\"]\nflags:\n  - onlyStrict\n  - async\nnegative:\n  phase: parse\n  type: SyntaxError\nfeatures: [Symbol]\n---*/\n$DONOTEVALUATE();\n";
+    assert_eq!(
+        parse_metadata(source).unwrap(),
+        Metadata {
+            includes: vec!["compareArray.js".into(), "propertyHelper.js".into()],
+            flags: vec!["onlyStrict".into(), "async".into()],
+            features: vec!["Symbol".into()],
+            negative: Some(Negative {
+                phase: "parse".into(),
+                error_type: "SyntaxError".into(),
+            }),
+        }
+    );
+    assert!(parse_metadata("1 + 1").is_err());
+}
+
+#[test]
+fn matches_the_expectations() {
+    let expectations = Expectations::parse(
+        "# comment\nfail test/built-ins/Array\nskip test/built-ins/Array/from.js # slow\nskip feature:Proxy\nfail flag:async\n",
+    )
+    .unwrap();
+    let metadata = Metadata::default();
+    let get = |name: &str, metadata: &Metadata| expectations.get(name, metadata);
+
+    assert_eq!(
+        get("test/built-ins/Array/of.js", &metadata),
+        Some(Expectation::Fail)
+    );
+    assert_eq!(
+        get("test/built-ins/Array/from.js", &metadata),
+        Some(Expectation::Skip)
+    );
+    assert_eq!(get("test/built-ins/ArrayBuffer/a.js", &metadata), None);
+
+    let metadata = Metadata {
+        features: vec!["Proxy".into()],
+        flags: vec!["async".into()],
+        ..Metadata::default()
+    };
+    assert_eq!(get("test/a.js", &metadata), Some(Expectation::Skip));
+    assert!(Expectations::parse("ignore test").is_err());
+    assert!(Expectations::parse("skip a b").is_err());
+}
+
+#[test]
+fn groups_the_tests_by_directory() {
+    assert_eq!(
+        directory("test/built-ins/Array/from/a.js", 2),
+        "built-ins/Array"
+    );
+    assert_eq!(directory("test/language/a.js", 2), "language");
+}
@@ -0,0 +1,195 @@
+//! Helpers to test applications embedding the Boa JavaScript engine.
+//!
+//! `TestContext` wraps a `Context`, captures the output of its `console` object, and provides
+//! assertions on the results of scripts, the errors they throw, what they print and the
+//! microtasks they leave behind:
+//!
+//! ```
+//! use boa::builtins::console::LogMessage;
+//! use boa_testing::TestContext;
+//!
+//! let mut context = TestContext::new();
+//! context.assert_result("1 + 1", "2");
+//! context.assert_throws("null.x", "TypeError", "cannot convert 'null' or 'undefined' to object");
+//!
+//! context.eval("console.log('hello')").unwrap();
+//! context.assert_console(&[LogMessage::Log("hello".to_owned())]);
+//! context.assert_jobs_exhausted();
+//! ```
+//!
+//! The assertions panic with a description of the mismatch, like the `assert!` macros, so
+//! they can be used directly in `#[test]` functions.
+
+#![deny(
+    unused_qualifications,
+    clippy::all,
+    unused_import_braces,
+    unused_lifetimes,
+    unreachable_pub,
+    trivial_numeric_casts,
+    // rustdoc,
+    missing_debug_implementations,
+    missing_copy_implementations,
+    deprecated_in_future,
+    non_ascii_idents,
+    rust_2018_compatibility,
+    rust_2018_idioms,
+    future_incompatible,
+    nonstandard_style
+)]
+#![warn(clippy::perf, clippy::single_match_else, clippy::dbg_macro)]
+#![allow(
+    clippy::missing_inline_in_public_items,
+    clippy::cognitive_complexity,
+    clippy::must_use_candidate,
+    clippy::missing_errors_doc,
+    clippy::as_conversions
+)]
+
+#[cfg(test)]
+mod tests;
+
+use boa::{
+    builtins::{
+        console::{ConsoleBuffer, LogMessage},
+        value::{InspectOptions, Value},
+    },
+    Context,
+};
+
+/// A context to run scripts in tests, with assertions on their outcome.
+#[derive(Debug)]
+pub struct TestContext {
+    context: Context,
+    console: ConsoleBuffer,
+}
+
+impl Default for TestContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestContext {
+    /// Creates a test context around a new `Context`.
+    pub fn new() -> Self {
+        Self::with_context(Context::new())
+    }
+
+    /// Creates a test context around an existing `Context`, for instance one built with
+    /// `Context::builder` or with the host bindings under test already registered.
+    ///
+    /// The output of the `console` object of the context is captured from now on.
+    pub fn with_context(mut context: Context) -> Self {
+        let console = ConsoleBuffer::default();
+        context.set_console_writer(console.clone());
+        Self { context, console }
+    }
+
+    /// Gets the wrapped context.
+    #[inline]
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Gets the wrapped context as a mutable reference, to register bindings.
+    #[inline]
+    pub fn context_mut(&mut self) -> &mut Context {
+        &mut self.context
+    }
+
+    /// Runs a script and its microtasks, see `Context::eval`.
+    pub fn eval(&mut self, src: &str) -> Result<Value, Value> {
+        self.context.eval(src)
+    }
+
+    /// Asserts that the script completes with a value that displays as `expected`.
+    ///
+    /// Strings display without quotes, so `"'a' + 'b'"` results in `ab`.
+    #[track_caller]
+    pub fn assert_result(&mut self, src: &str, expected: &str) {
+        match self.eval(src) {
+            Ok(value) => assert_eq!(
+                value.to_string(),
+                expected,
+                "unexpected result of `{}`",
+                src
+            ),
+            Err(error) => panic!("`{}` threw {}", src, describe(&error)),
+        }
+    }
+
+    /// Asserts that the script throws an error of the given kind, like `"TypeError"`, with the
+    /// given message.
+    #[track_caller]
+    pub fn assert_throws(&mut self, src: &str, kind: &str, message: &str) {
+        let error = match self.eval(src) {
+            Ok(value) => panic!("`{}` did not throw, it returned {}", src, describe(&value)),
+            Err(error) => error,
+        };
+        let (found_kind, found_message) = match self.error_parts(&error) {
+            Some(parts) => parts,
+            None => panic!(
+                "`{}` threw {}, which is not an error object",
+                src,
+                describe(&error)
+            ),
+        };
+        assert_eq!(
+            found_kind, kind,
+            "unexpected error kind thrown by `{}`",
+            src
+        );
+        assert_eq!(
+            found_message, message,
+            "unexpected error message thrown by `{}`",
+            src
+        );
+    }
+
+    /// Gets the `name` and the `message` of a thrown error object.
+    fn error_parts(&mut self, error: &Value) -> Option<(String, String)> {
+        if !error.is_object() {
+            return None;
+        }
+        let interpreter = self.context.interpreter_mut();
+        let mut field = |name: &str| {
+            let field = interpreter.get_field(error, name).ok()?;
+            interpreter.to_string(&field).ok().map(|s| s.to_string())
+        };
+        Some((field("name")?, field("message")?))
+    }
+
+    /// Gets the messages printed through the `console` object so far.
+    pub fn console_output(&self) -> Vec<LogMessage> {
+        self.console.messages()
+    }
+
+    /// Gets the messages printed through the `console` object so far, and forgets them.
+    pub fn take_console_output(&mut self) -> Vec<LogMessage> {
+        self.console.take()
+    }
+
+    /// Asserts that exactly these messages were printed through the `console` object since the
+    /// last check, and forgets them.
+    #[track_caller]
+    pub fn assert_console(&mut self, expected: &[LogMessage]) {
+        let output = self.take_console_output();
+        assert_eq!(output, expected, "unexpected console output");
+    }
+
+    /// Asserts that no microtask is waiting to run, for instance because a host binding queued
+    /// one after the script finished.
+    #[track_caller]
+    pub fn assert_jobs_exhausted(&self) {
+        assert!(
+            !self.context.interpreter().has_pending_jobs(),
+            "microtasks are still waiting to run"
+        );
+    }
+}
+
+/// Describes a value in the messages of failed assertions.
+fn describe(value: &Value) -> String {
+    value.inspect(&InspectOptions::default()).text
+}
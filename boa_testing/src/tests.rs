@@ -0,0 +1,59 @@
+use crate::TestContext;
+use boa::{builtins::console::LogMessage, Context};
+
+#[test]
+fn asserts_results() {
+    let mut context = TestContext::new();
+    context.assert_result("let x = 40; x + 2", "42");
+    context.assert_result("'a' + 'b'", "ab");
+}
+
+#[test]
+#[should_panic(expected = "threw TypeError")]
+fn results_fail_on_errors() {
+    TestContext::new().assert_result("null.x", "1");
+}
+
+#[test]
+fn asserts_thrown_errors() {
+    let mut context = TestContext::new();
+    context.assert_throws("throw new RangeError('too far')", "RangeError", "too far");
+}
+
+#[test]
+#[should_panic(expected = "did not throw")]
+fn thrown_errors_fail_on_results() {
+    TestContext::new().assert_throws("1", "Error", "");
+}
+
+#[test]
+#[should_panic(expected = "not an error object")]
+fn thrown_errors_must_be_objects() {
+    TestContext::new().assert_throws("throw 'oops'", "Error", "oops");
+}
+
+#[test]
+fn captures_console_output() {
+    let mut context = TestContext::new();
+    context
+        .eval("console.log('a'); console.error('b')")
+        .unwrap();
+    context.assert_console(&[
+        LogMessage::Log("a".to_owned()),
+        LogMessage::Error("b".to_owned()),
+    ]);
+    context.assert_console(&[]);
+}
+
+#[test]
+fn checks_pending_jobs() {
+    let mut context = TestContext::with_context(Context::new());
+    context
+        .eval("queueMicrotask(() => console.log('later')); console.log('now')")
+        .unwrap();
+    context.assert_jobs_exhausted();
+    context.assert_console(&[
+        LogMessage::Log("now".to_owned()),
+        LogMessage::Log("later".to_owned()),
+    ]);
+}
@@ -1,24 +1,46 @@
-use boa::{Executable, Interpreter, Lexer, Parser, Realm};
+//! The WebAssembly bindings of Boa, used by the playground.
+//!
+//! Build them with `wasm-pack build` from this directory. The engine itself does not need the
+//! allocator, the file system or the line editor of the CLI, so none of them are compiled here.
+
+use boa::{forward_val, syntax::ast::token::Token, Interpreter, Lexer, Parser, Realm};
 use wasm_bindgen::prelude::*;
 
+/// Runs the source in a new interpreter and returns its completion value as a string.
+///
+/// Syntax errors and uncaught exceptions are returned as an error message.
 #[wasm_bindgen]
 pub fn evaluate(src: &str) -> Result<String, JsValue> {
-    let mut lexer = Lexer::new(src);
-    lexer
-        .lex()
-        .map_err(|e| JsValue::from(format!("Syntax Error: {}", e)))?;
-
-    let tokens = lexer.tokens;
-    let expr = Parser::new(&tokens)
-        .parse_all()
-        .map_err(|e| JsValue::from(format!("Parsing Error: {}", e)))?;
-
-    // Setup executor
     let realm = Realm::create();
     let mut engine = Interpreter::new(realm);
 
-    // Setup executor
-    expr.run(&mut engine)
-        .map_err(|e| JsValue::from(format!("Error: {}", e)))
+    forward_val(&mut engine, src)
         .map(|v| v.to_string())
+        .map_err(|e| JsValue::from(e.to_string()))
+}
+
+/// Lexes the source and returns its tokens as pretty printed JSON.
+#[wasm_bindgen]
+pub fn dump_tokens(src: &str) -> Result<String, JsValue> {
+    let tokens = lex(src)?;
+    serde_json::to_string_pretty(&tokens).map_err(|e| JsValue::from(e.to_string()))
+}
+
+/// Parses the source and returns its syntax tree as pretty printed JSON.
+#[wasm_bindgen]
+pub fn dump_ast(src: &str) -> Result<String, JsValue> {
+    let tokens = lex(src)?;
+    let ast = Parser::new(&tokens)
+        .parse_all()
+        .map_err(|e| JsValue::from(format!("Parsing Error: {}", e)))?;
+    serde_json::to_string_pretty(&ast).map_err(|e| JsValue::from(e.to_string()))
+}
+
+/// Lexes the source into its tokens.
+fn lex(src: &str) -> Result<Vec<Token>, JsValue> {
+    let mut lexer = Lexer::new(src);
+    lexer
+        .lex()
+        .map_err(|e| JsValue::from(format!("Syntax Error: {}", e)))?;
+    Ok(lexer.tokens)
 }
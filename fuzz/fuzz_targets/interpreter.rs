@@ -0,0 +1,8 @@
+//! Runs arbitrary text, terminated after a budget of steps.
+
+#![no_main]
+
+use boa_fuzz::run;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|src: &str| run(src));
@@ -0,0 +1,10 @@
+//! Lexes arbitrary text.
+
+#![no_main]
+
+use boa::syntax::lexer::Lexer;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|src: &str| {
+    let _ = Lexer::new(src).lex();
+});
@@ -0,0 +1,22 @@
+//! Parses arbitrary text that lexes, as a script and as strict mode code.
+
+#![no_main]
+
+use boa::{
+    syntax::{lexer::Lexer, parser::Parser},
+    ParseOptions,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|src: &str| {
+    let mut lexer = Lexer::new(src);
+    if lexer.lex().is_ok() {
+        let _ = Parser::new(&lexer.tokens).parse_all();
+    }
+    let options = ParseOptions {
+        lazy_functions: true,
+        strict: true,
+        ..ParseOptions::default()
+    };
+    let _ = boa::parse(src, options);
+});
@@ -0,0 +1,9 @@
+//! Runs arbitrary programs that are valid JavaScript, which get past the parser far more often
+//! than arbitrary text, terminated after a budget of steps.
+
+#![no_main]
+
+use boa_fuzz::{js::Program, run};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|program: Program| run(&program.to_string()));
@@ -0,0 +1,298 @@
+//! Arbitrary JavaScript programs, from a subset of the grammar.
+//!
+//! The programs are generated from the fuzzer's bytes with `Arbitrary`, and printed as source
+//! code. Every expression is parenthesized, so the programs always parse as they were built, and
+//! the identifiers are taken from a small set, so that the programs mostly use bindings that
+//! they defined and the builtins.
+
+use arbitrary::Arbitrary;
+use std::fmt::{self, Display, Formatter};
+
+/// A script.
+#[derive(Debug, Arbitrary)]
+pub struct Program(pub Vec<Statement>);
+
+#[derive(Debug, Arbitrary)]
+pub enum Statement {
+    Expression(Expression),
+    Var(Ident, Expression),
+    Let(Ident, Expression),
+    If(Expression, Vec<Statement>, Vec<Statement>),
+    While(Expression, Vec<Statement>),
+    For(Ident, Expression, Vec<Statement>),
+    Function(Ident, Vec<Ident>, Vec<Statement>),
+    Return(Option<Expression>),
+    Throw(Expression),
+    Try(Vec<Statement>, Ident, Vec<Statement>),
+    Break,
+    Continue,
+}
+
+#[derive(Debug, Arbitrary)]
+pub enum Expression {
+    Number(f64),
+    String(String),
+    Boolean(bool),
+    Null,
+    Undefined,
+    Ident(Ident),
+    Array(Vec<Expression>),
+    Object(Vec<(Ident, Expression)>),
+    Unary(UnaryOp, Box<Expression>),
+    Binary(Box<Expression>, BinaryOp, Box<Expression>),
+    Assign(Ident, Box<Expression>),
+    Call(Box<Expression>, Vec<Expression>),
+    Member(Box<Expression>, Ident),
+    Index(Box<Expression>, Box<Expression>),
+    New(Ident, Vec<Expression>),
+    Arrow(Vec<Ident>, Box<Expression>),
+    Conditional(Box<Expression>, Box<Expression>, Box<Expression>),
+}
+
+#[derive(Debug, Clone, Copy, Arbitrary)]
+pub enum Ident {
+    A,
+    B,
+    C,
+    F,
+    Object,
+    Array,
+    Math,
+    String,
+    Number,
+    Symbol,
+    Promise,
+    Length,
+    Prototype,
+    ToString,
+    ValueOf,
+}
+
+#[derive(Debug, Clone, Copy, Arbitrary)]
+pub enum UnaryOp {
+    Not,
+    Minus,
+    Plus,
+    Tilde,
+    Typeof,
+    Void,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, Arbitrary)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Exp,
+    And,
+    Or,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
+    UShr,
+    StrictEq,
+    Eq,
+    Lt,
+    GtEq,
+    In,
+    InstanceOf,
+    Comma,
+}
+
+impl Display for Program {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for statement in &self.0 {
+            writeln!(f, "{}", statement)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes a block of statements.
+fn block(f: &mut Formatter<'_>, statements: &[Statement]) -> fmt::Result {
+    f.write_str("{\n")?;
+    for statement in statements {
+        writeln!(f, "{}", statement)?;
+    }
+    f.write_str("}")
+}
+
+/// Writes a comma separated list.
+fn list<T: Display>(f: &mut Formatter<'_>, items: &[T]) -> fmt::Result {
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+impl Display for Statement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Expression(expression) => write!(f, "{};", expression),
+            Self::Var(name, init) => write!(f, "var {} = {};", name, init),
+            Self::Let(name, init) => write!(f, "let {} = {};", name, init),
+            Self::If(condition, body, otherwise) => {
+                write!(f, "if ({}) ", condition)?;
+                block(f, body)?;
+                f.write_str(" else ")?;
+                block(f, otherwise)
+            }
+            Self::While(condition, body) => {
+                write!(f, "while ({}) ", condition)?;
+                block(f, body)
+            }
+            Self::For(name, end, body) => {
+                write!(f, "for (let {0} = 0; {0} < {1}; {0}++) ", name, end)?;
+                block(f, body)
+            }
+            Self::Function(name, params, body) => {
+                write!(f, "function {}(", name)?;
+                list(f, params)?;
+                f.write_str(") ")?;
+                block(f, body)
+            }
+            Self::Return(Some(value)) => write!(f, "return {};", value),
+            Self::Return(None) => f.write_str("return;"),
+            Self::Throw(value) => write!(f, "throw {};", value),
+            Self::Try(body, name, handler) => {
+                f.write_str("try ")?;
+                block(f, body)?;
+                write!(f, " catch ({}) ", name)?;
+                block(f, handler)
+            }
+            Self::Break => f.write_str("break;"),
+            Self::Continue => f.write_str("continue;"),
+        }
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("(")?;
+        match self {
+            Self::Number(number) if number.is_nan() => f.write_str("NaN")?,
+            Self::Number(number) if number.is_infinite() && *number > 0.0 => {
+                f.write_str("Infinity")?
+            }
+            Self::Number(number) if number.is_infinite() => f.write_str("-Infinity")?,
+            Self::Number(number) => write!(f, "{}", number)?,
+            // The escapes of Rust strings are valid in JavaScript strings.
+            Self::String(string) => write!(f, "{:?}", string)?,
+            Self::Boolean(boolean) => write!(f, "{}", boolean)?,
+            Self::Null => f.write_str("null")?,
+            Self::Undefined => f.write_str("undefined")?,
+            Self::Ident(name) => write!(f, "{}", name)?,
+            Self::Array(items) => {
+                f.write_str("[")?;
+                list(f, items)?;
+                f.write_str("]")?;
+            }
+            Self::Object(properties) => {
+                f.write_str("{")?;
+                for (index, (name, value)) in properties.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}: {}", name, value)?;
+                }
+                f.write_str("}")?;
+            }
+            Self::Unary(op, operand) => write!(f, "{} {}", op, operand)?,
+            Self::Binary(left, op, right) => write!(f, "{} {} {}", left, op, right)?,
+            Self::Assign(name, value) => write!(f, "{} = {}", name, value)?,
+            Self::Call(callee, args) => {
+                write!(f, "{}(", callee)?;
+                list(f, args)?;
+                f.write_str(")")?;
+            }
+            Self::Member(object, name) => write!(f, "{}.{}", object, name)?,
+            Self::Index(object, index) => write!(f, "{}[{}]", object, index)?,
+            Self::New(constructor, args) => {
+                write!(f, "new {}(", constructor)?;
+                list(f, args)?;
+                f.write_str(")")?;
+            }
+            Self::Arrow(params, body) => {
+                f.write_str("(")?;
+                list(f, params)?;
+                write!(f, ") => {}", body)?;
+            }
+            Self::Conditional(condition, then, otherwise) => {
+                write!(f, "{} ? {} : {}", condition, then, otherwise)?
+            }
+        }
+        f.write_str(")")
+    }
+}
+
+impl Display for Ident {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::A => "a",
+            Self::B => "b",
+            Self::C => "c",
+            Self::F => "f",
+            Self::Object => "Object",
+            Self::Array => "Array",
+            Self::Math => "Math",
+            Self::String => "String",
+            Self::Number => "Number",
+            Self::Symbol => "Symbol",
+            Self::Promise => "Promise",
+            Self::Length => "length",
+            Self::Prototype => "prototype",
+            Self::ToString => "toString",
+            Self::ValueOf => "valueOf",
+        })
+    }
+}
+
+impl Display for UnaryOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Not => "!",
+            Self::Minus => "-",
+            Self::Plus => "+",
+            Self::Tilde => "~",
+            Self::Typeof => "typeof",
+            Self::Void => "void",
+            Self::Delete => "delete",
+        })
+    }
+}
+
+impl Display for BinaryOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Mod => "%",
+            Self::Exp => "**",
+            Self::And => "&&",
+            Self::Or => "||",
+            Self::BitAnd => "&",
+            Self::BitOr => "|",
+            Self::BitXor => "^",
+            Self::Shl => "<<",
+            Self::Shr => ">>",
+            Self::UShr => ">>>",
+            Self::StrictEq => "===",
+            Self::Eq => "==",
+            Self::Lt => "<",
+            Self::GtEq => ">=",
+            Self::In => "in",
+            Self::InstanceOf => "instanceof",
+            Self::Comma => ",",
+        })
+    }
+}
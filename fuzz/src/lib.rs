@@ -0,0 +1,34 @@
+//! The shared code of the fuzzing targets.
+//!
+//! The targets are run with [cargo-fuzz](https://github.com/rust-fuzz/cargo-fuzz), from the
+//! root of the repository:
+//!
+//! ```text
+//! cargo install cargo-fuzz
+//! cargo +nightly fuzz run parser
+//! ```
+//!
+//! - `lexer` lexes arbitrary text.
+//! - `parser` parses arbitrary text.
+//! - `interpreter` runs arbitrary text.
+//! - `structured` runs arbitrary programs generated from a subset of the grammar, see `js`.
+
+pub mod js;
+
+use boa::{forward_val, Context};
+
+/// The number of steps, loop iterations and function calls, after which a script is terminated,
+/// so that infinite loops are not reported as timeouts.
+pub const STEP_BUDGET: u64 = 100_000;
+
+/// The bytes of native stack that a script may use, well below the stack of the fuzzer.
+pub const MAX_STACK_SIZE: usize = 256 * 1024;
+
+/// Runs a script in a fresh context, ignoring its result: only panics are bugs.
+pub fn run(src: &str) {
+    let mut context = Context::builder()
+        .step_budget(STEP_BUDGET)
+        .max_stack_size(MAX_STACK_SIZE)
+        .build();
+    let _ = forward_val(context.interpreter_mut(), src);
+}